@@ -0,0 +1,649 @@
+use crate::{model::*, service::repository::*, ArticleParentFilter};
+use chrono::Utc;
+use postgres::types::ToSql;
+use std::error::Error;
+use tracing::{debug, warn};
+use universe_database::Database;
+use universe_entity::{Identity, Page, Pagination};
+use universe_worlds::WorldID;
+use uuid::Uuid;
+
+impl From<&postgres::Row> for ArticleEntity {
+  fn from(row: &postgres::Row) -> Self {
+    ArticleEntity {
+      identity: Identity {
+        id: row.get("article_id"),
+        version: row.get("version"),
+        created: row.get("created"),
+        updated: row.get("updated"),
+      },
+      data: ArticleData {
+        world: row.get("world_id"),
+        parent: row.get("parent_id"),
+        title: row.get("title"),
+        slug: row.get("slug"),
+        body: row.get("body"),
+      },
+    }
+  }
+}
+
+impl ArticleRepository for Database {
+  /// Retrieve the article that has the given unique ID, as long as it belongs to the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the article belongs to
+  /// * `article_id` The ID of the article to retrieve
+  ///
+  /// # Returns
+  /// The article, or `None` if it wasn't found
+  fn get_article_by_id(&self, world_id: &WorldID, article_id: &ArticleID) -> Option<ArticleEntity> {
+    let mut client = self.client().unwrap();
+
+    let article = client
+      .query(
+        "SELECT * FROM articles WHERE world_id = $1 AND article_id = $2",
+        &[&world_id, &article_id],
+      )
+      .map_err(|e| {
+        warn!("Error loading article from database: {}", e);
+        e
+      })
+      .ok()
+      .and_then(|rows| rows.get(0).map(|row| row.into()));
+
+    debug!("Article for ID {}: {:?}", article_id, article);
+    article
+  }
+
+  /// Retrieve a page of the articles of a world, sorted by title
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the articles of
+  /// * `parent` Where in the tree of articles to list articles from, or `None` for all of them
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of articles
+  fn list_articles(
+    &self,
+    world_id: &WorldID,
+    parent: Option<ArticleParentFilter>,
+    pagination: Pagination,
+  ) -> Page<ArticleEntity> {
+    let mut client = self.client().unwrap();
+
+    let mut binds: Vec<&(dyn ToSql + Sync)> = vec![world_id];
+    let clause = match &parent {
+      None => "",
+      Some(ArticleParentFilter::TopLevel) => " AND parent_id IS NULL",
+      Some(ArticleParentFilter::Children(parent_id)) => {
+        binds.push(parent_id);
+        " AND parent_id = $2"
+      }
+    };
+
+    let count_sql = format!(
+      "SELECT COUNT(*) AS total FROM articles WHERE world_id = $1{}",
+      clause
+    );
+    let total: i64 = client
+      .query_one(count_sql.as_str(), &binds)
+      .map_err(|e| {
+        warn!("Error counting articles in database: {}", e);
+        e
+      })
+      .map(|row| row.get("total"))
+      .unwrap_or(0);
+
+    let select_sql = format!(
+      "SELECT * FROM articles WHERE world_id = $1{}
+        ORDER BY UPPER(title) ASC, article_id ASC
+        LIMIT ${} OFFSET ${}",
+      clause,
+      binds.len() + 1,
+      binds.len() + 2
+    );
+    let limit = i64::from(pagination.limit);
+    let offset = i64::from(pagination.offset);
+    binds.push(&limit);
+    binds.push(&offset);
+
+    let entries: Vec<ArticleEntity> = client
+      .query(select_sql.as_str(), &binds)
+      .map_err(|e| {
+        warn!("Error listing articles in database: {}", e);
+        e
+      })
+      .map(|rows| rows.iter().map(|row| row.into()).collect())
+      .unwrap_or_default();
+
+    let page = Page {
+      entries,
+      total: total as u32,
+      offset: pagination.offset,
+    };
+    debug!("Articles of world {}: {:?}", world_id, page);
+
+    page
+  }
+
+  /// Retrieve every article of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the articles of
+  ///
+  /// # Returns
+  /// The articles
+  fn list_all_articles(&self, world_id: &WorldID) -> Vec<ArticleEntity> {
+    let mut client = self.client().unwrap();
+
+    let articles = client
+      .query(
+        "SELECT * FROM articles WHERE world_id = $1 ORDER BY created ASC, article_id ASC",
+        &[&world_id],
+      )
+      .map_err(|e| {
+        warn!("Error listing articles in database: {}", e);
+        e
+      })
+      .map(|rows| rows.iter().map(|row| row.into()).collect())
+      .unwrap_or_default();
+
+    debug!("All articles of world {}: {:?}", world_id, articles);
+    articles
+  }
+
+  /// Retrieve the IDs of an article and of every article above it in the tree
+  ///
+  /// # Arguments
+  /// * `article_id` The ID of the article to start from
+  ///
+  /// # Returns
+  /// The IDs of the article and all of its ancestors
+  fn get_article_ancestors(&self, article_id: &ArticleID) -> Vec<ArticleID> {
+    let mut client = self.client().unwrap();
+
+    let ancestors = client
+      .query(
+        "WITH RECURSIVE ancestors(article_id, parent_id) AS (
+            SELECT article_id, parent_id FROM articles WHERE article_id = $1
+          UNION
+            SELECT articles.article_id, articles.parent_id FROM articles
+            JOIN ancestors ON articles.article_id = ancestors.parent_id
+          )
+          SELECT article_id FROM ancestors",
+        &[&article_id],
+      )
+      .map_err(|e| {
+        warn!("Error loading article ancestors from database: {}", e);
+        e
+      })
+      .map(|rows| rows.iter().map(|row| row.get("article_id")).collect())
+      .unwrap_or_default();
+
+    debug!("Ancestors of article {}: {:?}", article_id, ancestors);
+    ancestors
+  }
+
+  /// Create a new article record in the data store
+  ///
+  /// # Arguments
+  /// * `article` The article details to persist to the data store
+  ///
+  /// # Returns
+  /// The article that was persisted
+  fn create_article(&self, article: ArticleData) -> Result<ArticleEntity, PersistArticleError> {
+    debug!("Creating record for article: {:?}", article);
+
+    let mut client = self.client().unwrap();
+
+    let new_id = ArticleID::default();
+    let new_version = Uuid::new_v4();
+    let new_updated = Utc::now();
+
+    let result: ArticleEntity = client
+      .query(
+        "INSERT INTO articles(article_id, version, created, updated, world_id, parent_id, title, slug, body)
+          VALUES ($1, $2, $3, $3, $4, $5, $6, $7, $8)
+          RETURNING *",
+        &[
+          &new_id,
+          &new_version,
+          &new_updated,
+          &article.world,
+          &article.parent,
+          &article.title,
+          &article.slug,
+          &article.body,
+        ],
+      )
+      .map(|rows| rows.get(0).unwrap().into())?;
+
+    debug!("Created record for article: {:?}", result);
+    Ok(result)
+  }
+
+  /// Update an existing article record in the data store
+  ///
+  /// # Arguments
+  /// * `article` The article details to persist to the data store
+  ///
+  /// # Returns
+  /// The article that was persisted
+  fn update_article(&self, article: ArticleEntity) -> Result<ArticleEntity, PersistArticleError> {
+    debug!("Updating record for article: {:?}", article);
+
+    let mut client = self.client().unwrap();
+
+    let new_version = Uuid::new_v4();
+    let new_updated = Utc::now();
+
+    let rows = client.query(
+      "UPDATE articles SET parent_id = $1, title = $2, slug = $3, body = $4, version = $5, updated = $6
+          WHERE article_id = $7
+          AND world_id = $8
+          AND version = $9
+          RETURNING *",
+      &[
+        &article.data.parent,
+        &article.data.title,
+        &article.data.slug,
+        &article.data.body,
+        &new_version,
+        &new_updated,
+        &article.identity.id,
+        &article.data.world,
+        &article.identity.version,
+      ],
+    )?;
+
+    if let Some(row) = rows.get(0) {
+      let result = row.into();
+      debug!("Updated record for article: {:?}", result);
+      Ok(result)
+    } else {
+      let article_found = client.query(
+        "SELECT version FROM articles WHERE article_id = $1 AND world_id = $2",
+        &[&article.identity.id, &article.data.world],
+      )?;
+
+      if article_found.is_empty() {
+        warn!(
+          "Attempted to update article {} that wasn't found",
+          article.identity.id
+        );
+        Err(PersistArticleError::ArticleNotFound)
+      } else {
+        let old_version: Uuid = article_found.get(0).unwrap().get("version");
+
+        warn!(
+          "Attempted to update article {}. Expected version {} but database had {}",
+          article.identity.id, article.identity.version, old_version
+        );
+        Err(PersistArticleError::OptimisticLockFailure)
+      }
+    }
+  }
+
+  /// Delete an existing article record from the data store
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the article belongs to
+  /// * `article_id` The ID of the article to delete
+  fn delete_article(
+    &self,
+    world_id: &WorldID,
+    article_id: &ArticleID,
+  ) -> Result<(), PersistArticleError> {
+    debug!("Deleting article {} from world {}", article_id, world_id);
+
+    let mut client = self.client().unwrap();
+
+    let deleted = client
+      .execute(
+        "DELETE FROM articles WHERE world_id = $1 AND article_id = $2",
+        &[&world_id, &article_id],
+      )
+      .map_err(|e| match PersistArticleError::from(e) {
+        // The only reference that can stop an article being deleted is from another article
+        PersistArticleError::ParentNotFound => PersistArticleError::HasChildren,
+        e => e,
+      })?;
+
+    if deleted == 0 {
+      warn!(
+        "Attempted to delete article {} that wasn't found",
+        article_id
+      );
+      Err(PersistArticleError::ArticleNotFound)
+    } else {
+      Ok(())
+    }
+  }
+}
+
+impl From<postgres::Error> for PersistArticleError {
+  fn from(error: postgres::Error) -> Self {
+    warn!("Error persisting article in database: {:?}", error);
+
+    error
+      .source()
+      .and_then(|e| e.downcast_ref::<postgres::error::DbError>())
+      .map(|e| match e.constraint() {
+        Some("articles_world_slug_key") => PersistArticleError::DuplicateSlug,
+        Some("articles_world_id_fkey") => PersistArticleError::WorldNotFound,
+        Some("articles_world_id_parent_id_fkey") => PersistArticleError::ParentNotFound,
+        _ => PersistArticleError::UnknownError,
+      })
+      .unwrap_or(PersistArticleError::UnknownError)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use spectral::prelude::*;
+  use test_env_log::test;
+  use universe_test_database_wrapper::TestDatabaseWrapper;
+  use universe_testdata::{seed, Article, User, World};
+
+  /// Seed a world with a small tree of articles into the database
+  ///
+  /// The articles are "Places", with "Rivendell" and "Mordor" under it, and "People"
+  fn seed_articles(database: &TestDatabaseWrapper) -> (World, Vec<Article>) {
+    let user: User = Default::default();
+    let world = World {
+      owner_id: user.user_id,
+      ..Default::default()
+    };
+    let places = Article {
+      world_id: world.world_id,
+      title: "Places".to_owned(),
+      slug: "places".to_owned(),
+      ..Default::default()
+    };
+    let articles = vec![
+      places.clone(),
+      Article {
+        world_id: world.world_id,
+        parent_id: Some(places.article_id),
+        title: "Rivendell".to_owned(),
+        slug: "rivendell".to_owned(),
+        ..Default::default()
+      },
+      Article {
+        world_id: world.world_id,
+        parent_id: Some(places.article_id),
+        title: "Mordor".to_owned(),
+        slug: "mordor".to_owned(),
+        ..Default::default()
+      },
+      Article {
+        world_id: world.world_id,
+        title: "People".to_owned(),
+        slug: "people".to_owned(),
+        ..Default::default()
+      },
+    ];
+
+    seed(
+      &database,
+      vec![
+        &user,
+        &world,
+        &articles[0],
+        &articles[1],
+        &articles[2],
+        &articles[3],
+      ],
+    );
+
+    (world, articles)
+  }
+
+  fn titles(page: &Page<ArticleEntity>) -> Vec<String> {
+    page
+      .entries
+      .iter()
+      .map(|article| article.data.title.clone())
+      .collect()
+  }
+
+  const ALL: Pagination = Pagination {
+    offset: 0,
+    limit: 10,
+  };
+
+  #[test]
+  fn test_get_article() {
+    let database = TestDatabaseWrapper::new();
+    let (world, articles) = seed_articles(&database);
+
+    let article = database.wrapper.get_article_by_id(
+      &WorldID::from_uuid(world.world_id),
+      &ArticleID::from_uuid(articles[1].article_id),
+    );
+    assert_that(&article)
+      .is_some()
+      .is_equal_to(ArticleEntity::from(articles[1].clone()));
+  }
+
+  #[test]
+  fn test_get_article_wrong_world() {
+    let database = TestDatabaseWrapper::new();
+    let (_, articles) = seed_articles(&database);
+
+    let article = database.wrapper.get_article_by_id(
+      &Default::default(),
+      &ArticleID::from_uuid(articles[1].article_id),
+    );
+    assert_that(&article).is_none();
+  }
+
+  #[test]
+  fn test_list_articles() {
+    let database = TestDatabaseWrapper::new();
+    let (world, articles) = seed_articles(&database);
+    let world_id = WorldID::from_uuid(world.world_id);
+
+    let all = database.wrapper.list_articles(&world_id, None, ALL);
+    assert_that(&titles(&all)).is_equal_to(vec![
+      "Mordor".to_owned(),
+      "People".to_owned(),
+      "Places".to_owned(),
+      "Rivendell".to_owned(),
+    ]);
+    assert_that(&all.total).is_equal_to(4);
+
+    let top_level =
+      database
+        .wrapper
+        .list_articles(&world_id, Some(ArticleParentFilter::TopLevel), ALL);
+    assert_that(&titles(&top_level)).is_equal_to(vec!["People".to_owned(), "Places".to_owned()]);
+
+    let children = database.wrapper.list_articles(
+      &world_id,
+      Some(ArticleParentFilter::Children(ArticleID::from_uuid(
+        articles[0].article_id,
+      ))),
+      Pagination {
+        offset: 1,
+        limit: 10,
+      },
+    );
+    assert_that(&titles(&children)).is_equal_to(vec!["Rivendell".to_owned()]);
+    assert_that(&children.total).is_equal_to(2);
+  }
+
+  #[test]
+  fn test_get_article_ancestors() {
+    let database = TestDatabaseWrapper::new();
+    let (_, articles) = seed_articles(&database);
+
+    let mut ancestors = database
+      .wrapper
+      .get_article_ancestors(&ArticleID::from_uuid(articles[1].article_id));
+    ancestors.sort_by_key(|id| id.to_string());
+
+    let mut expected = vec![
+      ArticleID::from_uuid(articles[0].article_id),
+      ArticleID::from_uuid(articles[1].article_id),
+    ];
+    expected.sort_by_key(|id| id.to_string());
+    assert_that(&ancestors).is_equal_to(expected);
+  }
+
+  #[test]
+  fn test_create_article() {
+    let database = TestDatabaseWrapper::new();
+    let (world, articles) = seed_articles(&database);
+
+    let article = ArticleData {
+      world: WorldID::from_uuid(world.world_id),
+      parent: Some(ArticleID::from_uuid(articles[3].article_id)),
+      title: "Hobbits".to_owned(),
+      slug: "hobbits".parse().unwrap(),
+      body: "Small and *hairy*".to_owned(),
+    };
+    let created = database.wrapper.create_article(article.clone());
+    assert_that(&created)
+      .is_ok()
+      .map(|created| &created.data)
+      .is_equal_to(&article);
+  }
+
+  #[test]
+  fn test_create_article_duplicate_slug() {
+    let database = TestDatabaseWrapper::new();
+    let (world, _) = seed_articles(&database);
+
+    let created = database.wrapper.create_article(ArticleData {
+      world: WorldID::from_uuid(world.world_id),
+      parent: None,
+      title: "More Places".to_owned(),
+      slug: "PLACES".parse().unwrap(),
+      body: "".to_owned(),
+    });
+    assert_that(&created)
+      .is_err()
+      .is_equal_to(PersistArticleError::DuplicateSlug);
+  }
+
+  #[test]
+  fn test_create_article_parent_in_other_world() {
+    let database = TestDatabaseWrapper::new();
+    let (world, articles) = seed_articles(&database);
+    let other_world = World {
+      owner_id: world.owner_id,
+      slug: "other-world".to_owned(),
+      ..Default::default()
+    };
+    seed(&database, vec![&other_world]);
+
+    let created = database.wrapper.create_article(ArticleData {
+      world: WorldID::from_uuid(other_world.world_id),
+      parent: Some(ArticleID::from_uuid(articles[0].article_id)),
+      title: "Places".to_owned(),
+      slug: "places".parse().unwrap(),
+      body: "".to_owned(),
+    });
+    assert_that(&created)
+      .is_err()
+      .is_equal_to(PersistArticleError::ParentNotFound);
+  }
+
+  #[test]
+  fn test_create_article_unknown_world() {
+    let database = TestDatabaseWrapper::new();
+
+    let created = database.wrapper.create_article(ArticleData {
+      world: Default::default(),
+      parent: None,
+      title: "Places".to_owned(),
+      slug: "places".parse().unwrap(),
+      body: "".to_owned(),
+    });
+    assert_that(&created)
+      .is_err()
+      .is_equal_to(PersistArticleError::WorldNotFound);
+  }
+
+  #[test]
+  fn test_update_article() {
+    let database = TestDatabaseWrapper::new();
+    let (_, articles) = seed_articles(&database);
+
+    let mut article = ArticleEntity::from(articles[2].clone());
+    article.data.parent = None;
+    article.data.title = "Mount Doom".to_owned();
+
+    let updated = database.wrapper.update_article(article.clone());
+    assert_that(&updated)
+      .is_ok()
+      .map(|updated| &updated.data)
+      .is_equal_to(&article.data);
+    assert_that(&updated.unwrap().identity.version).is_not_equal_to(article.identity.version);
+  }
+
+  #[test]
+  fn test_update_article_wrong_version() {
+    let database = TestDatabaseWrapper::new();
+    let (_, articles) = seed_articles(&database);
+
+    let mut article = ArticleEntity::from(articles[2].clone());
+    article.identity.version = Uuid::new_v4();
+
+    let updated = database.wrapper.update_article(article);
+    assert_that(&updated)
+      .is_err()
+      .is_equal_to(PersistArticleError::OptimisticLockFailure);
+  }
+
+  #[test]
+  fn test_update_unknown_article() {
+    let database = TestDatabaseWrapper::new();
+    let (world, _) = seed_articles(&database);
+
+    let mut article = ArticleEntity::from(Article {
+      world_id: world.world_id,
+      ..Default::default()
+    });
+    article.data.title = "Unknown".to_owned();
+
+    let updated = database.wrapper.update_article(article);
+    assert_that(&updated)
+      .is_err()
+      .is_equal_to(PersistArticleError::ArticleNotFound);
+  }
+
+  #[test]
+  fn test_delete_article() {
+    let database = TestDatabaseWrapper::new();
+    let (world, articles) = seed_articles(&database);
+    let world_id = WorldID::from_uuid(world.world_id);
+    let article_id = ArticleID::from_uuid(articles[3].article_id);
+
+    let deleted = database.wrapper.delete_article(&world_id, &article_id);
+    assert_that(&deleted).is_ok();
+
+    let deleted = database.wrapper.delete_article(&world_id, &article_id);
+    assert_that(&deleted)
+      .is_err()
+      .is_equal_to(PersistArticleError::ArticleNotFound);
+  }
+
+  #[test]
+  fn test_delete_article_with_children() {
+    let database = TestDatabaseWrapper::new();
+    let (world, articles) = seed_articles(&database);
+
+    let deleted = database.wrapper.delete_article(
+      &WorldID::from_uuid(world.world_id),
+      &ArticleID::from_uuid(articles[0].article_id),
+    );
+    assert_that(&deleted)
+      .is_err()
+      .is_equal_to(PersistArticleError::HasChildren);
+  }
+}
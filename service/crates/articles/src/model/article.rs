@@ -0,0 +1,45 @@
+use crate::ArticleID;
+use universe_entity::Identity;
+use universe_worlds::{Slug, WorldID};
+
+/// Struct to represent the data about a single Article
+#[derive(Debug, PartialEq, Clone)]
+pub struct ArticleData {
+  /// The world that the article belongs to
+  pub world: WorldID,
+  /// The article that this one sits under, or `None` if it is at the top level of the world
+  pub parent: Option<ArticleID>,
+  pub title: String,
+  /// The slug of the article, which is unique within the world
+  pub slug: Slug,
+  /// The body of the article, as CommonMark source
+  pub body: String,
+}
+
+/// Type to represent the entity that is a persisted article record
+#[derive(Debug, PartialEq, Clone)]
+pub struct ArticleEntity {
+  pub identity: Identity<ArticleID>,
+  pub data: ArticleData,
+}
+
+#[cfg(test)]
+impl From<universe_testdata::Article> for ArticleEntity {
+  fn from(article: universe_testdata::Article) -> ArticleEntity {
+    ArticleEntity {
+      identity: Identity {
+        id: ArticleID::from_uuid(article.article_id),
+        version: article.version,
+        created: article.created,
+        updated: article.updated,
+      },
+      data: ArticleData {
+        world: WorldID::from_uuid(article.world_id),
+        parent: article.parent_id.map(ArticleID::from_uuid),
+        title: article.title.clone(),
+        slug: article.slug.parse().unwrap(),
+        body: article.body.clone(),
+      },
+    }
+  }
+}
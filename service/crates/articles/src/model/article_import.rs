@@ -0,0 +1,149 @@
+use std::collections::HashSet;
+use universe_worlds::Slug;
+
+/// An article that is to be imported into a world from outside of the system.
+///
+/// Articles being imported refer to each other by a key that only needs to be unique amongst the
+/// articles being imported together. They are all given new IDs when they are created.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ArticleImport {
+  pub key: String,
+  /// The key of the article that this one sits under, if any
+  pub parent: Option<String>,
+  pub title: String,
+  pub slug: Slug,
+  pub body: String,
+}
+
+/// Errors that can happen when preparing a set of articles to be imported
+#[derive(Debug, PartialEq, Clone, thiserror::Error)]
+pub enum ArticleImportError {
+  #[error("The article key {0} is used more than once")]
+  DuplicateKey(String),
+  #[error("The parent of article {0} is not being imported")]
+  UnknownParent(String),
+  #[error("Article {0} is its own ancestor")]
+  ParentCycle(String),
+}
+
+/// Put a set of articles that are to be imported into an order where every article comes after
+/// its parent, so that they can be created one at a time. Articles otherwise keep the order that
+/// they were provided in.
+///
+/// # Arguments
+/// * `articles` The articles to put in order
+///
+/// # Returns
+/// The ordered articles, or an error if the articles don't form a tree
+pub fn order_article_imports(
+  articles: Vec<ArticleImport>,
+) -> Result<Vec<ArticleImport>, ArticleImportError> {
+  let mut keys = HashSet::new();
+  for article in &articles {
+    if !keys.insert(article.key.as_str()) {
+      return Err(ArticleImportError::DuplicateKey(article.key.clone()));
+    }
+  }
+
+  if let Some(orphan) = articles.iter().find(|article| match &article.parent {
+    Some(parent) => !keys.contains(parent.as_str()),
+    None => false,
+  }) {
+    return Err(ArticleImportError::UnknownParent(orphan.key.clone()));
+  }
+
+  let mut placed = HashSet::new();
+  let mut ordered = Vec::with_capacity(articles.len());
+  let mut pending = articles;
+
+  while !pending.is_empty() {
+    let (ready, waiting): (Vec<_>, Vec<_>) =
+      pending
+        .into_iter()
+        .partition(|article| match &article.parent {
+          Some(parent) => placed.contains(parent),
+          None => true,
+        });
+
+    // If nothing could be placed then every remaining article is waiting on another one
+    if ready.is_empty() {
+      return Err(ArticleImportError::ParentCycle(waiting[0].key.clone()));
+    }
+
+    placed.extend(ready.iter().map(|article| article.key.clone()));
+    ordered.extend(ready);
+    pending = waiting;
+  }
+
+  Ok(ordered)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use spectral::prelude::*;
+
+  fn article(key: &str, parent: Option<&str>) -> ArticleImport {
+    ArticleImport {
+      key: key.to_owned(),
+      parent: parent.map(|parent| parent.to_owned()),
+      title: key.to_owned(),
+      slug: key.parse().unwrap(),
+      body: "".to_owned(),
+    }
+  }
+
+  fn keys(articles: &[ArticleImport]) -> Vec<&str> {
+    articles
+      .iter()
+      .map(|article| article.key.as_str())
+      .collect()
+  }
+
+  #[test]
+  fn test_order_parents_first() {
+    let ordered = order_article_imports(vec![
+      article("grandchild", Some("child")),
+      article("child", Some("root")),
+      article("other", None),
+      article("root", None),
+    ])
+    .unwrap();
+
+    assert_that(&keys(&ordered)).is_equal_to(vec!["other", "root", "child", "grandchild"]);
+  }
+
+  #[test]
+  fn test_order_duplicate_key() {
+    let ordered = order_article_imports(vec![article("root", None), article("root", None)]);
+
+    assert_that(&ordered)
+      .is_err()
+      .is_equal_to(ArticleImportError::DuplicateKey("root".to_owned()));
+  }
+
+  #[test]
+  fn test_order_unknown_parent() {
+    let ordered = order_article_imports(vec![
+      article("root", None),
+      article("child", Some("missing")),
+    ]);
+
+    assert_that(&ordered)
+      .is_err()
+      .is_equal_to(ArticleImportError::UnknownParent("child".to_owned()));
+  }
+
+  #[test]
+  fn test_order_cycle() {
+    let ordered = order_article_imports(vec![
+      article("root", None),
+      article("first", Some("second")),
+      article("second", Some("first")),
+    ]);
+
+    assert_that(&ordered)
+      .is_err()
+      .is_equal_to(ArticleImportError::ParentCycle("first".to_owned()));
+  }
+}
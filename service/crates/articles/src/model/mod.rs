@@ -0,0 +1,7 @@
+mod article;
+mod article_id;
+mod article_import;
+
+pub use article::*;
+pub use article_id::*;
+pub use article_import::*;
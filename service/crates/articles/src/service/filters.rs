@@ -0,0 +1,10 @@
+use crate::ArticleID;
+
+/// Where in the tree of articles of a world to list articles from
+#[derive(Debug, PartialEq, Clone)]
+pub enum ArticleParentFilter {
+  /// Only articles that are at the top level of the world
+  TopLevel,
+  /// Only articles that sit directly under the given article
+  Children(ArticleID),
+}
@@ -0,0 +1,517 @@
+use super::{repository::*, service::*, ArticleParentFilter};
+use crate::model::*;
+use tracing::{debug, info, warn};
+use universe_entity::{IdMap, Page, Pagination};
+use universe_worlds::WorldID;
+
+/// The maximum number of suffixes to try when generating a unique slug for an article
+const MAX_SLUG_SUFFIX: u32 = 1000;
+
+/// The Article Service to allow interactions with article entities
+pub struct ArticleServiceImpl<Repo> {
+  repository: Repo,
+}
+
+/// Create a new Article Service
+///
+/// # Returns
+/// The Article Service
+pub fn new_article_service<Repo: ArticleRepository + Send + Sync>(
+  repository: Repo,
+) -> impl ArticleService {
+  ArticleServiceImpl { repository }
+}
+
+impl<Repo: ArticleRepository + Send + Sync> ArticleService for ArticleServiceImpl<Repo> {
+  /// Retrieve the article that has the given unique ID, as long as it belongs to the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the article belongs to
+  /// * `article_id` The ID of the article to retrieve
+  ///
+  /// # Returns
+  /// The article, or `None` if it wasn't found
+  fn get_article_by_id(&self, world_id: &WorldID, article_id: &ArticleID) -> Option<ArticleEntity> {
+    let article = self.repository.get_article_by_id(world_id, article_id);
+
+    if article.is_none() {
+      warn!(
+        "No article found with ID {} in world {}",
+        article_id, world_id
+      );
+    }
+
+    article
+  }
+
+  /// Retrieve a page of the articles of a world, sorted by title
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the articles of
+  /// * `parent` Where in the tree of articles to list articles from, or `None` for all of them
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of articles
+  fn list_articles(
+    &self,
+    world_id: &WorldID,
+    parent: Option<ArticleParentFilter>,
+    pagination: Pagination,
+  ) -> Page<ArticleEntity> {
+    self.repository.list_articles(world_id, parent, pagination)
+  }
+
+  /// Retrieve every article of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the articles of
+  ///
+  /// # Returns
+  /// The articles
+  fn list_all_articles(&self, world_id: &WorldID) -> Vec<ArticleEntity> {
+    self.repository.list_all_articles(world_id)
+  }
+
+  /// Create a new article
+  ///
+  /// # Arguments
+  /// * `article` The article data to create the article from
+  ///
+  /// # Returns
+  /// The article that was persisted
+  fn create_article(&self, article: ArticleData) -> Result<ArticleEntity, CreateArticleError> {
+    let created = self.repository.create_article(article)?;
+    Ok(created)
+  }
+
+  /// Create a new article, treating the slug as a starting point.
+  ///
+  /// If the slug is already used by another article in the same world then a numeric suffix is
+  /// appended to it until it is unique.
+  ///
+  /// # Arguments
+  /// * `article` The article data to create the article from
+  ///
+  /// # Returns
+  /// The article that was persisted
+  fn create_article_with_unique_slug(
+    &self,
+    article: ArticleData,
+  ) -> Result<ArticleEntity, CreateArticleError> {
+    let base_slug = article.slug.clone();
+    let mut candidate = article;
+
+    for suffix in 2..=MAX_SLUG_SUFFIX {
+      match self.repository.create_article(candidate.clone()) {
+        Err(PersistArticleError::DuplicateSlug) => {
+          debug!("Slug {} is already in use", candidate.slug);
+          candidate.slug = base_slug.with_suffix(suffix);
+        }
+        result => return Ok(result?),
+      }
+    }
+
+    warn!("Unable to find a unique slug based on {}", base_slug);
+    Err(CreateArticleError::ValidationError(vec![
+      ArticleValidationError::DuplicateSlug,
+    ]))
+  }
+
+  /// Update an existing article.
+  ///
+  /// This will load the article by ID, and then call a provided callback to mutate the article
+  /// before persisting the changes back to the database. The article may not be moved under
+  /// itself or any of the articles beneath it.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the article belongs to
+  /// * `article_id` The ID of the article to update
+  /// * `updater` The callback to mutate the article with
+  ///
+  /// # Returns
+  /// The newly updated article
+  fn update_article(
+    &self,
+    world_id: &WorldID,
+    article_id: &ArticleID,
+    updater: &mut dyn FnMut(ArticleData) -> Result<ArticleData, Box<dyn std::error::Error>>,
+  ) -> Result<ArticleEntity, UpdateArticleError> {
+    let article = self
+      .get_article_by_id(world_id, article_id)
+      .ok_or(UpdateArticleError::UnknownArticle)?;
+
+    let world = article.data.world.clone();
+    let previous_parent = article.data.parent.clone();
+    let updated = updater(article.data).map_err(UpdateArticleError::UpdateError)?;
+
+    if updated.parent != previous_parent {
+      if let Some(parent) = &updated.parent {
+        // The ancestors of the new parent include the parent itself
+        if self
+          .repository
+          .get_article_ancestors(parent)
+          .contains(article_id)
+        {
+          warn!(
+            "Moving article {} under {} would create a cycle",
+            article_id, parent
+          );
+          return Err(UpdateArticleError::ValidationError(vec![
+            ArticleValidationError::ParentCycle,
+          ]));
+        }
+      }
+    }
+
+    let saved = self.repository.update_article(ArticleEntity {
+      identity: article.identity,
+      // Articles can't be moved between worlds
+      data: ArticleData { world, ..updated },
+    })?;
+    Ok(saved)
+  }
+
+  /// Delete an article. Articles that have other articles under them can't be deleted
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the article belongs to
+  /// * `article_id` The ID of the article to delete
+  fn delete_article(
+    &self,
+    world_id: &WorldID,
+    article_id: &ArticleID,
+  ) -> Result<(), DeleteArticleError> {
+    self.repository.delete_article(world_id, article_id)?;
+    Ok(())
+  }
+
+  /// Create a set of articles in a world, keeping the tree that they form.
+  ///
+  /// Every article is given a new ID, and slugs are made unique within the world if needed.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to create the articles in
+  /// * `articles` The articles to create
+  /// * `ids` The map to record the new IDs of the articles in, by their keys
+  ///
+  /// # Returns
+  /// The articles that were created, parents before their children
+  fn import_articles(
+    &self,
+    world_id: &WorldID,
+    articles: Vec<ArticleImport>,
+    ids: &mut IdMap,
+  ) -> Result<Vec<ArticleEntity>, ImportArticlesError> {
+    let articles = order_article_imports(articles)?;
+
+    let mut created = Vec::with_capacity(articles.len());
+
+    for article in articles {
+      let parent = article
+        .parent
+        .and_then(|parent| ids.get("article", &parent))
+        .and_then(|parent| parent.parse().ok());
+      let result = self
+        .create_article_with_unique_slug(ArticleData {
+          world: world_id.clone(),
+          parent,
+          title: article.title,
+          slug: article.slug,
+          body: article.body,
+        })
+        .map_err(|e| match e {
+          CreateArticleError::UnknownWorld => ImportArticlesError::UnknownWorld,
+          _ => ImportArticlesError::UnknownError,
+        })?;
+
+      ids.insert("article", &article.key, &result.identity.id);
+      created.push(result);
+    }
+    info!(
+      "Imported {} articles into world {}",
+      created.len(),
+      world_id
+    );
+
+    Ok(created)
+  }
+
+  /// Copy every article of one world into another, keeping the tree that they form
+  ///
+  /// # Arguments
+  /// * `from` The ID of the world to copy the articles from
+  /// * `to` The ID of the world to copy the articles into
+  /// * `ids` The map to record the new IDs of the articles in, by their original IDs
+  ///
+  /// # Returns
+  /// The articles that were created
+  fn copy_articles(
+    &self,
+    from: &WorldID,
+    to: &WorldID,
+    ids: &mut IdMap,
+  ) -> Result<Vec<ArticleEntity>, ImportArticlesError> {
+    let articles = self
+      .list_all_articles(from)
+      .into_iter()
+      .map(|article| ArticleImport {
+        key: article.identity.id.to_string(),
+        parent: article.data.parent.map(|parent| parent.to_string()),
+        title: article.data.title,
+        slug: article.data.slug,
+        body: article.data.body,
+      })
+      .collect();
+
+    self.import_articles(to, articles, ids)
+  }
+}
+
+impl From<PersistArticleError> for CreateArticleError {
+  fn from(e: PersistArticleError) -> Self {
+    warn!("Error creating article: {}", e);
+    match e {
+      PersistArticleError::DuplicateSlug => {
+        CreateArticleError::ValidationError(vec![ArticleValidationError::DuplicateSlug])
+      }
+      PersistArticleError::ParentNotFound => {
+        CreateArticleError::ValidationError(vec![ArticleValidationError::UnknownParent])
+      }
+      PersistArticleError::WorldNotFound => CreateArticleError::UnknownWorld,
+      _ => CreateArticleError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistArticleError> for UpdateArticleError {
+  fn from(e: PersistArticleError) -> Self {
+    warn!("Error updating article: {}", e);
+    match e {
+      PersistArticleError::DuplicateSlug => {
+        UpdateArticleError::ValidationError(vec![ArticleValidationError::DuplicateSlug])
+      }
+      PersistArticleError::ParentNotFound => {
+        UpdateArticleError::ValidationError(vec![ArticleValidationError::UnknownParent])
+      }
+      PersistArticleError::ArticleNotFound => UpdateArticleError::UnknownArticle,
+      PersistArticleError::OptimisticLockFailure => UpdateArticleError::OptimisticLockFailure,
+      _ => UpdateArticleError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistArticleError> for DeleteArticleError {
+  fn from(e: PersistArticleError) -> Self {
+    warn!("Error deleting article: {}", e);
+    match e {
+      PersistArticleError::ArticleNotFound => DeleteArticleError::UnknownArticle,
+      PersistArticleError::HasChildren => DeleteArticleError::HasChildren,
+      _ => DeleteArticleError::UnknownError,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use assert_matches::assert_matches;
+  use mockall::predicate::*;
+  use spectral::prelude::*;
+  use test_env_log::test;
+  use universe_entity::Identity;
+
+  fn article(parent: Option<ArticleID>) -> ArticleEntity {
+    ArticleEntity {
+      identity: Identity::default(),
+      data: ArticleData {
+        world: Default::default(),
+        parent,
+        title: "Rivendell".to_owned(),
+        slug: "rivendell".parse().unwrap(),
+        body: "".to_owned(),
+      },
+    }
+  }
+
+  #[test]
+  fn test_create_article_with_unique_slug() {
+    let mut repository = MockArticleRepository::new();
+    repository
+      .expect_create_article()
+      .withf(|article| article.slug.to_string() == "rivendell")
+      .times(1)
+      .returning(|_| Err(PersistArticleError::DuplicateSlug));
+    repository
+      .expect_create_article()
+      .withf(|article| article.slug.to_string() == "rivendell-2")
+      .times(1)
+      .returning(|data| {
+        Ok(ArticleEntity {
+          identity: Identity::default(),
+          data,
+        })
+      });
+
+    let service = new_article_service(repository);
+
+    let created = service.create_article_with_unique_slug(article(None).data);
+    assert_that(&created)
+      .is_ok()
+      .map(|created| &created.data.slug)
+      .is_equal_to(&"rivendell-2".parse().unwrap());
+  }
+
+  #[test]
+  fn test_create_article_unknown_parent() {
+    let mut repository = MockArticleRepository::new();
+    repository
+      .expect_create_article()
+      .times(1)
+      .returning(|_| Err(PersistArticleError::ParentNotFound));
+
+    let service = new_article_service(repository);
+
+    let created = service.create_article(article(Some(Default::default())).data);
+    assert_that(&created)
+      .is_err()
+      .is_equal_to(CreateArticleError::ValidationError(vec![
+        ArticleValidationError::UnknownParent,
+      ]));
+  }
+
+  #[test]
+  fn test_update_article_parent_cycle() {
+    let existing = article(None);
+    let article_id = existing.identity.id.clone();
+    let descendant = ArticleID::default();
+
+    let mut repository = MockArticleRepository::new();
+    let returned = existing.clone();
+    repository
+      .expect_get_article_by_id()
+      .times(1)
+      .returning(move |_, _| Some(returned.clone()));
+    let ancestors = vec![descendant.clone(), article_id.clone()];
+    repository
+      .expect_get_article_ancestors()
+      .with(eq(descendant.clone()))
+      .times(1)
+      .returning(move |_| ancestors.clone());
+    repository.expect_update_article().never();
+
+    let service = new_article_service(repository);
+
+    let updated = service.update_article(&existing.data.world, &article_id, &mut |data| {
+      Ok(ArticleData {
+        parent: Some(descendant.clone()),
+        ..data
+      })
+    });
+    assert_matches!(updated, Err(UpdateArticleError::ValidationError(errors)) => {
+      assert_that(&errors).is_equal_to(vec![ArticleValidationError::ParentCycle]);
+    });
+  }
+
+  #[test]
+  fn test_update_article_keeps_parent_without_checking() {
+    let existing = article(Some(Default::default()));
+    let article_id = existing.identity.id.clone();
+
+    let mut repository = MockArticleRepository::new();
+    let returned = existing.clone();
+    repository
+      .expect_get_article_by_id()
+      .times(1)
+      .returning(move |_, _| Some(returned.clone()));
+    repository.expect_get_article_ancestors().never();
+    repository.expect_update_article().times(1).returning(Ok);
+
+    let service = new_article_service(repository);
+
+    let updated = service.update_article(&existing.data.world, &article_id, &mut |data| {
+      Ok(ArticleData {
+        title: "Imladris".to_owned(),
+        ..data
+      })
+    });
+    assert_that(&updated)
+      .is_ok()
+      .map(|updated| &updated.data.title)
+      .is_equal_to(&"Imladris".to_owned());
+  }
+
+  #[test]
+  fn test_import_articles_remaps_parents() {
+    let world_id = WorldID::default();
+
+    let mut repository = MockArticleRepository::new();
+    repository
+      .expect_create_article()
+      .times(2)
+      .returning(|data| {
+        Ok(ArticleEntity {
+          identity: Identity::default(),
+          data,
+        })
+      });
+
+    let service = new_article_service(repository);
+
+    let mut ids = IdMap::default();
+    let imported = service
+      .import_articles(
+        &world_id,
+        vec![
+          ArticleImport {
+            key: "child".to_owned(),
+            parent: Some("root".to_owned()),
+            title: "Rivendell".to_owned(),
+            slug: "rivendell".parse().unwrap(),
+            body: "".to_owned(),
+          },
+          ArticleImport {
+            key: "root".to_owned(),
+            parent: None,
+            title: "Places".to_owned(),
+            slug: "places".parse().unwrap(),
+            body: "".to_owned(),
+          },
+        ],
+        &mut ids,
+      )
+      .unwrap();
+
+    assert_that(&imported).has_length(2);
+    assert_that(&imported[0].data.title).is_equal_to("Places".to_owned());
+    assert_that(&imported[0].data.world).is_equal_to(world_id);
+    assert_that(&imported[1].data.parent).is_equal_to(Some(imported[0].identity.id.clone()));
+    assert_that(&ids.get("article", "root"))
+      .is_equal_to(Some(imported[0].identity.id.to_string().as_str()));
+  }
+
+  #[test]
+  fn test_import_articles_cycle() {
+    let mut repository = MockArticleRepository::new();
+    repository.expect_create_article().never();
+
+    let service = new_article_service(repository);
+
+    let imported = service.import_articles(
+      &Default::default(),
+      vec![ArticleImport {
+        key: "root".to_owned(),
+        parent: Some("root".to_owned()),
+        title: "Places".to_owned(),
+        slug: "places".parse().unwrap(),
+        body: "".to_owned(),
+      }],
+      &mut IdMap::default(),
+    );
+    assert_that(&imported)
+      .is_err()
+      .is_equal_to(ImportArticlesError::InvalidTree(
+        ArticleImportError::ParentCycle("root".to_owned()),
+      ));
+  }
+}
@@ -0,0 +1,102 @@
+use super::ArticleParentFilter;
+use crate::model::*;
+#[cfg(test)]
+use mockall::automock;
+use universe_entity::{Page, Pagination};
+use universe_worlds::WorldID;
+
+/// Repository that describes how to access article data
+#[cfg_attr(test, automock)]
+pub trait ArticleRepository {
+  /// Retrieve the article that has the given unique ID, as long as it belongs to the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the article belongs to
+  /// * `article_id` The ID of the article to retrieve
+  ///
+  /// # Returns
+  /// The article, or `None` if it wasn't found
+  fn get_article_by_id(&self, world_id: &WorldID, article_id: &ArticleID) -> Option<ArticleEntity>;
+
+  /// Retrieve a page of the articles of a world, sorted by title
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the articles of
+  /// * `parent` Where in the tree of articles to list articles from, or `None` for all of them
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of articles
+  fn list_articles(
+    &self,
+    world_id: &WorldID,
+    parent: Option<ArticleParentFilter>,
+    pagination: Pagination,
+  ) -> Page<ArticleEntity>;
+
+  /// Retrieve every article of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the articles of
+  ///
+  /// # Returns
+  /// The articles
+  fn list_all_articles(&self, world_id: &WorldID) -> Vec<ArticleEntity>;
+
+  /// Retrieve the IDs of an article and of every article above it in the tree
+  ///
+  /// # Arguments
+  /// * `article_id` The ID of the article to start from
+  ///
+  /// # Returns
+  /// The IDs of the article and all of its ancestors
+  fn get_article_ancestors(&self, article_id: &ArticleID) -> Vec<ArticleID>;
+
+  /// Create a new article record in the data store
+  ///
+  /// # Arguments
+  /// * `article` The article details to persist to the data store
+  ///
+  /// # Returns
+  /// The article that was persisted
+  fn create_article(&self, article: ArticleData) -> Result<ArticleEntity, PersistArticleError>;
+
+  /// Update an existing article record in the data store
+  ///
+  /// # Arguments
+  /// * `article` The article details to persist to the data store
+  ///
+  /// # Returns
+  /// The article that was persisted
+  fn update_article(&self, article: ArticleEntity) -> Result<ArticleEntity, PersistArticleError>;
+
+  /// Delete an existing article record from the data store
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the article belongs to
+  /// * `article_id` The ID of the article to delete
+  fn delete_article(
+    &self,
+    world_id: &WorldID,
+    article_id: &ArticleID,
+  ) -> Result<(), PersistArticleError>;
+}
+
+/// Errors that can occur when persisting an article record
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum PersistArticleError {
+  #[error("Duplicate Slug")]
+  DuplicateSlug,
+  #[error("The world was not found")]
+  WorldNotFound,
+  #[error("The article was not found")]
+  ArticleNotFound,
+  #[error("The parent article was not found")]
+  ParentNotFound,
+  #[error("The article has other articles under it")]
+  HasChildren,
+  #[error("The version of the article record did not match")]
+  OptimisticLockFailure,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
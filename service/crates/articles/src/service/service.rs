@@ -0,0 +1,187 @@
+use super::ArticleParentFilter;
+use crate::model::*;
+use universe_entity::{IdMap, Page, Pagination};
+use universe_worlds::WorldID;
+
+/// The Article Service to allow interactions with article entities
+pub trait ArticleService: Send + Sync {
+  /// Retrieve the article that has the given unique ID, as long as it belongs to the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the article belongs to
+  /// * `article_id` The ID of the article to retrieve
+  ///
+  /// # Returns
+  /// The article, or `None` if it wasn't found
+  fn get_article_by_id(&self, world_id: &WorldID, article_id: &ArticleID) -> Option<ArticleEntity>;
+
+  /// Retrieve a page of the articles of a world, sorted by title
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the articles of
+  /// * `parent` Where in the tree of articles to list articles from, or `None` for all of them
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of articles
+  fn list_articles(
+    &self,
+    world_id: &WorldID,
+    parent: Option<ArticleParentFilter>,
+    pagination: Pagination,
+  ) -> Page<ArticleEntity>;
+
+  /// Retrieve every article of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the articles of
+  ///
+  /// # Returns
+  /// The articles
+  fn list_all_articles(&self, world_id: &WorldID) -> Vec<ArticleEntity>;
+
+  /// Create a new article
+  ///
+  /// # Arguments
+  /// * `article` The article data to create the article from
+  ///
+  /// # Returns
+  /// The article that was persisted
+  fn create_article(&self, article: ArticleData) -> Result<ArticleEntity, CreateArticleError>;
+
+  /// Create a new article, treating the slug as a starting point.
+  ///
+  /// If the slug is already used by another article in the same world then a numeric suffix is
+  /// appended to it until it is unique.
+  ///
+  /// # Arguments
+  /// * `article` The article data to create the article from
+  ///
+  /// # Returns
+  /// The article that was persisted
+  fn create_article_with_unique_slug(
+    &self,
+    article: ArticleData,
+  ) -> Result<ArticleEntity, CreateArticleError>;
+
+  /// Update an existing article.
+  ///
+  /// This will load the article by ID, and then call a provided callback to mutate the article
+  /// before persisting the changes back to the database. The article may not be moved under
+  /// itself or any of the articles beneath it.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the article belongs to
+  /// * `article_id` The ID of the article to update
+  /// * `updater` The callback to mutate the article with
+  ///
+  /// # Returns
+  /// The newly updated article
+  fn update_article(
+    &self,
+    world_id: &WorldID,
+    article_id: &ArticleID,
+    updater: &mut dyn FnMut(ArticleData) -> Result<ArticleData, Box<dyn std::error::Error>>,
+  ) -> Result<ArticleEntity, UpdateArticleError>;
+
+  /// Delete an article. Articles that have other articles under them can't be deleted
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the article belongs to
+  /// * `article_id` The ID of the article to delete
+  fn delete_article(
+    &self,
+    world_id: &WorldID,
+    article_id: &ArticleID,
+  ) -> Result<(), DeleteArticleError>;
+
+  /// Create a set of articles in a world, keeping the tree that they form.
+  ///
+  /// Every article is given a new ID, and slugs are made unique within the world if needed.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to create the articles in
+  /// * `articles` The articles to create
+  /// * `ids` The map to record the new IDs of the articles in, by their keys
+  ///
+  /// # Returns
+  /// The articles that were created, parents before their children
+  fn import_articles(
+    &self,
+    world_id: &WorldID,
+    articles: Vec<ArticleImport>,
+    ids: &mut IdMap,
+  ) -> Result<Vec<ArticleEntity>, ImportArticlesError>;
+
+  /// Copy every article of one world into another, keeping the tree that they form
+  ///
+  /// # Arguments
+  /// * `from` The ID of the world to copy the articles from
+  /// * `to` The ID of the world to copy the articles into
+  /// * `ids` The map to record the new IDs of the articles in, by their original IDs
+  ///
+  /// # Returns
+  /// The articles that were created
+  fn copy_articles(
+    &self,
+    from: &WorldID,
+    to: &WorldID,
+    ids: &mut IdMap,
+  ) -> Result<Vec<ArticleEntity>, ImportArticlesError>;
+}
+
+/// Enumeration of validation errors for an article
+#[derive(Debug, PartialEq)]
+pub enum ArticleValidationError {
+  DuplicateSlug,
+  UnknownParent,
+  ParentCycle,
+}
+
+/// Enumeration of reasons why we failed to create a new article
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum CreateArticleError {
+  #[error("Article details were invalid: {0:?}")]
+  ValidationError(Vec<ArticleValidationError>),
+  #[error("The world was not found")]
+  UnknownWorld,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to update an existing article
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateArticleError {
+  #[error("Article details were invalid: {0:?}")]
+  ValidationError(Vec<ArticleValidationError>),
+  #[error("The article was not found")]
+  UnknownArticle,
+  #[error("The version of the article record did not match")]
+  OptimisticLockFailure,
+  #[error("An error occurred updating the article details: {0}")]
+  UpdateError(Box<dyn std::error::Error>),
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to delete an article
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum DeleteArticleError {
+  #[error("The article was not found")]
+  UnknownArticle,
+  #[error("The article has other articles under it")]
+  HasChildren,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to import a set of articles
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum ImportArticlesError {
+  #[error("The articles did not form a tree: {0}")]
+  InvalidTree(#[from] ArticleImportError),
+  #[error("The world was not found")]
+  UnknownWorld,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
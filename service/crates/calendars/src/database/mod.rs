@@ -0,0 +1,921 @@
+use crate::{model::*, service::repository::*};
+use chrono::Utc;
+use postgres::types::Json;
+use std::error::Error;
+use tracing::{debug, warn};
+use universe_database::Database;
+use universe_entity::{Identity, Page, Pagination};
+use universe_worlds::WorldID;
+use uuid::Uuid;
+
+impl From<&postgres::Row> for CalendarEntity {
+  fn from(row: &postgres::Row) -> Self {
+    let definition: Json<CalendarDefinition> = row.get("definition");
+
+    CalendarEntity {
+      identity: Identity {
+        id: row.get("calendar_id"),
+        version: row.get("version"),
+        created: row.get("created"),
+        updated: row.get("updated"),
+      },
+      data: CalendarData {
+        world: row.get("world_id"),
+        name: row.get("name"),
+        definition: definition.0,
+      },
+    }
+  }
+}
+
+impl From<&postgres::Row> for EventEntity {
+  fn from(row: &postgres::Row) -> Self {
+    let month: i32 = row.get("month");
+    let day: i32 = row.get("day");
+
+    EventEntity {
+      identity: Identity {
+        id: row.get("event_id"),
+        version: row.get("version"),
+        created: row.get("created"),
+        updated: row.get("updated"),
+      },
+      data: EventData {
+        world: row.get("world_id"),
+        calendar: row.get("calendar_id"),
+        title: row.get("title"),
+        description: row.get("description"),
+        date: CalendarDate {
+          year: row.get("year"),
+          month: month as u32,
+          day: day as u32,
+        },
+      },
+    }
+  }
+}
+
+impl CalendarRepository for Database {
+  /// Retrieve the calendar that has the given unique ID, as long as it belongs to the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the calendar belongs to
+  /// * `calendar_id` The ID of the calendar to retrieve
+  ///
+  /// # Returns
+  /// The calendar, or `None` if it wasn't found
+  fn get_calendar_by_id(
+    &self,
+    world_id: &WorldID,
+    calendar_id: &CalendarID,
+  ) -> Option<CalendarEntity> {
+    let mut client = self.client().unwrap();
+
+    let calendar = client
+      .query(
+        "SELECT * FROM calendars WHERE world_id = $1 AND calendar_id = $2",
+        &[&world_id, &calendar_id],
+      )
+      .map_err(|e| {
+        warn!("Error loading calendar from database: {}", e);
+        e
+      })
+      .ok()
+      .and_then(|rows| rows.get(0).map(|row| row.into()));
+
+    debug!("Calendar for ID {}: {:?}", calendar_id, calendar);
+    calendar
+  }
+
+  /// Retrieve a page of the calendars of a world, sorted by name
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the calendars of
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of calendars
+  fn list_calendars(&self, world_id: &WorldID, pagination: Pagination) -> Page<CalendarEntity> {
+    let mut client = self.client().unwrap();
+
+    let total: i64 = client
+      .query_one(
+        "SELECT COUNT(*) AS total FROM calendars WHERE world_id = $1",
+        &[&world_id],
+      )
+      .map_err(|e| {
+        warn!("Error counting calendars in database: {}", e);
+        e
+      })
+      .map(|row| row.get("total"))
+      .unwrap_or(0);
+
+    let entries: Vec<CalendarEntity> = client
+      .query(
+        "SELECT * FROM calendars WHERE world_id = $1
+          ORDER BY UPPER(name) ASC, calendar_id ASC
+          LIMIT $2 OFFSET $3",
+        &[
+          &world_id,
+          &i64::from(pagination.limit),
+          &i64::from(pagination.offset),
+        ],
+      )
+      .map_err(|e| {
+        warn!("Error listing calendars in database: {}", e);
+        e
+      })
+      .map(|rows| rows.iter().map(|row| row.into()).collect())
+      .unwrap_or_default();
+
+    let page = Page {
+      entries,
+      total: total as u32,
+      offset: pagination.offset,
+    };
+    debug!("Calendars of world {}: {:?}", world_id, page);
+
+    page
+  }
+
+  /// Retrieve every calendar of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the calendars of
+  ///
+  /// # Returns
+  /// The calendars
+  fn list_all_calendars(&self, world_id: &WorldID) -> Vec<CalendarEntity> {
+    let mut client = self.client().unwrap();
+
+    let calendars = client
+      .query(
+        "SELECT * FROM calendars WHERE world_id = $1 ORDER BY created ASC, calendar_id ASC",
+        &[&world_id],
+      )
+      .map_err(|e| {
+        warn!("Error listing calendars in database: {}", e);
+        e
+      })
+      .map(|rows| rows.iter().map(|row| row.into()).collect())
+      .unwrap_or_default();
+
+    debug!("All calendars of world {}: {:?}", world_id, calendars);
+    calendars
+  }
+
+  /// Create a new calendar record in the data store
+  ///
+  /// # Arguments
+  /// * `calendar` The calendar details to persist to the data store
+  ///
+  /// # Returns
+  /// The calendar that was persisted
+  fn create_calendar(
+    &self,
+    calendar: CalendarData,
+  ) -> Result<CalendarEntity, PersistCalendarError> {
+    debug!("Creating record for calendar: {:?}", calendar);
+
+    let mut client = self.client().unwrap();
+
+    let new_id = CalendarID::default();
+    let new_version = Uuid::new_v4();
+    let new_updated = Utc::now();
+
+    let result: CalendarEntity = client
+      .query(
+        "INSERT INTO calendars(calendar_id, version, created, updated, world_id, name, definition)
+          VALUES ($1, $2, $3, $3, $4, $5, $6)
+          RETURNING *",
+        &[
+          &new_id,
+          &new_version,
+          &new_updated,
+          &calendar.world,
+          &calendar.name,
+          &Json(&calendar.definition),
+        ],
+      )
+      .map(|rows| rows.get(0).unwrap().into())?;
+
+    debug!("Created record for calendar: {:?}", result);
+    Ok(result)
+  }
+
+  /// Update an existing calendar record in the data store, along with the day numbers of its
+  /// events in the updated calendar
+  ///
+  /// # Arguments
+  /// * `calendar` The calendar details to persist to the data store
+  /// * `day_numbers` The new day number of each event of the calendar
+  ///
+  /// # Returns
+  /// The calendar that was persisted
+  fn update_calendar(
+    &self,
+    calendar: CalendarEntity,
+    day_numbers: &[(EventID, i64)],
+  ) -> Result<CalendarEntity, PersistCalendarError> {
+    debug!("Updating record for calendar: {:?}", calendar);
+
+    let mut client = self.client().unwrap();
+    let mut transaction = client.transaction().unwrap();
+
+    let new_version = Uuid::new_v4();
+    let new_updated = Utc::now();
+
+    let rows = transaction.query(
+      "UPDATE calendars SET name = $1, definition = $2, version = $3, updated = $4
+          WHERE calendar_id = $5
+          AND world_id = $6
+          AND version = $7
+          RETURNING *",
+      &[
+        &calendar.data.name,
+        &Json(&calendar.data.definition),
+        &new_version,
+        &new_updated,
+        &calendar.identity.id,
+        &calendar.data.world,
+        &calendar.identity.version,
+      ],
+    )?;
+
+    if let Some(row) = rows.get(0) {
+      let result = row.into();
+
+      for (event_id, day_number) in day_numbers {
+        transaction.execute(
+          "UPDATE timeline_events SET day_number = $1 WHERE event_id = $2 AND calendar_id = $3",
+          &[&day_number, &event_id, &calendar.identity.id],
+        )?;
+      }
+
+      transaction.commit()?;
+
+      debug!("Updated record for calendar: {:?}", result);
+      Ok(result)
+    } else {
+      let calendar_found = transaction.query(
+        "SELECT version FROM calendars WHERE calendar_id = $1 AND world_id = $2",
+        &[&calendar.identity.id, &calendar.data.world],
+      )?;
+
+      if calendar_found.is_empty() {
+        warn!(
+          "Attempted to update calendar {} that wasn't found",
+          calendar.identity.id
+        );
+        Err(PersistCalendarError::CalendarNotFound)
+      } else {
+        let old_version: Uuid = calendar_found.get(0).unwrap().get("version");
+
+        warn!(
+          "Attempted to update calendar {}. Expected version {} but database had {}",
+          calendar.identity.id, calendar.identity.version, old_version
+        );
+        Err(PersistCalendarError::OptimisticLockFailure)
+      }
+    }
+  }
+
+  /// Delete an existing calendar record from the data store, along with every event of it
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the calendar belongs to
+  /// * `calendar_id` The ID of the calendar to delete
+  fn delete_calendar(
+    &self,
+    world_id: &WorldID,
+    calendar_id: &CalendarID,
+  ) -> Result<(), PersistCalendarError> {
+    debug!("Deleting calendar {} from world {}", calendar_id, world_id);
+
+    let mut client = self.client().unwrap();
+
+    // The events of the calendar are removed along with it by the database
+    let deleted = client.execute(
+      "DELETE FROM calendars WHERE world_id = $1 AND calendar_id = $2",
+      &[&world_id, &calendar_id],
+    )?;
+
+    if deleted == 0 {
+      warn!(
+        "Attempted to delete calendar {} that wasn't found",
+        calendar_id
+      );
+      Err(PersistCalendarError::CalendarNotFound)
+    } else {
+      Ok(())
+    }
+  }
+
+  /// Retrieve the event that has the given unique ID, as long as it is dated in the given
+  /// calendar
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the calendar belongs to
+  /// * `calendar_id` The ID of the calendar that the event is dated in
+  /// * `event_id` The ID of the event to retrieve
+  ///
+  /// # Returns
+  /// The event, or `None` if it wasn't found
+  fn get_event_by_id(
+    &self,
+    world_id: &WorldID,
+    calendar_id: &CalendarID,
+    event_id: &EventID,
+  ) -> Option<EventEntity> {
+    let mut client = self.client().unwrap();
+
+    let event = client
+      .query(
+        "SELECT * FROM timeline_events WHERE world_id = $1 AND calendar_id = $2 AND event_id = $3",
+        &[&world_id, &calendar_id, &event_id],
+      )
+      .map_err(|e| {
+        warn!("Error loading event from database: {}", e);
+        e
+      })
+      .ok()
+      .and_then(|rows| rows.get(0).map(|row| row.into()));
+
+    debug!("Event for ID {}: {:?}", event_id, event);
+    event
+  }
+
+  /// Retrieve a page of the events of a calendar, in the order that they happened
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the calendar belongs to
+  /// * `calendar_id` The ID of the calendar to list the events of
+  /// * `from` The day number of the earliest events to list, if any
+  /// * `to` The day number of the latest events to list, if any
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of events
+  fn list_events(
+    &self,
+    world_id: &WorldID,
+    calendar_id: &CalendarID,
+    from: Option<i64>,
+    to: Option<i64>,
+    pagination: Pagination,
+  ) -> Page<EventEntity> {
+    let mut client = self.client().unwrap();
+
+    let total: i64 = client
+      .query_one(
+        "SELECT COUNT(*) AS total FROM timeline_events WHERE world_id = $1 AND calendar_id = $2
+          AND ($3::BIGINT IS NULL OR day_number >= $3::BIGINT)
+          AND ($4::BIGINT IS NULL OR day_number <= $4::BIGINT)",
+        &[&world_id, &calendar_id, &from, &to],
+      )
+      .map_err(|e| {
+        warn!("Error counting events in database: {}", e);
+        e
+      })
+      .map(|row| row.get("total"))
+      .unwrap_or(0);
+
+    let entries: Vec<EventEntity> = client
+      .query(
+        "SELECT * FROM timeline_events WHERE world_id = $1 AND calendar_id = $2
+          AND ($3::BIGINT IS NULL OR day_number >= $3::BIGINT)
+          AND ($4::BIGINT IS NULL OR day_number <= $4::BIGINT)
+          ORDER BY day_number ASC, created ASC, event_id ASC
+          LIMIT $5 OFFSET $6",
+        &[
+          &world_id,
+          &calendar_id,
+          &from,
+          &to,
+          &i64::from(pagination.limit),
+          &i64::from(pagination.offset),
+        ],
+      )
+      .map_err(|e| {
+        warn!("Error listing events in database: {}", e);
+        e
+      })
+      .map(|rows| rows.iter().map(|row| row.into()).collect())
+      .unwrap_or_default();
+
+    let page = Page {
+      entries,
+      total: total as u32,
+      offset: pagination.offset,
+    };
+    debug!("Events of calendar {}: {:?}", calendar_id, page);
+
+    page
+  }
+
+  /// Retrieve every event of a calendar
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the calendar belongs to
+  /// * `calendar_id` The ID of the calendar to list the events of
+  ///
+  /// # Returns
+  /// The events
+  fn list_all_events(&self, world_id: &WorldID, calendar_id: &CalendarID) -> Vec<EventEntity> {
+    let mut client = self.client().unwrap();
+
+    let events = client
+      .query(
+        "SELECT * FROM timeline_events WHERE world_id = $1 AND calendar_id = $2
+          ORDER BY day_number ASC, created ASC, event_id ASC",
+        &[&world_id, &calendar_id],
+      )
+      .map_err(|e| {
+        warn!("Error listing events in database: {}", e);
+        e
+      })
+      .map(|rows| rows.iter().map(|row| row.into()).collect())
+      .unwrap_or_default();
+
+    debug!("All events of calendar {}: {:?}", calendar_id, events);
+    events
+  }
+
+  /// Create a new event record in the data store
+  ///
+  /// # Arguments
+  /// * `event` The event details to persist to the data store
+  /// * `day_number` The day number of the date of the event in its calendar
+  ///
+  /// # Returns
+  /// The event that was persisted
+  fn create_event(
+    &self,
+    event: EventData,
+    day_number: i64,
+  ) -> Result<EventEntity, PersistCalendarError> {
+    debug!("Creating record for event: {:?}", event);
+
+    let mut client = self.client().unwrap();
+
+    let new_id = EventID::default();
+    let new_version = Uuid::new_v4();
+    let new_updated = Utc::now();
+
+    let result: EventEntity = client
+      .query(
+        "INSERT INTO timeline_events(event_id, version, created, updated, world_id, calendar_id, title, description, year, month, day, day_number)
+          VALUES ($1, $2, $3, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+          RETURNING *",
+        &[
+          &new_id,
+          &new_version,
+          &new_updated,
+          &event.world,
+          &event.calendar,
+          &event.title,
+          &event.description,
+          &event.date.year,
+          &(event.date.month as i32),
+          &(event.date.day as i32),
+          &day_number,
+        ],
+      )
+      .map(|rows| rows.get(0).unwrap().into())?;
+
+    debug!("Created record for event: {:?}", result);
+    Ok(result)
+  }
+
+  /// Update an existing event record in the data store
+  ///
+  /// # Arguments
+  /// * `event` The event details to persist to the data store
+  /// * `day_number` The day number of the date of the event in its calendar
+  ///
+  /// # Returns
+  /// The event that was persisted
+  fn update_event(
+    &self,
+    event: EventEntity,
+    day_number: i64,
+  ) -> Result<EventEntity, PersistCalendarError> {
+    debug!("Updating record for event: {:?}", event);
+
+    let mut client = self.client().unwrap();
+
+    let new_version = Uuid::new_v4();
+    let new_updated = Utc::now();
+
+    let rows = client.query(
+      "UPDATE timeline_events SET title = $1, description = $2, year = $3, month = $4, day = $5, day_number = $6, version = $7, updated = $8
+          WHERE event_id = $9
+          AND world_id = $10
+          AND calendar_id = $11
+          AND version = $12
+          RETURNING *",
+      &[
+        &event.data.title,
+        &event.data.description,
+        &event.data.date.year,
+        &(event.data.date.month as i32),
+        &(event.data.date.day as i32),
+        &day_number,
+        &new_version,
+        &new_updated,
+        &event.identity.id,
+        &event.data.world,
+        &event.data.calendar,
+        &event.identity.version,
+      ],
+    )?;
+
+    if let Some(row) = rows.get(0) {
+      let result = row.into();
+      debug!("Updated record for event: {:?}", result);
+      Ok(result)
+    } else {
+      let event_found = client.query(
+        "SELECT version FROM timeline_events WHERE event_id = $1 AND world_id = $2 AND calendar_id = $3",
+        &[&event.identity.id, &event.data.world, &event.data.calendar],
+      )?;
+
+      if event_found.is_empty() {
+        warn!(
+          "Attempted to update event {} that wasn't found",
+          event.identity.id
+        );
+        Err(PersistCalendarError::EventNotFound)
+      } else {
+        let old_version: Uuid = event_found.get(0).unwrap().get("version");
+
+        warn!(
+          "Attempted to update event {}. Expected version {} but database had {}",
+          event.identity.id, event.identity.version, old_version
+        );
+        Err(PersistCalendarError::OptimisticLockFailure)
+      }
+    }
+  }
+
+  /// Delete an existing event record from the data store
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the calendar belongs to
+  /// * `calendar_id` The ID of the calendar that the event is dated in
+  /// * `event_id` The ID of the event to delete
+  fn delete_event(
+    &self,
+    world_id: &WorldID,
+    calendar_id: &CalendarID,
+    event_id: &EventID,
+  ) -> Result<(), PersistCalendarError> {
+    debug!("Deleting event {} from calendar {}", event_id, calendar_id);
+
+    let mut client = self.client().unwrap();
+
+    let deleted = client.execute(
+      "DELETE FROM timeline_events WHERE world_id = $1 AND calendar_id = $2 AND event_id = $3",
+      &[&world_id, &calendar_id, &event_id],
+    )?;
+
+    if deleted == 0 {
+      warn!("Attempted to delete event {} that wasn't found", event_id);
+      Err(PersistCalendarError::EventNotFound)
+    } else {
+      Ok(())
+    }
+  }
+}
+
+impl From<postgres::Error> for PersistCalendarError {
+  fn from(error: postgres::Error) -> Self {
+    warn!("Error persisting calendar in database: {:?}", error);
+
+    error
+      .source()
+      .and_then(|e| e.downcast_ref::<postgres::error::DbError>())
+      .map(|e| match e.constraint() {
+        Some("calendars_world_id_fkey") => PersistCalendarError::WorldNotFound,
+        Some("timeline_events_world_id_calendar_id_fkey") => PersistCalendarError::CalendarNotFound,
+        _ => PersistCalendarError::UnknownError,
+      })
+      .unwrap_or(PersistCalendarError::UnknownError)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+  use spectral::prelude::*;
+  use test_env_log::test;
+  use universe_test_database_wrapper::TestDatabaseWrapper;
+  use universe_testdata::{seed, Calendar, TimelineEvent, User, World};
+
+  /// Seed a world with two calendars into the database. The first calendar has three events, which
+  /// are seeded out of order, and the second has none
+  fn seed_calendars(database: &TestDatabaseWrapper) -> (World, Vec<Calendar>, Vec<TimelineEvent>) {
+    let user: User = Default::default();
+    let world = World {
+      owner_id: user.user_id,
+      ..Default::default()
+    };
+    let shire = Calendar {
+      world_id: world.world_id,
+      name: "Shire Reckoning".to_owned(),
+      ..Default::default()
+    };
+    let kings = Calendar {
+      world_id: world.world_id,
+      name: "Kings' Reckoning".to_owned(),
+      definition: json!({
+        "months": [{ "name": "Narwain", "days": 30 }],
+        "weekdays": ["Elenya", "Anarya"]
+      }),
+      ..Default::default()
+    };
+    let party = TimelineEvent {
+      world_id: world.world_id,
+      calendar_id: shire.calendar_id,
+      title: "Long-expected Party".to_owned(),
+      year: 3,
+      month: 3,
+      day: 22,
+      day_number: 900,
+      ..Default::default()
+    };
+    let arrival = TimelineEvent {
+      world_id: world.world_id,
+      calendar_id: shire.calendar_id,
+      title: "Arrival in Bree".to_owned(),
+      year: 4,
+      month: 2,
+      day: 1,
+      day_number: 1170,
+      ..Default::default()
+    };
+    let founding = TimelineEvent {
+      world_id: world.world_id,
+      calendar_id: shire.calendar_id,
+      title: "Founding of the Shire".to_owned(),
+      ..Default::default()
+    };
+
+    seed(
+      &database,
+      vec![&user, &world, &shire, &kings, &party, &arrival, &founding],
+    );
+
+    (world, vec![shire, kings], vec![founding, party, arrival])
+  }
+
+  fn titles(events: &[EventEntity]) -> Vec<String> {
+    events
+      .iter()
+      .map(|event| event.data.title.clone())
+      .collect()
+  }
+
+  #[test]
+  fn test_get_calendar() {
+    let database = TestDatabaseWrapper::new();
+    let (world, calendars, _) = seed_calendars(&database);
+
+    for calendar in calendars {
+      let found = database.wrapper.get_calendar_by_id(
+        &WorldID::from_uuid(world.world_id),
+        &CalendarID::from_uuid(calendar.calendar_id),
+      );
+      assert_that(&found)
+        .is_some()
+        .is_equal_to(CalendarEntity::from(calendar));
+    }
+  }
+
+  #[test]
+  fn test_get_calendar_wrong_world() {
+    let database = TestDatabaseWrapper::new();
+    let (_, calendars, _) = seed_calendars(&database);
+
+    let found = database.wrapper.get_calendar_by_id(
+      &Default::default(),
+      &CalendarID::from_uuid(calendars[0].calendar_id),
+    );
+    assert_that(&found).is_none();
+  }
+
+  #[test]
+  fn test_list_calendars() {
+    let database = TestDatabaseWrapper::new();
+    let (world, _, _) = seed_calendars(&database);
+
+    let calendars = database.wrapper.list_calendars(
+      &WorldID::from_uuid(world.world_id),
+      Pagination {
+        offset: 0,
+        limit: 10,
+      },
+    );
+    assert_that(&calendars.total).is_equal_to(2);
+    assert_that(
+      &calendars
+        .entries
+        .iter()
+        .map(|calendar| calendar.data.name.clone())
+        .collect::<Vec<_>>(),
+    )
+    .is_equal_to(vec![
+      "Kings' Reckoning".to_owned(),
+      "Shire Reckoning".to_owned(),
+    ]);
+  }
+
+  #[test]
+  fn test_create_calendar_unknown_world() {
+    let database = TestDatabaseWrapper::new();
+
+    let created = database.wrapper.create_calendar(CalendarData {
+      world: Default::default(),
+      name: "Steward's Reckoning".to_owned(),
+      definition: Default::default(),
+    });
+    assert_that(&created)
+      .is_err()
+      .is_equal_to(PersistCalendarError::WorldNotFound);
+  }
+
+  #[test]
+  fn test_update_calendar_and_day_numbers() {
+    let database = TestDatabaseWrapper::new();
+    let (world, calendars, events) = seed_calendars(&database);
+    let world_id = WorldID::from_uuid(world.world_id);
+    let calendar_id = CalendarID::from_uuid(calendars[0].calendar_id);
+
+    let mut calendar = database
+      .wrapper
+      .get_calendar_by_id(&world_id, &calendar_id)
+      .unwrap();
+    calendar.data.name = "New Reckoning".to_owned();
+    // Move the founding to after everything else
+    let updated = database
+      .wrapper
+      .update_calendar(
+        calendar.clone(),
+        &[(EventID::from_uuid(events[0].event_id), 5000)],
+      )
+      .unwrap();
+    assert_that(&updated.data).is_equal_to(&calendar.data);
+    assert_that(&updated.identity.version).is_not_equal_to(calendar.identity.version);
+
+    let all = database.wrapper.list_all_events(&world_id, &calendar_id);
+    assert_that(&titles(&all)).is_equal_to(vec![
+      "Long-expected Party".to_owned(),
+      "Arrival in Bree".to_owned(),
+      "Founding of the Shire".to_owned(),
+    ]);
+
+    let updated = database.wrapper.update_calendar(calendar, &[]);
+    assert_that(&updated)
+      .is_err()
+      .is_equal_to(PersistCalendarError::OptimisticLockFailure);
+  }
+
+  #[test]
+  fn test_delete_calendar_removes_events() {
+    let database = TestDatabaseWrapper::new();
+    let (world, calendars, _) = seed_calendars(&database);
+    let world_id = WorldID::from_uuid(world.world_id);
+    let calendar_id = CalendarID::from_uuid(calendars[0].calendar_id);
+
+    let deleted = database.wrapper.delete_calendar(&world_id, &calendar_id);
+    assert_that(&deleted).is_ok();
+    assert_that(&database.wrapper.list_all_events(&world_id, &calendar_id)).is_empty();
+
+    let deleted = database.wrapper.delete_calendar(&world_id, &calendar_id);
+    assert_that(&deleted)
+      .is_err()
+      .is_equal_to(PersistCalendarError::CalendarNotFound);
+  }
+
+  #[test]
+  fn test_list_events_in_order() {
+    let database = TestDatabaseWrapper::new();
+    let (world, calendars, events) = seed_calendars(&database);
+    let world_id = WorldID::from_uuid(world.world_id);
+    let calendar_id = CalendarID::from_uuid(calendars[0].calendar_id);
+
+    let all = database.wrapper.list_events(
+      &world_id,
+      &calendar_id,
+      None,
+      None,
+      Pagination {
+        offset: 0,
+        limit: 10,
+      },
+    );
+    assert_that(&all.total).is_equal_to(3);
+    assert_that(&all.entries).is_equal_to(
+      events
+        .iter()
+        .cloned()
+        .map(EventEntity::from)
+        .collect::<Vec<_>>(),
+    );
+
+    let between = database.wrapper.list_events(
+      &world_id,
+      &calendar_id,
+      Some(1),
+      Some(1170),
+      Pagination {
+        offset: 0,
+        limit: 10,
+      },
+    );
+    assert_that(&between.total).is_equal_to(2);
+    assert_that(&titles(&between.entries)).is_equal_to(vec![
+      "Long-expected Party".to_owned(),
+      "Arrival in Bree".to_owned(),
+    ]);
+
+    let paged = database.wrapper.list_events(
+      &world_id,
+      &calendar_id,
+      None,
+      None,
+      Pagination {
+        offset: 2,
+        limit: 10,
+      },
+    );
+    assert_that(&paged.total).is_equal_to(3);
+    assert_that(&titles(&paged.entries)).is_equal_to(vec!["Arrival in Bree".to_owned()]);
+  }
+
+  #[test]
+  fn test_create_update_and_delete_event() {
+    let database = TestDatabaseWrapper::new();
+    let (world, calendars, _) = seed_calendars(&database);
+    let world_id = WorldID::from_uuid(world.world_id);
+    let calendar_id = CalendarID::from_uuid(calendars[1].calendar_id);
+
+    let data = EventData {
+      world: world_id.clone(),
+      calendar: calendar_id.clone(),
+      title: "Coronation".to_owned(),
+      description: "A new king is crowned".to_owned(),
+      date: CalendarDate {
+        year: -5,
+        month: 1,
+        day: 12,
+      },
+    };
+    let created = database.wrapper.create_event(data.clone(), -169).unwrap();
+    assert_that(&created.data).is_equal_to(&data);
+
+    let mut event = created.clone();
+    event.data.date.day = 13;
+    let updated = database.wrapper.update_event(event.clone(), -168).unwrap();
+    assert_that(&updated.data).is_equal_to(&event.data);
+
+    let updated = database.wrapper.update_event(event, -168);
+    assert_that(&updated)
+      .is_err()
+      .is_equal_to(PersistCalendarError::OptimisticLockFailure);
+
+    let deleted = database
+      .wrapper
+      .delete_event(&world_id, &calendar_id, &created.identity.id);
+    assert_that(&deleted).is_ok();
+    let found = database
+      .wrapper
+      .get_event_by_id(&world_id, &calendar_id, &created.identity.id);
+    assert_that(&found).is_none();
+  }
+
+  #[test]
+  fn test_create_event_unknown_calendar() {
+    let database = TestDatabaseWrapper::new();
+    let (world, _, _) = seed_calendars(&database);
+
+    let created = database.wrapper.create_event(
+      EventData {
+        world: WorldID::from_uuid(world.world_id),
+        calendar: Default::default(),
+        title: "Coronation".to_owned(),
+        description: "".to_owned(),
+        date: CalendarDate {
+          year: 1,
+          month: 1,
+          day: 1,
+        },
+      },
+      0,
+    );
+    assert_that(&created)
+      .is_err()
+      .is_equal_to(PersistCalendarError::CalendarNotFound);
+  }
+}
@@ -0,0 +1,6 @@
+mod database;
+mod model;
+mod service;
+
+pub use model::*;
+pub use service::*;
@@ -0,0 +1,39 @@
+use crate::{CalendarDefinition, CalendarID};
+use universe_entity::Identity;
+use universe_worlds::WorldID;
+
+/// Struct to represent the data about a single Calendar
+#[derive(Debug, PartialEq, Clone)]
+pub struct CalendarData {
+  /// The world that the calendar belongs to
+  pub world: WorldID,
+  pub name: String,
+  /// How the calendar counts days, months and years
+  pub definition: CalendarDefinition,
+}
+
+/// Type to represent the entity that is a persisted calendar record
+#[derive(Debug, PartialEq, Clone)]
+pub struct CalendarEntity {
+  pub identity: Identity<CalendarID>,
+  pub data: CalendarData,
+}
+
+#[cfg(test)]
+impl From<universe_testdata::Calendar> for CalendarEntity {
+  fn from(calendar: universe_testdata::Calendar) -> CalendarEntity {
+    CalendarEntity {
+      identity: Identity {
+        id: CalendarID::from_uuid(calendar.calendar_id),
+        version: calendar.version,
+        created: calendar.created,
+        updated: calendar.updated,
+      },
+      data: CalendarData {
+        world: WorldID::from_uuid(calendar.world_id),
+        name: calendar.name.clone(),
+        definition: serde_json::from_value(calendar.definition).unwrap(),
+      },
+    }
+  }
+}
@@ -0,0 +1,148 @@
+use bytes::BytesMut;
+use postgres::types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
+use serde::Serialize;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Representation of a Calendar ID of some calendar in the system.
+///
+/// A Calendar ID is any valid UUID.
+#[derive(Debug, PartialEq, Clone, Serialize, FromSql)]
+pub struct CalendarID(Uuid);
+
+/// Errors that can happen when parsing a string into a Calendar ID.
+#[derive(Debug, PartialEq, Clone, thiserror::Error)]
+pub enum CalendarIDParseError {
+  #[error("Calendar ID was malformed: {0}")]
+  Malformed(#[from] uuid::Error),
+}
+
+impl CalendarID {
+  /// Construct a Calendar ID from a UUID value
+  ///
+  /// # Arguments
+  /// * `uuid` The UUID to use
+  ///
+  /// # Returns
+  /// The Calendar ID
+  #[allow(unused)]
+  pub fn from_uuid(uuid: Uuid) -> Self {
+    CalendarID(uuid)
+  }
+}
+
+impl std::fmt::Display for CalendarID {
+  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl Default for CalendarID {
+  fn default() -> Self {
+    CalendarID(Uuid::new_v4())
+  }
+}
+/// Implementation of the standard `FromStr` trait to allow us to parse any String into a `CalendarID` object
+impl FromStr for CalendarID {
+  type Err = CalendarIDParseError;
+
+  /// Attempt to parse a string into a CalendarID object.
+  ///
+  /// A Calendar ID is any valid UUID.
+  ///
+  /// # Arguments
+  /// * `s` The string to parse
+  ///
+  /// # Returns
+  /// The result of parsing the Calendar ID. Either an `CalendarID` object or an error if the incoming
+  /// string was not valid.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let uuid: Uuid = s.trim().parse()?;
+    Ok(CalendarID(uuid))
+  }
+}
+
+/// Allow us to pass `CalendarID` objects to Postgres as part of executing a database query.
+///
+/// The implementation of this trait allows objects of this type to be used directly as database
+/// binds without ever needing to extract the string from inside it.
+impl ToSql for CalendarID {
+  fn to_sql(
+    &self,
+    t: &Type,
+    w: &mut BytesMut,
+  ) -> Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+    self.0.to_sql(t, w)
+  }
+
+  accepts!(UUID);
+  to_sql_checked!();
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use assert_matches::*;
+  use serde_json::json;
+  use spectral::prelude::*;
+  use test_env_log::test;
+
+  #[test]
+  fn test_parse_valid_calendar_id() {
+    let calendar_id: Result<CalendarID, CalendarIDParseError> =
+      "f2c55656-d7a1-4e41-a311-fe653b9b15de".parse();
+
+    assert_that(&calendar_id).is_ok().is_equal_to(CalendarID(
+      "f2c55656-d7a1-4e41-a311-fe653b9b15de".parse().unwrap(),
+    ));
+  }
+
+  #[test]
+  fn test_parse_padded_calendar_id() {
+    let calendar_id: Result<CalendarID, CalendarIDParseError> =
+      "  f2c55656-d7a1-4e41-a311-fe653b9b15de    ".parse();
+
+    assert_that(&calendar_id).is_ok().is_equal_to(CalendarID(
+      "f2c55656-d7a1-4e41-a311-fe653b9b15de".parse().unwrap(),
+    ));
+  }
+
+  #[test]
+  fn test_parse_empty_string() {
+    let calendar_id: Result<CalendarID, CalendarIDParseError> = "".parse();
+
+    assert_matches!(calendar_id.unwrap_err(), CalendarIDParseError::Malformed(_));
+  }
+
+  #[test]
+  fn test_parse_blank_string() {
+    let calendar_id: Result<CalendarID, CalendarIDParseError> = "     ".parse();
+
+    assert_matches!(calendar_id.unwrap_err(), CalendarIDParseError::Malformed(_));
+  }
+
+  #[test]
+  fn test_parse_invalid_string_bad_length() {
+    let calendar_id: Result<CalendarID, CalendarIDParseError> = "non-uuid".parse();
+
+    assert_matches!(calendar_id.unwrap_err(), CalendarIDParseError::Malformed(_));
+  }
+
+  #[test]
+  fn test_parse_invalid_string_bad_character() {
+    let calendar_id: Result<CalendarID, CalendarIDParseError> =
+      "C37837C7-3E8C-4235-8A00-0845F598D12Z".parse();
+
+    assert_matches!(calendar_id.unwrap_err(), CalendarIDParseError::Malformed(_));
+  }
+
+  #[test]
+  fn test_serialize_valid_calendar_id() {
+    let calendar_id = CalendarID("f2c55656-d7a1-4e41-a311-fe653b9b15de".parse().unwrap());
+
+    let serialized = serde_json::to_value(calendar_id);
+    assert_that(&serialized)
+      .is_ok()
+      .is_equal_to(json!("f2c55656-d7a1-4e41-a311-fe653b9b15de"));
+  }
+}
@@ -0,0 +1,20 @@
+use crate::{CalendarDate, CalendarDefinition};
+
+/// Details of a calendar to create as part of importing or copying the calendars of a world
+#[derive(Debug, PartialEq, Clone)]
+pub struct CalendarImport {
+  /// The key that the calendar is known by, which is recorded against the ID of the new calendar
+  pub key: String,
+  pub name: String,
+  pub definition: CalendarDefinition,
+  /// The events to date in the calendar. Their dates must exist in its definition
+  pub events: Vec<EventImport>,
+}
+
+/// Details of an event to date in a calendar that is being imported or copied
+#[derive(Debug, PartialEq, Clone)]
+pub struct EventImport {
+  pub title: String,
+  pub description: String,
+  pub date: CalendarDate,
+}
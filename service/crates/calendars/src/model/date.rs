@@ -0,0 +1,661 @@
+use crate::{CalendarDefinition, Era, MAX_YEAR};
+use std::cmp::Ordering;
+
+/// A date in some calendar.
+///
+/// The year is the absolute year, regardless of which era it's in. The month and day both count
+/// from 1. Dates of the same calendar order the same way as the calendar itself does, as long as
+/// they are valid in that calendar.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct CalendarDate {
+  pub year: i64,
+  pub month: u32,
+  pub day: u32,
+}
+
+/// Errors that can happen when working with the dates of a calendar
+#[derive(Debug, PartialEq, Clone, thiserror::Error)]
+pub enum DateError {
+  #[error("The date was malformed")]
+  Malformed,
+  #[error("The calendar has no month with that name")]
+  UnknownMonth,
+  #[error("The calendar has no era with that abbreviation")]
+  UnknownEra,
+  #[error("The year is not part of the era")]
+  YearOutsideEra,
+  #[error("The year is too far from year 1")]
+  YearOutOfRange,
+  #[error("The calendar doesn't have that many months")]
+  MonthOutOfRange,
+  #[error("The month doesn't have that many days")]
+  DayOutOfRange,
+}
+
+impl CalendarDefinition {
+  /// Get the number of days in a month of a year
+  ///
+  /// # Arguments
+  /// * `year` The absolute year
+  /// * `month` The index of the month, counting from 0
+  fn month_length(&self, year: i64, month: usize) -> u32 {
+    let month = &self.months[month];
+
+    if self.is_leap_year(year) {
+      month.days + month.leap_days
+    } else {
+      month.days
+    }
+  }
+
+  /// Get the number of days from the start of year 1 until the start of the given year. This is
+  /// negative for years before year 1
+  fn days_before_year(&self, year: i64) -> i64 {
+    let common: i64 = self.months.iter().map(|month| i64::from(month.days)).sum();
+    let leap: i64 = self
+      .months
+      .iter()
+      .map(|month| i64::from(month.leap_days))
+      .sum();
+    let leap_years = self
+      .leap_rule
+      .as_ref()
+      .map_or(0, |leap_rule| leap_rule.leap_years_through(year - 1));
+
+    (year - 1) * common + leap_years * leap
+  }
+
+  /// Check that a date exists in this calendar
+  ///
+  /// # Arguments
+  /// * `date` The date to check
+  pub fn check_date(&self, date: &CalendarDate) -> Result<(), DateError> {
+    if date.year.abs() > MAX_YEAR {
+      return Err(DateError::YearOutOfRange);
+    }
+    if date.month == 0 || date.month as usize > self.months.len() {
+      return Err(DateError::MonthOutOfRange);
+    }
+    if date.day == 0 || date.day > self.month_length(date.year, date.month as usize - 1) {
+      return Err(DateError::DayOutOfRange);
+    }
+
+    Ok(())
+  }
+
+  /// Get the day number of a date. This is the number of days since the first day of year 1, and
+  /// is what the calendar orders dates by.
+  ///
+  /// The date must be valid for the calendar.
+  pub fn day_number(&self, date: &CalendarDate) -> i64 {
+    let months: i64 = (0..(date.month as usize - 1))
+      .map(|month| i64::from(self.month_length(date.year, month)))
+      .sum();
+
+    self.days_before_year(date.year) + months + i64::from(date.day) - 1
+  }
+
+  /// Get the date that has the given day number
+  ///
+  /// # Arguments
+  /// * `day_number` The number of days since the first day of year 1
+  ///
+  /// # Returns
+  /// The date, or an error if it's too far from year 1
+  pub fn date_from_day_number(&self, day_number: i64) -> Result<CalendarDate, DateError> {
+    let common: i64 = self.months.iter().map(|month| i64::from(month.days)).sum();
+    let leap: i64 = self
+      .months
+      .iter()
+      .map(|month| i64::from(month.leap_days))
+      .sum();
+    let density = self
+      .leap_rule
+      .as_ref()
+      .map_or(0.0, |leap_rule| leap_rule.density());
+
+    // Start from a guess using the average length of a year, which is never more than a couple of
+    // years out, and then walk to the actual year
+    let average = common as f64 + leap as f64 * density;
+    let guess = 1.0 + (day_number as f64 / average).floor();
+    if guess.abs() > (MAX_YEAR + 2) as f64 {
+      return Err(DateError::YearOutOfRange);
+    }
+
+    let mut year = guess as i64;
+    while self.days_before_year(year) > day_number {
+      year -= 1;
+    }
+    while self.days_before_year(year + 1) <= day_number {
+      year += 1;
+    }
+    if year.abs() > MAX_YEAR {
+      return Err(DateError::YearOutOfRange);
+    }
+
+    let mut remaining = day_number - self.days_before_year(year);
+    for month in 0..self.months.len() {
+      let length = i64::from(self.month_length(year, month));
+      if remaining < length {
+        return Ok(CalendarDate {
+          year,
+          month: month as u32 + 1,
+          day: remaining as u32 + 1,
+        });
+      }
+      remaining -= length;
+    }
+
+    unreachable!("Day {} is not in year {}", day_number, year)
+  }
+
+  /// Get the name of the weekday that a date falls on.
+  ///
+  /// The date must be valid for the calendar.
+  ///
+  /// # Returns
+  /// The name of the weekday, or `None` if the calendar doesn't have weeks
+  pub fn weekday(&self, date: &CalendarDate) -> Option<&str> {
+    if self.weekdays.is_empty() {
+      None
+    } else {
+      let weekday = (i64::from(self.epoch_weekday) + self.day_number(date))
+        .rem_euclid(self.weekdays.len() as i64);
+      Some(self.weekdays[weekday as usize].trim())
+    }
+  }
+
+  /// Get the era that an absolute year is in
+  ///
+  /// # Returns
+  /// The era and the year of the era, or `None` if the year is before the first era
+  pub fn era(&self, year: i64) -> Option<(&Era, i64)> {
+    self
+      .eras
+      .iter()
+      .rev()
+      .find(|era| era.start_year <= year)
+      .map(|era| (era, year - era.start_year + 1))
+  }
+
+  /// Format a date for display, as the day, the name of the month and the year. The year is
+  /// written as the year of the era followed by the abbreviation of the era if it's in one, or as
+  /// the absolute year if not. E.g. "22 Halimath 2890 TA".
+  ///
+  /// The date must be valid for the calendar.
+  pub fn format_date(&self, date: &CalendarDate) -> String {
+    let month = self.months[date.month as usize - 1].name.trim();
+
+    match self.era(date.year) {
+      Some((era, year)) => format!(
+        "{} {} {} {}",
+        date.day,
+        month,
+        year,
+        era.abbreviation.trim()
+      ),
+      None => format!("{} {} {}", date.day, month, date.year),
+    }
+  }
+
+  /// Parse a date in this calendar.
+  ///
+  /// This accepts dates in the same form that `format_date` produces, where the era is optional
+  /// and a year without one is an absolute year. The names of months and eras are matched
+  /// regardless of case. It also accepts dates as numbers in the form "year-month-day", where the
+  /// year is always an absolute year.
+  ///
+  /// # Arguments
+  /// * `input` The date to parse
+  ///
+  /// # Returns
+  /// The date, or an error if it's not a valid date of the calendar
+  pub fn parse_date(&self, input: &str) -> Result<CalendarDate, DateError> {
+    let tokens: Vec<&str> = input.split_whitespace().collect();
+
+    let date = match tokens.as_slice() {
+      [] => Err(DateError::Malformed),
+      [numeric] => parse_numeric_date(numeric),
+      [day, rest @ ..] => {
+        let day: u32 = day.parse().map_err(|_| DateError::Malformed)?;
+
+        // The era is optional, so the last word is either the era or the year
+        let era = rest.last().and_then(|token| self.find_era(token));
+        if era.is_none()
+          && rest.len() >= 2
+          && is_year(rest[rest.len() - 2])
+          && !is_year(rest[rest.len() - 1])
+        {
+          return Err(DateError::UnknownEra);
+        }
+        let (month, year) = match era {
+          Some(_) => rest.split_at(rest.len().saturating_sub(2)),
+          None => rest.split_at(rest.len() - 1),
+        };
+        if month.is_empty() {
+          return Err(DateError::Malformed);
+        }
+
+        let year: i64 = year[0].parse().map_err(|_| DateError::Malformed)?;
+        let month = self
+          .find_month(&month.join(" "))
+          .ok_or(DateError::UnknownMonth)?;
+        let year = match era {
+          Some(era) => self.year_of_era(era, year)?,
+          None => year,
+        };
+
+        Ok(CalendarDate {
+          year,
+          month: month as u32 + 1,
+          day,
+        })
+      }
+    }?;
+
+    self.check_date(&date)?;
+    Ok(date)
+  }
+
+  /// Convert the year of an era into an absolute year
+  ///
+  /// # Arguments
+  /// * `era` The index of the era
+  /// * `year` The year of the era, counting from 1
+  fn year_of_era(&self, era: usize, year: i64) -> Result<i64, DateError> {
+    if year < 1 || year > MAX_YEAR * 2 {
+      return Err(DateError::YearOutsideEra);
+    }
+
+    let absolute = self.eras[era].start_year + year - 1;
+    match self.eras.get(era + 1) {
+      Some(next) if absolute >= next.start_year => Err(DateError::YearOutsideEra),
+      _ => Ok(absolute),
+    }
+  }
+
+  /// Compare two dates of this calendar
+  pub fn compare_dates(&self, a: &CalendarDate, b: &CalendarDate) -> Ordering {
+    self.day_number(a).cmp(&self.day_number(b))
+  }
+
+  /// Count the days from one date until another. This is negative if the second date is before
+  /// the first one
+  pub fn days_between(&self, from: &CalendarDate, to: &CalendarDate) -> i64 {
+    self.day_number(to) - self.day_number(from)
+  }
+
+  /// Move a date by a number of days
+  ///
+  /// # Arguments
+  /// * `date` The date to move
+  /// * `days` How many days to move the date by. This can be negative to move it backwards
+  pub fn add_days(&self, date: &CalendarDate, days: i64) -> Result<CalendarDate, DateError> {
+    let day_number = self
+      .day_number(date)
+      .checked_add(days)
+      .ok_or(DateError::YearOutOfRange)?;
+
+    self.date_from_day_number(day_number)
+  }
+
+  /// Move a date by a number of months. If the new month is too short for the day of the month,
+  /// the date ends up on the last day of the month instead
+  ///
+  /// # Arguments
+  /// * `date` The date to move
+  /// * `months` How many months to move the date by. This can be negative to move it backwards
+  pub fn add_months(&self, date: &CalendarDate, months: i64) -> Result<CalendarDate, DateError> {
+    let months_per_year = self.months.len() as i64;
+    let month = date
+      .year
+      .checked_mul(months_per_year)
+      .and_then(|month| month.checked_add(i64::from(date.month) - 1))
+      .and_then(|month| month.checked_add(months))
+      .ok_or(DateError::YearOutOfRange)?;
+
+    self.clamp_date(
+      month.div_euclid(months_per_year),
+      month.rem_euclid(months_per_year) as u32 + 1,
+      date.day,
+    )
+  }
+
+  /// Move a date by a number of years. If the month is shorter in the new year, such as when
+  /// moving from a leap day, the date ends up on the last day of the month instead
+  ///
+  /// # Arguments
+  /// * `date` The date to move
+  /// * `years` How many years to move the date by. This can be negative to move it backwards
+  pub fn add_years(&self, date: &CalendarDate, years: i64) -> Result<CalendarDate, DateError> {
+    let year = date
+      .year
+      .checked_add(years)
+      .ok_or(DateError::YearOutOfRange)?;
+
+    self.clamp_date(year, date.month, date.day)
+  }
+
+  /// Build a date, moving it back to the end of the month if the month is too short
+  fn clamp_date(&self, year: i64, month: u32, day: u32) -> Result<CalendarDate, DateError> {
+    if year.abs() > MAX_YEAR {
+      return Err(DateError::YearOutOfRange);
+    }
+
+    Ok(CalendarDate {
+      year,
+      month,
+      day: day.min(self.month_length(year, month as usize - 1)),
+    })
+  }
+}
+
+/// Check if a word of a date is a year number
+fn is_year(word: &str) -> bool {
+  word.parse::<i64>().is_ok()
+}
+
+/// Parse a date in the numeric form "year-month-day". The year may be negative
+fn parse_numeric_date(input: &str) -> Result<CalendarDate, DateError> {
+  let (sign, input) = if input.starts_with('-') {
+    (-1, &input[1..])
+  } else {
+    (1, input)
+  };
+
+  let parts: Vec<&str> = input.split('-').collect();
+  match parts.as_slice() {
+    [year, month, day] => Ok(CalendarDate {
+      year: sign * year.parse::<i64>().map_err(|_| DateError::Malformed)?,
+      month: month.parse().map_err(|_| DateError::Malformed)?,
+      day: day.parse().map_err(|_| DateError::Malformed)?,
+    }),
+    _ => Err(DateError::Malformed),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{LeapRule, Month};
+  use assert_matches::assert_matches;
+  use rstest::rstest;
+  use spectral::prelude::*;
+
+  /// The proleptic Gregorian calendar, which is an easy one to check the results of
+  fn gregorian() -> CalendarDefinition {
+    let month = |name: &str, days: u32, leap_days: u32| Month {
+      name: name.to_owned(),
+      days,
+      leap_days,
+    };
+
+    CalendarDefinition {
+      months: vec![
+        month("January", 31, 0),
+        month("February", 28, 1),
+        month("March", 31, 0),
+        month("April", 30, 0),
+        month("May", 31, 0),
+        month("June", 30, 0),
+        month("July", 31, 0),
+        month("August", 31, 0),
+        month("September", 30, 0),
+        month("October", 31, 0),
+        month("November", 30, 0),
+        month("December", 31, 0),
+      ],
+      weekdays: vec![
+        "Monday".to_owned(),
+        "Tuesday".to_owned(),
+        "Wednesday".to_owned(),
+        "Thursday".to_owned(),
+        "Friday".to_owned(),
+        "Saturday".to_owned(),
+        "Sunday".to_owned(),
+      ],
+      leap_rule: Some(LeapRule {
+        every: 4,
+        except_every: Some(100),
+        unless_every: Some(400),
+      }),
+      eras: vec![],
+      epoch_weekday: 0,
+    }
+  }
+
+  /// A calendar of eight months of 45 days, with a leap day every 3 years and two eras
+  fn fantasy() -> CalendarDefinition {
+    let month = |name: &str, leap_days: u32| Month {
+      name: name.to_owned(),
+      days: 45,
+      leap_days,
+    };
+
+    CalendarDefinition {
+      months: vec![
+        month("Deepwinter", 0),
+        month("Thaw", 0),
+        month("Greening", 0),
+        month("High Sun", 1),
+        month("Harvest", 0),
+        month("Leaffall", 0),
+        month("Frost", 0),
+        month("Long Night", 0),
+      ],
+      weekdays: vec![
+        "Moonday".to_owned(),
+        "Fireday".to_owned(),
+        "Starday".to_owned(),
+      ],
+      leap_rule: Some(LeapRule {
+        every: 3,
+        except_every: None,
+        unless_every: None,
+      }),
+      eras: vec![
+        Era {
+          name: "Age of Dawn".to_owned(),
+          abbreviation: "AD".to_owned(),
+          start_year: 1,
+        },
+        Era {
+          name: "Age of Crowns".to_owned(),
+          abbreviation: "AC".to_owned(),
+          start_year: 501,
+        },
+      ],
+      epoch_weekday: 2,
+    }
+  }
+
+  fn date(year: i64, month: u32, day: u32) -> CalendarDate {
+    CalendarDate { year, month, day }
+  }
+
+  #[rstest(input, expected,
+    case(date(1, 1, 1), 0),
+    case(date(1, 12, 31), 364),
+    case(date(2, 1, 1), 365),
+    case(date(2000, 1, 1), 730_119),
+    case(date(2020, 3, 11), 737_494),
+    case(date(0, 12, 31), -1),
+    case(date(0, 1, 1), -366),
+    case(date(-400, 1, 1), -146_463),
+  )]
+  fn test_gregorian_day_number(input: CalendarDate, expected: i64) {
+    let calendar = gregorian();
+
+    assert_that(&calendar.day_number(&input)).is_equal_to(expected);
+    assert_that(&calendar.date_from_day_number(expected))
+      .is_ok()
+      .is_equal_to(input);
+  }
+
+  #[test]
+  fn test_day_numbers_round_trip() {
+    let calendar = fantasy();
+
+    let mut expected = date(-5, 1, 1);
+    for day_number in calendar.day_number(&expected)..(360 * 12) {
+      let found = calendar.date_from_day_number(day_number).unwrap();
+      assert_that(&found).is_equal_to(expected);
+      assert_that(&calendar.day_number(&found)).is_equal_to(day_number);
+
+      expected = if calendar.check_date(&CalendarDate {
+        day: expected.day + 1,
+        ..expected
+      }) == Ok(())
+      {
+        CalendarDate {
+          day: expected.day + 1,
+          ..expected
+        }
+      } else if (expected.month as usize) < calendar.months.len() {
+        date(expected.year, expected.month + 1, 1)
+      } else {
+        date(expected.year + 1, 1, 1)
+      };
+    }
+  }
+
+  #[rstest(
+    input,
+    expected,
+    case(date(2020, 3, 11), Some("Wednesday")),
+    case(date(1, 1, 1), Some("Monday")),
+    case(date(0, 12, 31), Some("Sunday"))
+  )]
+  fn test_gregorian_weekday(input: CalendarDate, expected: Option<&str>) {
+    assert_that(&gregorian().weekday(&input)).is_equal_to(expected);
+  }
+
+  #[test]
+  fn test_weekday_without_weeks() {
+    let calendar = CalendarDefinition {
+      weekdays: vec![],
+      ..fantasy()
+    };
+
+    assert_that(&calendar.weekday(&date(1, 1, 1))).is_none();
+  }
+
+  #[rstest(input, expected,
+    case(date(1, 1, 1), "1 Deepwinter 1 AD"),
+    case(date(498, 4, 46), "46 High Sun 498 AD"),
+    case(date(501, 8, 45), "45 Long Night 1 AC"),
+    case(date(1250, 2, 3), "3 Thaw 750 AC"),
+    case(date(0, 1, 1), "1 Deepwinter 0"),
+    case(date(-20, 5, 9), "9 Harvest -20"),
+  )]
+  fn test_format_date(input: CalendarDate, expected: &str) {
+    let calendar = fantasy();
+
+    assert_that(&calendar.format_date(&input)).is_equal_to(expected.to_owned());
+    assert_that(&calendar.parse_date(expected))
+      .is_ok()
+      .is_equal_to(input);
+  }
+
+  #[rstest(input, expected,
+    case("  3   high  SUN 20 ad ", Ok(date(20, 4, 3))),
+    case("3 High Sun 520", Ok(date(520, 4, 3))),
+    case("3 High Sun 20 AC", Ok(date(520, 4, 3))),
+    case("520-4-3", Ok(date(520, 4, 3))),
+    case("-12-1-1", Ok(date(-12, 1, 1))),
+    case("", Err(DateError::Malformed)),
+    case("High Sun 20 AD", Err(DateError::Malformed)),
+    case("3 20 AD", Err(DateError::Malformed)),
+    case("3 AD", Err(DateError::Malformed)),
+    case("3 High Sun AD", Err(DateError::Malformed)),
+    case("3 High Sun twenty", Err(DateError::Malformed)),
+    case("1-2", Err(DateError::Malformed)),
+    case("3 Midsummer 20 AD", Err(DateError::UnknownMonth)),
+    case("3 High Sun 20 XY", Err(DateError::UnknownEra)),
+    case("3 High Sun 0 AD", Err(DateError::YearOutsideEra)),
+    case("3 High Sun 501 AD", Err(DateError::YearOutsideEra)),
+    case("46 High Sun 20 AD", Err(DateError::DayOutOfRange)),
+    case("46 High Sun 21 AD", Ok(date(21, 4, 46))),
+    case("0 High Sun 21 AD", Err(DateError::DayOutOfRange)),
+    case("1-9-1", Err(DateError::MonthOutOfRange)),
+    case("2000000000-1-1", Err(DateError::YearOutOfRange)),
+  )]
+  fn test_parse_date(input: &str, expected: Result<CalendarDate, DateError>) {
+    assert_that(&fantasy().parse_date(input)).is_equal_to(expected);
+  }
+
+  #[test]
+  fn test_compare_dates() {
+    let calendar = fantasy();
+
+    assert_that(&calendar.compare_dates(&date(500, 8, 45), &date(501, 1, 1)))
+      .is_equal_to(Ordering::Less);
+    assert_that(&calendar.compare_dates(&date(501, 1, 1), &date(501, 1, 1)))
+      .is_equal_to(Ordering::Equal);
+    assert_that(&calendar.compare_dates(&date(-1, 2, 1), &date(-1, 1, 45)))
+      .is_equal_to(Ordering::Greater);
+  }
+
+  #[test]
+  fn test_days_between() {
+    let calendar = gregorian();
+
+    assert_that(&calendar.days_between(&date(2020, 2, 1), &date(2020, 3, 1))).is_equal_to(29);
+    assert_that(&calendar.days_between(&date(2021, 3, 1), &date(2021, 2, 1))).is_equal_to(-28);
+  }
+
+  #[rstest(input, days, expected,
+    case(date(2020, 2, 28), 1, date(2020, 2, 29)),
+    case(date(2020, 2, 28), 2, date(2020, 3, 1)),
+    case(date(2020, 12, 31), 1, date(2021, 1, 1)),
+    case(date(2020, 3, 1), -1, date(2020, 2, 29)),
+    case(date(2000, 1, 1), 7_305, date(2020, 1, 1)),
+  )]
+  fn test_add_days(input: CalendarDate, days: i64, expected: CalendarDate) {
+    assert_that(&gregorian().add_days(&input, days))
+      .is_ok()
+      .is_equal_to(expected);
+  }
+
+  #[rstest(input, months, expected,
+    case(date(2020, 1, 31), 1, date(2020, 2, 29)),
+    case(date(2021, 1, 31), 1, date(2021, 2, 28)),
+    case(date(2020, 11, 15), 3, date(2021, 2, 15)),
+    case(date(2020, 1, 15), -1, date(2019, 12, 15)),
+    case(date(1, 1, 1), -13, date(-1, 12, 1)),
+  )]
+  fn test_add_months(input: CalendarDate, months: i64, expected: CalendarDate) {
+    assert_that(&gregorian().add_months(&input, months))
+      .is_ok()
+      .is_equal_to(expected);
+  }
+
+  #[rstest(input, years, expected,
+    case(date(2020, 2, 29), 1, date(2021, 2, 28)),
+    case(date(2020, 2, 29), 4, date(2024, 2, 29)),
+    case(date(2020, 6, 1), -2020, date(0, 6, 1)),
+  )]
+  fn test_add_years(input: CalendarDate, years: i64, expected: CalendarDate) {
+    assert_that(&gregorian().add_years(&input, years))
+      .is_ok()
+      .is_equal_to(expected);
+  }
+
+  #[test]
+  fn test_add_out_of_range() {
+    let calendar = gregorian();
+
+    assert_matches!(
+      calendar.add_years(&date(2020, 1, 1), MAX_YEAR),
+      Err(DateError::YearOutOfRange)
+    );
+    assert_matches!(
+      calendar.add_months(&date(2020, 1, 1), i64::MAX),
+      Err(DateError::YearOutOfRange)
+    );
+    assert_matches!(
+      calendar.add_days(&date(2020, 1, 1), i64::MAX),
+      Err(DateError::YearOutOfRange)
+    );
+  }
+}
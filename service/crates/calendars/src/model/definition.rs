@@ -0,0 +1,454 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// The most months that a calendar may have in a year
+pub const MAX_MONTHS: usize = 100;
+
+/// The most days that a month may have, including any leap days
+pub const MAX_MONTH_DAYS: u32 = 1000;
+
+/// The most weekdays that a calendar may have
+pub const MAX_WEEKDAYS: usize = 100;
+
+/// The furthest absolute year from year 1 that a date may be in, in either direction
+pub const MAX_YEAR: i64 = 1_000_000_000;
+
+/// A single month of a calendar
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Month {
+  pub name: String,
+  /// How many days the month has in a common year
+  pub days: u32,
+  /// How many extra days the month gains in a leap year
+  #[serde(default)]
+  pub leap_days: u32,
+}
+
+/// The rule for which years of a calendar are leap years.
+///
+/// This works the same way as the Gregorian calendar. A year is a leap year if it's a multiple of
+/// `every`, except that multiples of `except_every` are not, unless they are also multiples of
+/// `unless_every`. The Gregorian calendar itself is `every: 4, except_every: 100, unless_every: 400`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeapRule {
+  pub every: u32,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub except_every: Option<u32>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub unless_every: Option<u32>,
+}
+
+/// A named era of a calendar. Years are counted from 1 at the start of each era, and each era
+/// runs until the next one starts
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Era {
+  pub name: String,
+  /// The short name of the era, as written after the year of a date. E.g. "TA" for "Third Age"
+  pub abbreviation: String,
+  /// The absolute year that the era starts in
+  pub start_year: i64,
+}
+
+/// The definition of how a calendar counts days, months and years.
+///
+/// Every date is counted from the first day of the first month of absolute year 1. Years before
+/// that are year 0, year -1, and so on.
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarDefinition {
+  /// The months of each year, in order
+  #[serde(default)]
+  pub months: Vec<Month>,
+  /// The days of the week, in order. This may be empty if the calendar has no weeks
+  #[serde(default)]
+  pub weekdays: Vec<String>,
+  /// The rule for which years are leap years, or `None` if the calendar never has leap years
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub leap_rule: Option<LeapRule>,
+  /// The eras that years are counted in, in order of when they start
+  #[serde(default)]
+  pub eras: Vec<Era>,
+  /// The index into `weekdays` of the weekday that the very first day of year 1 falls on
+  #[serde(default)]
+  pub epoch_weekday: u32,
+}
+
+/// Enumeration of the problems that a calendar definition can have
+#[derive(Debug, PartialEq, Clone, thiserror::Error)]
+pub enum DefinitionError {
+  #[error("The calendar has no months")]
+  NoMonths,
+  #[error("The calendar has too many months")]
+  TooManyMonths,
+  #[error("Month {0} has no name")]
+  MonthName(usize),
+  #[error("Month {0} has the same name as an earlier month")]
+  DuplicateMonthName(usize),
+  #[error("Month {0} has an invalid number of days")]
+  MonthLength(usize),
+  #[error("The calendar has too many weekdays")]
+  TooManyWeekdays,
+  #[error("Weekday {0} has no name")]
+  WeekdayName(usize),
+  #[error("The epoch weekday is not one of the weekdays")]
+  EpochWeekday,
+  #[error("The leap rule is invalid")]
+  LeapRule,
+  #[error("Era {0} has no name")]
+  EraName(usize),
+  #[error("Era {0} has an invalid abbreviation")]
+  EraAbbreviation(usize),
+  #[error("Era {0} has the same abbreviation as an earlier era")]
+  DuplicateEraAbbreviation(usize),
+  #[error("Era {0} starts too far from year 1")]
+  EraStartYear(usize),
+  #[error("Era {0} doesn't start after the era before it")]
+  EraOrder(usize),
+}
+
+impl LeapRule {
+  /// Check if the given absolute year is a leap year under this rule
+  pub fn is_leap_year(&self, year: i64) -> bool {
+    let is_multiple =
+      |n: Option<u32>| n.map_or(false, |n| n != 0 && year.rem_euclid(i64::from(n)) == 0);
+
+    is_multiple(Some(self.every))
+      && (!is_multiple(self.except_every) || is_multiple(self.unless_every))
+  }
+
+  /// Count the leap years between year 1 and the given absolute year, inclusive. For years before
+  /// year 1 this is instead the negative of the number of leap years after the given year, up to
+  /// and including year 0
+  pub(crate) fn leap_years_through(&self, year: i64) -> i64 {
+    let multiples = |n: u32| year.div_euclid(i64::from(n));
+
+    multiples(self.every) - self.except_every.map_or(0, multiples)
+      + self.unless_every.map_or(0, multiples)
+  }
+
+  /// The average proportion of years that are leap years
+  pub(crate) fn density(&self) -> f64 {
+    let fraction = |n: Option<u32>| n.map_or(0.0, |n| 1.0 / f64::from(n));
+
+    fraction(Some(self.every)) - fraction(self.except_every) + fraction(self.unless_every)
+  }
+
+  /// Check that the rule is one that we can work with
+  fn is_valid(&self) -> bool {
+    // Every exception has to be a subset of the years before it, or else the leap years can't be
+    // counted without looking at every single year
+    match (self.except_every, self.unless_every) {
+      _ if self.every == 0 => false,
+      (None, None) => true,
+      (None, Some(_)) => false,
+      (Some(except), unless) => {
+        except != 0
+          && except % self.every == 0
+          && unless.map_or(true, |unless| unless != 0 && unless % except == 0)
+      }
+    }
+  }
+}
+
+impl CalendarDefinition {
+  /// Check the definition for any problems that stop it from being used
+  ///
+  /// # Returns
+  /// Every problem found with the definition
+  pub fn validate(&self) -> Vec<DefinitionError> {
+    let mut errors = vec![];
+
+    if self.months.is_empty() {
+      errors.push(DefinitionError::NoMonths);
+    } else if self.months.len() > MAX_MONTHS {
+      errors.push(DefinitionError::TooManyMonths);
+    }
+
+    let mut month_names = HashSet::new();
+    for (index, month) in self.months.iter().enumerate() {
+      let name = normalize_name(&month.name);
+      if name.is_empty() {
+        errors.push(DefinitionError::MonthName(index));
+      } else if !month_names.insert(name) {
+        errors.push(DefinitionError::DuplicateMonthName(index));
+      }
+
+      if month.days == 0 || month.days.saturating_add(month.leap_days) > MAX_MONTH_DAYS {
+        errors.push(DefinitionError::MonthLength(index));
+      }
+    }
+
+    if self.weekdays.len() > MAX_WEEKDAYS {
+      errors.push(DefinitionError::TooManyWeekdays);
+    }
+    for (index, weekday) in self.weekdays.iter().enumerate() {
+      if weekday.trim().is_empty() {
+        errors.push(DefinitionError::WeekdayName(index));
+      }
+    }
+    // A calendar without weeks has nothing for the epoch to fall on
+    let weekdays = self.weekdays.len().max(1);
+    if self.epoch_weekday as usize >= weekdays {
+      errors.push(DefinitionError::EpochWeekday);
+    }
+
+    if let Some(leap_rule) = &self.leap_rule {
+      if !leap_rule.is_valid() {
+        errors.push(DefinitionError::LeapRule);
+      }
+    }
+
+    let mut abbreviations = HashSet::new();
+    for (index, era) in self.eras.iter().enumerate() {
+      if era.name.trim().is_empty() {
+        errors.push(DefinitionError::EraName(index));
+      }
+
+      // Abbreviations are written as a single word after the year, so they can't look like one
+      let abbreviation = era.abbreviation.trim();
+      if abbreviation.is_empty()
+        || abbreviation.contains(char::is_whitespace)
+        || abbreviation.parse::<i64>().is_ok()
+      {
+        errors.push(DefinitionError::EraAbbreviation(index));
+      } else if !abbreviations.insert(abbreviation.to_uppercase()) {
+        errors.push(DefinitionError::DuplicateEraAbbreviation(index));
+      }
+
+      if era.start_year.abs() > MAX_YEAR {
+        errors.push(DefinitionError::EraStartYear(index));
+      } else if index > 0 && era.start_year <= self.eras[index - 1].start_year {
+        errors.push(DefinitionError::EraOrder(index));
+      }
+    }
+
+    errors
+  }
+
+  /// Check if the given absolute year is a leap year
+  pub fn is_leap_year(&self, year: i64) -> bool {
+    self
+      .leap_rule
+      .as_ref()
+      .map_or(false, |leap_rule| leap_rule.is_leap_year(year))
+  }
+
+  /// Find the month with the given name, ignoring case and spacing
+  ///
+  /// # Returns
+  /// The index of the month, or `None` if there is no such month
+  pub(crate) fn find_month(&self, name: &str) -> Option<usize> {
+    let name = normalize_name(name);
+    self
+      .months
+      .iter()
+      .position(|month| normalize_name(&month.name) == name)
+  }
+
+  /// Find the era with the given abbreviation, ignoring case
+  ///
+  /// # Returns
+  /// The index of the era, or `None` if there is no such era
+  pub(crate) fn find_era(&self, abbreviation: &str) -> Option<usize> {
+    self
+      .eras
+      .iter()
+      .position(|era| era.abbreviation.trim().eq_ignore_ascii_case(abbreviation))
+  }
+}
+
+/// Normalize the name of a month so that names can be compared regardless of case and spacing
+fn normalize_name(name: &str) -> String {
+  name
+    .split_whitespace()
+    .collect::<Vec<_>>()
+    .join(" ")
+    .to_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use rstest::rstest;
+  use spectral::prelude::*;
+
+  fn month(name: &str, days: u32) -> Month {
+    Month {
+      name: name.to_owned(),
+      days,
+      leap_days: 0,
+    }
+  }
+
+  fn era(name: &str, abbreviation: &str, start_year: i64) -> Era {
+    Era {
+      name: name.to_owned(),
+      abbreviation: abbreviation.to_owned(),
+      start_year,
+    }
+  }
+
+  #[rstest(every, except_every, unless_every, year, expected,
+    case(4, None, None, 4, true),
+    case(4, None, None, 5, false),
+    case(4, None, None, 0, true),
+    case(4, None, None, -4, true),
+    case(4, Some(100), Some(400), 1900, false),
+    case(4, Some(100), Some(400), 2000, true),
+    case(4, Some(100), Some(400), 2020, true),
+    case(4, Some(100), Some(400), 2021, false),
+    case(4, Some(100), None, 2000, false),
+  )]
+  fn test_is_leap_year(
+    every: u32,
+    except_every: Option<u32>,
+    unless_every: Option<u32>,
+    year: i64,
+    expected: bool,
+  ) {
+    let rule = LeapRule {
+      every,
+      except_every,
+      unless_every,
+    };
+
+    assert_that(&rule.is_leap_year(year)).is_equal_to(expected);
+  }
+
+  #[rstest(year,
+    case(-401),
+    case(-1),
+    case(0),
+    case(1),
+    case(4),
+    case(399),
+    case(2020),
+  )]
+  fn test_leap_years_through(year: i64) {
+    let rule = LeapRule {
+      every: 4,
+      except_every: Some(100),
+      unless_every: Some(400),
+    };
+
+    let expected = if year >= 0 {
+      (1..=year).filter(|y| rule.is_leap_year(*y)).count() as i64
+    } else {
+      -(((year + 1)..=0).filter(|y| rule.is_leap_year(*y)).count() as i64)
+    };
+    assert_that(&rule.leap_years_through(year)).is_equal_to(expected);
+  }
+
+  #[test]
+  fn test_validate_valid() {
+    let definition = CalendarDefinition {
+      months: vec![month("Afteryule", 30), month("Solmath", 30)],
+      weekdays: vec!["Sterday".to_owned(), "Sunday".to_owned()],
+      leap_rule: Some(LeapRule {
+        every: 4,
+        except_every: Some(100),
+        unless_every: None,
+      }),
+      eras: vec![era("Second Age", "SA", 1), era("Third Age", "TA", 3442)],
+      epoch_weekday: 1,
+    };
+
+    assert_that(&definition.validate()).is_empty();
+  }
+
+  #[test]
+  fn test_validate_no_months() {
+    let definition = CalendarDefinition::default();
+
+    assert_that(&definition.validate()).is_equal_to(vec![DefinitionError::NoMonths]);
+  }
+
+  #[test]
+  fn test_validate_invalid() {
+    let definition = CalendarDefinition {
+      months: vec![
+        month("Afteryule", 30),
+        month("  ", 30),
+        month(" afteryule", 30),
+        month("Rethe", 0),
+        Month {
+          name: "Astron".to_owned(),
+          days: 999,
+          leap_days: 2,
+        },
+      ],
+      weekdays: vec!["Sterday".to_owned(), "".to_owned()],
+      leap_rule: Some(LeapRule {
+        every: 4,
+        except_every: Some(10),
+        unless_every: None,
+      }),
+      eras: vec![
+        era("Second Age", "SA", 1),
+        era("", "Third Age", 3442),
+        era("Fourth Age", "sa", 3400),
+        era("Fifth Age", "5", 4000),
+      ],
+      epoch_weekday: 2,
+    };
+
+    assert_that(&definition.validate()).is_equal_to(vec![
+      DefinitionError::MonthName(1),
+      DefinitionError::DuplicateMonthName(2),
+      DefinitionError::MonthLength(3),
+      DefinitionError::MonthLength(4),
+      DefinitionError::WeekdayName(1),
+      DefinitionError::EpochWeekday,
+      DefinitionError::LeapRule,
+      DefinitionError::EraName(1),
+      DefinitionError::EraAbbreviation(1),
+      DefinitionError::DuplicateEraAbbreviation(2),
+      DefinitionError::EraOrder(2),
+      DefinitionError::EraAbbreviation(3),
+    ]);
+  }
+
+  #[rstest(
+    every,
+    except_every,
+    unless_every,
+    expected,
+    case(4, None, None, true),
+    case(0, None, None, false),
+    case(4, Some(100), Some(400), true),
+    case(4, Some(0), None, false),
+    case(4, Some(10), None, false),
+    case(4, None, Some(400), false),
+    case(4, Some(100), Some(250), false)
+  )]
+  fn test_leap_rule_is_valid(
+    every: u32,
+    except_every: Option<u32>,
+    unless_every: Option<u32>,
+    expected: bool,
+  ) {
+    let rule = LeapRule {
+      every,
+      except_every,
+      unless_every,
+    };
+
+    assert_that(&rule.is_valid()).is_equal_to(expected);
+  }
+
+  #[test]
+  fn test_deserialize_minimal() {
+    let definition: CalendarDefinition = serde_json::from_value(serde_json::json!({
+      "months": [{ "name": "Afteryule", "days": 30 }]
+    }))
+    .unwrap();
+
+    assert_that(&definition).is_equal_to(CalendarDefinition {
+      months: vec![month("Afteryule", 30)],
+      ..Default::default()
+    });
+  }
+}
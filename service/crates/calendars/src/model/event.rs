@@ -0,0 +1,48 @@
+use crate::{CalendarDate, CalendarID, EventID};
+use universe_entity::Identity;
+use universe_worlds::WorldID;
+
+/// Struct to represent the data about a single event on the timeline of a calendar
+#[derive(Debug, PartialEq, Clone)]
+pub struct EventData {
+  /// The world that the calendar belongs to
+  pub world: WorldID,
+  /// The calendar that the event is dated in
+  pub calendar: CalendarID,
+  pub title: String,
+  pub description: String,
+  /// When the event happened
+  pub date: CalendarDate,
+}
+
+/// Type to represent the entity that is a persisted event record
+#[derive(Debug, PartialEq, Clone)]
+pub struct EventEntity {
+  pub identity: Identity<EventID>,
+  pub data: EventData,
+}
+
+#[cfg(test)]
+impl From<universe_testdata::TimelineEvent> for EventEntity {
+  fn from(event: universe_testdata::TimelineEvent) -> EventEntity {
+    EventEntity {
+      identity: Identity {
+        id: EventID::from_uuid(event.event_id),
+        version: event.version,
+        created: event.created,
+        updated: event.updated,
+      },
+      data: EventData {
+        world: WorldID::from_uuid(event.world_id),
+        calendar: CalendarID::from_uuid(event.calendar_id),
+        title: event.title.clone(),
+        description: event.description.clone(),
+        date: CalendarDate {
+          year: event.year,
+          month: event.month as u32,
+          day: event.day as u32,
+        },
+      },
+    }
+  }
+}
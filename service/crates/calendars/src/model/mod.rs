@@ -0,0 +1,15 @@
+mod calendar;
+mod calendar_id;
+mod calendar_import;
+mod date;
+mod definition;
+mod event;
+mod event_id;
+
+pub use calendar::*;
+pub use calendar_id::*;
+pub use calendar_import::*;
+pub use date::*;
+pub use definition::*;
+pub use event::*;
+pub use event_id::*;
@@ -0,0 +1,565 @@
+use super::{repository::*, service::*};
+use crate::model::*;
+use tracing::{info, warn};
+use universe_entity::{IdMap, Page, Pagination};
+use universe_worlds::WorldID;
+
+/// The Calendar Service to allow interactions with calendar entities
+pub struct CalendarServiceImpl<Repo> {
+  repository: Repo,
+}
+
+/// Create a new Calendar Service
+///
+/// # Arguments
+/// * `repository` The repository to load and save calendars with
+///
+/// # Returns
+/// The Calendar Service
+pub fn new_calendar_service<Repo: CalendarRepository + Send + Sync>(
+  repository: Repo,
+) -> impl CalendarService {
+  CalendarServiceImpl { repository }
+}
+
+impl<Repo: CalendarRepository + Send + Sync> CalendarService for CalendarServiceImpl<Repo> {
+  /// Retrieve the calendar that has the given unique ID, as long as it belongs to the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the calendar belongs to
+  /// * `calendar_id` The ID of the calendar to retrieve
+  ///
+  /// # Returns
+  /// The calendar, or `None` if it wasn't found
+  fn get_calendar_by_id(
+    &self,
+    world_id: &WorldID,
+    calendar_id: &CalendarID,
+  ) -> Option<CalendarEntity> {
+    let calendar = self.repository.get_calendar_by_id(world_id, calendar_id);
+
+    if calendar.is_none() {
+      warn!(
+        "No calendar found with ID {} in world {}",
+        calendar_id, world_id
+      );
+    }
+
+    calendar
+  }
+
+  /// Retrieve a page of the calendars of a world, sorted by name
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the calendars of
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of calendars
+  fn list_calendars(&self, world_id: &WorldID, pagination: Pagination) -> Page<CalendarEntity> {
+    self.repository.list_calendars(world_id, pagination)
+  }
+
+  /// Retrieve every calendar of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the calendars of
+  ///
+  /// # Returns
+  /// The calendars
+  fn list_all_calendars(&self, world_id: &WorldID) -> Vec<CalendarEntity> {
+    self.repository.list_all_calendars(world_id)
+  }
+
+  /// Create a new calendar
+  ///
+  /// # Arguments
+  /// * `calendar` The calendar data to create the calendar from
+  ///
+  /// # Returns
+  /// The calendar that was persisted
+  fn create_calendar(&self, calendar: CalendarData) -> Result<CalendarEntity, CreateCalendarError> {
+    let errors = calendar.definition.validate();
+    if !errors.is_empty() {
+      warn!("Calendar definition is invalid: {:?}", errors);
+      return Err(CreateCalendarError::ValidationError(errors));
+    }
+
+    let created = self.repository.create_calendar(calendar)?;
+    Ok(created)
+  }
+
+  /// Update an existing calendar.
+  ///
+  /// This will load the calendar by ID, and then call a provided callback to mutate the calendar
+  /// before persisting the changes back to the database. Events keep the same dates when their
+  /// calendar changes, so the change is refused if any of those dates no longer exist in it.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the calendar belongs to
+  /// * `calendar_id` The ID of the calendar to update
+  /// * `updater` The callback to mutate the calendar with
+  ///
+  /// # Returns
+  /// The newly updated calendar
+  fn update_calendar(
+    &self,
+    world_id: &WorldID,
+    calendar_id: &CalendarID,
+    updater: &mut dyn FnMut(CalendarData) -> Result<CalendarData, Box<dyn std::error::Error>>,
+  ) -> Result<CalendarEntity, UpdateCalendarError> {
+    let calendar = self
+      .get_calendar_by_id(world_id, calendar_id)
+      .ok_or(UpdateCalendarError::UnknownCalendar)?;
+
+    let world = calendar.data.world.clone();
+    let updated = updater(calendar.data).map_err(UpdateCalendarError::UpdateError)?;
+    // Calendars can't be moved between worlds
+    let updated = CalendarData { world, ..updated };
+
+    let errors = updated.definition.validate();
+    if !errors.is_empty() {
+      warn!("Calendar definition is invalid: {:?}", errors);
+      return Err(UpdateCalendarError::ValidationError(errors));
+    }
+
+    // The same date can fall on a different day in the new calendar, so the day numbers that
+    // events are ordered by have to be worked out again
+    let mut day_numbers = vec![];
+    let mut invalid = vec![];
+    for event in self.repository.list_all_events(world_id, calendar_id) {
+      match updated.definition.check_date(&event.data.date) {
+        Ok(()) => day_numbers.push((
+          event.identity.id,
+          updated.definition.day_number(&event.data.date),
+        )),
+        Err(_) => invalid.push(event.identity.id),
+      }
+    }
+    if !invalid.is_empty() {
+      warn!(
+        "Calendar {} would invalidate the dates of events {:?}",
+        calendar_id, invalid
+      );
+      return Err(UpdateCalendarError::EventDatesInvalid(invalid));
+    }
+
+    let saved = self.repository.update_calendar(
+      CalendarEntity {
+        identity: calendar.identity,
+        data: updated,
+      },
+      &day_numbers,
+    )?;
+    Ok(saved)
+  }
+
+  /// Delete a calendar, along with every event of it
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the calendar belongs to
+  /// * `calendar_id` The ID of the calendar to delete
+  fn delete_calendar(
+    &self,
+    world_id: &WorldID,
+    calendar_id: &CalendarID,
+  ) -> Result<(), DeleteCalendarError> {
+    self.repository.delete_calendar(world_id, calendar_id)?;
+    Ok(())
+  }
+
+  /// Create a set of calendars in a world, along with the events dated in them
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to create the calendars in
+  /// * `calendars` The calendars to create
+  /// * `ids` The map to record the new IDs of the calendars in
+  ///
+  /// # Returns
+  /// The calendars that were created
+  fn import_calendars(
+    &self,
+    world_id: &WorldID,
+    calendars: Vec<CalendarImport>,
+    ids: &mut IdMap,
+  ) -> Result<Vec<CalendarEntity>, ImportCalendarsError> {
+    let mut created = Vec::with_capacity(calendars.len());
+
+    for calendar in calendars {
+      let CalendarImport {
+        key,
+        name,
+        definition,
+        events,
+      } = calendar;
+
+      let result = self
+        .create_calendar(CalendarData {
+          world: world_id.clone(),
+          name,
+          definition,
+        })
+        .map_err(|e| match e {
+          CreateCalendarError::ValidationError(errors) => {
+            ImportCalendarsError::InvalidDefinition(key.clone(), errors)
+          }
+          CreateCalendarError::UnknownWorld => ImportCalendarsError::UnknownWorld,
+          _ => ImportCalendarsError::UnknownError,
+        })?;
+      ids.insert("calendar", &key, &result.identity.id);
+
+      let definition = &result.data.definition;
+      for event in events {
+        definition
+          .check_date(&event.date)
+          .map_err(|e| ImportCalendarsError::InvalidDate(key.clone(), e))?;
+
+        self
+          .repository
+          .create_event(
+            EventData {
+              world: world_id.clone(),
+              calendar: result.identity.id.clone(),
+              title: event.title,
+              description: event.description,
+              date: event.date,
+            },
+            definition.day_number(&event.date),
+          )
+          .map_err(|e| {
+            warn!("Error creating event: {}", e);
+            ImportCalendarsError::UnknownError
+          })?;
+      }
+
+      created.push(result);
+    }
+    info!(
+      "Imported {} calendars into world {}",
+      created.len(),
+      world_id
+    );
+
+    Ok(created)
+  }
+
+  /// Copy every calendar of one world into another, along with the events dated in them
+  ///
+  /// # Arguments
+  /// * `from` The ID of the world to copy the calendars from
+  /// * `to` The ID of the world to copy the calendars into
+  /// * `ids` The map to record the new IDs of the calendars in
+  ///
+  /// # Returns
+  /// The calendars that were created
+  fn copy_calendars(
+    &self,
+    from: &WorldID,
+    to: &WorldID,
+    ids: &mut IdMap,
+  ) -> Result<Vec<CalendarEntity>, ImportCalendarsError> {
+    let calendars = self
+      .list_all_calendars(from)
+      .into_iter()
+      .map(|calendar| {
+        let events = self
+          .repository
+          .list_all_events(from, &calendar.identity.id)
+          .into_iter()
+          .map(|event| EventImport {
+            title: event.data.title,
+            description: event.data.description,
+            date: event.data.date,
+          })
+          .collect();
+
+        CalendarImport {
+          key: calendar.identity.id.to_string(),
+          name: calendar.data.name,
+          definition: calendar.data.definition,
+          events,
+        }
+      })
+      .collect();
+
+    self.import_calendars(to, calendars, ids)
+  }
+}
+
+impl From<PersistCalendarError> for CreateCalendarError {
+  fn from(e: PersistCalendarError) -> Self {
+    warn!("Error creating calendar: {}", e);
+    match e {
+      PersistCalendarError::WorldNotFound => CreateCalendarError::UnknownWorld,
+      _ => CreateCalendarError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistCalendarError> for UpdateCalendarError {
+  fn from(e: PersistCalendarError) -> Self {
+    warn!("Error updating calendar: {}", e);
+    match e {
+      PersistCalendarError::CalendarNotFound => UpdateCalendarError::UnknownCalendar,
+      PersistCalendarError::OptimisticLockFailure => UpdateCalendarError::OptimisticLockFailure,
+      _ => UpdateCalendarError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistCalendarError> for DeleteCalendarError {
+  fn from(e: PersistCalendarError) -> Self {
+    warn!("Error deleting calendar: {}", e);
+    match e {
+      PersistCalendarError::CalendarNotFound => DeleteCalendarError::UnknownCalendar,
+      _ => DeleteCalendarError::UnknownError,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use assert_matches::assert_matches;
+  use mockall::predicate::*;
+  use spectral::prelude::*;
+  use test_env_log::test;
+  use universe_entity::Identity;
+
+  fn definition(days: u32) -> CalendarDefinition {
+    CalendarDefinition {
+      months: vec![
+        Month {
+          name: "Frost".to_owned(),
+          days,
+          leap_days: 0,
+        },
+        Month {
+          name: "Thaw".to_owned(),
+          days,
+          leap_days: 0,
+        },
+      ],
+      ..CalendarDefinition::default()
+    }
+  }
+
+  fn calendar() -> CalendarEntity {
+    CalendarEntity {
+      identity: Identity::default(),
+      data: CalendarData {
+        world: Default::default(),
+        name: "Reckoning".to_owned(),
+        definition: definition(30),
+      },
+    }
+  }
+
+  fn event(calendar: &CalendarEntity, month: u32, day: u32) -> EventEntity {
+    EventEntity {
+      identity: Identity::default(),
+      data: EventData {
+        world: calendar.data.world.clone(),
+        calendar: calendar.identity.id.clone(),
+        title: "Something happened".to_owned(),
+        description: "".to_owned(),
+        date: CalendarDate {
+          year: 1,
+          month,
+          day,
+        },
+      },
+    }
+  }
+
+  #[test]
+  fn test_create_invalid_calendar() {
+    let mut repository = MockCalendarRepository::new();
+    repository.expect_create_calendar().never();
+
+    let service = new_calendar_service(repository);
+    let result = service.create_calendar(CalendarData {
+      definition: CalendarDefinition::default(),
+      ..calendar().data
+    });
+
+    assert_that(&result)
+      .is_err()
+      .is_equal_to(CreateCalendarError::ValidationError(vec![
+        DefinitionError::NoMonths,
+      ]));
+  }
+
+  #[test]
+  fn test_update_calendar_recalculates_day_numbers() {
+    let existing = calendar();
+    let first = event(&existing, 1, 10);
+    let second = event(&existing, 2, 5);
+    let expected = vec![
+      (first.identity.id.clone(), 9),
+      (second.identity.id.clone(), 44),
+    ];
+
+    let mut repository = MockCalendarRepository::new();
+    let returned = existing.clone();
+    repository
+      .expect_get_calendar_by_id()
+      .times(1)
+      .returning(move |_, _| Some(returned.clone()));
+    repository
+      .expect_list_all_events()
+      .times(1)
+      .return_const(vec![first, second]);
+    repository
+      .expect_update_calendar()
+      .withf(move |calendar, day_numbers| {
+        calendar.data.definition == definition(40) && day_numbers == expected.as_slice()
+      })
+      .times(1)
+      .returning(|calendar, _| Ok(calendar));
+
+    let service = new_calendar_service(repository);
+    let result =
+      service.update_calendar(&existing.data.world, &existing.identity.id, &mut |data| {
+        Ok(CalendarData {
+          definition: definition(40),
+          ..data
+        })
+      });
+
+    assert_that(&result).is_ok();
+  }
+
+  #[test]
+  fn test_update_calendar_invalidating_events() {
+    let existing = calendar();
+    let fine = event(&existing, 1, 10);
+    let lost = event(&existing, 2, 25);
+    let lost_id = lost.identity.id.clone();
+
+    let mut repository = MockCalendarRepository::new();
+    let returned = existing.clone();
+    repository
+      .expect_get_calendar_by_id()
+      .times(1)
+      .returning(move |_, _| Some(returned.clone()));
+    repository
+      .expect_list_all_events()
+      .times(1)
+      .return_const(vec![fine, lost]);
+    repository.expect_update_calendar().never();
+
+    let service = new_calendar_service(repository);
+    let result =
+      service.update_calendar(&existing.data.world, &existing.identity.id, &mut |data| {
+        Ok(CalendarData {
+          definition: definition(20),
+          ..data
+        })
+      });
+
+    assert_matches!(result, Err(UpdateCalendarError::EventDatesInvalid(ids)) => {
+      assert_that(&ids).is_equal_to(vec![lost_id]);
+    });
+  }
+
+  #[test]
+  fn test_delete_unknown_calendar() {
+    let mut repository = MockCalendarRepository::new();
+    repository
+      .expect_delete_calendar()
+      .with(always(), always())
+      .times(1)
+      .returning(|_, _| Err(PersistCalendarError::CalendarNotFound));
+
+    let service = new_calendar_service(repository);
+    let result = service.delete_calendar(&Default::default(), &Default::default());
+
+    assert_that(&result)
+      .is_err()
+      .is_equal_to(DeleteCalendarError::UnknownCalendar);
+  }
+
+  fn calendar_import(month: u32, day: u32) -> CalendarImport {
+    CalendarImport {
+      key: "reckoning".to_owned(),
+      name: "Reckoning".to_owned(),
+      definition: definition(30),
+      events: vec![EventImport {
+        title: "Something happened".to_owned(),
+        description: "".to_owned(),
+        date: CalendarDate {
+          year: 1,
+          month,
+          day,
+        },
+      }],
+    }
+  }
+
+  #[test]
+  fn test_import_calendars_with_events() {
+    let world_id = WorldID::default();
+    let mut ids = IdMap::default();
+
+    let mut repository = MockCalendarRepository::new();
+    repository
+      .expect_create_calendar()
+      .times(1)
+      .returning(|data| {
+        Ok(CalendarEntity {
+          identity: Identity::default(),
+          data,
+        })
+      });
+    repository
+      .expect_create_event()
+      .withf(|event, day_number| event.title == "Something happened" && *day_number == 44)
+      .times(1)
+      .returning(|data, _| {
+        Ok(EventEntity {
+          identity: Identity::default(),
+          data,
+        })
+      });
+
+    let service = new_calendar_service(repository);
+    let imported = service
+      .import_calendars(&world_id, vec![calendar_import(2, 15)], &mut ids)
+      .unwrap();
+
+    assert_that(&imported).has_length(1);
+    assert_that(&imported[0].data.world).is_equal_to(&world_id);
+    assert_that(&ids.get("calendar", "reckoning"))
+      .is_equal_to(Some(imported[0].identity.id.to_string().as_str()));
+  }
+
+  #[test]
+  fn test_import_calendars_invalid_event_date() {
+    let mut repository = MockCalendarRepository::new();
+    repository
+      .expect_create_calendar()
+      .times(1)
+      .returning(|data| {
+        Ok(CalendarEntity {
+          identity: Identity::default(),
+          data,
+        })
+      });
+    repository.expect_create_event().never();
+
+    let service = new_calendar_service(repository);
+    let imported = service.import_calendars(
+      &Default::default(),
+      vec![calendar_import(1, 31)],
+      &mut IdMap::default(),
+    );
+
+    assert_that(&imported)
+      .is_err()
+      .is_equal_to(ImportCalendarsError::InvalidDate(
+        "reckoning".to_owned(),
+        DateError::DayOutOfRange,
+      ));
+  }
+}
@@ -0,0 +1,8 @@
+mod implementation;
+pub mod repository;
+mod service;
+mod timeline;
+
+pub use implementation::*;
+pub use service::*;
+pub use timeline::*;
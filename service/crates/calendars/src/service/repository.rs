@@ -0,0 +1,181 @@
+use crate::model::*;
+#[cfg(test)]
+use mockall::automock;
+use universe_entity::{Page, Pagination};
+use universe_worlds::WorldID;
+
+/// Repository that describes how to access calendar data
+#[cfg_attr(test, automock)]
+pub trait CalendarRepository {
+  /// Retrieve the calendar that has the given unique ID, as long as it belongs to the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the calendar belongs to
+  /// * `calendar_id` The ID of the calendar to retrieve
+  ///
+  /// # Returns
+  /// The calendar, or `None` if it wasn't found
+  fn get_calendar_by_id(
+    &self,
+    world_id: &WorldID,
+    calendar_id: &CalendarID,
+  ) -> Option<CalendarEntity>;
+
+  /// Retrieve a page of the calendars of a world, sorted by name
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the calendars of
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of calendars
+  fn list_calendars(&self, world_id: &WorldID, pagination: Pagination) -> Page<CalendarEntity>;
+
+  /// Retrieve every calendar of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the calendars of
+  ///
+  /// # Returns
+  /// The calendars
+  fn list_all_calendars(&self, world_id: &WorldID) -> Vec<CalendarEntity>;
+
+  /// Create a new calendar record in the data store
+  ///
+  /// # Arguments
+  /// * `calendar` The calendar details to persist to the data store
+  ///
+  /// # Returns
+  /// The calendar that was persisted
+  fn create_calendar(&self, calendar: CalendarData)
+    -> Result<CalendarEntity, PersistCalendarError>;
+
+  /// Update an existing calendar record in the data store, along with the day numbers of its
+  /// events in the updated calendar
+  ///
+  /// # Arguments
+  /// * `calendar` The calendar details to persist to the data store
+  /// * `day_numbers` The new day number of each event of the calendar
+  ///
+  /// # Returns
+  /// The calendar that was persisted
+  fn update_calendar(
+    &self,
+    calendar: CalendarEntity,
+    day_numbers: &[(EventID, i64)],
+  ) -> Result<CalendarEntity, PersistCalendarError>;
+
+  /// Delete an existing calendar record from the data store, along with every event of it
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the calendar belongs to
+  /// * `calendar_id` The ID of the calendar to delete
+  fn delete_calendar(
+    &self,
+    world_id: &WorldID,
+    calendar_id: &CalendarID,
+  ) -> Result<(), PersistCalendarError>;
+
+  /// Retrieve the event that has the given unique ID, as long as it is dated in the given
+  /// calendar
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the calendar belongs to
+  /// * `calendar_id` The ID of the calendar that the event is dated in
+  /// * `event_id` The ID of the event to retrieve
+  ///
+  /// # Returns
+  /// The event, or `None` if it wasn't found
+  fn get_event_by_id(
+    &self,
+    world_id: &WorldID,
+    calendar_id: &CalendarID,
+    event_id: &EventID,
+  ) -> Option<EventEntity>;
+
+  /// Retrieve a page of the events of a calendar, in the order that they happened
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the calendar belongs to
+  /// * `calendar_id` The ID of the calendar to list the events of
+  /// * `from` The day number of the earliest events to list, if any
+  /// * `to` The day number of the latest events to list, if any
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of events
+  fn list_events(
+    &self,
+    world_id: &WorldID,
+    calendar_id: &CalendarID,
+    from: Option<i64>,
+    to: Option<i64>,
+    pagination: Pagination,
+  ) -> Page<EventEntity>;
+
+  /// Retrieve every event of a calendar
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the calendar belongs to
+  /// * `calendar_id` The ID of the calendar to list the events of
+  ///
+  /// # Returns
+  /// The events
+  fn list_all_events(&self, world_id: &WorldID, calendar_id: &CalendarID) -> Vec<EventEntity>;
+
+  /// Create a new event record in the data store
+  ///
+  /// # Arguments
+  /// * `event` The event details to persist to the data store
+  /// * `day_number` The day number of the date of the event in its calendar
+  ///
+  /// # Returns
+  /// The event that was persisted
+  fn create_event(
+    &self,
+    event: EventData,
+    day_number: i64,
+  ) -> Result<EventEntity, PersistCalendarError>;
+
+  /// Update an existing event record in the data store
+  ///
+  /// # Arguments
+  /// * `event` The event details to persist to the data store
+  /// * `day_number` The day number of the date of the event in its calendar
+  ///
+  /// # Returns
+  /// The event that was persisted
+  fn update_event(
+    &self,
+    event: EventEntity,
+    day_number: i64,
+  ) -> Result<EventEntity, PersistCalendarError>;
+
+  /// Delete an existing event record from the data store
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the calendar belongs to
+  /// * `calendar_id` The ID of the calendar that the event is dated in
+  /// * `event_id` The ID of the event to delete
+  fn delete_event(
+    &self,
+    world_id: &WorldID,
+    calendar_id: &CalendarID,
+    event_id: &EventID,
+  ) -> Result<(), PersistCalendarError>;
+}
+
+/// Errors that can occur when persisting a calendar or event record
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum PersistCalendarError {
+  #[error("The world was not found")]
+  WorldNotFound,
+  #[error("The calendar was not found")]
+  CalendarNotFound,
+  #[error("The event was not found")]
+  EventNotFound,
+  #[error("The version of the record did not match")]
+  OptimisticLockFailure,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
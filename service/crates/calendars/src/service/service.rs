@@ -0,0 +1,161 @@
+use crate::model::*;
+use universe_entity::{IdMap, Page, Pagination};
+use universe_worlds::WorldID;
+
+/// The Calendar Service to allow interactions with calendar entities
+pub trait CalendarService: Send + Sync {
+  /// Retrieve the calendar that has the given unique ID, as long as it belongs to the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the calendar belongs to
+  /// * `calendar_id` The ID of the calendar to retrieve
+  ///
+  /// # Returns
+  /// The calendar, or `None` if it wasn't found
+  fn get_calendar_by_id(
+    &self,
+    world_id: &WorldID,
+    calendar_id: &CalendarID,
+  ) -> Option<CalendarEntity>;
+
+  /// Retrieve a page of the calendars of a world, sorted by name
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the calendars of
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of calendars
+  fn list_calendars(&self, world_id: &WorldID, pagination: Pagination) -> Page<CalendarEntity>;
+
+  /// Retrieve every calendar of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the calendars of
+  ///
+  /// # Returns
+  /// The calendars
+  fn list_all_calendars(&self, world_id: &WorldID) -> Vec<CalendarEntity>;
+
+  /// Create a new calendar
+  ///
+  /// # Arguments
+  /// * `calendar` The calendar data to create the calendar from
+  ///
+  /// # Returns
+  /// The calendar that was persisted
+  fn create_calendar(&self, calendar: CalendarData) -> Result<CalendarEntity, CreateCalendarError>;
+
+  /// Update an existing calendar.
+  ///
+  /// This will load the calendar by ID, and then call a provided callback to mutate the calendar
+  /// before persisting the changes back to the database. Events keep the same dates when their
+  /// calendar changes, so the change is refused if any of those dates no longer exist in it.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the calendar belongs to
+  /// * `calendar_id` The ID of the calendar to update
+  /// * `updater` The callback to mutate the calendar with
+  ///
+  /// # Returns
+  /// The newly updated calendar
+  fn update_calendar(
+    &self,
+    world_id: &WorldID,
+    calendar_id: &CalendarID,
+    updater: &mut dyn FnMut(CalendarData) -> Result<CalendarData, Box<dyn std::error::Error>>,
+  ) -> Result<CalendarEntity, UpdateCalendarError>;
+
+  /// Delete a calendar, along with every event of it
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the calendar belongs to
+  /// * `calendar_id` The ID of the calendar to delete
+  fn delete_calendar(
+    &self,
+    world_id: &WorldID,
+    calendar_id: &CalendarID,
+  ) -> Result<(), DeleteCalendarError>;
+
+  /// Create a set of calendars in a world, along with the events dated in them
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to create the calendars in
+  /// * `calendars` The calendars to create
+  /// * `ids` The map to record the new IDs of the calendars in
+  ///
+  /// # Returns
+  /// The calendars that were created
+  fn import_calendars(
+    &self,
+    world_id: &WorldID,
+    calendars: Vec<CalendarImport>,
+    ids: &mut IdMap,
+  ) -> Result<Vec<CalendarEntity>, ImportCalendarsError>;
+
+  /// Copy every calendar of one world into another, along with the events dated in them
+  ///
+  /// # Arguments
+  /// * `from` The ID of the world to copy the calendars from
+  /// * `to` The ID of the world to copy the calendars into
+  /// * `ids` The map to record the new IDs of the calendars in
+  ///
+  /// # Returns
+  /// The calendars that were created
+  fn copy_calendars(
+    &self,
+    from: &WorldID,
+    to: &WorldID,
+    ids: &mut IdMap,
+  ) -> Result<Vec<CalendarEntity>, ImportCalendarsError>;
+}
+
+/// Enumeration of reasons why we failed to create a new calendar
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum CreateCalendarError {
+  #[error("Calendar definition was invalid: {0:?}")]
+  ValidationError(Vec<DefinitionError>),
+  #[error("The world was not found")]
+  UnknownWorld,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to update an existing calendar
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateCalendarError {
+  #[error("Calendar definition was invalid: {0:?}")]
+  ValidationError(Vec<DefinitionError>),
+  #[error("Events have dates that don't exist in the updated calendar: {0:?}")]
+  EventDatesInvalid(Vec<EventID>),
+  #[error("The calendar was not found")]
+  UnknownCalendar,
+  #[error("The version of the calendar record did not match")]
+  OptimisticLockFailure,
+  #[error("An error occurred updating the calendar details: {0}")]
+  UpdateError(Box<dyn std::error::Error>),
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to delete a calendar
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum DeleteCalendarError {
+  #[error("The calendar was not found")]
+  UnknownCalendar,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to import a set of calendars
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum ImportCalendarsError {
+  #[error("The definition of calendar {0} was invalid: {1:?}")]
+  InvalidDefinition(String, Vec<DefinitionError>),
+  #[error("An event of calendar {0} has a date that doesn't exist in it: {1}")]
+  InvalidDate(String, DateError),
+  #[error("The world was not found")]
+  UnknownWorld,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
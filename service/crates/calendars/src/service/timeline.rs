@@ -0,0 +1,549 @@
+use super::repository::*;
+use crate::model::*;
+use tracing::warn;
+use universe_entity::{Page, Pagination};
+use universe_worlds::WorldID;
+
+/// The Timeline Service to allow interactions with the events dated in calendars
+pub trait TimelineService: Send + Sync {
+  /// Retrieve the event that has the given unique ID, as long as it is dated in the given
+  /// calendar
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the calendar belongs to
+  /// * `calendar_id` The ID of the calendar that the event is dated in
+  /// * `event_id` The ID of the event to retrieve
+  ///
+  /// # Returns
+  /// The event, or `None` if it wasn't found
+  fn get_event_by_id(
+    &self,
+    world_id: &WorldID,
+    calendar_id: &CalendarID,
+    event_id: &EventID,
+  ) -> Option<EventEntity>;
+
+  /// Retrieve a page of the events of a calendar, in the order that they happened
+  ///
+  /// # Arguments
+  /// * `calendar` The calendar to list the events of
+  /// * `from` The earliest date to list events from, if any
+  /// * `to` The latest date to list events until, if any
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of events
+  fn list_events(
+    &self,
+    calendar: &CalendarEntity,
+    from: Option<CalendarDate>,
+    to: Option<CalendarDate>,
+    pagination: Pagination,
+  ) -> Page<EventEntity>;
+
+  /// Retrieve every event of a calendar, in the order that they happened
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the calendar belongs to
+  /// * `calendar_id` The ID of the calendar to list the events of
+  ///
+  /// # Returns
+  /// The events
+  fn list_all_events(&self, world_id: &WorldID, calendar_id: &CalendarID) -> Vec<EventEntity>;
+
+  /// Create a new event. The date of the event must exist in its calendar
+  ///
+  /// # Arguments
+  /// * `event` The event data to create the event from
+  ///
+  /// # Returns
+  /// The event that was persisted
+  fn create_event(&self, event: EventData) -> Result<EventEntity, CreateEventError>;
+
+  /// Update an existing event.
+  ///
+  /// This will load the event by ID, and then call a provided callback to mutate the event before
+  /// persisting the changes back to the database. Events can't be moved to another calendar.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the calendar belongs to
+  /// * `calendar_id` The ID of the calendar that the event is dated in
+  /// * `event_id` The ID of the event to update
+  /// * `updater` The callback to mutate the event with
+  ///
+  /// # Returns
+  /// The newly updated event
+  fn update_event(
+    &self,
+    world_id: &WorldID,
+    calendar_id: &CalendarID,
+    event_id: &EventID,
+    updater: &mut dyn FnMut(EventData) -> Result<EventData, Box<dyn std::error::Error>>,
+  ) -> Result<EventEntity, UpdateEventError>;
+
+  /// Delete an event
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the calendar belongs to
+  /// * `calendar_id` The ID of the calendar that the event is dated in
+  /// * `event_id` The ID of the event to delete
+  fn delete_event(
+    &self,
+    world_id: &WorldID,
+    calendar_id: &CalendarID,
+    event_id: &EventID,
+  ) -> Result<(), DeleteEventError>;
+}
+
+/// Enumeration of reasons why we failed to create a new event
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum CreateEventError {
+  #[error("The date of the event doesn't exist in its calendar: {0}")]
+  InvalidDate(DateError),
+  #[error("The calendar was not found")]
+  UnknownCalendar,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to update an existing event
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateEventError {
+  #[error("The date of the event doesn't exist in its calendar: {0}")]
+  InvalidDate(DateError),
+  #[error("The event was not found")]
+  UnknownEvent,
+  #[error("The version of the event record did not match")]
+  OptimisticLockFailure,
+  #[error("An error occurred updating the event details: {0}")]
+  UpdateError(Box<dyn std::error::Error>),
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to delete an event
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum DeleteEventError {
+  #[error("The event was not found")]
+  UnknownEvent,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// The Timeline Service to allow interactions with the events dated in calendars
+pub struct TimelineServiceImpl<Repo> {
+  repository: Repo,
+}
+
+/// Create a new Timeline Service
+///
+/// # Returns
+/// The Timeline Service
+pub fn new_timeline_service<Repo: CalendarRepository + Send + Sync>(
+  repository: Repo,
+) -> impl TimelineService {
+  TimelineServiceImpl { repository }
+}
+
+impl<Repo: CalendarRepository + Send + Sync> TimelineService for TimelineServiceImpl<Repo> {
+  /// Retrieve the event that has the given unique ID, as long as it is dated in the given
+  /// calendar
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the calendar belongs to
+  /// * `calendar_id` The ID of the calendar that the event is dated in
+  /// * `event_id` The ID of the event to retrieve
+  ///
+  /// # Returns
+  /// The event, or `None` if it wasn't found
+  fn get_event_by_id(
+    &self,
+    world_id: &WorldID,
+    calendar_id: &CalendarID,
+    event_id: &EventID,
+  ) -> Option<EventEntity> {
+    let event = self
+      .repository
+      .get_event_by_id(world_id, calendar_id, event_id);
+
+    if event.is_none() {
+      warn!(
+        "No event found with ID {} in calendar {}",
+        event_id, calendar_id
+      );
+    }
+
+    event
+  }
+
+  /// Retrieve a page of the events of a calendar, in the order that they happened
+  ///
+  /// # Arguments
+  /// * `calendar` The calendar to list the events of
+  /// * `from` The earliest date to list events from, if any
+  /// * `to` The latest date to list events until, if any
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of events
+  fn list_events(
+    &self,
+    calendar: &CalendarEntity,
+    from: Option<CalendarDate>,
+    to: Option<CalendarDate>,
+    pagination: Pagination,
+  ) -> Page<EventEntity> {
+    let definition = &calendar.data.definition;
+    self.repository.list_events(
+      &calendar.data.world,
+      &calendar.identity.id,
+      from.map(|date| definition.day_number(&date)),
+      to.map(|date| definition.day_number(&date)),
+      pagination,
+    )
+  }
+
+  /// Retrieve every event of a calendar, in the order that they happened
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the calendar belongs to
+  /// * `calendar_id` The ID of the calendar to list the events of
+  ///
+  /// # Returns
+  /// The events
+  fn list_all_events(&self, world_id: &WorldID, calendar_id: &CalendarID) -> Vec<EventEntity> {
+    self.repository.list_all_events(world_id, calendar_id)
+  }
+
+  /// Create a new event. The date of the event must exist in its calendar
+  ///
+  /// # Arguments
+  /// * `event` The event data to create the event from
+  ///
+  /// # Returns
+  /// The event that was persisted
+  fn create_event(&self, event: EventData) -> Result<EventEntity, CreateEventError> {
+    let calendar = self
+      .repository
+      .get_calendar_by_id(&event.world, &event.calendar)
+      .ok_or(CreateEventError::UnknownCalendar)?;
+
+    let definition = &calendar.data.definition;
+    definition
+      .check_date(&event.date)
+      .map_err(CreateEventError::InvalidDate)?;
+
+    let day_number = definition.day_number(&event.date);
+    let created = self.repository.create_event(event, day_number)?;
+    Ok(created)
+  }
+
+  /// Update an existing event.
+  ///
+  /// This will load the event by ID, and then call a provided callback to mutate the event before
+  /// persisting the changes back to the database. Events can't be moved to another calendar.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the calendar belongs to
+  /// * `calendar_id` The ID of the calendar that the event is dated in
+  /// * `event_id` The ID of the event to update
+  /// * `updater` The callback to mutate the event with
+  ///
+  /// # Returns
+  /// The newly updated event
+  fn update_event(
+    &self,
+    world_id: &WorldID,
+    calendar_id: &CalendarID,
+    event_id: &EventID,
+    updater: &mut dyn FnMut(EventData) -> Result<EventData, Box<dyn std::error::Error>>,
+  ) -> Result<EventEntity, UpdateEventError> {
+    let calendar = self
+      .repository
+      .get_calendar_by_id(world_id, calendar_id)
+      .ok_or(UpdateEventError::UnknownEvent)?;
+    let event = self
+      .get_event_by_id(world_id, calendar_id, event_id)
+      .ok_or(UpdateEventError::UnknownEvent)?;
+
+    let world = event.data.world.clone();
+    let calendar_id = event.data.calendar.clone();
+    let updated = updater(event.data).map_err(UpdateEventError::UpdateError)?;
+    let updated = EventData {
+      world,
+      calendar: calendar_id,
+      ..updated
+    };
+
+    let definition = &calendar.data.definition;
+    definition
+      .check_date(&updated.date)
+      .map_err(UpdateEventError::InvalidDate)?;
+
+    let day_number = definition.day_number(&updated.date);
+    let saved = self.repository.update_event(
+      EventEntity {
+        identity: event.identity,
+        data: updated,
+      },
+      day_number,
+    )?;
+    Ok(saved)
+  }
+
+  /// Delete an event
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the calendar belongs to
+  /// * `calendar_id` The ID of the calendar that the event is dated in
+  /// * `event_id` The ID of the event to delete
+  fn delete_event(
+    &self,
+    world_id: &WorldID,
+    calendar_id: &CalendarID,
+    event_id: &EventID,
+  ) -> Result<(), DeleteEventError> {
+    self
+      .repository
+      .delete_event(world_id, calendar_id, event_id)?;
+    Ok(())
+  }
+}
+
+impl From<PersistCalendarError> for CreateEventError {
+  fn from(e: PersistCalendarError) -> Self {
+    warn!("Error creating event: {}", e);
+    match e {
+      PersistCalendarError::WorldNotFound | PersistCalendarError::CalendarNotFound => {
+        CreateEventError::UnknownCalendar
+      }
+      _ => CreateEventError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistCalendarError> for UpdateEventError {
+  fn from(e: PersistCalendarError) -> Self {
+    warn!("Error updating event: {}", e);
+    match e {
+      PersistCalendarError::CalendarNotFound | PersistCalendarError::EventNotFound => {
+        UpdateEventError::UnknownEvent
+      }
+      PersistCalendarError::OptimisticLockFailure => UpdateEventError::OptimisticLockFailure,
+      _ => UpdateEventError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistCalendarError> for DeleteEventError {
+  fn from(e: PersistCalendarError) -> Self {
+    warn!("Error deleting event: {}", e);
+    match e {
+      PersistCalendarError::CalendarNotFound | PersistCalendarError::EventNotFound => {
+        DeleteEventError::UnknownEvent
+      }
+      _ => DeleteEventError::UnknownError,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use assert_matches::assert_matches;
+  use mockall::predicate::*;
+  use spectral::prelude::*;
+  use test_env_log::test;
+  use universe_entity::Identity;
+
+  fn calendar() -> CalendarEntity {
+    CalendarEntity {
+      identity: Identity::default(),
+      data: CalendarData {
+        world: Default::default(),
+        name: "Reckoning".to_owned(),
+        definition: CalendarDefinition {
+          months: vec![
+            Month {
+              name: "Frost".to_owned(),
+              days: 30,
+              leap_days: 0,
+            },
+            Month {
+              name: "Thaw".to_owned(),
+              days: 30,
+              leap_days: 0,
+            },
+          ],
+          ..CalendarDefinition::default()
+        },
+      },
+    }
+  }
+
+  fn event(calendar: &CalendarEntity, date: CalendarDate) -> EventData {
+    EventData {
+      world: calendar.data.world.clone(),
+      calendar: calendar.identity.id.clone(),
+      title: "The Long Winter".to_owned(),
+      description: "".to_owned(),
+      date,
+    }
+  }
+
+  #[test]
+  fn test_create_event_stores_day_number() {
+    let calendar = calendar();
+    let mut repository = MockCalendarRepository::new();
+    let returned = calendar.clone();
+    repository
+      .expect_get_calendar_by_id()
+      .times(1)
+      .returning(move |_, _| Some(returned.clone()));
+    repository
+      .expect_create_event()
+      .with(always(), eq(-15))
+      .times(1)
+      .returning(|data, _| {
+        Ok(EventEntity {
+          identity: Identity::default(),
+          data,
+        })
+      });
+
+    let service = new_timeline_service(repository);
+    let result = service.create_event(event(
+      &calendar,
+      CalendarDate {
+        year: 0,
+        month: 2,
+        day: 16,
+      },
+    ));
+
+    assert_that(&result).is_ok();
+  }
+
+  #[test]
+  fn test_create_event_invalid_date() {
+    let calendar = calendar();
+    let mut repository = MockCalendarRepository::new();
+    let returned = calendar.clone();
+    repository
+      .expect_get_calendar_by_id()
+      .times(1)
+      .returning(move |_, _| Some(returned.clone()));
+    repository.expect_create_event().never();
+
+    let service = new_timeline_service(repository);
+    let result = service.create_event(event(
+      &calendar,
+      CalendarDate {
+        year: 1,
+        month: 3,
+        day: 1,
+      },
+    ));
+
+    assert_matches!(
+      result,
+      Err(CreateEventError::InvalidDate(DateError::MonthOutOfRange))
+    );
+  }
+
+  #[test]
+  fn test_update_event_keeps_calendar() {
+    let calendar = calendar();
+    let existing = EventEntity {
+      identity: Identity::default(),
+      data: event(
+        &calendar,
+        CalendarDate {
+          year: 1,
+          month: 1,
+          day: 1,
+        },
+      ),
+    };
+
+    let mut repository = MockCalendarRepository::new();
+    let returned = calendar.clone();
+    repository
+      .expect_get_calendar_by_id()
+      .times(1)
+      .returning(move |_, _| Some(returned.clone()));
+    let returned = existing.clone();
+    repository
+      .expect_get_event_by_id()
+      .times(1)
+      .returning(move |_, _, _| Some(returned.clone()));
+    let calendar_id = calendar.identity.id.clone();
+    repository
+      .expect_update_event()
+      .withf(move |event, day_number| event.data.calendar == calendar_id && *day_number == 60)
+      .times(1)
+      .returning(|event, _| Ok(event));
+
+    let service = new_timeline_service(repository);
+    let result = service.update_event(
+      &calendar.data.world,
+      &calendar.identity.id,
+      &existing.identity.id,
+      &mut |data| {
+        Ok(EventData {
+          calendar: Default::default(),
+          date: CalendarDate {
+            year: 2,
+            month: 1,
+            day: 1,
+          },
+          ..data
+        })
+      },
+    );
+
+    assert_that(&result).is_ok();
+  }
+
+  #[test]
+  fn test_list_events_between_dates() {
+    let calendar = calendar();
+    let mut repository = MockCalendarRepository::new();
+    repository
+      .expect_list_events()
+      .with(
+        eq(calendar.data.world.clone()),
+        eq(calendar.identity.id.clone()),
+        eq(Some(30)),
+        eq(Some(179)),
+        always(),
+      )
+      .times(1)
+      .returning(|_, _, _, _, _| Page {
+        offset: 0,
+        total: 0,
+        entries: vec![],
+      });
+
+    let service = new_timeline_service(repository);
+    let page = service.list_events(
+      &calendar,
+      Some(CalendarDate {
+        year: 1,
+        month: 2,
+        day: 1,
+      }),
+      Some(CalendarDate {
+        year: 3,
+        month: 2,
+        day: 30,
+      }),
+      Pagination {
+        offset: 0,
+        limit: 10,
+      },
+    );
+
+    assert_that(&page.total).is_equal_to(0);
+  }
+}
@@ -0,0 +1,596 @@
+mod search;
+
+use crate::{model::*, service::repository::*, CharacterFilters, CharacterSorts};
+use chrono::Utc;
+use postgres::types::{Json, ToSql};
+use search::CharacterSearchQuery;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::ops::Deref;
+use tracing::{debug, warn};
+use universe_database::Database;
+use universe_entity::{Identity, Page, Pagination, SortField};
+use universe_worlds::WorldID;
+use uuid::Uuid;
+
+impl From<&postgres::Row> for CharacterEntity {
+  fn from(row: &postgres::Row) -> Self {
+    let attributes: Json<BTreeMap<String, String>> = row.get("attributes");
+
+    CharacterEntity {
+      identity: Identity {
+        id: row.get("character_id"),
+        version: row.get("version"),
+        created: row.get("created"),
+        updated: row.get("updated"),
+      },
+      data: CharacterData {
+        world: row.get("world_id"),
+        name: row.get("name"),
+        aliases: row.get("aliases"),
+        summary: row.get("summary"),
+        biography: row.get("biography"),
+        attributes: attributes.0,
+      },
+    }
+  }
+}
+
+impl CharacterRepository for Database {
+  /// Retrieve the character that has the given unique ID, as long as it belongs to the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the character belongs to
+  /// * `character_id` The ID of the character to retrieve
+  ///
+  /// # Returns
+  /// The character, or `None` if it wasn't found
+  fn get_character_by_id(
+    &self,
+    world_id: &WorldID,
+    character_id: &CharacterID,
+  ) -> Option<CharacterEntity> {
+    let mut client = self.client().unwrap();
+
+    let character = client
+      .query(
+        "SELECT * FROM characters WHERE world_id = $1 AND character_id = $2",
+        &[&world_id, &character_id],
+      )
+      .map_err(|e| {
+        warn!("Error loading character from database: {}", e);
+        e
+      })
+      .ok()
+      .and_then(|rows| rows.get(0).map(|row| row.into()));
+
+    debug!("Character for ID {}: {:?}", character_id, character);
+    character
+  }
+
+  /// Search the characters of a world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to search the characters of
+  /// * `filters` The filters to apply when searching
+  /// * `sorts` The sorts to apply to the results
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of characters
+  fn search_characters(
+    &self,
+    world_id: &WorldID,
+    filters: CharacterFilters,
+    sorts: Vec<SortField<CharacterSorts>>,
+    pagination: Pagination,
+  ) -> Page<CharacterEntity> {
+    let mut client = self.client().unwrap();
+
+    let (select_sql, select_binds) =
+      CharacterSearchQuery::new(world_id.clone(), filters.clone()).select(&sorts, pagination);
+    let (count_sql, count_binds) = CharacterSearchQuery::new(world_id.clone(), filters).count();
+    debug!("Searching characters: {}", select_sql);
+
+    let select_binds: Vec<&(dyn ToSql + Sync)> = select_binds.iter().map(|b| b.deref()).collect();
+    let count_binds: Vec<&(dyn ToSql + Sync)> = count_binds.iter().map(|b| b.deref()).collect();
+
+    let entries: Vec<CharacterEntity> = client
+      .query(select_sql.as_str(), &select_binds)
+      .map_err(|e| {
+        warn!("Error searching characters in database: {}", e);
+        e
+      })
+      .map(|rows| rows.iter().map(|row| row.into()).collect())
+      .unwrap_or_default();
+
+    let total: i64 = client
+      .query_one(count_sql.as_str(), &count_binds)
+      .map_err(|e| {
+        warn!("Error counting characters in database: {}", e);
+        e
+      })
+      .map(|row| row.get("total"))
+      .unwrap_or(0);
+
+    let page = Page {
+      entries,
+      total: total as u32,
+      offset: pagination.offset,
+    };
+    debug!("Found characters: {:?}", page);
+
+    page
+  }
+
+  /// Retrieve every character of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the characters of
+  ///
+  /// # Returns
+  /// The characters
+  fn list_all_characters(&self, world_id: &WorldID) -> Vec<CharacterEntity> {
+    let mut client = self.client().unwrap();
+
+    let characters = client
+      .query(
+        "SELECT * FROM characters WHERE world_id = $1 ORDER BY created ASC, character_id ASC",
+        &[&world_id],
+      )
+      .map_err(|e| {
+        warn!("Error listing characters in database: {}", e);
+        e
+      })
+      .map(|rows| rows.iter().map(|row| row.into()).collect())
+      .unwrap_or_default();
+
+    debug!("All characters of world {}: {:?}", world_id, characters);
+    characters
+  }
+
+  /// Create a new character record in the data store
+  ///
+  /// # Arguments
+  /// * `character` The character details to persist to the data store
+  ///
+  /// # Returns
+  /// The character that was persisted
+  fn create_character(
+    &self,
+    character: CharacterData,
+  ) -> Result<CharacterEntity, PersistCharacterError> {
+    debug!("Creating record for character: {:?}", character);
+
+    let mut client = self.client().unwrap();
+
+    let new_id = CharacterID::default();
+    let new_version = Uuid::new_v4();
+    let new_updated = Utc::now();
+
+    let result: CharacterEntity = client
+      .query(
+        "INSERT INTO characters(character_id, version, created, updated, world_id, name, aliases, summary, biography, attributes)
+          VALUES ($1, $2, $3, $3, $4, $5, $6, $7, $8, $9)
+          RETURNING *",
+        &[
+          &new_id,
+          &new_version,
+          &new_updated,
+          &character.world,
+          &character.name,
+          &character.aliases,
+          &character.summary,
+          &character.biography,
+          &Json(&character.attributes),
+        ],
+      )
+      .map(|rows| rows.get(0).unwrap().into())?;
+
+    debug!("Created record for character: {:?}", result);
+    Ok(result)
+  }
+
+  /// Update an existing character record in the data store
+  ///
+  /// # Arguments
+  /// * `character` The character details to persist to the data store
+  ///
+  /// # Returns
+  /// The character that was persisted
+  fn update_character(
+    &self,
+    character: CharacterEntity,
+  ) -> Result<CharacterEntity, PersistCharacterError> {
+    debug!("Updating record for character: {:?}", character);
+
+    let mut client = self.client().unwrap();
+
+    let new_version = Uuid::new_v4();
+    let new_updated = Utc::now();
+
+    let rows = client.query(
+      "UPDATE characters SET name = $1, aliases = $2, summary = $3, biography = $4, attributes = $5, version = $6, updated = $7
+          WHERE character_id = $8
+          AND world_id = $9
+          AND version = $10
+          RETURNING *",
+      &[
+        &character.data.name,
+        &character.data.aliases,
+        &character.data.summary,
+        &character.data.biography,
+        &Json(&character.data.attributes),
+        &new_version,
+        &new_updated,
+        &character.identity.id,
+        &character.data.world,
+        &character.identity.version,
+      ],
+    )?;
+
+    if let Some(row) = rows.get(0) {
+      let result = row.into();
+      debug!("Updated record for character: {:?}", result);
+      Ok(result)
+    } else {
+      let character_found = client.query(
+        "SELECT version FROM characters WHERE character_id = $1 AND world_id = $2",
+        &[&character.identity.id, &character.data.world],
+      )?;
+
+      if character_found.is_empty() {
+        warn!(
+          "Attempted to update character {} that wasn't found",
+          character.identity.id
+        );
+        Err(PersistCharacterError::CharacterNotFound)
+      } else {
+        let old_version: Uuid = character_found.get(0).unwrap().get("version");
+
+        warn!(
+          "Attempted to update character {}. Expected version {} but database had {}",
+          character.identity.id, character.identity.version, old_version
+        );
+        Err(PersistCharacterError::OptimisticLockFailure)
+      }
+    }
+  }
+
+  /// Delete an existing character record from the data store
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the character belongs to
+  /// * `character_id` The ID of the character to delete
+  fn delete_character(
+    &self,
+    world_id: &WorldID,
+    character_id: &CharacterID,
+  ) -> Result<(), PersistCharacterError> {
+    debug!("Deleting character {} from world {}", character_id, world_id);
+
+    let mut client = self.client().unwrap();
+
+    let deleted = client.execute(
+      "DELETE FROM characters WHERE world_id = $1 AND character_id = $2",
+      &[&world_id, &character_id],
+    )?;
+
+    if deleted == 0 {
+      warn!(
+        "Attempted to delete character {} that wasn't found",
+        character_id
+      );
+      Err(PersistCharacterError::CharacterNotFound)
+    } else {
+      Ok(())
+    }
+  }
+}
+
+impl From<postgres::Error> for PersistCharacterError {
+  fn from(error: postgres::Error) -> Self {
+    warn!("Error persisting character in database: {:?}", error);
+
+    error
+      .source()
+      .and_then(|e| e.downcast_ref::<postgres::error::DbError>())
+      .map(|e| match e.constraint() {
+        Some("characters_world_id_fkey") => PersistCharacterError::WorldNotFound,
+        _ => PersistCharacterError::UnknownError,
+      })
+      .unwrap_or(PersistCharacterError::UnknownError)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use spectral::prelude::*;
+  use test_env_log::test;
+  use universe_entity::SortDirection;
+  use universe_test_database_wrapper::TestDatabaseWrapper;
+  use universe_testdata::{seed, Character, User, World};
+
+  /// Seed a world with some characters into the database
+  fn seed_characters(database: &TestDatabaseWrapper) -> (World, Vec<Character>) {
+    let user: User = Default::default();
+    let world = World {
+      owner_id: user.user_id,
+      ..Default::default()
+    };
+    let characters = vec![
+      Character {
+        world_id: world.world_id,
+        name: "Frodo Baggins".to_owned(),
+        aliases: vec!["Mr Underhill".to_owned(), "Ring-bearer".to_owned()],
+        ..Default::default()
+      },
+      Character {
+        world_id: world.world_id,
+        name: "Aragorn".to_owned(),
+        aliases: vec!["Strider".to_owned(), "Elessar".to_owned()],
+        ..Default::default()
+      },
+      Character {
+        world_id: world.world_id,
+        name: "Bilbo Baggins".to_owned(),
+        aliases: vec!["Ring-bearer".to_owned()],
+        ..Default::default()
+      },
+    ];
+
+    seed(
+      &database,
+      vec![&user, &world, &characters[0], &characters[1], &characters[2]],
+    );
+
+    (world, characters)
+  }
+
+  fn names(page: &Page<CharacterEntity>) -> Vec<String> {
+    page
+      .entries
+      .iter()
+      .map(|character| character.data.name.clone())
+      .collect()
+  }
+
+  const ALL: Pagination = Pagination {
+    offset: 0,
+    limit: 10,
+  };
+
+  fn by_name() -> Vec<SortField<CharacterSorts>> {
+    vec![SortField {
+      field: CharacterSorts::Name,
+      direction: SortDirection::Natural,
+    }]
+  }
+
+  #[test]
+  fn test_get_character() {
+    let database = TestDatabaseWrapper::new();
+    let (world, characters) = seed_characters(&database);
+
+    let character = database.wrapper.get_character_by_id(
+      &WorldID::from_uuid(world.world_id),
+      &CharacterID::from_uuid(characters[1].character_id),
+    );
+    assert_that(&character)
+      .is_some()
+      .is_equal_to(CharacterEntity::from(characters[1].clone()));
+  }
+
+  #[test]
+  fn test_get_character_wrong_world() {
+    let database = TestDatabaseWrapper::new();
+    let (_, characters) = seed_characters(&database);
+
+    let character = database.wrapper.get_character_by_id(
+      &Default::default(),
+      &CharacterID::from_uuid(characters[1].character_id),
+    );
+    assert_that(&character).is_none();
+  }
+
+  #[test]
+  fn test_search_characters() {
+    let database = TestDatabaseWrapper::new();
+    let (world, _) = seed_characters(&database);
+    let world_id = WorldID::from_uuid(world.world_id);
+
+    let all = database
+      .wrapper
+      .search_characters(&world_id, Default::default(), by_name(), ALL);
+    assert_that(&names(&all)).is_equal_to(vec![
+      "Aragorn".to_owned(),
+      "Bilbo Baggins".to_owned(),
+      "Frodo Baggins".to_owned(),
+    ]);
+    assert_that(&all.total).is_equal_to(3);
+
+    let reversed = database.wrapper.search_characters(
+      &world_id,
+      Default::default(),
+      vec![SortField {
+        field: CharacterSorts::Name,
+        direction: SortDirection::Descending,
+      }],
+      Pagination {
+        offset: 1,
+        limit: 1,
+      },
+    );
+    assert_that(&names(&reversed)).is_equal_to(vec!["Bilbo Baggins".to_owned()]);
+    assert_that(&reversed.total).is_equal_to(3);
+  }
+
+  #[test]
+  fn test_search_characters_by_name() {
+    let database = TestDatabaseWrapper::new();
+    let (world, _) = seed_characters(&database);
+
+    let page = database.wrapper.search_characters(
+      &WorldID::from_uuid(world.world_id),
+      CharacterFilters {
+        name: Some("baggins".to_owned()),
+        alias: None,
+      },
+      by_name(),
+      ALL,
+    );
+    assert_that(&names(&page))
+      .is_equal_to(vec!["Bilbo Baggins".to_owned(), "Frodo Baggins".to_owned()]);
+    assert_that(&page.total).is_equal_to(2);
+  }
+
+  #[test]
+  fn test_search_characters_by_alias() {
+    let database = TestDatabaseWrapper::new();
+    let (world, _) = seed_characters(&database);
+    let world_id = WorldID::from_uuid(world.world_id);
+
+    let page = database.wrapper.search_characters(
+      &world_id,
+      CharacterFilters {
+        name: None,
+        alias: Some("STRIDER".to_owned()),
+      },
+      by_name(),
+      ALL,
+    );
+    assert_that(&names(&page)).is_equal_to(vec!["Aragorn".to_owned()]);
+
+    let page = database.wrapper.search_characters(
+      &world_id,
+      CharacterFilters {
+        name: Some("frodo".to_owned()),
+        alias: Some("ring".to_owned()),
+      },
+      by_name(),
+      ALL,
+    );
+    assert_that(&names(&page)).is_equal_to(vec!["Frodo Baggins".to_owned()]);
+  }
+
+  #[test]
+  fn test_search_characters_other_world() {
+    let database = TestDatabaseWrapper::new();
+    seed_characters(&database);
+
+    let page = database
+      .wrapper
+      .search_characters(&Default::default(), Default::default(), by_name(), ALL);
+    assert_that(&page.entries).is_empty();
+    assert_that(&page.total).is_equal_to(0);
+  }
+
+  #[test]
+  fn test_create_character() {
+    let database = TestDatabaseWrapper::new();
+    let (world, _) = seed_characters(&database);
+
+    let character = CharacterData {
+      world: WorldID::from_uuid(world.world_id),
+      name: "Samwise Gamgee".to_owned(),
+      aliases: vec!["Sam".to_owned()],
+      summary: "A gardener".to_owned(),
+      biography: "Loyal *to a fault*".to_owned(),
+      attributes: vec![
+        ("Race".to_owned(), "Hobbit".to_owned()),
+        ("Home".to_owned(), "Hobbiton".to_owned()),
+      ]
+      .into_iter()
+      .collect(),
+    };
+    let created = database.wrapper.create_character(character.clone());
+    assert_that(&created)
+      .is_ok()
+      .map(|created| &created.data)
+      .is_equal_to(&character);
+  }
+
+  #[test]
+  fn test_create_character_unknown_world() {
+    let database = TestDatabaseWrapper::new();
+
+    let created = database.wrapper.create_character(CharacterData {
+      world: Default::default(),
+      name: "Samwise Gamgee".to_owned(),
+      aliases: vec![],
+      summary: "".to_owned(),
+      biography: "".to_owned(),
+      attributes: BTreeMap::new(),
+    });
+    assert_that(&created)
+      .is_err()
+      .is_equal_to(PersistCharacterError::WorldNotFound);
+  }
+
+  #[test]
+  fn test_update_character() {
+    let database = TestDatabaseWrapper::new();
+    let (_, characters) = seed_characters(&database);
+
+    let mut character = CharacterEntity::from(characters[1].clone());
+    character.data.name = "Elessar Telcontar".to_owned();
+    character
+      .data
+      .attributes
+      .insert("Title".to_owned(), "King of Gondor".to_owned());
+
+    let updated = database.wrapper.update_character(character.clone());
+    assert_that(&updated)
+      .is_ok()
+      .map(|updated| &updated.data)
+      .is_equal_to(&character.data);
+    assert_that(&updated.unwrap().identity.version).is_not_equal_to(character.identity.version);
+  }
+
+  #[test]
+  fn test_update_character_wrong_version() {
+    let database = TestDatabaseWrapper::new();
+    let (_, characters) = seed_characters(&database);
+
+    let mut character = CharacterEntity::from(characters[1].clone());
+    character.identity.version = Uuid::new_v4();
+
+    let updated = database.wrapper.update_character(character);
+    assert_that(&updated)
+      .is_err()
+      .is_equal_to(PersistCharacterError::OptimisticLockFailure);
+  }
+
+  #[test]
+  fn test_update_unknown_character() {
+    let database = TestDatabaseWrapper::new();
+    let (world, _) = seed_characters(&database);
+
+    let character = CharacterEntity::from(Character {
+      world_id: world.world_id,
+      ..Default::default()
+    });
+
+    let updated = database.wrapper.update_character(character);
+    assert_that(&updated)
+      .is_err()
+      .is_equal_to(PersistCharacterError::CharacterNotFound);
+  }
+
+  #[test]
+  fn test_delete_character() {
+    let database = TestDatabaseWrapper::new();
+    let (world, characters) = seed_characters(&database);
+    let world_id = WorldID::from_uuid(world.world_id);
+    let character_id = CharacterID::from_uuid(characters[0].character_id);
+
+    let deleted = database.wrapper.delete_character(&world_id, &character_id);
+    assert_that(&deleted).is_ok();
+
+    let deleted = database.wrapper.delete_character(&world_id, &character_id);
+    assert_that(&deleted)
+      .is_err()
+      .is_equal_to(PersistCharacterError::CharacterNotFound);
+  }
+}
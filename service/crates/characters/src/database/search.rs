@@ -0,0 +1,228 @@
+use crate::{CharacterFilters, CharacterSorts};
+use postgres::types::ToSql;
+use std::boxed::Box;
+use universe_entity::{Pagination, SortDirection, SortField};
+use universe_worlds::WorldID;
+
+/// Builder for the SQL statements needed to search for the characters of a world
+pub struct CharacterSearchQuery {
+  /// The clauses to combine into the WHERE of the queries
+  clauses: Vec<String>,
+  /// The values to bind into the queries
+  binds: Vec<Box<dyn ToSql + Sync>>,
+}
+
+impl CharacterSearchQuery {
+  /// Construct the search query for the given filters
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to search the characters of
+  /// * `filters` The filters to apply when searching for characters
+  ///
+  /// # Returns
+  /// The search query
+  pub fn new(world_id: WorldID, filters: CharacterFilters) -> Self {
+    let mut query = CharacterSearchQuery {
+      clauses: vec![],
+      binds: vec![],
+    };
+
+    let bind = query.bind(world_id);
+    query.clauses.push(format!("world_id = ${}", bind));
+
+    // Name and alias filters match anywhere in the value, and use STRPOS rather than LIKE so that
+    // the filter text never needs escaping
+    if let Some(name) = filters.name.filter(|name| !name.trim().is_empty()) {
+      let bind = query.bind(name.trim().to_owned());
+      query
+        .clauses
+        .push(format!("STRPOS(UPPER(name), UPPER(${})) > 0", bind));
+    }
+
+    if let Some(alias) = filters.alias.filter(|alias| !alias.trim().is_empty()) {
+      let bind = query.bind(alias.trim().to_owned());
+      query.clauses.push(format!(
+        "EXISTS (SELECT 1 FROM UNNEST(aliases) AS alias WHERE STRPOS(UPPER(alias), UPPER(${})) > 0)",
+        bind
+      ));
+    }
+
+    query
+  }
+
+  /// Add a new value to bind into the queries
+  ///
+  /// # Arguments
+  /// * `value` The value to bind
+  ///
+  /// # Returns
+  /// The index of the bind placeholder to use in the SQL
+  fn bind<T>(&mut self, value: T) -> usize
+  where
+    T: ToSql + Sync + 'static,
+  {
+    self.binds.push(Box::new(value));
+    self.binds.len()
+  }
+
+  /// Build the WHERE clause for the queries
+  fn where_clause(&self) -> String {
+    format!(" WHERE {}", self.clauses.join(" AND "))
+  }
+
+  /// Build the ORDER BY clause for the requested sorts.
+  ///
+  /// The Character ID is always used as a final sort so that the ordering is deterministic even
+  /// if the requested sorts have duplicate values
+  ///
+  /// # Arguments
+  /// * `sorts` The sorts to apply
+  fn order_by_clause(&self, sorts: &[SortField<CharacterSorts>]) -> String {
+    let mut order_by: Vec<String> = sorts
+      .iter()
+      .map(|sort| {
+        let (expression, natural) = match sort.field {
+          CharacterSorts::Name => ("UPPER(name)", "ASC"),
+          CharacterSorts::Created => ("created", "DESC"),
+          CharacterSorts::Updated => ("updated", "DESC"),
+          CharacterSorts::Id => ("character_id", "ASC"),
+        };
+
+        let direction = match sort.direction {
+          SortDirection::Ascending => "ASC",
+          SortDirection::Descending => "DESC",
+          SortDirection::Natural => natural,
+        };
+
+        format!("{} {}", expression, direction)
+      })
+      .collect();
+    order_by.push("character_id ASC".to_owned());
+
+    format!(" ORDER BY {}", order_by.join(", "))
+  }
+
+  /// Build the SQL to select the requested page of characters
+  ///
+  /// # Arguments
+  /// * `sorts` The sorts to apply
+  /// * `pagination` The page of characters to select
+  ///
+  /// # Returns
+  /// The SQL and the binds to use with it
+  pub fn select(
+    mut self,
+    sorts: &[SortField<CharacterSorts>],
+    pagination: Pagination,
+  ) -> (String, Vec<Box<dyn ToSql + Sync>>) {
+    let mut sql = format!(
+      "SELECT * FROM characters{}{}",
+      self.where_clause(),
+      self.order_by_clause(sorts)
+    );
+
+    let limit = self.bind(i64::from(pagination.limit));
+    let offset = self.bind(i64::from(pagination.offset));
+    sql.push_str(&format!(" LIMIT ${} OFFSET ${}", limit, offset));
+
+    (sql, self.binds)
+  }
+
+  /// Build the SQL to count all of the characters that match the filters
+  ///
+  /// # Returns
+  /// The SQL and the binds to use with it
+  pub fn count(self) -> (String, Vec<Box<dyn ToSql + Sync>>) {
+    let sql = format!(
+      "SELECT COUNT(*) AS total FROM characters{}",
+      self.where_clause()
+    );
+
+    (sql, self.binds)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use spectral::prelude::*;
+
+  #[test]
+  fn test_select_no_filters_no_sorts() {
+    let query = CharacterSearchQuery::new(Default::default(), Default::default());
+    let (sql, binds) = query.select(
+      &[],
+      Pagination {
+        offset: 0,
+        limit: 10,
+      },
+    );
+
+    assert_that(&sql.as_str()).is_equal_to(
+      "SELECT * FROM characters WHERE world_id = $1 ORDER BY character_id ASC LIMIT $2 OFFSET $3",
+    );
+    assert_that(&binds).has_length(3);
+  }
+
+  #[test]
+  fn test_select_sorts() {
+    let query = CharacterSearchQuery::new(Default::default(), Default::default());
+    let (sql, _) = query.select(
+      &[
+        SortField {
+          field: CharacterSorts::Name,
+          direction: SortDirection::Natural,
+        },
+        SortField {
+          field: CharacterSorts::Updated,
+          direction: SortDirection::Natural,
+        },
+        SortField {
+          field: CharacterSorts::Created,
+          direction: SortDirection::Ascending,
+        },
+      ],
+      Pagination {
+        offset: 0,
+        limit: 10,
+      },
+    );
+
+    assert_that(&sql.as_str()).is_equal_to(
+      "SELECT * FROM characters WHERE world_id = $1 ORDER BY UPPER(name) ASC, updated DESC, created ASC, character_id ASC LIMIT $2 OFFSET $3",
+    );
+  }
+
+  #[test]
+  fn test_count_with_filters() {
+    let query = CharacterSearchQuery::new(
+      Default::default(),
+      CharacterFilters {
+        name: Some(" frodo ".to_owned()),
+        alias: Some("underhill".to_owned()),
+      },
+    );
+    let (sql, binds) = query.count();
+
+    assert_that(&sql.as_str()).is_equal_to(
+      "SELECT COUNT(*) AS total FROM characters WHERE world_id = $1 AND STRPOS(UPPER(name), UPPER($2)) > 0 AND EXISTS (SELECT 1 FROM UNNEST(aliases) AS alias WHERE STRPOS(UPPER(alias), UPPER($3)) > 0)",
+    );
+    assert_that(&binds).has_length(3);
+  }
+
+  #[test]
+  fn test_count_with_blank_filters() {
+    let query = CharacterSearchQuery::new(
+      Default::default(),
+      CharacterFilters {
+        name: Some("  ".to_owned()),
+        alias: Some("".to_owned()),
+      },
+    );
+    let (sql, binds) = query.count();
+
+    assert_that(&sql.as_str())
+      .is_equal_to("SELECT COUNT(*) AS total FROM characters WHERE world_id = $1");
+    assert_that(&binds).has_length(1);
+  }
+}
@@ -0,0 +1,49 @@
+use crate::CharacterID;
+use std::collections::BTreeMap;
+use universe_entity::Identity;
+use universe_worlds::WorldID;
+
+/// Struct to represent the data about a single Character
+#[derive(Debug, PartialEq, Clone)]
+pub struct CharacterData {
+  /// The world that the character belongs to
+  pub world: WorldID,
+  pub name: String,
+  /// Other names that the character is known by
+  pub aliases: Vec<String>,
+  /// A short, plain text, summary of who the character is
+  pub summary: String,
+  /// The biography of the character, as CommonMark source
+  pub biography: String,
+  /// Any other details about the character, as free-form name/value pairs
+  pub attributes: BTreeMap<String, String>,
+}
+
+/// Type to represent the entity that is a persisted character record
+#[derive(Debug, PartialEq, Clone)]
+pub struct CharacterEntity {
+  pub identity: Identity<CharacterID>,
+  pub data: CharacterData,
+}
+
+#[cfg(test)]
+impl From<universe_testdata::Character> for CharacterEntity {
+  fn from(character: universe_testdata::Character) -> CharacterEntity {
+    CharacterEntity {
+      identity: Identity {
+        id: CharacterID::from_uuid(character.character_id),
+        version: character.version,
+        created: character.created,
+        updated: character.updated,
+      },
+      data: CharacterData {
+        world: WorldID::from_uuid(character.world_id),
+        name: character.name.clone(),
+        aliases: character.aliases.clone(),
+        summary: character.summary.clone(),
+        biography: character.biography.clone(),
+        attributes: character.attributes,
+      },
+    }
+  }
+}
@@ -0,0 +1,106 @@
+use std::collections::BTreeMap;
+
+/// Errors that can happen when tidying up the details of a character.
+#[derive(Debug, PartialEq, Clone, thiserror::Error)]
+pub enum CharacterDetailsError {
+  #[error("Alias was blank")]
+  BlankAlias,
+  #[error("Attribute name was blank")]
+  BlankAttributeName,
+}
+
+/// Tidy up the aliases of a character, trimming them and dropping any repeats
+///
+/// # Arguments
+/// * `aliases` The aliases to tidy up
+///
+/// # Returns
+/// The aliases, or an error if any of them were blank
+pub fn parse_aliases(aliases: Vec<String>) -> Result<Vec<String>, CharacterDetailsError> {
+  let mut result: Vec<String> = Vec::with_capacity(aliases.len());
+
+  for alias in aliases {
+    let alias = alias.trim();
+    if alias.is_empty() {
+      return Err(CharacterDetailsError::BlankAlias);
+    }
+    if !result.iter().any(|existing| existing == alias) {
+      result.push(alias.to_owned());
+    }
+  }
+
+  Ok(result)
+}
+
+/// Tidy up the custom attributes of a character, trimming their names and values
+///
+/// # Arguments
+/// * `attributes` The attributes to tidy up
+///
+/// # Returns
+/// The attributes, or an error if any of them had a blank name
+pub fn parse_attributes(
+  attributes: BTreeMap<String, String>,
+) -> Result<BTreeMap<String, String>, CharacterDetailsError> {
+  attributes
+    .into_iter()
+    .map(|(name, value)| {
+      let name = name.trim();
+      if name.is_empty() {
+        Err(CharacterDetailsError::BlankAttributeName)
+      } else {
+        Ok((name.to_owned(), value.trim().to_owned()))
+      }
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use spectral::prelude::*;
+  use test_env_log::test;
+
+  #[test]
+  fn test_parse_aliases_removes_duplicates() {
+    let aliases = parse_aliases(vec![
+      " The Doctor ".to_owned(),
+      "John Smith".to_owned(),
+      "The Doctor".to_owned(),
+    ]);
+    assert_that(&aliases)
+      .is_ok()
+      .is_equal_to(vec!["The Doctor".to_owned(), "John Smith".to_owned()]);
+  }
+
+  #[test]
+  fn test_parse_blank_alias() {
+    let aliases = parse_aliases(vec!["The Doctor".to_owned(), "  ".to_owned()]);
+    assert_that(&aliases)
+      .is_err()
+      .is_equal_to(CharacterDetailsError::BlankAlias);
+  }
+
+  #[test]
+  fn test_parse_attributes_trims() {
+    let mut attributes = BTreeMap::new();
+    attributes.insert(" Species ".to_owned(), " Time Lord ".to_owned());
+
+    let mut expected = BTreeMap::new();
+    expected.insert("Species".to_owned(), "Time Lord".to_owned());
+
+    assert_that(&parse_attributes(attributes))
+      .is_ok()
+      .is_equal_to(expected);
+  }
+
+  #[test]
+  fn test_parse_blank_attribute_name() {
+    let mut attributes = BTreeMap::new();
+    attributes.insert(" ".to_owned(), "Gallifrey".to_owned());
+
+    assert_that(&parse_attributes(attributes))
+      .is_err()
+      .is_equal_to(CharacterDetailsError::BlankAttributeName);
+  }
+}
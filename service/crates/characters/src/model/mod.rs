@@ -0,0 +1,7 @@
+mod character;
+mod character_id;
+mod details;
+
+pub use character::*;
+pub use character_id::*;
+pub use details::*;
@@ -0,0 +1,8 @@
+/// Filters that can be applied when searching for the characters of a world
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct CharacterFilters {
+  /// Only return characters whose name contains this text, ignoring case
+  pub name: Option<String>,
+  /// Only return characters that have an alias containing this text, ignoring case
+  pub alias: Option<String>,
+}
@@ -0,0 +1,321 @@
+use super::{repository::*, service::*, CharacterFilters, CharacterSorts};
+use crate::model::*;
+use tracing::{info, warn};
+use universe_entity::{IdMap, Page, Pagination, SortField};
+use universe_worlds::WorldID;
+
+/// The Character Service to allow interactions with character entities
+pub struct CharacterServiceImpl<Repo> {
+  repository: Repo,
+}
+
+/// Create a new Character Service
+///
+/// # Returns
+/// The Character Service
+pub fn new_character_service<Repo: CharacterRepository + Send + Sync>(
+  repository: Repo,
+) -> impl CharacterService {
+  CharacterServiceImpl { repository }
+}
+
+impl<Repo: CharacterRepository + Send + Sync> CharacterService for CharacterServiceImpl<Repo> {
+  /// Retrieve the character that has the given unique ID, as long as it belongs to the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the character belongs to
+  /// * `character_id` The ID of the character to retrieve
+  ///
+  /// # Returns
+  /// The character, or `None` if it wasn't found
+  fn get_character_by_id(
+    &self,
+    world_id: &WorldID,
+    character_id: &CharacterID,
+  ) -> Option<CharacterEntity> {
+    let character = self.repository.get_character_by_id(world_id, character_id);
+
+    if character.is_none() {
+      warn!(
+        "No character found with ID {} in world {}",
+        character_id, world_id
+      );
+    }
+
+    character
+  }
+
+  /// Search the characters of a world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to search the characters of
+  /// * `filters` The filters to apply when searching
+  /// * `sorts` The sorts to apply to the results
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of characters
+  fn search_characters(
+    &self,
+    world_id: &WorldID,
+    filters: CharacterFilters,
+    sorts: Vec<SortField<CharacterSorts>>,
+    pagination: Pagination,
+  ) -> Page<CharacterEntity> {
+    self
+      .repository
+      .search_characters(world_id, filters, sorts, pagination)
+  }
+
+  /// Retrieve every character of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the characters of
+  ///
+  /// # Returns
+  /// The characters
+  fn list_all_characters(&self, world_id: &WorldID) -> Vec<CharacterEntity> {
+    self.repository.list_all_characters(world_id)
+  }
+
+  /// Create a new character
+  ///
+  /// # Arguments
+  /// * `character` The character data to create the character from
+  ///
+  /// # Returns
+  /// The character that was persisted
+  fn create_character(
+    &self,
+    character: CharacterData,
+  ) -> Result<CharacterEntity, CreateCharacterError> {
+    let created = self.repository.create_character(character)?;
+    Ok(created)
+  }
+
+  /// Update an existing character.
+  ///
+  /// This will load the character by ID, and then call a provided callback to mutate the character
+  /// before persisting the changes back to the database.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the character belongs to
+  /// * `character_id` The ID of the character to update
+  /// * `updater` The callback to mutate the character with
+  ///
+  /// # Returns
+  /// The newly updated character
+  fn update_character(
+    &self,
+    world_id: &WorldID,
+    character_id: &CharacterID,
+    updater: &mut dyn FnMut(CharacterData) -> Result<CharacterData, Box<dyn std::error::Error>>,
+  ) -> Result<CharacterEntity, UpdateCharacterError> {
+    let character = self
+      .get_character_by_id(world_id, character_id)
+      .ok_or(UpdateCharacterError::UnknownCharacter)?;
+
+    let world = character.data.world.clone();
+    let updated = updater(character.data).map_err(UpdateCharacterError::UpdateError)?;
+
+    let saved = self.repository.update_character(CharacterEntity {
+      identity: character.identity,
+      // Characters can't be moved between worlds
+      data: CharacterData { world, ..updated },
+    })?;
+    Ok(saved)
+  }
+
+  /// Delete a character
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the character belongs to
+  /// * `character_id` The ID of the character to delete
+  fn delete_character(
+    &self,
+    world_id: &WorldID,
+    character_id: &CharacterID,
+  ) -> Result<(), DeleteCharacterError> {
+    self.repository.delete_character(world_id, character_id)?;
+    Ok(())
+  }
+
+  /// Copy every character of one world into another
+  ///
+  /// # Arguments
+  /// * `from` The ID of the world to copy the characters from
+  /// * `to` The ID of the world to copy the characters into
+  /// * `ids` The map to record the new IDs of the characters in, by their original IDs
+  ///
+  /// # Returns
+  /// The characters that were created
+  fn copy_characters(
+    &self,
+    from: &WorldID,
+    to: &WorldID,
+    ids: &mut IdMap,
+  ) -> Result<Vec<CharacterEntity>, CreateCharacterError> {
+    let mut created = vec![];
+    for character in self.list_all_characters(from) {
+      let result = self.create_character(CharacterData {
+        world: to.clone(),
+        ..character.data
+      })?;
+
+      ids.insert(
+        "character",
+        &character.identity.id.to_string(),
+        &result.identity.id,
+      );
+      created.push(result);
+    }
+    info!(
+      "Copied {} characters from world {} into world {}",
+      created.len(),
+      from,
+      to
+    );
+
+    Ok(created)
+  }
+}
+
+impl From<PersistCharacterError> for CreateCharacterError {
+  fn from(e: PersistCharacterError) -> Self {
+    warn!("Error creating character: {}", e);
+    match e {
+      PersistCharacterError::WorldNotFound => CreateCharacterError::UnknownWorld,
+      _ => CreateCharacterError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistCharacterError> for UpdateCharacterError {
+  fn from(e: PersistCharacterError) -> Self {
+    warn!("Error updating character: {}", e);
+    match e {
+      PersistCharacterError::CharacterNotFound => UpdateCharacterError::UnknownCharacter,
+      PersistCharacterError::OptimisticLockFailure => UpdateCharacterError::OptimisticLockFailure,
+      _ => UpdateCharacterError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistCharacterError> for DeleteCharacterError {
+  fn from(e: PersistCharacterError) -> Self {
+    warn!("Error deleting character: {}", e);
+    match e {
+      PersistCharacterError::CharacterNotFound => DeleteCharacterError::UnknownCharacter,
+      _ => DeleteCharacterError::UnknownError,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use assert_matches::assert_matches;
+  use mockall::predicate::*;
+  use spectral::prelude::*;
+  use test_env_log::test;
+  use universe_entity::Identity;
+
+  fn character(world: WorldID) -> CharacterEntity {
+    CharacterEntity {
+      identity: Identity::default(),
+      data: CharacterData {
+        world,
+        name: "Frodo Baggins".to_owned(),
+        aliases: vec!["Mr Underhill".to_owned()],
+        summary: "A hobbit of the Shire".to_owned(),
+        biography: "".to_owned(),
+        attributes: vec![("Race".to_owned(), "Hobbit".to_owned())]
+          .into_iter()
+          .collect(),
+      },
+    }
+  }
+
+  #[test]
+  fn test_update_character_keeps_world() {
+    let world_id = WorldID::default();
+    let existing = character(world_id.clone());
+    let character_id = existing.identity.id.clone();
+
+    let mut repository = MockCharacterRepository::new();
+    let returned = existing;
+    repository
+      .expect_get_character_by_id()
+      .times(1)
+      .returning(move |_, _| Some(returned.clone()));
+    let expected_world = world_id.clone();
+    repository
+      .expect_update_character()
+      .withf(move |character| character.data.world == expected_world)
+      .times(1)
+      .returning(Ok);
+
+    let service = new_character_service(repository);
+
+    let updated = service.update_character(&world_id, &character_id, &mut |data| {
+      Ok(CharacterData {
+        world: Default::default(),
+        name: "Frodo".to_owned(),
+        ..data
+      })
+    });
+    assert_that(&updated)
+      .is_ok()
+      .map(|updated| &updated.data.name)
+      .is_equal_to(&"Frodo".to_owned());
+  }
+
+  #[test]
+  fn test_update_unknown_character() {
+    let mut repository = MockCharacterRepository::new();
+    repository
+      .expect_get_character_by_id()
+      .times(1)
+      .returning(|_, _| None);
+    repository.expect_update_character().never();
+
+    let service = new_character_service(repository);
+
+    let updated =
+      service.update_character(&Default::default(), &Default::default(), &mut |data| Ok(data));
+    assert_matches!(updated, Err(UpdateCharacterError::UnknownCharacter));
+  }
+
+  #[test]
+  fn test_copy_characters() {
+    let from = WorldID::default();
+    let to = WorldID::default();
+
+    let mut repository = MockCharacterRepository::new();
+    let existing = vec![character(from.clone()), character(from.clone())];
+    let original_id = existing[0].identity.id.to_string();
+    repository
+      .expect_list_all_characters()
+      .with(eq(from.clone()))
+      .times(1)
+      .returning(move |_| existing.clone());
+    repository
+      .expect_create_character()
+      .times(2)
+      .returning(|data| {
+        Ok(CharacterEntity {
+          identity: Identity::default(),
+          data,
+        })
+      });
+
+    let service = new_character_service(repository);
+
+    let mut ids = IdMap::default();
+    let copied = service.copy_characters(&from, &to, &mut ids).unwrap();
+    assert_that(&copied).has_length(2);
+    assert_that(&copied[0].data).is_equal_to(&character(to).data);
+    assert_that(&ids.get("character", &original_id))
+      .is_equal_to(Some(copied[0].identity.id.to_string().as_str()));
+  }
+}
@@ -0,0 +1,10 @@
+mod filters;
+mod implementation;
+pub mod repository;
+mod service;
+mod sorts;
+
+pub use filters::*;
+pub use implementation::*;
+pub use service::*;
+pub use sorts::*;
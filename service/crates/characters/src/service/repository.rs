@@ -0,0 +1,99 @@
+use super::{CharacterFilters, CharacterSorts};
+use crate::model::*;
+#[cfg(test)]
+use mockall::automock;
+use universe_entity::{Page, Pagination, SortField};
+use universe_worlds::WorldID;
+
+/// Repository that describes how to access character data
+#[cfg_attr(test, automock)]
+pub trait CharacterRepository {
+  /// Retrieve the character that has the given unique ID, as long as it belongs to the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the character belongs to
+  /// * `character_id` The ID of the character to retrieve
+  ///
+  /// # Returns
+  /// The character, or `None` if it wasn't found
+  fn get_character_by_id(
+    &self,
+    world_id: &WorldID,
+    character_id: &CharacterID,
+  ) -> Option<CharacterEntity>;
+
+  /// Search the characters of a world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to search the characters of
+  /// * `filters` The filters to apply when searching
+  /// * `sorts` The sorts to apply to the results
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of characters
+  fn search_characters(
+    &self,
+    world_id: &WorldID,
+    filters: CharacterFilters,
+    sorts: Vec<SortField<CharacterSorts>>,
+    pagination: Pagination,
+  ) -> Page<CharacterEntity>;
+
+  /// Retrieve every character of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the characters of
+  ///
+  /// # Returns
+  /// The characters
+  fn list_all_characters(&self, world_id: &WorldID) -> Vec<CharacterEntity>;
+
+  /// Create a new character record in the data store
+  ///
+  /// # Arguments
+  /// * `character` The character details to persist to the data store
+  ///
+  /// # Returns
+  /// The character that was persisted
+  fn create_character(
+    &self,
+    character: CharacterData,
+  ) -> Result<CharacterEntity, PersistCharacterError>;
+
+  /// Update an existing character record in the data store
+  ///
+  /// # Arguments
+  /// * `character` The character details to persist to the data store
+  ///
+  /// # Returns
+  /// The character that was persisted
+  fn update_character(
+    &self,
+    character: CharacterEntity,
+  ) -> Result<CharacterEntity, PersistCharacterError>;
+
+  /// Delete an existing character record from the data store
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the character belongs to
+  /// * `character_id` The ID of the character to delete
+  fn delete_character(
+    &self,
+    world_id: &WorldID,
+    character_id: &CharacterID,
+  ) -> Result<(), PersistCharacterError>;
+}
+
+/// Errors that can occur when persisting a character record
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum PersistCharacterError {
+  #[error("The world was not found")]
+  WorldNotFound,
+  #[error("The character was not found")]
+  CharacterNotFound,
+  #[error("The version of the character record did not match")]
+  OptimisticLockFailure,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
@@ -0,0 +1,137 @@
+use super::{CharacterFilters, CharacterSorts};
+use crate::model::*;
+use universe_entity::{IdMap, Page, Pagination, SortField};
+use universe_worlds::WorldID;
+
+/// The Character Service to allow interactions with character entities
+pub trait CharacterService: Send + Sync {
+  /// Retrieve the character that has the given unique ID, as long as it belongs to the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the character belongs to
+  /// * `character_id` The ID of the character to retrieve
+  ///
+  /// # Returns
+  /// The character, or `None` if it wasn't found
+  fn get_character_by_id(
+    &self,
+    world_id: &WorldID,
+    character_id: &CharacterID,
+  ) -> Option<CharacterEntity>;
+
+  /// Search the characters of a world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to search the characters of
+  /// * `filters` The filters to apply when searching
+  /// * `sorts` The sorts to apply to the results
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of characters
+  fn search_characters(
+    &self,
+    world_id: &WorldID,
+    filters: CharacterFilters,
+    sorts: Vec<SortField<CharacterSorts>>,
+    pagination: Pagination,
+  ) -> Page<CharacterEntity>;
+
+  /// Retrieve every character of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the characters of
+  ///
+  /// # Returns
+  /// The characters
+  fn list_all_characters(&self, world_id: &WorldID) -> Vec<CharacterEntity>;
+
+  /// Create a new character
+  ///
+  /// # Arguments
+  /// * `character` The character data to create the character from
+  ///
+  /// # Returns
+  /// The character that was persisted
+  fn create_character(
+    &self,
+    character: CharacterData,
+  ) -> Result<CharacterEntity, CreateCharacterError>;
+
+  /// Update an existing character.
+  ///
+  /// This will load the character by ID, and then call a provided callback to mutate the character
+  /// before persisting the changes back to the database.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the character belongs to
+  /// * `character_id` The ID of the character to update
+  /// * `updater` The callback to mutate the character with
+  ///
+  /// # Returns
+  /// The newly updated character
+  fn update_character(
+    &self,
+    world_id: &WorldID,
+    character_id: &CharacterID,
+    updater: &mut dyn FnMut(CharacterData) -> Result<CharacterData, Box<dyn std::error::Error>>,
+  ) -> Result<CharacterEntity, UpdateCharacterError>;
+
+  /// Delete a character
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the character belongs to
+  /// * `character_id` The ID of the character to delete
+  fn delete_character(
+    &self,
+    world_id: &WorldID,
+    character_id: &CharacterID,
+  ) -> Result<(), DeleteCharacterError>;
+
+  /// Copy every character of one world into another
+  ///
+  /// # Arguments
+  /// * `from` The ID of the world to copy the characters from
+  /// * `to` The ID of the world to copy the characters into
+  /// * `ids` The map to record the new IDs of the characters in, by their original IDs
+  ///
+  /// # Returns
+  /// The characters that were created
+  fn copy_characters(
+    &self,
+    from: &WorldID,
+    to: &WorldID,
+    ids: &mut IdMap,
+  ) -> Result<Vec<CharacterEntity>, CreateCharacterError>;
+}
+
+/// Enumeration of reasons why we failed to create a new character
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum CreateCharacterError {
+  #[error("The world was not found")]
+  UnknownWorld,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to update an existing character
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateCharacterError {
+  #[error("The character was not found")]
+  UnknownCharacter,
+  #[error("The version of the character record did not match")]
+  OptimisticLockFailure,
+  #[error("An error occurred updating the character details: {0}")]
+  UpdateError(Box<dyn std::error::Error>),
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to delete a character
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum DeleteCharacterError {
+  #[error("The character was not found")]
+  UnknownCharacter,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
@@ -0,0 +1,10 @@
+use strum_macros::{EnumString, EnumVariantNames};
+
+/// Fields that we can sort by when searching characters
+#[derive(Debug, PartialEq, EnumVariantNames, EnumString)]
+pub enum CharacterSorts {
+  Name,
+  Created,
+  Updated,
+  Id,
+}
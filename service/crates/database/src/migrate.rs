@@ -124,10 +124,9 @@ fn apply_migrations(
         } else {
             debug!("Processing file: {:?}", entry);
             let source: String = fs::read_to_string(&entry)?;
-            let commands = source.split(";\n");
 
-            for command in commands {
-                transaction.query(command, &[])?;
+            for command in split_commands(&source) {
+                transaction.query(command.as_str(), &[])?;
             }
 
             transaction.execute(
@@ -142,6 +141,42 @@ fn apply_migrations(
     Ok(applied)
 }
 
+/// Split the source of a migration file into the individual commands to execute.
+///
+/// Commands are separated by a semicolon at the end of a line. Semicolons inside of dollar-quoted
+/// strings, such as the bodies of functions, don't end the command, so functions can be written
+/// across several lines. Anything between the commands that is only whitespace, such as after
+/// the final semicolon, is dropped rather than being sent to the database.
+///
+/// # Arguments
+/// * `source` The source of the migration file
+///
+/// # Returns
+/// The commands, in the order that they appear
+fn split_commands(source: &str) -> Vec<String> {
+    let mut commands = vec![];
+    let mut current = String::new();
+
+    for part in source.split(";\n") {
+        current.push_str(part);
+
+        // An odd number of dollar quotes means that the split was inside of a quoted string
+        if current.matches("$$").count() % 2 == 0 {
+            if !current.trim().is_empty() {
+                commands.push(current);
+            }
+            current = String::new();
+        } else {
+            current.push_str(";\n");
+        }
+    }
+    if !current.trim().is_empty() {
+        commands.push(current);
+    }
+
+    commands
+}
+
 /// Error returned when migrating the database fails for some reason
 #[derive(Debug, thiserror::Error)]
 pub enum MigrationError {
@@ -164,6 +199,18 @@ mod tests {
     use test_env_log::test;
     use universe_test_database_container::TestDatabase;
 
+    #[test]
+    fn test_split_commands() {
+        let commands = split_commands(
+            "CREATE TABLE first(id BIGINT PRIMARY KEY);\n\nCREATE FUNCTION touch() RETURNS trigger AS $$\nBEGIN\n  NEW.id := NEW.id + 1;\n  RETURN NEW;\nEND\n$$ LANGUAGE plpgsql;\n  \n",
+        );
+
+        assert_that(&commands).is_equal_to(vec![
+            "CREATE TABLE first(id BIGINT PRIMARY KEY)".to_owned(),
+            "\nCREATE FUNCTION touch() RETURNS trigger AS $$\nBEGIN\n  NEW.id := NEW.id + 1;\n  RETURN NEW;\nEND\n$$ LANGUAGE plpgsql".to_owned(),
+        ]);
+    }
+
     #[test]
     fn test_invalid_migrations_glob() {
         let database = TestDatabase::new();
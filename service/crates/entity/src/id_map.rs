@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// Reference to an entity by its kind, such as "article" or "location", and the key that it was
+/// known by before it was copied or imported into a world
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct EntityKey {
+  pub kind: String,
+  pub key: String,
+}
+
+impl EntityKey {
+  /// Build a reference to an entity
+  ///
+  /// # Arguments
+  /// * `kind` The kind of entity
+  /// * `key` The key that the entity was known by beforehand
+  pub fn new(kind: &str, key: &str) -> Self {
+    EntityKey {
+      kind: kind.to_owned(),
+      key: key.to_owned(),
+    }
+  }
+}
+
+/// Record of the new IDs that entities were given when they were copied or imported into a world.
+///
+/// Entities are recorded by their kind along with the key that they were known by beforehand.
+/// When copying a world this is the ID of the original entity, and when importing an archive it
+/// is the ID that the entity had in the world that was exported. Anything that refers to other
+/// entities can then be pointed at the new ones.
+#[derive(Debug, Default, PartialEq)]
+pub struct IdMap {
+  ids: HashMap<(String, String), String>,
+}
+
+impl IdMap {
+  /// Record the new ID of an entity
+  ///
+  /// # Arguments
+  /// * `kind` The kind of entity
+  /// * `key` The key that the entity was known by beforehand
+  /// * `id` The new ID of the entity
+  pub fn insert(&mut self, kind: &str, key: &str, id: &dyn Display) {
+    self
+      .ids
+      .insert((kind.to_owned(), key.to_owned()), id.to_string());
+  }
+
+  /// Look up the new ID of an entity
+  ///
+  /// # Arguments
+  /// * `kind` The kind of entity
+  /// * `key` The key that the entity was known by beforehand
+  ///
+  /// # Returns
+  /// The new ID of the entity, or `None` if it hasn't been recorded
+  pub fn get(&self, kind: &str, key: &str) -> Option<&str> {
+    self
+      .ids
+      .get(&(kind.to_owned(), key.to_owned()))
+      .map(String::as_str)
+  }
+
+  /// Look up the new ID of the entity that a key refers to
+  ///
+  /// # Arguments
+  /// * `entity` The entity to look up
+  ///
+  /// # Returns
+  /// The new ID of the entity, or `None` if it hasn't been recorded
+  pub fn resolve(&self, entity: &EntityKey) -> Option<&str> {
+    self.get(&entity.kind, &entity.key)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use spectral::prelude::*;
+
+  #[test]
+  fn test_lookup_by_kind_and_key() {
+    let mut ids = IdMap::default();
+    ids.insert("article", "first", &"1234");
+    ids.insert("location", "first", &"5678");
+
+    assert_that(&ids.get("article", "first")).is_equal_to(Some("1234"));
+    assert_that(&ids.get("location", "first")).is_equal_to(Some("5678"));
+    assert_that(&ids.get("character", "first")).is_none();
+    assert_that(&ids.get("article", "second")).is_none();
+
+    assert_that(&ids.resolve(&EntityKey::new("location", "first"))).is_equal_to(Some("5678"));
+  }
+}
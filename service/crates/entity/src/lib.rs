@@ -1,7 +1,9 @@
+mod id_map;
 mod identity;
 mod pagination;
 mod sorts;
 
+pub use id_map::*;
 pub use identity::*;
 pub use pagination::*;
 pub use sorts::*;
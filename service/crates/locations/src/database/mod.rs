@@ -0,0 +1,870 @@
+use crate::{model::*, service::repository::*, LocationParentFilter};
+use chrono::Utc;
+use postgres::types::ToSql;
+use std::error::Error;
+use tracing::{debug, warn};
+use universe_database::Database;
+use universe_entity::{Identity, Page, Pagination};
+use universe_worlds::WorldID;
+use uuid::Uuid;
+
+impl From<&postgres::Row> for LocationEntity {
+  fn from(row: &postgres::Row) -> Self {
+    let x: Option<f64> = row.get("x");
+    let y: Option<f64> = row.get("y");
+
+    LocationEntity {
+      identity: Identity {
+        id: row.get("location_id"),
+        version: row.get("version"),
+        created: row.get("created"),
+        updated: row.get("updated"),
+      },
+      data: LocationData {
+        world: row.get("world_id"),
+        parent: row.get("parent_id"),
+        name: row.get("name"),
+        kind: row.get("kind"),
+        description: row.get("description"),
+        // The database only allows both coordinates or neither
+        coordinates: match (x, y) {
+          (Some(x), Some(y)) => Some(Coordinates { x, y }),
+          _ => None,
+        },
+      },
+    }
+  }
+}
+
+impl From<&postgres::Row> for LocationDescendant {
+  fn from(row: &postgres::Row) -> Self {
+    let depth: i32 = row.get("depth");
+
+    LocationDescendant {
+      depth: depth as u32,
+      location: row.into(),
+    }
+  }
+}
+
+impl LocationRepository for Database {
+  /// Retrieve the location that has the given unique ID, as long as it belongs to the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the location belongs to
+  /// * `location_id` The ID of the location to retrieve
+  ///
+  /// # Returns
+  /// The location, or `None` if it wasn't found
+  fn get_location_by_id(
+    &self,
+    world_id: &WorldID,
+    location_id: &LocationID,
+  ) -> Option<LocationEntity> {
+    let mut client = self.client().unwrap();
+
+    let location = client
+      .query(
+        "SELECT * FROM locations WHERE world_id = $1 AND location_id = $2",
+        &[&world_id, &location_id],
+      )
+      .map_err(|e| {
+        warn!("Error loading location from database: {}", e);
+        e
+      })
+      .ok()
+      .and_then(|rows| rows.get(0).map(|row| row.into()));
+
+    debug!("Location for ID {}: {:?}", location_id, location);
+    location
+  }
+
+  /// Retrieve a page of the locations of a world, sorted by name
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the locations of
+  /// * `parent` Where in the tree of locations to list locations from, or `None` for all of them
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of locations
+  fn list_locations(
+    &self,
+    world_id: &WorldID,
+    parent: Option<LocationParentFilter>,
+    pagination: Pagination,
+  ) -> Page<LocationEntity> {
+    let mut client = self.client().unwrap();
+
+    let mut binds: Vec<&(dyn ToSql + Sync)> = vec![world_id];
+    let clause = match &parent {
+      None => "",
+      Some(LocationParentFilter::TopLevel) => " AND parent_id IS NULL",
+      Some(LocationParentFilter::Children(parent_id)) => {
+        binds.push(parent_id);
+        " AND parent_id = $2"
+      }
+    };
+
+    let count_sql = format!(
+      "SELECT COUNT(*) AS total FROM locations WHERE world_id = $1{}",
+      clause
+    );
+    let total: i64 = client
+      .query_one(count_sql.as_str(), &binds)
+      .map_err(|e| {
+        warn!("Error counting locations in database: {}", e);
+        e
+      })
+      .map(|row| row.get("total"))
+      .unwrap_or(0);
+
+    let select_sql = format!(
+      "SELECT * FROM locations WHERE world_id = $1{}
+        ORDER BY UPPER(name) ASC, location_id ASC
+        LIMIT ${} OFFSET ${}",
+      clause,
+      binds.len() + 1,
+      binds.len() + 2
+    );
+    let limit = i64::from(pagination.limit);
+    let offset = i64::from(pagination.offset);
+    binds.push(&limit);
+    binds.push(&offset);
+
+    let entries: Vec<LocationEntity> = client
+      .query(select_sql.as_str(), &binds)
+      .map_err(|e| {
+        warn!("Error listing locations in database: {}", e);
+        e
+      })
+      .map(|rows| rows.iter().map(|row| row.into()).collect())
+      .unwrap_or_default();
+
+    let page = Page {
+      entries,
+      total: total as u32,
+      offset: pagination.offset,
+    };
+    debug!("Locations of world {}: {:?}", world_id, page);
+
+    page
+  }
+
+  /// Retrieve every location of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the locations of
+  ///
+  /// # Returns
+  /// The locations
+  fn list_all_locations(&self, world_id: &WorldID) -> Vec<LocationEntity> {
+    let mut client = self.client().unwrap();
+
+    let locations = client
+      .query(
+        "SELECT * FROM locations WHERE world_id = $1 ORDER BY created ASC, location_id ASC",
+        &[&world_id],
+      )
+      .map_err(|e| {
+        warn!("Error listing locations in database: {}", e);
+        e
+      })
+      .map(|rows| rows.iter().map(|row| row.into()).collect())
+      .unwrap_or_default();
+
+    debug!("All locations of world {}: {:?}", world_id, locations);
+    locations
+  }
+
+  /// Retrieve every location that the given location sits inside
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the location belongs to
+  /// * `location_id` The ID of the location to start from
+  ///
+  /// # Returns
+  /// The ancestors of the location, starting from the top of the tree and ending with its parent
+  fn get_location_ancestors(
+    &self,
+    world_id: &WorldID,
+    location_id: &LocationID,
+  ) -> Vec<LocationEntity> {
+    let mut client = self.client().unwrap();
+
+    // The path of visited locations stops the walk from going round forever if a cycle ever
+    // manages to get into the data
+    let ancestors = client
+      .query(
+        "WITH RECURSIVE ancestors(location_id, parent_id, depth, path) AS (
+            SELECT location_id, parent_id, 0, ARRAY[location_id] FROM locations
+            WHERE world_id = $1 AND location_id = $2
+          UNION ALL
+            SELECT locations.location_id, locations.parent_id, ancestors.depth + 1, ancestors.path || locations.location_id
+            FROM locations
+            JOIN ancestors ON locations.location_id = ancestors.parent_id
+            WHERE NOT locations.location_id = ANY(ancestors.path)
+          )
+          SELECT locations.* FROM locations
+          JOIN ancestors ON locations.location_id = ancestors.location_id
+          WHERE ancestors.depth > 0
+          ORDER BY ancestors.depth DESC",
+        &[&world_id, &location_id],
+      )
+      .map_err(|e| {
+        warn!("Error loading location ancestors from database: {}", e);
+        e
+      })
+      .map(|rows| rows.iter().map(|row| row.into()).collect())
+      .unwrap_or_default();
+
+    debug!("Ancestors of location {}: {:?}", location_id, ancestors);
+    ancestors
+  }
+
+  /// Retrieve every location that sits somewhere inside the given location
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the location belongs to
+  /// * `location_id` The ID of the location to start from
+  /// * `max_depth` How many levels of the tree to descend, or `None` for all of them
+  ///
+  /// # Returns
+  /// The descendants of the location, shallowest first and then sorted by name
+  fn get_location_descendants(
+    &self,
+    world_id: &WorldID,
+    location_id: &LocationID,
+    max_depth: Option<u32>,
+  ) -> Vec<LocationDescendant> {
+    let mut client = self.client().unwrap();
+
+    let max_depth = max_depth.map(|depth| depth as i32);
+    let descendants = client
+      .query(
+        "WITH RECURSIVE descendants(location_id, depth, path) AS (
+            SELECT location_id, 1, ARRAY[location_id] FROM locations
+            WHERE world_id = $1 AND parent_id = $2
+          UNION ALL
+            SELECT locations.location_id, descendants.depth + 1, descendants.path || locations.location_id
+            FROM locations
+            JOIN descendants ON locations.parent_id = descendants.location_id
+            WHERE NOT locations.location_id = ANY(descendants.path)
+            AND ($3::INTEGER IS NULL OR descendants.depth < $3::INTEGER)
+          )
+          SELECT locations.*, descendants.depth FROM locations
+          JOIN descendants ON locations.location_id = descendants.location_id
+          ORDER BY descendants.depth ASC, UPPER(locations.name) ASC, locations.location_id ASC",
+        &[&world_id, &location_id, &max_depth],
+      )
+      .map_err(|e| {
+        warn!("Error loading location descendants from database: {}", e);
+        e
+      })
+      .map(|rows| rows.iter().map(|row| row.into()).collect())
+      .unwrap_or_default();
+
+    debug!("Descendants of location {}: {:?}", location_id, descendants);
+    descendants
+  }
+
+  /// Create a new location record in the data store
+  ///
+  /// # Arguments
+  /// * `location` The location details to persist to the data store
+  ///
+  /// # Returns
+  /// The location that was persisted
+  fn create_location(
+    &self,
+    location: LocationData,
+  ) -> Result<LocationEntity, PersistLocationError> {
+    debug!("Creating record for location: {:?}", location);
+
+    let mut client = self.client().unwrap();
+
+    let new_id = LocationID::default();
+    let new_version = Uuid::new_v4();
+    let new_updated = Utc::now();
+
+    let result: LocationEntity = client
+      .query(
+        "INSERT INTO locations(location_id, version, created, updated, world_id, parent_id, name, kind, description, x, y)
+          VALUES ($1, $2, $3, $3, $4, $5, $6, $7, $8, $9, $10)
+          RETURNING *",
+        &[
+          &new_id,
+          &new_version,
+          &new_updated,
+          &location.world,
+          &location.parent,
+          &location.name,
+          &location.kind,
+          &location.description,
+          &location.coordinates.map(|coordinates| coordinates.x),
+          &location.coordinates.map(|coordinates| coordinates.y),
+        ],
+      )
+      .map(|rows| rows.get(0).unwrap().into())?;
+
+    debug!("Created record for location: {:?}", result);
+    Ok(result)
+  }
+
+  /// Update an existing location record in the data store. The location may not be moved
+  /// inside itself or any of the locations within it
+  ///
+  /// # Arguments
+  /// * `location` The location details to persist to the data store
+  ///
+  /// # Returns
+  /// The location that was persisted
+  fn update_location(
+    &self,
+    location: LocationEntity,
+  ) -> Result<LocationEntity, PersistLocationError> {
+    debug!("Updating record for location: {:?}", location);
+
+    let mut client = self.client().unwrap();
+    let mut transaction = client.transaction().unwrap();
+
+    if let Some(parent) = &location.data.parent {
+      lock_new_ancestors(
+        &mut transaction,
+        &location.data.world,
+        &location.identity.id,
+        parent,
+      )?;
+    }
+
+    let new_version = Uuid::new_v4();
+    let new_updated = Utc::now();
+
+    let rows = transaction.query(
+      "UPDATE locations SET parent_id = $1, name = $2, kind = $3, description = $4, x = $5, y = $6, version = $7, updated = $8
+          WHERE location_id = $9
+          AND world_id = $10
+          AND version = $11
+          RETURNING *",
+      &[
+        &location.data.parent,
+        &location.data.name,
+        &location.data.kind,
+        &location.data.description,
+        &location.data.coordinates.map(|coordinates| coordinates.x),
+        &location.data.coordinates.map(|coordinates| coordinates.y),
+        &new_version,
+        &new_updated,
+        &location.identity.id,
+        &location.data.world,
+        &location.identity.version,
+      ],
+    )?;
+
+    if let Some(row) = rows.get(0) {
+      let result = row.into();
+      transaction.commit()?;
+      debug!("Updated record for location: {:?}", result);
+      Ok(result)
+    } else {
+      let location_found = transaction.query(
+        "SELECT version FROM locations WHERE location_id = $1 AND world_id = $2",
+        &[&location.identity.id, &location.data.world],
+      )?;
+
+      if location_found.is_empty() {
+        warn!(
+          "Attempted to update location {} that wasn't found",
+          location.identity.id
+        );
+        Err(PersistLocationError::LocationNotFound)
+      } else {
+        let old_version: Uuid = location_found.get(0).unwrap().get("version");
+
+        warn!(
+          "Attempted to update location {}. Expected version {} but database had {}",
+          location.identity.id, location.identity.version, old_version
+        );
+        Err(PersistLocationError::OptimisticLockFailure)
+      }
+    }
+  }
+
+  /// Delete an existing location record from the data store
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the location belongs to
+  /// * `location_id` The ID of the location to delete
+  fn delete_location(
+    &self,
+    world_id: &WorldID,
+    location_id: &LocationID,
+  ) -> Result<(), PersistLocationError> {
+    debug!("Deleting location {} from world {}", location_id, world_id);
+
+    let mut client = self.client().unwrap();
+
+    let deleted = client
+      .execute(
+        "DELETE FROM locations WHERE world_id = $1 AND location_id = $2",
+        &[&world_id, &location_id],
+      )
+      .map_err(|e| match PersistLocationError::from(e) {
+        // The only reference that can stop a location being deleted is from another location
+        PersistLocationError::ParentNotFound => PersistLocationError::HasChildren,
+        e => e,
+      })?;
+
+    if deleted == 0 {
+      warn!(
+        "Attempted to delete location {} that wasn't found",
+        location_id
+      );
+      Err(PersistLocationError::LocationNotFound)
+    } else {
+      Ok(())
+    }
+  }
+}
+
+/// Lock the location that is being moved along with every location above its new parent, and make
+/// sure that it isn't being moved inside itself.
+///
+/// Any other move that touches the same part of the tree has to wait for these locks, so two moves
+/// can't each pass the check and then form a cycle between them. The ancestors are read again
+/// once they are locked, since they may have been moved while we were waiting for them.
+///
+/// # Arguments
+/// * `transaction` The transaction to take the locks in
+/// * `world_id` The ID of the world that the location belongs to
+/// * `location_id` The ID of the location that is being moved
+/// * `parent_id` The ID of the location that it is being moved inside
+fn lock_new_ancestors(
+  transaction: &mut postgres::Transaction,
+  world_id: &WorldID,
+  location_id: &LocationID,
+  parent_id: &LocationID,
+) -> Result<(), PersistLocationError> {
+  let mut locked: Vec<LocationID> = vec![];
+
+  loop {
+    let mut ancestors: Vec<LocationID> = transaction
+      .query(
+        "WITH RECURSIVE ancestors(location_id, parent_id, path) AS (
+            SELECT location_id, parent_id, ARRAY[location_id] FROM locations
+            WHERE world_id = $1 AND location_id = $2
+          UNION ALL
+            SELECT locations.location_id, locations.parent_id, ancestors.path || locations.location_id
+            FROM locations
+            JOIN ancestors ON locations.location_id = ancestors.parent_id
+            WHERE NOT locations.location_id = ANY(ancestors.path)
+          )
+          SELECT location_id FROM ancestors",
+        &[&world_id, &parent_id],
+      )?
+      .iter()
+      .map(|row| row.get("location_id"))
+      .collect();
+
+    if ancestors.contains(location_id) {
+      warn!(
+        "Moving location {} inside {} would create a cycle",
+        location_id, parent_id
+      );
+      return Err(PersistLocationError::ParentCycle);
+    }
+
+    ancestors.push(location_id.clone());
+    if ancestors.iter().all(|ancestor| locked.contains(ancestor)) {
+      return Ok(());
+    }
+
+    transaction.query(
+      "SELECT location_id FROM locations
+          WHERE world_id = $1 AND location_id = ANY($2)
+          ORDER BY location_id
+          FOR UPDATE",
+      &[&world_id, &ancestors],
+    )?;
+    locked = ancestors;
+  }
+}
+
+impl From<postgres::Error> for PersistLocationError {
+  fn from(error: postgres::Error) -> Self {
+    warn!("Error persisting location in database: {:?}", error);
+
+    error
+      .source()
+      .and_then(|e| e.downcast_ref::<postgres::error::DbError>())
+      .map(|e| match e.constraint() {
+        Some("locations_world_id_fkey") => PersistLocationError::WorldNotFound,
+        Some("locations_world_id_parent_id_fkey") => PersistLocationError::ParentNotFound,
+        _ => PersistLocationError::UnknownError,
+      })
+      .unwrap_or(PersistLocationError::UnknownError)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use spectral::prelude::*;
+  use test_env_log::test;
+  use universe_test_database_wrapper::TestDatabaseWrapper;
+  use universe_testdata::{seed, Location, User, World};
+
+  /// Seed a world with a small tree of locations into the database
+  ///
+  /// The locations are "Middle-earth", with "Eriador" inside it, "The Shire" inside that and
+  /// "Hobbiton" inside that. "Valinor" sits alongside "Middle-earth"
+  fn seed_locations(database: &TestDatabaseWrapper) -> (World, Vec<Location>) {
+    let user: User = Default::default();
+    let world = World {
+      owner_id: user.user_id,
+      ..Default::default()
+    };
+    let middle_earth = Location {
+      world_id: world.world_id,
+      name: "Middle-earth".to_owned(),
+      kind: "Continent".to_owned(),
+      ..Default::default()
+    };
+    let eriador = Location {
+      world_id: world.world_id,
+      parent_id: Some(middle_earth.location_id),
+      name: "Eriador".to_owned(),
+      kind: "Region".to_owned(),
+      ..Default::default()
+    };
+    let shire = Location {
+      world_id: world.world_id,
+      parent_id: Some(eriador.location_id),
+      name: "The Shire".to_owned(),
+      kind: "Region".to_owned(),
+      ..Default::default()
+    };
+    let hobbiton = Location {
+      world_id: world.world_id,
+      parent_id: Some(shire.location_id),
+      name: "Hobbiton".to_owned(),
+      kind: "City".to_owned(),
+      x: Some(120.5),
+      y: Some(80.0),
+      ..Default::default()
+    };
+    let valinor = Location {
+      world_id: world.world_id,
+      name: "Valinor".to_owned(),
+      kind: "Continent".to_owned(),
+      ..Default::default()
+    };
+    let locations = vec![middle_earth, eriador, shire, hobbiton, valinor];
+
+    seed(
+      &database,
+      vec![
+        &user,
+        &world,
+        &locations[0],
+        &locations[1],
+        &locations[2],
+        &locations[3],
+        &locations[4],
+      ],
+    );
+
+    (world, locations)
+  }
+
+  fn names<'a>(locations: impl IntoIterator<Item = &'a LocationEntity>) -> Vec<String> {
+    locations
+      .into_iter()
+      .map(|location| location.data.name.clone())
+      .collect()
+  }
+
+  const ALL: Pagination = Pagination {
+    offset: 0,
+    limit: 10,
+  };
+
+  #[test]
+  fn test_get_location() {
+    let database = TestDatabaseWrapper::new();
+    let (world, locations) = seed_locations(&database);
+
+    let location = database.wrapper.get_location_by_id(
+      &WorldID::from_uuid(world.world_id),
+      &LocationID::from_uuid(locations[3].location_id),
+    );
+    assert_that(&location)
+      .is_some()
+      .is_equal_to(LocationEntity::from(locations[3].clone()));
+  }
+
+  #[test]
+  fn test_get_location_wrong_world() {
+    let database = TestDatabaseWrapper::new();
+    let (_, locations) = seed_locations(&database);
+
+    let location = database.wrapper.get_location_by_id(
+      &Default::default(),
+      &LocationID::from_uuid(locations[1].location_id),
+    );
+    assert_that(&location).is_none();
+  }
+
+  #[test]
+  fn test_list_locations() {
+    let database = TestDatabaseWrapper::new();
+    let (world, locations) = seed_locations(&database);
+    let world_id = WorldID::from_uuid(world.world_id);
+
+    let all = database.wrapper.list_locations(&world_id, None, ALL);
+    assert_that(&names(&all.entries)).is_equal_to(vec![
+      "Eriador".to_owned(),
+      "Hobbiton".to_owned(),
+      "Middle-earth".to_owned(),
+      "The Shire".to_owned(),
+      "Valinor".to_owned(),
+    ]);
+    assert_that(&all.total).is_equal_to(5);
+
+    let top_level =
+      database
+        .wrapper
+        .list_locations(&world_id, Some(LocationParentFilter::TopLevel), ALL);
+    assert_that(&names(&top_level.entries))
+      .is_equal_to(vec!["Middle-earth".to_owned(), "Valinor".to_owned()]);
+
+    let children = database.wrapper.list_locations(
+      &world_id,
+      Some(LocationParentFilter::Children(LocationID::from_uuid(
+        locations[0].location_id,
+      ))),
+      ALL,
+    );
+    assert_that(&names(&children.entries)).is_equal_to(vec!["Eriador".to_owned()]);
+    assert_that(&children.total).is_equal_to(1);
+  }
+
+  #[test]
+  fn test_get_location_ancestors() {
+    let database = TestDatabaseWrapper::new();
+    let (world, locations) = seed_locations(&database);
+    let world_id = WorldID::from_uuid(world.world_id);
+
+    let ancestors = database
+      .wrapper
+      .get_location_ancestors(&world_id, &LocationID::from_uuid(locations[3].location_id));
+    assert_that(&names(&ancestors)).is_equal_to(vec![
+      "Middle-earth".to_owned(),
+      "Eriador".to_owned(),
+      "The Shire".to_owned(),
+    ]);
+
+    let ancestors = database
+      .wrapper
+      .get_location_ancestors(&world_id, &LocationID::from_uuid(locations[0].location_id));
+    assert_that(&ancestors).is_empty();
+  }
+
+  #[test]
+  fn test_get_location_descendants() {
+    let database = TestDatabaseWrapper::new();
+    let (world, locations) = seed_locations(&database);
+    let world_id = WorldID::from_uuid(world.world_id);
+    let middle_earth = LocationID::from_uuid(locations[0].location_id);
+
+    let descendants = database
+      .wrapper
+      .get_location_descendants(&world_id, &middle_earth, None);
+    assert_that(&names(descendants.iter().map(|d| &d.location))).is_equal_to(vec![
+      "Eriador".to_owned(),
+      "The Shire".to_owned(),
+      "Hobbiton".to_owned(),
+    ]);
+    assert_that(&descendants.iter().map(|d| d.depth).collect::<Vec<_>>())
+      .is_equal_to(vec![1, 2, 3]);
+
+    let descendants = database
+      .wrapper
+      .get_location_descendants(&world_id, &middle_earth, Some(2));
+    assert_that(&names(descendants.iter().map(|d| &d.location)))
+      .is_equal_to(vec!["Eriador".to_owned(), "The Shire".to_owned()]);
+  }
+
+  #[test]
+  fn test_create_location() {
+    let database = TestDatabaseWrapper::new();
+    let (world, locations) = seed_locations(&database);
+
+    let location = LocationData {
+      world: WorldID::from_uuid(world.world_id),
+      parent: Some(LocationID::from_uuid(locations[3].location_id)),
+      name: "Bag End".to_owned(),
+      kind: "Building".to_owned(),
+      description: "A *hobbit hole*".to_owned(),
+      coordinates: Some(Coordinates { x: 121.0, y: 79.5 }),
+    };
+    let created = database.wrapper.create_location(location.clone());
+    assert_that(&created)
+      .is_ok()
+      .map(|created| &created.data)
+      .is_equal_to(&location);
+  }
+
+  #[test]
+  fn test_create_location_parent_in_other_world() {
+    let database = TestDatabaseWrapper::new();
+    let (world, locations) = seed_locations(&database);
+    let other_world = World {
+      owner_id: world.owner_id,
+      slug: "other-world".to_owned(),
+      ..Default::default()
+    };
+    seed(&database, vec![&other_world]);
+
+    let created = database.wrapper.create_location(LocationData {
+      world: WorldID::from_uuid(other_world.world_id),
+      parent: Some(LocationID::from_uuid(locations[0].location_id)),
+      name: "Mordor".to_owned(),
+      kind: "".to_owned(),
+      description: "".to_owned(),
+      coordinates: None,
+    });
+    assert_that(&created)
+      .is_err()
+      .is_equal_to(PersistLocationError::ParentNotFound);
+  }
+
+  #[test]
+  fn test_create_location_unknown_world() {
+    let database = TestDatabaseWrapper::new();
+
+    let created = database.wrapper.create_location(LocationData {
+      world: Default::default(),
+      parent: None,
+      name: "Mordor".to_owned(),
+      kind: "".to_owned(),
+      description: "".to_owned(),
+      coordinates: None,
+    });
+    assert_that(&created)
+      .is_err()
+      .is_equal_to(PersistLocationError::WorldNotFound);
+  }
+
+  #[test]
+  fn test_update_location() {
+    let database = TestDatabaseWrapper::new();
+    let (world, locations) = seed_locations(&database);
+    let world_id = WorldID::from_uuid(world.world_id);
+
+    let mut location = LocationEntity::from(locations[2].clone());
+    location.data.parent = Some(LocationID::from_uuid(locations[4].location_id));
+    location.data.coordinates = Some(Coordinates { x: 1.0, y: 2.0 });
+
+    let updated = database.wrapper.update_location(location.clone());
+    assert_that(&updated)
+      .is_ok()
+      .map(|updated| &updated.data)
+      .is_equal_to(&location.data);
+    assert_that(&updated.unwrap().identity.version).is_not_equal_to(location.identity.version);
+
+    // Everything inside the location moves along with it
+    let ancestors = database
+      .wrapper
+      .get_location_ancestors(&world_id, &LocationID::from_uuid(locations[3].location_id));
+    assert_that(&names(&ancestors)).is_equal_to(vec!["Valinor".to_owned(), "The Shire".to_owned()]);
+  }
+
+  #[test]
+  fn test_update_location_inside_descendant() {
+    let database = TestDatabaseWrapper::new();
+    let (_, locations) = seed_locations(&database);
+
+    let mut location = LocationEntity::from(locations[1].clone());
+    location.data.parent = Some(LocationID::from_uuid(locations[3].location_id));
+
+    let updated = database.wrapper.update_location(location);
+    assert_that(&updated)
+      .is_err()
+      .is_equal_to(PersistLocationError::ParentCycle);
+  }
+
+  #[test]
+  fn test_update_location_inside_itself() {
+    let database = TestDatabaseWrapper::new();
+    let (_, locations) = seed_locations(&database);
+
+    let mut location = LocationEntity::from(locations[1].clone());
+    location.data.parent = Some(location.identity.id.clone());
+
+    let updated = database.wrapper.update_location(location);
+    assert_that(&updated)
+      .is_err()
+      .is_equal_to(PersistLocationError::ParentCycle);
+  }
+
+  #[test]
+  fn test_update_location_wrong_version() {
+    let database = TestDatabaseWrapper::new();
+    let (_, locations) = seed_locations(&database);
+
+    let mut location = LocationEntity::from(locations[2].clone());
+    location.identity.version = Uuid::new_v4();
+
+    let updated = database.wrapper.update_location(location);
+    assert_that(&updated)
+      .is_err()
+      .is_equal_to(PersistLocationError::OptimisticLockFailure);
+  }
+
+  #[test]
+  fn test_update_unknown_location() {
+    let database = TestDatabaseWrapper::new();
+    let (world, _) = seed_locations(&database);
+
+    let location = LocationEntity::from(Location {
+      world_id: world.world_id,
+      ..Default::default()
+    });
+
+    let updated = database.wrapper.update_location(location);
+    assert_that(&updated)
+      .is_err()
+      .is_equal_to(PersistLocationError::LocationNotFound);
+  }
+
+  #[test]
+  fn test_delete_location() {
+    let database = TestDatabaseWrapper::new();
+    let (world, locations) = seed_locations(&database);
+    let world_id = WorldID::from_uuid(world.world_id);
+    let location_id = LocationID::from_uuid(locations[4].location_id);
+
+    let deleted = database.wrapper.delete_location(&world_id, &location_id);
+    assert_that(&deleted).is_ok();
+
+    let deleted = database.wrapper.delete_location(&world_id, &location_id);
+    assert_that(&deleted)
+      .is_err()
+      .is_equal_to(PersistLocationError::LocationNotFound);
+  }
+
+  #[test]
+  fn test_delete_location_with_children() {
+    let database = TestDatabaseWrapper::new();
+    let (world, locations) = seed_locations(&database);
+
+    let deleted = database.wrapper.delete_location(
+      &WorldID::from_uuid(world.world_id),
+      &LocationID::from_uuid(locations[0].location_id),
+    );
+    assert_that(&deleted)
+      .is_err()
+      .is_equal_to(PersistLocationError::HasChildren);
+  }
+}
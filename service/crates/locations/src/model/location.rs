@@ -0,0 +1,68 @@
+use crate::LocationID;
+use serde::{Deserialize, Serialize};
+use universe_entity::Identity;
+use universe_worlds::WorldID;
+
+/// The position of a location on the map of its world
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub struct Coordinates {
+  pub x: f64,
+  pub y: f64,
+}
+
+/// Struct to represent the data about a single Location
+#[derive(Debug, PartialEq, Clone)]
+pub struct LocationData {
+  /// The world that the location belongs to
+  pub world: WorldID,
+  /// The location that this one sits inside, or `None` if it is at the top level of the world
+  pub parent: Option<LocationID>,
+  pub name: String,
+  /// What sort of location this is, such as "continent" or "city". This is free text, and may be
+  /// empty
+  pub kind: String,
+  /// The description of the location, as CommonMark source
+  pub description: String,
+  /// Where the location is on the map of the world, if anywhere
+  pub coordinates: Option<Coordinates>,
+}
+
+/// Type to represent the entity that is a persisted location record
+#[derive(Debug, PartialEq, Clone)]
+pub struct LocationEntity {
+  pub identity: Identity<LocationID>,
+  pub data: LocationData,
+}
+
+/// A location that sits somewhere beneath another location
+#[derive(Debug, PartialEq, Clone)]
+pub struct LocationDescendant {
+  /// How far beneath the other location this one is, where direct children have a depth of 1
+  pub depth: u32,
+  pub location: LocationEntity,
+}
+
+#[cfg(test)]
+impl From<universe_testdata::Location> for LocationEntity {
+  fn from(location: universe_testdata::Location) -> LocationEntity {
+    LocationEntity {
+      identity: Identity {
+        id: LocationID::from_uuid(location.location_id),
+        version: location.version,
+        created: location.created,
+        updated: location.updated,
+      },
+      data: LocationData {
+        world: WorldID::from_uuid(location.world_id),
+        parent: location.parent_id.map(LocationID::from_uuid),
+        name: location.name.clone(),
+        kind: location.kind.clone(),
+        description: location.description.clone(),
+        coordinates: match (location.x, location.y) {
+          (Some(x), Some(y)) => Some(Coordinates { x, y }),
+          _ => None,
+        },
+      },
+    }
+  }
+}
@@ -0,0 +1,151 @@
+use crate::Coordinates;
+use std::collections::HashSet;
+
+/// A location that is to be imported into a world from outside of the system.
+///
+/// Locations being imported refer to each other by a key that only needs to be unique amongst the
+/// locations being imported together. They are all given new IDs when they are created.
+#[derive(Debug, PartialEq, Clone)]
+pub struct LocationImport {
+  pub key: String,
+  /// The key of the location that this one sits inside, if any
+  pub parent: Option<String>,
+  pub name: String,
+  pub kind: String,
+  pub description: String,
+  pub coordinates: Option<Coordinates>,
+}
+
+/// Errors that can happen when preparing a set of locations to be imported
+#[derive(Debug, PartialEq, Clone, thiserror::Error)]
+pub enum LocationImportError {
+  #[error("The location key {0} is used more than once")]
+  DuplicateKey(String),
+  #[error("The parent of location {0} is not being imported")]
+  UnknownParent(String),
+  #[error("Location {0} is its own ancestor")]
+  ParentCycle(String),
+}
+
+/// Put a set of locations that are to be imported into an order where every location comes after
+/// its parent, so that they can be created one at a time. Locations otherwise keep the order that
+/// they were provided in.
+///
+/// # Arguments
+/// * `locations` The locations to put in order
+///
+/// # Returns
+/// The ordered locations, or an error if the locations don't form a tree
+pub fn order_location_imports(
+  locations: Vec<LocationImport>,
+) -> Result<Vec<LocationImport>, LocationImportError> {
+  let mut keys = HashSet::new();
+  for location in &locations {
+    if !keys.insert(location.key.as_str()) {
+      return Err(LocationImportError::DuplicateKey(location.key.clone()));
+    }
+  }
+
+  if let Some(orphan) = locations.iter().find(|location| match &location.parent {
+    Some(parent) => !keys.contains(parent.as_str()),
+    None => false,
+  }) {
+    return Err(LocationImportError::UnknownParent(orphan.key.clone()));
+  }
+
+  let mut placed = HashSet::new();
+  let mut ordered = Vec::with_capacity(locations.len());
+  let mut pending = locations;
+
+  while !pending.is_empty() {
+    let (ready, waiting): (Vec<_>, Vec<_>) =
+      pending
+        .into_iter()
+        .partition(|location| match &location.parent {
+          Some(parent) => placed.contains(parent),
+          None => true,
+        });
+
+    // If nothing could be placed then every remaining location is waiting on another one
+    if ready.is_empty() {
+      return Err(LocationImportError::ParentCycle(waiting[0].key.clone()));
+    }
+
+    placed.extend(ready.iter().map(|location| location.key.clone()));
+    ordered.extend(ready);
+    pending = waiting;
+  }
+
+  Ok(ordered)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use spectral::prelude::*;
+
+  fn location(key: &str, parent: Option<&str>) -> LocationImport {
+    LocationImport {
+      key: key.to_owned(),
+      parent: parent.map(|parent| parent.to_owned()),
+      name: key.to_owned(),
+      kind: "".to_owned(),
+      description: "".to_owned(),
+      coordinates: None,
+    }
+  }
+
+  fn keys(locations: &[LocationImport]) -> Vec<&str> {
+    locations
+      .iter()
+      .map(|location| location.key.as_str())
+      .collect()
+  }
+
+  #[test]
+  fn test_order_parents_first() {
+    let ordered = order_location_imports(vec![
+      location("grandchild", Some("child")),
+      location("child", Some("root")),
+      location("other", None),
+      location("root", None),
+    ])
+    .unwrap();
+
+    assert_that(&keys(&ordered)).is_equal_to(vec!["other", "root", "child", "grandchild"]);
+  }
+
+  #[test]
+  fn test_order_duplicate_key() {
+    let ordered = order_location_imports(vec![location("root", None), location("root", None)]);
+
+    assert_that(&ordered)
+      .is_err()
+      .is_equal_to(LocationImportError::DuplicateKey("root".to_owned()));
+  }
+
+  #[test]
+  fn test_order_unknown_parent() {
+    let ordered = order_location_imports(vec![
+      location("root", None),
+      location("child", Some("missing")),
+    ]);
+
+    assert_that(&ordered)
+      .is_err()
+      .is_equal_to(LocationImportError::UnknownParent("child".to_owned()));
+  }
+
+  #[test]
+  fn test_order_cycle() {
+    let ordered = order_location_imports(vec![
+      location("root", None),
+      location("first", Some("second")),
+      location("second", Some("first")),
+    ]);
+
+    assert_that(&ordered)
+      .is_err()
+      .is_equal_to(LocationImportError::ParentCycle("first".to_owned()));
+  }
+}
@@ -0,0 +1,7 @@
+mod location;
+mod location_id;
+mod location_import;
+
+pub use location::*;
+pub use location_id::*;
+pub use location_import::*;
@@ -0,0 +1,10 @@
+use crate::LocationID;
+
+/// Where in the tree of locations of a world to list locations from
+#[derive(Debug, PartialEq, Clone)]
+pub enum LocationParentFilter {
+  /// Only locations that are at the top level of the world
+  TopLevel,
+  /// Only locations that sit directly under the given location
+  Children(LocationID),
+}
@@ -0,0 +1,420 @@
+use super::{repository::*, service::*, LocationParentFilter};
+use crate::model::*;
+use tracing::{info, warn};
+use universe_entity::{IdMap, Page, Pagination};
+use universe_worlds::WorldID;
+
+/// The Location Service to allow interactions with location entities
+pub struct LocationServiceImpl<Repo> {
+  repository: Repo,
+}
+
+/// Create a new Location Service
+///
+/// # Returns
+/// The Location Service
+pub fn new_location_service<Repo: LocationRepository + Send + Sync>(
+  repository: Repo,
+) -> impl LocationService {
+  LocationServiceImpl { repository }
+}
+
+impl<Repo: LocationRepository + Send + Sync> LocationService for LocationServiceImpl<Repo> {
+  /// Retrieve the location that has the given unique ID, as long as it belongs to the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the location belongs to
+  /// * `location_id` The ID of the location to retrieve
+  ///
+  /// # Returns
+  /// The location, or `None` if it wasn't found
+  fn get_location_by_id(
+    &self,
+    world_id: &WorldID,
+    location_id: &LocationID,
+  ) -> Option<LocationEntity> {
+    let location = self.repository.get_location_by_id(world_id, location_id);
+
+    if location.is_none() {
+      warn!(
+        "No location found with ID {} in world {}",
+        location_id, world_id
+      );
+    }
+
+    location
+  }
+
+  /// Retrieve a page of the locations of a world, sorted by name
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the locations of
+  /// * `parent` Where in the tree of locations to list locations from, or `None` for all of them
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of locations
+  fn list_locations(
+    &self,
+    world_id: &WorldID,
+    parent: Option<LocationParentFilter>,
+    pagination: Pagination,
+  ) -> Page<LocationEntity> {
+    self.repository.list_locations(world_id, parent, pagination)
+  }
+
+  /// Retrieve every location of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the locations of
+  ///
+  /// # Returns
+  /// The locations
+  fn list_all_locations(&self, world_id: &WorldID) -> Vec<LocationEntity> {
+    self.repository.list_all_locations(world_id)
+  }
+
+  /// Retrieve every location that the given location sits inside
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the location belongs to
+  /// * `location_id` The ID of the location to start from
+  ///
+  /// # Returns
+  /// The ancestors of the location, starting from the top of the tree and ending with its parent
+  fn get_location_ancestors(
+    &self,
+    world_id: &WorldID,
+    location_id: &LocationID,
+  ) -> Vec<LocationEntity> {
+    self
+      .repository
+      .get_location_ancestors(world_id, location_id)
+  }
+
+  /// Retrieve every location that sits somewhere inside the given location
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the location belongs to
+  /// * `location_id` The ID of the location to start from
+  /// * `max_depth` How many levels of the tree to descend, or `None` for all of them
+  ///
+  /// # Returns
+  /// The descendants of the location, shallowest first and then sorted by name
+  fn get_location_descendants(
+    &self,
+    world_id: &WorldID,
+    location_id: &LocationID,
+    max_depth: Option<u32>,
+  ) -> Vec<LocationDescendant> {
+    self
+      .repository
+      .get_location_descendants(world_id, location_id, max_depth)
+  }
+
+  /// Create a new location
+  ///
+  /// # Arguments
+  /// * `location` The location data to create the location from
+  ///
+  /// # Returns
+  /// The location that was persisted
+  fn create_location(&self, location: LocationData) -> Result<LocationEntity, CreateLocationError> {
+    let created = self.repository.create_location(location)?;
+    Ok(created)
+  }
+
+  /// Update an existing location.
+  ///
+  /// This will load the location by ID, and then call a provided callback to mutate the location
+  /// before persisting the changes back to the database. Changing the parent of a location moves
+  /// everything inside it along with it, so a location may not be moved inside itself or any of
+  /// the locations within it.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the location belongs to
+  /// * `location_id` The ID of the location to update
+  /// * `updater` The callback to mutate the location with
+  ///
+  /// # Returns
+  /// The newly updated location
+  fn update_location(
+    &self,
+    world_id: &WorldID,
+    location_id: &LocationID,
+    updater: &mut dyn FnMut(LocationData) -> Result<LocationData, Box<dyn std::error::Error>>,
+  ) -> Result<LocationEntity, UpdateLocationError> {
+    let location = self
+      .get_location_by_id(world_id, location_id)
+      .ok_or(UpdateLocationError::UnknownLocation)?;
+
+    let world = location.data.world.clone();
+    let updated = updater(location.data).map_err(UpdateLocationError::UpdateError)?;
+
+    let saved = self.repository.update_location(LocationEntity {
+      identity: location.identity,
+      // Locations can't be moved between worlds
+      data: LocationData { world, ..updated },
+    })?;
+    Ok(saved)
+  }
+
+  /// Delete a location. Locations that have other locations inside them can't be deleted
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the location belongs to
+  /// * `location_id` The ID of the location to delete
+  fn delete_location(
+    &self,
+    world_id: &WorldID,
+    location_id: &LocationID,
+  ) -> Result<(), DeleteLocationError> {
+    self.repository.delete_location(world_id, location_id)?;
+    Ok(())
+  }
+
+  /// Create a set of locations in a world, keeping the tree that they form.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to create the locations in
+  /// * `locations` The locations to create
+  /// * `ids` The map to record the new IDs of the locations in, by their keys
+  ///
+  /// # Returns
+  /// The locations that were created, parents before their children
+  fn import_locations(
+    &self,
+    world_id: &WorldID,
+    locations: Vec<LocationImport>,
+    ids: &mut IdMap,
+  ) -> Result<Vec<LocationEntity>, ImportLocationsError> {
+    let locations = order_location_imports(locations)?;
+
+    let mut created = Vec::with_capacity(locations.len());
+
+    for location in locations {
+      let parent = location
+        .parent
+        .and_then(|parent| ids.get("location", &parent))
+        .and_then(|parent| parent.parse().ok());
+      let result = self
+        .create_location(LocationData {
+          world: world_id.clone(),
+          parent,
+          name: location.name,
+          kind: location.kind,
+          description: location.description,
+          coordinates: location.coordinates,
+        })
+        .map_err(|e| match e {
+          CreateLocationError::UnknownWorld => ImportLocationsError::UnknownWorld,
+          _ => ImportLocationsError::UnknownError,
+        })?;
+
+      ids.insert("location", &location.key, &result.identity.id);
+      created.push(result);
+    }
+    info!(
+      "Imported {} locations into world {}",
+      created.len(),
+      world_id
+    );
+
+    Ok(created)
+  }
+
+  /// Copy every location of one world into another, keeping the tree that they form
+  ///
+  /// # Arguments
+  /// * `from` The ID of the world to copy the locations from
+  /// * `to` The ID of the world to copy the locations into
+  /// * `ids` The map to record the new IDs of the locations in, by their original IDs
+  ///
+  /// # Returns
+  /// The locations that were created
+  fn copy_locations(
+    &self,
+    from: &WorldID,
+    to: &WorldID,
+    ids: &mut IdMap,
+  ) -> Result<Vec<LocationEntity>, ImportLocationsError> {
+    let locations = self
+      .list_all_locations(from)
+      .into_iter()
+      .map(|location| LocationImport {
+        key: location.identity.id.to_string(),
+        parent: location.data.parent.map(|parent| parent.to_string()),
+        name: location.data.name,
+        kind: location.data.kind,
+        description: location.data.description,
+        coordinates: location.data.coordinates,
+      })
+      .collect();
+
+    self.import_locations(to, locations, ids)
+  }
+}
+
+impl From<PersistLocationError> for CreateLocationError {
+  fn from(e: PersistLocationError) -> Self {
+    warn!("Error creating location: {}", e);
+    match e {
+      PersistLocationError::ParentNotFound => {
+        CreateLocationError::ValidationError(vec![LocationValidationError::UnknownParent])
+      }
+      PersistLocationError::WorldNotFound => CreateLocationError::UnknownWorld,
+      _ => CreateLocationError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistLocationError> for UpdateLocationError {
+  fn from(e: PersistLocationError) -> Self {
+    warn!("Error updating location: {}", e);
+    match e {
+      PersistLocationError::ParentNotFound => {
+        UpdateLocationError::ValidationError(vec![LocationValidationError::UnknownParent])
+      }
+      PersistLocationError::ParentCycle => {
+        UpdateLocationError::ValidationError(vec![LocationValidationError::ParentCycle])
+      }
+      PersistLocationError::LocationNotFound => UpdateLocationError::UnknownLocation,
+      PersistLocationError::OptimisticLockFailure => UpdateLocationError::OptimisticLockFailure,
+      _ => UpdateLocationError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistLocationError> for DeleteLocationError {
+  fn from(e: PersistLocationError) -> Self {
+    warn!("Error deleting location: {}", e);
+    match e {
+      PersistLocationError::LocationNotFound => DeleteLocationError::UnknownLocation,
+      PersistLocationError::HasChildren => DeleteLocationError::HasChildren,
+      _ => DeleteLocationError::UnknownError,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use assert_matches::assert_matches;
+  use spectral::prelude::*;
+  use test_env_log::test;
+  use universe_entity::Identity;
+
+  fn location(parent: Option<LocationID>) -> LocationEntity {
+    LocationEntity {
+      identity: Identity::default(),
+      data: LocationData {
+        world: Default::default(),
+        parent,
+        name: "Rivendell".to_owned(),
+        kind: "City".to_owned(),
+        description: "".to_owned(),
+        coordinates: None,
+      },
+    }
+  }
+
+  #[test]
+  fn test_create_location_unknown_parent() {
+    let mut repository = MockLocationRepository::new();
+    repository
+      .expect_create_location()
+      .times(1)
+      .returning(|_| Err(PersistLocationError::ParentNotFound));
+
+    let service = new_location_service(repository);
+
+    let created = service.create_location(location(Some(Default::default())).data);
+    assert_that(&created)
+      .is_err()
+      .is_equal_to(CreateLocationError::ValidationError(vec![
+        LocationValidationError::UnknownParent,
+      ]));
+  }
+
+  #[test]
+  fn test_update_location_parent_cycle() {
+    let existing = location(None);
+    let location_id = existing.identity.id.clone();
+    let descendant_id = location(Some(location_id.clone())).identity.id;
+
+    let mut repository = MockLocationRepository::new();
+    let returned = existing.clone();
+    repository
+      .expect_get_location_by_id()
+      .times(1)
+      .returning(move |_, _| Some(returned.clone()));
+    repository
+      .expect_update_location()
+      .times(1)
+      .returning(|_| Err(PersistLocationError::ParentCycle));
+
+    let service = new_location_service(repository);
+
+    let updated = service.update_location(&existing.data.world, &location_id, &mut |data| {
+      Ok(LocationData {
+        parent: Some(descendant_id.clone()),
+        ..data
+      })
+    });
+    assert_matches!(updated, Err(UpdateLocationError::ValidationError(errors)) => {
+      assert_that(&errors).is_equal_to(vec![LocationValidationError::ParentCycle]);
+    });
+  }
+
+  #[test]
+  fn test_import_locations_remaps_parents() {
+    let world_id = WorldID::default();
+
+    let mut repository = MockLocationRepository::new();
+    repository
+      .expect_create_location()
+      .times(2)
+      .returning(|data| {
+        Ok(LocationEntity {
+          identity: Identity::default(),
+          data,
+        })
+      });
+
+    let service = new_location_service(repository);
+
+    let mut ids = IdMap::default();
+    let imported = service
+      .import_locations(
+        &world_id,
+        vec![
+          LocationImport {
+            key: "child".to_owned(),
+            parent: Some("root".to_owned()),
+            name: "Rivendell".to_owned(),
+            kind: "City".to_owned(),
+            description: "".to_owned(),
+            coordinates: Some(Coordinates { x: 12.5, y: 40.0 }),
+          },
+          LocationImport {
+            key: "root".to_owned(),
+            parent: None,
+            name: "Eriador".to_owned(),
+            kind: "Region".to_owned(),
+            description: "".to_owned(),
+            coordinates: None,
+          },
+        ],
+        &mut ids,
+      )
+      .unwrap();
+
+    assert_that(&imported).has_length(2);
+    assert_that(&imported[0].data.name).is_equal_to("Eriador".to_owned());
+    assert_that(&imported[0].data.world).is_equal_to(world_id);
+    assert_that(&imported[1].data.parent).is_equal_to(Some(imported[0].identity.id.clone()));
+    assert_that(&imported[1].data.coordinates).is_equal_to(Some(Coordinates { x: 12.5, y: 40.0 }));
+    assert_that(&ids.get("location", "root"))
+      .is_equal_to(Some(imported[0].identity.id.to_string().as_str()));
+  }
+}
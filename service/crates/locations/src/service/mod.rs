@@ -0,0 +1,8 @@
+mod filters;
+mod implementation;
+pub mod repository;
+mod service;
+
+pub use filters::*;
+pub use implementation::*;
+pub use service::*;
@@ -0,0 +1,132 @@
+use super::LocationParentFilter;
+use crate::model::*;
+#[cfg(test)]
+use mockall::automock;
+use universe_entity::{Page, Pagination};
+use universe_worlds::WorldID;
+
+/// Repository that describes how to access location data
+#[cfg_attr(test, automock)]
+pub trait LocationRepository {
+  /// Retrieve the location that has the given unique ID, as long as it belongs to the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the location belongs to
+  /// * `location_id` The ID of the location to retrieve
+  ///
+  /// # Returns
+  /// The location, or `None` if it wasn't found
+  fn get_location_by_id(
+    &self,
+    world_id: &WorldID,
+    location_id: &LocationID,
+  ) -> Option<LocationEntity>;
+
+  /// Retrieve a page of the locations of a world, sorted by name
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the locations of
+  /// * `parent` Where in the tree of locations to list locations from, or `None` for all of them
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of locations
+  fn list_locations(
+    &self,
+    world_id: &WorldID,
+    parent: Option<LocationParentFilter>,
+    pagination: Pagination,
+  ) -> Page<LocationEntity>;
+
+  /// Retrieve every location of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the locations of
+  ///
+  /// # Returns
+  /// The locations
+  fn list_all_locations(&self, world_id: &WorldID) -> Vec<LocationEntity>;
+
+  /// Retrieve every location that the given location sits inside
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the location belongs to
+  /// * `location_id` The ID of the location to start from
+  ///
+  /// # Returns
+  /// The ancestors of the location, starting from the top of the tree and ending with its parent
+  fn get_location_ancestors(
+    &self,
+    world_id: &WorldID,
+    location_id: &LocationID,
+  ) -> Vec<LocationEntity>;
+
+  /// Retrieve every location that sits somewhere inside the given location
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the location belongs to
+  /// * `location_id` The ID of the location to start from
+  /// * `max_depth` How many levels of the tree to descend, or `None` for all of them
+  ///
+  /// # Returns
+  /// The descendants of the location, shallowest first and then sorted by name
+  fn get_location_descendants(
+    &self,
+    world_id: &WorldID,
+    location_id: &LocationID,
+    max_depth: Option<u32>,
+  ) -> Vec<LocationDescendant>;
+
+  /// Create a new location record in the data store
+  ///
+  /// # Arguments
+  /// * `location` The location details to persist to the data store
+  ///
+  /// # Returns
+  /// The location that was persisted
+  fn create_location(&self, location: LocationData)
+    -> Result<LocationEntity, PersistLocationError>;
+
+  /// Update an existing location record in the data store. The location may not be moved
+  /// inside itself or any of the locations within it
+  ///
+  /// # Arguments
+  /// * `location` The location details to persist to the data store
+  ///
+  /// # Returns
+  /// The location that was persisted
+  fn update_location(
+    &self,
+    location: LocationEntity,
+  ) -> Result<LocationEntity, PersistLocationError>;
+
+  /// Delete an existing location record from the data store
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the location belongs to
+  /// * `location_id` The ID of the location to delete
+  fn delete_location(
+    &self,
+    world_id: &WorldID,
+    location_id: &LocationID,
+  ) -> Result<(), PersistLocationError>;
+}
+
+/// Errors that can occur when persisting a location record
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum PersistLocationError {
+  #[error("The world was not found")]
+  WorldNotFound,
+  #[error("The location was not found")]
+  LocationNotFound,
+  #[error("The parent location was not found")]
+  ParentNotFound,
+  #[error("The location has other locations inside it")]
+  HasChildren,
+  #[error("The location would end up inside itself")]
+  ParentCycle,
+  #[error("The version of the location record did not match")]
+  OptimisticLockFailure,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
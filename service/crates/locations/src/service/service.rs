@@ -0,0 +1,204 @@
+use super::LocationParentFilter;
+use crate::model::*;
+use universe_entity::{IdMap, Page, Pagination};
+use universe_worlds::WorldID;
+
+/// The Location Service to allow interactions with location entities
+pub trait LocationService: Send + Sync {
+  /// Retrieve the location that has the given unique ID, as long as it belongs to the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the location belongs to
+  /// * `location_id` The ID of the location to retrieve
+  ///
+  /// # Returns
+  /// The location, or `None` if it wasn't found
+  fn get_location_by_id(
+    &self,
+    world_id: &WorldID,
+    location_id: &LocationID,
+  ) -> Option<LocationEntity>;
+
+  /// Retrieve a page of the locations of a world, sorted by name
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the locations of
+  /// * `parent` Where in the tree of locations to list locations from, or `None` for all of them
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of locations
+  fn list_locations(
+    &self,
+    world_id: &WorldID,
+    parent: Option<LocationParentFilter>,
+    pagination: Pagination,
+  ) -> Page<LocationEntity>;
+
+  /// Retrieve every location of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the locations of
+  ///
+  /// # Returns
+  /// The locations
+  fn list_all_locations(&self, world_id: &WorldID) -> Vec<LocationEntity>;
+
+  /// Retrieve every location that the given location sits inside
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the location belongs to
+  /// * `location_id` The ID of the location to start from
+  ///
+  /// # Returns
+  /// The ancestors of the location, starting from the top of the tree and ending with its parent
+  fn get_location_ancestors(
+    &self,
+    world_id: &WorldID,
+    location_id: &LocationID,
+  ) -> Vec<LocationEntity>;
+
+  /// Retrieve every location that sits somewhere inside the given location
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the location belongs to
+  /// * `location_id` The ID of the location to start from
+  /// * `max_depth` How many levels of the tree to descend, or `None` for all of them
+  ///
+  /// # Returns
+  /// The descendants of the location, shallowest first and then sorted by name
+  fn get_location_descendants(
+    &self,
+    world_id: &WorldID,
+    location_id: &LocationID,
+    max_depth: Option<u32>,
+  ) -> Vec<LocationDescendant>;
+
+  /// Create a new location
+  ///
+  /// # Arguments
+  /// * `location` The location data to create the location from
+  ///
+  /// # Returns
+  /// The location that was persisted
+  fn create_location(&self, location: LocationData) -> Result<LocationEntity, CreateLocationError>;
+
+  /// Update an existing location.
+  ///
+  /// This will load the location by ID, and then call a provided callback to mutate the location
+  /// before persisting the changes back to the database. Changing the parent of a location moves
+  /// everything inside it along with it, so a location may not be moved inside itself or any of
+  /// the locations within it.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the location belongs to
+  /// * `location_id` The ID of the location to update
+  /// * `updater` The callback to mutate the location with
+  ///
+  /// # Returns
+  /// The newly updated location
+  fn update_location(
+    &self,
+    world_id: &WorldID,
+    location_id: &LocationID,
+    updater: &mut dyn FnMut(LocationData) -> Result<LocationData, Box<dyn std::error::Error>>,
+  ) -> Result<LocationEntity, UpdateLocationError>;
+
+  /// Delete a location. Locations that have other locations inside them can't be deleted
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the location belongs to
+  /// * `location_id` The ID of the location to delete
+  fn delete_location(
+    &self,
+    world_id: &WorldID,
+    location_id: &LocationID,
+  ) -> Result<(), DeleteLocationError>;
+
+  /// Create a set of locations in a world, keeping the tree that they form.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to create the locations in
+  /// * `locations` The locations to create
+  /// * `ids` The map to record the new IDs of the locations in, by their keys
+  ///
+  /// # Returns
+  /// The locations that were created, parents before their children
+  fn import_locations(
+    &self,
+    world_id: &WorldID,
+    locations: Vec<LocationImport>,
+    ids: &mut IdMap,
+  ) -> Result<Vec<LocationEntity>, ImportLocationsError>;
+
+  /// Copy every location of one world into another, keeping the tree that they form
+  ///
+  /// # Arguments
+  /// * `from` The ID of the world to copy the locations from
+  /// * `to` The ID of the world to copy the locations into
+  /// * `ids` The map to record the new IDs of the locations in, by their original IDs
+  ///
+  /// # Returns
+  /// The locations that were created
+  fn copy_locations(
+    &self,
+    from: &WorldID,
+    to: &WorldID,
+    ids: &mut IdMap,
+  ) -> Result<Vec<LocationEntity>, ImportLocationsError>;
+}
+
+/// Enumeration of validation errors for a location
+#[derive(Debug, PartialEq)]
+pub enum LocationValidationError {
+  UnknownParent,
+  ParentCycle,
+}
+
+/// Enumeration of reasons why we failed to create a new location
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum CreateLocationError {
+  #[error("Location details were invalid: {0:?}")]
+  ValidationError(Vec<LocationValidationError>),
+  #[error("The world was not found")]
+  UnknownWorld,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to update an existing location
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateLocationError {
+  #[error("Location details were invalid: {0:?}")]
+  ValidationError(Vec<LocationValidationError>),
+  #[error("The location was not found")]
+  UnknownLocation,
+  #[error("The version of the location record did not match")]
+  OptimisticLockFailure,
+  #[error("An error occurred updating the location details: {0}")]
+  UpdateError(Box<dyn std::error::Error>),
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to delete a location
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum DeleteLocationError {
+  #[error("The location was not found")]
+  UnknownLocation,
+  #[error("The location has other locations inside it")]
+  HasChildren,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to import a set of locations
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum ImportLocationsError {
+  #[error("The locations did not form a tree: {0}")]
+  InvalidTree(#[from] LocationImportError),
+  #[error("The world was not found")]
+  UnknownWorld,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
@@ -0,0 +1,984 @@
+use crate::{model::*, service::repository::*};
+use chrono::Utc;
+use postgres::types::{Json, ToSql};
+use std::error::Error;
+use tracing::{debug, warn};
+use universe_articles::ArticleID;
+use universe_characters::CharacterID;
+use universe_database::Database;
+use universe_entity::{Identity, Page, Pagination};
+use universe_locations::LocationID;
+use universe_worlds::WorldID;
+use uuid::Uuid;
+
+impl From<&postgres::Row> for MapEntity {
+  fn from(row: &postgres::Row) -> Self {
+    let image_id: Option<MapImageID> = row.get("image_id");
+    let content_type: Option<String> = row.get("image_content_type");
+    let width: Option<i32> = row.get("image_width");
+    let height: Option<i32> = row.get("image_height");
+
+    MapEntity {
+      identity: Identity {
+        id: row.get("map_id"),
+        version: row.get("version"),
+        created: row.get("created"),
+        updated: row.get("updated"),
+      },
+      data: MapData {
+        world: row.get("world_id"),
+        name: row.get("name"),
+        // The database only allows all of the image details or none of them
+        image: match (image_id, content_type, width, height) {
+          (Some(id), Some(content_type), Some(width), Some(height)) => Some(MapImage {
+            id,
+            content_type,
+            width: width as u32,
+            height: height as u32,
+          }),
+          _ => None,
+        },
+      },
+    }
+  }
+}
+
+impl From<&postgres::Row> for FeatureEntity {
+  fn from(row: &postgres::Row) -> Self {
+    let geometry: Json<Geometry> = row.get("geometry");
+    let location_id: Option<LocationID> = row.get("location_id");
+    let character_id: Option<CharacterID> = row.get("character_id");
+    let article_id: Option<ArticleID> = row.get("article_id");
+
+    FeatureEntity {
+      identity: Identity {
+        id: row.get("feature_id"),
+        version: row.get("version"),
+        created: row.get("created"),
+        updated: row.get("updated"),
+      },
+      data: FeatureData {
+        world: row.get("world_id"),
+        map: row.get("map_id"),
+        name: row.get("name"),
+        geometry: geometry.0,
+        // The database only allows a feature to link to one entity at most
+        link: location_id
+          .map(FeatureLink::Location)
+          .or_else(|| character_id.map(FeatureLink::Character))
+          .or_else(|| article_id.map(FeatureLink::Article)),
+      },
+    }
+  }
+}
+
+/// The columns that the link of a feature is stored in
+struct LinkColumns<'a> {
+  location_id: Option<&'a LocationID>,
+  character_id: Option<&'a CharacterID>,
+  article_id: Option<&'a ArticleID>,
+}
+
+impl<'a> From<&'a Option<FeatureLink>> for LinkColumns<'a> {
+  fn from(link: &'a Option<FeatureLink>) -> Self {
+    LinkColumns {
+      location_id: match link {
+        Some(FeatureLink::Location(id)) => Some(id),
+        _ => None,
+      },
+      character_id: match link {
+        Some(FeatureLink::Character(id)) => Some(id),
+        _ => None,
+      },
+      article_id: match link {
+        Some(FeatureLink::Article(id)) => Some(id),
+        _ => None,
+      },
+    }
+  }
+}
+
+impl MapRepository for Database {
+  /// Retrieve the map that has the given unique ID, as long as it belongs to the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map to retrieve
+  ///
+  /// # Returns
+  /// The map, or `None` if it wasn't found
+  fn get_map_by_id(&self, world_id: &WorldID, map_id: &MapID) -> Option<MapEntity> {
+    let mut client = self.client().unwrap();
+
+    let map = client
+      .query(
+        "SELECT * FROM maps WHERE world_id = $1 AND map_id = $2",
+        &[&world_id, &map_id],
+      )
+      .map_err(|e| {
+        warn!("Error loading map from database: {}", e);
+        e
+      })
+      .ok()
+      .and_then(|rows| rows.get(0).map(|row| row.into()));
+
+    debug!("Map for ID {}: {:?}", map_id, map);
+    map
+  }
+
+  /// Retrieve a page of the maps of a world, sorted by name
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the maps of
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of maps
+  fn list_maps(&self, world_id: &WorldID, pagination: Pagination) -> Page<MapEntity> {
+    let mut client = self.client().unwrap();
+
+    let total: i64 = client
+      .query_one(
+        "SELECT COUNT(*) AS total FROM maps WHERE world_id = $1",
+        &[&world_id],
+      )
+      .map_err(|e| {
+        warn!("Error counting maps in database: {}", e);
+        e
+      })
+      .map(|row| row.get("total"))
+      .unwrap_or(0);
+
+    let entries: Vec<MapEntity> = client
+      .query(
+        "SELECT * FROM maps WHERE world_id = $1
+          ORDER BY UPPER(name) ASC, map_id ASC
+          LIMIT $2 OFFSET $3",
+        &[
+          &world_id,
+          &i64::from(pagination.limit),
+          &i64::from(pagination.offset),
+        ],
+      )
+      .map_err(|e| {
+        warn!("Error listing maps in database: {}", e);
+        e
+      })
+      .map(|rows| rows.iter().map(|row| row.into()).collect())
+      .unwrap_or_default();
+
+    let page = Page {
+      entries,
+      total: total as u32,
+      offset: pagination.offset,
+    };
+    debug!("Maps of world {}: {:?}", world_id, page);
+
+    page
+  }
+
+  /// Retrieve every map of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the maps of
+  ///
+  /// # Returns
+  /// The maps
+  fn list_all_maps(&self, world_id: &WorldID) -> Vec<MapEntity> {
+    let mut client = self.client().unwrap();
+
+    let maps = client
+      .query(
+        "SELECT * FROM maps WHERE world_id = $1 ORDER BY created ASC, map_id ASC",
+        &[&world_id],
+      )
+      .map_err(|e| {
+        warn!("Error listing maps in database: {}", e);
+        e
+      })
+      .map(|rows| rows.iter().map(|row| row.into()).collect())
+      .unwrap_or_default();
+
+    debug!("All maps of world {}: {:?}", world_id, maps);
+    maps
+  }
+
+  /// Create a new map record in the data store
+  ///
+  /// # Arguments
+  /// * `map` The map details to persist to the data store
+  ///
+  /// # Returns
+  /// The map that was persisted
+  fn create_map(&self, map: MapData) -> Result<MapEntity, PersistMapError> {
+    debug!("Creating record for map: {:?}", map);
+
+    let mut client = self.client().unwrap();
+
+    let new_id = MapID::default();
+    let new_version = Uuid::new_v4();
+    let new_updated = Utc::now();
+
+    let result: MapEntity = client
+      .query(
+        "INSERT INTO maps(map_id, version, created, updated, world_id, name, image_id, image_content_type, image_width, image_height)
+          VALUES ($1, $2, $3, $3, $4, $5, $6, $7, $8, $9)
+          RETURNING *",
+        &[
+          &new_id,
+          &new_version,
+          &new_updated,
+          &map.world,
+          &map.name,
+          &map.image.as_ref().map(|image| &image.id),
+          &map.image.as_ref().map(|image| &image.content_type),
+          &map.image.as_ref().map(|image| image.width as i32),
+          &map.image.as_ref().map(|image| image.height as i32),
+        ],
+      )
+      .map(|rows| rows.get(0).unwrap().into())?;
+
+    debug!("Created record for map: {:?}", result);
+    Ok(result)
+  }
+
+  /// Update an existing map record in the data store
+  ///
+  /// # Arguments
+  /// * `map` The map details to persist to the data store
+  ///
+  /// # Returns
+  /// The map that was persisted
+  fn update_map(&self, map: MapEntity) -> Result<MapEntity, PersistMapError> {
+    debug!("Updating record for map: {:?}", map);
+
+    let mut client = self.client().unwrap();
+
+    let new_version = Uuid::new_v4();
+    let new_updated = Utc::now();
+
+    let rows = client.query(
+      "UPDATE maps SET name = $1, image_id = $2, image_content_type = $3, image_width = $4, image_height = $5, version = $6, updated = $7
+          WHERE map_id = $8
+          AND world_id = $9
+          AND version = $10
+          RETURNING *",
+      &[
+        &map.data.name,
+        &map.data.image.as_ref().map(|image| &image.id),
+        &map.data.image.as_ref().map(|image| &image.content_type),
+        &map.data.image.as_ref().map(|image| image.width as i32),
+        &map.data.image.as_ref().map(|image| image.height as i32),
+        &new_version,
+        &new_updated,
+        &map.identity.id,
+        &map.data.world,
+        &map.identity.version,
+      ],
+    )?;
+
+    if let Some(row) = rows.get(0) {
+      let result = row.into();
+      debug!("Updated record for map: {:?}", result);
+      Ok(result)
+    } else {
+      let map_found = client.query(
+        "SELECT version FROM maps WHERE map_id = $1 AND world_id = $2",
+        &[&map.identity.id, &map.data.world],
+      )?;
+
+      if map_found.is_empty() {
+        warn!(
+          "Attempted to update map {} that wasn't found",
+          map.identity.id
+        );
+        Err(PersistMapError::MapNotFound)
+      } else {
+        let old_version: Uuid = map_found.get(0).unwrap().get("version");
+
+        warn!(
+          "Attempted to update map {}. Expected version {} but database had {}",
+          map.identity.id, map.identity.version, old_version
+        );
+        Err(PersistMapError::OptimisticLockFailure)
+      }
+    }
+  }
+
+  /// Delete an existing map record from the data store, along with every feature on it
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map to delete
+  fn delete_map(&self, world_id: &WorldID, map_id: &MapID) -> Result<(), PersistMapError> {
+    debug!("Deleting map {} from world {}", map_id, world_id);
+
+    let mut client = self.client().unwrap();
+
+    // The features on the map are removed along with it by the database
+    let deleted = client.execute(
+      "DELETE FROM maps WHERE world_id = $1 AND map_id = $2",
+      &[&world_id, &map_id],
+    )?;
+
+    if deleted == 0 {
+      warn!("Attempted to delete map {} that wasn't found", map_id);
+      Err(PersistMapError::MapNotFound)
+    } else {
+      Ok(())
+    }
+  }
+
+  /// Retrieve the feature that has the given unique ID, as long as it is on the given map
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map that the feature is on
+  /// * `feature_id` The ID of the feature to retrieve
+  ///
+  /// # Returns
+  /// The feature, or `None` if it wasn't found
+  fn get_feature_by_id(
+    &self,
+    world_id: &WorldID,
+    map_id: &MapID,
+    feature_id: &FeatureID,
+  ) -> Option<FeatureEntity> {
+    let mut client = self.client().unwrap();
+
+    let feature = client
+      .query(
+        "SELECT * FROM map_features WHERE world_id = $1 AND map_id = $2 AND feature_id = $3",
+        &[&world_id, &map_id, &feature_id],
+      )
+      .map_err(|e| {
+        warn!("Error loading feature from database: {}", e);
+        e
+      })
+      .ok()
+      .and_then(|rows| rows.get(0).map(|row| row.into()));
+
+    debug!("Feature for ID {}: {:?}", feature_id, feature);
+    feature
+  }
+
+  /// Retrieve the features on a map, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map to list the features of
+  /// * `kind` The kind of features to list, or `None` for all of them
+  ///
+  /// # Returns
+  /// The features
+  fn list_features(
+    &self,
+    world_id: &WorldID,
+    map_id: &MapID,
+    kind: Option<FeatureKind>,
+  ) -> Vec<FeatureEntity> {
+    let mut client = self.client().unwrap();
+
+    let kind = kind.map(|kind| kind.to_string());
+    let features = client
+      .query(
+        "SELECT * FROM map_features WHERE world_id = $1 AND map_id = $2
+          AND ($3::TEXT IS NULL OR kind = $3::TEXT)
+          ORDER BY created ASC, feature_id ASC",
+        &[&world_id, &map_id, &kind],
+      )
+      .map_err(|e| {
+        warn!("Error listing features in database: {}", e);
+        e
+      })
+      .map(|rows| rows.iter().map(|row| row.into()).collect())
+      .unwrap_or_default();
+
+    debug!("Features of map {}: {:?}", map_id, features);
+    features
+  }
+
+  /// Check whether an entity that a feature links to is part of the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  /// * `link` The entity to look for
+  ///
+  /// # Returns
+  /// True if the entity exists and belongs to the world
+  fn is_link_in_world(&self, world_id: &WorldID, link: &FeatureLink) -> bool {
+    let mut client = self.client().unwrap();
+
+    let (sql, id): (&str, &(dyn ToSql + Sync)) = match link {
+      FeatureLink::Location(id) => (
+        "SELECT 1 FROM locations WHERE world_id = $1 AND location_id = $2",
+        id,
+      ),
+      FeatureLink::Character(id) => (
+        "SELECT 1 FROM characters WHERE world_id = $1 AND character_id = $2",
+        id,
+      ),
+      FeatureLink::Article(id) => (
+        "SELECT 1 FROM articles WHERE world_id = $1 AND article_id = $2",
+        id,
+      ),
+    };
+
+    let found = client
+      .query(sql, &[&world_id, id])
+      .map_err(|e| {
+        warn!("Error looking up feature link in database: {}", e);
+        e
+      })
+      .map(|rows| !rows.is_empty())
+      .unwrap_or(false);
+
+    debug!("Link {:?} in world {}: {}", link, world_id, found);
+    found
+  }
+
+  /// Create a new feature record in the data store
+  ///
+  /// # Arguments
+  /// * `feature` The feature details to persist to the data store
+  ///
+  /// # Returns
+  /// The feature that was persisted
+  fn create_feature(&self, feature: FeatureData) -> Result<FeatureEntity, PersistMapError> {
+    debug!("Creating record for feature: {:?}", feature);
+
+    let mut client = self.client().unwrap();
+
+    let new_id = FeatureID::default();
+    let new_version = Uuid::new_v4();
+    let new_updated = Utc::now();
+    let link = LinkColumns::from(&feature.link);
+
+    let result: FeatureEntity = client
+      .query(
+        "INSERT INTO map_features(feature_id, version, created, updated, world_id, map_id, name, kind, geometry, location_id, character_id, article_id)
+          VALUES ($1, $2, $3, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+          RETURNING *",
+        &[
+          &new_id,
+          &new_version,
+          &new_updated,
+          &feature.world,
+          &feature.map,
+          &feature.name,
+          &feature.geometry.kind().to_string(),
+          &Json(&feature.geometry),
+          &link.location_id,
+          &link.character_id,
+          &link.article_id,
+        ],
+      )
+      .map(|rows| rows.get(0).unwrap().into())?;
+
+    debug!("Created record for feature: {:?}", result);
+    Ok(result)
+  }
+
+  /// Update an existing feature record in the data store
+  ///
+  /// # Arguments
+  /// * `feature` The feature details to persist to the data store
+  ///
+  /// # Returns
+  /// The feature that was persisted
+  fn update_feature(&self, feature: FeatureEntity) -> Result<FeatureEntity, PersistMapError> {
+    debug!("Updating record for feature: {:?}", feature);
+
+    let mut client = self.client().unwrap();
+
+    let new_version = Uuid::new_v4();
+    let new_updated = Utc::now();
+    let link = LinkColumns::from(&feature.data.link);
+
+    let rows = client.query(
+      "UPDATE map_features SET name = $1, kind = $2, geometry = $3, location_id = $4, character_id = $5, article_id = $6, version = $7, updated = $8
+          WHERE feature_id = $9
+          AND world_id = $10
+          AND map_id = $11
+          AND version = $12
+          RETURNING *",
+      &[
+        &feature.data.name,
+        &feature.data.geometry.kind().to_string(),
+        &Json(&feature.data.geometry),
+        &link.location_id,
+        &link.character_id,
+        &link.article_id,
+        &new_version,
+        &new_updated,
+        &feature.identity.id,
+        &feature.data.world,
+        &feature.data.map,
+        &feature.identity.version,
+      ],
+    )?;
+
+    if let Some(row) = rows.get(0) {
+      let result = row.into();
+      debug!("Updated record for feature: {:?}", result);
+      Ok(result)
+    } else {
+      let feature_found = client.query(
+        "SELECT version FROM map_features WHERE feature_id = $1 AND world_id = $2 AND map_id = $3",
+        &[&feature.identity.id, &feature.data.world, &feature.data.map],
+      )?;
+
+      if feature_found.is_empty() {
+        warn!(
+          "Attempted to update feature {} that wasn't found",
+          feature.identity.id
+        );
+        Err(PersistMapError::FeatureNotFound)
+      } else {
+        let old_version: Uuid = feature_found.get(0).unwrap().get("version");
+
+        warn!(
+          "Attempted to update feature {}. Expected version {} but database had {}",
+          feature.identity.id, feature.identity.version, old_version
+        );
+        Err(PersistMapError::OptimisticLockFailure)
+      }
+    }
+  }
+
+  /// Delete an existing feature record from the data store
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map that the feature is on
+  /// * `feature_id` The ID of the feature to delete
+  fn delete_feature(
+    &self,
+    world_id: &WorldID,
+    map_id: &MapID,
+    feature_id: &FeatureID,
+  ) -> Result<(), PersistMapError> {
+    debug!("Deleting feature {} from map {}", feature_id, map_id);
+
+    let mut client = self.client().unwrap();
+
+    let deleted = client.execute(
+      "DELETE FROM map_features WHERE world_id = $1 AND map_id = $2 AND feature_id = $3",
+      &[&world_id, &map_id, &feature_id],
+    )?;
+
+    if deleted == 0 {
+      warn!(
+        "Attempted to delete feature {} that wasn't found",
+        feature_id
+      );
+      Err(PersistMapError::FeatureNotFound)
+    } else {
+      Ok(())
+    }
+  }
+}
+
+impl From<postgres::Error> for PersistMapError {
+  fn from(error: postgres::Error) -> Self {
+    warn!("Error persisting map in database: {:?}", error);
+
+    error
+      .source()
+      .and_then(|e| e.downcast_ref::<postgres::error::DbError>())
+      .map(|e| match e.constraint() {
+        Some("maps_world_id_fkey") => PersistMapError::WorldNotFound,
+        Some("map_features_world_id_map_id_fkey") => PersistMapError::MapNotFound,
+        Some("map_features_location_id_fkey")
+        | Some("map_features_character_id_fkey")
+        | Some("map_features_article_id_fkey") => PersistMapError::LinkNotFound,
+        _ => PersistMapError::UnknownError,
+      })
+      .unwrap_or(PersistMapError::UnknownError)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+  use spectral::prelude::*;
+  use test_env_log::test;
+  use universe_test_database_wrapper::TestDatabaseWrapper;
+  use universe_testdata::{seed, Character, Location, Map, MapFeature, User, World};
+
+  /// Seed a world with two maps into the database. The first map has an image with a pin, a road
+  /// and a region on it, and the second has no image yet
+  fn seed_maps(database: &TestDatabaseWrapper) -> (World, Vec<Map>, Vec<MapFeature>) {
+    let user: User = Default::default();
+    let world = World {
+      owner_id: user.user_id,
+      ..Default::default()
+    };
+    let location = Location {
+      world_id: world.world_id,
+      name: "Hobbiton".to_owned(),
+      ..Default::default()
+    };
+    let middle_earth = Map {
+      world_id: world.world_id,
+      name: "Middle-earth".to_owned(),
+      ..Default::default()
+    };
+    let beleriand = Map {
+      world_id: world.world_id,
+      name: "Beleriand".to_owned(),
+      image_id: None,
+      image_content_type: None,
+      image_width: None,
+      image_height: None,
+      ..Default::default()
+    };
+    let hobbiton = MapFeature {
+      world_id: world.world_id,
+      map_id: middle_earth.map_id,
+      name: "Hobbiton".to_owned(),
+      location_id: Some(location.location_id),
+      ..Default::default()
+    };
+    let road = MapFeature {
+      world_id: world.world_id,
+      map_id: middle_earth.map_id,
+      name: "East Road".to_owned(),
+      kind: "polyline".to_owned(),
+      geometry: json!({ "type": "LineString", "coordinates": [[100.0, 100.0], [300.0, 120.0]] }),
+      created: hobbiton.created + chrono::Duration::seconds(1),
+      ..Default::default()
+    };
+    let shire = MapFeature {
+      world_id: world.world_id,
+      map_id: middle_earth.map_id,
+      name: "The Shire".to_owned(),
+      kind: "region".to_owned(),
+      geometry: json!({ "type": "Polygon", "coordinates": [[[50.0, 50.0], [150.0, 50.0], [150.0, 150.0], [50.0, 50.0]]] }),
+      created: hobbiton.created + chrono::Duration::seconds(2),
+      ..Default::default()
+    };
+
+    seed(
+      &database,
+      vec![
+        &user,
+        &world,
+        &location,
+        &middle_earth,
+        &beleriand,
+        &hobbiton,
+        &road,
+        &shire,
+      ],
+    );
+
+    (
+      world,
+      vec![middle_earth, beleriand],
+      vec![hobbiton, road, shire],
+    )
+  }
+
+  fn names(features: &[FeatureEntity]) -> Vec<String> {
+    features
+      .iter()
+      .map(|feature| feature.data.name.clone())
+      .collect()
+  }
+
+  #[test]
+  fn test_get_map() {
+    let database = TestDatabaseWrapper::new();
+    let (world, maps, _) = seed_maps(&database);
+
+    for map in maps {
+      let found = database.wrapper.get_map_by_id(
+        &WorldID::from_uuid(world.world_id),
+        &MapID::from_uuid(map.map_id),
+      );
+      assert_that(&found)
+        .is_some()
+        .is_equal_to(MapEntity::from(map));
+    }
+  }
+
+  #[test]
+  fn test_get_map_wrong_world() {
+    let database = TestDatabaseWrapper::new();
+    let (_, maps, _) = seed_maps(&database);
+
+    let found = database
+      .wrapper
+      .get_map_by_id(&Default::default(), &MapID::from_uuid(maps[0].map_id));
+    assert_that(&found).is_none();
+  }
+
+  #[test]
+  fn test_list_maps() {
+    let database = TestDatabaseWrapper::new();
+    let (world, _, _) = seed_maps(&database);
+
+    let maps = database.wrapper.list_maps(
+      &WorldID::from_uuid(world.world_id),
+      Pagination {
+        offset: 0,
+        limit: 10,
+      },
+    );
+    assert_that(&maps.total).is_equal_to(2);
+    assert_that(
+      &maps
+        .entries
+        .iter()
+        .map(|map| map.data.name.clone())
+        .collect::<Vec<_>>(),
+    )
+    .is_equal_to(vec!["Beleriand".to_owned(), "Middle-earth".to_owned()]);
+  }
+
+  #[test]
+  fn test_create_and_update_map() {
+    let database = TestDatabaseWrapper::new();
+    let (world, _, _) = seed_maps(&database);
+
+    let data = MapData {
+      world: WorldID::from_uuid(world.world_id),
+      name: "Númenor".to_owned(),
+      image: None,
+    };
+    let created = database.wrapper.create_map(data.clone()).unwrap();
+    assert_that(&created.data).is_equal_to(&data);
+
+    let mut map = created.clone();
+    map.data.image = Some(MapImage {
+      id: MapImageID::default(),
+      content_type: "image/webp".to_owned(),
+      width: 640,
+      height: 480,
+    });
+    let updated = database.wrapper.update_map(map.clone()).unwrap();
+    assert_that(&updated.data).is_equal_to(&map.data);
+    assert_that(&updated.identity.version).is_not_equal_to(created.identity.version);
+
+    let updated = database.wrapper.update_map(map);
+    assert_that(&updated)
+      .is_err()
+      .is_equal_to(PersistMapError::OptimisticLockFailure);
+  }
+
+  #[test]
+  fn test_create_map_unknown_world() {
+    let database = TestDatabaseWrapper::new();
+
+    let created = database.wrapper.create_map(MapData {
+      world: Default::default(),
+      name: "Númenor".to_owned(),
+      image: None,
+    });
+    assert_that(&created)
+      .is_err()
+      .is_equal_to(PersistMapError::WorldNotFound);
+  }
+
+  #[test]
+  fn test_delete_map_removes_features() {
+    let database = TestDatabaseWrapper::new();
+    let (world, maps, _) = seed_maps(&database);
+    let world_id = WorldID::from_uuid(world.world_id);
+    let map_id = MapID::from_uuid(maps[0].map_id);
+
+    let deleted = database.wrapper.delete_map(&world_id, &map_id);
+    assert_that(&deleted).is_ok();
+    assert_that(&database.wrapper.list_features(&world_id, &map_id, None)).is_empty();
+
+    let deleted = database.wrapper.delete_map(&world_id, &map_id);
+    assert_that(&deleted)
+      .is_err()
+      .is_equal_to(PersistMapError::MapNotFound);
+  }
+
+  #[test]
+  fn test_list_features() {
+    let database = TestDatabaseWrapper::new();
+    let (world, maps, features) = seed_maps(&database);
+    let world_id = WorldID::from_uuid(world.world_id);
+    let map_id = MapID::from_uuid(maps[0].map_id);
+
+    let all = database.wrapper.list_features(&world_id, &map_id, None);
+    assert_that(&all).is_equal_to(
+      features
+        .iter()
+        .cloned()
+        .map(FeatureEntity::from)
+        .collect::<Vec<_>>(),
+    );
+
+    let regions = database
+      .wrapper
+      .list_features(&world_id, &map_id, Some(FeatureKind::Region));
+    assert_that(&names(&regions)).is_equal_to(vec!["The Shire".to_owned()]);
+
+    let other_map =
+      database
+        .wrapper
+        .list_features(&world_id, &MapID::from_uuid(maps[1].map_id), None);
+    assert_that(&other_map).is_empty();
+  }
+
+  #[test]
+  fn test_is_link_in_world() {
+    let database = TestDatabaseWrapper::new();
+    let (world, _, features) = seed_maps(&database);
+    let world_id = WorldID::from_uuid(world.world_id);
+    let character = Character {
+      world_id: world.world_id,
+      ..Default::default()
+    };
+    seed(&database, vec![&character]);
+
+    let location = FeatureLink::Location(LocationID::from_uuid(features[0].location_id.unwrap()));
+    assert_that(&database.wrapper.is_link_in_world(&world_id, &location)).is_true();
+    assert_that(
+      &database
+        .wrapper
+        .is_link_in_world(&Default::default(), &location),
+    )
+    .is_false();
+
+    let character = FeatureLink::Character(CharacterID::from_uuid(character.character_id));
+    assert_that(&database.wrapper.is_link_in_world(&world_id, &character)).is_true();
+
+    let article = FeatureLink::Article(Default::default());
+    assert_that(&database.wrapper.is_link_in_world(&world_id, &article)).is_false();
+  }
+
+  #[test]
+  fn test_create_and_update_feature() {
+    let database = TestDatabaseWrapper::new();
+    let (world, maps, features) = seed_maps(&database);
+
+    let data = FeatureData {
+      world: WorldID::from_uuid(world.world_id),
+      map: MapID::from_uuid(maps[0].map_id),
+      name: "Bree".to_owned(),
+      geometry: Geometry::Point([320.5, 118.0]),
+      link: Some(FeatureLink::Location(LocationID::from_uuid(
+        features[0].location_id.unwrap(),
+      ))),
+    };
+    let created = database.wrapper.create_feature(data.clone()).unwrap();
+    assert_that(&created.data).is_equal_to(&data);
+
+    let mut feature = created;
+    feature.data.geometry = Geometry::LineString(vec![[0.0, 0.0], [10.0, 10.0]]);
+    feature.data.link = None;
+    let updated = database.wrapper.update_feature(feature.clone()).unwrap();
+    assert_that(&updated.data).is_equal_to(&feature.data);
+
+    let polylines = database.wrapper.list_features(
+      &feature.data.world,
+      &feature.data.map,
+      Some(FeatureKind::Polyline),
+    );
+    assert_that(&names(&polylines)).is_equal_to(vec!["East Road".to_owned(), "Bree".to_owned()]);
+  }
+
+  #[test]
+  fn test_create_feature_unknown_map() {
+    let database = TestDatabaseWrapper::new();
+    let (world, _, _) = seed_maps(&database);
+
+    let created = database.wrapper.create_feature(FeatureData {
+      world: WorldID::from_uuid(world.world_id),
+      map: Default::default(),
+      name: "Bree".to_owned(),
+      geometry: Geometry::Point([1.0, 1.0]),
+      link: None,
+    });
+    assert_that(&created)
+      .is_err()
+      .is_equal_to(PersistMapError::MapNotFound);
+  }
+
+  #[test]
+  fn test_create_feature_unknown_link() {
+    let database = TestDatabaseWrapper::new();
+    let (world, maps, _) = seed_maps(&database);
+
+    let created = database.wrapper.create_feature(FeatureData {
+      world: WorldID::from_uuid(world.world_id),
+      map: MapID::from_uuid(maps[0].map_id),
+      name: "Bree".to_owned(),
+      geometry: Geometry::Point([1.0, 1.0]),
+      link: Some(FeatureLink::Article(Default::default())),
+    });
+    assert_that(&created)
+      .is_err()
+      .is_equal_to(PersistMapError::LinkNotFound);
+  }
+
+  #[test]
+  fn test_update_unknown_feature() {
+    let database = TestDatabaseWrapper::new();
+    let (world, maps, _) = seed_maps(&database);
+
+    let feature = FeatureEntity::from(MapFeature {
+      world_id: world.world_id,
+      map_id: maps[0].map_id,
+      ..Default::default()
+    });
+    let updated = database.wrapper.update_feature(feature);
+    assert_that(&updated)
+      .is_err()
+      .is_equal_to(PersistMapError::FeatureNotFound);
+  }
+
+  #[test]
+  fn test_delete_feature() {
+    let database = TestDatabaseWrapper::new();
+    let (world, maps, features) = seed_maps(&database);
+    let world_id = WorldID::from_uuid(world.world_id);
+    let map_id = MapID::from_uuid(maps[0].map_id);
+    let feature_id = FeatureID::from_uuid(features[1].feature_id);
+
+    let deleted = database
+      .wrapper
+      .delete_feature(&world_id, &map_id, &feature_id);
+    assert_that(&deleted).is_ok();
+
+    let deleted = database
+      .wrapper
+      .delete_feature(&world_id, &map_id, &feature_id);
+    assert_that(&deleted)
+      .is_err()
+      .is_equal_to(PersistMapError::FeatureNotFound);
+  }
+
+  #[test]
+  fn test_deleting_location_unlinks_feature() {
+    let database = TestDatabaseWrapper::new();
+    let (world, maps, features) = seed_maps(&database);
+    let world_id = WorldID::from_uuid(world.world_id);
+    let map_id = MapID::from_uuid(maps[0].map_id);
+
+    let mut client = database.wrapper.client().unwrap();
+    client
+      .execute(
+        "DELETE FROM locations WHERE location_id = $1",
+        &[&features[0].location_id],
+      )
+      .unwrap();
+
+    let feature = database.wrapper.get_feature_by_id(
+      &world_id,
+      &map_id,
+      &FeatureID::from_uuid(features[0].feature_id),
+    );
+    assert_that(&feature)
+      .is_some()
+      .map(|feature| &feature.data.link)
+      .is_none();
+  }
+}
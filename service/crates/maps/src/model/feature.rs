@@ -0,0 +1,105 @@
+use crate::{FeatureID, Geometry, MapID};
+use universe_articles::ArticleID;
+use universe_characters::CharacterID;
+use universe_entity::Identity;
+use universe_locations::LocationID;
+use universe_worlds::WorldID;
+
+/// Another entity of the same world that a feature on a map refers to
+#[derive(Debug, PartialEq, Clone)]
+pub enum FeatureLink {
+  Location(LocationID),
+  Character(CharacterID),
+  Article(ArticleID),
+}
+
+impl FeatureLink {
+  /// Build a link to an entity from the type of entity and its ID
+  ///
+  /// # Arguments
+  /// * `kind` The type of entity. One of "location", "character" or "article"
+  /// * `id` The ID of the entity
+  ///
+  /// # Returns
+  /// The link to the entity, or `None` if the type isn't known or the ID is malformed
+  pub fn new(kind: &str, id: &str) -> Option<Self> {
+    match kind {
+      "location" => id.parse().ok().map(FeatureLink::Location),
+      "character" => id.parse().ok().map(FeatureLink::Character),
+      "article" => id.parse().ok().map(FeatureLink::Article),
+      _ => None,
+    }
+  }
+
+  /// The type of entity that this links to
+  pub fn kind(&self) -> &'static str {
+    match self {
+      FeatureLink::Location(_) => "location",
+      FeatureLink::Character(_) => "character",
+      FeatureLink::Article(_) => "article",
+    }
+  }
+
+  /// The ID of the entity that this links to
+  pub fn id(&self) -> String {
+    match self {
+      FeatureLink::Location(id) => id.to_string(),
+      FeatureLink::Character(id) => id.to_string(),
+      FeatureLink::Article(id) => id.to_string(),
+    }
+  }
+}
+
+/// Struct to represent the data about a single feature on a map
+#[derive(Debug, PartialEq, Clone)]
+pub struct FeatureData {
+  /// The world that the map belongs to
+  pub world: WorldID,
+  /// The map that the feature is placed on
+  pub map: MapID,
+  /// The name of the feature. This may be empty
+  pub name: String,
+  /// The shape of the feature, in pixels of the map image
+  pub geometry: Geometry,
+  /// The entity that the feature refers to, if any
+  pub link: Option<FeatureLink>,
+}
+
+/// Type to represent the entity that is a persisted map feature record
+#[derive(Debug, PartialEq, Clone)]
+pub struct FeatureEntity {
+  pub identity: Identity<FeatureID>,
+  pub data: FeatureData,
+}
+
+#[cfg(test)]
+impl From<universe_testdata::MapFeature> for FeatureEntity {
+  fn from(feature: universe_testdata::MapFeature) -> FeatureEntity {
+    let link = match (
+      feature.location_id,
+      feature.character_id,
+      feature.article_id,
+    ) {
+      (Some(id), _, _) => Some(FeatureLink::Location(LocationID::from_uuid(id))),
+      (_, Some(id), _) => Some(FeatureLink::Character(CharacterID::from_uuid(id))),
+      (_, _, Some(id)) => Some(FeatureLink::Article(ArticleID::from_uuid(id))),
+      _ => None,
+    };
+
+    FeatureEntity {
+      identity: Identity {
+        id: FeatureID::from_uuid(feature.feature_id),
+        version: feature.version,
+        created: feature.created,
+        updated: feature.updated,
+      },
+      data: FeatureData {
+        world: WorldID::from_uuid(feature.world_id),
+        map: MapID::from_uuid(feature.map_id),
+        name: feature.name.clone(),
+        geometry: serde_json::from_value(feature.geometry).unwrap(),
+        link,
+      },
+    }
+  }
+}
@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString};
+
+/// A position on a map, as the `[x, y]` pixel coordinates within the map image. The origin is the
+/// top-left corner of the image, with `x` increasing to the right and `y` increasing downwards
+pub type Position = [f64; 2];
+
+/// The shape of a feature on a map.
+///
+/// This serializes to and from a GeoJSON geometry object, with the coordinates in image pixels
+/// rather than longitude and latitude.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "coordinates")]
+pub enum Geometry {
+  /// A single position
+  Point(Position),
+  /// A line made up of two or more positions
+  LineString(Vec<Position>),
+  /// An area, made up of an outer ring followed by any holes in it. Every ring starts and ends
+  /// at the same position
+  Polygon(Vec<Vec<Position>>),
+}
+
+/// The different kinds of feature that can be placed on a map, each of which is drawn as its own
+/// layer
+#[derive(Debug, PartialEq, Clone, Copy, Display, EnumString, Serialize)]
+#[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum FeatureKind {
+  /// A single marked point
+  Pin,
+  /// A line, such as a road or a river
+  Polyline,
+  /// An area, such as a kingdom or a forest
+  Region,
+}
+
+/// Errors that can occur when checking the geometry of a feature against its map
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum GeometryError {
+  #[error("A line needs at least 2 positions, and every ring of an area needs at least 4")]
+  TooFewPositions,
+  #[error("Every ring of an area must start and end at the same position")]
+  UnclosedRing,
+  #[error("Every position must be within the map image")]
+  OutOfBounds,
+}
+
+impl Geometry {
+  /// Work out what kind of feature has this geometry
+  pub fn kind(&self) -> FeatureKind {
+    match self {
+      Geometry::Point(_) => FeatureKind::Pin,
+      Geometry::LineString(_) => FeatureKind::Polyline,
+      Geometry::Polygon(_) => FeatureKind::Region,
+    }
+  }
+
+  /// Check that the geometry is well formed, and fits within a map image of the given size
+  ///
+  /// # Arguments
+  /// * `width` The width of the map image, in pixels
+  /// * `height` The height of the map image, in pixels
+  ///
+  /// # Returns
+  /// Nothing if the geometry is valid, or the first problem found with it otherwise
+  pub fn validate(&self, width: u32, height: u32) -> Result<(), GeometryError> {
+    let positions: Vec<&Position> = match self {
+      Geometry::Point(position) => vec![position],
+      Geometry::LineString(line) => {
+        if line.len() < 2 {
+          return Err(GeometryError::TooFewPositions);
+        }
+        line.iter().collect()
+      }
+      Geometry::Polygon(rings) => {
+        if rings.is_empty() || rings.iter().any(|ring| ring.len() < 4) {
+          return Err(GeometryError::TooFewPositions);
+        }
+        if rings.iter().any(|ring| ring.first() != ring.last()) {
+          return Err(GeometryError::UnclosedRing);
+        }
+        rings.iter().flatten().collect()
+      }
+    };
+
+    let (width, height) = (f64::from(width), f64::from(height));
+    let in_bounds = positions
+      .iter()
+      .all(|[x, y]| (0.0..=width).contains(x) && (0.0..=height).contains(y));
+
+    if in_bounds {
+      Ok(())
+    } else {
+      Err(GeometryError::OutOfBounds)
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use rstest::rstest;
+  use serde_json::json;
+  use spectral::prelude::*;
+
+  #[rstest(input, expected,
+    case(Geometry::Point([10.0, 20.0]), json!({ "type": "Point", "coordinates": [10.0, 20.0] })),
+    case(
+      Geometry::LineString(vec![[0.0, 0.0], [5.5, 6.5]]),
+      json!({ "type": "LineString", "coordinates": [[0.0, 0.0], [5.5, 6.5]] })
+    ),
+    case(
+      Geometry::Polygon(vec![vec![[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 0.0]]]),
+      json!({ "type": "Polygon", "coordinates": [[[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 0.0]]] })
+    )
+  )]
+  fn test_serialize_geometry(input: Geometry, expected: serde_json::Value) {
+    assert_that(&serde_json::to_value(&input).unwrap()).is_equal_to(&expected);
+
+    let parsed: Geometry = serde_json::from_value(expected).unwrap();
+    assert_that(&parsed).is_equal_to(input);
+  }
+
+  #[rstest(
+    input,
+    case(json!({ "type": "MultiPoint", "coordinates": [[0.0, 0.0]] })),
+    case(json!({ "type": "Point", "coordinates": [0.0] })),
+    case(json!({ "type": "Point" }))
+  )]
+  fn test_parse_unsupported_geometry(input: serde_json::Value) {
+    let parsed: Result<Geometry, _> = serde_json::from_value(input);
+    assert_that(&parsed).is_err();
+  }
+
+  #[rstest(input, expected,
+    case(Geometry::Point([0.0, 0.0]), FeatureKind::Pin),
+    case(Geometry::LineString(vec![]), FeatureKind::Polyline),
+    case(Geometry::Polygon(vec![]), FeatureKind::Region)
+  )]
+  fn test_geometry_kind(input: Geometry, expected: FeatureKind) {
+    assert_that(&input.kind()).is_equal_to(expected);
+  }
+
+  #[rstest(input, expected,
+    case(Geometry::Point([0.0, 0.0]), Ok(())),
+    case(Geometry::Point([100.0, 50.0]), Ok(())),
+    case(Geometry::Point([100.5, 50.0]), Err(GeometryError::OutOfBounds)),
+    case(Geometry::Point([-0.5, 10.0]), Err(GeometryError::OutOfBounds)),
+    case(Geometry::Point([10.0, 51.0]), Err(GeometryError::OutOfBounds)),
+    case(Geometry::LineString(vec![[0.0, 0.0], [100.0, 50.0]]), Ok(())),
+    case(Geometry::LineString(vec![[0.0, 0.0]]), Err(GeometryError::TooFewPositions)),
+    case(Geometry::LineString(vec![[0.0, 0.0], [0.0, 60.0]]), Err(GeometryError::OutOfBounds)),
+    case(
+      Geometry::Polygon(vec![vec![[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 0.0]]]),
+      Ok(())
+    ),
+    case(Geometry::Polygon(vec![]), Err(GeometryError::TooFewPositions)),
+    case(
+      Geometry::Polygon(vec![vec![[0.0, 0.0], [10.0, 0.0], [0.0, 0.0]]]),
+      Err(GeometryError::TooFewPositions)
+    ),
+    case(
+      Geometry::Polygon(vec![vec![[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]]]),
+      Err(GeometryError::UnclosedRing)
+    ),
+    case(
+      Geometry::Polygon(vec![vec![[0.0, 0.0], [200.0, 0.0], [200.0, 10.0], [0.0, 0.0]]]),
+      Err(GeometryError::OutOfBounds)
+    )
+  )]
+  fn test_validate_geometry(input: Geometry, expected: Result<(), GeometryError>) {
+    assert_that(&input.validate(100, 50)).is_equal_to(expected);
+  }
+}
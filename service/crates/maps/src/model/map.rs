@@ -0,0 +1,44 @@
+use crate::{MapID, MapImage};
+use universe_entity::Identity;
+use universe_worlds::WorldID;
+
+/// Struct to represent the data about a single Map
+#[derive(Debug, PartialEq, Clone)]
+pub struct MapData {
+  /// The world that the map belongs to
+  pub world: WorldID,
+  pub name: String,
+  /// The image that the map is drawn on, or `None` if one hasn't been uploaded yet
+  pub image: Option<MapImage>,
+}
+
+/// Type to represent the entity that is a persisted map record
+#[derive(Debug, PartialEq, Clone)]
+pub struct MapEntity {
+  pub identity: Identity<MapID>,
+  pub data: MapData,
+}
+
+#[cfg(test)]
+impl From<universe_testdata::Map> for MapEntity {
+  fn from(map: universe_testdata::Map) -> MapEntity {
+    MapEntity {
+      identity: Identity {
+        id: MapID::from_uuid(map.map_id),
+        version: map.version,
+        created: map.created,
+        updated: map.updated,
+      },
+      data: MapData {
+        world: WorldID::from_uuid(map.world_id),
+        name: map.name.clone(),
+        image: map.image_id.map(|image_id| MapImage {
+          id: crate::MapImageID::from_uuid(image_id),
+          content_type: map.image_content_type.clone().unwrap_or_default(),
+          width: map.image_width.unwrap_or_default() as u32,
+          height: map.image_height.unwrap_or_default() as u32,
+        }),
+      },
+    }
+  }
+}
@@ -0,0 +1,146 @@
+use bytes::BytesMut;
+use postgres::types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
+use serde::Serialize;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Representation of a Map ID of some map in the system.
+///
+/// A Map ID is any valid UUID.
+#[derive(Debug, PartialEq, Clone, Serialize, FromSql)]
+pub struct MapID(Uuid);
+
+/// Errors that can happen when parsing a string into a Map ID.
+#[derive(Debug, PartialEq, Clone, thiserror::Error)]
+pub enum MapIDParseError {
+  #[error("Map ID was malformed: {0}")]
+  Malformed(#[from] uuid::Error),
+}
+
+impl MapID {
+  /// Construct a Map ID from a UUID value
+  ///
+  /// # Arguments
+  /// * `uuid` The UUID to use
+  ///
+  /// # Returns
+  /// The Map ID
+  #[allow(unused)]
+  pub fn from_uuid(uuid: Uuid) -> Self {
+    MapID(uuid)
+  }
+}
+
+impl std::fmt::Display for MapID {
+  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl Default for MapID {
+  fn default() -> Self {
+    MapID(Uuid::new_v4())
+  }
+}
+/// Implementation of the standard `FromStr` trait to allow us to parse any String into a `MapID` object
+impl FromStr for MapID {
+  type Err = MapIDParseError;
+
+  /// Attempt to parse a string into a MapID object.
+  ///
+  /// A Map ID is any valid UUID.
+  ///
+  /// # Arguments
+  /// * `s` The string to parse
+  ///
+  /// # Returns
+  /// The result of parsing the Map ID. Either an `MapID` object or an error if the incoming
+  /// string was not valid.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let uuid: Uuid = s.trim().parse()?;
+    Ok(MapID(uuid))
+  }
+}
+
+/// Allow us to pass `MapID` objects to Postgres as part of executing a database query.
+///
+/// The implementation of this trait allows objects of this type to be used directly as database
+/// binds without ever needing to extract the string from inside it.
+impl ToSql for MapID {
+  fn to_sql(
+    &self,
+    t: &Type,
+    w: &mut BytesMut,
+  ) -> Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+    self.0.to_sql(t, w)
+  }
+
+  accepts!(UUID);
+  to_sql_checked!();
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use assert_matches::*;
+  use serde_json::json;
+  use spectral::prelude::*;
+  use test_env_log::test;
+
+  #[test]
+  fn test_parse_valid_map_id() {
+    let map_id: Result<MapID, MapIDParseError> = "f2c55656-d7a1-4e41-a311-fe653b9b15de".parse();
+
+    assert_that(&map_id).is_ok().is_equal_to(MapID(
+      "f2c55656-d7a1-4e41-a311-fe653b9b15de".parse().unwrap(),
+    ));
+  }
+
+  #[test]
+  fn test_parse_padded_map_id() {
+    let map_id: Result<MapID, MapIDParseError> =
+      "  f2c55656-d7a1-4e41-a311-fe653b9b15de    ".parse();
+
+    assert_that(&map_id).is_ok().is_equal_to(MapID(
+      "f2c55656-d7a1-4e41-a311-fe653b9b15de".parse().unwrap(),
+    ));
+  }
+
+  #[test]
+  fn test_parse_empty_string() {
+    let map_id: Result<MapID, MapIDParseError> = "".parse();
+
+    assert_matches!(map_id.unwrap_err(), MapIDParseError::Malformed(_));
+  }
+
+  #[test]
+  fn test_parse_blank_string() {
+    let map_id: Result<MapID, MapIDParseError> = "     ".parse();
+
+    assert_matches!(map_id.unwrap_err(), MapIDParseError::Malformed(_));
+  }
+
+  #[test]
+  fn test_parse_invalid_string_bad_length() {
+    let map_id: Result<MapID, MapIDParseError> = "non-uuid".parse();
+
+    assert_matches!(map_id.unwrap_err(), MapIDParseError::Malformed(_));
+  }
+
+  #[test]
+  fn test_parse_invalid_string_bad_character() {
+    let map_id: Result<MapID, MapIDParseError> = "C37837C7-3E8C-4235-8A00-0845F598D12Z".parse();
+
+    assert_matches!(map_id.unwrap_err(), MapIDParseError::Malformed(_));
+  }
+
+  #[test]
+  fn test_serialize_valid_map_id() {
+    let map_id = MapID("f2c55656-d7a1-4e41-a311-fe653b9b15de".parse().unwrap());
+
+    let serialized = serde_json::to_value(map_id);
+    assert_that(&serialized)
+      .is_ok()
+      .is_equal_to(json!("f2c55656-d7a1-4e41-a311-fe653b9b15de"));
+  }
+}
@@ -0,0 +1,84 @@
+use bytes::BytesMut;
+use postgres::types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
+use serde::Serialize;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Representation of the ID of an image that has been uploaded for a map.
+///
+/// Every upload gets a new ID, so the URL of a map image never changes and can be cached forever.
+#[derive(Debug, PartialEq, Clone, Serialize, FromSql)]
+pub struct MapImageID(Uuid);
+
+impl MapImageID {
+  /// Construct a Map Image ID from a UUID value
+  ///
+  /// # Arguments
+  /// * `uuid` The UUID to use
+  ///
+  /// # Returns
+  /// The Map Image ID
+  pub fn from_uuid(uuid: Uuid) -> Self {
+    MapImageID(uuid)
+  }
+}
+
+impl std::fmt::Display for MapImageID {
+  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl Default for MapImageID {
+  fn default() -> Self {
+    MapImageID(Uuid::new_v4())
+  }
+}
+
+impl FromStr for MapImageID {
+  type Err = uuid::Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let uuid: Uuid = s.trim().parse()?;
+    Ok(MapImageID(uuid))
+  }
+}
+
+impl ToSql for MapImageID {
+  fn to_sql(
+    &self,
+    t: &Type,
+    w: &mut BytesMut,
+  ) -> Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+    self.0.to_sql(t, w)
+  }
+
+  accepts!(UUID);
+  to_sql_checked!();
+}
+
+/// The details of the image that a map is drawn on
+#[derive(Debug, PartialEq, Clone)]
+pub struct MapImage {
+  pub id: MapImageID,
+  /// The MIME type of the image
+  pub content_type: String,
+  /// The width of the image, in pixels
+  pub width: u32,
+  /// The height of the image, in pixels
+  pub height: u32,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use spectral::prelude::*;
+
+  #[test]
+  fn test_parse_map_image_id() {
+    let result: Result<MapImageID, _> = " 6c1a3f4e-5b8d-4f2a-9e1c-7d3b2a1f0e9d ".parse();
+    assert_that(&result.unwrap()).is_equal_to(MapImageID::from_uuid(
+      "6c1a3f4e-5b8d-4f2a-9e1c-7d3b2a1f0e9d".parse().unwrap(),
+    ));
+  }
+}
@@ -0,0 +1,24 @@
+use crate::Geometry;
+use universe_entity::EntityKey;
+
+/// Details of a map to create as part of importing or copying the maps of a world
+#[derive(Debug, PartialEq, Clone)]
+pub struct MapImport {
+  /// The key that the map is known by, which is recorded against the ID of the new map
+  pub key: String,
+  pub name: String,
+  /// The raw bytes of the image that the map is drawn on, if it has one
+  pub image: Option<Vec<u8>>,
+  /// The features to place on the map. These need the map to have an image
+  pub features: Vec<FeatureImport>,
+}
+
+/// Details of a feature to place on a map that is being imported or copied
+#[derive(Debug, PartialEq, Clone)]
+pub struct FeatureImport {
+  pub name: String,
+  pub geometry: Geometry,
+  /// The entity that the feature refers to, by the key that it was known by before it was
+  /// imported or copied itself
+  pub link: Option<EntityKey>,
+}
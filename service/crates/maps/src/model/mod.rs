@@ -0,0 +1,15 @@
+mod feature;
+mod feature_id;
+mod geometry;
+mod map;
+mod map_id;
+mod map_image;
+mod map_import;
+
+pub use feature::*;
+pub use feature_id::*;
+pub use geometry::*;
+pub use map::*;
+pub use map_id::*;
+pub use map_image::*;
+pub use map_import::*;
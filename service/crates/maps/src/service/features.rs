@@ -0,0 +1,589 @@
+use super::repository::*;
+use crate::model::*;
+use tracing::warn;
+use universe_worlds::WorldID;
+
+/// The Map Feature Service to allow interactions with the features placed on maps
+pub trait MapFeatureService: Send + Sync {
+  /// Retrieve the feature that has the given unique ID, as long as it is on the given map
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map that the feature is on
+  /// * `feature_id` The ID of the feature to retrieve
+  ///
+  /// # Returns
+  /// The feature, or `None` if it wasn't found
+  fn get_feature_by_id(
+    &self,
+    world_id: &WorldID,
+    map_id: &MapID,
+    feature_id: &FeatureID,
+  ) -> Option<FeatureEntity>;
+
+  /// Retrieve the features on a map, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map to list the features of
+  /// * `kind` The kind of features to list, or `None` for all of them
+  ///
+  /// # Returns
+  /// The features
+  fn list_features(
+    &self,
+    world_id: &WorldID,
+    map_id: &MapID,
+    kind: Option<FeatureKind>,
+  ) -> Vec<FeatureEntity>;
+
+  /// Place a new feature on a map. The map must already have an image, and the feature must fit
+  /// within it
+  ///
+  /// # Arguments
+  /// * `feature` The feature data to create the feature from
+  ///
+  /// # Returns
+  /// The feature that was persisted
+  fn create_feature(&self, feature: FeatureData) -> Result<FeatureEntity, CreateFeatureError>;
+
+  /// Update an existing feature.
+  ///
+  /// This will load the feature by ID, and then call a provided callback to mutate the feature
+  /// before persisting the changes back to the database. Features can't be moved to another map.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map that the feature is on
+  /// * `feature_id` The ID of the feature to update
+  /// * `updater` The callback to mutate the feature with
+  ///
+  /// # Returns
+  /// The newly updated feature
+  fn update_feature(
+    &self,
+    world_id: &WorldID,
+    map_id: &MapID,
+    feature_id: &FeatureID,
+    updater: &mut dyn FnMut(FeatureData) -> Result<FeatureData, Box<dyn std::error::Error>>,
+  ) -> Result<FeatureEntity, UpdateFeatureError>;
+
+  /// Remove a feature from a map
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map that the feature is on
+  /// * `feature_id` The ID of the feature to delete
+  fn delete_feature(
+    &self,
+    world_id: &WorldID,
+    map_id: &MapID,
+    feature_id: &FeatureID,
+  ) -> Result<(), DeleteFeatureError>;
+}
+
+/// Enumeration of validation errors for a feature
+#[derive(Debug, PartialEq)]
+pub enum FeatureValidationError {
+  /// The map has no image yet, so there is nothing to place the feature on
+  NoImage,
+  /// The geometry of the feature is malformed, or doesn't fit within the map image
+  Geometry(GeometryError),
+  /// The entity that the feature links to isn't part of the world
+  UnknownLink,
+}
+
+/// Enumeration of reasons why we failed to create a new feature
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum CreateFeatureError {
+  #[error("Feature details were invalid: {0:?}")]
+  ValidationError(Vec<FeatureValidationError>),
+  #[error("The map was not found")]
+  UnknownMap,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to update an existing feature
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateFeatureError {
+  #[error("Feature details were invalid: {0:?}")]
+  ValidationError(Vec<FeatureValidationError>),
+  #[error("The feature was not found")]
+  UnknownFeature,
+  #[error("The version of the feature record did not match")]
+  OptimisticLockFailure,
+  #[error("An error occurred updating the feature details: {0}")]
+  UpdateError(Box<dyn std::error::Error>),
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to delete a feature
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum DeleteFeatureError {
+  #[error("The feature was not found")]
+  UnknownFeature,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// The Map Feature Service to allow interactions with the features placed on maps
+pub struct MapFeatureServiceImpl<Repo> {
+  repository: Repo,
+}
+
+/// Create a new Map Feature Service
+///
+/// # Returns
+/// The Map Feature Service
+pub fn new_map_feature_service<Repo: MapRepository + Send + Sync>(
+  repository: Repo,
+) -> impl MapFeatureService {
+  MapFeatureServiceImpl { repository }
+}
+
+impl<Repo: MapRepository + Send + Sync> MapFeatureServiceImpl<Repo> {
+  /// Check a feature against the map that it is on
+  ///
+  /// # Arguments
+  /// * `map` The map that the feature is on
+  /// * `feature` The feature to check
+  ///
+  /// # Returns
+  /// Every problem found with the feature
+  fn validate(&self, map: &MapEntity, feature: &FeatureData) -> Vec<FeatureValidationError> {
+    let mut errors = vec![];
+
+    match &map.data.image {
+      None => errors.push(FeatureValidationError::NoImage),
+      Some(image) => {
+        if let Err(e) = feature.geometry.validate(image.width, image.height) {
+          errors.push(FeatureValidationError::Geometry(e));
+        }
+      }
+    }
+
+    if let Some(link) = &feature.link {
+      if !self.repository.is_link_in_world(&feature.world, link) {
+        errors.push(FeatureValidationError::UnknownLink);
+      }
+    }
+
+    if !errors.is_empty() {
+      warn!(
+        "Feature on map {} is invalid: {:?}",
+        map.identity.id, errors
+      );
+    }
+    errors
+  }
+}
+
+impl<Repo: MapRepository + Send + Sync> MapFeatureService for MapFeatureServiceImpl<Repo> {
+  /// Retrieve the feature that has the given unique ID, as long as it is on the given map
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map that the feature is on
+  /// * `feature_id` The ID of the feature to retrieve
+  ///
+  /// # Returns
+  /// The feature, or `None` if it wasn't found
+  fn get_feature_by_id(
+    &self,
+    world_id: &WorldID,
+    map_id: &MapID,
+    feature_id: &FeatureID,
+  ) -> Option<FeatureEntity> {
+    let feature = self
+      .repository
+      .get_feature_by_id(world_id, map_id, feature_id);
+
+    if feature.is_none() {
+      warn!("No feature found with ID {} on map {}", feature_id, map_id);
+    }
+
+    feature
+  }
+
+  /// Retrieve the features on a map, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map to list the features of
+  /// * `kind` The kind of features to list, or `None` for all of them
+  ///
+  /// # Returns
+  /// The features
+  fn list_features(
+    &self,
+    world_id: &WorldID,
+    map_id: &MapID,
+    kind: Option<FeatureKind>,
+  ) -> Vec<FeatureEntity> {
+    self.repository.list_features(world_id, map_id, kind)
+  }
+
+  /// Place a new feature on a map. The map must already have an image, and the feature must fit
+  /// within it
+  ///
+  /// # Arguments
+  /// * `feature` The feature data to create the feature from
+  ///
+  /// # Returns
+  /// The feature that was persisted
+  fn create_feature(&self, feature: FeatureData) -> Result<FeatureEntity, CreateFeatureError> {
+    let map = self
+      .repository
+      .get_map_by_id(&feature.world, &feature.map)
+      .ok_or(CreateFeatureError::UnknownMap)?;
+
+    let errors = self.validate(&map, &feature);
+    if !errors.is_empty() {
+      return Err(CreateFeatureError::ValidationError(errors));
+    }
+
+    let created = self.repository.create_feature(feature)?;
+    Ok(created)
+  }
+
+  /// Update an existing feature.
+  ///
+  /// This will load the feature by ID, and then call a provided callback to mutate the feature
+  /// before persisting the changes back to the database. Features can't be moved to another map.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map that the feature is on
+  /// * `feature_id` The ID of the feature to update
+  /// * `updater` The callback to mutate the feature with
+  ///
+  /// # Returns
+  /// The newly updated feature
+  fn update_feature(
+    &self,
+    world_id: &WorldID,
+    map_id: &MapID,
+    feature_id: &FeatureID,
+    updater: &mut dyn FnMut(FeatureData) -> Result<FeatureData, Box<dyn std::error::Error>>,
+  ) -> Result<FeatureEntity, UpdateFeatureError> {
+    let map = self
+      .repository
+      .get_map_by_id(world_id, map_id)
+      .ok_or(UpdateFeatureError::UnknownFeature)?;
+    let feature = self
+      .get_feature_by_id(world_id, map_id, feature_id)
+      .ok_or(UpdateFeatureError::UnknownFeature)?;
+
+    let world = feature.data.world.clone();
+    let map_id = feature.data.map.clone();
+    let updated = updater(feature.data).map_err(UpdateFeatureError::UpdateError)?;
+    let updated = FeatureData {
+      world,
+      map: map_id,
+      ..updated
+    };
+
+    let errors = self.validate(&map, &updated);
+    if !errors.is_empty() {
+      return Err(UpdateFeatureError::ValidationError(errors));
+    }
+
+    let saved = self.repository.update_feature(FeatureEntity {
+      identity: feature.identity,
+      data: updated,
+    })?;
+    Ok(saved)
+  }
+
+  /// Remove a feature from a map
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map that the feature is on
+  /// * `feature_id` The ID of the feature to delete
+  fn delete_feature(
+    &self,
+    world_id: &WorldID,
+    map_id: &MapID,
+    feature_id: &FeatureID,
+  ) -> Result<(), DeleteFeatureError> {
+    self
+      .repository
+      .delete_feature(world_id, map_id, feature_id)?;
+    Ok(())
+  }
+}
+
+impl From<PersistMapError> for CreateFeatureError {
+  fn from(e: PersistMapError) -> Self {
+    warn!("Error creating feature: {}", e);
+    match e {
+      PersistMapError::WorldNotFound | PersistMapError::MapNotFound => {
+        CreateFeatureError::UnknownMap
+      }
+      PersistMapError::LinkNotFound => {
+        CreateFeatureError::ValidationError(vec![FeatureValidationError::UnknownLink])
+      }
+      _ => CreateFeatureError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistMapError> for UpdateFeatureError {
+  fn from(e: PersistMapError) -> Self {
+    warn!("Error updating feature: {}", e);
+    match e {
+      PersistMapError::MapNotFound | PersistMapError::FeatureNotFound => {
+        UpdateFeatureError::UnknownFeature
+      }
+      PersistMapError::LinkNotFound => {
+        UpdateFeatureError::ValidationError(vec![FeatureValidationError::UnknownLink])
+      }
+      PersistMapError::OptimisticLockFailure => UpdateFeatureError::OptimisticLockFailure,
+      _ => UpdateFeatureError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistMapError> for DeleteFeatureError {
+  fn from(e: PersistMapError) -> Self {
+    warn!("Error deleting feature: {}", e);
+    match e {
+      PersistMapError::MapNotFound | PersistMapError::FeatureNotFound => {
+        DeleteFeatureError::UnknownFeature
+      }
+      _ => DeleteFeatureError::UnknownError,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use assert_matches::assert_matches;
+  use spectral::prelude::*;
+  use test_env_log::test;
+  use universe_entity::Identity;
+  use universe_locations::LocationID;
+
+  fn map(image: bool) -> MapEntity {
+    MapEntity {
+      identity: Identity::default(),
+      data: MapData {
+        world: Default::default(),
+        name: "Middle-earth".to_owned(),
+        image: if image {
+          Some(MapImage {
+            id: Default::default(),
+            content_type: "image/png".to_owned(),
+            width: 100,
+            height: 50,
+          })
+        } else {
+          None
+        },
+      },
+    }
+  }
+
+  fn feature(map: &MapEntity, geometry: Geometry, link: Option<FeatureLink>) -> FeatureData {
+    FeatureData {
+      world: map.data.world.clone(),
+      map: map.identity.id.clone(),
+      name: "Hobbiton".to_owned(),
+      geometry,
+      link,
+    }
+  }
+
+  fn repository_with_map(map: &MapEntity) -> MockMapRepository {
+    let mut repository = MockMapRepository::new();
+    let returned = map.clone();
+    repository
+      .expect_get_map_by_id()
+      .returning(move |_, _| Some(returned.clone()));
+    repository
+  }
+
+  #[test]
+  fn test_create_feature() {
+    let map = map(true);
+    let mut repository = repository_with_map(&map);
+    repository
+      .expect_is_link_in_world()
+      .times(1)
+      .return_const(true);
+    repository
+      .expect_create_feature()
+      .times(1)
+      .returning(|data| {
+        Ok(FeatureEntity {
+          identity: Identity::default(),
+          data,
+        })
+      });
+
+    let service = new_map_feature_service(repository);
+    let data = feature(
+      &map,
+      Geometry::Point([100.0, 50.0]),
+      Some(FeatureLink::Location(LocationID::default())),
+    );
+    let created = service.create_feature(data.clone());
+
+    assert_that(&created.unwrap().data).is_equal_to(data);
+  }
+
+  #[test]
+  fn test_create_feature_unknown_map() {
+    let map = map(true);
+    let mut repository = MockMapRepository::new();
+    repository
+      .expect_get_map_by_id()
+      .times(1)
+      .return_const(None);
+    repository.expect_create_feature().never();
+
+    let service = new_map_feature_service(repository);
+    let created = service.create_feature(feature(&map, Geometry::Point([1.0, 1.0]), None));
+
+    assert_that(&created)
+      .is_err()
+      .is_equal_to(CreateFeatureError::UnknownMap);
+  }
+
+  #[test]
+  fn test_create_feature_without_image() {
+    let map = map(false);
+    let mut repository = repository_with_map(&map);
+    repository.expect_create_feature().never();
+
+    let service = new_map_feature_service(repository);
+    let created = service.create_feature(feature(&map, Geometry::Point([1.0, 1.0]), None));
+
+    assert_that(&created)
+      .is_err()
+      .is_equal_to(CreateFeatureError::ValidationError(vec![
+        FeatureValidationError::NoImage,
+      ]));
+  }
+
+  #[test]
+  fn test_create_feature_invalid() {
+    let map = map(true);
+    let mut repository = repository_with_map(&map);
+    repository
+      .expect_is_link_in_world()
+      .times(1)
+      .return_const(false);
+    repository.expect_create_feature().never();
+
+    let service = new_map_feature_service(repository);
+    let created = service.create_feature(feature(
+      &map,
+      Geometry::LineString(vec![[0.0, 0.0], [101.0, 10.0]]),
+      Some(FeatureLink::Location(LocationID::default())),
+    ));
+
+    assert_that(&created)
+      .is_err()
+      .is_equal_to(CreateFeatureError::ValidationError(vec![
+        FeatureValidationError::Geometry(GeometryError::OutOfBounds),
+        FeatureValidationError::UnknownLink,
+      ]));
+  }
+
+  #[test]
+  fn test_update_feature_keeps_map() {
+    let map = map(true);
+    let existing = FeatureEntity {
+      identity: Identity::default(),
+      data: feature(&map, Geometry::Point([1.0, 1.0]), None),
+    };
+    let mut repository = repository_with_map(&map);
+    let returned = existing.clone();
+    repository
+      .expect_get_feature_by_id()
+      .times(1)
+      .returning(move |_, _, _| Some(returned.clone()));
+    let expected_map = map.identity.id.clone();
+    repository
+      .expect_update_feature()
+      .withf(move |feature| feature.data.map == expected_map)
+      .times(1)
+      .returning(Ok);
+
+    let service = new_map_feature_service(repository);
+    let updated = service.update_feature(
+      &map.data.world,
+      &map.identity.id,
+      &existing.identity.id,
+      &mut |data| {
+        Ok(FeatureData {
+          map: MapID::default(),
+          geometry: Geometry::Point([20.0, 30.0]),
+          ..data
+        })
+      },
+    );
+
+    assert_that(&updated.unwrap().data.geometry).is_equal_to(Geometry::Point([20.0, 30.0]));
+  }
+
+  #[test]
+  fn test_update_feature_out_of_bounds() {
+    let map = map(true);
+    let existing = FeatureEntity {
+      identity: Identity::default(),
+      data: feature(&map, Geometry::Point([1.0, 1.0]), None),
+    };
+    let mut repository = repository_with_map(&map);
+    let returned = existing.clone();
+    repository
+      .expect_get_feature_by_id()
+      .times(1)
+      .returning(move |_, _, _| Some(returned.clone()));
+    repository.expect_update_feature().never();
+
+    let service = new_map_feature_service(repository);
+    let updated = service.update_feature(
+      &map.data.world,
+      &map.identity.id,
+      &existing.identity.id,
+      &mut |data| {
+        Ok(FeatureData {
+          geometry: Geometry::Point([20.0, 51.0]),
+          ..data
+        })
+      },
+    );
+
+    assert_matches!(updated, Err(UpdateFeatureError::ValidationError(errors)) => {
+      assert_that(&errors).is_equal_to(vec![
+        FeatureValidationError::Geometry(GeometryError::OutOfBounds),
+      ]);
+    });
+  }
+
+  #[test]
+  fn test_delete_unknown_feature() {
+    let mut repository = MockMapRepository::new();
+    repository
+      .expect_delete_feature()
+      .times(1)
+      .returning(|_, _, _| Err(PersistMapError::FeatureNotFound));
+
+    let service = new_map_feature_service(repository);
+    let deleted = service.delete_feature(
+      &Default::default(),
+      &Default::default(),
+      &Default::default(),
+    );
+
+    assert_that(&deleted)
+      .is_err()
+      .is_equal_to(DeleteFeatureError::UnknownFeature);
+  }
+}
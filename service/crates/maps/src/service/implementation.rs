@@ -0,0 +1,789 @@
+use super::{check_map_image, repository::*, service::*, FeatureValidationError};
+use crate::model::*;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+use universe_entity::{EntityKey, IdMap, Page, Pagination};
+use universe_storage::{Blob, BlobStore};
+use universe_worlds::WorldID;
+
+/// The Map Service to allow interactions with map entities and their images
+pub struct MapServiceImpl<Repo> {
+  repository: Repo,
+  blob_store: Arc<dyn BlobStore>,
+}
+
+/// Create a new Map Service
+///
+/// # Arguments
+/// * `repository` The repository to load and save maps with
+/// * `blob_store` The store to keep the map images in
+///
+/// # Returns
+/// The Map Service
+pub fn new_map_service<Repo: MapRepository + Send + Sync>(
+  repository: Repo,
+  blob_store: Arc<dyn BlobStore>,
+) -> impl MapService {
+  MapServiceImpl {
+    repository,
+    blob_store,
+  }
+}
+
+/// Build the key that an image of a map is stored under
+fn image_key(world_id: &WorldID, map_id: &MapID, image_id: &MapImageID) -> String {
+  format!("worlds/{}/maps/{}/{}", world_id, map_id, image_id)
+}
+
+impl<Repo: MapRepository + Send + Sync> MapServiceImpl<Repo> {
+  /// Delete an image of a map. Failures are logged but otherwise ignored, since all they leave
+  /// behind is an unreferenced image
+  fn delete_image(&self, world_id: &WorldID, map_id: &MapID, image_id: &MapImageID) {
+    let key = image_key(world_id, map_id, image_id);
+    if let Err(e) = self.blob_store.delete(&key) {
+      warn!("Failed to delete map image {}: {}", key, e);
+    }
+  }
+}
+
+impl<Repo: MapRepository + Send + Sync> MapService for MapServiceImpl<Repo> {
+  /// Retrieve the map that has the given unique ID, as long as it belongs to the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map to retrieve
+  ///
+  /// # Returns
+  /// The map, or `None` if it wasn't found
+  fn get_map_by_id(&self, world_id: &WorldID, map_id: &MapID) -> Option<MapEntity> {
+    let map = self.repository.get_map_by_id(world_id, map_id);
+
+    if map.is_none() {
+      warn!("No map found with ID {} in world {}", map_id, world_id);
+    }
+
+    map
+  }
+
+  /// Retrieve a page of the maps of a world, sorted by name
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the maps of
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of maps
+  fn list_maps(&self, world_id: &WorldID, pagination: Pagination) -> Page<MapEntity> {
+    self.repository.list_maps(world_id, pagination)
+  }
+
+  /// Retrieve every map of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the maps of
+  ///
+  /// # Returns
+  /// The maps
+  fn list_all_maps(&self, world_id: &WorldID) -> Vec<MapEntity> {
+    self.repository.list_all_maps(world_id)
+  }
+
+  /// Create a new map. Maps are always created without an image, which is uploaded separately
+  ///
+  /// # Arguments
+  /// * `map` The map data to create the map from
+  ///
+  /// # Returns
+  /// The map that was persisted
+  fn create_map(&self, map: MapData) -> Result<MapEntity, CreateMapError> {
+    let created = self.repository.create_map(MapData { image: None, ..map })?;
+    Ok(created)
+  }
+
+  /// Update an existing map.
+  ///
+  /// This will load the map by ID, and then call a provided callback to mutate the map before
+  /// persisting the changes back to the database. The image of the map can't be changed this
+  /// way.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map to update
+  /// * `updater` The callback to mutate the map with
+  ///
+  /// # Returns
+  /// The newly updated map
+  fn update_map(
+    &self,
+    world_id: &WorldID,
+    map_id: &MapID,
+    updater: &mut dyn FnMut(MapData) -> Result<MapData, Box<dyn std::error::Error>>,
+  ) -> Result<MapEntity, UpdateMapError> {
+    let map = self
+      .get_map_by_id(world_id, map_id)
+      .ok_or(UpdateMapError::UnknownMap)?;
+
+    let world = map.data.world.clone();
+    let image = map.data.image.clone();
+    let updated = updater(map.data).map_err(UpdateMapError::UpdateError)?;
+
+    let saved = self.repository.update_map(MapEntity {
+      identity: map.identity,
+      // Maps can't be moved between worlds, and their images are only changed by uploading them
+      data: MapData {
+        world,
+        image,
+        ..updated
+      },
+    })?;
+    Ok(saved)
+  }
+
+  /// Delete a map, along with its image and every feature on it
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map to delete
+  fn delete_map(&self, world_id: &WorldID, map_id: &MapID) -> Result<(), DeleteMapError> {
+    let map = self
+      .get_map_by_id(world_id, map_id)
+      .ok_or(DeleteMapError::UnknownMap)?;
+
+    self.repository.delete_map(world_id, map_id)?;
+
+    if let Some(image) = map.data.image {
+      self.delete_image(world_id, map_id, &image.id);
+    }
+
+    Ok(())
+  }
+
+  /// Upload a new image for a map, replacing any existing one.
+  ///
+  /// Features already on the map keep their pixel positions, so replacing the image with one of a
+  /// different size may leave some of them outside of it.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map
+  /// * `image` The raw bytes of the uploaded image
+  ///
+  /// # Returns
+  /// The map with its new image
+  fn set_map_image(
+    &self,
+    world_id: &WorldID,
+    map_id: &MapID,
+    image: &[u8],
+  ) -> Result<MapEntity, SetMapImageError> {
+    let map = self
+      .get_map_by_id(world_id, map_id)
+      .ok_or(SetMapImageError::UnknownMap)?;
+
+    let checked = check_map_image(image)?;
+
+    let image_id = MapImageID::default();
+    debug!("Storing image {} for map {}", image_id, map_id);
+    let blob = Blob {
+      content_type: checked.content_type.to_owned(),
+      data: image.to_vec(),
+    };
+    if let Err(e) = self
+      .blob_store
+      .put(&image_key(world_id, map_id, &image_id), &blob)
+    {
+      warn!("Failed to store map image: {}", e);
+      return Err(SetMapImageError::UnknownError);
+    }
+
+    let previous = map.data.image.clone();
+    let saved = self
+      .repository
+      .update_map(MapEntity {
+        identity: map.identity,
+        data: MapData {
+          image: Some(MapImage {
+            id: image_id.clone(),
+            content_type: checked.content_type.to_owned(),
+            width: checked.width,
+            height: checked.height,
+          }),
+          ..map.data
+        },
+      })
+      .map_err(|e| {
+        self.delete_image(world_id, map_id, &image_id);
+        e
+      })?;
+
+    if let Some(previous) = previous {
+      self.delete_image(world_id, map_id, &previous.id);
+    }
+
+    Ok(saved)
+  }
+
+  /// Retrieve an image of a map
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map
+  /// * `image_id` The ID of the image
+  ///
+  /// # Returns
+  /// The image, or `None` if it doesn't exist
+  fn get_map_image(
+    &self,
+    world_id: &WorldID,
+    map_id: &MapID,
+    image_id: &MapImageID,
+  ) -> Option<Blob> {
+    self
+      .blob_store
+      .get(&image_key(world_id, map_id, image_id))
+      .unwrap_or_else(|e| {
+        warn!("Failed to load map image: {}", e);
+        None
+      })
+  }
+
+  /// Create a set of maps in a world, along with their images and features.
+  ///
+  /// Features link to other entities by the keys that those entities were known by before they
+  /// were imported themselves, so they must already have been recorded in `ids`.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to create the maps in
+  /// * `maps` The maps to create
+  /// * `ids` The map to look up linked entities in, and to record the new IDs of the maps in
+  ///
+  /// # Returns
+  /// The maps that were created
+  fn import_maps(
+    &self,
+    world_id: &WorldID,
+    maps: Vec<MapImport>,
+    ids: &mut IdMap,
+  ) -> Result<Vec<MapEntity>, ImportMapsError> {
+    let mut created = Vec::with_capacity(maps.len());
+
+    for map in maps {
+      let MapImport {
+        key,
+        name,
+        image,
+        features,
+      } = map;
+
+      let mut result = self
+        .create_map(MapData {
+          world: world_id.clone(),
+          name,
+          image: None,
+        })
+        .map_err(|e| match e {
+          CreateMapError::UnknownWorld => ImportMapsError::UnknownWorld,
+          _ => ImportMapsError::UnknownError,
+        })?;
+      ids.insert("map", &key, &result.identity.id);
+
+      if let Some(image) = image {
+        result = self
+          .set_map_image(world_id, &result.identity.id, &image)
+          .map_err(|e| match e {
+            SetMapImageError::InvalidImage(e) => ImportMapsError::InvalidImage(key.clone(), e),
+            _ => ImportMapsError::UnknownError,
+          })?;
+      }
+
+      for feature in features {
+        let invalid = |e| ImportMapsError::InvalidFeature(key.clone(), e);
+
+        let image = result
+          .data
+          .image
+          .as_ref()
+          .ok_or_else(|| invalid(FeatureValidationError::NoImage))?;
+        feature
+          .geometry
+          .validate(image.width, image.height)
+          .map_err(|e| invalid(FeatureValidationError::Geometry(e)))?;
+
+        let link = match feature.link {
+          None => None,
+          Some(link) => Some(
+            ids
+              .resolve(&link)
+              .and_then(|id| FeatureLink::new(&link.kind, id))
+              .ok_or_else(|| invalid(FeatureValidationError::UnknownLink))?,
+          ),
+        };
+
+        self
+          .repository
+          .create_feature(FeatureData {
+            world: world_id.clone(),
+            map: result.identity.id.clone(),
+            name: feature.name,
+            geometry: feature.geometry,
+            link,
+          })
+          .map_err(|e| {
+            warn!("Error creating feature: {}", e);
+            ImportMapsError::UnknownError
+          })?;
+      }
+
+      created.push(result);
+    }
+    info!("Imported {} maps into world {}", created.len(), world_id);
+
+    Ok(created)
+  }
+
+  /// Copy every map of one world into another, along with their images and features.
+  ///
+  /// Anything that the features link to must already have been copied and recorded in `ids`.
+  ///
+  /// # Arguments
+  /// * `from` The ID of the world to copy the maps from
+  /// * `to` The ID of the world to copy the maps into
+  /// * `ids` The map to look up linked entities in, and to record the new IDs of the maps in
+  ///
+  /// # Returns
+  /// The maps that were created
+  fn copy_maps(
+    &self,
+    from: &WorldID,
+    to: &WorldID,
+    ids: &mut IdMap,
+  ) -> Result<Vec<MapEntity>, ImportMapsError> {
+    let maps = self
+      .list_all_maps(from)
+      .into_iter()
+      .map(|map| {
+        let image = match &map.data.image {
+          None => None,
+          Some(image) => {
+            let blob = self
+              .get_map_image(from, &map.identity.id, &image.id)
+              .ok_or_else(|| {
+                warn!("Image {} of map {} is missing", image.id, map.identity.id);
+                ImportMapsError::UnknownError
+              })?;
+            Some(blob.data)
+          }
+        };
+
+        let features = self
+          .repository
+          .list_features(from, &map.identity.id, None)
+          .into_iter()
+          .map(|feature| FeatureImport {
+            name: feature.data.name,
+            geometry: feature.data.geometry,
+            link: feature
+              .data
+              .link
+              .map(|link| EntityKey::new(link.kind(), &link.id())),
+          })
+          .collect();
+
+        Ok(MapImport {
+          key: map.identity.id.to_string(),
+          name: map.data.name,
+          image,
+          features,
+        })
+      })
+      .collect::<Result<Vec<_>, _>>()?;
+
+    self.import_maps(to, maps, ids)
+  }
+}
+
+impl From<PersistMapError> for CreateMapError {
+  fn from(e: PersistMapError) -> Self {
+    warn!("Error creating map: {}", e);
+    match e {
+      PersistMapError::WorldNotFound => CreateMapError::UnknownWorld,
+      _ => CreateMapError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistMapError> for UpdateMapError {
+  fn from(e: PersistMapError) -> Self {
+    warn!("Error updating map: {}", e);
+    match e {
+      PersistMapError::MapNotFound => UpdateMapError::UnknownMap,
+      PersistMapError::OptimisticLockFailure => UpdateMapError::OptimisticLockFailure,
+      _ => UpdateMapError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistMapError> for DeleteMapError {
+  fn from(e: PersistMapError) -> Self {
+    warn!("Error deleting map: {}", e);
+    match e {
+      PersistMapError::MapNotFound => DeleteMapError::UnknownMap,
+      _ => DeleteMapError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistMapError> for SetMapImageError {
+  fn from(e: PersistMapError) -> Self {
+    warn!("Error setting map image: {}", e);
+    match e {
+      PersistMapError::MapNotFound => SetMapImageError::UnknownMap,
+      PersistMapError::OptimisticLockFailure => SetMapImageError::OptimisticLockFailure,
+      _ => SetMapImageError::UnknownError,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::MapImageError;
+  use assert_matches::assert_matches;
+  use image::{DynamicImage, ImageBuffer, ImageOutputFormat, Rgb};
+  use mockall::predicate::*;
+  use spectral::prelude::*;
+  use test_env_log::test;
+  use universe_entity::Identity;
+  use universe_locations::LocationID;
+  use universe_storage::MemoryBlobStore;
+
+  fn map(image: Option<MapImage>) -> MapEntity {
+    MapEntity {
+      identity: Identity::default(),
+      data: MapData {
+        world: Default::default(),
+        name: "Middle-earth".to_owned(),
+        image,
+      },
+    }
+  }
+
+  fn png() -> Vec<u8> {
+    let image = ImageBuffer::from_pixel(64, 32, Rgb([10u8, 20, 30]));
+    let mut output = vec![];
+    DynamicImage::ImageRgb8(image)
+      .write_to(&mut output, ImageOutputFormat::Png)
+      .unwrap();
+    output
+  }
+
+  fn store_image(blob_store: &MemoryBlobStore, map: &MapEntity, image_id: &MapImageID) {
+    blob_store
+      .put(
+        &image_key(&map.data.world, &map.identity.id, image_id),
+        &Blob {
+          content_type: "image/png".to_owned(),
+          data: vec![],
+        },
+      )
+      .unwrap();
+  }
+
+  #[test]
+  fn test_create_map_without_image() {
+    let mut repository = MockMapRepository::new();
+    repository
+      .expect_create_map()
+      .withf(|map| map.image.is_none())
+      .times(1)
+      .returning(|data| {
+        Ok(MapEntity {
+          identity: Identity::default(),
+          data,
+        })
+      });
+
+    let service = new_map_service(repository, Arc::new(MemoryBlobStore::default()));
+    let created = service.create_map(
+      map(Some(MapImage {
+        id: MapImageID::default(),
+        content_type: "image/png".to_owned(),
+        width: 10,
+        height: 10,
+      }))
+      .data,
+    );
+
+    assert_that(&created.unwrap().data.image).is_none();
+  }
+
+  #[test]
+  fn test_update_map_keeps_image() {
+    let image = MapImage {
+      id: MapImageID::default(),
+      content_type: "image/png".to_owned(),
+      width: 10,
+      height: 10,
+    };
+    let existing = map(Some(image.clone()));
+
+    let mut repository = MockMapRepository::new();
+    let returned = existing.clone();
+    repository
+      .expect_get_map_by_id()
+      .times(1)
+      .returning(move |_, _| Some(returned.clone()));
+    let expected = Some(image.clone());
+    repository
+      .expect_update_map()
+      .withf(move |map| map.data.image == expected && map.data.name == "Arda")
+      .times(1)
+      .returning(Ok);
+
+    let service = new_map_service(repository, Arc::new(MemoryBlobStore::default()));
+    let updated = service.update_map(&existing.data.world, &existing.identity.id, &mut |data| {
+      Ok(MapData {
+        name: "Arda".to_owned(),
+        image: None,
+        ..data
+      })
+    });
+
+    assert_that(&updated.unwrap().data.image).is_equal_to(Some(image));
+  }
+
+  #[test]
+  fn test_set_image_replaces_existing() {
+    let previous = MapImageID::default();
+    let existing = map(Some(MapImage {
+      id: previous.clone(),
+      content_type: "image/png".to_owned(),
+      width: 10,
+      height: 10,
+    }));
+    let blob_store = Arc::new(MemoryBlobStore::default());
+    store_image(&blob_store, &existing, &previous);
+
+    let mut repository = MockMapRepository::new();
+    let returned = existing.clone();
+    repository
+      .expect_get_map_by_id()
+      .times(1)
+      .returning(move |_, _| Some(returned.clone()));
+    repository.expect_update_map().times(1).returning(Ok);
+
+    let service = new_map_service(repository, blob_store.clone());
+    let result = service
+      .set_map_image(&existing.data.world, &existing.identity.id, &png())
+      .unwrap();
+
+    let image = result.data.image.unwrap();
+    assert_that(&image.id).is_not_equal_to(&previous);
+    assert_that(&image.content_type).is_equal_to("image/png".to_owned());
+    assert_that(&(image.width, image.height)).is_equal_to((64, 32));
+
+    assert_that(&blob_store.keys()).is_equal_to(vec![image_key(
+      &existing.data.world,
+      &existing.identity.id,
+      &image.id,
+    )]);
+    let stored = service.get_map_image(&existing.data.world, &existing.identity.id, &image.id);
+    assert_that(&stored.unwrap().data).is_equal_to(png());
+  }
+
+  #[test]
+  fn test_set_image_invalid_image() {
+    let existing = map(None);
+    let mut repository = MockMapRepository::new();
+    let returned = existing.clone();
+    repository
+      .expect_get_map_by_id()
+      .times(1)
+      .returning(move |_, _| Some(returned.clone()));
+    repository.expect_update_map().never();
+    let blob_store = Arc::new(MemoryBlobStore::default());
+
+    let service = new_map_service(repository, blob_store.clone());
+    let result = service.set_map_image(&existing.data.world, &existing.identity.id, b"Hello");
+
+    assert_matches!(
+      result,
+      Err(SetMapImageError::InvalidImage(
+        MapImageError::UnsupportedFormat
+      ))
+    );
+    assert_that(&blob_store.keys()).is_empty();
+  }
+
+  #[test]
+  fn test_set_image_update_fails() {
+    let existing = map(None);
+    let mut repository = MockMapRepository::new();
+    let returned = existing.clone();
+    repository
+      .expect_get_map_by_id()
+      .times(1)
+      .returning(move |_, _| Some(returned.clone()));
+    repository
+      .expect_update_map()
+      .times(1)
+      .returning(|_| Err(PersistMapError::OptimisticLockFailure));
+    let blob_store = Arc::new(MemoryBlobStore::default());
+
+    let service = new_map_service(repository, blob_store.clone());
+    let result = service.set_map_image(&existing.data.world, &existing.identity.id, &png());
+
+    assert_matches!(result, Err(SetMapImageError::OptimisticLockFailure));
+    assert_that(&blob_store.keys()).is_empty();
+  }
+
+  fn map_import(link: EntityKey) -> MapImport {
+    MapImport {
+      key: "middle-earth".to_owned(),
+      name: "Middle-earth".to_owned(),
+      image: Some(png()),
+      features: vec![FeatureImport {
+        name: "Hobbiton".to_owned(),
+        geometry: Geometry::Point([10.0, 20.0]),
+        link: Some(link),
+      }],
+    }
+  }
+
+  fn mock_import_repository() -> MockMapRepository {
+    let mut repository = MockMapRepository::new();
+    repository.expect_create_map().times(1).returning(|data| {
+      Ok(MapEntity {
+        identity: Identity::default(),
+        data,
+      })
+    });
+    repository
+      .expect_get_map_by_id()
+      .times(1)
+      .returning(|world_id, map_id| {
+        Some(MapEntity {
+          identity: Identity {
+            id: map_id.clone(),
+            ..Identity::default()
+          },
+          data: MapData {
+            world: world_id.clone(),
+            name: "Middle-earth".to_owned(),
+            image: None,
+          },
+        })
+      });
+    repository.expect_update_map().times(1).returning(Ok);
+    repository
+  }
+
+  #[test]
+  fn test_import_maps_remaps_links() {
+    let world_id = WorldID::default();
+    let location_id = LocationID::default();
+    let mut ids = IdMap::default();
+    ids.insert("location", "hobbiton", &location_id);
+
+    let mut repository = mock_import_repository();
+    let expected_link = Some(FeatureLink::Location(location_id));
+    repository
+      .expect_create_feature()
+      .withf(move |feature| feature.link == expected_link)
+      .times(1)
+      .returning(|data| {
+        Ok(FeatureEntity {
+          identity: Identity::default(),
+          data,
+        })
+      });
+    let blob_store = Arc::new(MemoryBlobStore::default());
+
+    let service = new_map_service(repository, blob_store.clone());
+    let imported = service
+      .import_maps(
+        &world_id,
+        vec![map_import(EntityKey::new("location", "hobbiton"))],
+        &mut ids,
+      )
+      .unwrap();
+
+    assert_that(&imported).has_length(1);
+    assert_that(&imported[0].data.world).is_equal_to(&world_id);
+    assert_that(&imported[0].data.image).is_some();
+    assert_that(&blob_store.keys()).has_length(1);
+    assert_that(&ids.get("map", "middle-earth"))
+      .is_equal_to(Some(imported[0].identity.id.to_string().as_str()));
+  }
+
+  #[test]
+  fn test_import_maps_unknown_link() {
+    let mut repository = mock_import_repository();
+    repository.expect_create_feature().never();
+
+    let service = new_map_service(repository, Arc::new(MemoryBlobStore::default()));
+    let imported = service.import_maps(
+      &Default::default(),
+      vec![map_import(EntityKey::new("location", "hobbiton"))],
+      &mut IdMap::default(),
+    );
+
+    assert_that(&imported)
+      .is_err()
+      .is_equal_to(ImportMapsError::InvalidFeature(
+        "middle-earth".to_owned(),
+        FeatureValidationError::UnknownLink,
+      ));
+  }
+
+  #[test]
+  fn test_delete_map_removes_image() {
+    let image_id = MapImageID::default();
+    let existing = map(Some(MapImage {
+      id: image_id.clone(),
+      content_type: "image/png".to_owned(),
+      width: 10,
+      height: 10,
+    }));
+    let blob_store = Arc::new(MemoryBlobStore::default());
+    store_image(&blob_store, &existing, &image_id);
+
+    let mut repository = MockMapRepository::new();
+    let returned = existing.clone();
+    repository
+      .expect_get_map_by_id()
+      .times(1)
+      .returning(move |_, _| Some(returned.clone()));
+    repository
+      .expect_delete_map()
+      .with(
+        eq(existing.data.world.clone()),
+        eq(existing.identity.id.clone()),
+      )
+      .times(1)
+      .returning(|_, _| Ok(()));
+
+    let service = new_map_service(repository, blob_store.clone());
+    let result = service.delete_map(&existing.data.world, &existing.identity.id);
+
+    assert_that(&result).is_ok();
+    assert_that(&blob_store.keys()).is_empty();
+  }
+
+  #[test]
+  fn test_delete_unknown_map() {
+    let mut repository = MockMapRepository::new();
+    repository
+      .expect_get_map_by_id()
+      .times(1)
+      .return_const(None);
+    repository.expect_delete_map().never();
+
+    let service = new_map_service(repository, Arc::new(MemoryBlobStore::default()));
+    let result = service.delete_map(&Default::default(), &Default::default());
+
+    assert_that(&result)
+      .is_err()
+      .is_equal_to(DeleteMapError::UnknownMap);
+  }
+}
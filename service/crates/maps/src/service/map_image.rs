@@ -0,0 +1,160 @@
+use image::{io::Reader, ImageFormat};
+use std::io::Cursor;
+
+/// The largest map image, in bytes, that we will accept
+pub const MAX_MAP_IMAGE_BYTES: usize = 20 * 1024 * 1024;
+
+/// The largest width or height, in pixels, of a map image that we will accept
+pub const MAX_MAP_IMAGE_DIMENSION: u32 = 16384;
+
+/// Errors that can occur when checking an uploaded map image
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum MapImageError {
+  #[error("The image was larger than {} bytes", MAX_MAP_IMAGE_BYTES)]
+  TooLarge,
+  #[error("The image was not a PNG, JPEG or WebP image")]
+  UnsupportedFormat,
+  #[error(
+    "The image was wider or taller than {} pixels",
+    MAX_MAP_IMAGE_DIMENSION
+  )]
+  DimensionsTooLarge,
+  #[error("The image could not be decoded")]
+  Malformed,
+}
+
+/// The details of an uploaded map image that has been checked
+#[derive(Debug, PartialEq)]
+pub struct CheckedMapImage {
+  /// The MIME type of the image
+  pub content_type: &'static str,
+  /// The width of the image, in pixels
+  pub width: u32,
+  /// The height of the image, in pixels
+  pub height: u32,
+}
+
+/// Check that an uploaded map image is one that we can accept, and work out its size.
+///
+/// Map images are stored exactly as they were uploaded, since features are placed on them by
+/// pixel, so only the header of the image is read rather than decoding the whole thing.
+///
+/// # Arguments
+/// * `data` The uploaded image
+///
+/// # Returns
+/// The details of the image
+pub fn check_map_image(data: &[u8]) -> Result<CheckedMapImage, MapImageError> {
+  if data.len() > MAX_MAP_IMAGE_BYTES {
+    return Err(MapImageError::TooLarge);
+  }
+
+  let format = image::guess_format(data).map_err(|_| MapImageError::UnsupportedFormat)?;
+  let content_type = match format {
+    ImageFormat::Png => "image/png",
+    ImageFormat::Jpeg => "image/jpeg",
+    ImageFormat::WebP => "image/webp",
+    _ => return Err(MapImageError::UnsupportedFormat),
+  };
+
+  let (width, height) = Reader::with_format(Cursor::new(data), format)
+    .into_dimensions()
+    .map_err(|_| MapImageError::Malformed)?;
+  if width == 0 || height == 0 {
+    return Err(MapImageError::Malformed);
+  }
+  if width > MAX_MAP_IMAGE_DIMENSION || height > MAX_MAP_IMAGE_DIMENSION {
+    return Err(MapImageError::DimensionsTooLarge);
+  }
+
+  Ok(CheckedMapImage {
+    content_type,
+    width,
+    height,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use assert_matches::assert_matches;
+  use image::{DynamicImage, ImageBuffer, ImageOutputFormat, Rgba};
+  use spectral::prelude::*;
+
+  fn encode_image(width: u32, height: u32, format: ImageOutputFormat) -> Vec<u8> {
+    let image = ImageBuffer::from_pixel(width, height, Rgba([200u8, 100, 50, 255]));
+    let mut output = vec![];
+    DynamicImage::ImageRgba8(image)
+      .write_to(&mut output, format)
+      .unwrap();
+    output
+  }
+
+  #[test]
+  fn test_check_png() {
+    let data = encode_image(640, 480, ImageOutputFormat::Png);
+
+    let result = check_map_image(&data);
+
+    assert_that(&result).is_ok().is_equal_to(CheckedMapImage {
+      content_type: "image/png",
+      width: 640,
+      height: 480,
+    });
+  }
+
+  #[test]
+  fn test_check_jpeg() {
+    let data = encode_image(100, 400, ImageOutputFormat::Jpeg(90));
+
+    let result = check_map_image(&data);
+
+    assert_that(&result).is_ok().is_equal_to(CheckedMapImage {
+      content_type: "image/jpeg",
+      width: 100,
+      height: 400,
+    });
+  }
+
+  #[test]
+  fn test_check_unsupported_format() {
+    let result = check_map_image(b"GIF89a\x01\x00\x01\x00\x00\x00\x00;");
+
+    assert_matches!(result, Err(MapImageError::UnsupportedFormat));
+  }
+
+  #[test]
+  fn test_check_not_an_image() {
+    let result = check_map_image(b"Hello, World");
+
+    assert_matches!(result, Err(MapImageError::UnsupportedFormat));
+  }
+
+  #[test]
+  fn test_check_truncated_image() {
+    let data = encode_image(100, 100, ImageOutputFormat::Png);
+
+    let result = check_map_image(&data[..10]);
+
+    assert_matches!(result, Err(MapImageError::Malformed));
+  }
+
+  #[test]
+  fn test_check_too_large() {
+    let mut data = encode_image(10, 10, ImageOutputFormat::Png);
+    data.resize(MAX_MAP_IMAGE_BYTES + 1, 0);
+
+    let result = check_map_image(&data);
+
+    assert_matches!(result, Err(MapImageError::TooLarge));
+  }
+
+  #[test]
+  fn test_check_dimensions_too_large() {
+    let data = encode_image(MAX_MAP_IMAGE_DIMENSION + 1, 1, ImageOutputFormat::Png);
+
+    let result = check_map_image(&data);
+
+    assert_matches!(result, Err(MapImageError::DimensionsTooLarge));
+  }
+}
@@ -0,0 +1,10 @@
+mod features;
+mod implementation;
+mod map_image;
+pub mod repository;
+mod service;
+
+pub use features::*;
+pub use implementation::*;
+pub use map_image::*;
+pub use service::*;
@@ -0,0 +1,153 @@
+use crate::model::*;
+#[cfg(test)]
+use mockall::automock;
+use universe_entity::{Page, Pagination};
+use universe_worlds::WorldID;
+
+/// Repository that describes how to access map data
+#[cfg_attr(test, automock)]
+pub trait MapRepository {
+  /// Retrieve the map that has the given unique ID, as long as it belongs to the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map to retrieve
+  ///
+  /// # Returns
+  /// The map, or `None` if it wasn't found
+  fn get_map_by_id(&self, world_id: &WorldID, map_id: &MapID) -> Option<MapEntity>;
+
+  /// Retrieve a page of the maps of a world, sorted by name
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the maps of
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of maps
+  fn list_maps(&self, world_id: &WorldID, pagination: Pagination) -> Page<MapEntity>;
+
+  /// Retrieve every map of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the maps of
+  ///
+  /// # Returns
+  /// The maps
+  fn list_all_maps(&self, world_id: &WorldID) -> Vec<MapEntity>;
+
+  /// Create a new map record in the data store
+  ///
+  /// # Arguments
+  /// * `map` The map details to persist to the data store
+  ///
+  /// # Returns
+  /// The map that was persisted
+  fn create_map(&self, map: MapData) -> Result<MapEntity, PersistMapError>;
+
+  /// Update an existing map record in the data store
+  ///
+  /// # Arguments
+  /// * `map` The map details to persist to the data store
+  ///
+  /// # Returns
+  /// The map that was persisted
+  fn update_map(&self, map: MapEntity) -> Result<MapEntity, PersistMapError>;
+
+  /// Delete an existing map record from the data store, along with every feature on it
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map to delete
+  fn delete_map(&self, world_id: &WorldID, map_id: &MapID) -> Result<(), PersistMapError>;
+
+  /// Retrieve the feature that has the given unique ID, as long as it is on the given map
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map that the feature is on
+  /// * `feature_id` The ID of the feature to retrieve
+  ///
+  /// # Returns
+  /// The feature, or `None` if it wasn't found
+  fn get_feature_by_id(
+    &self,
+    world_id: &WorldID,
+    map_id: &MapID,
+    feature_id: &FeatureID,
+  ) -> Option<FeatureEntity>;
+
+  /// Retrieve the features on a map, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map to list the features of
+  /// * `kind` The kind of features to list, or `None` for all of them
+  ///
+  /// # Returns
+  /// The features
+  fn list_features(
+    &self,
+    world_id: &WorldID,
+    map_id: &MapID,
+    kind: Option<FeatureKind>,
+  ) -> Vec<FeatureEntity>;
+
+  /// Check whether an entity that a feature links to is part of the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  /// * `link` The entity to look for
+  ///
+  /// # Returns
+  /// True if the entity exists and belongs to the world
+  fn is_link_in_world(&self, world_id: &WorldID, link: &FeatureLink) -> bool;
+
+  /// Create a new feature record in the data store
+  ///
+  /// # Arguments
+  /// * `feature` The feature details to persist to the data store
+  ///
+  /// # Returns
+  /// The feature that was persisted
+  fn create_feature(&self, feature: FeatureData) -> Result<FeatureEntity, PersistMapError>;
+
+  /// Update an existing feature record in the data store
+  ///
+  /// # Arguments
+  /// * `feature` The feature details to persist to the data store
+  ///
+  /// # Returns
+  /// The feature that was persisted
+  fn update_feature(&self, feature: FeatureEntity) -> Result<FeatureEntity, PersistMapError>;
+
+  /// Delete an existing feature record from the data store
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map that the feature is on
+  /// * `feature_id` The ID of the feature to delete
+  fn delete_feature(
+    &self,
+    world_id: &WorldID,
+    map_id: &MapID,
+    feature_id: &FeatureID,
+  ) -> Result<(), PersistMapError>;
+}
+
+/// Errors that can occur when persisting a map or feature record
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum PersistMapError {
+  #[error("The world was not found")]
+  WorldNotFound,
+  #[error("The map was not found")]
+  MapNotFound,
+  #[error("The feature was not found")]
+  FeatureNotFound,
+  #[error("The entity that the feature links to was not found")]
+  LinkNotFound,
+  #[error("The version of the record did not match")]
+  OptimisticLockFailure,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
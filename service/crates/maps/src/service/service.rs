@@ -0,0 +1,202 @@
+use super::{FeatureValidationError, MapImageError};
+use crate::model::*;
+use universe_entity::{IdMap, Page, Pagination};
+use universe_storage::Blob;
+use universe_worlds::WorldID;
+
+/// The Map Service to allow interactions with map entities and their images
+pub trait MapService: Send + Sync {
+  /// Retrieve the map that has the given unique ID, as long as it belongs to the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map to retrieve
+  ///
+  /// # Returns
+  /// The map, or `None` if it wasn't found
+  fn get_map_by_id(&self, world_id: &WorldID, map_id: &MapID) -> Option<MapEntity>;
+
+  /// Retrieve a page of the maps of a world, sorted by name
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the maps of
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of maps
+  fn list_maps(&self, world_id: &WorldID, pagination: Pagination) -> Page<MapEntity>;
+
+  /// Retrieve every map of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the maps of
+  ///
+  /// # Returns
+  /// The maps
+  fn list_all_maps(&self, world_id: &WorldID) -> Vec<MapEntity>;
+
+  /// Create a new map. Maps are always created without an image, which is uploaded separately
+  ///
+  /// # Arguments
+  /// * `map` The map data to create the map from
+  ///
+  /// # Returns
+  /// The map that was persisted
+  fn create_map(&self, map: MapData) -> Result<MapEntity, CreateMapError>;
+
+  /// Update an existing map.
+  ///
+  /// This will load the map by ID, and then call a provided callback to mutate the map before
+  /// persisting the changes back to the database. The image of the map can't be changed this
+  /// way.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map to update
+  /// * `updater` The callback to mutate the map with
+  ///
+  /// # Returns
+  /// The newly updated map
+  fn update_map(
+    &self,
+    world_id: &WorldID,
+    map_id: &MapID,
+    updater: &mut dyn FnMut(MapData) -> Result<MapData, Box<dyn std::error::Error>>,
+  ) -> Result<MapEntity, UpdateMapError>;
+
+  /// Delete a map, along with its image and every feature on it
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map to delete
+  fn delete_map(&self, world_id: &WorldID, map_id: &MapID) -> Result<(), DeleteMapError>;
+
+  /// Upload a new image for a map, replacing any existing one.
+  ///
+  /// Features already on the map keep their pixel positions, so replacing the image with one of a
+  /// different size may leave some of them outside of it.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map
+  /// * `image` The raw bytes of the uploaded image
+  ///
+  /// # Returns
+  /// The map with its new image
+  fn set_map_image(
+    &self,
+    world_id: &WorldID,
+    map_id: &MapID,
+    image: &[u8],
+  ) -> Result<MapEntity, SetMapImageError>;
+
+  /// Retrieve an image of a map
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the map belongs to
+  /// * `map_id` The ID of the map
+  /// * `image_id` The ID of the image
+  ///
+  /// # Returns
+  /// The image, or `None` if it doesn't exist
+  fn get_map_image(
+    &self,
+    world_id: &WorldID,
+    map_id: &MapID,
+    image_id: &MapImageID,
+  ) -> Option<Blob>;
+
+  /// Create a set of maps in a world, along with their images and features.
+  ///
+  /// Features link to other entities by the keys that those entities were known by before they
+  /// were imported themselves, so they must already have been recorded in `ids`.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to create the maps in
+  /// * `maps` The maps to create
+  /// * `ids` The map to look up linked entities in, and to record the new IDs of the maps in
+  ///
+  /// # Returns
+  /// The maps that were created
+  fn import_maps(
+    &self,
+    world_id: &WorldID,
+    maps: Vec<MapImport>,
+    ids: &mut IdMap,
+  ) -> Result<Vec<MapEntity>, ImportMapsError>;
+
+  /// Copy every map of one world into another, along with their images and features.
+  ///
+  /// Anything that the features link to must already have been copied and recorded in `ids`.
+  ///
+  /// # Arguments
+  /// * `from` The ID of the world to copy the maps from
+  /// * `to` The ID of the world to copy the maps into
+  /// * `ids` The map to look up linked entities in, and to record the new IDs of the maps in
+  ///
+  /// # Returns
+  /// The maps that were created
+  fn copy_maps(
+    &self,
+    from: &WorldID,
+    to: &WorldID,
+    ids: &mut IdMap,
+  ) -> Result<Vec<MapEntity>, ImportMapsError>;
+}
+
+/// Enumeration of reasons why we failed to create a new map
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum CreateMapError {
+  #[error("The world was not found")]
+  UnknownWorld,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to update an existing map
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateMapError {
+  #[error("The map was not found")]
+  UnknownMap,
+  #[error("The version of the map record did not match")]
+  OptimisticLockFailure,
+  #[error("An error occurred updating the map details: {0}")]
+  UpdateError(Box<dyn std::error::Error>),
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to delete a map
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum DeleteMapError {
+  #[error("The map was not found")]
+  UnknownMap,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to set the image of a map
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum SetMapImageError {
+  #[error("The map was not found")]
+  UnknownMap,
+  #[error("The image was not acceptable: {0}")]
+  InvalidImage(#[from] MapImageError),
+  #[error("The version of the map record did not match")]
+  OptimisticLockFailure,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to import a set of maps
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum ImportMapsError {
+  #[error("The image of map {0} was not acceptable: {1}")]
+  InvalidImage(String, MapImageError),
+  #[error("A feature of map {0} was not acceptable: {1:?}")]
+  InvalidFeature(String, FeatureValidationError),
+  #[error("The world was not found")]
+  UnknownWorld,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
@@ -0,0 +1,3 @@
+mod render;
+
+pub use render::*;
@@ -0,0 +1,148 @@
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag};
+
+/// The URL schemes that links and images are allowed to use. URLs without a scheme are relative,
+/// and are always allowed
+const SAFE_SCHEMES: [&str; 3] = ["http", "https", "mailto"];
+
+/// Render some CommonMark source into HTML that is safe to embed directly into a page.
+///
+/// Raw HTML in the source is removed entirely, so that no scripts, styles or event handler
+/// attributes can make it into the output. Links and images are only kept if they use a safe URL
+/// scheme, and everything else that ends up in the output is escaped by the renderer.
+///
+/// # Arguments
+/// * `source` The CommonMark source to render
+///
+/// # Returns
+/// The sanitized HTML
+pub fn render_html(source: &str) -> String {
+  let parser = Parser::new_ext(source, Options::empty()).filter_map(|event| match event {
+    Event::Html(_) => None,
+    Event::Start(tag) => Some(Event::Start(sanitize_tag(tag))),
+    Event::End(tag) => Some(Event::End(sanitize_tag(tag))),
+    event => Some(event),
+  });
+
+  let mut output = String::with_capacity(source.len() * 3 / 2);
+  html::push_html(&mut output, parser);
+  output
+}
+
+/// Replace the destination of a link or image with an empty one if it isn't safe to use
+fn sanitize_tag(tag: Tag) -> Tag {
+  match tag {
+    Tag::Link(link_type, url, title) if !is_safe_url(&url) => {
+      Tag::Link(link_type, CowStr::Borrowed(""), title)
+    }
+    Tag::Image(link_type, url, title) if !is_safe_url(&url) => {
+      Tag::Image(link_type, CowStr::Borrowed(""), title)
+    }
+    tag => tag,
+  }
+}
+
+/// Check whether a URL is safe to include in the rendered HTML.
+///
+/// Browsers ignore whitespace and control characters inside the scheme, so these are removed
+/// before looking at it.
+fn is_safe_url(url: &str) -> bool {
+  let url: String = url
+    .chars()
+    .filter(|c| !c.is_whitespace() && !c.is_control())
+    .collect::<String>()
+    .to_lowercase();
+
+  match url.find(|c| c == ':' || c == '/' || c == '?' || c == '#') {
+    Some(index) if url[index..].starts_with(':') => SAFE_SCHEMES.contains(&&url[..index]),
+    _ => true,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use rstest::rstest;
+  use spectral::prelude::*;
+
+  #[rstest(
+    input,
+    expected,
+    case("", ""),
+    case("Hello, World", "<p>Hello, World</p>\n"),
+    case(
+      "*Hello*, **World**",
+      "<p><em>Hello</em>, <strong>World</strong></p>\n"
+    ),
+    case("# Heading\n\nParagraph", "<h1>Heading</h1>\n<p>Paragraph</p>\n"),
+    case("- One\n- Two", "<ul>\n<li>One</li>\n<li>Two</li>\n</ul>\n"),
+    case("`a < b`", "<p><code>a &lt; b</code></p>\n"),
+    case("a < b & c", "<p>a &lt; b &amp; c</p>\n")
+  )]
+  fn test_render_markdown(input: &str, expected: &str) {
+    assert_that(&render_html(input)).is_equal_to(expected.to_owned());
+  }
+
+  #[rstest(
+    input,
+    expected,
+    case("<script>alert('Hello')</script>", ""),
+    case(
+      "Hello <script>alert('Hello')</script>",
+      "<p>Hello alert('Hello')</p>\n"
+    ),
+    case(
+      "<div onclick=\"alert('Hello')\">\n\nHello\n\n</div>",
+      "<p>Hello</p>\n"
+    ),
+    case(
+      "Hello <b onmouseover=\"alert('Hello')\">World</b>",
+      "<p>Hello World</p>\n"
+    )
+  )]
+  fn test_render_strips_html(input: &str, expected: &str) {
+    assert_that(&render_html(input)).is_equal_to(expected.to_owned());
+  }
+
+  #[rstest(
+    input,
+    expected,
+    case(
+      "[Link](https://example.com)",
+      "<p><a href=\"https://example.com\">Link</a></p>\n"
+    ),
+    case(
+      "[Link](HTTP://example.com)",
+      "<p><a href=\"HTTP://example.com\">Link</a></p>\n"
+    ),
+    case(
+      "[Link](mailto:test@example.com)",
+      "<p><a href=\"mailto:test@example.com\">Link</a></p>\n"
+    ),
+    case("[Link](/worlds/abc)", "<p><a href=\"/worlds/abc\">Link</a></p>\n"),
+    case("[Link](#top)", "<p><a href=\"#top\">Link</a></p>\n"),
+    case("[Link](page?a=b:c)", "<p><a href=\"page?a=b:c\">Link</a></p>\n"),
+    case("[Link](javascript:alert(1))", "<p><a href=\"\">Link</a></p>\n"),
+    case("[Link](JavaScript:alert(1))", "<p><a href=\"\">Link</a></p>\n"),
+    case("[Link](<java\tscript:alert(1)>)", "<p><a href=\"\">Link</a></p>\n"),
+    case("[Link](vbscript:msgbox)", "<p><a href=\"\">Link</a></p>\n"),
+    case(
+      "<javascript:alert(1)>",
+      "<p><a href=\"\">javascript:alert(1)</a></p>\n"
+    ),
+    case(
+      "![Image](https://example.com/a.png)",
+      "<p><img src=\"https://example.com/a.png\" alt=\"Image\" /></p>\n"
+    ),
+    case(
+      "![Image](data:image/png;base64,AAAA)",
+      "<p><img src=\"\" alt=\"Image\" /></p>\n"
+    ),
+    case(
+      "[Link](https://example.com \"A \\\"title\\\"\")",
+      "<p><a href=\"https://example.com\" title=\"A &quot;title&quot;\">Link</a></p>\n"
+    )
+  )]
+  fn test_render_sanitizes_urls(input: &str, expected: &str) {
+    assert_that(&render_html(input)).is_equal_to(expected.to_owned());
+  }
+}
@@ -0,0 +1,1062 @@
+use crate::{model::*, service::repository::*, service::RelationshipFilters};
+use chrono::Utc;
+use postgres::types::ToSql;
+use std::error::Error;
+use tracing::{debug, warn};
+use universe_articles::ArticleID;
+use universe_calendars::CalendarDate;
+use universe_characters::CharacterID;
+use universe_database::Database;
+use universe_entity::{Identity, Page, Pagination};
+use universe_locations::LocationID;
+use universe_maps::MapID;
+use universe_worlds::WorldID;
+use uuid::Uuid;
+
+/// The query to load relationships along with their dates, if they have any
+const SELECT_RELATIONSHIPS: &str =
+  "SELECT r.*, d.calendar_id, d.start_year, d.start_month, d.start_day, d.end_year, d.end_month, d.end_day
+    FROM relationships r
+    LEFT JOIN relationship_dates d ON d.relationship_id = r.relationship_id";
+
+/// Read the entity at one end of a relationship from a database row
+///
+/// # Arguments
+/// * `row` The row to read
+/// * `end` Which end of the relationship to read. Either "source" or "target"
+fn entity_from_row(row: &postgres::Row, end: &str) -> EntityRef {
+  let article_id: Option<ArticleID> = row.get(format!("{}_article_id", end).as_str());
+  let character_id: Option<CharacterID> = row.get(format!("{}_character_id", end).as_str());
+  let location_id: Option<LocationID> = row.get(format!("{}_location_id", end).as_str());
+  let map_id: Option<MapID> = row.get(format!("{}_map_id", end).as_str());
+
+  // The database requires exactly one of these for each end of a relationship
+  article_id
+    .map(EntityRef::Article)
+    .or_else(|| character_id.map(EntityRef::Character))
+    .or_else(|| location_id.map(EntityRef::Location))
+    .or_else(|| map_id.map(EntityRef::Map))
+    .unwrap()
+}
+
+/// Read a date of a relationship from a database row
+///
+/// # Arguments
+/// * `row` The row to read
+/// * `end` Which end of the date range to read. Either "start" or "end"
+fn date_from_row(row: &postgres::Row, end: &str) -> Option<CalendarDate> {
+  let year: Option<i64> = row.get(format!("{}_year", end).as_str());
+  let month: Option<i32> = row.get(format!("{}_month", end).as_str());
+  let day: Option<i32> = row.get(format!("{}_day", end).as_str());
+
+  match (year, month, day) {
+    (Some(year), Some(month), Some(day)) => Some(CalendarDate {
+      year,
+      month: month as u32,
+      day: day as u32,
+    }),
+    _ => None,
+  }
+}
+
+impl From<&postgres::Row> for RelationshipEntity {
+  fn from(row: &postgres::Row) -> Self {
+    RelationshipEntity {
+      identity: Identity {
+        id: row.get("relationship_id"),
+        version: row.get("version"),
+        created: row.get("created"),
+        updated: row.get("updated"),
+      },
+      data: RelationshipData {
+        world: row.get("world_id"),
+        source: entity_from_row(row, "source"),
+        target: entity_from_row(row, "target"),
+        relation: row.get("relation"),
+        symmetric: row.get("is_symmetric"),
+        during: row
+          .get::<_, Option<universe_calendars::CalendarID>>("calendar_id")
+          .map(|calendar| DateRange {
+            calendar,
+            start: date_from_row(row, "start"),
+            end: date_from_row(row, "end"),
+          }),
+      },
+    }
+  }
+}
+
+/// The columns that one end of a relationship is stored in
+struct EntityColumns<'a> {
+  article_id: Option<&'a ArticleID>,
+  character_id: Option<&'a CharacterID>,
+  location_id: Option<&'a LocationID>,
+  map_id: Option<&'a MapID>,
+}
+
+impl<'a> From<&'a EntityRef> for EntityColumns<'a> {
+  fn from(entity: &'a EntityRef) -> Self {
+    EntityColumns {
+      article_id: match entity {
+        EntityRef::Article(id) => Some(id),
+        _ => None,
+      },
+      character_id: match entity {
+        EntityRef::Character(id) => Some(id),
+        _ => None,
+      },
+      location_id: match entity {
+        EntityRef::Location(id) => Some(id),
+        _ => None,
+      },
+      map_id: match entity {
+        EntityRef::Map(id) => Some(id),
+        _ => None,
+      },
+    }
+  }
+}
+
+/// The ID of an entity, as a database bind
+fn entity_id(entity: &EntityRef) -> &(dyn ToSql + Sync) {
+  match entity {
+    EntityRef::Article(id) => id,
+    EntityRef::Character(id) => id,
+    EntityRef::Location(id) => id,
+    EntityRef::Map(id) => id,
+  }
+}
+
+/// Replace the dates of a relationship as part of a transaction
+///
+/// # Arguments
+/// * `transaction` The transaction to replace the dates in
+/// * `relationship_id` The ID of the relationship
+/// * `relationship` The relationship details, including the new dates
+fn save_relationship_dates(
+  transaction: &mut postgres::Transaction,
+  relationship_id: &RelationshipID,
+  relationship: &RelationshipData,
+) -> Result<(), postgres::Error> {
+  transaction.execute(
+    "DELETE FROM relationship_dates WHERE relationship_id = $1",
+    &[&relationship_id],
+  )?;
+
+  if let Some(during) = &relationship.during {
+    transaction.execute(
+      "INSERT INTO relationship_dates(relationship_id, world_id, calendar_id, start_year, start_month, start_day, end_year, end_month, end_day)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+      &[
+        &relationship_id,
+        &relationship.world,
+        &during.calendar,
+        &during.start.map(|date| date.year),
+        &during.start.map(|date| date.month as i32),
+        &during.start.map(|date| date.day as i32),
+        &during.end.map(|date| date.year),
+        &during.end.map(|date| date.month as i32),
+        &during.end.map(|date| date.day as i32),
+      ],
+    )?;
+  }
+
+  Ok(())
+}
+
+impl RelationshipRepository for Database {
+  /// Retrieve the relationship that has the given unique ID, as long as it belongs to the given
+  /// world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the relationship belongs to
+  /// * `relationship_id` The ID of the relationship to retrieve
+  ///
+  /// # Returns
+  /// The relationship, or `None` if it wasn't found
+  fn get_relationship_by_id(
+    &self,
+    world_id: &WorldID,
+    relationship_id: &RelationshipID,
+  ) -> Option<RelationshipEntity> {
+    let mut client = self.client().unwrap();
+
+    let relationship = client
+      .query(
+        format!(
+          "{} WHERE r.world_id = $1 AND r.relationship_id = $2",
+          SELECT_RELATIONSHIPS
+        )
+        .as_str(),
+        &[&world_id, &relationship_id],
+      )
+      .map_err(|e| {
+        warn!("Error loading relationship from database: {}", e);
+        e
+      })
+      .ok()
+      .and_then(|rows| rows.get(0).map(|row| row.into()));
+
+    debug!("Relationship {}: {:?}", relationship_id, relationship);
+    relationship
+  }
+
+  /// Retrieve a page of the relationships of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the relationships of
+  /// * `filters` The filters to apply to the relationships
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of relationships
+  fn list_relationships(
+    &self,
+    world_id: &WorldID,
+    filters: RelationshipFilters,
+    pagination: Pagination,
+  ) -> Page<RelationshipEntity> {
+    let mut client = self.client().unwrap();
+
+    let limit = i64::from(pagination.limit);
+    let offset = i64::from(pagination.offset);
+    let mut binds: Vec<&(dyn ToSql + Sync)> = vec![world_id, &filters.relation];
+    let mut filter =
+      "r.world_id = $1 AND ($2::TEXT IS NULL OR UPPER(r.relation) = UPPER($2::TEXT))".to_owned();
+    if let Some(entity) = &filters.entity {
+      // The type of entity decides which columns to look in
+      filter = format!(
+        "{} AND (r.source_{kind}_id = $3 OR r.target_{kind}_id = $3)",
+        filter,
+        kind = entity.kind()
+      );
+      binds.push(entity_id(entity));
+    }
+
+    let total: i64 = client
+      .query_one(
+        format!(
+          "SELECT COUNT(*) AS total FROM relationships r WHERE {}",
+          filter
+        )
+        .as_str(),
+        &binds,
+      )
+      .map_err(|e| {
+        warn!("Error counting relationships in database: {}", e);
+        e
+      })
+      .map(|row| row.get("total"))
+      .unwrap_or(0);
+
+    let paging = format!("LIMIT ${} OFFSET ${}", binds.len() + 1, binds.len() + 2);
+    binds.push(&limit);
+    binds.push(&offset);
+    let entries: Vec<RelationshipEntity> = client
+      .query(
+        format!(
+          "{} WHERE {} ORDER BY r.created ASC, r.relationship_id ASC {}",
+          SELECT_RELATIONSHIPS, filter, paging
+        )
+        .as_str(),
+        &binds,
+      )
+      .map_err(|e| {
+        warn!("Error listing relationships in database: {}", e);
+        e
+      })
+      .map(|rows| rows.iter().map(|row| row.into()).collect())
+      .unwrap_or_default();
+
+    let page = Page {
+      entries,
+      total: total as u32,
+      offset: pagination.offset,
+    };
+    debug!("Relationships of world {}: {:?}", world_id, page);
+
+    page
+  }
+
+  /// Retrieve every relationship of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the relationships of
+  ///
+  /// # Returns
+  /// The relationships
+  fn list_all_relationships(&self, world_id: &WorldID) -> Vec<RelationshipEntity> {
+    let mut client = self.client().unwrap();
+
+    let relationships: Vec<RelationshipEntity> = client
+      .query(
+        format!(
+          "{} WHERE r.world_id = $1 ORDER BY r.created ASC, r.relationship_id ASC",
+          SELECT_RELATIONSHIPS
+        )
+        .as_str(),
+        &[&world_id],
+      )
+      .map_err(|e| {
+        warn!("Error listing relationships in database: {}", e);
+        e
+      })
+      .map(|rows| rows.iter().map(|row| row.into()).collect())
+      .unwrap_or_default();
+
+    debug!("Relationships of world {}: {:?}", world_id, relationships);
+    relationships
+  }
+
+  /// Retrieve every entity of a world that takes part in at least one relationship
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the entities of
+  ///
+  /// # Returns
+  /// The entities, along with their names
+  fn list_related_entities(&self, world_id: &WorldID) -> Vec<GraphNode> {
+    let mut client = self.client().unwrap();
+
+    let entities: Vec<GraphNode> = client
+      .query(
+        "WITH related AS (SELECT * FROM relationships WHERE world_id = $1)
+          SELECT 'article' AS kind, article_id AS id, title AS name FROM articles
+            WHERE article_id IN (SELECT source_article_id FROM related UNION SELECT target_article_id FROM related)
+          UNION ALL
+          SELECT 'character' AS kind, character_id AS id, name FROM characters
+            WHERE character_id IN (SELECT source_character_id FROM related UNION SELECT target_character_id FROM related)
+          UNION ALL
+          SELECT 'location' AS kind, location_id AS id, name FROM locations
+            WHERE location_id IN (SELECT source_location_id FROM related UNION SELECT target_location_id FROM related)
+          UNION ALL
+          SELECT 'map' AS kind, map_id AS id, name FROM maps
+            WHERE map_id IN (SELECT source_map_id FROM related UNION SELECT target_map_id FROM related)",
+        &[&world_id],
+      )
+      .map_err(|e| {
+        warn!("Error listing related entities in database: {}", e);
+        e
+      })
+      .map(|rows| {
+        rows
+          .iter()
+          .map(|row| {
+            let kind: String = row.get("kind");
+            let id: Uuid = row.get("id");
+
+            GraphNode {
+              entity: EntityRef::new(&kind, &id.to_string()).unwrap(),
+              name: row.get("name"),
+            }
+          })
+          .collect()
+      })
+      .unwrap_or_default();
+
+    debug!("Related entities of world {}: {:?}", world_id, entities);
+    entities
+  }
+
+  /// Check whether an entity is part of the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  /// * `entity` The entity to look for
+  ///
+  /// # Returns
+  /// True if the entity exists and belongs to the world
+  fn is_entity_in_world(&self, world_id: &WorldID, entity: &EntityRef) -> bool {
+    let mut client = self.client().unwrap();
+
+    let sql = match entity {
+      EntityRef::Article(_) => "SELECT 1 FROM articles WHERE world_id = $1 AND article_id = $2",
+      EntityRef::Character(_) => {
+        "SELECT 1 FROM characters WHERE world_id = $1 AND character_id = $2"
+      }
+      EntityRef::Location(_) => "SELECT 1 FROM locations WHERE world_id = $1 AND location_id = $2",
+      EntityRef::Map(_) => "SELECT 1 FROM maps WHERE world_id = $1 AND map_id = $2",
+    };
+
+    let found = client
+      .query(sql, &[&world_id, entity_id(entity)])
+      .map_err(|e| {
+        warn!("Error looking up entity in database: {}", e);
+        e
+      })
+      .map(|rows| !rows.is_empty())
+      .unwrap_or(false);
+
+    debug!("Entity {} in world {}: {}", entity, world_id, found);
+    found
+  }
+
+  /// Create a new relationship record in the data store
+  ///
+  /// # Arguments
+  /// * `relationship` The relationship details to persist to the data store
+  ///
+  /// # Returns
+  /// The relationship that was persisted
+  fn create_relationship(
+    &self,
+    relationship: RelationshipData,
+  ) -> Result<RelationshipEntity, PersistRelationshipError> {
+    debug!("Creating record for relationship: {:?}", relationship);
+
+    let mut client = self.client().unwrap();
+    let mut transaction = client.transaction().unwrap();
+
+    let new_id = RelationshipID::default();
+    let new_version = Uuid::new_v4();
+    let new_updated = Utc::now();
+    let source = EntityColumns::from(&relationship.source);
+    let target = EntityColumns::from(&relationship.target);
+
+    transaction.execute(
+      "INSERT INTO relationships(relationship_id, version, created, updated, world_id, relation, is_symmetric,
+          source_article_id, source_character_id, source_location_id, source_map_id,
+          target_article_id, target_character_id, target_location_id, target_map_id)
+        VALUES ($1, $2, $3, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)",
+      &[
+        &new_id,
+        &new_version,
+        &new_updated,
+        &relationship.world,
+        &relationship.relation,
+        &relationship.symmetric,
+        &source.article_id,
+        &source.character_id,
+        &source.location_id,
+        &source.map_id,
+        &target.article_id,
+        &target.character_id,
+        &target.location_id,
+        &target.map_id,
+      ],
+    )?;
+    save_relationship_dates(&mut transaction, &new_id, &relationship)?;
+
+    let result: RelationshipEntity = transaction
+      .query_one(
+        format!("{} WHERE r.relationship_id = $1", SELECT_RELATIONSHIPS).as_str(),
+        &[&new_id],
+      )
+      .map(|row| (&row).into())?;
+    transaction.commit()?;
+
+    debug!("Created record for relationship: {:?}", result);
+    Ok(result)
+  }
+
+  /// Update an existing relationship record in the data store
+  ///
+  /// # Arguments
+  /// * `relationship` The relationship details to persist to the data store
+  ///
+  /// # Returns
+  /// The relationship that was persisted
+  fn update_relationship(
+    &self,
+    relationship: RelationshipEntity,
+  ) -> Result<RelationshipEntity, PersistRelationshipError> {
+    debug!("Updating record for relationship: {:?}", relationship);
+
+    let mut client = self.client().unwrap();
+    let mut transaction = client.transaction().unwrap();
+
+    let new_version = Uuid::new_v4();
+    let new_updated = Utc::now();
+    let source = EntityColumns::from(&relationship.data.source);
+    let target = EntityColumns::from(&relationship.data.target);
+
+    let updated = transaction.execute(
+      "UPDATE relationships SET relation = $1, is_symmetric = $2,
+          source_article_id = $3, source_character_id = $4, source_location_id = $5, source_map_id = $6,
+          target_article_id = $7, target_character_id = $8, target_location_id = $9, target_map_id = $10,
+          version = $11, updated = $12
+        WHERE relationship_id = $13
+        AND world_id = $14
+        AND version = $15",
+      &[
+        &relationship.data.relation,
+        &relationship.data.symmetric,
+        &source.article_id,
+        &source.character_id,
+        &source.location_id,
+        &source.map_id,
+        &target.article_id,
+        &target.character_id,
+        &target.location_id,
+        &target.map_id,
+        &new_version,
+        &new_updated,
+        &relationship.identity.id,
+        &relationship.data.world,
+        &relationship.identity.version,
+      ],
+    )?;
+
+    if updated == 1 {
+      save_relationship_dates(
+        &mut transaction,
+        &relationship.identity.id,
+        &relationship.data,
+      )?;
+
+      let result: RelationshipEntity = transaction
+        .query_one(
+          format!("{} WHERE r.relationship_id = $1", SELECT_RELATIONSHIPS).as_str(),
+          &[&relationship.identity.id],
+        )
+        .map(|row| (&row).into())?;
+      transaction.commit()?;
+
+      debug!("Updated record for relationship: {:?}", result);
+      Ok(result)
+    } else {
+      let relationship_found = transaction.query(
+        "SELECT version FROM relationships WHERE relationship_id = $1 AND world_id = $2",
+        &[&relationship.identity.id, &relationship.data.world],
+      )?;
+
+      if relationship_found.is_empty() {
+        warn!(
+          "Attempted to update relationship {} that wasn't found",
+          relationship.identity.id
+        );
+        Err(PersistRelationshipError::RelationshipNotFound)
+      } else {
+        let old_version: Uuid = relationship_found.get(0).unwrap().get("version");
+
+        warn!(
+          "Attempted to update relationship {}. Expected version {} but database had {}",
+          relationship.identity.id, relationship.identity.version, old_version
+        );
+        Err(PersistRelationshipError::OptimisticLockFailure)
+      }
+    }
+  }
+
+  /// Delete an existing relationship record from the data store
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the relationship belongs to
+  /// * `relationship_id` The ID of the relationship to delete
+  fn delete_relationship(
+    &self,
+    world_id: &WorldID,
+    relationship_id: &RelationshipID,
+  ) -> Result<(), PersistRelationshipError> {
+    debug!(
+      "Deleting relationship {} from world {}",
+      relationship_id, world_id
+    );
+
+    let mut client = self.client().unwrap();
+
+    let deleted = client.execute(
+      "DELETE FROM relationships WHERE world_id = $1 AND relationship_id = $2",
+      &[&world_id, &relationship_id],
+    )?;
+
+    if deleted == 0 {
+      warn!(
+        "Attempted to delete relationship {} that wasn't found",
+        relationship_id
+      );
+      Err(PersistRelationshipError::RelationshipNotFound)
+    } else {
+      Ok(())
+    }
+  }
+}
+
+impl From<postgres::Error> for PersistRelationshipError {
+  fn from(error: postgres::Error) -> Self {
+    warn!("Error persisting relationship in database: {:?}", error);
+
+    error
+      .source()
+      .and_then(|e| e.downcast_ref::<postgres::error::DbError>())
+      .map(|e| match e.constraint() {
+        Some("relationships_world_id_fkey") => PersistRelationshipError::WorldNotFound,
+        Some("relationship_dates_world_id_calendar_id_fkey") => {
+          PersistRelationshipError::CalendarNotFound
+        }
+        Some(constraint)
+          if constraint.starts_with("relationships_source_")
+            || constraint.starts_with("relationships_target_") =>
+        {
+          PersistRelationshipError::EntityNotFound
+        }
+        _ => PersistRelationshipError::UnknownError,
+      })
+      .unwrap_or(PersistRelationshipError::UnknownError)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use spectral::prelude::*;
+  use test_env_log::test;
+  use universe_calendars::CalendarID;
+  use universe_test_database_wrapper::TestDatabaseWrapper;
+  use universe_testdata::{
+    seed, Article, Calendar, Character, Location, Relationship, RelationshipDates, User, World,
+  };
+
+  /// The entities of the seeded world, and the relationships between them
+  struct Seeded {
+    world: World,
+    frodo: EntityRef,
+    bilbo: EntityRef,
+    bag_end: EntityRef,
+    red_book: EntityRef,
+    calendar: CalendarID,
+    relationships: Vec<RelationshipEntity>,
+  }
+
+  /// Seed a world with two characters, a location and an article into the database, along with
+  /// three relationships between them. The second relationship started on a date of the calendar
+  fn seed_relationships(database: &TestDatabaseWrapper) -> Seeded {
+    let user: User = Default::default();
+    let world = World {
+      owner_id: user.user_id,
+      ..Default::default()
+    };
+    let frodo = Character {
+      world_id: world.world_id,
+      name: "Frodo Baggins".to_owned(),
+      ..Default::default()
+    };
+    let bilbo = Character {
+      world_id: world.world_id,
+      name: "Bilbo Baggins".to_owned(),
+      ..Default::default()
+    };
+    let bag_end = Location {
+      world_id: world.world_id,
+      name: "Bag End".to_owned(),
+      ..Default::default()
+    };
+    let red_book = Article {
+      world_id: world.world_id,
+      title: "The Red Book of Westmarch".to_owned(),
+      ..Default::default()
+    };
+    let calendar = Calendar {
+      world_id: world.world_id,
+      ..Default::default()
+    };
+    let uncle = Relationship {
+      world_id: world.world_id,
+      created: "2020-03-11T13:00:31Z".parse().unwrap(),
+      relation: "uncle of".to_owned(),
+      source_id: bilbo.character_id,
+      target_id: frodo.character_id,
+      ..Default::default()
+    };
+    let lives_in = Relationship {
+      world_id: world.world_id,
+      created: "2020-03-11T13:00:32Z".parse().unwrap(),
+      relation: "lives in".to_owned(),
+      source_id: frodo.character_id,
+      target_type: "location".to_owned(),
+      target_id: bag_end.location_id,
+      ..Default::default()
+    };
+    let lives_in_dates = RelationshipDates {
+      relationship_id: lives_in.relationship_id,
+      world_id: world.world_id,
+      calendar_id: calendar.calendar_id,
+      start: Some((2, 1, 22)),
+      end: None,
+    };
+    let author = Relationship {
+      world_id: world.world_id,
+      created: "2020-03-11T13:00:33Z".parse().unwrap(),
+      relation: "author of".to_owned(),
+      source_id: bilbo.character_id,
+      target_type: "article".to_owned(),
+      target_id: red_book.article_id,
+      ..Default::default()
+    };
+
+    seed(
+      &database,
+      vec![
+        &user,
+        &world,
+        &frodo,
+        &bilbo,
+        &bag_end,
+        &red_book,
+        &calendar,
+        &uncle,
+        &lives_in,
+        &lives_in_dates,
+        &author,
+      ],
+    );
+
+    let mut relationships: Vec<RelationshipEntity> =
+      vec![uncle.into(), lives_in.into(), author.into()];
+    relationships[1].data.during = Some(DateRange {
+      calendar: CalendarID::from_uuid(calendar.calendar_id),
+      start: Some(CalendarDate {
+        year: 2,
+        month: 1,
+        day: 22,
+      }),
+      end: None,
+    });
+
+    Seeded {
+      world,
+      frodo: EntityRef::Character(CharacterID::from_uuid(frodo.character_id)),
+      bilbo: EntityRef::Character(CharacterID::from_uuid(bilbo.character_id)),
+      bag_end: EntityRef::Location(LocationID::from_uuid(bag_end.location_id)),
+      red_book: EntityRef::Article(ArticleID::from_uuid(red_book.article_id)),
+      calendar: CalendarID::from_uuid(calendar.calendar_id),
+      relationships,
+    }
+  }
+
+  fn all() -> Pagination {
+    Pagination {
+      offset: 0,
+      limit: 10,
+    }
+  }
+
+  #[test]
+  fn test_get_relationship() {
+    let database = TestDatabaseWrapper::new();
+    let seeded = seed_relationships(&database);
+    let world_id = WorldID::from_uuid(seeded.world.world_id);
+
+    for relationship in seeded.relationships {
+      let found = database
+        .wrapper
+        .get_relationship_by_id(&world_id, &relationship.identity.id);
+      assert_that(&found).is_some().is_equal_to(relationship);
+    }
+  }
+
+  #[test]
+  fn test_get_relationship_wrong_world() {
+    let database = TestDatabaseWrapper::new();
+    let seeded = seed_relationships(&database);
+
+    let found = database
+      .wrapper
+      .get_relationship_by_id(&Default::default(), &seeded.relationships[0].identity.id);
+    assert_that(&found).is_none();
+  }
+
+  #[test]
+  fn test_list_relationships() {
+    let database = TestDatabaseWrapper::new();
+    let seeded = seed_relationships(&database);
+    let world_id = WorldID::from_uuid(seeded.world.world_id);
+
+    let page = database
+      .wrapper
+      .list_relationships(&world_id, Default::default(), all());
+    assert_that(&page.total).is_equal_to(3);
+    assert_that(&page.entries).is_equal_to(seeded.relationships.clone());
+
+    let page = database.wrapper.list_relationships(
+      &world_id,
+      Default::default(),
+      Pagination {
+        offset: 1,
+        limit: 1,
+      },
+    );
+    assert_that(&page.total).is_equal_to(3);
+    assert_that(&page.entries).is_equal_to(vec![seeded.relationships[1].clone()]);
+  }
+
+  #[test]
+  fn test_list_relationships_filtered() {
+    let database = TestDatabaseWrapper::new();
+    let seeded = seed_relationships(&database);
+    let world_id = WorldID::from_uuid(seeded.world.world_id);
+
+    let page = database.wrapper.list_relationships(
+      &world_id,
+      RelationshipFilters {
+        entity: Some(seeded.frodo.clone()),
+        relation: None,
+      },
+      all(),
+    );
+    assert_that(&page.total).is_equal_to(2);
+    assert_that(&page.entries).is_equal_to(seeded.relationships[0..2].to_vec());
+
+    let page = database.wrapper.list_relationships(
+      &world_id,
+      RelationshipFilters {
+        entity: Some(seeded.red_book.clone()),
+        relation: None,
+      },
+      all(),
+    );
+    assert_that(&page.entries).is_equal_to(vec![seeded.relationships[2].clone()]);
+
+    let page = database.wrapper.list_relationships(
+      &world_id,
+      RelationshipFilters {
+        entity: Some(seeded.bilbo.clone()),
+        relation: Some("UNCLE OF".to_owned()),
+      },
+      all(),
+    );
+    assert_that(&page.total).is_equal_to(1);
+    assert_that(&page.entries).is_equal_to(vec![seeded.relationships[0].clone()]);
+
+    let page = database.wrapper.list_relationships(
+      &world_id,
+      RelationshipFilters {
+        entity: Some(seeded.bag_end),
+        relation: Some("uncle of".to_owned()),
+      },
+      all(),
+    );
+    assert_that(&page.total).is_equal_to(0);
+    assert_that(&page.entries).is_empty();
+  }
+
+  #[test]
+  fn test_list_related_entities() {
+    let database = TestDatabaseWrapper::new();
+    let seeded = seed_relationships(&database);
+    let world_id = WorldID::from_uuid(seeded.world.world_id);
+
+    let mut entities = database.wrapper.list_related_entities(&world_id);
+    entities.sort_by(|a, b| a.name.cmp(&b.name));
+    assert_that(&entities).is_equal_to(vec![
+      GraphNode {
+        entity: seeded.bag_end,
+        name: "Bag End".to_owned(),
+      },
+      GraphNode {
+        entity: seeded.bilbo,
+        name: "Bilbo Baggins".to_owned(),
+      },
+      GraphNode {
+        entity: seeded.frodo,
+        name: "Frodo Baggins".to_owned(),
+      },
+      GraphNode {
+        entity: seeded.red_book,
+        name: "The Red Book of Westmarch".to_owned(),
+      },
+    ]);
+
+    assert_that(&database.wrapper.list_related_entities(&Default::default())).is_empty();
+  }
+
+  #[test]
+  fn test_is_entity_in_world() {
+    let database = TestDatabaseWrapper::new();
+    let seeded = seed_relationships(&database);
+    let world_id = WorldID::from_uuid(seeded.world.world_id);
+
+    assert_that(
+      &database
+        .wrapper
+        .is_entity_in_world(&world_id, &seeded.frodo),
+    )
+    .is_true();
+    assert_that(
+      &database
+        .wrapper
+        .is_entity_in_world(&world_id, &seeded.bag_end),
+    )
+    .is_true();
+    assert_that(
+      &database
+        .wrapper
+        .is_entity_in_world(&world_id, &seeded.red_book),
+    )
+    .is_true();
+    assert_that(
+      &database
+        .wrapper
+        .is_entity_in_world(&Default::default(), &seeded.frodo),
+    )
+    .is_false();
+    assert_that(
+      &database
+        .wrapper
+        .is_entity_in_world(&world_id, &EntityRef::Map(Default::default())),
+    )
+    .is_false();
+  }
+
+  #[test]
+  fn test_create_relationship_with_dates() {
+    let database = TestDatabaseWrapper::new();
+    let seeded = seed_relationships(&database);
+    let world_id = WorldID::from_uuid(seeded.world.world_id);
+
+    let data = RelationshipData {
+      world: world_id.clone(),
+      source: seeded.frodo,
+      target: seeded.bilbo,
+      relation: "cousin of".to_owned(),
+      symmetric: true,
+      during: Some(DateRange {
+        calendar: seeded.calendar,
+        start: None,
+        end: Some(CalendarDate {
+          year: 3,
+          month: 4,
+          day: 1,
+        }),
+      }),
+    };
+    let created = database.wrapper.create_relationship(data.clone()).unwrap();
+    assert_that(&created.data).is_equal_to(&data);
+
+    let found = database
+      .wrapper
+      .get_relationship_by_id(&world_id, &created.identity.id);
+    assert_that(&found).is_some().is_equal_to(created);
+  }
+
+  #[test]
+  fn test_create_relationship_unknown_calendar() {
+    let database = TestDatabaseWrapper::new();
+    let seeded = seed_relationships(&database);
+    let world_id = WorldID::from_uuid(seeded.world.world_id);
+
+    let created = database.wrapper.create_relationship(RelationshipData {
+      world: world_id.clone(),
+      source: seeded.frodo,
+      target: seeded.bilbo,
+      relation: "cousin of".to_owned(),
+      symmetric: true,
+      during: Some(DateRange {
+        calendar: Default::default(),
+        start: None,
+        end: None,
+      }),
+    });
+    assert_that(&created)
+      .is_err()
+      .is_equal_to(PersistRelationshipError::CalendarNotFound);
+    assert_that(
+      &database
+        .wrapper
+        .list_relationships(&world_id, Default::default(), all())
+        .total,
+    )
+    .is_equal_to(3);
+  }
+
+  #[test]
+  fn test_create_relationship_unknown_entity() {
+    let database = TestDatabaseWrapper::new();
+    let seeded = seed_relationships(&database);
+
+    let created = database.wrapper.create_relationship(RelationshipData {
+      world: WorldID::from_uuid(seeded.world.world_id),
+      source: seeded.frodo,
+      target: EntityRef::Map(Default::default()),
+      relation: "appears on".to_owned(),
+      symmetric: false,
+      during: None,
+    });
+    assert_that(&created)
+      .is_err()
+      .is_equal_to(PersistRelationshipError::EntityNotFound);
+  }
+
+  #[test]
+  fn test_update_relationship() {
+    let database = TestDatabaseWrapper::new();
+    let seeded = seed_relationships(&database);
+
+    let mut relationship = seeded.relationships[1].clone();
+    relationship.data.relation = "owns".to_owned();
+    relationship.data.source = seeded.bilbo;
+    relationship.data.during = None;
+
+    let updated = database
+      .wrapper
+      .update_relationship(relationship.clone())
+      .unwrap();
+    assert_that(&updated.data).is_equal_to(&relationship.data);
+    assert_that(&updated.identity.version).is_not_equal_to(relationship.identity.version);
+
+    let updated = database.wrapper.update_relationship(relationship);
+    assert_that(&updated)
+      .is_err()
+      .is_equal_to(PersistRelationshipError::OptimisticLockFailure);
+
+    let updated = database.wrapper.update_relationship(RelationshipEntity {
+      identity: Default::default(),
+      data: seeded.relationships[0].data.clone(),
+    });
+    assert_that(&updated)
+      .is_err()
+      .is_equal_to(PersistRelationshipError::RelationshipNotFound);
+  }
+
+  #[test]
+  fn test_delete_relationship() {
+    let database = TestDatabaseWrapper::new();
+    let seeded = seed_relationships(&database);
+    let world_id = WorldID::from_uuid(seeded.world.world_id);
+    let relationship_id = &seeded.relationships[1].identity.id;
+
+    let deleted = database
+      .wrapper
+      .delete_relationship(&world_id, relationship_id);
+    assert_that(&deleted).is_ok();
+    assert_that(
+      &database
+        .wrapper
+        .get_relationship_by_id(&world_id, relationship_id),
+    )
+    .is_none();
+
+    let deleted = database
+      .wrapper
+      .delete_relationship(&world_id, relationship_id);
+    assert_that(&deleted)
+      .is_err()
+      .is_equal_to(PersistRelationshipError::RelationshipNotFound);
+  }
+
+  #[test]
+  fn test_deleting_entities_and_calendars() {
+    let database = TestDatabaseWrapper::new();
+    let seeded = seed_relationships(&database);
+    let world_id = WorldID::from_uuid(seeded.world.world_id);
+
+    let mut client = database.wrapper.client().unwrap();
+    client
+      .execute(
+        "DELETE FROM calendars WHERE calendar_id = $1",
+        &[&seeded.calendar],
+      )
+      .unwrap();
+    client
+      .execute(
+        "DELETE FROM articles WHERE article_id = $1",
+        &[entity_id(&seeded.red_book)],
+      )
+      .unwrap();
+
+    // The relationship that was dated in the calendar remains, but no longer has dates
+    let mut remaining = seeded.relationships[0..2].to_vec();
+    remaining[1].data.during = None;
+    assert_that(&database.wrapper.list_all_relationships(&world_id)).is_equal_to(remaining);
+  }
+}
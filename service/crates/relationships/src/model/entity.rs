@@ -0,0 +1,154 @@
+use std::str::FromStr;
+use universe_articles::ArticleID;
+use universe_characters::CharacterID;
+use universe_locations::LocationID;
+use universe_maps::MapID;
+
+/// An entity of a world that can take part in relationships
+#[derive(Debug, PartialEq, Clone)]
+pub enum EntityRef {
+  Article(ArticleID),
+  Character(CharacterID),
+  Location(LocationID),
+  Map(MapID),
+}
+
+/// Errors that can happen when parsing a reference to an entity
+#[derive(Debug, PartialEq, Clone, thiserror::Error)]
+pub enum EntityRefParseError {
+  #[error("The type of entity was not known")]
+  UnknownType,
+  #[error("The ID of the entity was malformed")]
+  MalformedID,
+}
+
+impl EntityRef {
+  /// Build a reference to an entity from the type of entity and its ID
+  ///
+  /// # Arguments
+  /// * `kind` The type of entity. One of "article", "character", "location" or "map"
+  /// * `id` The ID of the entity
+  ///
+  /// # Returns
+  /// The reference to the entity, or the reason it couldn't be built
+  pub fn new(kind: &str, id: &str) -> Result<Self, EntityRefParseError> {
+    match kind {
+      "article" => id
+        .parse()
+        .map(EntityRef::Article)
+        .map_err(|_| EntityRefParseError::MalformedID),
+      "character" => id
+        .parse()
+        .map(EntityRef::Character)
+        .map_err(|_| EntityRefParseError::MalformedID),
+      "location" => id
+        .parse()
+        .map(EntityRef::Location)
+        .map_err(|_| EntityRefParseError::MalformedID),
+      "map" => id
+        .parse()
+        .map(EntityRef::Map)
+        .map_err(|_| EntityRefParseError::MalformedID),
+      _ => Err(EntityRefParseError::UnknownType),
+    }
+  }
+
+  /// The type of entity that this refers to
+  pub fn kind(&self) -> &'static str {
+    match self {
+      EntityRef::Article(_) => "article",
+      EntityRef::Character(_) => "character",
+      EntityRef::Location(_) => "location",
+      EntityRef::Map(_) => "map",
+    }
+  }
+
+  /// The ID of the entity that this refers to
+  pub fn id(&self) -> String {
+    match self {
+      EntityRef::Article(id) => id.to_string(),
+      EntityRef::Character(id) => id.to_string(),
+      EntityRef::Location(id) => id.to_string(),
+      EntityRef::Map(id) => id.to_string(),
+    }
+  }
+}
+
+/// Entity references are written as the type of entity and its ID, separated by a colon. E.g.
+/// "character:2fcc3850-bb9b-405e-bbab-22978283fef8"
+impl std::fmt::Display for EntityRef {
+  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    write!(f, "{}:{}", self.kind(), self.id())
+  }
+}
+
+impl FromStr for EntityRef {
+  type Err = EntityRefParseError;
+
+  /// Parse an entity reference in the same form that it is displayed in
+  ///
+  /// # Arguments
+  /// * `s` The string to parse
+  ///
+  /// # Returns
+  /// The reference to the entity, or the reason it couldn't be parsed
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut parts = s.trim().splitn(2, ':');
+    let kind = parts.next().unwrap_or("");
+    let id = parts.next().ok_or(EntityRefParseError::MalformedID)?;
+
+    EntityRef::new(kind, id)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use rstest::rstest;
+  use spectral::prelude::*;
+
+  #[rstest(input, expected,
+    case("article:0b2e4c6a-8d1f-4e3a-b5c7-9d1f3b5a7c01", EntityRef::Article("0b2e4c6a-8d1f-4e3a-b5c7-9d1f3b5a7c01".parse().unwrap())),
+    case("character:0b2e4c6a-8d1f-4e3a-b5c7-9d1f3b5a7c01", EntityRef::Character("0b2e4c6a-8d1f-4e3a-b5c7-9d1f3b5a7c01".parse().unwrap())),
+    case("location:0b2e4c6a-8d1f-4e3a-b5c7-9d1f3b5a7c01", EntityRef::Location("0b2e4c6a-8d1f-4e3a-b5c7-9d1f3b5a7c01".parse().unwrap())),
+    case("  map:0b2e4c6a-8d1f-4e3a-b5c7-9d1f3b5a7c01  ", EntityRef::Map("0b2e4c6a-8d1f-4e3a-b5c7-9d1f3b5a7c01".parse().unwrap())),
+  )]
+  fn test_parse_valid(input: &str, expected: EntityRef) {
+    let parsed: Result<EntityRef, EntityRefParseError> = input.parse();
+
+    assert_that(&parsed).is_ok().is_equal_to(expected);
+  }
+
+  #[rstest(
+    input,
+    expected,
+    case("", EntityRefParseError::MalformedID),
+    case("character", EntityRefParseError::MalformedID),
+    case("character:", EntityRefParseError::MalformedID),
+    case("character:not-a-uuid", EntityRefParseError::MalformedID),
+    case(
+      "dragon:0b2e4c6a-8d1f-4e3a-b5c7-9d1f3b5a7c01",
+      EntityRefParseError::UnknownType
+    ),
+    case(
+      "Character:0b2e4c6a-8d1f-4e3a-b5c7-9d1f3b5a7c01",
+      EntityRefParseError::UnknownType
+    )
+  )]
+  fn test_parse_invalid(input: &str, expected: EntityRefParseError) {
+    let parsed: Result<EntityRef, EntityRefParseError> = input.parse();
+
+    assert_that(&parsed).is_err().is_equal_to(expected);
+  }
+
+  #[test]
+  fn test_display_round_trip() {
+    let entity = EntityRef::Location("0b2e4c6a-8d1f-4e3a-b5c7-9d1f3b5a7c01".parse().unwrap());
+
+    assert_that(&entity.to_string())
+      .is_equal_to("location:0b2e4c6a-8d1f-4e3a-b5c7-9d1f3b5a7c01".to_owned());
+    assert_that(&entity.to_string().parse::<EntityRef>())
+      .is_ok()
+      .is_equal_to(entity);
+  }
+}
@@ -0,0 +1,464 @@
+use crate::{EntityRef, RelationshipEntity};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// An entity of a world, as a node of the graph of its relationships
+#[derive(Debug, PartialEq, Clone)]
+pub struct GraphNode {
+  pub entity: EntityRef,
+  /// The name of the entity, or the title of an article
+  pub name: String,
+}
+
+/// Which ways relationships may be followed when walking the graph. Symmetric relationships can
+/// always be followed both ways
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Direction {
+  /// Follow relationships either way
+  Both,
+  /// Only follow relationships from their source to their target
+  Outgoing,
+  /// Only follow relationships from their target back to their source
+  Incoming,
+}
+
+/// A single step across a relationship, from the entity at one end of it to the other
+#[derive(Debug, PartialEq, Clone)]
+pub struct Step<'a> {
+  pub relationship: &'a RelationshipEntity,
+  /// Whether the step goes from the source of the relationship to its target
+  pub forwards: bool,
+}
+
+/// The graph of the relationships between the entities of a world
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Graph {
+  /// The entities that take part in the relationships, sorted by name
+  pub nodes: Vec<GraphNode>,
+  /// The relationships between the entities
+  pub edges: Vec<RelationshipEntity>,
+}
+
+impl<'a> Step<'a> {
+  /// The entity that the step starts from
+  pub fn from(&self) -> &'a EntityRef {
+    if self.forwards {
+      &self.relationship.data.source
+    } else {
+      &self.relationship.data.target
+    }
+  }
+
+  /// The entity that the step ends at
+  pub fn to(&self) -> &'a EntityRef {
+    if self.forwards {
+      &self.relationship.data.target
+    } else {
+      &self.relationship.data.source
+    }
+  }
+}
+
+impl Graph {
+  /// Build a graph from the relationships of a world and the entities that take part in them
+  ///
+  /// # Arguments
+  /// * `nodes` The entities
+  /// * `edges` The relationships between the entities
+  ///
+  /// # Returns
+  /// The graph
+  pub fn new(mut nodes: Vec<GraphNode>, edges: Vec<RelationshipEntity>) -> Self {
+    nodes.sort_by(|a, b| {
+      a.name
+        .to_uppercase()
+        .cmp(&b.name.to_uppercase())
+        .then_with(|| a.entity.to_string().cmp(&b.entity.to_string()))
+    });
+
+    Graph { nodes, edges }
+  }
+
+  /// Find the node of the graph for the given entity
+  ///
+  /// # Arguments
+  /// * `entity` The entity to look for
+  ///
+  /// # Returns
+  /// The node, or `None` if the entity isn't part of any relationship
+  pub fn node(&self, entity: &EntityRef) -> Option<&GraphNode> {
+    self.nodes.iter().find(|node| &node.entity == entity)
+  }
+
+  /// Find every step that can be taken from the given entity, in the order that the relationships
+  /// are in
+  ///
+  /// # Arguments
+  /// * `entity` The entity to start from
+  /// * `direction` Which ways relationships may be followed
+  ///
+  /// # Returns
+  /// The steps to the neighbours of the entity
+  pub fn neighbours(&self, entity: &EntityRef, direction: Direction) -> Vec<Step> {
+    self
+      .edges
+      .iter()
+      .filter_map(|edge| Self::step_from(edge, entity, direction))
+      .collect()
+  }
+
+  /// Find the shortest path between two entities. When there are several, this is the one that
+  /// follows the earliest relationships.
+  ///
+  /// # Arguments
+  /// * `from` The entity to start from
+  /// * `to` The entity to finish at
+  /// * `direction` Which ways relationships may be followed
+  ///
+  /// # Returns
+  /// The steps from one entity to the other, or `None` if there is no way between them
+  pub fn shortest_path(
+    &self,
+    from: &EntityRef,
+    to: &EntityRef,
+    direction: Direction,
+  ) -> Option<Vec<Step>> {
+    if from == to {
+      return self.node(from).map(|_| vec![]);
+    }
+
+    let adjacency = self.adjacency(direction);
+    let mut previous: HashMap<String, Step> = HashMap::new();
+    let mut visited: HashSet<String> = HashSet::new();
+    let mut queue = VecDeque::new();
+    visited.insert(from.to_string());
+    queue.push_back(from.to_string());
+
+    while let Some(current) = queue.pop_front() {
+      for step in adjacency.get(&current).into_iter().flatten() {
+        let next = step.to().to_string();
+        if !visited.insert(next.clone()) {
+          continue;
+        }
+        previous.insert(next.clone(), step.clone());
+
+        if step.to() == to {
+          // Walk back from the end to find the route that we got here by
+          let mut path = vec![];
+          let mut key = next;
+          while let Some(step) = previous.remove(&key) {
+            key = step.from().to_string();
+            path.push(step);
+          }
+          path.reverse();
+          return Some(path);
+        }
+        queue.push_back(next);
+      }
+    }
+
+    None
+  }
+
+  /// Build the part of the graph that is within some number of steps of an entity. This has every
+  /// entity that can be reached in that many steps, and every relationship between those entities.
+  ///
+  /// # Arguments
+  /// * `entity` The entity at the centre of the subgraph
+  /// * `hops` The most steps to take from the entity
+  /// * `direction` Which ways relationships may be followed
+  ///
+  /// # Returns
+  /// The subgraph, which is empty if the entity isn't part of any relationship
+  pub fn within(&self, entity: &EntityRef, hops: u32, direction: Direction) -> Graph {
+    if self.node(entity).is_none() {
+      return Graph::default();
+    }
+
+    let adjacency = self.adjacency(direction);
+    let mut reached: HashSet<String> = HashSet::new();
+    let mut frontier = vec![entity.to_string()];
+    reached.insert(entity.to_string());
+
+    for _ in 0..hops {
+      let mut next_frontier = vec![];
+      for current in frontier {
+        for step in adjacency.get(&current).into_iter().flatten() {
+          let next = step.to().to_string();
+          if reached.insert(next.clone()) {
+            next_frontier.push(next);
+          }
+        }
+      }
+      if next_frontier.is_empty() {
+        break;
+      }
+      frontier = next_frontier;
+    }
+
+    Graph {
+      nodes: self
+        .nodes
+        .iter()
+        .filter(|node| reached.contains(&node.entity.to_string()))
+        .cloned()
+        .collect(),
+      edges: self
+        .edges
+        .iter()
+        .filter(|edge| {
+          reached.contains(&edge.data.source.to_string())
+            && reached.contains(&edge.data.target.to_string())
+        })
+        .cloned()
+        .collect(),
+    }
+  }
+
+  /// Build the steps that can be taken from every entity of the graph, keyed by the entity
+  fn adjacency(&self, direction: Direction) -> HashMap<String, Vec<Step>> {
+    let mut adjacency: HashMap<String, Vec<Step>> = HashMap::new();
+
+    for edge in &self.edges {
+      for entity in &[&edge.data.source, &edge.data.target] {
+        if let Some(step) = Self::step_from(edge, entity, direction) {
+          adjacency
+            .entry(entity.to_string())
+            .or_insert_with(Vec::new)
+            .push(step);
+        }
+      }
+    }
+
+    adjacency
+  }
+
+  /// Work out the step across a relationship from the given entity, if there is one
+  fn step_from<'a>(
+    edge: &'a RelationshipEntity,
+    entity: &EntityRef,
+    direction: Direction,
+  ) -> Option<Step<'a>> {
+    let data = &edge.data;
+
+    if &data.source == entity && (data.symmetric || direction != Direction::Incoming) {
+      Some(Step {
+        relationship: edge,
+        forwards: true,
+      })
+    } else if &data.target == entity && (data.symmetric || direction != Direction::Outgoing) {
+      Some(Step {
+        relationship: edge,
+        forwards: false,
+      })
+    } else {
+      None
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{RelationshipData, RelationshipID};
+  use spectral::prelude::*;
+  use test_env_log::test;
+  use universe_entity::Identity;
+
+  fn entity(n: u32) -> EntityRef {
+    EntityRef::Character(
+      format!("0b2e4c6a-8d1f-4e3a-b5c7-9d1f3b5a7c{:02}", n)
+        .parse()
+        .unwrap(),
+    )
+  }
+
+  fn relationship(source: u32, relation: &str, target: u32, symmetric: bool) -> RelationshipEntity {
+    RelationshipEntity {
+      identity: Identity {
+        id: RelationshipID::default(),
+        ..Default::default()
+      },
+      data: RelationshipData {
+        world: Default::default(),
+        source: entity(source),
+        target: entity(target),
+        relation: relation.to_owned(),
+        symmetric,
+        during: None,
+      },
+    }
+  }
+
+  /// Build a family tree:
+  ///
+  /// 1 is the parent of 2 and 3, 2 is the parent of 4, 3 and 5 are rivals, and 6 is on its own
+  /// with 7
+  fn graph() -> Graph {
+    Graph::new(
+      (1..=7)
+        .map(|n| GraphNode {
+          entity: entity(n),
+          name: format!("Character {}", 8 - n),
+        })
+        .collect(),
+      vec![
+        relationship(1, "parent of", 2, false),
+        relationship(1, "parent of", 3, false),
+        relationship(2, "parent of", 4, false),
+        relationship(3, "rival of", 5, true),
+        relationship(6, "friend of", 7, true),
+      ],
+    )
+  }
+
+  fn path_entities(path: &[Step]) -> Vec<EntityRef> {
+    path.iter().map(|step| step.to().clone()).collect()
+  }
+
+  fn node_entities(graph: &Graph) -> Vec<EntityRef> {
+    graph.nodes.iter().map(|node| node.entity.clone()).collect()
+  }
+
+  #[test]
+  fn test_nodes_sorted_by_name() {
+    let graph = graph();
+
+    assert_that(&node_entities(&graph)).is_equal_to(vec![
+      entity(7),
+      entity(6),
+      entity(5),
+      entity(4),
+      entity(3),
+      entity(2),
+      entity(1),
+    ]);
+  }
+
+  #[test]
+  fn test_neighbours_both_ways() {
+    let graph = graph();
+    let neighbours = graph.neighbours(&entity(2), Direction::Both);
+
+    assert_that(&neighbours).has_length(2);
+    assert_that(&neighbours[0].to()).is_equal_to(&entity(1));
+    assert_that(&neighbours[0].forwards).is_false();
+    assert_that(&neighbours[1].to()).is_equal_to(&entity(4));
+    assert_that(&neighbours[1].forwards).is_true();
+  }
+
+  #[test]
+  fn test_neighbours_outgoing() {
+    let graph = graph();
+
+    let neighbours = graph.neighbours(&entity(2), Direction::Outgoing);
+    assert_that(&path_entities(&neighbours)).is_equal_to(vec![entity(4)]);
+
+    let neighbours = graph.neighbours(&entity(2), Direction::Incoming);
+    assert_that(&path_entities(&neighbours)).is_equal_to(vec![entity(1)]);
+  }
+
+  #[test]
+  fn test_neighbours_symmetric() {
+    let graph = graph();
+
+    let neighbours = graph.neighbours(&entity(5), Direction::Outgoing);
+    assert_that(&path_entities(&neighbours)).is_equal_to(vec![entity(3)]);
+    assert_that(&neighbours[0].forwards).is_false();
+  }
+
+  #[test]
+  fn test_neighbours_unknown_entity() {
+    let graph = graph();
+
+    assert_that(&graph.neighbours(&entity(9), Direction::Both)).is_empty();
+  }
+
+  #[test]
+  fn test_shortest_path() {
+    let graph = graph();
+
+    let path = graph.shortest_path(&entity(4), &entity(5), Direction::Both);
+    assert_that(&path.as_deref().map(path_entities)).is_equal_to(Some(vec![
+      entity(2),
+      entity(1),
+      entity(3),
+      entity(5),
+    ]));
+    assert_that(&path.unwrap()[0].from()).is_equal_to(&entity(4));
+  }
+
+  #[test]
+  fn test_shortest_path_directed() {
+    let graph = graph();
+
+    let path = graph.shortest_path(&entity(1), &entity(5), Direction::Outgoing);
+    assert_that(&path.as_deref().map(path_entities)).is_equal_to(Some(vec![entity(3), entity(5)]));
+
+    let path = graph.shortest_path(&entity(4), &entity(5), Direction::Outgoing);
+    assert_that(&path).is_none();
+
+    let path = graph.shortest_path(&entity(4), &entity(1), Direction::Incoming);
+    assert_that(&path.as_deref().map(path_entities)).is_equal_to(Some(vec![entity(2), entity(1)]));
+  }
+
+  #[test]
+  fn test_shortest_path_disconnected() {
+    let graph = graph();
+
+    assert_that(&graph.shortest_path(&entity(1), &entity(7), Direction::Both)).is_none();
+    assert_that(&graph.shortest_path(&entity(1), &entity(9), Direction::Both)).is_none();
+  }
+
+  #[test]
+  fn test_shortest_path_to_self() {
+    let graph = graph();
+
+    assert_that(&graph.shortest_path(&entity(1), &entity(1), Direction::Both))
+      .is_equal_to(Some(vec![]));
+    assert_that(&graph.shortest_path(&entity(9), &entity(9), Direction::Both)).is_none();
+  }
+
+  #[test]
+  fn test_within_hops() {
+    let graph = graph();
+
+    let subgraph = graph.within(&entity(2), 0, Direction::Both);
+    assert_that(&node_entities(&subgraph)).is_equal_to(vec![entity(2)]);
+    assert_that(&subgraph.edges).is_empty();
+
+    let subgraph = graph.within(&entity(2), 1, Direction::Both);
+    assert_that(&node_entities(&subgraph)).is_equal_to(vec![entity(4), entity(2), entity(1)]);
+    assert_that(&subgraph.edges).has_length(2);
+
+    let subgraph = graph.within(&entity(2), 2, Direction::Both);
+    assert_that(&node_entities(&subgraph)).is_equal_to(vec![
+      entity(4),
+      entity(3),
+      entity(2),
+      entity(1),
+    ]);
+    assert_that(&subgraph.edges).has_length(3);
+
+    let subgraph = graph.within(&entity(2), 10, Direction::Both);
+    assert_that(&subgraph.nodes).has_length(5);
+    assert_that(&subgraph.edges).has_length(4);
+  }
+
+  #[test]
+  fn test_within_hops_directed() {
+    let graph = graph();
+
+    let subgraph = graph.within(&entity(1), 5, Direction::Outgoing);
+    assert_that(&subgraph.nodes).has_length(5);
+
+    let subgraph = graph.within(&entity(4), 5, Direction::Incoming);
+    assert_that(&node_entities(&subgraph)).is_equal_to(vec![entity(4), entity(2), entity(1)]);
+  }
+
+  #[test]
+  fn test_within_unknown_entity() {
+    let graph = graph();
+
+    assert_that(&graph.within(&entity(9), 5, Direction::Both)).is_equal_to(Graph::default());
+  }
+}
@@ -0,0 +1,11 @@
+mod entity;
+mod graph;
+mod relationship;
+mod relationship_import;
+mod relationship_id;
+
+pub use entity::*;
+pub use graph::*;
+pub use relationship::*;
+pub use relationship_import::*;
+pub use relationship_id::*;
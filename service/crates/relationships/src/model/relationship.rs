@@ -0,0 +1,76 @@
+use crate::{EntityRef, RelationshipID};
+use universe_calendars::{CalendarDate, CalendarID};
+use universe_entity::Identity;
+use universe_worlds::WorldID;
+
+/// The in-world dates that a relationship held between, in some calendar of the world
+#[derive(Debug, PartialEq, Clone)]
+pub struct DateRange {
+  /// The calendar that the dates are in
+  pub calendar: CalendarID,
+  /// The first day of the relationship, or `None` if it has always held
+  pub start: Option<CalendarDate>,
+  /// The last day of the relationship, or `None` if it still holds
+  pub end: Option<CalendarDate>,
+}
+
+/// Struct to represent the data about a single relationship between two entities of a world
+#[derive(Debug, PartialEq, Clone)]
+pub struct RelationshipData {
+  /// The world that both entities belong to
+  pub world: WorldID,
+  /// The entity that the relationship is from
+  pub source: EntityRef,
+  /// The entity that the relationship is to
+  pub target: EntityRef,
+  /// The type of relationship, read from the source to the target. E.g. "parent of"
+  pub relation: String,
+  /// Whether the relationship reads the same in both directions, e.g. "rival of"
+  pub symmetric: bool,
+  /// When the relationship held, if it hasn't always
+  pub during: Option<DateRange>,
+}
+
+/// Type to represent the entity that is a persisted relationship record
+#[derive(Debug, PartialEq, Clone)]
+pub struct RelationshipEntity {
+  pub identity: Identity<RelationshipID>,
+  pub data: RelationshipData,
+}
+
+impl RelationshipData {
+  /// Check whether the relationship involves the given entity, at either end
+  pub fn involves(&self, entity: &EntityRef) -> bool {
+    &self.source == entity || &self.target == entity
+  }
+}
+
+#[cfg(test)]
+impl From<universe_testdata::Relationship> for RelationshipEntity {
+  fn from(relationship: universe_testdata::Relationship) -> RelationshipEntity {
+    RelationshipEntity {
+      identity: Identity {
+        id: RelationshipID::from_uuid(relationship.relationship_id),
+        version: relationship.version,
+        created: relationship.created,
+        updated: relationship.updated,
+      },
+      data: RelationshipData {
+        world: WorldID::from_uuid(relationship.world_id),
+        source: EntityRef::new(
+          &relationship.source_type,
+          &relationship.source_id.to_string(),
+        )
+        .unwrap(),
+        target: EntityRef::new(
+          &relationship.target_type,
+          &relationship.target_id.to_string(),
+        )
+        .unwrap(),
+        relation: relationship.relation.clone(),
+        symmetric: relationship.symmetric,
+        during: None,
+      },
+    }
+  }
+}
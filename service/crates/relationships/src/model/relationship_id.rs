@@ -0,0 +1,164 @@
+use bytes::BytesMut;
+use postgres::types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
+use serde::Serialize;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Representation of a Relationship ID of some relationship in the system.
+///
+/// A Relationship ID is any valid UUID.
+#[derive(Debug, PartialEq, Clone, Serialize, FromSql)]
+pub struct RelationshipID(Uuid);
+
+/// Errors that can happen when parsing a string into a Relationship ID.
+#[derive(Debug, PartialEq, Clone, thiserror::Error)]
+pub enum RelationshipIDParseError {
+  #[error("Relationship ID was malformed: {0}")]
+  Malformed(#[from] uuid::Error),
+}
+
+impl RelationshipID {
+  /// Construct a Relationship ID from a UUID value
+  ///
+  /// # Arguments
+  /// * `uuid` The UUID to use
+  ///
+  /// # Returns
+  /// The Relationship ID
+  #[allow(unused)]
+  pub fn from_uuid(uuid: Uuid) -> Self {
+    RelationshipID(uuid)
+  }
+}
+
+impl std::fmt::Display for RelationshipID {
+  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl Default for RelationshipID {
+  fn default() -> Self {
+    RelationshipID(Uuid::new_v4())
+  }
+}
+/// Implementation of the standard `FromStr` trait to allow us to parse any String into a `RelationshipID` object
+impl FromStr for RelationshipID {
+  type Err = RelationshipIDParseError;
+
+  /// Attempt to parse a string into a RelationshipID object.
+  ///
+  /// A Relationship ID is any valid UUID.
+  ///
+  /// # Arguments
+  /// * `s` The string to parse
+  ///
+  /// # Returns
+  /// The result of parsing the Relationship ID. Either an `RelationshipID` object or an error if the incoming
+  /// string was not valid.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let uuid: Uuid = s.trim().parse()?;
+    Ok(RelationshipID(uuid))
+  }
+}
+
+/// Allow us to pass `RelationshipID` objects to Postgres as part of executing a database query.
+///
+/// The implementation of this trait allows objects of this type to be used directly as database
+/// binds without ever needing to extract the string from inside it.
+impl ToSql for RelationshipID {
+  fn to_sql(
+    &self,
+    t: &Type,
+    w: &mut BytesMut,
+  ) -> Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+    self.0.to_sql(t, w)
+  }
+
+  accepts!(UUID);
+  to_sql_checked!();
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use assert_matches::*;
+  use serde_json::json;
+  use spectral::prelude::*;
+  use test_env_log::test;
+
+  #[test]
+  fn test_parse_valid_relationship_id() {
+    let relationship_id: Result<RelationshipID, RelationshipIDParseError> =
+      "f2c55656-d7a1-4e41-a311-fe653b9b15de".parse();
+
+    assert_that(&relationship_id)
+      .is_ok()
+      .is_equal_to(RelationshipID(
+        "f2c55656-d7a1-4e41-a311-fe653b9b15de".parse().unwrap(),
+      ));
+  }
+
+  #[test]
+  fn test_parse_padded_relationship_id() {
+    let relationship_id: Result<RelationshipID, RelationshipIDParseError> =
+      "  f2c55656-d7a1-4e41-a311-fe653b9b15de    ".parse();
+
+    assert_that(&relationship_id)
+      .is_ok()
+      .is_equal_to(RelationshipID(
+        "f2c55656-d7a1-4e41-a311-fe653b9b15de".parse().unwrap(),
+      ));
+  }
+
+  #[test]
+  fn test_parse_empty_string() {
+    let relationship_id: Result<RelationshipID, RelationshipIDParseError> = "".parse();
+
+    assert_matches!(
+      relationship_id.unwrap_err(),
+      RelationshipIDParseError::Malformed(_)
+    );
+  }
+
+  #[test]
+  fn test_parse_blank_string() {
+    let relationship_id: Result<RelationshipID, RelationshipIDParseError> = "     ".parse();
+
+    assert_matches!(
+      relationship_id.unwrap_err(),
+      RelationshipIDParseError::Malformed(_)
+    );
+  }
+
+  #[test]
+  fn test_parse_invalid_string_bad_length() {
+    let relationship_id: Result<RelationshipID, RelationshipIDParseError> = "non-uuid".parse();
+
+    assert_matches!(
+      relationship_id.unwrap_err(),
+      RelationshipIDParseError::Malformed(_)
+    );
+  }
+
+  #[test]
+  fn test_parse_invalid_string_bad_character() {
+    let relationship_id: Result<RelationshipID, RelationshipIDParseError> =
+      "C37837C7-3E8C-4235-8A00-0845F598D12Z".parse();
+
+    assert_matches!(
+      relationship_id.unwrap_err(),
+      RelationshipIDParseError::Malformed(_)
+    );
+  }
+
+  #[test]
+  fn test_serialize_valid_relationship_id() {
+    let relationship_id = RelationshipID("f2c55656-d7a1-4e41-a311-fe653b9b15de".parse().unwrap());
+
+    let serialized = serde_json::to_value(relationship_id);
+    assert_that(&serialized)
+      .is_ok()
+      .is_equal_to(json!("f2c55656-d7a1-4e41-a311-fe653b9b15de"));
+  }
+}
@@ -0,0 +1,23 @@
+use universe_calendars::CalendarDate;
+use universe_entity::EntityKey;
+
+/// Details of a relationship to create as part of importing or copying the relationships of a
+/// world. The entities at either end, and the calendar of its dates, are referred to by the keys
+/// that they were known by before they were imported or copied themselves
+#[derive(Debug, PartialEq, Clone)]
+pub struct RelationshipImport {
+  pub source: EntityKey,
+  pub target: EntityKey,
+  pub relation: String,
+  pub symmetric: bool,
+  pub during: Option<DateRangeImport>,
+}
+
+/// The dates that a relationship that is being imported or copied held between
+#[derive(Debug, PartialEq, Clone)]
+pub struct DateRangeImport {
+  /// The key that the calendar of the dates was known by
+  pub calendar: String,
+  pub start: Option<CalendarDate>,
+  pub end: Option<CalendarDate>,
+}
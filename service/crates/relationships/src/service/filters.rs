@@ -0,0 +1,10 @@
+use crate::model::EntityRef;
+
+/// Filters that can be applied when listing the relationships of a world
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct RelationshipFilters {
+  /// Only return relationships that this entity takes part in, at either end
+  pub entity: Option<EntityRef>,
+  /// Only return relationships of this type, ignoring case
+  pub relation: Option<String>,
+}
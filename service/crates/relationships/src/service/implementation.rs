@@ -0,0 +1,650 @@
+use super::{repository::*, service::*, RelationshipFilters};
+use crate::model::*;
+use tracing::{info, warn};
+use universe_entity::{EntityKey, IdMap, Page, Pagination};
+use universe_worlds::WorldID;
+
+/// The Relationship Service to allow interactions with the relationships between entities
+pub struct RelationshipServiceImpl<Repo> {
+  repository: Repo,
+}
+
+/// Create a new Relationship Service
+///
+/// # Arguments
+/// * `repository` The repository to load and save relationships with
+///
+/// # Returns
+/// The Relationship Service
+pub fn new_relationship_service<Repo: RelationshipRepository + Send + Sync>(
+  repository: Repo,
+) -> impl RelationshipService {
+  RelationshipServiceImpl { repository }
+}
+
+impl<Repo: RelationshipRepository + Send + Sync> RelationshipServiceImpl<Repo> {
+  /// Check the details of a relationship before it's saved
+  ///
+  /// # Arguments
+  /// * `relationship` The relationship to check
+  ///
+  /// # Returns
+  /// Every problem found with the relationship
+  fn validate(&self, relationship: &RelationshipData) -> Vec<RelationshipValidationError> {
+    let mut errors = vec![];
+
+    if !self
+      .repository
+      .is_entity_in_world(&relationship.world, &relationship.source)
+    {
+      errors.push(RelationshipValidationError::UnknownSource);
+    }
+    if !self
+      .repository
+      .is_entity_in_world(&relationship.world, &relationship.target)
+    {
+      errors.push(RelationshipValidationError::UnknownTarget);
+    }
+    if relationship.source == relationship.target {
+      errors.push(RelationshipValidationError::SameEntity);
+    }
+
+    if !errors.is_empty() {
+      warn!("Relationship is invalid: {:?}", errors);
+    }
+    errors
+  }
+}
+
+impl<Repo: RelationshipRepository + Send + Sync> RelationshipService
+  for RelationshipServiceImpl<Repo>
+{
+  /// Retrieve the relationship that has the given unique ID, as long as it belongs to the given
+  /// world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the relationship belongs to
+  /// * `relationship_id` The ID of the relationship to retrieve
+  ///
+  /// # Returns
+  /// The relationship, or `None` if it wasn't found
+  fn get_relationship_by_id(
+    &self,
+    world_id: &WorldID,
+    relationship_id: &RelationshipID,
+  ) -> Option<RelationshipEntity> {
+    let relationship = self
+      .repository
+      .get_relationship_by_id(world_id, relationship_id);
+
+    if relationship.is_none() {
+      warn!(
+        "No relationship found with ID {} in world {}",
+        relationship_id, world_id
+      );
+    }
+
+    relationship
+  }
+
+  /// Retrieve a page of the relationships of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the relationships of
+  /// * `filters` The filters to apply to the relationships
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of relationships
+  fn list_relationships(
+    &self,
+    world_id: &WorldID,
+    filters: RelationshipFilters,
+    pagination: Pagination,
+  ) -> Page<RelationshipEntity> {
+    self
+      .repository
+      .list_relationships(world_id, filters, pagination)
+  }
+
+  /// Retrieve every relationship of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the relationships of
+  ///
+  /// # Returns
+  /// The relationships
+  fn list_all_relationships(&self, world_id: &WorldID) -> Vec<RelationshipEntity> {
+    self.repository.list_all_relationships(world_id)
+  }
+
+  /// Load the graph of every relationship of a world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to load the graph of
+  ///
+  /// # Returns
+  /// The graph
+  fn load_graph(&self, world_id: &WorldID) -> Graph {
+    let nodes = self.repository.list_related_entities(world_id);
+    let edges = self.repository.list_all_relationships(world_id);
+
+    Graph::new(nodes, edges)
+  }
+
+  /// Create a new relationship
+  ///
+  /// # Arguments
+  /// * `relationship` The relationship data to create the relationship from
+  ///
+  /// # Returns
+  /// The relationship that was persisted
+  fn create_relationship(
+    &self,
+    relationship: RelationshipData,
+  ) -> Result<RelationshipEntity, CreateRelationshipError> {
+    let errors = self.validate(&relationship);
+    if !errors.is_empty() {
+      return Err(CreateRelationshipError::ValidationError(errors));
+    }
+
+    let created = self.repository.create_relationship(relationship)?;
+    Ok(created)
+  }
+
+  /// Update an existing relationship.
+  ///
+  /// This will load the relationship by ID, and then call a provided callback to mutate the
+  /// relationship before persisting the changes back to the database.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the relationship belongs to
+  /// * `relationship_id` The ID of the relationship to update
+  /// * `updater` The callback to mutate the relationship with
+  ///
+  /// # Returns
+  /// The newly updated relationship
+  fn update_relationship(
+    &self,
+    world_id: &WorldID,
+    relationship_id: &RelationshipID,
+    updater: &mut dyn FnMut(
+      RelationshipData,
+    ) -> Result<RelationshipData, Box<dyn std::error::Error>>,
+  ) -> Result<RelationshipEntity, UpdateRelationshipError> {
+    let relationship = self
+      .get_relationship_by_id(world_id, relationship_id)
+      .ok_or(UpdateRelationshipError::UnknownRelationship)?;
+
+    let world = relationship.data.world.clone();
+    let updated = updater(relationship.data).map_err(UpdateRelationshipError::UpdateError)?;
+    // Relationships can't be moved between worlds
+    let updated = RelationshipData { world, ..updated };
+
+    let errors = self.validate(&updated);
+    if !errors.is_empty() {
+      return Err(UpdateRelationshipError::ValidationError(errors));
+    }
+
+    let saved = self.repository.update_relationship(RelationshipEntity {
+      identity: relationship.identity,
+      data: updated,
+    })?;
+    Ok(saved)
+  }
+
+  /// Delete a relationship
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the relationship belongs to
+  /// * `relationship_id` The ID of the relationship to delete
+  fn delete_relationship(
+    &self,
+    world_id: &WorldID,
+    relationship_id: &RelationshipID,
+  ) -> Result<(), DeleteRelationshipError> {
+    self
+      .repository
+      .delete_relationship(world_id, relationship_id)?;
+    Ok(())
+  }
+
+  /// Create a set of relationships in a world.
+  ///
+  /// The entities at either end of the relationships, and the calendars that they are dated in,
+  /// are referred to by the keys that those were known by before they were imported themselves,
+  /// so they must already have been recorded in `ids`.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to create the relationships in
+  /// * `relationships` The relationships to create
+  /// * `ids` The map to look up the entities and calendars of the relationships in
+  ///
+  /// # Returns
+  /// The relationships that were created
+  fn import_relationships(
+    &self,
+    world_id: &WorldID,
+    relationships: Vec<RelationshipImport>,
+    ids: &IdMap,
+  ) -> Result<Vec<RelationshipEntity>, ImportRelationshipsError> {
+    let resolve = |key: &EntityKey| {
+      ids
+        .resolve(key)
+        .and_then(|id| EntityRef::new(&key.kind, id).ok())
+    };
+
+    let mut created = Vec::with_capacity(relationships.len());
+
+    for (index, relationship) in relationships.into_iter().enumerate() {
+      let source = resolve(&relationship.source);
+      let target = resolve(&relationship.target);
+      let (source, target) = match (source, target) {
+        (Some(source), Some(target)) => (source, target),
+        (source, target) => {
+          let mut errors = vec![];
+          if source.is_none() {
+            errors.push(RelationshipValidationError::UnknownSource);
+          }
+          if target.is_none() {
+            errors.push(RelationshipValidationError::UnknownTarget);
+          }
+          warn!("Relationship {} is invalid: {:?}", index, errors);
+          return Err(ImportRelationshipsError::ValidationError(index, errors));
+        }
+      };
+
+      let during = match relationship.during {
+        None => None,
+        Some(during) => Some(DateRange {
+          calendar: ids
+            .get("calendar", &during.calendar)
+            .and_then(|calendar| calendar.parse().ok())
+            .ok_or(ImportRelationshipsError::UnknownCalendar(index))?,
+          start: during.start,
+          end: during.end,
+        }),
+      };
+
+      let result = self
+        .create_relationship(RelationshipData {
+          world: world_id.clone(),
+          source,
+          target,
+          relation: relationship.relation,
+          symmetric: relationship.symmetric,
+          during,
+        })
+        .map_err(|e| match e {
+          CreateRelationshipError::ValidationError(errors) => {
+            ImportRelationshipsError::ValidationError(index, errors)
+          }
+          CreateRelationshipError::UnknownWorld => ImportRelationshipsError::UnknownWorld,
+          CreateRelationshipError::UnknownCalendar => {
+            ImportRelationshipsError::UnknownCalendar(index)
+          }
+          _ => ImportRelationshipsError::UnknownError,
+        })?;
+
+      created.push(result);
+    }
+    info!(
+      "Imported {} relationships into world {}",
+      created.len(),
+      world_id
+    );
+
+    Ok(created)
+  }
+
+  /// Copy every relationship of one world into another.
+  ///
+  /// Everything that the relationships refer to must already have been copied and recorded in
+  /// `ids`.
+  ///
+  /// # Arguments
+  /// * `from` The ID of the world to copy the relationships from
+  /// * `to` The ID of the world to copy the relationships into
+  /// * `ids` The map to look up the entities and calendars of the relationships in
+  ///
+  /// # Returns
+  /// The relationships that were created
+  fn copy_relationships(
+    &self,
+    from: &WorldID,
+    to: &WorldID,
+    ids: &IdMap,
+  ) -> Result<Vec<RelationshipEntity>, ImportRelationshipsError> {
+    let relationships = self
+      .list_all_relationships(from)
+      .into_iter()
+      .map(|relationship| {
+        let RelationshipData {
+          source,
+          target,
+          relation,
+          symmetric,
+          during,
+          ..
+        } = relationship.data;
+
+        RelationshipImport {
+          source: EntityKey::new(source.kind(), &source.id()),
+          target: EntityKey::new(target.kind(), &target.id()),
+          relation,
+          symmetric,
+          during: during.map(|during| DateRangeImport {
+            calendar: during.calendar.to_string(),
+            start: during.start,
+            end: during.end,
+          }),
+        }
+      })
+      .collect();
+
+    self.import_relationships(to, relationships, ids)
+  }
+}
+
+impl From<PersistRelationshipError> for CreateRelationshipError {
+  fn from(e: PersistRelationshipError) -> Self {
+    warn!("Error creating relationship: {}", e);
+    match e {
+      PersistRelationshipError::WorldNotFound => CreateRelationshipError::UnknownWorld,
+      PersistRelationshipError::CalendarNotFound => CreateRelationshipError::UnknownCalendar,
+      _ => CreateRelationshipError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistRelationshipError> for UpdateRelationshipError {
+  fn from(e: PersistRelationshipError) -> Self {
+    warn!("Error updating relationship: {}", e);
+    match e {
+      PersistRelationshipError::RelationshipNotFound => {
+        UpdateRelationshipError::UnknownRelationship
+      }
+      PersistRelationshipError::CalendarNotFound => UpdateRelationshipError::UnknownCalendar,
+      PersistRelationshipError::OptimisticLockFailure => {
+        UpdateRelationshipError::OptimisticLockFailure
+      }
+      _ => UpdateRelationshipError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistRelationshipError> for DeleteRelationshipError {
+  fn from(e: PersistRelationshipError) -> Self {
+    warn!("Error deleting relationship: {}", e);
+    match e {
+      PersistRelationshipError::RelationshipNotFound => {
+        DeleteRelationshipError::UnknownRelationship
+      }
+      _ => DeleteRelationshipError::UnknownError,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use assert_matches::assert_matches;
+  use spectral::prelude::*;
+  use test_env_log::test;
+  use universe_calendars::CalendarID;
+  use universe_characters::CharacterID;
+  use universe_entity::Identity;
+  use universe_locations::LocationID;
+
+  fn relationship(source: &str, target: &str) -> RelationshipData {
+    RelationshipData {
+      world: Default::default(),
+      source: source.parse().unwrap(),
+      target: target.parse().unwrap(),
+      relation: "parent of".to_owned(),
+      symmetric: false,
+      during: None,
+    }
+  }
+
+  #[test]
+  fn test_create_relationship() {
+    let data = relationship(
+      "character:0b2e4c6a-8d1f-4e3a-b5c7-9d1f3b5a7c01",
+      "character:0b2e4c6a-8d1f-4e3a-b5c7-9d1f3b5a7c02",
+    );
+
+    let mut repository = MockRelationshipRepository::new();
+    repository
+      .expect_is_entity_in_world()
+      .times(2)
+      .return_const(true);
+    repository
+      .expect_create_relationship()
+      .times(1)
+      .returning(|data| {
+        Ok(RelationshipEntity {
+          identity: Identity::default(),
+          data,
+        })
+      });
+
+    let service = new_relationship_service(repository);
+    let created = service.create_relationship(data.clone());
+
+    assert_that(&created.map(|r| r.data))
+      .is_ok()
+      .is_equal_to(data);
+  }
+
+  #[test]
+  fn test_create_relationship_invalid() {
+    let data = relationship(
+      "character:0b2e4c6a-8d1f-4e3a-b5c7-9d1f3b5a7c01",
+      "character:0b2e4c6a-8d1f-4e3a-b5c7-9d1f3b5a7c01",
+    );
+
+    let mut repository = MockRelationshipRepository::new();
+    repository
+      .expect_is_entity_in_world()
+      .times(2)
+      .return_const(false);
+    repository.expect_create_relationship().never();
+
+    let service = new_relationship_service(repository);
+    let created = service.create_relationship(data);
+
+    assert_that(&created)
+      .is_err()
+      .is_equal_to(CreateRelationshipError::ValidationError(vec![
+        RelationshipValidationError::UnknownSource,
+        RelationshipValidationError::UnknownTarget,
+        RelationshipValidationError::SameEntity,
+      ]));
+  }
+
+  #[test]
+  fn test_update_relationship_keeps_world() {
+    let existing = RelationshipEntity {
+      identity: Identity::default(),
+      data: relationship(
+        "character:0b2e4c6a-8d1f-4e3a-b5c7-9d1f3b5a7c01",
+        "character:0b2e4c6a-8d1f-4e3a-b5c7-9d1f3b5a7c02",
+      ),
+    };
+    let world = existing.data.world.clone();
+
+    let mut repository = MockRelationshipRepository::new();
+    let returned = existing.clone();
+    repository
+      .expect_get_relationship_by_id()
+      .times(1)
+      .returning(move |_, _| Some(returned.clone()));
+    repository
+      .expect_is_entity_in_world()
+      .times(2)
+      .return_const(true);
+    repository
+      .expect_update_relationship()
+      .times(1)
+      .returning(Ok);
+
+    let service = new_relationship_service(repository);
+    let updated = service.update_relationship(&world, &existing.identity.id, &mut |data| {
+      Ok(RelationshipData {
+        world: Default::default(),
+        relation: "rival of".to_owned(),
+        symmetric: true,
+        ..data
+      })
+    });
+
+    let updated = updated.unwrap();
+    assert_that(&updated.data.world).is_equal_to(world);
+    assert_that(&updated.data.relation).is_equal_to("rival of".to_owned());
+    assert_that(&updated.data.symmetric).is_true();
+  }
+
+  #[test]
+  fn test_update_unknown_relationship() {
+    let mut repository = MockRelationshipRepository::new();
+    repository
+      .expect_get_relationship_by_id()
+      .times(1)
+      .return_const(None);
+    repository.expect_update_relationship().never();
+
+    let service = new_relationship_service(repository);
+    let updated =
+      service.update_relationship(&Default::default(), &Default::default(), &mut |data| {
+        Ok(data)
+      });
+
+    assert_matches!(updated, Err(UpdateRelationshipError::UnknownRelationship));
+  }
+
+  #[test]
+  fn test_load_graph() {
+    let edge = RelationshipEntity {
+      identity: Identity::default(),
+      data: relationship(
+        "character:0b2e4c6a-8d1f-4e3a-b5c7-9d1f3b5a7c01",
+        "location:0b2e4c6a-8d1f-4e3a-b5c7-9d1f3b5a7c02",
+      ),
+    };
+    let nodes = vec![
+      GraphNode {
+        entity: edge.data.source.clone(),
+        name: "Frodo".to_owned(),
+      },
+      GraphNode {
+        entity: edge.data.target.clone(),
+        name: "Bag End".to_owned(),
+      },
+    ];
+
+    let mut repository = MockRelationshipRepository::new();
+    repository
+      .expect_list_related_entities()
+      .times(1)
+      .return_const(nodes.clone());
+    repository
+      .expect_list_all_relationships()
+      .times(1)
+      .return_const(vec![edge.clone()]);
+
+    let service = new_relationship_service(repository);
+    let graph = service.load_graph(&Default::default());
+
+    assert_that(&graph.nodes).is_equal_to(vec![nodes[1].clone(), nodes[0].clone()]);
+    assert_that(&graph.edges).is_equal_to(vec![edge]);
+  }
+
+  fn relationship_import(source: &str, target: &str) -> RelationshipImport {
+    RelationshipImport {
+      source: EntityKey::new("character", source),
+      target: EntityKey::new("location", target),
+      relation: "lives in".to_owned(),
+      symmetric: false,
+      during: Some(DateRangeImport {
+        calendar: "shire-reckoning".to_owned(),
+        start: None,
+        end: None,
+      }),
+    }
+  }
+
+  #[test]
+  fn test_import_relationships_remaps_ids() {
+    let character_id: CharacterID = "0b2e4c6a-8d1f-4e3a-b5c7-9d1f3b5a7c01".parse().unwrap();
+    let location_id: LocationID = "0b2e4c6a-8d1f-4e3a-b5c7-9d1f3b5a7c02".parse().unwrap();
+    let calendar_id: CalendarID = "0b2e4c6a-8d1f-4e3a-b5c7-9d1f3b5a7c03".parse().unwrap();
+    let mut ids = IdMap::default();
+    ids.insert("character", "frodo", &character_id);
+    ids.insert("location", "hobbiton", &location_id);
+    ids.insert("calendar", "shire-reckoning", &calendar_id);
+
+    let mut repository = MockRelationshipRepository::new();
+    repository
+      .expect_is_entity_in_world()
+      .times(2)
+      .return_const(true);
+    repository
+      .expect_create_relationship()
+      .times(1)
+      .returning(|data| {
+        Ok(RelationshipEntity {
+          identity: Identity::default(),
+          data,
+        })
+      });
+
+    let service = new_relationship_service(repository);
+    let imported = service
+      .import_relationships(
+        &Default::default(),
+        vec![relationship_import("frodo", "hobbiton")],
+        &ids,
+      )
+      .unwrap();
+
+    assert_that(&imported).has_length(1);
+    assert_that(&imported[0].data.source).is_equal_to(EntityRef::Character(character_id));
+    assert_that(&imported[0].data.target).is_equal_to(EntityRef::Location(location_id));
+    assert_that(
+      &imported[0]
+        .data
+        .during
+        .as_ref()
+        .map(|during| &during.calendar),
+    )
+    .is_equal_to(Some(&calendar_id));
+  }
+
+  #[test]
+  fn test_import_relationships_unknown_entity() {
+    let mut ids = IdMap::default();
+    ids.insert(
+      "character",
+      "frodo",
+      &"0b2e4c6a-8d1f-4e3a-b5c7-9d1f3b5a7c01",
+    );
+
+    let mut repository = MockRelationshipRepository::new();
+    repository.expect_create_relationship().never();
+
+    let service = new_relationship_service(repository);
+    let imported = service.import_relationships(
+      &Default::default(),
+      vec![relationship_import("frodo", "hobbiton")],
+      &ids,
+    );
+
+    assert_that(&imported)
+      .is_err()
+      .is_equal_to(ImportRelationshipsError::ValidationError(
+        0,
+        vec![RelationshipValidationError::UnknownTarget],
+      ));
+  }
+}
@@ -0,0 +1,121 @@
+use super::RelationshipFilters;
+use crate::model::*;
+#[cfg(test)]
+use mockall::automock;
+use universe_entity::{Page, Pagination};
+use universe_worlds::WorldID;
+
+/// Repository that describes how to access relationship data
+#[cfg_attr(test, automock)]
+pub trait RelationshipRepository {
+  /// Retrieve the relationship that has the given unique ID, as long as it belongs to the given
+  /// world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the relationship belongs to
+  /// * `relationship_id` The ID of the relationship to retrieve
+  ///
+  /// # Returns
+  /// The relationship, or `None` if it wasn't found
+  fn get_relationship_by_id(
+    &self,
+    world_id: &WorldID,
+    relationship_id: &RelationshipID,
+  ) -> Option<RelationshipEntity>;
+
+  /// Retrieve a page of the relationships of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the relationships of
+  /// * `filters` The filters to apply to the relationships
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of relationships
+  fn list_relationships(
+    &self,
+    world_id: &WorldID,
+    filters: RelationshipFilters,
+    pagination: Pagination,
+  ) -> Page<RelationshipEntity>;
+
+  /// Retrieve every relationship of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the relationships of
+  ///
+  /// # Returns
+  /// The relationships
+  fn list_all_relationships(&self, world_id: &WorldID) -> Vec<RelationshipEntity>;
+
+  /// Retrieve every entity of a world that takes part in at least one relationship
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the entities of
+  ///
+  /// # Returns
+  /// The entities, along with their names
+  fn list_related_entities(&self, world_id: &WorldID) -> Vec<GraphNode>;
+
+  /// Check whether an entity is part of the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  /// * `entity` The entity to look for
+  ///
+  /// # Returns
+  /// True if the entity exists and belongs to the world
+  fn is_entity_in_world(&self, world_id: &WorldID, entity: &EntityRef) -> bool;
+
+  /// Create a new relationship record in the data store
+  ///
+  /// # Arguments
+  /// * `relationship` The relationship details to persist to the data store
+  ///
+  /// # Returns
+  /// The relationship that was persisted
+  fn create_relationship(
+    &self,
+    relationship: RelationshipData,
+  ) -> Result<RelationshipEntity, PersistRelationshipError>;
+
+  /// Update an existing relationship record in the data store
+  ///
+  /// # Arguments
+  /// * `relationship` The relationship details to persist to the data store
+  ///
+  /// # Returns
+  /// The relationship that was persisted
+  fn update_relationship(
+    &self,
+    relationship: RelationshipEntity,
+  ) -> Result<RelationshipEntity, PersistRelationshipError>;
+
+  /// Delete an existing relationship record from the data store
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the relationship belongs to
+  /// * `relationship_id` The ID of the relationship to delete
+  fn delete_relationship(
+    &self,
+    world_id: &WorldID,
+    relationship_id: &RelationshipID,
+  ) -> Result<(), PersistRelationshipError>;
+}
+
+/// Errors that can occur when persisting a relationship record
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum PersistRelationshipError {
+  #[error("The world was not found")]
+  WorldNotFound,
+  #[error("The relationship was not found")]
+  RelationshipNotFound,
+  #[error("An entity of the relationship was not found")]
+  EntityNotFound,
+  #[error("The calendar of the relationship dates was not found")]
+  CalendarNotFound,
+  #[error("The version of the record did not match")]
+  OptimisticLockFailure,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
@@ -0,0 +1,202 @@
+use super::RelationshipFilters;
+use crate::model::*;
+use universe_entity::{IdMap, Page, Pagination};
+use universe_worlds::WorldID;
+
+/// The Relationship Service to allow interactions with the relationships between entities
+pub trait RelationshipService: Send + Sync {
+  /// Retrieve the relationship that has the given unique ID, as long as it belongs to the given
+  /// world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the relationship belongs to
+  /// * `relationship_id` The ID of the relationship to retrieve
+  ///
+  /// # Returns
+  /// The relationship, or `None` if it wasn't found
+  fn get_relationship_by_id(
+    &self,
+    world_id: &WorldID,
+    relationship_id: &RelationshipID,
+  ) -> Option<RelationshipEntity>;
+
+  /// Retrieve a page of the relationships of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the relationships of
+  /// * `filters` The filters to apply to the relationships
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of relationships
+  fn list_relationships(
+    &self,
+    world_id: &WorldID,
+    filters: RelationshipFilters,
+    pagination: Pagination,
+  ) -> Page<RelationshipEntity>;
+
+  /// Retrieve every relationship of a world, oldest first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the relationships of
+  ///
+  /// # Returns
+  /// The relationships
+  fn list_all_relationships(&self, world_id: &WorldID) -> Vec<RelationshipEntity>;
+
+  /// Load the graph of every relationship of a world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to load the graph of
+  ///
+  /// # Returns
+  /// The graph
+  fn load_graph(&self, world_id: &WorldID) -> Graph;
+
+  /// Create a new relationship
+  ///
+  /// # Arguments
+  /// * `relationship` The relationship data to create the relationship from
+  ///
+  /// # Returns
+  /// The relationship that was persisted
+  fn create_relationship(
+    &self,
+    relationship: RelationshipData,
+  ) -> Result<RelationshipEntity, CreateRelationshipError>;
+
+  /// Update an existing relationship.
+  ///
+  /// This will load the relationship by ID, and then call a provided callback to mutate the
+  /// relationship before persisting the changes back to the database.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the relationship belongs to
+  /// * `relationship_id` The ID of the relationship to update
+  /// * `updater` The callback to mutate the relationship with
+  ///
+  /// # Returns
+  /// The newly updated relationship
+  fn update_relationship(
+    &self,
+    world_id: &WorldID,
+    relationship_id: &RelationshipID,
+    updater: &mut dyn FnMut(
+      RelationshipData,
+    ) -> Result<RelationshipData, Box<dyn std::error::Error>>,
+  ) -> Result<RelationshipEntity, UpdateRelationshipError>;
+
+  /// Delete a relationship
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world that the relationship belongs to
+  /// * `relationship_id` The ID of the relationship to delete
+  fn delete_relationship(
+    &self,
+    world_id: &WorldID,
+    relationship_id: &RelationshipID,
+  ) -> Result<(), DeleteRelationshipError>;
+
+  /// Create a set of relationships in a world.
+  ///
+  /// The entities at either end of the relationships, and the calendars that they are dated in,
+  /// are referred to by the keys that those were known by before they were imported themselves,
+  /// so they must already have been recorded in `ids`.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to create the relationships in
+  /// * `relationships` The relationships to create
+  /// * `ids` The map to look up the entities and calendars of the relationships in
+  ///
+  /// # Returns
+  /// The relationships that were created
+  fn import_relationships(
+    &self,
+    world_id: &WorldID,
+    relationships: Vec<RelationshipImport>,
+    ids: &IdMap,
+  ) -> Result<Vec<RelationshipEntity>, ImportRelationshipsError>;
+
+  /// Copy every relationship of one world into another.
+  ///
+  /// Everything that the relationships refer to must already have been copied and recorded in
+  /// `ids`.
+  ///
+  /// # Arguments
+  /// * `from` The ID of the world to copy the relationships from
+  /// * `to` The ID of the world to copy the relationships into
+  /// * `ids` The map to look up the entities and calendars of the relationships in
+  ///
+  /// # Returns
+  /// The relationships that were created
+  fn copy_relationships(
+    &self,
+    from: &WorldID,
+    to: &WorldID,
+    ids: &IdMap,
+  ) -> Result<Vec<RelationshipEntity>, ImportRelationshipsError>;
+}
+
+/// Enumeration of validation errors for a relationship
+#[derive(Debug, PartialEq)]
+pub enum RelationshipValidationError {
+  /// The source of the relationship isn't part of the world
+  UnknownSource,
+  /// The target of the relationship isn't part of the world
+  UnknownTarget,
+  /// The source and target of the relationship are the same entity
+  SameEntity,
+}
+
+/// Enumeration of reasons why we failed to create a new relationship
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum CreateRelationshipError {
+  #[error("Relationship details were invalid: {0:?}")]
+  ValidationError(Vec<RelationshipValidationError>),
+  #[error("The world was not found")]
+  UnknownWorld,
+  #[error("The calendar of the relationship dates was not found")]
+  UnknownCalendar,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to update an existing relationship
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateRelationshipError {
+  #[error("Relationship details were invalid: {0:?}")]
+  ValidationError(Vec<RelationshipValidationError>),
+  #[error("The relationship was not found")]
+  UnknownRelationship,
+  #[error("The calendar of the relationship dates was not found")]
+  UnknownCalendar,
+  #[error("The version of the relationship record did not match")]
+  OptimisticLockFailure,
+  #[error("An error occurred updating the relationship details: {0}")]
+  UpdateError(Box<dyn std::error::Error>),
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to delete a relationship
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum DeleteRelationshipError {
+  #[error("The relationship was not found")]
+  UnknownRelationship,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to import a set of relationships
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum ImportRelationshipsError {
+  #[error("Relationship {0} was invalid: {1:?}")]
+  ValidationError(usize, Vec<RelationshipValidationError>),
+  #[error("The calendar of the dates of relationship {0} was not found")]
+  UnknownCalendar(usize),
+  #[error("The world was not found")]
+  UnknownWorld,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
@@ -0,0 +1,62 @@
+use crate::sniff_content_type;
+use std::io::Read;
+
+/// A blob of binary data, along with the type of content that it contains
+#[derive(Debug, PartialEq, Clone)]
+pub struct Blob {
+  /// The MIME type of the data
+  pub content_type: String,
+  /// The actual data
+  pub data: Vec<u8>,
+}
+
+impl Blob {
+  /// Construct a blob for some data, working out the content type from the data itself
+  ///
+  /// # Arguments
+  /// * `data` The data of the blob
+  ///
+  /// # Returns
+  /// The blob
+  pub fn sniffed(data: Vec<u8>) -> Self {
+    Blob {
+      content_type: sniff_content_type(&data).to_owned(),
+      data,
+    }
+  }
+}
+
+/// A blob that is being read from a store, rather than having been read into memory up front
+pub struct BlobStream {
+  /// The MIME type of the data
+  pub content_type: String,
+  /// The length of the data, in bytes
+  pub length: u64,
+  /// The reader to read the data from
+  pub reader: Box<dyn Read + Send>,
+}
+
+impl std::fmt::Debug for BlobStream {
+  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    f.debug_struct("BlobStream")
+      .field("content_type", &self.content_type)
+      .field("length", &self.length)
+      .finish()
+  }
+}
+
+impl BlobStream {
+  /// Read the entire stream into memory
+  ///
+  /// # Returns
+  /// The blob that was streamed
+  pub fn into_blob(mut self) -> Result<Blob, std::io::Error> {
+    let mut data = Vec::with_capacity(self.length as usize);
+    self.reader.read_to_end(&mut data)?;
+
+    Ok(Blob {
+      content_type: self.content_type,
+      data,
+    })
+  }
+}
@@ -0,0 +1,28 @@
+use crate::BlobStore;
+use std::sync::Arc;
+use universe_health::Healthcheck;
+
+/// Healthcheck for a shared Blob Store, so that the same store can be both used by the service
+/// and registered with the healthchecks
+pub struct StorageHealthcheck {
+  store: Arc<dyn BlobStore>,
+}
+
+impl StorageHealthcheck {
+  /// Create a new healthcheck for the given Blob Store
+  ///
+  /// # Arguments
+  /// * `store` The Blob Store to check the health of
+  ///
+  /// # Returns
+  /// The healthcheck
+  pub fn new(store: Arc<dyn BlobStore>) -> Self {
+    StorageHealthcheck { store }
+  }
+}
+
+impl Healthcheck for StorageHealthcheck {
+  fn check_health(&self) -> Result<String, String> {
+    self.store.check_health()
+  }
+}
@@ -0,0 +1,19 @@
+mod blob;
+mod health;
+mod local;
+mod memory;
+mod quota;
+mod s3;
+mod sniff;
+mod store;
+mod url_signer;
+
+pub use blob::*;
+pub use health::*;
+pub use local::*;
+pub use memory::*;
+pub use quota::*;
+pub use s3::*;
+pub use sniff::*;
+pub use store::*;
+pub use url_signer::*;
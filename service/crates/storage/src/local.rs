@@ -0,0 +1,397 @@
+use crate::{
+  parse_key, Blob, BlobStore, BlobStream, StorageError, UrlSigner, DEFAULT_CONTENT_TYPE,
+};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tracing::debug;
+use universe_health::Healthcheck;
+
+/// Blob Store that keeps the blobs on the local filesystem.
+///
+/// The data for each blob is stored in a file under `<root>/data`, and the content type of the
+/// blob in a file with the same path under `<root>/meta`. Files are written under `<root>/tmp`
+/// first and then moved into place. The filesystem can't serve the blobs itself, so signed URLs
+/// are generated by a `UrlSigner` and must be served by the application.
+pub struct LocalBlobStore {
+  root: PathBuf,
+  url_signer: UrlSigner,
+}
+
+impl LocalBlobStore {
+  /// Create a new Blob Store that keeps its blobs under the given directory. The directory is
+  /// created when the first blob is stored if it doesn't already exist
+  ///
+  /// # Arguments
+  /// * `root` The directory to store blobs under
+  /// * `url_signer` The means to generate signed URLs for blobs
+  ///
+  /// # Returns
+  /// The Blob Store
+  pub fn new<P: Into<PathBuf>>(root: P, url_signer: UrlSigner) -> Self {
+    LocalBlobStore {
+      root: root.into(),
+      url_signer,
+    }
+  }
+
+  /// Build the paths to the data and metadata files for the given key
+  fn paths(&self, key: &str) -> Result<(PathBuf, PathBuf), StorageError> {
+    let segments = parse_key(key)?;
+
+    let data = segments
+      .iter()
+      .fold(self.root.join("data"), |path, segment| path.join(segment));
+    let meta = segments
+      .iter()
+      .fold(self.root.join("meta"), |path, segment| path.join(segment));
+
+    Ok((data, meta))
+  }
+
+  /// Write a file by writing to a temporary file and then renaming it into place, so that readers
+  /// never see a partially written file.
+  ///
+  /// The temporary files live in a directory of their own so that they can never be mistaken for
+  /// blobs, whatever their keys look like.
+  fn write_file(&self, path: &Path, contents: &[u8]) -> Result<(), std::io::Error> {
+    static NEXT_TEMP_FILE: AtomicUsize = AtomicUsize::new(0);
+
+    if let Some(parent) = path.parent() {
+      fs::create_dir_all(parent)?;
+    }
+    let temp_dir = self.root.join("tmp");
+    fs::create_dir_all(&temp_dir)?;
+
+    let temp = temp_dir.join(format!(
+      "{}-{}",
+      std::process::id(),
+      NEXT_TEMP_FILE.fetch_add(1, Ordering::SeqCst)
+    ));
+    fs::write(&temp, contents)?;
+    fs::rename(&temp, path)
+  }
+}
+
+/// Remove a file, treating a file that doesn't exist as having been removed
+fn remove_file(path: &Path) -> Result<(), std::io::Error> {
+  match fs::remove_file(path) {
+    Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+    result => result,
+  }
+}
+
+/// Work out the total size of every file under a path
+fn total_size(path: &Path) -> Result<u64, std::io::Error> {
+  let metadata = match fs::metadata(path) {
+    Err(e) if e.kind() == ErrorKind::NotFound => return Ok(0),
+    result => result?,
+  };
+
+  if metadata.is_dir() {
+    fs::read_dir(path)?.try_fold(0, |total, entry| Ok(total + total_size(&entry?.path())?))
+  } else {
+    Ok(metadata.len())
+  }
+}
+
+/// Find the keys of every file under a path
+fn list_files(path: &Path, key: &str, keys: &mut Vec<String>) -> Result<(), std::io::Error> {
+  let metadata = match fs::metadata(path) {
+    Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+    result => result?,
+  };
+
+  if metadata.is_dir() {
+    for entry in fs::read_dir(path)? {
+      let entry = entry?;
+      let name = entry.file_name();
+      list_files(
+        &entry.path(),
+        &format!("{}/{}", key, name.to_string_lossy()),
+        keys,
+      )?;
+    }
+  } else {
+    keys.push(key.to_owned());
+  }
+
+  Ok(())
+}
+
+impl BlobStore for LocalBlobStore {
+  fn put(&self, key: &str, blob: &Blob) -> Result<(), StorageError> {
+    let (data, meta) = self.paths(key)?;
+    debug!("Storing blob {} at {:?}", key, data);
+
+    self.write_file(&data, &blob.data)?;
+    self.write_file(&meta, blob.content_type.as_bytes())?;
+
+    Ok(())
+  }
+
+  fn stream(&self, key: &str) -> Result<Option<BlobStream>, StorageError> {
+    let (data, meta) = self.paths(key)?;
+
+    let file = match fs::File::open(&data) {
+      Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+      result => result?,
+    };
+    let content_type = match fs::read_to_string(&meta) {
+      Err(e) if e.kind() == ErrorKind::NotFound => DEFAULT_CONTENT_TYPE.to_owned(),
+      result => result?,
+    };
+
+    Ok(Some(BlobStream {
+      content_type,
+      length: file.metadata()?.len(),
+      reader: Box::new(file),
+    }))
+  }
+
+  fn delete(&self, key: &str) -> Result<(), StorageError> {
+    let (data, meta) = self.paths(key)?;
+    debug!("Deleting blob {} from {:?}", key, data);
+
+    remove_file(&data)?;
+    remove_file(&meta)?;
+
+    Ok(())
+  }
+
+  fn signed_url(&self, key: &str, expires_in: Duration) -> Result<String, StorageError> {
+    self.url_signer.sign(key, expires_in)
+  }
+
+  fn usage(&self, prefix: &str) -> Result<u64, StorageError> {
+    let (data, _) = self.paths(prefix)?;
+
+    Ok(total_size(&data)?)
+  }
+
+  fn list(&self, prefix: &str) -> Result<Vec<String>, StorageError> {
+    let (data, _) = self.paths(prefix)?;
+
+    let mut keys = vec![];
+    list_files(&data, prefix, &mut keys)?;
+    keys.sort();
+    Ok(keys)
+  }
+}
+
+impl Healthcheck for LocalBlobStore {
+  /// Check the health of the filesystem storage.
+  /// This works by making sure that the data directory exists and that we are able to write to it
+  ///
+  /// # Returns
+  /// The result of the healthcheck
+  fn check_health(&self) -> Result<String, String> {
+    let data = self.root.join("data");
+    fs::create_dir_all(&data).map_err(|e| format!("Error creating storage directory: {}", e))?;
+
+    let metadata =
+      fs::metadata(&data).map_err(|e| format!("Error accessing storage directory: {}", e))?;
+    if metadata.permissions().readonly() {
+      return Err("Storage directory is read-only".to_owned());
+    }
+
+    Ok("Ok".to_owned())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use assert_matches::assert_matches;
+  use spectral::prelude::*;
+
+  /// Wrapper around a Local Blob Store in a temporary directory that is removed afterwards
+  struct TestStore {
+    root: PathBuf,
+    store: LocalBlobStore,
+  }
+
+  impl TestStore {
+    fn new() -> Self {
+      let root = std::env::temp_dir().join(format!("universe-storage-{}", uuid::Uuid::new_v4()));
+      let store = LocalBlobStore::new(&root, UrlSigner::new("/storage", "secret"));
+      TestStore { root, store }
+    }
+  }
+
+  impl Drop for TestStore {
+    fn drop(&mut self) {
+      let _ = fs::remove_dir_all(&self.root);
+    }
+  }
+
+  fn blob(content_type: &str, data: &[u8]) -> Blob {
+    Blob {
+      content_type: content_type.to_owned(),
+      data: data.to_vec(),
+    }
+  }
+
+  #[test]
+  fn test_get_unknown_blob() {
+    let test = TestStore::new();
+
+    let result = test.store.get("unknown/blob");
+    assert_that(&result.unwrap()).is_none();
+  }
+
+  #[test]
+  fn test_put_and_get_blob() {
+    let test = TestStore::new();
+
+    test
+      .store
+      .put("worlds/1234/cover", &blob("image/png", b"Hello"))
+      .unwrap();
+
+    let result = test.store.get("worlds/1234/cover");
+    assert_that(&result.unwrap()).contains_value(blob("image/png", b"Hello"));
+  }
+
+  #[test]
+  fn test_replace_blob() {
+    let test = TestStore::new();
+
+    test
+      .store
+      .put("cover", &blob("image/png", b"Hello"))
+      .unwrap();
+    test
+      .store
+      .put("cover", &blob("image/jpeg", b"World"))
+      .unwrap();
+
+    let result = test.store.get("cover");
+    assert_that(&result.unwrap()).contains_value(blob("image/jpeg", b"World"));
+  }
+
+  #[test]
+  fn test_delete_blob() {
+    let test = TestStore::new();
+
+    test
+      .store
+      .put("cover", &blob("image/png", b"Hello"))
+      .unwrap();
+    test.store.delete("cover").unwrap();
+
+    let result = test.store.get("cover");
+    assert_that(&result.unwrap()).is_none();
+  }
+
+  #[test]
+  fn test_delete_unknown_blob() {
+    let test = TestStore::new();
+
+    let result = test.store.delete("cover");
+    assert_that(&result.is_ok()).is_true();
+  }
+
+  #[test]
+  fn test_stream_blob() {
+    let test = TestStore::new();
+
+    test
+      .store
+      .put("cover", &blob("image/png", b"Hello"))
+      .unwrap();
+
+    let stream = test.store.stream("cover").unwrap().unwrap();
+    assert_that(&stream.content_type).is_equal_to("image/png".to_owned());
+    assert_that(&stream.length).is_equal_to(5);
+    assert_that(&stream.into_blob().unwrap()).is_equal_to(blob("image/png", b"Hello"));
+  }
+
+  #[test]
+  fn test_usage() {
+    let test = TestStore::new();
+
+    test
+      .store
+      .put("users/1234/avatar", &blob("image/png", b"Hello"))
+      .unwrap();
+    test
+      .store
+      .put("users/1234/files/notes", &blob("text/plain", b"World!"))
+      .unwrap();
+    test
+      .store
+      .put("users/12345/avatar", &blob("image/png", b"Other"))
+      .unwrap();
+
+    assert_that(&test.store.usage("users/1234").unwrap()).is_equal_to(11);
+    assert_that(&test.store.usage("users/1234/avatar").unwrap()).is_equal_to(5);
+    assert_that(&test.store.usage("users/9999").unwrap()).is_equal_to(0);
+  }
+
+  #[test]
+  fn test_list_and_delete_all() {
+    let test = TestStore::new();
+
+    for key in &["users/1234/avatar", "users/1234/files/notes", "users/12345/avatar"] {
+      test
+        .store
+        .put(key, &blob("text/plain", b"Hello"))
+        .unwrap();
+    }
+
+    assert_that(&test.store.list("users/1234").unwrap()).is_equal_to(vec![
+      "users/1234/avatar".to_owned(),
+      "users/1234/files/notes".to_owned(),
+    ]);
+    assert_that(&test.store.list("users/9999").unwrap()).is_empty();
+
+    test.store.delete_all("users/1234").unwrap();
+    assert_that(&test.store.list("users/1234").unwrap()).is_empty();
+    assert_that(&test.store.get("users/12345/avatar").unwrap()).is_some();
+  }
+
+  #[test]
+  fn test_keys_that_look_temporary() {
+    let test = TestStore::new();
+
+    test
+      .store
+      .put("users/1234/files/notes.tmp", &blob("text/plain", b"Hello"))
+      .unwrap();
+
+    assert_that(&test.store.usage("users/1234").unwrap()).is_equal_to(5);
+    assert_that(&test.store.list("users/1234").unwrap())
+      .is_equal_to(vec!["users/1234/files/notes.tmp".to_owned()]);
+  }
+
+  #[test]
+  fn test_signed_url() {
+    let test = TestStore::new();
+
+    let url = test
+      .store
+      .signed_url("users/1234/avatar", Duration::from_secs(60))
+      .unwrap();
+    assert_that(&url.starts_with("/storage/users/1234/avatar?expires=")).is_true();
+  }
+
+  #[test]
+  fn test_healthcheck() {
+    let test = TestStore::new();
+
+    let result = test.store.check_health();
+    assert_that(&result).is_ok();
+    assert_that(&test.root.join("data").is_dir()).is_true();
+  }
+
+  #[test]
+  fn test_invalid_key() {
+    let test = TestStore::new();
+
+    let result = test.store.put("../cover", &blob("image/png", b"Hello"));
+    assert_matches!(result, Err(StorageError::InvalidKey(_)));
+  }
+}
@@ -0,0 +1,148 @@
+use crate::{parse_key, Blob, BlobStore, BlobStream, StorageError};
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::RwLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use universe_health::Healthcheck;
+
+/// Blob Store that keeps the blobs in memory. Nothing is persisted, so this is only really useful
+/// for tests
+#[derive(Default)]
+pub struct MemoryBlobStore {
+  blobs: RwLock<HashMap<String, Blob>>,
+}
+
+impl MemoryBlobStore {
+  /// Get the keys of every blob that is currently stored, in sorted order
+  pub fn keys(&self) -> Vec<String> {
+    let mut keys: Vec<String> = self.blobs.read().unwrap().keys().cloned().collect();
+    keys.sort();
+    keys
+  }
+}
+
+impl BlobStore for MemoryBlobStore {
+  fn put(&self, key: &str, blob: &Blob) -> Result<(), StorageError> {
+    parse_key(key)?;
+    self
+      .blobs
+      .write()
+      .unwrap()
+      .insert(key.to_owned(), blob.clone());
+    Ok(())
+  }
+
+  fn get(&self, key: &str) -> Result<Option<Blob>, StorageError> {
+    parse_key(key)?;
+    Ok(self.blobs.read().unwrap().get(key).cloned())
+  }
+
+  fn stream(&self, key: &str) -> Result<Option<BlobStream>, StorageError> {
+    Ok(self.get(key)?.map(|blob| BlobStream {
+      content_type: blob.content_type,
+      length: blob.data.len() as u64,
+      reader: Box::new(Cursor::new(blob.data)),
+    }))
+  }
+
+  fn delete(&self, key: &str) -> Result<(), StorageError> {
+    parse_key(key)?;
+    self.blobs.write().unwrap().remove(key);
+    Ok(())
+  }
+
+  fn signed_url(&self, key: &str, expires_in: Duration) -> Result<String, StorageError> {
+    parse_key(key)?;
+
+    // Nothing can serve these URLs, so there's no point in actually signing them
+    let expires = SystemTime::now() + expires_in;
+    let expires = expires.duration_since(UNIX_EPOCH).unwrap_or_default();
+    Ok(format!("memory:{}?expires={}", key, expires.as_secs()))
+  }
+
+  fn usage(&self, prefix: &str) -> Result<u64, StorageError> {
+    parse_key(prefix)?;
+    let nested = format!("{}/", prefix);
+
+    Ok(
+      self
+        .blobs
+        .read()
+        .unwrap()
+        .iter()
+        .filter(|(key, _)| *key == prefix || key.starts_with(&nested))
+        .map(|(_, blob)| blob.data.len() as u64)
+        .sum(),
+    )
+  }
+
+  fn list(&self, prefix: &str) -> Result<Vec<String>, StorageError> {
+    parse_key(prefix)?;
+    let nested = format!("{}/", prefix);
+
+    Ok(
+      self
+        .keys()
+        .into_iter()
+        .filter(|key| *key == prefix || key.starts_with(&nested))
+        .collect(),
+    )
+  }
+}
+
+impl Healthcheck for MemoryBlobStore {
+  fn check_health(&self) -> Result<String, String> {
+    Ok("Ok".to_owned())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use spectral::prelude::*;
+
+  fn blob(data: &[u8]) -> Blob {
+    Blob {
+      content_type: "text/plain".to_owned(),
+      data: data.to_vec(),
+    }
+  }
+
+  #[test]
+  fn test_usage() {
+    let store = MemoryBlobStore::default();
+    store.put("users/1234/avatar", &blob(b"Hello")).unwrap();
+    store.put("users/1234/files/a", &blob(b"World!")).unwrap();
+    store.put("users/12345/avatar", &blob(b"Other")).unwrap();
+
+    assert_that(&store.usage("users/1234").unwrap()).is_equal_to(11);
+    assert_that(&store.usage("users/1234/avatar").unwrap()).is_equal_to(5);
+    assert_that(&store.usage("users/9999").unwrap()).is_equal_to(0);
+  }
+
+  #[test]
+  fn test_list_and_delete_all() {
+    let store = MemoryBlobStore::default();
+    store.put("users/1234/avatar", &blob(b"Hello")).unwrap();
+    store.put("users/1234/files/a", &blob(b"World!")).unwrap();
+    store.put("users/12345/avatar", &blob(b"Other")).unwrap();
+
+    assert_that(&store.list("users/1234").unwrap()).is_equal_to(vec![
+      "users/1234/avatar".to_owned(),
+      "users/1234/files/a".to_owned(),
+    ]);
+
+    store.delete_all("users/1234").unwrap();
+    assert_that(&store.keys()).is_equal_to(vec!["users/12345/avatar".to_owned()]);
+  }
+
+  #[test]
+  fn test_stream() {
+    let store = MemoryBlobStore::default();
+    store.put("users/1234/avatar", &blob(b"Hello")).unwrap();
+
+    let stream = store.stream("users/1234/avatar").unwrap().unwrap();
+    assert_that(&stream.length).is_equal_to(5);
+    assert_that(&stream.into_blob().unwrap()).is_equal_to(blob(b"Hello"));
+  }
+}
@@ -0,0 +1,275 @@
+use crate::{parse_key, Blob, BlobStore, BlobStream, StorageError};
+use std::sync::Arc;
+use std::time::Duration;
+use universe_health::Healthcheck;
+
+/// Source of who owns the blobs that aren't stored under `users/<user>/`
+pub trait BlobOwners: Send + Sync {
+  /// Work out which user a blob belongs to, if any
+  ///
+  /// # Arguments
+  /// * `key` The key of the blob
+  ///
+  /// # Returns
+  /// The user that the blob belongs to
+  fn owner(&self, key: &str) -> Option<String>;
+
+  /// List the prefixes that the blobs of a user are stored under, other than `users/<user>/`
+  ///
+  /// # Arguments
+  /// * `owner` The user to list the prefixes for
+  ///
+  /// # Returns
+  /// The prefixes
+  fn prefixes(&self, owner: &str) -> Vec<String>;
+}
+
+/// Blob Store that wraps another one, limiting how much data each user may store in it.
+///
+/// Blobs belong to a user if they are stored under `users/<user>/`, or if the `BlobOwners` given
+/// to the store says that they do, and the total size of these may not go over the quota. Blobs
+/// that don't belong to anyone don't count towards any quota. The check is made before the blob
+/// is stored, so two uploads for the same user at the same time may between them take the user
+/// slightly over their quota.
+pub struct QuotaBlobStore {
+  inner: Arc<dyn BlobStore>,
+  quota: u64,
+  owners: Option<Arc<dyn BlobOwners>>,
+}
+
+impl QuotaBlobStore {
+  /// Create a new Blob Store that enforces quotas on another one
+  ///
+  /// # Arguments
+  /// * `inner` The Blob Store to actually store blobs in
+  /// * `quota` The maximum number of bytes that each user may store
+  ///
+  /// # Returns
+  /// The Blob Store
+  pub fn new(inner: Arc<dyn BlobStore>, quota: u64) -> Self {
+    QuotaBlobStore {
+      inner,
+      quota,
+      owners: None,
+    }
+  }
+
+  /// Charge blobs stored outside of `users/` to the users that own them
+  ///
+  /// # Arguments
+  /// * `owners` The source of who owns each blob
+  ///
+  /// # Returns
+  /// The Blob Store
+  pub fn with_owners(self, owners: Arc<dyn BlobOwners>) -> Self {
+    QuotaBlobStore {
+      owners: Some(owners),
+      ..self
+    }
+  }
+
+  /// Work out how much space a user is currently using
+  ///
+  /// # Arguments
+  /// * `owner` The user to work out the usage of
+  ///
+  /// # Returns
+  /// The total size in bytes of every blob belonging to the user
+  fn owner_usage(&self, owner: &str) -> Result<u64, StorageError> {
+    let mut usage = self.inner.usage(&format!("users/{}", owner))?;
+    if let Some(owners) = &self.owners {
+      for prefix in owners.prefixes(owner) {
+        usage += self.inner.usage(&prefix)?;
+      }
+    }
+
+    Ok(usage)
+  }
+}
+
+/// Work out which user a blob belongs to, if any
+///
+/// # Arguments
+/// * `key` The key of the blob
+///
+/// # Returns
+/// The user that the blob belongs to
+fn owner(key: &str) -> Result<Option<&str>, StorageError> {
+  match parse_key(key)?.as_slice() {
+    ["users", user, _, ..] => Ok(Some(*user)),
+    _ => Ok(None),
+  }
+}
+
+impl BlobStore for QuotaBlobStore {
+  fn put(&self, key: &str, blob: &Blob) -> Result<(), StorageError> {
+    let owner = match owner(key)? {
+      Some(user) => Some(user.to_owned()),
+      None => self.owners.as_ref().and_then(|owners| owners.owner(key)),
+    };
+
+    if let Some(user) = owner {
+      let usage = self.owner_usage(&user)?;
+      // Replacing a blob frees up the space used by the old one
+      let replaced = self
+        .inner
+        .stream(key)?
+        .map(|stream| stream.length)
+        .unwrap_or(0);
+
+      if usage - replaced.min(usage) + blob.data.len() as u64 > self.quota {
+        return Err(StorageError::QuotaExceeded {
+          owner: user,
+          quota: self.quota,
+        });
+      }
+    }
+
+    self.inner.put(key, blob)
+  }
+
+  fn get(&self, key: &str) -> Result<Option<Blob>, StorageError> {
+    self.inner.get(key)
+  }
+
+  fn stream(&self, key: &str) -> Result<Option<BlobStream>, StorageError> {
+    self.inner.stream(key)
+  }
+
+  fn delete(&self, key: &str) -> Result<(), StorageError> {
+    self.inner.delete(key)
+  }
+
+  fn signed_url(&self, key: &str, expires_in: Duration) -> Result<String, StorageError> {
+    self.inner.signed_url(key, expires_in)
+  }
+
+  fn usage(&self, prefix: &str) -> Result<u64, StorageError> {
+    self.inner.usage(prefix)
+  }
+
+  fn list(&self, prefix: &str) -> Result<Vec<String>, StorageError> {
+    self.inner.list(prefix)
+  }
+}
+
+impl Healthcheck for QuotaBlobStore {
+  fn check_health(&self) -> Result<String, String> {
+    self.inner.check_health()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::MemoryBlobStore;
+  use assert_matches::assert_matches;
+  use rstest::rstest;
+  use spectral::prelude::*;
+
+  fn blob(size: usize) -> Blob {
+    Blob {
+      content_type: "application/octet-stream".to_owned(),
+      data: vec![0; size],
+    }
+  }
+
+  fn build_store() -> (Arc<MemoryBlobStore>, QuotaBlobStore) {
+    let inner = Arc::new(MemoryBlobStore::default());
+    inner.put("users/1234/existing", &blob(60)).unwrap();
+    inner.put("users/5678/existing", &blob(90)).unwrap();
+
+    let store = QuotaBlobStore::new(inner.clone(), 100);
+    (inner, store)
+  }
+
+  #[rstest(
+    input,
+    expected,
+    case("users/1234/avatar", Some("1234")),
+    case("users/1234/files/notes", Some("1234")),
+    case("users/1234", None),
+    case("worlds/1234/cover", None)
+  )]
+  fn test_owner(input: &str, expected: Option<&str>) {
+    assert_that(&owner(input).unwrap()).is_equal_to(expected);
+  }
+
+  #[test]
+  fn test_put_within_quota() {
+    let (inner, store) = build_store();
+
+    let result = store.put("users/1234/avatar", &blob(40));
+    assert_that(&result.is_ok()).is_true();
+    assert_that(&inner.usage("users/1234").unwrap()).is_equal_to(100);
+  }
+
+  #[test]
+  fn test_put_over_quota() {
+    let (inner, store) = build_store();
+
+    let result = store.put("users/1234/avatar", &blob(41));
+    assert_matches!(result, Err(StorageError::QuotaExceeded { owner, quota: 100 }) if owner == "1234");
+    assert_that(&inner.get("users/1234/avatar").unwrap()).is_none();
+  }
+
+  #[test]
+  fn test_replace_within_quota() {
+    let (inner, store) = build_store();
+
+    let result = store.put("users/5678/existing", &blob(100));
+    assert_that(&result.is_ok()).is_true();
+    assert_that(&inner.usage("users/5678").unwrap()).is_equal_to(100);
+  }
+
+  /// Blob owners that say every world belongs to user 1234
+  struct TestWorldOwners;
+
+  impl BlobOwners for TestWorldOwners {
+    fn owner(&self, key: &str) -> Option<String> {
+      Some("1234".to_owned()).filter(|_| key.starts_with("worlds/"))
+    }
+
+    fn prefixes(&self, owner: &str) -> Vec<String> {
+      match owner {
+        "1234" => vec!["worlds/abcd".to_owned(), "worlds/efgh".to_owned()],
+        _ => vec![],
+      }
+    }
+  }
+
+  #[test]
+  fn test_put_with_owners_within_quota() {
+    let (inner, store) = build_store();
+    let store = store.with_owners(Arc::new(TestWorldOwners));
+    inner.put("worlds/abcd/cover", &blob(20)).unwrap();
+
+    let result = store.put("worlds/efgh/cover", &blob(20));
+    assert_that(&result.is_ok()).is_true();
+    assert_that(&inner.usage("worlds/efgh").unwrap()).is_equal_to(20);
+  }
+
+  #[test]
+  fn test_put_with_owners_over_quota() {
+    let (inner, store) = build_store();
+    let store = store.with_owners(Arc::new(TestWorldOwners));
+    inner.put("worlds/abcd/cover", &blob(20)).unwrap();
+
+    let result = store.put("worlds/efgh/cover", &blob(21));
+    assert_matches!(result, Err(StorageError::QuotaExceeded { owner, quota: 100 }) if owner == "1234");
+    assert_that(&inner.get("worlds/efgh/cover").unwrap()).is_none();
+
+    // Blobs belonging to a world count against the quota for the user's own blobs too
+    let result = store.put("users/1234/avatar", &blob(21));
+    assert_matches!(result, Err(StorageError::QuotaExceeded { owner, quota: 100 }) if owner == "1234");
+  }
+
+  #[test]
+  fn test_put_without_owner() {
+    let (inner, store) = build_store();
+
+    let result = store.put("worlds/1234/cover", &blob(1000));
+    assert_that(&result.is_ok()).is_true();
+    assert_that(&inner.usage("worlds/1234").unwrap()).is_equal_to(1000);
+  }
+}
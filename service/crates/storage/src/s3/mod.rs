@@ -0,0 +1,5 @@
+mod signing;
+mod store;
+mod tls;
+
+pub use store::*;
@@ -0,0 +1,566 @@
+use super::signing::{hex_sha256, uri_encode, CanonicalRequest, Credentials};
+use super::tls::OpensslClient;
+use crate::{parse_key, Blob, BlobStore, BlobStream, StorageError, DEFAULT_CONTENT_TYPE};
+use hyper::{
+  client::{pool::Pool, Client, Response},
+  header::{ContentLength, ContentType, Headers, Host},
+  method::Method,
+  net::HttpsConnector,
+  status::StatusCode,
+  Url,
+};
+use std::io::Read;
+use std::time::Duration;
+use tracing::{debug, warn};
+use universe_health::Healthcheck;
+
+/// The configuration needed to connect to an S3-compatible store
+#[derive(Debug, Clone)]
+pub struct S3Config {
+  /// The base URL of the store, such as `https://s3.eu-west-2.amazonaws.com` or
+  /// `http://localhost:9000`
+  pub endpoint: String,
+  /// The region that the bucket is in
+  pub region: String,
+  /// The bucket to store blobs in. This must already exist
+  pub bucket: String,
+  /// The Access Key ID to authenticate with
+  pub access_key_id: String,
+  /// The Secret Access Key to authenticate with
+  pub secret_access_key: String,
+}
+
+/// Blob Store that keeps the blobs in a bucket of an S3-compatible store, such as AWS S3 or
+/// MinIO.
+///
+/// Blobs are addressed using path-style URLs - `<endpoint>/<bucket>/<key>` - since these work with
+/// every S3-compatible store, and requests are authenticated using AWS Signature Version 4.
+pub struct S3BlobStore {
+  client: Client,
+  endpoint: Url,
+  bucket: String,
+  credentials: Credentials,
+}
+
+impl S3BlobStore {
+  /// Create a new Blob Store that talks to an S3-compatible store
+  ///
+  /// # Arguments
+  /// * `config` The details of the store to talk to
+  ///
+  /// # Returns
+  /// The Blob Store
+  pub fn new(config: S3Config) -> Result<Self, StorageError> {
+    let endpoint = Url::parse(&config.endpoint)
+      .map_err(|e| StorageError::RemoteError(format!("Invalid S3 endpoint: {}", e)))?;
+    parse_key(&config.bucket)?;
+
+    let ssl = OpensslClient::new()
+      .map_err(|e| StorageError::RemoteError(format!("Failed to initialise SSL: {}", e)))?;
+    let connector = Pool::with_connector(Default::default(), HttpsConnector::new(ssl));
+    let mut client = Client::with_connector(connector);
+    client.set_read_timeout(Some(Duration::from_secs(30)));
+    client.set_write_timeout(Some(Duration::from_secs(30)));
+
+    Ok(S3BlobStore {
+      client,
+      endpoint,
+      bucket: config.bucket,
+      credentials: Credentials {
+        access_key_id: config.access_key_id,
+        secret_access_key: config.secret_access_key,
+        region: config.region,
+      },
+    })
+  }
+
+  /// Build the value of the `Host` header for requests to the store
+  fn host(&self) -> Host {
+    Host {
+      hostname: self.endpoint.host_str().unwrap_or_default().to_owned(),
+      port: self.endpoint.port(),
+    }
+  }
+
+  /// Build the path to an object in the bucket
+  fn path(&self, key: Option<&str>) -> Result<String, StorageError> {
+    let base = self.endpoint.path().trim_end_matches('/');
+    match key {
+      Some(key) => {
+        parse_key(key)?;
+        Ok(format!(
+          "{}/{}/{}",
+          base,
+          self.bucket,
+          uri_encode(key, false)
+        ))
+      }
+      None => Ok(format!("{}/{}", base, self.bucket)),
+    }
+  }
+
+  /// Build the full URL for a path and query string
+  fn url(&self, path: &str, query: &str) -> Result<Url, StorageError> {
+    let mut url = format!("{}://{}{}", self.endpoint.scheme(), self.host(), path);
+    if !query.is_empty() {
+      url.push('?');
+      url.push_str(query);
+    }
+
+    Url::parse(&url).map_err(|e| StorageError::RemoteError(format!("Invalid URL: {}", e)))
+  }
+
+  /// Send a signed request to the store
+  ///
+  /// # Arguments
+  /// * `method` The HTTP method to use
+  /// * `key` The key of the object to send the request for, or `None` for the bucket itself
+  /// * `query` Any query string parameters to send
+  /// * `body` The blob to send as the request body, if any
+  ///
+  /// # Returns
+  /// The response from the store
+  fn send(
+    &self,
+    method: Method,
+    key: Option<&str>,
+    query: Vec<(String, String)>,
+    body: Option<&Blob>,
+  ) -> Result<Response, StorageError> {
+    let path = self.path(key)?;
+    let amz_date = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let payload_hash = hex_sha256(body.map(|blob| blob.data.as_slice()).unwrap_or_default());
+    let method_name = method.to_string();
+
+    let request = CanonicalRequest {
+      method: &method_name,
+      path: &path,
+      query,
+      headers: vec![
+        ("host".to_owned(), self.host().to_string()),
+        ("x-amz-content-sha256".to_owned(), payload_hash.clone()),
+        ("x-amz-date".to_owned(), amz_date.clone()),
+      ],
+      payload_hash: &payload_hash,
+    };
+    let url = self.url(&path, &request.query_string())?;
+
+    let mut headers = Headers::new();
+    headers.set(self.host());
+    headers.set_raw(
+      "x-amz-content-sha256",
+      vec![payload_hash.clone().into_bytes()],
+    );
+    headers.set_raw("x-amz-date", vec![amz_date.clone().into_bytes()]);
+    headers.set_raw(
+      "Authorization",
+      vec![self
+        .credentials
+        .authorization(&request, &amz_date)
+        .into_bytes()],
+    );
+
+    debug!("Sending {} request to {}", method, url);
+    let builder = self.client.request(method, url);
+    let result = match body {
+      Some(blob) => {
+        headers.set_raw("Content-Type", vec![blob.content_type.clone().into_bytes()]);
+        builder.headers(headers).body(blob.data.as_slice()).send()
+      }
+      None => builder.headers(headers).send(),
+    };
+
+    result.map_err(|e| StorageError::RemoteError(format!("Error talking to S3: {}", e)))
+  }
+
+  /// Produce an error for an unexpected response from the store
+  fn unexpected_response(mut response: Response) -> StorageError {
+    let mut body = String::new();
+    let _ = response.read_to_string(&mut body);
+    warn!("Unexpected response from S3: {} {}", response.status, body);
+
+    StorageError::RemoteError(format!("Unexpected response from S3: {}", response.status))
+  }
+
+  /// List every object stored under the given prefix, following the pages of results as needed
+  ///
+  /// # Arguments
+  /// * `prefix` The prefix to look under. This is a complete key, so `users/1234` includes
+  ///   `users/1234/avatar` but not `users/12345/avatar`
+  ///
+  /// # Returns
+  /// The key and size of each object
+  fn list_objects(&self, prefix: &str) -> Result<Vec<(String, u64)>, StorageError> {
+    parse_key(prefix)?;
+    let nested = format!("{}/", prefix);
+
+    let mut objects = vec![];
+    let mut continuation_token = None;
+    loop {
+      let mut query = vec![
+        ("list-type".to_owned(), "2".to_owned()),
+        ("prefix".to_owned(), prefix.to_owned()),
+      ];
+      if let Some(token) = continuation_token {
+        query.push(("continuation-token".to_owned(), token));
+      }
+
+      let mut response = self.send(Method::Get, None, query, None)?;
+      if response.status != StatusCode::Ok {
+        return Err(Self::unexpected_response(response));
+      }
+      let mut body = String::new();
+      response.read_to_string(&mut body)?;
+
+      // The prefix matches keys that merely start with it, such as `users/12345` for a prefix of
+      // `users/1234`, so these need to be filtered out
+      for contents in xml_elements(&body, "Contents") {
+        let key = xml_elements(contents, "Key").next().unwrap_or_default();
+        if key == prefix || key.starts_with(&nested) {
+          let size = xml_elements(contents, "Size")
+            .next()
+            .and_then(|size| size.parse::<u64>().ok())
+            .unwrap_or_default();
+          objects.push((key.to_owned(), size));
+        }
+      }
+
+      continuation_token = match xml_elements(&body, "IsTruncated").next() {
+        Some("true") => xml_elements(&body, "NextContinuationToken")
+          .next()
+          .map(|token| token.to_owned()),
+        _ => None,
+      };
+      if continuation_token.is_none() {
+        break;
+      }
+    }
+
+    Ok(objects)
+  }
+}
+
+impl BlobStore for S3BlobStore {
+  fn put(&self, key: &str, blob: &Blob) -> Result<(), StorageError> {
+    debug!("Storing blob {} in bucket {}", key, self.bucket);
+    let response = self.send(Method::Put, Some(key), vec![], Some(blob))?;
+
+    match response.status {
+      StatusCode::Ok => Ok(()),
+      _ => Err(Self::unexpected_response(response)),
+    }
+  }
+
+  fn stream(&self, key: &str) -> Result<Option<BlobStream>, StorageError> {
+    let response = self.send(Method::Get, Some(key), vec![], None)?;
+
+    match response.status {
+      StatusCode::Ok => {
+        let content_type = response
+          .headers
+          .get::<ContentType>()
+          .map(|content_type| content_type.to_string())
+          .unwrap_or_else(|| DEFAULT_CONTENT_TYPE.to_owned());
+        let length = response
+          .headers
+          .get::<ContentLength>()
+          .map(|length| length.0)
+          .unwrap_or_default();
+
+        Ok(Some(BlobStream {
+          content_type,
+          length,
+          reader: Box::new(response),
+        }))
+      }
+      StatusCode::NotFound => Ok(None),
+      _ => Err(Self::unexpected_response(response)),
+    }
+  }
+
+  fn delete(&self, key: &str) -> Result<(), StorageError> {
+    debug!("Deleting blob {} from bucket {}", key, self.bucket);
+    let response = self.send(Method::Delete, Some(key), vec![], None)?;
+
+    match response.status {
+      StatusCode::Ok | StatusCode::NoContent | StatusCode::NotFound => Ok(()),
+      _ => Err(Self::unexpected_response(response)),
+    }
+  }
+
+  fn signed_url(&self, key: &str, expires_in: Duration) -> Result<String, StorageError> {
+    let path = self.path(Some(key))?;
+    let amz_date = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+
+    let query = self.credentials.presign(
+      "GET",
+      &path,
+      &self.host().to_string(),
+      &amz_date,
+      expires_in.as_secs(),
+    );
+
+    Ok(self.url(&path, &query)?.to_string())
+  }
+
+  fn usage(&self, prefix: &str) -> Result<u64, StorageError> {
+    Ok(
+      self
+        .list_objects(prefix)?
+        .into_iter()
+        .map(|(_, size)| size)
+        .sum(),
+    )
+  }
+
+  fn list(&self, prefix: &str) -> Result<Vec<String>, StorageError> {
+    let mut keys: Vec<String> = self
+      .list_objects(prefix)?
+      .into_iter()
+      .map(|(key, _)| key)
+      .collect();
+    keys.sort();
+    Ok(keys)
+  }
+}
+
+impl Healthcheck for S3BlobStore {
+  /// Check the health of the S3 store.
+  /// This works by checking that the bucket exists and that we are allowed to access it
+  ///
+  /// # Returns
+  /// The result of the healthcheck
+  fn check_health(&self) -> Result<String, String> {
+    let response = self
+      .send(Method::Head, None, vec![], None)
+      .map_err(|e| e.to_string())?;
+
+    match response.status {
+      StatusCode::Ok => Ok("Ok".to_owned()),
+      status => Err(format!(
+        "Error accessing bucket {}: {}",
+        self.bucket, status
+      )),
+    }
+  }
+}
+
+/// Find the contents of every element with the given name in some XML.
+///
+/// This is nowhere near a full XML parser, but the responses from S3 are simple enough - and our
+/// keys restricted enough - that it doesn't need to be.
+fn xml_elements<'a>(xml: &'a str, name: &str) -> impl Iterator<Item = &'a str> {
+  let open = format!("<{}>", name);
+  let close = format!("</{}>", name);
+
+  let mut remaining = xml;
+  std::iter::from_fn(move || {
+    let start = remaining.find(&open)? + open.len();
+    let end = remaining[start..].find(&close)? + start;
+    let contents = &remaining[start..end];
+    remaining = &remaining[end + close.len()..];
+    Some(contents)
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use assert_matches::assert_matches;
+  use spectral::prelude::*;
+  use universe_test_storage_container::TestStorage;
+
+  fn blob(content_type: &str, data: &[u8]) -> Blob {
+    Blob {
+      content_type: content_type.to_owned(),
+      data: data.to_vec(),
+    }
+  }
+
+  fn config(storage: &TestStorage, bucket: &str) -> S3Config {
+    S3Config {
+      endpoint: storage.endpoint.clone(),
+      region: "us-east-1".to_owned(),
+      bucket: bucket.to_owned(),
+      access_key_id: storage.access_key_id.clone(),
+      secret_access_key: storage.secret_access_key.clone(),
+    }
+  }
+
+  /// Build a Blob Store for a new bucket in the given storage
+  fn build_store(storage: &TestStorage) -> S3BlobStore {
+    let store = S3BlobStore::new(config(storage, "universe")).unwrap();
+
+    let response = store.send(Method::Put, None, vec![], None).unwrap();
+    assert_that(&response.status).is_equal_to(StatusCode::Ok);
+
+    store
+  }
+
+  #[test]
+  fn test_xml_elements() {
+    let xml =
+      "<R><Contents><Key>a</Key><Size>1</Size></Contents><Contents><Key>b</Key></Contents></R>";
+
+    let contents: Vec<&str> = xml_elements(xml, "Contents").collect();
+    assert_that(&contents).is_equal_to(vec!["<Key>a</Key><Size>1</Size>", "<Key>b</Key>"]);
+
+    let keys: Vec<&str> = xml_elements(xml, "Key").collect();
+    assert_that(&keys).is_equal_to(vec!["a", "b"]);
+
+    assert_that(&xml_elements(xml, "Missing").next()).is_none();
+  }
+
+  #[test]
+  fn test_get_unknown_blob() {
+    let storage = TestStorage::new();
+    let store = build_store(&storage);
+
+    let result = store.get("unknown/blob");
+    assert_that(&result.unwrap()).is_none();
+  }
+
+  #[test]
+  fn test_put_and_get_blob() {
+    let storage = TestStorage::new();
+    let store = build_store(&storage);
+
+    store
+      .put("worlds/1234/cover", &blob("image/png", b"Hello"))
+      .unwrap();
+
+    let result = store.get("worlds/1234/cover");
+    assert_that(&result.unwrap()).contains_value(blob("image/png", b"Hello"));
+  }
+
+  #[test]
+  fn test_stream_blob() {
+    let storage = TestStorage::new();
+    let store = build_store(&storage);
+
+    store.put("cover", &blob("image/png", b"Hello")).unwrap();
+
+    let stream = store.stream("cover").unwrap().unwrap();
+    assert_that(&stream.content_type).is_equal_to("image/png".to_owned());
+    assert_that(&stream.length).is_equal_to(5);
+    assert_that(&stream.into_blob().unwrap()).is_equal_to(blob("image/png", b"Hello"));
+  }
+
+  #[test]
+  fn test_delete_blob() {
+    let storage = TestStorage::new();
+    let store = build_store(&storage);
+
+    store.put("cover", &blob("image/png", b"Hello")).unwrap();
+    store.delete("cover").unwrap();
+
+    let result = store.get("cover");
+    assert_that(&result.unwrap()).is_none();
+  }
+
+  #[test]
+  fn test_delete_unknown_blob() {
+    let storage = TestStorage::new();
+    let store = build_store(&storage);
+
+    let result = store.delete("cover");
+    assert_that(&result.is_ok()).is_true();
+  }
+
+  #[test]
+  fn test_usage() {
+    let storage = TestStorage::new();
+    let store = build_store(&storage);
+
+    for (key, data) in &[
+      ("users/1234/avatar", "Hello"),
+      ("users/1234/files/a", "World!"),
+      ("users/1234/files/b", "Again"),
+      ("users/12345/avatar", "Other"),
+    ] {
+      store
+        .put(key, &blob("text/plain", data.as_bytes()))
+        .unwrap();
+    }
+
+    assert_that(&store.usage("users/1234").unwrap()).is_equal_to(16);
+    assert_that(&store.usage("users/1234/avatar").unwrap()).is_equal_to(5);
+    assert_that(&store.usage("users/9999").unwrap()).is_equal_to(0);
+  }
+
+  #[test]
+  fn test_list_and_delete_all() {
+    let storage = TestStorage::new();
+    let store = build_store(&storage);
+
+    for key in &[
+      "worlds/1234/covers/a",
+      "worlds/1234/covers/b",
+      "worlds/1234/maps/c",
+      "worlds/12345/covers/a",
+    ] {
+      store.put(key, &blob("text/plain", b"Hello")).unwrap();
+    }
+
+    assert_that(&store.list("worlds/1234").unwrap()).is_equal_to(vec![
+      "worlds/1234/covers/a".to_owned(),
+      "worlds/1234/covers/b".to_owned(),
+      "worlds/1234/maps/c".to_owned(),
+    ]);
+
+    store.delete_all("worlds/1234").unwrap();
+    assert_that(&store.list("worlds/1234").unwrap()).is_empty();
+    assert_that(&store.usage("worlds/12345").unwrap()).is_equal_to(5);
+  }
+
+  #[test]
+  fn test_signed_url() {
+    let storage = TestStorage::new();
+    let store = build_store(&storage);
+
+    store.put("cover", &blob("image/png", b"Hello")).unwrap();
+
+    let url = store.signed_url("cover", Duration::from_secs(60)).unwrap();
+    assert_that(&url.starts_with(&format!("{}/universe/cover?", storage.endpoint))).is_true();
+
+    let mut response = Client::new().get(&url).send().unwrap();
+    let mut body = String::new();
+    response.read_to_string(&mut body).unwrap();
+    assert_that(&response.status).is_equal_to(StatusCode::Ok);
+    assert_that(&body).is_equal_to("Hello".to_owned());
+
+    let tampered = url.replace("/cover?", "/other?");
+    let response = Client::new().get(&tampered).send().unwrap();
+    assert_that(&response.status).is_equal_to(StatusCode::Forbidden);
+  }
+
+  #[test]
+  fn test_wrong_credentials() {
+    let storage = TestStorage::new();
+    build_store(&storage);
+    let store = S3BlobStore::new(S3Config {
+      secret_access_key: "wrong".to_owned(),
+      ..config(&storage, "universe")
+    })
+    .unwrap();
+
+    let result = store.put("cover", &blob("image/png", b"Hello"));
+    assert_matches!(result, Err(StorageError::RemoteError(_)));
+  }
+
+  #[test]
+  fn test_healthcheck() {
+    let storage = TestStorage::new();
+    let store = build_store(&storage);
+
+    assert_that(&store.check_health()).is_ok();
+  }
+
+  #[test]
+  fn test_healthcheck_unknown_bucket() {
+    let storage = TestStorage::new();
+    let store = S3BlobStore::new(config(&storage, "unknown")).unwrap();
+
+    assert_that(&store.check_health()).is_err();
+  }
+}
@@ -0,0 +1,72 @@
+//! Support for talking to S3 over HTTPS, by plugging OpenSSL into the HTTP client
+use hyper::net::{HttpStream, NetworkStream, SslClient};
+use openssl::ssl::{SslConnector, SslMethod, SslStream};
+use std::io::{Read, Write};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// SSL implementation for the HTTP client that uses OpenSSL
+pub struct OpensslClient {
+  connector: SslConnector,
+}
+
+impl OpensslClient {
+  /// Create a new SSL implementation that verifies certificates against the system trust store
+  pub fn new() -> Result<Self, openssl::error::ErrorStack> {
+    let connector = SslConnector::builder(SslMethod::tls())?.build();
+    Ok(OpensslClient { connector })
+  }
+}
+
+impl SslClient for OpensslClient {
+  type Stream = TlsStream;
+
+  fn wrap_client(&self, stream: HttpStream, host: &str) -> hyper::Result<Self::Stream> {
+    self
+      .connector
+      .connect(host, stream)
+      .map(|stream| TlsStream(Arc::new(Mutex::new(stream))))
+      .map_err(|e| {
+        hyper::Error::Ssl(Box::new(std::io::Error::new(
+          std::io::ErrorKind::Other,
+          e.to_string(),
+        )))
+      })
+  }
+}
+
+/// A stream that is protected by SSL. The HTTP client requires streams to be cloneable, so this
+/// shares the underlying stream between all of the clones
+#[derive(Clone)]
+pub struct TlsStream(Arc<Mutex<SslStream<HttpStream>>>);
+
+impl Read for TlsStream {
+  fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+    self.0.lock().unwrap().read(buf)
+  }
+}
+
+impl Write for TlsStream {
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    self.0.lock().unwrap().write(buf)
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    self.0.lock().unwrap().flush()
+  }
+}
+
+impl NetworkStream for TlsStream {
+  fn peer_addr(&mut self) -> std::io::Result<SocketAddr> {
+    self.0.lock().unwrap().get_mut().peer_addr()
+  }
+
+  fn set_read_timeout(&self, dur: Option<Duration>) -> std::io::Result<()> {
+    self.0.lock().unwrap().get_ref().set_read_timeout(dur)
+  }
+
+  fn set_write_timeout(&self, dur: Option<Duration>) -> std::io::Result<()> {
+    self.0.lock().unwrap().get_ref().set_write_timeout(dur)
+  }
+}
@@ -0,0 +1,76 @@
+/// The content type to use for data that we can't recognise
+pub const DEFAULT_CONTENT_TYPE: &str = "application/octet-stream";
+
+/// The signatures of the content types that we are able to recognise, as the offset that the
+/// signature appears at, the bytes of the signature and the content type
+const SIGNATURES: &[(usize, &[u8], &str)] = &[
+  (0, b"\x89PNG\r\n\x1a\n", "image/png"),
+  (0, b"\xff\xd8\xff", "image/jpeg"),
+  (0, b"GIF87a", "image/gif"),
+  (0, b"GIF89a", "image/gif"),
+  (8, b"WEBP", "image/webp"),
+  (0, b"%PDF-", "application/pdf"),
+  (0, b"PK\x03\x04", "application/zip"),
+  (0, b"\x1f\x8b", "application/gzip"),
+];
+
+/// Work out the content type of some data by looking at the data itself.
+///
+/// Binary formats are recognised by the signatures at the start of the data. Anything else that
+/// is non-empty, valid UTF-8 and has no control characters other than whitespace is treated as
+/// plain text, and everything else is treated as arbitrary binary data.
+///
+/// # Arguments
+/// * `data` The data to examine
+///
+/// # Returns
+/// The MIME type of the data
+pub fn sniff_content_type(data: &[u8]) -> &'static str {
+  let signature = SIGNATURES.iter().find(|(offset, signature, _)| {
+    data
+      .get(*offset..offset + signature.len())
+      .map(|bytes| bytes == *signature)
+      .unwrap_or(false)
+  });
+
+  // WebP files are a RIFF container, so need the RIFF header as well as the WebP marker
+  match signature {
+    Some((_, _, "image/webp")) if !data.starts_with(b"RIFF") => {}
+    Some((_, _, content_type)) => return content_type,
+    None => {}
+  }
+
+  match std::str::from_utf8(data) {
+    Ok(text) if !text.is_empty() && !text.chars().any(|c| c.is_control() && !c.is_whitespace()) => {
+      "text/plain; charset=utf-8"
+    }
+    _ => DEFAULT_CONTENT_TYPE,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use rstest::rstest;
+  use spectral::prelude::*;
+
+  #[rstest(
+    input,
+    expected,
+    case(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR", "image/png"),
+    case(b"\xff\xd8\xff\xe0\0\x10JFIF", "image/jpeg"),
+    case(b"GIF89a\x01\0\x01\0", "image/gif"),
+    case(b"RIFF\x24\0\0\0WEBPVP8 ", "image/webp"),
+    case(b"RIFF\x24\0\0\0WAVEfmt ", "application/octet-stream"),
+    case(b"%PDF-1.4", "application/pdf"),
+    case(b"PK\x03\x04\x14\0", "application/zip"),
+    case(b"\x1f\x8b\x08\0", "application/gzip"),
+    case(b"Hello, World!\n", "text/plain; charset=utf-8"),
+    case(b"", "application/octet-stream"),
+    case(b"Hello\0World", "application/octet-stream"),
+    case(b"\xc3\x28", "application/octet-stream")
+  )]
+  fn test_sniff_content_type(input: &[u8], expected: &str) {
+    assert_that(&sniff_content_type(input)).is_equal_to(expected);
+  }
+}
@@ -0,0 +1,169 @@
+use crate::{Blob, BlobStream};
+use std::time::Duration;
+use universe_health::Healthcheck;
+
+/// Errors that can occur when working with a blob store
+#[derive(Debug, thiserror::Error)]
+pub enum StorageError {
+  #[error("Invalid blob key: {0}")]
+  InvalidKey(String),
+
+  #[error("Storing the blob would take {owner} over their quota of {quota} bytes")]
+  QuotaExceeded { owner: String, quota: u64 },
+
+  #[error("Error accessing storage: {0}")]
+  IoError(#[from] std::io::Error),
+
+  #[error("Error from remote storage: {0}")]
+  RemoteError(String),
+}
+
+/// Trait that any store of binary blobs must implement.
+///
+/// Blobs are identified by keys made up of segments separated by slashes, such as
+/// `worlds/1234/cover/original`. Each segment may only contain letters, digits, dots, hyphens and
+/// underscores, and may not be `.` or `..`.
+///
+/// Every store is also able to check its own health, so that it can be included in the
+/// healthchecks of the service.
+pub trait BlobStore: Healthcheck {
+  /// Store a blob under the given key, replacing anything that was already stored there
+  ///
+  /// # Arguments
+  /// * `key` The key to store the blob under
+  /// * `blob` The blob to store
+  fn put(&self, key: &str, blob: &Blob) -> Result<(), StorageError>;
+
+  /// Retrieve the blob stored under the given key, reading it entirely into memory
+  ///
+  /// # Arguments
+  /// * `key` The key of the blob to retrieve
+  ///
+  /// # Returns
+  /// The blob, or `None` if nothing is stored under the key
+  fn get(&self, key: &str) -> Result<Option<Blob>, StorageError> {
+    match self.stream(key)? {
+      Some(stream) => Ok(Some(stream.into_blob()?)),
+      None => Ok(None),
+    }
+  }
+
+  /// Start reading the blob stored under the given key, without reading it all into memory
+  ///
+  /// # Arguments
+  /// * `key` The key of the blob to retrieve
+  ///
+  /// # Returns
+  /// The stream to read the blob from, or `None` if nothing is stored under the key
+  fn stream(&self, key: &str) -> Result<Option<BlobStream>, StorageError>;
+
+  /// Delete the blob stored under the given key. Deleting a key that has nothing stored under it
+  /// is not an error
+  ///
+  /// # Arguments
+  /// * `key` The key of the blob to delete
+  fn delete(&self, key: &str) -> Result<(), StorageError>;
+
+  /// Generate a URL that allows anyone who has it to download the blob stored under the given
+  /// key, without any further authentication, until it expires
+  ///
+  /// # Arguments
+  /// * `key` The key of the blob to generate the URL for
+  /// * `expires_in` How long the URL should be usable for
+  ///
+  /// # Returns
+  /// The URL
+  fn signed_url(&self, key: &str, expires_in: Duration) -> Result<String, StorageError>;
+
+  /// Work out the total size of every blob stored under the given prefix
+  ///
+  /// # Arguments
+  /// * `prefix` The prefix to look under. This is a complete key, so `users/1234` includes
+  ///   `users/1234/avatar` but not `users/12345/avatar`
+  ///
+  /// # Returns
+  /// The total size, in bytes
+  fn usage(&self, prefix: &str) -> Result<u64, StorageError>;
+
+  /// List the keys of every blob stored under the given prefix
+  ///
+  /// # Arguments
+  /// * `prefix` The prefix to look under. This is a complete key, in the same way as for `usage`
+  ///
+  /// # Returns
+  /// The keys of the blobs, in sorted order
+  fn list(&self, prefix: &str) -> Result<Vec<String>, StorageError>;
+
+  /// Delete every blob stored under the given prefix
+  ///
+  /// # Arguments
+  /// * `prefix` The prefix to delete everything under. This is a complete key, in the same way as
+  ///   for `usage`
+  fn delete_all(&self, prefix: &str) -> Result<(), StorageError> {
+    for key in self.list(prefix)? {
+      self.delete(&key)?;
+    }
+    Ok(())
+  }
+}
+
+/// Check that a blob key is one that we are able to store
+///
+/// # Arguments
+/// * `key` The key to check
+///
+/// # Returns
+/// The individual segments of the key, or an error if the key is not valid
+pub fn parse_key(key: &str) -> Result<Vec<&str>, StorageError> {
+  let segments: Vec<&str> = key.split('/').collect();
+
+  let valid = segments.iter().all(|segment| {
+    !segment.is_empty()
+      && *segment != "."
+      && *segment != ".."
+      && segment
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '_')
+  });
+
+  if valid {
+    Ok(segments)
+  } else {
+    Err(StorageError::InvalidKey(key.to_owned()))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use assert_matches::assert_matches;
+  use rstest::rstest;
+  use spectral::prelude::*;
+
+  #[rstest(input, expected,
+    case("cover", vec!["cover"]),
+    case("worlds/1234/cover.png", vec!["worlds", "1234", "cover.png"]),
+    case("a-b_c/.hidden", vec!["a-b_c", ".hidden"])
+  )]
+  fn test_parse_valid_key(input: &str, expected: Vec<&str>) {
+    let result = parse_key(input);
+    assert_that(&result.unwrap()).is_equal_to(expected);
+  }
+
+  #[rstest(
+    input,
+    case(""),
+    case("/"),
+    case("/worlds"),
+    case("worlds/"),
+    case("worlds//cover"),
+    case("worlds/../users"),
+    case("worlds/./cover"),
+    case("worlds/cover image"),
+    case("worlds\\cover")
+  )]
+  fn test_parse_invalid_key(input: &str) {
+    let result = parse_key(input);
+    assert_matches!(result, Err(StorageError::InvalidKey(key)) if key == input);
+  }
+}
@@ -0,0 +1,192 @@
+use crate::{parse_key, StorageError};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Enumeration of reasons why a signed URL might not be valid
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum SignatureError {
+  #[error("The signed URL has expired")]
+  Expired,
+  #[error("The signature of the URL is not valid")]
+  Invalid,
+}
+
+/// Mechanism to generate and verify signed URLs for blobs, for stores that can't generate them
+/// themselves.
+///
+/// The URLs are of the form `<base_url>/<key>?expires=<expires>&signature=<signature>`, where
+/// `expires` is the Unix timestamp at which the URL stops working and `signature` is an
+/// HMAC-SHA256 of the key and the expiry. Whatever serves `base_url` is expected to call `verify`
+/// before returning the blob.
+#[derive(Clone)]
+pub struct UrlSigner {
+  base_url: String,
+  secret: Vec<u8>,
+}
+
+impl UrlSigner {
+  /// Create a new URL Signer
+  ///
+  /// # Arguments
+  /// * `base_url` The URL that blobs are served from
+  /// * `secret` The secret key used to sign URLs
+  ///
+  /// # Returns
+  /// The URL Signer
+  pub fn new<U, S>(base_url: U, secret: S) -> Self
+  where
+    U: Into<String>,
+    S: AsRef<[u8]>,
+  {
+    UrlSigner {
+      base_url: base_url.into(),
+      secret: secret.as_ref().to_vec(),
+    }
+  }
+
+  /// Generate a signed URL for a blob
+  ///
+  /// # Arguments
+  /// * `key` The key of the blob
+  /// * `expires_in` How long the URL should be usable for
+  ///
+  /// # Returns
+  /// The signed URL
+  pub fn sign(&self, key: &str, expires_in: Duration) -> Result<String, StorageError> {
+    parse_key(key)?;
+
+    let expires = (SystemTime::now() + expires_in)
+      .duration_since(UNIX_EPOCH)
+      .unwrap_or_default()
+      .as_secs();
+    let signature = hex::encode(self.mac(key, expires).result().code());
+
+    Ok(format!(
+      "{}/{}?expires={}&signature={}",
+      self.base_url, key, expires, signature
+    ))
+  }
+
+  /// Verify that a signed URL is valid
+  ///
+  /// # Arguments
+  /// * `key` The key of the blob, from the URL
+  /// * `expires` The expiry of the URL, from the URL
+  /// * `signature` The signature, from the URL
+  ///
+  /// # Returns
+  /// Nothing if the URL is valid, or the reason it isn't
+  pub fn verify(&self, key: &str, expires: u64, signature: &str) -> Result<(), SignatureError> {
+    let signature = hex::decode(signature).map_err(|_| SignatureError::Invalid)?;
+    self
+      .mac(key, expires)
+      .verify(&signature)
+      .map_err(|_| SignatureError::Invalid)?;
+
+    let now = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .unwrap_or_default()
+      .as_secs();
+    if expires < now {
+      return Err(SignatureError::Expired);
+    }
+
+    Ok(())
+  }
+
+  /// Build the MAC for the given key and expiry
+  fn mac(&self, key: &str, expires: u64) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_varkey(&self.secret).expect("HMAC accepts any key length");
+    mac.input(format!("{}\n{}", key, expires).as_bytes());
+    mac
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use assert_matches::assert_matches;
+  use spectral::prelude::*;
+
+  /// Split a signed URL into the key, expiry and signature
+  fn parse_url(url: &str) -> (String, u64, String) {
+    let (path, query) = url.split_at(url.find('?').unwrap());
+    let key = path.trim_start_matches("/storage/").to_owned();
+    let params: Vec<&str> = query[1..].split('&').collect();
+    let expires = params[0].trim_start_matches("expires=").parse().unwrap();
+    let signature = params[1].trim_start_matches("signature=").to_owned();
+
+    (key, expires, signature)
+  }
+
+  #[test]
+  fn test_sign_and_verify() {
+    let signer = UrlSigner::new("/storage", "secret");
+
+    let url = signer
+      .sign("users/1234/avatar", Duration::from_secs(60))
+      .unwrap();
+    assert_that(&url.starts_with("/storage/users/1234/avatar?expires=")).is_true();
+
+    let (key, expires, signature) = parse_url(&url);
+    assert_that(&signer.verify(&key, expires, &signature)).is_ok();
+  }
+
+  #[test]
+  fn test_verify_different_key() {
+    let signer = UrlSigner::new("/storage", "secret");
+
+    let url = signer
+      .sign("users/1234/avatar", Duration::from_secs(60))
+      .unwrap();
+    let (_, expires, signature) = parse_url(&url);
+
+    let result = signer.verify("users/5678/avatar", expires, &signature);
+    assert_matches!(result, Err(SignatureError::Invalid));
+  }
+
+  #[test]
+  fn test_verify_different_expiry() {
+    let signer = UrlSigner::new("/storage", "secret");
+
+    let url = signer
+      .sign("users/1234/avatar", Duration::from_secs(60))
+      .unwrap();
+    let (key, expires, signature) = parse_url(&url);
+
+    let result = signer.verify(&key, expires + 3600, &signature);
+    assert_matches!(result, Err(SignatureError::Invalid));
+  }
+
+  #[test]
+  fn test_verify_different_secret() {
+    let signer = UrlSigner::new("/storage", "secret");
+    let other = UrlSigner::new("/storage", "other");
+
+    let url = signer
+      .sign("users/1234/avatar", Duration::from_secs(60))
+      .unwrap();
+    let (key, expires, signature) = parse_url(&url);
+
+    let result = other.verify(&key, expires, &signature);
+    assert_matches!(result, Err(SignatureError::Invalid));
+  }
+
+  #[test]
+  fn test_verify_malformed_signature() {
+    let signer = UrlSigner::new("/storage", "secret");
+
+    let result = signer.verify("users/1234/avatar", u64::max_value(), "not-hex");
+    assert_matches!(result, Err(SignatureError::Invalid));
+  }
+
+  #[test]
+  fn test_verify_expired() {
+    let signer = UrlSigner::new("/storage", "secret");
+    let signature = hex::encode(signer.mac("users/1234/avatar", 1000).result().code());
+
+    let result = signer.verify("users/1234/avatar", 1000, &signature);
+    assert_matches!(result, Err(SignatureError::Expired));
+  }
+}
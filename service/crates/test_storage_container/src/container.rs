@@ -0,0 +1,48 @@
+use crate::minio::{Minio, ACCESS_KEY_ID, SECRET_ACCESS_KEY};
+use lazy_static::lazy_static;
+use testcontainers::{clients::Cli, Container, Docker};
+use tracing::info;
+
+lazy_static! {
+    static ref DOCKER: Cli = { Cli::default() };
+}
+
+/// Wrapper around a Docker container that runs MinIO for our tests.
+///
+/// MinIO starts without any buckets, so the tests need to create the ones that they use.
+pub struct TestStorage<'d> {
+    #[allow(dead_code)]
+    node: Container<'d, Cli, Minio>,
+    pub host: String,
+    pub port: u32,
+    pub endpoint: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+}
+
+impl<'d> Default for TestStorage<'d> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'d> TestStorage<'d> {
+    pub fn new() -> Self {
+        info!("Starting MinIO");
+        let node = DOCKER.run(Minio::default());
+
+        let host = std::env::var("DOCKER_HOSTNAME").unwrap_or_else(|_| "localhost".to_owned());
+        let port = node.get_host_port(9000).unwrap();
+        let endpoint = format!("http://{}:{}", host, port);
+        info!("Running MinIO on {}", endpoint);
+
+        TestStorage {
+            node,
+            host,
+            port,
+            endpoint,
+            access_key_id: ACCESS_KEY_ID.to_owned(),
+            secret_access_key: SECRET_ACCESS_KEY.to_owned(),
+        }
+    }
+}
@@ -0,0 +1,4 @@
+mod container;
+pub(crate) mod minio;
+
+pub use container::TestStorage;
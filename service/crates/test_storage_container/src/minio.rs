@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+use testcontainers::{Container, Docker, Image, WaitForMessage};
+
+/// The Access Key ID that MinIO is started with
+pub const ACCESS_KEY_ID: &str = "minioadmin";
+
+/// The Secret Access Key that MinIO is started with
+pub const SECRET_ACCESS_KEY: &str = "minioadmin";
+
+#[derive(Debug)]
+pub struct Minio {
+  arguments: MinioArgs,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct MinioArgs {}
+
+impl IntoIterator for MinioArgs {
+  type Item = String;
+  type IntoIter = ::std::vec::IntoIter<String>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    vec!["server".to_owned(), "/data".to_owned()].into_iter()
+  }
+}
+
+impl Default for Minio {
+  fn default() -> Self {
+    Self {
+      arguments: MinioArgs::default(),
+    }
+  }
+}
+
+impl Image for Minio {
+  type Args = MinioArgs;
+  type EnvVars = HashMap<String, String>;
+
+  fn descriptor(&self) -> String {
+    "minio/minio:RELEASE.2020-10-18T21-54-12Z".to_string()
+  }
+
+  fn wait_until_ready<D: Docker>(&self, container: &Container<'_, D, Self>) {
+    container
+      .logs()
+      .stdout
+      .wait_for_message("Endpoint:")
+      .unwrap();
+  }
+
+  fn args(&self) -> Self::Args {
+    self.arguments.clone()
+  }
+
+  fn env_vars(&self) -> Self::EnvVars {
+    let mut env_vars = HashMap::new();
+    env_vars.insert("MINIO_ACCESS_KEY".to_owned(), ACCESS_KEY_ID.to_owned());
+    env_vars.insert("MINIO_SECRET_KEY".to_owned(), SECRET_ACCESS_KEY.to_owned());
+    env_vars
+  }
+
+  fn with_args(self, arguments: Self::Args) -> Self {
+    Self { arguments, ..self }
+  }
+}
@@ -0,0 +1,56 @@
+use crate::testdata::TestData;
+use chrono::{DateTime, Timelike, Utc};
+use postgres_types::ToSql;
+use std::boxed::Box;
+use uuid::Uuid;
+
+/// Test Data for an Article record
+#[derive(Debug, PartialEq, Clone)]
+pub struct Article {
+  pub article_id: Uuid,
+  pub version: Uuid,
+  pub created: DateTime<Utc>,
+  pub updated: DateTime<Utc>,
+  pub world_id: Uuid,
+  pub parent_id: Option<Uuid>,
+  pub title: String,
+  pub slug: String,
+  pub body: String,
+}
+
+impl Default for Article {
+  /// Generate a default set of values for the test Article structure
+  fn default() -> Self {
+    Self {
+      article_id: Uuid::new_v4(),
+      version: Uuid::new_v4(),
+      created: Utc::now().with_nanosecond(0).unwrap(),
+      updated: Utc::now().with_nanosecond(0).unwrap(),
+      world_id: Uuid::new_v4(),
+      parent_id: None,
+      title: "Test Article".to_owned(),
+      slug: "test-article".to_owned(),
+      body: "This is a test article".to_owned(),
+    }
+  }
+}
+
+impl TestData for Article {
+  fn sql(&self) -> String {
+    "INSERT INTO articles(article_id, version, created, updated, world_id, parent_id, title, slug, body) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)".to_owned()
+  }
+
+  fn binds(&self) -> Vec<Box<(dyn ToSql + Sync)>> {
+    vec![
+      Box::new(self.article_id),
+      Box::new(self.version),
+      Box::new(self.created),
+      Box::new(self.updated),
+      Box::new(self.world_id),
+      Box::new(self.parent_id),
+      Box::new(self.title.clone()),
+      Box::new(self.slug.clone()),
+      Box::new(self.body.clone()),
+    ]
+  }
+}
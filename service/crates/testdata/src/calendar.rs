@@ -0,0 +1,61 @@
+use crate::testdata::TestData;
+use chrono::{DateTime, Timelike, Utc};
+use postgres_types::ToSql;
+use serde_json::json;
+use std::boxed::Box;
+use uuid::Uuid;
+
+/// Test Data for a Calendar record
+#[derive(Debug, PartialEq, Clone)]
+pub struct Calendar {
+  pub calendar_id: Uuid,
+  pub version: Uuid,
+  pub created: DateTime<Utc>,
+  pub updated: DateTime<Utc>,
+  pub world_id: Uuid,
+  pub name: String,
+  /// How the calendar counts days, months and years, in the same form as the API uses
+  pub definition: serde_json::Value,
+}
+
+impl Default for Calendar {
+  /// Generate a default set of values for the test Calendar structure.
+  ///
+  /// The default calendar has four months of 90 days, and nothing else.
+  fn default() -> Self {
+    Self {
+      calendar_id: Uuid::new_v4(),
+      version: Uuid::new_v4(),
+      created: Utc::now().with_nanosecond(0).unwrap(),
+      updated: Utc::now().with_nanosecond(0).unwrap(),
+      world_id: Uuid::new_v4(),
+      name: "Test Calendar".to_owned(),
+      definition: json!({
+        "months": [
+          { "name": "Spring", "days": 90 },
+          { "name": "Summer", "days": 90 },
+          { "name": "Autumn", "days": 90 },
+          { "name": "Winter", "days": 90 }
+        ]
+      }),
+    }
+  }
+}
+
+impl TestData for Calendar {
+  fn sql(&self) -> String {
+    "INSERT INTO calendars(calendar_id, version, created, updated, world_id, name, definition) VALUES ($1, $2, $3, $4, $5, $6, $7)".to_owned()
+  }
+
+  fn binds(&self) -> Vec<Box<(dyn ToSql + Sync)>> {
+    vec![
+      Box::new(self.calendar_id),
+      Box::new(self.version),
+      Box::new(self.created),
+      Box::new(self.updated),
+      Box::new(self.world_id),
+      Box::new(self.name.clone()),
+      Box::new(self.definition.clone()),
+    ]
+  }
+}
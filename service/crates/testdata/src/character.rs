@@ -0,0 +1,60 @@
+use crate::testdata::TestData;
+use chrono::{DateTime, Timelike, Utc};
+use postgres_types::{Json, ToSql};
+use std::boxed::Box;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// Test Data for a Character record
+#[derive(Debug, PartialEq, Clone)]
+pub struct Character {
+  pub character_id: Uuid,
+  pub version: Uuid,
+  pub created: DateTime<Utc>,
+  pub updated: DateTime<Utc>,
+  pub world_id: Uuid,
+  pub name: String,
+  pub aliases: Vec<String>,
+  pub summary: String,
+  pub biography: String,
+  pub attributes: BTreeMap<String, String>,
+}
+
+impl Default for Character {
+  /// Generate a default set of values for the test Character structure
+  fn default() -> Self {
+    Self {
+      character_id: Uuid::new_v4(),
+      version: Uuid::new_v4(),
+      created: Utc::now().with_nanosecond(0).unwrap(),
+      updated: Utc::now().with_nanosecond(0).unwrap(),
+      world_id: Uuid::new_v4(),
+      name: "Test Character".to_owned(),
+      aliases: vec![],
+      summary: "A test character".to_owned(),
+      biography: "This is a test character".to_owned(),
+      attributes: BTreeMap::new(),
+    }
+  }
+}
+
+impl TestData for Character {
+  fn sql(&self) -> String {
+    "INSERT INTO characters(character_id, version, created, updated, world_id, name, aliases, summary, biography, attributes) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)".to_owned()
+  }
+
+  fn binds(&self) -> Vec<Box<(dyn ToSql + Sync)>> {
+    vec![
+      Box::new(self.character_id),
+      Box::new(self.version),
+      Box::new(self.created),
+      Box::new(self.updated),
+      Box::new(self.world_id),
+      Box::new(self.name.clone()),
+      Box::new(self.aliases.clone()),
+      Box::new(self.summary.clone()),
+      Box::new(self.biography.clone()),
+      Box::new(Json(self.attributes.clone())),
+    ]
+  }
+}
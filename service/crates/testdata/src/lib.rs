@@ -1,7 +1,29 @@
+mod article;
+mod calendar;
+mod character;
+mod location;
+mod map;
+mod map_feature;
+mod relationship;
 mod testdata;
+mod timeline_event;
 mod user;
 mod world;
+mod world_member;
+mod world_star;
+mod world_transfer;
 
+pub use article::*;
+pub use calendar::*;
+pub use character::*;
+pub use location::*;
+pub use map::*;
+pub use map_feature::*;
+pub use relationship::*;
 pub use testdata::seed;
+pub use timeline_event::*;
 pub use user::*;
 pub use world::*;
+pub use world_member::*;
+pub use world_star::*;
+pub use world_transfer::*;
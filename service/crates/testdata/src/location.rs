@@ -0,0 +1,62 @@
+use crate::testdata::TestData;
+use chrono::{DateTime, Timelike, Utc};
+use postgres_types::ToSql;
+use std::boxed::Box;
+use uuid::Uuid;
+
+/// Test Data for a Location record
+#[derive(Debug, PartialEq, Clone)]
+pub struct Location {
+  pub location_id: Uuid,
+  pub version: Uuid,
+  pub created: DateTime<Utc>,
+  pub updated: DateTime<Utc>,
+  pub world_id: Uuid,
+  pub parent_id: Option<Uuid>,
+  pub name: String,
+  pub kind: String,
+  pub description: String,
+  pub x: Option<f64>,
+  pub y: Option<f64>,
+}
+
+impl Default for Location {
+  /// Generate a default set of values for the test Location structure
+  fn default() -> Self {
+    Self {
+      location_id: Uuid::new_v4(),
+      version: Uuid::new_v4(),
+      created: Utc::now().with_nanosecond(0).unwrap(),
+      updated: Utc::now().with_nanosecond(0).unwrap(),
+      world_id: Uuid::new_v4(),
+      parent_id: None,
+      name: "Test Location".to_owned(),
+      kind: "".to_owned(),
+      description: "This is a test location".to_owned(),
+      x: None,
+      y: None,
+    }
+  }
+}
+
+impl TestData for Location {
+  fn sql(&self) -> String {
+    "INSERT INTO locations(location_id, version, created, updated, world_id, parent_id, name, kind, description, x, y) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)".to_owned()
+  }
+
+  fn binds(&self) -> Vec<Box<(dyn ToSql + Sync)>> {
+    vec![
+      Box::new(self.location_id),
+      Box::new(self.version),
+      Box::new(self.created),
+      Box::new(self.updated),
+      Box::new(self.world_id),
+      Box::new(self.parent_id),
+      Box::new(self.name.clone()),
+      Box::new(self.kind.clone()),
+      Box::new(self.description.clone()),
+      Box::new(self.x),
+      Box::new(self.y),
+    ]
+  }
+}
@@ -0,0 +1,59 @@
+use crate::testdata::TestData;
+use chrono::{DateTime, Timelike, Utc};
+use postgres_types::ToSql;
+use std::boxed::Box;
+use uuid::Uuid;
+
+/// Test Data for a Map record
+#[derive(Debug, PartialEq, Clone)]
+pub struct Map {
+  pub map_id: Uuid,
+  pub version: Uuid,
+  pub created: DateTime<Utc>,
+  pub updated: DateTime<Utc>,
+  pub world_id: Uuid,
+  pub name: String,
+  pub image_id: Option<Uuid>,
+  pub image_content_type: Option<String>,
+  pub image_width: Option<i32>,
+  pub image_height: Option<i32>,
+}
+
+impl Default for Map {
+  /// Generate a default set of values for the test Map structure
+  fn default() -> Self {
+    Self {
+      map_id: Uuid::new_v4(),
+      version: Uuid::new_v4(),
+      created: Utc::now().with_nanosecond(0).unwrap(),
+      updated: Utc::now().with_nanosecond(0).unwrap(),
+      world_id: Uuid::new_v4(),
+      name: "Test Map".to_owned(),
+      image_id: Some(Uuid::new_v4()),
+      image_content_type: Some("image/png".to_owned()),
+      image_width: Some(1000),
+      image_height: Some(800),
+    }
+  }
+}
+
+impl TestData for Map {
+  fn sql(&self) -> String {
+    "INSERT INTO maps(map_id, version, created, updated, world_id, name, image_id, image_content_type, image_width, image_height) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)".to_owned()
+  }
+
+  fn binds(&self) -> Vec<Box<(dyn ToSql + Sync)>> {
+    vec![
+      Box::new(self.map_id),
+      Box::new(self.version),
+      Box::new(self.created),
+      Box::new(self.updated),
+      Box::new(self.world_id),
+      Box::new(self.name.clone()),
+      Box::new(self.image_id),
+      Box::new(self.image_content_type.clone()),
+      Box::new(self.image_width),
+      Box::new(self.image_height),
+    ]
+  }
+}
@@ -0,0 +1,68 @@
+use crate::testdata::TestData;
+use chrono::{DateTime, Timelike, Utc};
+use postgres_types::ToSql;
+use serde_json::json;
+use std::boxed::Box;
+use uuid::Uuid;
+
+/// Test Data for a Map Feature record
+#[derive(Debug, PartialEq, Clone)]
+pub struct MapFeature {
+  pub feature_id: Uuid,
+  pub version: Uuid,
+  pub created: DateTime<Utc>,
+  pub updated: DateTime<Utc>,
+  pub world_id: Uuid,
+  pub map_id: Uuid,
+  pub name: String,
+  /// The kind of feature. This must match the type of the geometry
+  pub kind: String,
+  /// The geometry of the feature, as a GeoJSON geometry object
+  pub geometry: serde_json::Value,
+  pub location_id: Option<Uuid>,
+  pub character_id: Option<Uuid>,
+  pub article_id: Option<Uuid>,
+}
+
+impl Default for MapFeature {
+  /// Generate a default set of values for the test Map Feature structure
+  fn default() -> Self {
+    Self {
+      feature_id: Uuid::new_v4(),
+      version: Uuid::new_v4(),
+      created: Utc::now().with_nanosecond(0).unwrap(),
+      updated: Utc::now().with_nanosecond(0).unwrap(),
+      world_id: Uuid::new_v4(),
+      map_id: Uuid::new_v4(),
+      name: "Test Feature".to_owned(),
+      kind: "pin".to_owned(),
+      geometry: json!({ "type": "Point", "coordinates": [100.0, 100.0] }),
+      location_id: None,
+      character_id: None,
+      article_id: None,
+    }
+  }
+}
+
+impl TestData for MapFeature {
+  fn sql(&self) -> String {
+    "INSERT INTO map_features(feature_id, version, created, updated, world_id, map_id, name, kind, geometry, location_id, character_id, article_id) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)".to_owned()
+  }
+
+  fn binds(&self) -> Vec<Box<(dyn ToSql + Sync)>> {
+    vec![
+      Box::new(self.feature_id),
+      Box::new(self.version),
+      Box::new(self.created),
+      Box::new(self.updated),
+      Box::new(self.world_id),
+      Box::new(self.map_id),
+      Box::new(self.name.clone()),
+      Box::new(self.kind.clone()),
+      Box::new(self.geometry.clone()),
+      Box::new(self.location_id),
+      Box::new(self.character_id),
+      Box::new(self.article_id),
+    ]
+  }
+}
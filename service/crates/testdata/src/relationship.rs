@@ -0,0 +1,112 @@
+use crate::testdata::TestData;
+use chrono::{DateTime, Timelike, Utc};
+use postgres_types::ToSql;
+use std::boxed::Box;
+use uuid::Uuid;
+
+/// Test Data for a Relationship record
+#[derive(Debug, PartialEq, Clone)]
+pub struct Relationship {
+  pub relationship_id: Uuid,
+  pub version: Uuid,
+  pub created: DateTime<Utc>,
+  pub updated: DateTime<Utc>,
+  pub world_id: Uuid,
+  pub relation: String,
+  pub symmetric: bool,
+  /// The type of entity that the relationship is from. One of "article", "character", "location"
+  /// or "map"
+  pub source_type: String,
+  pub source_id: Uuid,
+  /// The type of entity that the relationship is to. One of "article", "character", "location" or
+  /// "map"
+  pub target_type: String,
+  pub target_id: Uuid,
+}
+
+impl Default for Relationship {
+  /// Generate a default set of values for the test Relationship structure
+  fn default() -> Self {
+    Self {
+      relationship_id: Uuid::new_v4(),
+      version: Uuid::new_v4(),
+      created: Utc::now().with_nanosecond(0).unwrap(),
+      updated: Utc::now().with_nanosecond(0).unwrap(),
+      world_id: Uuid::new_v4(),
+      relation: "related to".to_owned(),
+      symmetric: false,
+      source_type: "character".to_owned(),
+      source_id: Uuid::new_v4(),
+      target_type: "character".to_owned(),
+      target_id: Uuid::new_v4(),
+    }
+  }
+}
+
+impl TestData for Relationship {
+  fn sql(&self) -> String {
+    format!(
+      "INSERT INTO relationships(relationship_id, version, created, updated, world_id, relation, is_symmetric, source_{}_id, target_{}_id) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+      self.source_type, self.target_type
+    )
+  }
+
+  fn binds(&self) -> Vec<Box<(dyn ToSql + Sync)>> {
+    vec![
+      Box::new(self.relationship_id),
+      Box::new(self.version),
+      Box::new(self.created),
+      Box::new(self.updated),
+      Box::new(self.world_id),
+      Box::new(self.relation.clone()),
+      Box::new(self.symmetric),
+      Box::new(self.source_id),
+      Box::new(self.target_id),
+    ]
+  }
+}
+
+/// Test Data for the in-world dates of a Relationship record
+#[derive(Debug, PartialEq, Clone)]
+pub struct RelationshipDates {
+  pub relationship_id: Uuid,
+  pub world_id: Uuid,
+  pub calendar_id: Uuid,
+  /// The first day of the relationship, as the year, month and day
+  pub start: Option<(i64, i32, i32)>,
+  /// The last day of the relationship, as the year, month and day
+  pub end: Option<(i64, i32, i32)>,
+}
+
+impl Default for RelationshipDates {
+  /// Generate a default set of values for the test Relationship Dates structure
+  fn default() -> Self {
+    Self {
+      relationship_id: Uuid::new_v4(),
+      world_id: Uuid::new_v4(),
+      calendar_id: Uuid::new_v4(),
+      start: None,
+      end: None,
+    }
+  }
+}
+
+impl TestData for RelationshipDates {
+  fn sql(&self) -> String {
+    "INSERT INTO relationship_dates(relationship_id, world_id, calendar_id, start_year, start_month, start_day, end_year, end_month, end_day) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)".to_owned()
+  }
+
+  fn binds(&self) -> Vec<Box<(dyn ToSql + Sync)>> {
+    vec![
+      Box::new(self.relationship_id),
+      Box::new(self.world_id),
+      Box::new(self.calendar_id),
+      Box::new(self.start.map(|(year, _, _)| year)),
+      Box::new(self.start.map(|(_, month, _)| month)),
+      Box::new(self.start.map(|(_, _, day)| day)),
+      Box::new(self.end.map(|(year, _, _)| year)),
+      Box::new(self.end.map(|(_, month, _)| month)),
+      Box::new(self.end.map(|(_, _, day)| day)),
+    ]
+  }
+}
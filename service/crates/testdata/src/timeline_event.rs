@@ -0,0 +1,67 @@
+use crate::testdata::TestData;
+use chrono::{DateTime, Timelike, Utc};
+use postgres_types::ToSql;
+use std::boxed::Box;
+use uuid::Uuid;
+
+/// Test Data for a Timeline Event record
+#[derive(Debug, PartialEq, Clone)]
+pub struct TimelineEvent {
+  pub event_id: Uuid,
+  pub version: Uuid,
+  pub created: DateTime<Utc>,
+  pub updated: DateTime<Utc>,
+  pub world_id: Uuid,
+  pub calendar_id: Uuid,
+  pub title: String,
+  pub description: String,
+  pub year: i64,
+  pub month: i32,
+  pub day: i32,
+  /// The number of days from the start of the calendar until the date of the event. This must
+  /// match the date in the calendar of the event
+  pub day_number: i64,
+}
+
+impl Default for TimelineEvent {
+  /// Generate a default set of values for the test Timeline Event structure
+  fn default() -> Self {
+    Self {
+      event_id: Uuid::new_v4(),
+      version: Uuid::new_v4(),
+      created: Utc::now().with_nanosecond(0).unwrap(),
+      updated: Utc::now().with_nanosecond(0).unwrap(),
+      world_id: Uuid::new_v4(),
+      calendar_id: Uuid::new_v4(),
+      title: "Test Event".to_owned(),
+      description: "".to_owned(),
+      year: 1,
+      month: 1,
+      day: 1,
+      day_number: 0,
+    }
+  }
+}
+
+impl TestData for TimelineEvent {
+  fn sql(&self) -> String {
+    "INSERT INTO timeline_events(event_id, version, created, updated, world_id, calendar_id, title, description, year, month, day, day_number) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)".to_owned()
+  }
+
+  fn binds(&self) -> Vec<Box<(dyn ToSql + Sync)>> {
+    vec![
+      Box::new(self.event_id),
+      Box::new(self.version),
+      Box::new(self.created),
+      Box::new(self.updated),
+      Box::new(self.world_id),
+      Box::new(self.calendar_id),
+      Box::new(self.title.clone()),
+      Box::new(self.description.clone()),
+      Box::new(self.year),
+      Box::new(self.month),
+      Box::new(self.day),
+      Box::new(self.day_number),
+    ]
+  }
+}
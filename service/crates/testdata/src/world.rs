@@ -15,6 +15,11 @@ pub struct World {
   pub name: String,
   pub slug: String,
   pub description: String,
+  pub deleted: Option<DateTime<Utc>>,
+  pub visibility: String,
+  pub forked_from: Option<Uuid>,
+  pub tags: Vec<String>,
+  pub cover_id: Option<Uuid>,
 }
 
 impl Default for World {
@@ -29,13 +34,18 @@ impl Default for World {
       name: "Test World".to_owned(),
       slug: "test-world".to_owned(),
       description: "This is a test world".to_owned(),
+      deleted: None,
+      visibility: "public".to_owned(),
+      forked_from: None,
+      tags: vec![],
+      cover_id: None,
     }
   }
 }
 
 impl TestData for World {
   fn sql(&self) -> String {
-    "INSERT INTO worlds(world_id, version, created, updated, owner_id, name, slug, description) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)".to_owned()
+    "INSERT INTO worlds(world_id, version, created, updated, owner_id, name, slug, description, deleted, visibility, forked_from, tags, cover_id) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)".to_owned()
   }
 
   fn binds(&self) -> Vec<Box<(dyn ToSql + Sync)>> {
@@ -48,6 +58,11 @@ impl TestData for World {
       Box::new(self.name.clone()),
       Box::new(self.slug.clone()),
       Box::new(self.description.clone()),
+      Box::new(self.deleted),
+      Box::new(self.visibility.clone()),
+      Box::new(self.forked_from),
+      Box::new(self.tags.clone()),
+      Box::new(self.cover_id),
     ]
   }
 }
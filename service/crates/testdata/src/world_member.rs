@@ -0,0 +1,44 @@
+use crate::testdata::TestData;
+use chrono::{DateTime, Timelike, Utc};
+use postgres_types::ToSql;
+use std::boxed::Box;
+use uuid::Uuid;
+
+/// Test Data for a World Member record
+#[derive(Debug, PartialEq, Clone)]
+pub struct WorldMember {
+  pub world_id: Uuid,
+  pub user_id: Uuid,
+  pub role: String,
+  pub created: DateTime<Utc>,
+  pub updated: DateTime<Utc>,
+}
+
+impl Default for WorldMember {
+  /// Generate a default set of values for the test World Member structure
+  fn default() -> Self {
+    Self {
+      world_id: Uuid::new_v4(),
+      user_id: Uuid::new_v4(),
+      role: "viewer".to_owned(),
+      created: Utc::now().with_nanosecond(0).unwrap(),
+      updated: Utc::now().with_nanosecond(0).unwrap(),
+    }
+  }
+}
+
+impl TestData for WorldMember {
+  fn sql(&self) -> String {
+    "INSERT INTO world_members(world_id, user_id, role, created, updated) VALUES ($1, $2, $3, $4, $5)".to_owned()
+  }
+
+  fn binds(&self) -> Vec<Box<(dyn ToSql + Sync)>> {
+    vec![
+      Box::new(self.world_id),
+      Box::new(self.user_id),
+      Box::new(self.role.clone()),
+      Box::new(self.created),
+      Box::new(self.updated),
+    ]
+  }
+}
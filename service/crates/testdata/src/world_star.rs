@@ -0,0 +1,38 @@
+use crate::testdata::TestData;
+use chrono::{DateTime, Timelike, Utc};
+use postgres_types::ToSql;
+use std::boxed::Box;
+use uuid::Uuid;
+
+/// Test Data for a World Star record
+#[derive(Debug, PartialEq, Clone)]
+pub struct WorldStar {
+  pub world_id: Uuid,
+  pub user_id: Uuid,
+  pub created: DateTime<Utc>,
+}
+
+impl Default for WorldStar {
+  /// Generate a default set of values for the test World Star structure
+  fn default() -> Self {
+    Self {
+      world_id: Uuid::new_v4(),
+      user_id: Uuid::new_v4(),
+      created: Utc::now().with_nanosecond(0).unwrap(),
+    }
+  }
+}
+
+impl TestData for WorldStar {
+  fn sql(&self) -> String {
+    "INSERT INTO world_stars(world_id, user_id, created) VALUES ($1, $2, $3)".to_owned()
+  }
+
+  fn binds(&self) -> Vec<Box<(dyn ToSql + Sync)>> {
+    vec![
+      Box::new(self.world_id),
+      Box::new(self.user_id),
+      Box::new(self.created),
+    ]
+  }
+}
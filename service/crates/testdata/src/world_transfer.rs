@@ -0,0 +1,41 @@
+use crate::testdata::TestData;
+use chrono::{DateTime, Timelike, Utc};
+use postgres_types::ToSql;
+use std::boxed::Box;
+use uuid::Uuid;
+
+/// Test Data for a World Transfer record
+#[derive(Debug, PartialEq, Clone)]
+pub struct WorldTransfer {
+  pub world_id: Uuid,
+  pub from_user_id: Uuid,
+  pub to_user_id: Uuid,
+  pub created: DateTime<Utc>,
+}
+
+impl Default for WorldTransfer {
+  /// Generate a default set of values for the test World Transfer structure
+  fn default() -> Self {
+    Self {
+      world_id: Uuid::new_v4(),
+      from_user_id: Uuid::new_v4(),
+      to_user_id: Uuid::new_v4(),
+      created: Utc::now().with_nanosecond(0).unwrap(),
+    }
+  }
+}
+
+impl TestData for WorldTransfer {
+  fn sql(&self) -> String {
+    "INSERT INTO world_transfers(world_id, from_user_id, to_user_id, created) VALUES ($1, $2, $3, $4)".to_owned()
+  }
+
+  fn binds(&self) -> Vec<Box<(dyn ToSql + Sync)>> {
+    vec![
+      Box::new(self.world_id),
+      Box::new(self.from_user_id),
+      Box::new(self.to_user_id),
+      Box::new(self.created),
+    ]
+  }
+}
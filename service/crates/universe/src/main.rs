@@ -1,9 +1,61 @@
+use config::ConfigError;
 use dotenv::dotenv;
+use std::sync::Arc;
 use tracing::{debug, info};
+use universe_storage::{BlobStore, LocalBlobStore, S3BlobStore, S3Config, UrlSigner};
 
 mod settings;
 
-fn main() {
+/// Get the value of a setting that is required by the chosen storage backend
+fn required_setting(value: &Option<String>, name: &str) -> Result<String, ConfigError> {
+    value
+        .clone()
+        .ok_or_else(|| ConfigError::NotFound(name.to_owned()))
+}
+
+/// Build the Blob Store to keep uploaded files in, based on the settings
+///
+/// # Arguments
+/// * `settings` The settings to build the Blob Store from
+/// * `url_signer` The means to generate signed URLs for blobs that the backend can't sign itself
+///
+/// # Returns
+/// The Blob Store, or the reason that the storage settings weren't usable
+fn build_blob_store(
+    settings: &settings::Settings,
+    url_signer: UrlSigner,
+) -> Result<Arc<dyn BlobStore>, ConfigError> {
+    match settings.storage_backend.as_deref() {
+        Some("s3") => {
+            let store = S3BlobStore::new(S3Config {
+                endpoint: required_setting(&settings.s3_endpoint, "s3_endpoint")?,
+                region: settings
+                    .s3_region
+                    .clone()
+                    .unwrap_or_else(|| "us-east-1".to_owned()),
+                bucket: required_setting(&settings.s3_bucket, "s3_bucket")?,
+                access_key_id: required_setting(&settings.s3_access_key_id, "s3_access_key_id")?,
+                secret_access_key: required_setting(
+                    &settings.s3_secret_access_key,
+                    "s3_secret_access_key",
+                )?,
+            })
+            .map_err(|e| ConfigError::Message(format!("Invalid S3 storage settings: {}", e)))?;
+
+            Ok(Arc::new(store))
+        }
+        Some("local") | None => Ok(Arc::new(LocalBlobStore::new(
+            settings.storage_path.as_deref().unwrap_or("storage"),
+            url_signer,
+        ))),
+        Some(backend) => Err(ConfigError::Message(format!(
+            "Unknown storage backend: {}",
+            backend
+        ))),
+    }
+}
+
+fn main() -> Result<(), ConfigError> {
     dotenv().ok();
 
     tracing_subscriber::fmt::init();
@@ -12,15 +64,27 @@ fn main() {
     let migrations_path = service_base.join("migrations");
     let migrations_glob = format!("{}/**/*.sql", migrations_path.to_str().unwrap());
 
-    let settings = settings::Settings::new();
+    let settings = settings::Settings::new()?;
     debug!("Universe settings: {:?}", settings);
 
+    let url_signer = UrlSigner::new(
+        format!("{}/storage", settings.storage_url.as_deref().unwrap_or("")),
+        &settings.storage_signing_key,
+    );
+    let blob_store = build_blob_store(&settings, url_signer.clone())?;
+
     let service = universe_webapp::Service::new(
         &settings.database_url,
         settings.port,
         &settings.access_token_key,
         &migrations_glob,
+        chrono::Duration::days(settings.world_retention_days.unwrap_or(30)),
+        blob_store,
+        settings.storage_user_quota,
+        url_signer,
     );
     info!("Starting Universe");
     service.launch();
+
+    Ok(())
 }
@@ -1,4 +1,4 @@
-use config::{Config, Environment};
+use config::{Config, ConfigError, Environment};
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize)]
@@ -6,13 +6,24 @@ pub struct Settings {
     pub port: Option<u16>,
     pub database_url: String,
     pub access_token_key: String,
+    pub world_retention_days: Option<i64>,
+    pub storage_backend: Option<String>,
+    pub storage_path: Option<String>,
+    pub storage_url: Option<String>,
+    pub storage_signing_key: String,
+    pub storage_user_quota: Option<u64>,
+    pub s3_endpoint: Option<String>,
+    pub s3_region: Option<String>,
+    pub s3_bucket: Option<String>,
+    pub s3_access_key_id: Option<String>,
+    pub s3_secret_access_key: Option<String>,
 }
 
 impl Settings {
-    pub fn new() -> Settings {
+    pub fn new() -> Result<Settings, ConfigError> {
         let mut s = Config::new();
-        s.merge(Environment::default()).unwrap();
+        s.merge(Environment::default())?;
 
-        s.try_into().unwrap()
+        s.try_into()
     }
 }
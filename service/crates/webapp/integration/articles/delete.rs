@@ -0,0 +1,142 @@
+use crate::{authenticate_user, build_headers, build_json_body, ServiceWrapper};
+use insta::{assert_json_snapshot, assert_snapshot};
+use test_env_log::test;
+use universe_testdata::{seed, Article, User, World};
+
+fn seed_articles(service: &ServiceWrapper) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    ..Default::default()
+  };
+  let history = Article {
+    article_id: uuid::Uuid::parse_str("0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f01").unwrap(),
+    world_id: world.world_id,
+    title: "History".to_owned(),
+    slug: "history".to_owned(),
+    ..Default::default()
+  };
+  let first_age = Article {
+    article_id: uuid::Uuid::parse_str("0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f02").unwrap(),
+    world_id: world.world_id,
+    parent_id: Some(history.article_id),
+    title: "The First Age".to_owned(),
+    slug: "first-age".to_owned(),
+    ..Default::default()
+  };
+  seed(
+    service.database(),
+    vec![&user, &world, &history, &first_age],
+  );
+
+  user
+}
+
+#[test]
+fn test_delete_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_articles(&service);
+
+  let response = service
+    .delete(
+      "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles/0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f02",
+    )
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_delete_success() {
+  let service = ServiceWrapper::default();
+  let user = seed_articles(&service);
+
+  let response = service
+    .delete(
+      "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles/0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f02",
+    )
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 204 No Content.
+  Server: Rocket
+  "###);
+
+  let response = service
+    .get(
+      "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles/0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f02",
+    )
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_delete_with_children() {
+  let service = ServiceWrapper::default();
+  let user = seed_articles(&service);
+
+  let mut response = service
+    .delete(
+      "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles/0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f01",
+    )
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 409 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 409,
+    "title": "The article has other articles under it",
+    "type": "tag:universe,2020:articles/problems/has-children"
+  }
+  "###);
+}
+
+#[test]
+fn test_delete_unknown_article() {
+  let service = ServiceWrapper::default();
+  let user = seed_articles(&service);
+
+  let mut response = service
+    .delete(
+      "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles/0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f09",
+    )
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested article could not be found",
+    "type": "tag:universe,2020:articles/problems/unknown-article"
+  }
+  "###);
+}
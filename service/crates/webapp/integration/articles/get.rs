@@ -0,0 +1,347 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use test_env_log::test;
+use universe_testdata::{seed, Article, User, World};
+
+fn seed_articles(service: &ServiceWrapper, visibility: &str) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    visibility: visibility.to_owned(),
+    ..Default::default()
+  };
+  let history = Article {
+    article_id: uuid::Uuid::parse_str("0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f01").unwrap(),
+    version: uuid::Uuid::parse_str("5d1c6a7e-3b2f-4c8d-9e0a-1f2b3c4d5e01").unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    title: "History".to_owned(),
+    slug: "history".to_owned(),
+    body: "The *history* of the world".to_owned(),
+    ..Default::default()
+  };
+  let first_age = Article {
+    article_id: uuid::Uuid::parse_str("0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f02").unwrap(),
+    version: uuid::Uuid::parse_str("5d1c6a7e-3b2f-4c8d-9e0a-1f2b3c4d5e02").unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    parent_id: Some(history.article_id),
+    title: "The First Age".to_owned(),
+    slug: "first-age".to_owned(),
+    body: "Long ago".to_owned(),
+    ..Default::default()
+  };
+  let geography = Article {
+    article_id: uuid::Uuid::parse_str("0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f03").unwrap(),
+    version: uuid::Uuid::parse_str("5d1c6a7e-3b2f-4c8d-9e0a-1f2b3c4d5e03").unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    title: "Geography".to_owned(),
+    slug: "geography".to_owned(),
+    body: "Mountains and rivers".to_owned(),
+    ..Default::default()
+  };
+  seed(
+    service.database(),
+    vec![&user, &world, &history, &first_age, &geography],
+  );
+
+  user
+}
+
+#[test]
+fn test_get_article() {
+  let service = ServiceWrapper::default();
+  seed_articles(&service, "public");
+
+  let mut response = service
+    .get(
+      "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles/0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f02",
+    )
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles/0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f02>; rel="self"
+  Accept-Patch: application/merge-patch+json
+  ETag: "5d1c6a7e-3b2f-4c8d-9e0a-1f2b3c4d5e02"
+  Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT
+  Cache-Control: public, max-age=3600
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/articles/{articleId}", "get", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "body": "Long ago",
+    "bodyHtml": "<p>Long ago</p>\n",
+    "id": "0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f02",
+    "parent": "0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f01",
+    "slug": "first-age",
+    "title": "The First Age",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_unknown_article() {
+  let service = ServiceWrapper::default();
+  seed_articles(&service, "public");
+
+  let mut response = service
+    .get(
+      "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles/0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f09",
+    )
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested article could not be found",
+    "type": "tag:universe,2020:articles/problems/unknown-article"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_article_private_world_anonymous() {
+  let service = ServiceWrapper::default();
+  seed_articles(&service, "private");
+
+  let mut response = service
+    .get(
+      "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles/0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f01",
+    )
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested world could not be found",
+    "type": "tag:universe,2020:worlds/problems/unknown-world"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_article_private_world_owner() {
+  let service = ServiceWrapper::default();
+  let user = seed_articles(&service, "private");
+
+  let mut response = service
+    .get(
+      "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles/0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f01",
+    )
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles/0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f01>; rel="self"
+  Accept-Patch: application/merge-patch+json
+  ETag: "5d1c6a7e-3b2f-4c8d-9e0a-1f2b3c4d5e01"
+  Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT
+  Cache-Control: private, max-age=3600
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "body": "The *history* of the world",
+    "bodyHtml": "<p>The <em>history</em> of the world</p>\n",
+    "id": "0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f01",
+    "slug": "history",
+    "title": "History",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_list_all_articles() {
+  let service = ServiceWrapper::default();
+  seed_articles(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Cache-Control: public, max-age=3600
+  Vary: Authorization
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/articles", "get", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "entries": [
+      {
+        "body": "Mountains and rivers",
+        "bodyHtml": "<p>Mountains and rivers</p>\n",
+        "id": "0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f03",
+        "slug": "geography",
+        "title": "Geography",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "body": "The *history* of the world",
+        "bodyHtml": "<p>The <em>history</em> of the world</p>\n",
+        "id": "0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f01",
+        "slug": "history",
+        "title": "History",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "body": "Long ago",
+        "bodyHtml": "<p>Long ago</p>\n",
+        "id": "0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f02",
+        "parent": "0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f01",
+        "slug": "first-age",
+        "title": "The First Age",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      }
+    ],
+    "total": 3
+  }
+  "###);
+}
+
+#[test]
+fn test_list_top_level_articles() {
+  let service = ServiceWrapper::default();
+  seed_articles(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles?parent=none")
+    .dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "entries": [
+      {
+        "body": "Mountains and rivers",
+        "bodyHtml": "<p>Mountains and rivers</p>\n",
+        "id": "0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f03",
+        "slug": "geography",
+        "title": "Geography",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "body": "The *history* of the world",
+        "bodyHtml": "<p>The <em>history</em> of the world</p>\n",
+        "id": "0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f01",
+        "slug": "history",
+        "title": "History",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      }
+    ],
+    "total": 2
+  }
+  "###);
+}
+
+#[test]
+fn test_list_child_articles() {
+  let service = ServiceWrapper::default();
+  seed_articles(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles?parent=0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f01")
+    .dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "entries": [
+      {
+        "body": "Long ago",
+        "bodyHtml": "<p>Long ago</p>\n",
+        "id": "0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f02",
+        "parent": "0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f01",
+        "slug": "first-age",
+        "title": "The First Age",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      }
+    ],
+    "total": 1
+  }
+  "###);
+}
+
+#[test]
+fn test_list_articles_invalid_parent() {
+  let service = ServiceWrapper::default();
+  seed_articles(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles?parent=history")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "parent",
+        "title": "The parent article is not an article of the world",
+        "type": "tag:universe,2020:articles/validation-errors/parent/unknown"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_list_articles_unknown_world() {
+  let service = ServiceWrapper::default();
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested world could not be found",
+    "type": "tag:universe,2020:worlds/problems/unknown-world"
+  }
+  "###);
+}
@@ -0,0 +1,4 @@
+mod delete;
+mod get;
+mod patch;
+mod post;
@@ -0,0 +1,256 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use rocket::http::ContentType;
+use serde_json::json;
+use std::str::FromStr;
+use test_env_log::test;
+use universe_testdata::{seed, Article, User, World};
+
+fn seed_articles(service: &ServiceWrapper) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    ..Default::default()
+  };
+  let history = Article {
+    article_id: uuid::Uuid::parse_str("0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f01").unwrap(),
+    world_id: world.world_id,
+    title: "History".to_owned(),
+    slug: "history".to_owned(),
+    ..Default::default()
+  };
+  let first_age = Article {
+    article_id: uuid::Uuid::parse_str("0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f02").unwrap(),
+    world_id: world.world_id,
+    parent_id: Some(history.article_id),
+    title: "The First Age".to_owned(),
+    slug: "first-age".to_owned(),
+    ..Default::default()
+  };
+  let geography = Article {
+    article_id: uuid::Uuid::parse_str("0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f03").unwrap(),
+    world_id: world.world_id,
+    title: "Geography".to_owned(),
+    slug: "geography".to_owned(),
+    ..Default::default()
+  };
+  seed(
+    service.database(),
+    vec![&user, &world, &history, &first_age, &geography],
+  );
+
+  user
+}
+
+#[test]
+fn test_patch_not_member() {
+  let service = ServiceWrapper::default();
+  seed_articles(&service);
+  let other_user = User {
+    username: "otheruser".to_owned(),
+    email: "other@example.com".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&other_user]);
+
+  let mut response = service
+    .patch(
+      "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles/0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f01",
+    )
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .body(json!({ "title": "Stolen" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 403 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 403,
+    "title": "You are not permitted to perform this request",
+    "type": "tag:universe,2020:problems/authentication/forbidden"
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_unknown_article() {
+  let service = ServiceWrapper::default();
+  let user = seed_articles(&service);
+
+  let mut response = service
+    .patch(
+      "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles/0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f09",
+    )
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "title": "Missing" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested article could not be found",
+    "type": "tag:universe,2020:articles/problems/unknown-article"
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_with_differences() {
+  let service = ServiceWrapper::default();
+  let user = seed_articles(&service);
+
+  let mut response = service
+    .patch(
+      "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles/0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f03",
+    )
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "title": "  Rivers  ",
+        "slug": "rivers",
+        "body": "Where the *water* flows",
+        "parent": "0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f02"
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(
+    &body,
+    "/worlds/{worldId}/articles/{articleId}",
+    "patch",
+    200,
+  );
+  assert_json_snapshot!(body, @r###"
+  {
+    "body": "Where the *water* flows",
+    "bodyHtml": "<p>Where the <em>water</em> flows</p>\n",
+    "id": "0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f03",
+    "parent": "0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f02",
+    "slug": "rivers",
+    "title": "Rivers",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_move_to_top_level() {
+  let service = ServiceWrapper::default();
+  let user = seed_articles(&service);
+
+  let mut response = service
+    .patch(
+      "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles/0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f02",
+    )
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "parent": null }).to_string())
+    .dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "body": "This is a test article",
+    "bodyHtml": "<p>This is a test article</p>\n",
+    "id": "0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f02",
+    "slug": "first-age",
+    "title": "The First Age",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_parent_cycle() {
+  let service = ServiceWrapper::default();
+  let user = seed_articles(&service);
+
+  let mut response = service
+    .patch(
+      "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles/0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f01",
+    )
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "parent": "0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f02" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "parent",
+        "title": "An article may not be moved under itself",
+        "type": "tag:universe,2020:articles/validation-errors/parent/cycle"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_duplicate_slug() {
+  let service = ServiceWrapper::default();
+  let user = seed_articles(&service);
+
+  let mut response = service
+    .patch(
+      "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles/0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f03",
+    )
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "slug": "history" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "slug",
+        "title": "The slug is already used by another article in the world",
+        "type": "tag:universe,2020:articles/validation-errors/slug/duplicate"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
@@ -0,0 +1,317 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, build_rewrite_headers,
+  regex_replace, ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use rocket::http::ContentType;
+use serde_json::json;
+use test_env_log::test;
+use universe_testdata::{seed, Article, User, World};
+
+fn seed_world(service: &ServiceWrapper) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    ..Default::default()
+  };
+  let history = Article {
+    article_id: uuid::Uuid::parse_str("0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f01").unwrap(),
+    world_id: world.world_id,
+    title: "History".to_owned(),
+    slug: "history".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&user, &world, &history]);
+
+  user
+}
+
+#[test]
+fn test_post_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+
+  let response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles")
+    .header(ContentType::JSON)
+    .body(json!({ "title": "Geography" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_post_private_world_not_member() {
+  let service = ServiceWrapper::default();
+  let user = seed_world(&service);
+  let other_user = User {
+    username: "otheruser".to_owned(),
+    email: "other@example.com".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("5d6c1b2a-8e4f-4a3b-9c7d-2e1f0a9b8c7d").unwrap(),
+    owner_id: user.user_id,
+    name: "Secret World".to_owned(),
+    slug: "secret-world".to_owned(),
+    visibility: "private".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&other_user, &world]);
+
+  let mut response = service
+    .post("/worlds/5d6c1b2a-8e4f-4a3b-9c7d-2e1f0a9b8c7d/articles")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .body(json!({ "title": "Geography" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested world could not be found",
+    "type": "tag:universe,2020:worlds/problems/unknown-world"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_not_member() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+  let other_user = User {
+    username: "otheruser".to_owned(),
+    email: "other@example.com".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&other_user]);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .body(json!({ "title": "Geography" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 403 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 403,
+    "title": "You are not permitted to perform this request",
+    "type": "tag:universe,2020:problems/authentication/forbidden"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_success() {
+  let service = ServiceWrapper::default();
+  let user = seed_world(&service);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "title": "  The First Age  ",
+        "slug": "first-age",
+        "body": "Long *ago*",
+        "parent": "0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f01"
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_rewrite_headers(&response, |h| {
+    regex_replace(h, r#"/articles/[a-z0-9]{8}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{12}"#, "/articles/[uuid]")
+  }), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles/[uuid]>; rel="canonical"
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/articles", "post", 200);
+  assert_json_snapshot!(body, {
+        ".id" => "[uuid]",
+    },
+    @r###"
+  {
+    "body": "Long *ago*",
+    "bodyHtml": "<p>Long <em>ago</em></p>\n",
+    "id": "[uuid]",
+    "parent": "0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f01",
+    "slug": "first-age",
+    "title": "The First Age",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_generated_slug_already_used() {
+  let service = ServiceWrapper::default();
+  let user = seed_world(&service);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "title": "History" }).to_string())
+    .dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), {
+        ".id" => "[uuid]",
+    },
+    @r###"
+  {
+    "body": "",
+    "bodyHtml": "",
+    "id": "[uuid]",
+    "slug": "history-2",
+    "title": "History",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_duplicate_slug() {
+  let service = ServiceWrapper::default();
+  let user = seed_world(&service);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "title": "More History", "slug": "HISTORY" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "slug",
+        "title": "The slug is already used by another article in the world",
+        "type": "tag:universe,2020:articles/validation-errors/slug/duplicate"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_invalid_values() {
+  let service = ServiceWrapper::default();
+  let user = seed_world(&service);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "title": " ", "slug": "no spaces", "parent": "history" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "title",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      },
+      {
+        "field": "slug",
+        "title": "The slug may only contain letters, digits, hyphens and underscores",
+        "type": "tag:universe,2020:worlds/validation-errors/slug/invalid-characters"
+      },
+      {
+        "field": "parent",
+        "title": "The parent article is not an article of the world",
+        "type": "tag:universe,2020:articles/validation-errors/parent/unknown"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_unknown_parent() {
+  let service = ServiceWrapper::default();
+  let user = seed_world(&service);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/articles")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "title": "Geography",
+        "parent": "0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f09"
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "parent",
+        "title": "The parent article is not an article of the world",
+        "type": "tag:universe,2020:articles/validation-errors/parent/unknown"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
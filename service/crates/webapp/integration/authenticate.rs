@@ -0,0 +1,34 @@
+use crate::{build_json_body, ServiceWrapper};
+use rocket::http::{ContentType, Header, Status};
+use serde_json::json;
+use universe_testdata::User;
+
+/// Authenticate as the given test user, returning the `Authorization` header to use on requests
+pub fn authenticate_user<'h>(service: &ServiceWrapper, user: &User) -> Option<Header<'h>> {
+    authenticate(&service, &user.username, &user.password)
+}
+
+/// Authenticate with the given credentials, returning the `Authorization` header to use on requests
+pub fn authenticate<'h>(
+    service: &ServiceWrapper,
+    username: &str,
+    password: &str,
+) -> Option<Header<'h>> {
+    let req = service.post("/login").header(ContentType::JSON).body(
+        json!({
+            "username": username,
+            "password": password
+        })
+        .to_string(),
+    );
+    let mut response = req.dispatch();
+    if response.status() != Status::Ok {
+        return None;
+    }
+
+    let body = build_json_body(&mut response);
+    body.get("accessToken")
+        .and_then(|token| token.get("token"))
+        .and_then(|token| token.as_str())
+        .map(|token| Header::new("Authorization", format!("Bearer {}", token)))
+}
@@ -0,0 +1,369 @@
+use crate::{assert_response_schema, build_headers, build_json_body, ServiceWrapper};
+use insta::{assert_json_snapshot, assert_snapshot};
+use serde_json::json;
+use test_env_log::test;
+use universe_testdata::{seed, Calendar, TimelineEvent, User, World};
+
+fn seed_calendars(service: &ServiceWrapper, visibility: &str) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    visibility: visibility.to_owned(),
+    ..Default::default()
+  };
+  let shire = Calendar {
+    calendar_id: uuid::Uuid::parse_str("3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01").unwrap(),
+    version: uuid::Uuid::parse_str("4c6e8a0b-2d3f-4b7c-9e1a-3f5b7d9c1e01").unwrap(),
+    created: "2020-03-11T13:00:31Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    name: "Shire Reckoning".to_owned(),
+    definition: json!({
+      "months": [
+        { "name": "Afteryule", "days": 30 },
+        { "name": "Solmath", "days": 30, "leapDays": 1 },
+        { "name": "Rethe", "days": 30 },
+        { "name": "Astron", "days": 30 }
+      ],
+      "weekdays": ["Sterday", "Sunday", "Monday", "Trewsday", "Hevensday", "Mersday", "Highday"],
+      "leapRule": { "every": 4, "exceptEvery": 100 },
+      "eras": [
+        { "name": "First Age", "abbreviation": "FA", "startYear": 1 },
+        { "name": "Second Age", "abbreviation": "SA", "startYear": 101 }
+      ]
+    }),
+  };
+  let kings = Calendar {
+    calendar_id: uuid::Uuid::parse_str("3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d02").unwrap(),
+    version: uuid::Uuid::parse_str("4c6e8a0b-2d3f-4b7c-9e1a-3f5b7d9c1e02").unwrap(),
+    created: "2020-03-11T13:00:32Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    name: "Kings' Reckoning".to_owned(),
+    definition: json!({
+      "months": [{ "name": "Narwain", "days": 365 }]
+    }),
+  };
+  let founding = TimelineEvent {
+    event_id: uuid::Uuid::parse_str("6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c01").unwrap(),
+    version: uuid::Uuid::parse_str("7f9b1d3e-5a7c-4e9f-b1d3-6c8e0a2b4d01").unwrap(),
+    created: "2020-03-11T13:00:33Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    calendar_id: shire.calendar_id,
+    title: "Founding of the Shire".to_owned(),
+    description: "Marcho and Blanco cross the *Brandywine*".to_owned(),
+    year: 1,
+    month: 1,
+    day: 1,
+    day_number: 0,
+  };
+  let coronation = TimelineEvent {
+    event_id: uuid::Uuid::parse_str("6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c02").unwrap(),
+    version: uuid::Uuid::parse_str("7f9b1d3e-5a7c-4e9f-b1d3-6c8e0a2b4d02").unwrap(),
+    created: "2020-03-11T13:00:34Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    calendar_id: shire.calendar_id,
+    title: "Coronation".to_owned(),
+    description: "".to_owned(),
+    year: 5,
+    month: 3,
+    day: 1,
+    day_number: 541,
+  };
+  let battle = TimelineEvent {
+    event_id: uuid::Uuid::parse_str("6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c03").unwrap(),
+    version: uuid::Uuid::parse_str("7f9b1d3e-5a7c-4e9f-b1d3-6c8e0a2b4d03").unwrap(),
+    created: "2020-03-11T13:00:35Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    calendar_id: shire.calendar_id,
+    title: "Battle of Greenfields".to_owned(),
+    description: "".to_owned(),
+    year: 3,
+    month: 2,
+    day: 15,
+    day_number: 284,
+  };
+  seed(
+    service.database(),
+    vec![&user, &world, &shire, &kings, &founding, &coronation, &battle],
+  );
+
+  user
+}
+
+#[test]
+fn test_get_date() {
+  let service = ServiceWrapper::default();
+  seed_calendars(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/dates?date=3-2-15")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Cache-Control: public, max-age=3600
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/calendars/{calendarId}/dates", "get", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "day": 15,
+    "era": "FA",
+    "eraYear": 3,
+    "month": 2,
+    "monthName": "Solmath",
+    "text": "15 Solmath 3 FA",
+    "weekday": "Hevensday",
+    "year": 3
+  }
+  "###);
+}
+
+#[test]
+fn test_get_date_across_eras() {
+  let service = ServiceWrapper::default();
+  seed_calendars(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/dates?date=30%20astron%20100%20fa&add_days=1")
+    .dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/calendars/{calendarId}/dates", "get", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "day": 1,
+    "era": "SA",
+    "eraYear": 1,
+    "month": 1,
+    "monthName": "Afteryule",
+    "text": "1 Afteryule 1 SA",
+    "weekday": "Mersday",
+    "year": 101
+  }
+  "###);
+}
+
+#[test]
+fn test_get_date_add_to_leap_day() {
+  let service = ServiceWrapper::default();
+  seed_calendars(&service, "public");
+
+  // Solmath only has 31 days in leap years, so a year later is the last day of Solmath instead
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/dates?date=31%20Solmath%204%20FA&add_years=1&add_months=1&add_days=-1")
+    .dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "day": 29,
+    "era": "FA",
+    "eraYear": 5,
+    "month": 3,
+    "monthName": "Rethe",
+    "text": "29 Rethe 5 FA",
+    "weekday": "Monday",
+    "year": 5
+  }
+  "###);
+}
+
+#[test]
+fn test_get_date_invalid() {
+  let service = ServiceWrapper::default();
+  seed_calendars(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/dates?date=1%20High%20Sun%203")
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "date",
+        "title": "The calendar has no month with that name",
+        "type": "tag:universe,2020:calendars/validation-errors/date/unknown-month"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/dates?date=31%20Solmath%203%20FA")
+    .dispatch();
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "date",
+        "title": "The month doesn't have that many days",
+        "type": "tag:universe,2020:calendars/validation-errors/date/day-out-of-range"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/dates?date=1%20Rethe%20101%20FA")
+    .dispatch();
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "date",
+        "title": "The year is not part of the era",
+        "type": "tag:universe,2020:calendars/validation-errors/date/year-outside-era"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/dates")
+    .dispatch();
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "date",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_date_difference() {
+  let service = ServiceWrapper::default();
+  seed_calendars(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/dates/difference?from=5-3-1&to=1%20Afteryule%201%20FA")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Cache-Control: public, max-age=3600
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(
+    &body,
+    "/worlds/{worldId}/calendars/{calendarId}/dates/difference",
+    "get",
+    200,
+  );
+  assert_json_snapshot!(body, @r###"
+  {
+    "days": -541,
+    "from": {
+      "day": 1,
+      "era": "FA",
+      "eraYear": 5,
+      "month": 3,
+      "monthName": "Rethe",
+      "text": "1 Rethe 5 FA",
+      "weekday": "Monday",
+      "year": 5
+    },
+    "order": "after",
+    "to": {
+      "day": 1,
+      "era": "FA",
+      "eraYear": 1,
+      "month": 1,
+      "monthName": "Afteryule",
+      "text": "1 Afteryule 1 FA",
+      "weekday": "Sterday",
+      "year": 1
+    }
+  }
+  "###);
+}
+
+#[test]
+fn test_get_date_difference_invalid() {
+  let service = ServiceWrapper::default();
+  seed_calendars(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/dates/difference?from=yesterday")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "from",
+        "title": "The date was malformed",
+        "type": "tag:universe,2020:calendars/validation-errors/date/malformed"
+      },
+      {
+        "field": "to",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_date_private_world() {
+  let service = ServiceWrapper::default();
+  seed_calendars(&service, "private");
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/dates?date=3-2-15")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
@@ -0,0 +1,179 @@
+use crate::{authenticate_user, build_headers, build_json_body, ServiceWrapper};
+use insta::{assert_json_snapshot, assert_snapshot};
+use serde_json::json;
+use test_env_log::test;
+use universe_testdata::{seed, Calendar, TimelineEvent, User, World};
+
+fn seed_calendars(service: &ServiceWrapper, visibility: &str) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    visibility: visibility.to_owned(),
+    ..Default::default()
+  };
+  let shire = Calendar {
+    calendar_id: uuid::Uuid::parse_str("3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01").unwrap(),
+    version: uuid::Uuid::parse_str("4c6e8a0b-2d3f-4b7c-9e1a-3f5b7d9c1e01").unwrap(),
+    created: "2020-03-11T13:00:31Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    name: "Shire Reckoning".to_owned(),
+    definition: json!({
+      "months": [
+        { "name": "Afteryule", "days": 30 },
+        { "name": "Solmath", "days": 30, "leapDays": 1 },
+        { "name": "Rethe", "days": 30 },
+        { "name": "Astron", "days": 30 }
+      ],
+      "weekdays": ["Sterday", "Sunday", "Monday", "Trewsday", "Hevensday", "Mersday", "Highday"],
+      "leapRule": { "every": 4, "exceptEvery": 100 },
+      "eras": [
+        { "name": "First Age", "abbreviation": "FA", "startYear": 1 },
+        { "name": "Second Age", "abbreviation": "SA", "startYear": 101 }
+      ]
+    }),
+  };
+  let kings = Calendar {
+    calendar_id: uuid::Uuid::parse_str("3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d02").unwrap(),
+    version: uuid::Uuid::parse_str("4c6e8a0b-2d3f-4b7c-9e1a-3f5b7d9c1e02").unwrap(),
+    created: "2020-03-11T13:00:32Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    name: "Kings' Reckoning".to_owned(),
+    definition: json!({
+      "months": [{ "name": "Narwain", "days": 365 }]
+    }),
+  };
+  let founding = TimelineEvent {
+    event_id: uuid::Uuid::parse_str("6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c01").unwrap(),
+    version: uuid::Uuid::parse_str("7f9b1d3e-5a7c-4e9f-b1d3-6c8e0a2b4d01").unwrap(),
+    created: "2020-03-11T13:00:33Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    calendar_id: shire.calendar_id,
+    title: "Founding of the Shire".to_owned(),
+    description: "Marcho and Blanco cross the *Brandywine*".to_owned(),
+    year: 1,
+    month: 1,
+    day: 1,
+    day_number: 0,
+  };
+  let coronation = TimelineEvent {
+    event_id: uuid::Uuid::parse_str("6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c02").unwrap(),
+    version: uuid::Uuid::parse_str("7f9b1d3e-5a7c-4e9f-b1d3-6c8e0a2b4d02").unwrap(),
+    created: "2020-03-11T13:00:34Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    calendar_id: shire.calendar_id,
+    title: "Coronation".to_owned(),
+    description: "".to_owned(),
+    year: 5,
+    month: 3,
+    day: 1,
+    day_number: 541,
+  };
+  let battle = TimelineEvent {
+    event_id: uuid::Uuid::parse_str("6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c03").unwrap(),
+    version: uuid::Uuid::parse_str("7f9b1d3e-5a7c-4e9f-b1d3-6c8e0a2b4d03").unwrap(),
+    created: "2020-03-11T13:00:35Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    calendar_id: shire.calendar_id,
+    title: "Battle of Greenfields".to_owned(),
+    description: "".to_owned(),
+    year: 3,
+    month: 2,
+    day: 15,
+    day_number: 284,
+  };
+  seed(
+    service.database(),
+    vec![&user, &world, &shire, &kings, &founding, &coronation, &battle],
+  );
+
+  user
+}
+
+#[test]
+fn test_delete_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_calendars(&service, "public");
+
+  let response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_delete_success() {
+  let service = ServiceWrapper::default();
+  let user = seed_calendars(&service, "public");
+
+  let response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 204 No Content.
+  Server: Rocket
+  "###);
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01")
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/events/6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c01")
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_delete_unknown_calendar() {
+  let service = ServiceWrapper::default();
+  let user = seed_calendars(&service, "public");
+
+  let mut response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d09")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested calendar could not be found",
+    "type": "tag:universe,2020:calendars/problems/unknown-calendar"
+  }
+  "###);
+}
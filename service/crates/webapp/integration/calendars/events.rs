@@ -0,0 +1,745 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, build_rewrite_headers,
+  regex_replace, ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use rocket::http::ContentType;
+use serde_json::json;
+use std::str::FromStr;
+use test_env_log::test;
+use universe_testdata::{seed, Calendar, TimelineEvent, User, World};
+
+fn seed_calendars(service: &ServiceWrapper, visibility: &str) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    visibility: visibility.to_owned(),
+    ..Default::default()
+  };
+  let shire = Calendar {
+    calendar_id: uuid::Uuid::parse_str("3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01").unwrap(),
+    version: uuid::Uuid::parse_str("4c6e8a0b-2d3f-4b7c-9e1a-3f5b7d9c1e01").unwrap(),
+    created: "2020-03-11T13:00:31Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    name: "Shire Reckoning".to_owned(),
+    definition: json!({
+      "months": [
+        { "name": "Afteryule", "days": 30 },
+        { "name": "Solmath", "days": 30, "leapDays": 1 },
+        { "name": "Rethe", "days": 30 },
+        { "name": "Astron", "days": 30 }
+      ],
+      "weekdays": ["Sterday", "Sunday", "Monday", "Trewsday", "Hevensday", "Mersday", "Highday"],
+      "leapRule": { "every": 4, "exceptEvery": 100 },
+      "eras": [
+        { "name": "First Age", "abbreviation": "FA", "startYear": 1 },
+        { "name": "Second Age", "abbreviation": "SA", "startYear": 101 }
+      ]
+    }),
+  };
+  let kings = Calendar {
+    calendar_id: uuid::Uuid::parse_str("3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d02").unwrap(),
+    version: uuid::Uuid::parse_str("4c6e8a0b-2d3f-4b7c-9e1a-3f5b7d9c1e02").unwrap(),
+    created: "2020-03-11T13:00:32Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    name: "Kings' Reckoning".to_owned(),
+    definition: json!({
+      "months": [{ "name": "Narwain", "days": 365 }]
+    }),
+  };
+  let founding = TimelineEvent {
+    event_id: uuid::Uuid::parse_str("6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c01").unwrap(),
+    version: uuid::Uuid::parse_str("7f9b1d3e-5a7c-4e9f-b1d3-6c8e0a2b4d01").unwrap(),
+    created: "2020-03-11T13:00:33Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    calendar_id: shire.calendar_id,
+    title: "Founding of the Shire".to_owned(),
+    description: "Marcho and Blanco cross the *Brandywine*".to_owned(),
+    year: 1,
+    month: 1,
+    day: 1,
+    day_number: 0,
+  };
+  let coronation = TimelineEvent {
+    event_id: uuid::Uuid::parse_str("6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c02").unwrap(),
+    version: uuid::Uuid::parse_str("7f9b1d3e-5a7c-4e9f-b1d3-6c8e0a2b4d02").unwrap(),
+    created: "2020-03-11T13:00:34Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    calendar_id: shire.calendar_id,
+    title: "Coronation".to_owned(),
+    description: "".to_owned(),
+    year: 5,
+    month: 3,
+    day: 1,
+    day_number: 541,
+  };
+  let battle = TimelineEvent {
+    event_id: uuid::Uuid::parse_str("6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c03").unwrap(),
+    version: uuid::Uuid::parse_str("7f9b1d3e-5a7c-4e9f-b1d3-6c8e0a2b4d03").unwrap(),
+    created: "2020-03-11T13:00:35Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    calendar_id: shire.calendar_id,
+    title: "Battle of Greenfields".to_owned(),
+    description: "".to_owned(),
+    year: 3,
+    month: 2,
+    day: 15,
+    day_number: 284,
+  };
+  seed(
+    service.database(),
+    vec![&user, &world, &shire, &kings, &founding, &coronation, &battle],
+  );
+
+  user
+}
+
+#[test]
+fn test_list_events() {
+  let service = ServiceWrapper::default();
+  seed_calendars(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/events")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Cache-Control: public, max-age=3600
+  Vary: Authorization
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/calendars/{calendarId}/events", "get", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "entries": [
+      {
+        "calendar": "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01",
+        "date": {
+          "day": 1,
+          "era": "FA",
+          "eraYear": 1,
+          "month": 1,
+          "monthName": "Afteryule",
+          "text": "1 Afteryule 1 FA",
+          "weekday": "Sterday",
+          "year": 1
+        },
+        "description": "Marcho and Blanco cross the *Brandywine*",
+        "descriptionHtml": "<p>Marcho and Blanco cross the <em>Brandywine</em></p>\n",
+        "id": "6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c01",
+        "title": "Founding of the Shire",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "calendar": "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01",
+        "date": {
+          "day": 15,
+          "era": "FA",
+          "eraYear": 3,
+          "month": 2,
+          "monthName": "Solmath",
+          "text": "15 Solmath 3 FA",
+          "weekday": "Hevensday",
+          "year": 3
+        },
+        "description": "",
+        "descriptionHtml": "",
+        "id": "6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c03",
+        "title": "Battle of Greenfields",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "calendar": "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01",
+        "date": {
+          "day": 1,
+          "era": "FA",
+          "eraYear": 5,
+          "month": 3,
+          "monthName": "Rethe",
+          "text": "1 Rethe 5 FA",
+          "weekday": "Monday",
+          "year": 5
+        },
+        "description": "",
+        "descriptionHtml": "",
+        "id": "6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c02",
+        "title": "Coronation",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      }
+    ],
+    "total": 3
+  }
+  "###);
+}
+
+#[test]
+fn test_list_events_between_dates() {
+  let service = ServiceWrapper::default();
+  seed_calendars(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/events?from=1%20Afteryule%202%20FA&to=1%20Rethe%205%20FA")
+    .dispatch();
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "entries": [
+      {
+        "calendar": "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01",
+        "date": {
+          "day": 15,
+          "era": "FA",
+          "eraYear": 3,
+          "month": 2,
+          "monthName": "Solmath",
+          "text": "15 Solmath 3 FA",
+          "weekday": "Hevensday",
+          "year": 3
+        },
+        "description": "",
+        "descriptionHtml": "",
+        "id": "6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c03",
+        "title": "Battle of Greenfields",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "calendar": "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01",
+        "date": {
+          "day": 1,
+          "era": "FA",
+          "eraYear": 5,
+          "month": 3,
+          "monthName": "Rethe",
+          "text": "1 Rethe 5 FA",
+          "weekday": "Monday",
+          "year": 5
+        },
+        "description": "",
+        "descriptionHtml": "",
+        "id": "6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c02",
+        "title": "Coronation",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      }
+    ],
+    "total": 2
+  }
+  "###);
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/events?to=3-2-14")
+    .dispatch();
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "entries": [
+      {
+        "calendar": "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01",
+        "date": {
+          "day": 1,
+          "era": "FA",
+          "eraYear": 1,
+          "month": 1,
+          "monthName": "Afteryule",
+          "text": "1 Afteryule 1 FA",
+          "weekday": "Sterday",
+          "year": 1
+        },
+        "description": "Marcho and Blanco cross the *Brandywine*",
+        "descriptionHtml": "<p>Marcho and Blanco cross the <em>Brandywine</em></p>\n",
+        "id": "6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c01",
+        "title": "Founding of the Shire",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      }
+    ],
+    "total": 1
+  }
+  "###);
+}
+
+#[test]
+fn test_list_events_invalid_dates() {
+  let service = ServiceWrapper::default();
+  seed_calendars(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/events?from=1%20Narwain%202&to=3-5-1")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "from",
+        "title": "The calendar has no month with that name",
+        "type": "tag:universe,2020:calendars/validation-errors/date/unknown-month"
+      },
+      {
+        "field": "to",
+        "title": "The calendar doesn't have that many months",
+        "type": "tag:universe,2020:calendars/validation-errors/date/month-out-of-range"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_event() {
+  let service = ServiceWrapper::default();
+  seed_calendars(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/events/6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c01")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/events/6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c01>; rel="self"
+  Accept-Patch: application/merge-patch+json
+  ETag: "7f9b1d3e-5a7c-4e9f-b1d3-6c8e0a2b4d01"
+  Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT
+  Cache-Control: public, max-age=3600
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(
+    &body,
+    "/worlds/{worldId}/calendars/{calendarId}/events/{eventId}",
+    "get",
+    200,
+  );
+  assert_json_snapshot!(body, @r###"
+  {
+    "calendar": "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01",
+    "date": {
+      "day": 1,
+      "era": "FA",
+      "eraYear": 1,
+      "month": 1,
+      "monthName": "Afteryule",
+      "text": "1 Afteryule 1 FA",
+      "weekday": "Sterday",
+      "year": 1
+    },
+    "description": "Marcho and Blanco cross the *Brandywine*",
+    "descriptionHtml": "<p>Marcho and Blanco cross the <em>Brandywine</em></p>\n",
+    "id": "6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c01",
+    "title": "Founding of the Shire",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_event_wrong_calendar() {
+  let service = ServiceWrapper::default();
+  seed_calendars(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d02/events/6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c01")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested event could not be found",
+    "type": "tag:universe,2020:calendars/problems/unknown-event"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_event_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_calendars(&service, "public");
+
+  let response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/events")
+    .header(ContentType::JSON)
+    .body(json!({ "title": "Fell Winter", "date": "2 Rethe 4 FA" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_post_event_success() {
+  let service = ServiceWrapper::default();
+  let user = seed_calendars(&service, "public");
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/events")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "title": "  Fell Winter  ",
+        "description": "The Brandywine *freezes*",
+        "date": "2 Rethe 4 FA"
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_rewrite_headers(&response, |h| {
+    regex_replace(h, r#"/events/[a-z0-9]{8}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{12}"#, "/events/[uuid]")
+  }), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/events/[uuid]>; rel="canonical"
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/calendars/{calendarId}/events", "post", 200);
+  assert_json_snapshot!(body, {
+        ".id" => "[uuid]",
+    },
+    @r###"
+  {
+    "calendar": "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01",
+    "date": {
+      "day": 2,
+      "era": "FA",
+      "eraYear": 4,
+      "month": 3,
+      "monthName": "Rethe",
+      "text": "2 Rethe 4 FA",
+      "weekday": "Monday",
+      "year": 4
+    },
+    "description": "The Brandywine *freezes*",
+    "descriptionHtml": "<p>The Brandywine <em>freezes</em></p>\n",
+    "id": "[uuid]",
+    "title": "Fell Winter",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/events?from=3-1-1")
+    .dispatch();
+  assert_json_snapshot!(build_json_body(&mut response), {
+        ".entries[1].id" => "[uuid]",
+    },
+    @r###"
+  {
+    "entries": [
+      {
+        "calendar": "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01",
+        "date": {
+          "day": 15,
+          "era": "FA",
+          "eraYear": 3,
+          "month": 2,
+          "monthName": "Solmath",
+          "text": "15 Solmath 3 FA",
+          "weekday": "Hevensday",
+          "year": 3
+        },
+        "description": "",
+        "descriptionHtml": "",
+        "id": "6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c03",
+        "title": "Battle of Greenfields",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "calendar": "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01",
+        "date": {
+          "day": 2,
+          "era": "FA",
+          "eraYear": 4,
+          "month": 3,
+          "monthName": "Rethe",
+          "text": "2 Rethe 4 FA",
+          "weekday": "Monday",
+          "year": 4
+        },
+        "description": "The Brandywine *freezes*",
+        "descriptionHtml": "<p>The Brandywine <em>freezes</em></p>\n",
+        "id": "[uuid]",
+        "title": "Fell Winter",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "calendar": "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01",
+        "date": {
+          "day": 1,
+          "era": "FA",
+          "eraYear": 5,
+          "month": 3,
+          "monthName": "Rethe",
+          "text": "1 Rethe 5 FA",
+          "weekday": "Monday",
+          "year": 5
+        },
+        "description": "",
+        "descriptionHtml": "",
+        "id": "6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c02",
+        "title": "Coronation",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      }
+    ],
+    "total": 3
+  }
+  "###);
+}
+
+#[test]
+fn test_post_event_invalid() {
+  let service = ServiceWrapper::default();
+  let user = seed_calendars(&service, "public");
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/events")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "title": "  ", "date": "31 Rethe 4 FA" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "title",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      },
+      {
+        "field": "date",
+        "title": "The month doesn't have that many days",
+        "type": "tag:universe,2020:calendars/validation-errors/date/day-out-of-range"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_event_date() {
+  let service = ServiceWrapper::default();
+  let user = seed_calendars(&service, "public");
+
+  let mut response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/events/6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c03")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "date": "10 Afteryule 7 FA", "description": "Bullroarer Took *wins*" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_rewrite_headers(&response, |h| {
+    let h = regex_replace(h, r#"ETag: "[a-z0-9]{8}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{12}""#, r#"ETag: "a7fd01dc-dcf7-45dd-a932-0b6b263e17d0""#);
+    regex_replace(h, r#"^Last-Modified: .*$"#, "Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT")
+  }), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/events/6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c03>; rel="self"
+  Accept-Patch: application/merge-patch+json
+  ETag: "a7fd01dc-dcf7-45dd-a932-0b6b263e17d0"
+  Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT
+  Cache-Control: public, max-age=3600
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(
+    &body,
+    "/worlds/{worldId}/calendars/{calendarId}/events/{eventId}",
+    "patch",
+    200,
+  );
+  assert_json_snapshot!(body, @r###"
+  {
+    "calendar": "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01",
+    "date": {
+      "day": 10,
+      "era": "FA",
+      "eraYear": 7,
+      "month": 1,
+      "monthName": "Afteryule",
+      "text": "10 Afteryule 7 FA",
+      "weekday": "Monday",
+      "year": 7
+    },
+    "description": "Bullroarer Took *wins*",
+    "descriptionHtml": "<p>Bullroarer Took <em>wins</em></p>\n",
+    "id": "6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c03",
+    "title": "Battle of Greenfields",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/events")
+    .dispatch();
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "entries": [
+      {
+        "calendar": "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01",
+        "date": {
+          "day": 1,
+          "era": "FA",
+          "eraYear": 1,
+          "month": 1,
+          "monthName": "Afteryule",
+          "text": "1 Afteryule 1 FA",
+          "weekday": "Sterday",
+          "year": 1
+        },
+        "description": "Marcho and Blanco cross the *Brandywine*",
+        "descriptionHtml": "<p>Marcho and Blanco cross the <em>Brandywine</em></p>\n",
+        "id": "6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c01",
+        "title": "Founding of the Shire",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "calendar": "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01",
+        "date": {
+          "day": 1,
+          "era": "FA",
+          "eraYear": 5,
+          "month": 3,
+          "monthName": "Rethe",
+          "text": "1 Rethe 5 FA",
+          "weekday": "Monday",
+          "year": 5
+        },
+        "description": "",
+        "descriptionHtml": "",
+        "id": "6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c02",
+        "title": "Coronation",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "calendar": "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01",
+        "date": {
+          "day": 10,
+          "era": "FA",
+          "eraYear": 7,
+          "month": 1,
+          "monthName": "Afteryule",
+          "text": "10 Afteryule 7 FA",
+          "weekday": "Monday",
+          "year": 7
+        },
+        "description": "Bullroarer Took *wins*",
+        "descriptionHtml": "<p>Bullroarer Took <em>wins</em></p>\n",
+        "id": "6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c03",
+        "title": "Battle of Greenfields",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      }
+    ],
+    "total": 3
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_event_invalid() {
+  let service = ServiceWrapper::default();
+  let user = seed_calendars(&service, "public");
+
+  let mut response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/events/6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c03")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "title": "", "date": "10 Afteryule 7 TA" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "title",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      },
+      {
+        "field": "date",
+        "title": "The calendar has no era with that abbreviation",
+        "type": "tag:universe,2020:calendars/validation-errors/date/unknown-era"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_delete_event() {
+  let service = ServiceWrapper::default();
+  let user = seed_calendars(&service, "public");
+
+  let response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/events/6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c03")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 204 No Content.
+  Server: Rocket
+  "###);
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/events/6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c03")
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested event could not be found",
+    "type": "tag:universe,2020:calendars/problems/unknown-event"
+  }
+  "###);
+}
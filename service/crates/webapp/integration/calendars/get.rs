@@ -0,0 +1,352 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use serde_json::json;
+use test_env_log::test;
+use universe_testdata::{seed, Calendar, TimelineEvent, User, World};
+
+fn seed_calendars(service: &ServiceWrapper, visibility: &str) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    visibility: visibility.to_owned(),
+    ..Default::default()
+  };
+  let shire = Calendar {
+    calendar_id: uuid::Uuid::parse_str("3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01").unwrap(),
+    version: uuid::Uuid::parse_str("4c6e8a0b-2d3f-4b7c-9e1a-3f5b7d9c1e01").unwrap(),
+    created: "2020-03-11T13:00:31Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    name: "Shire Reckoning".to_owned(),
+    definition: json!({
+      "months": [
+        { "name": "Afteryule", "days": 30 },
+        { "name": "Solmath", "days": 30, "leapDays": 1 },
+        { "name": "Rethe", "days": 30 },
+        { "name": "Astron", "days": 30 }
+      ],
+      "weekdays": ["Sterday", "Sunday", "Monday", "Trewsday", "Hevensday", "Mersday", "Highday"],
+      "leapRule": { "every": 4, "exceptEvery": 100 },
+      "eras": [
+        { "name": "First Age", "abbreviation": "FA", "startYear": 1 },
+        { "name": "Second Age", "abbreviation": "SA", "startYear": 101 }
+      ]
+    }),
+  };
+  let kings = Calendar {
+    calendar_id: uuid::Uuid::parse_str("3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d02").unwrap(),
+    version: uuid::Uuid::parse_str("4c6e8a0b-2d3f-4b7c-9e1a-3f5b7d9c1e02").unwrap(),
+    created: "2020-03-11T13:00:32Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    name: "Kings' Reckoning".to_owned(),
+    definition: json!({
+      "months": [{ "name": "Narwain", "days": 365 }]
+    }),
+  };
+  let founding = TimelineEvent {
+    event_id: uuid::Uuid::parse_str("6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c01").unwrap(),
+    version: uuid::Uuid::parse_str("7f9b1d3e-5a7c-4e9f-b1d3-6c8e0a2b4d01").unwrap(),
+    created: "2020-03-11T13:00:33Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    calendar_id: shire.calendar_id,
+    title: "Founding of the Shire".to_owned(),
+    description: "Marcho and Blanco cross the *Brandywine*".to_owned(),
+    year: 1,
+    month: 1,
+    day: 1,
+    day_number: 0,
+  };
+  let coronation = TimelineEvent {
+    event_id: uuid::Uuid::parse_str("6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c02").unwrap(),
+    version: uuid::Uuid::parse_str("7f9b1d3e-5a7c-4e9f-b1d3-6c8e0a2b4d02").unwrap(),
+    created: "2020-03-11T13:00:34Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    calendar_id: shire.calendar_id,
+    title: "Coronation".to_owned(),
+    description: "".to_owned(),
+    year: 5,
+    month: 3,
+    day: 1,
+    day_number: 541,
+  };
+  let battle = TimelineEvent {
+    event_id: uuid::Uuid::parse_str("6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c03").unwrap(),
+    version: uuid::Uuid::parse_str("7f9b1d3e-5a7c-4e9f-b1d3-6c8e0a2b4d03").unwrap(),
+    created: "2020-03-11T13:00:35Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    calendar_id: shire.calendar_id,
+    title: "Battle of Greenfields".to_owned(),
+    description: "".to_owned(),
+    year: 3,
+    month: 2,
+    day: 15,
+    day_number: 284,
+  };
+  seed(
+    service.database(),
+    vec![&user, &world, &shire, &kings, &founding, &coronation, &battle],
+  );
+
+  user
+}
+
+#[test]
+fn test_list_calendars() {
+  let service = ServiceWrapper::default();
+  seed_calendars(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Cache-Control: public, max-age=3600
+  Vary: Authorization
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/calendars", "get", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "entries": [
+      {
+        "epochWeekday": 0,
+        "eras": [],
+        "events": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d02/events",
+        "id": "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d02",
+        "months": [
+          {
+            "days": 365,
+            "leapDays": 0,
+            "name": "Narwain"
+          }
+        ],
+        "name": "Kings' Reckoning",
+        "weekdays": [],
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "epochWeekday": 0,
+        "eras": [
+          {
+            "abbreviation": "FA",
+            "name": "First Age",
+            "startYear": 1
+          },
+          {
+            "abbreviation": "SA",
+            "name": "Second Age",
+            "startYear": 101
+          }
+        ],
+        "events": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/events",
+        "id": "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01",
+        "leapRule": {
+          "every": 4,
+          "exceptEvery": 100
+        },
+        "months": [
+          {
+            "days": 30,
+            "leapDays": 0,
+            "name": "Afteryule"
+          },
+          {
+            "days": 30,
+            "leapDays": 1,
+            "name": "Solmath"
+          },
+          {
+            "days": 30,
+            "leapDays": 0,
+            "name": "Rethe"
+          },
+          {
+            "days": 30,
+            "leapDays": 0,
+            "name": "Astron"
+          }
+        ],
+        "name": "Shire Reckoning",
+        "weekdays": [
+          "Sterday",
+          "Sunday",
+          "Monday",
+          "Trewsday",
+          "Hevensday",
+          "Mersday",
+          "Highday"
+        ],
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      }
+    ],
+    "total": 2
+  }
+  "###);
+}
+
+#[test]
+fn test_get_calendar() {
+  let service = ServiceWrapper::default();
+  seed_calendars(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01>; rel="self"
+  ETag: "4c6e8a0b-2d3f-4b7c-9e1a-3f5b7d9c1e01"
+  Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT
+  Cache-Control: public, max-age=3600
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/calendars/{calendarId}", "get", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "epochWeekday": 0,
+    "eras": [
+      {
+        "abbreviation": "FA",
+        "name": "First Age",
+        "startYear": 1
+      },
+      {
+        "abbreviation": "SA",
+        "name": "Second Age",
+        "startYear": 101
+      }
+    ],
+    "events": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/events",
+    "id": "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01",
+    "leapRule": {
+      "every": 4,
+      "exceptEvery": 100
+    },
+    "months": [
+      {
+        "days": 30,
+        "leapDays": 0,
+        "name": "Afteryule"
+      },
+      {
+        "days": 30,
+        "leapDays": 1,
+        "name": "Solmath"
+      },
+      {
+        "days": 30,
+        "leapDays": 0,
+        "name": "Rethe"
+      },
+      {
+        "days": 30,
+        "leapDays": 0,
+        "name": "Astron"
+      }
+    ],
+    "name": "Shire Reckoning",
+    "weekdays": [
+      "Sterday",
+      "Sunday",
+      "Monday",
+      "Trewsday",
+      "Hevensday",
+      "Mersday",
+      "Highday"
+    ],
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_calendar_private_world() {
+  let service = ServiceWrapper::default();
+  let user = seed_calendars(&service, "private");
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d02")
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d02")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d02>; rel="self"
+  ETag: "4c6e8a0b-2d3f-4b7c-9e1a-3f5b7d9c1e02"
+  Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT
+  Cache-Control: private, max-age=3600
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "epochWeekday": 0,
+    "eras": [],
+    "events": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d02/events",
+    "id": "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d02",
+    "months": [
+      {
+        "days": 365,
+        "leapDays": 0,
+        "name": "Narwain"
+      }
+    ],
+    "name": "Kings' Reckoning",
+    "weekdays": [],
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_unknown_calendar() {
+  let service = ServiceWrapper::default();
+  seed_calendars(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d09")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested calendar could not be found",
+    "type": "tag:universe,2020:calendars/problems/unknown-calendar"
+  }
+  "###);
+}
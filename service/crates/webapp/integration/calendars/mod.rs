@@ -0,0 +1,6 @@
+mod dates;
+mod delete;
+mod events;
+mod get;
+mod post;
+mod put;
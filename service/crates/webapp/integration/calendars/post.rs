@@ -0,0 +1,265 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, build_rewrite_headers,
+  regex_replace, ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use rocket::http::ContentType;
+use serde_json::json;
+use test_env_log::test;
+use universe_testdata::{seed, User, World};
+
+fn seed_world(service: &ServiceWrapper) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&user, &world]);
+
+  user
+}
+
+#[test]
+fn test_post_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+
+  let response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars")
+    .header(ContentType::JSON)
+    .body(json!({ "name": "Shire Reckoning", "months": [{ "name": "Afteryule", "days": 30 }] }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_post_not_member() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+  let other_user = User {
+    username: "otheruser".to_owned(),
+    email: "other@example.com".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&other_user]);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .body(json!({ "name": "Shire Reckoning", "months": [{ "name": "Afteryule", "days": 30 }] }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 403 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 403,
+    "title": "You are not permitted to perform this request",
+    "type": "tag:universe,2020:problems/authentication/forbidden"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_success() {
+  let service = ServiceWrapper::default();
+  let user = seed_world(&service);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "name": "  Shire Reckoning  ",
+        "months": [
+          { "name": "Afteryule", "days": 30 },
+          { "name": "Solmath", "days": 30, "leapDays": 1 }
+        ],
+        "weekdays": ["Sterday", "Sunday", "Monday"],
+        "leapRule": { "every": 4 },
+        "eras": [{ "name": "Third Age", "abbreviation": "TA", "startYear": 1 }],
+        "epochWeekday": 2
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_rewrite_headers(&response, |h| {
+    regex_replace(h, r#"/calendars/[a-z0-9]{8}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{12}"#, "/calendars/[uuid]")
+  }), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/[uuid]>; rel="canonical"
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/calendars", "post", 200);
+  assert_json_snapshot!(body, {
+        ".id" => "[uuid]",
+        ".events" => "[url]",
+    },
+    @r###"
+  {
+    "epochWeekday": 2,
+    "eras": [
+      {
+        "abbreviation": "TA",
+        "name": "Third Age",
+        "startYear": 1
+      }
+    ],
+    "events": "[url]",
+    "id": "[uuid]",
+    "leapRule": {
+      "every": 4
+    },
+    "months": [
+      {
+        "days": 30,
+        "leapDays": 0,
+        "name": "Afteryule"
+      },
+      {
+        "days": 30,
+        "leapDays": 1,
+        "name": "Solmath"
+      }
+    ],
+    "name": "Shire Reckoning",
+    "weekdays": [
+      "Sterday",
+      "Sunday",
+      "Monday"
+    ],
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_missing_name() {
+  let service = ServiceWrapper::default();
+  let user = seed_world(&service);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "months": [{ "name": "Afteryule", "days": 30 }] }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "name",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_invalid_definition() {
+  let service = ServiceWrapper::default();
+  let user = seed_world(&service);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "name": "Shire Reckoning",
+        "months": [
+          { "name": "Afteryule", "days": 30 },
+          { "name": "afteryule", "days": 0 }
+        ],
+        "weekdays": ["Sterday"],
+        "leapRule": { "every": 4, "exceptEvery": 10 },
+        "eras": [
+          { "name": "Second Age", "abbreviation": "SA", "startYear": 100 },
+          { "name": "First Age", "abbreviation": "F A", "startYear": 1 }
+        ],
+        "epochWeekday": 3
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "months[1].name",
+        "title": "Month 1 has the same name as an earlier month",
+        "type": "tag:universe,2020:calendars/validation-errors/months/duplicate-name"
+      },
+      {
+        "field": "months[1].days",
+        "title": "Month 1 has an invalid number of days",
+        "type": "tag:universe,2020:calendars/validation-errors/months/invalid-days"
+      },
+      {
+        "field": "epochWeekday",
+        "title": "The epoch weekday is not one of the weekdays",
+        "type": "tag:universe,2020:calendars/validation-errors/epochWeekday/invalid"
+      },
+      {
+        "field": "leapRule",
+        "title": "The leap rule is invalid",
+        "type": "tag:universe,2020:calendars/validation-errors/leapRule/invalid"
+      },
+      {
+        "field": "eras[1].abbreviation",
+        "title": "Era 1 has an invalid abbreviation",
+        "type": "tag:universe,2020:calendars/validation-errors/eras/invalid-abbreviation"
+      },
+      {
+        "field": "eras[1].startYear",
+        "title": "Era 1 doesn't start after the era before it",
+        "type": "tag:universe,2020:calendars/validation-errors/eras/out-of-order"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
@@ -0,0 +1,338 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, build_rewrite_headers,
+  regex_replace, ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use rocket::http::ContentType;
+use serde_json::json;
+use test_env_log::test;
+use universe_testdata::{seed, Calendar, TimelineEvent, User, World};
+
+fn seed_calendars(service: &ServiceWrapper, visibility: &str) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    visibility: visibility.to_owned(),
+    ..Default::default()
+  };
+  let shire = Calendar {
+    calendar_id: uuid::Uuid::parse_str("3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01").unwrap(),
+    version: uuid::Uuid::parse_str("4c6e8a0b-2d3f-4b7c-9e1a-3f5b7d9c1e01").unwrap(),
+    created: "2020-03-11T13:00:31Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    name: "Shire Reckoning".to_owned(),
+    definition: json!({
+      "months": [
+        { "name": "Afteryule", "days": 30 },
+        { "name": "Solmath", "days": 30, "leapDays": 1 },
+        { "name": "Rethe", "days": 30 },
+        { "name": "Astron", "days": 30 }
+      ],
+      "weekdays": ["Sterday", "Sunday", "Monday", "Trewsday", "Hevensday", "Mersday", "Highday"],
+      "leapRule": { "every": 4, "exceptEvery": 100 },
+      "eras": [
+        { "name": "First Age", "abbreviation": "FA", "startYear": 1 },
+        { "name": "Second Age", "abbreviation": "SA", "startYear": 101 }
+      ]
+    }),
+  };
+  let kings = Calendar {
+    calendar_id: uuid::Uuid::parse_str("3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d02").unwrap(),
+    version: uuid::Uuid::parse_str("4c6e8a0b-2d3f-4b7c-9e1a-3f5b7d9c1e02").unwrap(),
+    created: "2020-03-11T13:00:32Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    name: "Kings' Reckoning".to_owned(),
+    definition: json!({
+      "months": [{ "name": "Narwain", "days": 365 }]
+    }),
+  };
+  let founding = TimelineEvent {
+    event_id: uuid::Uuid::parse_str("6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c01").unwrap(),
+    version: uuid::Uuid::parse_str("7f9b1d3e-5a7c-4e9f-b1d3-6c8e0a2b4d01").unwrap(),
+    created: "2020-03-11T13:00:33Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    calendar_id: shire.calendar_id,
+    title: "Founding of the Shire".to_owned(),
+    description: "Marcho and Blanco cross the *Brandywine*".to_owned(),
+    year: 1,
+    month: 1,
+    day: 1,
+    day_number: 0,
+  };
+  let coronation = TimelineEvent {
+    event_id: uuid::Uuid::parse_str("6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c02").unwrap(),
+    version: uuid::Uuid::parse_str("7f9b1d3e-5a7c-4e9f-b1d3-6c8e0a2b4d02").unwrap(),
+    created: "2020-03-11T13:00:34Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    calendar_id: shire.calendar_id,
+    title: "Coronation".to_owned(),
+    description: "".to_owned(),
+    year: 5,
+    month: 3,
+    day: 1,
+    day_number: 541,
+  };
+  let battle = TimelineEvent {
+    event_id: uuid::Uuid::parse_str("6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c03").unwrap(),
+    version: uuid::Uuid::parse_str("7f9b1d3e-5a7c-4e9f-b1d3-6c8e0a2b4d03").unwrap(),
+    created: "2020-03-11T13:00:35Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    calendar_id: shire.calendar_id,
+    title: "Battle of Greenfields".to_owned(),
+    description: "".to_owned(),
+    year: 3,
+    month: 2,
+    day: 15,
+    day_number: 284,
+  };
+  seed(
+    service.database(),
+    vec![&user, &world, &shire, &kings, &founding, &coronation, &battle],
+  );
+
+  user
+}
+
+#[test]
+fn test_put_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_calendars(&service, "public");
+
+  let response = service
+    .put("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01")
+    .header(ContentType::JSON)
+    .body(json!({ "name": "Shire Reckoning", "months": [{ "name": "Afteryule", "days": 30 }] }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_put_unknown_calendar() {
+  let service = ServiceWrapper::default();
+  let user = seed_calendars(&service, "public");
+
+  let mut response = service
+    .put("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d09")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "name": "Shire Reckoning", "months": [{ "name": "Afteryule", "days": 30 }] }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested calendar could not be found",
+    "type": "tag:universe,2020:calendars/problems/unknown-calendar"
+  }
+  "###);
+}
+
+#[test]
+fn test_put_success() {
+  let service = ServiceWrapper::default();
+  let user = seed_calendars(&service, "public");
+
+  // Doubling the length of the first month moves every event after it later in the timeline
+  let mut response = service
+    .put("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "name": "New Reckoning",
+        "months": [
+          { "name": "Afteryule", "days": 60 },
+          { "name": "Solmath", "days": 30 },
+          { "name": "Rethe", "days": 30 },
+          { "name": "Astron", "days": 30 }
+        ],
+        "weekdays": ["Sterday", "Sunday"]
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_rewrite_headers(&response, |h| {
+    let h = regex_replace(h, r#"ETag: "[a-z0-9]{8}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{12}""#, r#"ETag: "a7fd01dc-dcf7-45dd-a932-0b6b263e17d0""#);
+    regex_replace(h, r#"^Last-Modified: .*$"#, "Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT")
+  }), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01>; rel="self"
+  ETag: "a7fd01dc-dcf7-45dd-a932-0b6b263e17d0"
+  Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT
+  Cache-Control: public, max-age=3600
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/calendars/{calendarId}", "put", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "epochWeekday": 0,
+    "eras": [],
+    "events": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/events",
+    "id": "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01",
+    "months": [
+      {
+        "days": 60,
+        "leapDays": 0,
+        "name": "Afteryule"
+      },
+      {
+        "days": 30,
+        "leapDays": 0,
+        "name": "Solmath"
+      },
+      {
+        "days": 30,
+        "leapDays": 0,
+        "name": "Rethe"
+      },
+      {
+        "days": 30,
+        "leapDays": 0,
+        "name": "Astron"
+      }
+    ],
+    "name": "New Reckoning",
+    "weekdays": [
+      "Sterday",
+      "Sunday"
+    ],
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/events?from=1-2-1&to=5-3-1")
+    .dispatch();
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "entries": [
+      {
+        "calendar": "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01",
+        "date": {
+          "day": 15,
+          "month": 2,
+          "monthName": "Solmath",
+          "text": "15 Solmath 3",
+          "weekday": "Sterday",
+          "year": 3
+        },
+        "description": "",
+        "descriptionHtml": "",
+        "id": "6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c03",
+        "title": "Battle of Greenfields",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "calendar": "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01",
+        "date": {
+          "day": 1,
+          "month": 3,
+          "monthName": "Rethe",
+          "text": "1 Rethe 5",
+          "weekday": "Sterday",
+          "year": 5
+        },
+        "description": "",
+        "descriptionHtml": "",
+        "id": "6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c02",
+        "title": "Coronation",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      }
+    ],
+    "total": 2
+  }
+  "###);
+}
+
+#[test]
+fn test_put_invalidates_events() {
+  let service = ServiceWrapper::default();
+  let user = seed_calendars(&service, "public");
+
+  // The Battle of Greenfields is on the 15th of Solmath, which this calendar doesn't have
+  let mut response = service
+    .put("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "name": "Shire Reckoning",
+        "months": [
+          { "name": "Afteryule", "days": 30 },
+          { "name": "Solmath", "days": 10 },
+          { "name": "Rethe", "days": 30 }
+        ]
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 409 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "events": [
+      "6e8a0c2d-4f6b-4d8e-a0c2-5b7d9f1a3c03"
+    ],
+    "status": 409,
+    "title": "Some events of the calendar are on dates that the new calendar doesn't have",
+    "type": "tag:universe,2020:calendars/problems/events-invalidated"
+  }
+  "###);
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01")
+    .dispatch();
+  assert_json_snapshot!(build_json_body(&mut response), {
+        ".months" => "[months]",
+        ".weekdays" => "[weekdays]",
+        ".eras" => "[eras]",
+    },
+    @r###"
+  {
+    "epochWeekday": 0,
+    "eras": "[eras]",
+    "events": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/calendars/3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01/events",
+    "id": "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01",
+    "leapRule": {
+      "every": 4,
+      "exceptEvery": 100
+    },
+    "months": "[months]",
+    "name": "Shire Reckoning",
+    "weekdays": "[weekdays]",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
@@ -0,0 +1,96 @@
+use crate::{authenticate_user, build_headers, build_json_body, ServiceWrapper};
+use insta::{assert_json_snapshot, assert_snapshot};
+use test_env_log::test;
+use universe_testdata::{seed, Character, User, World};
+
+fn seed_characters(service: &ServiceWrapper) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    ..Default::default()
+  };
+  let frodo = Character {
+    character_id: uuid::Uuid::parse_str("6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e01").unwrap(),
+    world_id: world.world_id,
+    name: "Frodo Baggins".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&user, &world, &frodo]);
+
+  user
+}
+
+#[test]
+fn test_delete_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_characters(&service);
+
+  let response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e01")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_delete_success() {
+  let service = ServiceWrapper::default();
+  let user = seed_characters(&service);
+
+  let response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e01")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 204 No Content.
+  Server: Rocket
+  "###);
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e01")
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_delete_unknown_character() {
+  let service = ServiceWrapper::default();
+  let user = seed_characters(&service);
+
+  let mut response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e09")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested character could not be found",
+    "type": "tag:universe,2020:characters/problems/unknown-character"
+  }
+  "###);
+}
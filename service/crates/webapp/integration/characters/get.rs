@@ -0,0 +1,365 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use test_env_log::test;
+use universe_testdata::{seed, Character, User, World};
+
+fn seed_characters(service: &ServiceWrapper, visibility: &str) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    visibility: visibility.to_owned(),
+    ..Default::default()
+  };
+  let frodo = Character {
+    character_id: uuid::Uuid::parse_str("6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e01").unwrap(),
+    version: uuid::Uuid::parse_str("7c2a1d3f-9e5b-4a4c-8f6d-3b8e2c4d5f01").unwrap(),
+    created: "2020-03-11T13:00:31Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    name: "Frodo Baggins".to_owned(),
+    aliases: vec!["Mr Underhill".to_owned(), "Ring-bearer".to_owned()],
+    summary: "A hobbit of the Shire".to_owned(),
+    biography: "Bearer of the *One Ring*".to_owned(),
+    attributes: vec![
+      ("Race".to_owned(), "Hobbit".to_owned()),
+      ("Home".to_owned(), "Bag End".to_owned()),
+    ]
+    .into_iter()
+    .collect(),
+  };
+  let aragorn = Character {
+    character_id: uuid::Uuid::parse_str("6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e02").unwrap(),
+    version: uuid::Uuid::parse_str("7c2a1d3f-9e5b-4a4c-8f6d-3b8e2c4d5f02").unwrap(),
+    created: "2020-03-11T13:00:32Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    name: "Aragorn".to_owned(),
+    aliases: vec!["Strider".to_owned(), "Elessar".to_owned()],
+    summary: "Heir of Isildur".to_owned(),
+    biography: "".to_owned(),
+    ..Default::default()
+  };
+  let bilbo = Character {
+    character_id: uuid::Uuid::parse_str("6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e03").unwrap(),
+    version: uuid::Uuid::parse_str("7c2a1d3f-9e5b-4a4c-8f6d-3b8e2c4d5f03").unwrap(),
+    created: "2020-03-11T13:00:33Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    name: "Bilbo Baggins".to_owned(),
+    aliases: vec!["Ring-bearer".to_owned()],
+    summary: "Finder of the Ring".to_owned(),
+    biography: "".to_owned(),
+    ..Default::default()
+  };
+  seed(
+    service.database(),
+    vec![&user, &world, &frodo, &aragorn, &bilbo],
+  );
+
+  user
+}
+
+#[test]
+fn test_get_character() {
+  let service = ServiceWrapper::default();
+  seed_characters(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e01")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e01>; rel="self"
+  Accept-Patch: application/merge-patch+json
+  ETag: "7c2a1d3f-9e5b-4a4c-8f6d-3b8e2c4d5f01"
+  Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT
+  Cache-Control: public, max-age=3600
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(
+    &body,
+    "/worlds/{worldId}/characters/{characterId}",
+    "get",
+    200,
+  );
+  assert_json_snapshot!(body, @r###"
+  {
+    "aliases": [
+      "Mr Underhill",
+      "Ring-bearer"
+    ],
+    "attributes": {
+      "Home": "Bag End",
+      "Race": "Hobbit"
+    },
+    "biography": "Bearer of the *One Ring*",
+    "biographyHtml": "<p>Bearer of the <em>One Ring</em></p>\n",
+    "id": "6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e01",
+    "name": "Frodo Baggins",
+    "summary": "A hobbit of the Shire",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_unknown_character() {
+  let service = ServiceWrapper::default();
+  seed_characters(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e09")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested character could not be found",
+    "type": "tag:universe,2020:characters/problems/unknown-character"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_malformed_character_id() {
+  let service = ServiceWrapper::default();
+  seed_characters(&service, "public");
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/frodo")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_get_character_private_world() {
+  let service = ServiceWrapper::default();
+  seed_characters(&service, "private");
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e01")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_get_character_private_world_as_owner() {
+  let service = ServiceWrapper::default();
+  let user = seed_characters(&service, "private");
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e01")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e01>; rel="self"
+  Accept-Patch: application/merge-patch+json
+  ETag: "7c2a1d3f-9e5b-4a4c-8f6d-3b8e2c4d5f01"
+  Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT
+  Cache-Control: private, max-age=3600
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_search_characters() {
+  let service = ServiceWrapper::default();
+  seed_characters(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Cache-Control: public, max-age=3600
+  Vary: Authorization
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/characters", "get", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "entries": [
+      {
+        "aliases": [
+          "Strider",
+          "Elessar"
+        ],
+        "attributes": {},
+        "biography": "",
+        "biographyHtml": "",
+        "id": "6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e02",
+        "name": "Aragorn",
+        "summary": "Heir of Isildur",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "aliases": [
+          "Ring-bearer"
+        ],
+        "attributes": {},
+        "biography": "",
+        "biographyHtml": "",
+        "id": "6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e03",
+        "name": "Bilbo Baggins",
+        "summary": "Finder of the Ring",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "aliases": [
+          "Mr Underhill",
+          "Ring-bearer"
+        ],
+        "attributes": {
+          "Home": "Bag End",
+          "Race": "Hobbit"
+        },
+        "biography": "Bearer of the *One Ring*",
+        "biographyHtml": "<p>Bearer of the <em>One Ring</em></p>\n",
+        "id": "6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e01",
+        "name": "Frodo Baggins",
+        "summary": "A hobbit of the Shire",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      }
+    ],
+    "total": 3
+  }
+  "###);
+}
+
+#[test]
+fn test_search_characters_by_name() {
+  let service = ServiceWrapper::default();
+  seed_characters(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters?name=baggins&sort=-name&offset=1&limit=1")
+    .dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "entries": [
+      {
+        "aliases": [
+          "Ring-bearer"
+        ],
+        "attributes": {},
+        "biography": "",
+        "biographyHtml": "",
+        "id": "6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e03",
+        "name": "Bilbo Baggins",
+        "summary": "Finder of the Ring",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      }
+    ],
+    "total": 2
+  }
+  "###);
+}
+
+#[test]
+fn test_search_characters_by_alias() {
+  let service = ServiceWrapper::default();
+  seed_characters(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters?alias=RING&sort=created")
+    .dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "entries": [
+      {
+        "aliases": [
+          "Ring-bearer"
+        ],
+        "attributes": {},
+        "biography": "",
+        "biographyHtml": "",
+        "id": "6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e03",
+        "name": "Bilbo Baggins",
+        "summary": "Finder of the Ring",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "aliases": [
+          "Mr Underhill",
+          "Ring-bearer"
+        ],
+        "attributes": {
+          "Home": "Bag End",
+          "Race": "Hobbit"
+        },
+        "biography": "Bearer of the *One Ring*",
+        "biographyHtml": "<p>Bearer of the <em>One Ring</em></p>\n",
+        "id": "6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e01",
+        "name": "Frodo Baggins",
+        "summary": "A hobbit of the Shire",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      }
+    ],
+    "total": 2
+  }
+  "###);
+}
+
+#[test]
+fn test_search_characters_unknown_sort() {
+  let service = ServiceWrapper::default();
+  seed_characters(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters?sort=height")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 422,
+    "title": "Unknown Sort Fields requested",
+    "type": "tag:universe,2020:problems/unknown-sort-fields",
+    "unknownFields": [
+      "height"
+    ]
+  }
+  "###);
+}
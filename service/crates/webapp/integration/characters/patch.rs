@@ -0,0 +1,217 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use rocket::http::ContentType;
+use serde_json::json;
+use std::str::FromStr;
+use test_env_log::test;
+use universe_testdata::{seed, Character, User, World};
+
+fn seed_characters(service: &ServiceWrapper) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    ..Default::default()
+  };
+  let frodo = Character {
+    character_id: uuid::Uuid::parse_str("6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e01").unwrap(),
+    world_id: world.world_id,
+    name: "Frodo Baggins".to_owned(),
+    aliases: vec!["Mr Underhill".to_owned()],
+    summary: "A hobbit of the Shire".to_owned(),
+    biography: "Bearer of the *One Ring*".to_owned(),
+    attributes: vec![
+      ("Race".to_owned(), "Hobbit".to_owned()),
+      ("Home".to_owned(), "Bag End".to_owned()),
+    ]
+    .into_iter()
+    .collect(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&user, &world, &frodo]);
+
+  user
+}
+
+#[test]
+fn test_patch_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_characters(&service);
+
+  let response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e01")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .body(json!({ "name": "Frodo" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_patch_unknown_character() {
+  let service = ServiceWrapper::default();
+  let user = seed_characters(&service);
+
+  let mut response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e09")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "name": "Frodo" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested character could not be found",
+    "type": "tag:universe,2020:characters/problems/unknown-character"
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_with_differences() {
+  let service = ServiceWrapper::default();
+  let user = seed_characters(&service);
+
+  let mut response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e01")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "name": "  Frodo  ",
+        "aliases": ["Ring-bearer"],
+        "summary": "Nephew of Bilbo",
+        "biography": "Went *there and back again*",
+        "attributes": {
+          "Home": null,
+          "Age": " 50 "
+        }
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(
+    &body,
+    "/worlds/{worldId}/characters/{characterId}",
+    "patch",
+    200,
+  );
+  assert_json_snapshot!(body, @r###"
+  {
+    "aliases": [
+      "Ring-bearer"
+    ],
+    "attributes": {
+      "Age": "50",
+      "Race": "Hobbit"
+    },
+    "biography": "Went *there and back again*",
+    "biographyHtml": "<p>Went <em>there and back again</em></p>\n",
+    "id": "6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e01",
+    "name": "Frodo",
+    "summary": "Nephew of Bilbo",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_clear_attributes() {
+  let service = ServiceWrapper::default();
+  let user = seed_characters(&service);
+
+  let mut response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e01")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "attributes": null }).to_string())
+    .dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "aliases": [
+      "Mr Underhill"
+    ],
+    "attributes": {},
+    "biography": "Bearer of the *One Ring*",
+    "biographyHtml": "<p>Bearer of the <em>One Ring</em></p>\n",
+    "id": "6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e01",
+    "name": "Frodo Baggins",
+    "summary": "A hobbit of the Shire",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_invalid_values() {
+  let service = ServiceWrapper::default();
+  let user = seed_characters(&service);
+
+  let mut response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/6b1f0c2e-8d4a-4f3b-9e5c-2a7d1b3c4e01")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "name": "",
+        "aliases": [""],
+        "attributes": { "": "Unnamed" }
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "name",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      },
+      {
+        "field": "aliases",
+        "title": "Aliases may not be blank",
+        "type": "tag:universe,2020:characters/validation-errors/aliases/blank"
+      },
+      {
+        "field": "attributes",
+        "title": "Attribute names may not be blank",
+        "type": "tag:universe,2020:characters/validation-errors/attributes/blank-name"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
@@ -0,0 +1,219 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, build_rewrite_headers,
+  regex_replace, ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use rocket::http::ContentType;
+use serde_json::json;
+use test_env_log::test;
+use universe_testdata::{seed, User, World};
+
+fn seed_world(service: &ServiceWrapper) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&user, &world]);
+
+  user
+}
+
+#[test]
+fn test_post_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+
+  let response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters")
+    .header(ContentType::JSON)
+    .body(json!({ "name": "Frodo Baggins" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_post_not_member() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+  let other_user = User {
+    username: "otheruser".to_owned(),
+    email: "other@example.com".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&other_user]);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .body(json!({ "name": "Frodo Baggins" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 403 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 403,
+    "title": "You are not permitted to perform this request",
+    "type": "tag:universe,2020:problems/authentication/forbidden"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_success() {
+  let service = ServiceWrapper::default();
+  let user = seed_world(&service);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "name": "  Frodo Baggins  ",
+        "aliases": [" Mr Underhill ", "Ring-bearer", "Mr Underhill"],
+        "summary": "A hobbit of the Shire",
+        "biography": "Bearer of the *One Ring*",
+        "attributes": {
+          " Race ": " Hobbit ",
+          "Home": "Bag End"
+        }
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_rewrite_headers(&response, |h| {
+    regex_replace(h, r#"/characters/[a-z0-9]{8}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{12}"#, "/characters/[uuid]")
+  }), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/[uuid]>; rel="canonical"
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/characters", "post", 200);
+  assert_json_snapshot!(body, {
+        ".id" => "[uuid]",
+    },
+    @r###"
+  {
+    "aliases": [
+      "Mr Underhill",
+      "Ring-bearer"
+    ],
+    "attributes": {
+      "Home": "Bag End",
+      "Race": "Hobbit"
+    },
+    "biography": "Bearer of the *One Ring*",
+    "biographyHtml": "<p>Bearer of the <em>One Ring</em></p>\n",
+    "id": "[uuid]",
+    "name": "Frodo Baggins",
+    "summary": "A hobbit of the Shire",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_minimal() {
+  let service = ServiceWrapper::default();
+  let user = seed_world(&service);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "name": "Gandalf" }).to_string())
+    .dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/characters", "post", 200);
+  assert_json_snapshot!(body, {
+        ".id" => "[uuid]",
+    },
+    @r###"
+  {
+    "aliases": [],
+    "attributes": {},
+    "biography": "",
+    "biographyHtml": "",
+    "id": "[uuid]",
+    "name": "Gandalf",
+    "summary": "",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_invalid_values() {
+  let service = ServiceWrapper::default();
+  let user = seed_world(&service);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "name": " ",
+        "aliases": ["Strider", " "],
+        "attributes": { " ": "Unnamed" }
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "name",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      },
+      {
+        "field": "aliases",
+        "title": "Aliases may not be blank",
+        "type": "tag:universe,2020:characters/validation-errors/aliases/blank"
+      },
+      {
+        "field": "attributes",
+        "title": "Attribute names may not be blank",
+        "type": "tag:universe,2020:characters/validation-errors/attributes/blank-name"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
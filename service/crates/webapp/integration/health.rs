@@ -20,6 +20,10 @@ fn test_health() {
         "database": {
           "message": "Ok",
           "status": "OK"
+        },
+        "storage": {
+          "message": "Ok",
+          "status": "OK"
         }
       },
       "status": "OK"
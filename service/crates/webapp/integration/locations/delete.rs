@@ -0,0 +1,127 @@
+use crate::{authenticate_user, build_headers, build_json_body, ServiceWrapper};
+use insta::{assert_json_snapshot, assert_snapshot};
+use test_env_log::test;
+use universe_testdata::{seed, Location, User, World};
+
+fn seed_locations(service: &ServiceWrapper) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    ..Default::default()
+  };
+  let shire = Location {
+    location_id: uuid::Uuid::parse_str("3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03").unwrap(),
+    world_id: world.world_id,
+    name: "The Shire".to_owned(),
+    ..Default::default()
+  };
+  let hobbiton = Location {
+    location_id: uuid::Uuid::parse_str("3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04").unwrap(),
+    world_id: world.world_id,
+    parent_id: Some(shire.location_id),
+    name: "Hobbiton".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&user, &world, &shire, &hobbiton]);
+
+  user
+}
+
+#[test]
+fn test_delete_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_locations(&service);
+
+  let response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_delete_success() {
+  let service = ServiceWrapper::default();
+  let user = seed_locations(&service);
+
+  let response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 204 No Content.
+  Server: Rocket
+  "###);
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04")
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_delete_with_children() {
+  let service = ServiceWrapper::default();
+  let user = seed_locations(&service);
+
+  let mut response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 409 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 409,
+    "title": "The location has other locations inside it",
+    "type": "tag:universe,2020:locations/problems/has-children"
+  }
+  "###);
+}
+
+#[test]
+fn test_delete_unknown_location() {
+  let service = ServiceWrapper::default();
+  let user = seed_locations(&service);
+
+  let mut response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a09")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested location could not be found",
+    "type": "tag:universe,2020:locations/problems/unknown-location"
+  }
+  "###);
+}
@@ -0,0 +1,358 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use test_env_log::test;
+use universe_testdata::{seed, Location, User, World};
+
+fn seed_locations(service: &ServiceWrapper, visibility: &str) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    visibility: visibility.to_owned(),
+    ..Default::default()
+  };
+  let middle_earth = Location {
+    location_id: uuid::Uuid::parse_str("3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a01").unwrap(),
+    version: uuid::Uuid::parse_str("4f3c8d2b-7a5e-4c9f-b2da-6e8a3f9c5b01").unwrap(),
+    created: "2020-03-11T13:00:31Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    name: "Middle-earth".to_owned(),
+    kind: "Continent".to_owned(),
+    description: "".to_owned(),
+    ..Default::default()
+  };
+  let eriador = Location {
+    location_id: uuid::Uuid::parse_str("3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a02").unwrap(),
+    version: uuid::Uuid::parse_str("4f3c8d2b-7a5e-4c9f-b2da-6e8a3f9c5b02").unwrap(),
+    created: "2020-03-11T13:00:32Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    parent_id: Some(middle_earth.location_id),
+    name: "Eriador".to_owned(),
+    kind: "Region".to_owned(),
+    description: "".to_owned(),
+    ..Default::default()
+  };
+  let shire = Location {
+    location_id: uuid::Uuid::parse_str("3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03").unwrap(),
+    version: uuid::Uuid::parse_str("4f3c8d2b-7a5e-4c9f-b2da-6e8a3f9c5b03").unwrap(),
+    created: "2020-03-11T13:00:33Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    parent_id: Some(eriador.location_id),
+    name: "The Shire".to_owned(),
+    kind: "Region".to_owned(),
+    description: "".to_owned(),
+    ..Default::default()
+  };
+  let hobbiton = Location {
+    location_id: uuid::Uuid::parse_str("3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04").unwrap(),
+    version: uuid::Uuid::parse_str("4f3c8d2b-7a5e-4c9f-b2da-6e8a3f9c5b04").unwrap(),
+    created: "2020-03-11T13:00:34Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    parent_id: Some(shire.location_id),
+    name: "Hobbiton".to_owned(),
+    kind: "City".to_owned(),
+    description: "Home of *Bag End*".to_owned(),
+    x: Some(120.5),
+    y: Some(80.0),
+  };
+  let mordor = Location {
+    location_id: uuid::Uuid::parse_str("3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a05").unwrap(),
+    version: uuid::Uuid::parse_str("4f3c8d2b-7a5e-4c9f-b2da-6e8a3f9c5b05").unwrap(),
+    created: "2020-03-11T13:00:35Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    parent_id: Some(middle_earth.location_id),
+    name: "Mordor".to_owned(),
+    kind: "Region".to_owned(),
+    description: "".to_owned(),
+    ..Default::default()
+  };
+  seed(
+    service.database(),
+    vec![&user, &world, &middle_earth, &eriador, &shire, &hobbiton, &mordor],
+  );
+
+  user
+}
+
+#[test]
+fn test_get_location() {
+  let service = ServiceWrapper::default();
+  seed_locations(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04>; rel="self"
+  Accept-Patch: application/merge-patch+json
+  ETag: "4f3c8d2b-7a5e-4c9f-b2da-6e8a3f9c5b04"
+  Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT
+  Cache-Control: public, max-age=3600
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(
+    &body,
+    "/worlds/{worldId}/locations/{locationId}",
+    "get",
+    200,
+  );
+  assert_json_snapshot!(body, @r###"
+  {
+    "coordinates": {
+      "x": 120.5,
+      "y": 80.0
+    },
+    "description": "Home of *Bag End*",
+    "descriptionHtml": "<p>Home of <em>Bag End</em></p>\n",
+    "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04",
+    "kind": "City",
+    "name": "Hobbiton",
+    "parent": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_top_level_location() {
+  let service = ServiceWrapper::default();
+  seed_locations(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a01")
+    .dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "description": "",
+    "descriptionHtml": "",
+    "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a01",
+    "kind": "Continent",
+    "name": "Middle-earth",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_unknown_location() {
+  let service = ServiceWrapper::default();
+  seed_locations(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a09")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested location could not be found",
+    "type": "tag:universe,2020:locations/problems/unknown-location"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_malformed_location_id() {
+  let service = ServiceWrapper::default();
+  seed_locations(&service, "public");
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/hobbiton")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_get_location_private_world() {
+  let service = ServiceWrapper::default();
+  seed_locations(&service, "private");
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_get_location_private_world_as_owner() {
+  let service = ServiceWrapper::default();
+  let user = seed_locations(&service, "private");
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04>; rel="self"
+  Accept-Patch: application/merge-patch+json
+  ETag: "4f3c8d2b-7a5e-4c9f-b2da-6e8a3f9c5b04"
+  Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT
+  Cache-Control: private, max-age=3600
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_list_locations() {
+  let service = ServiceWrapper::default();
+  seed_locations(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations?offset=1&limit=2")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Cache-Control: public, max-age=3600
+  Vary: Authorization
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/locations", "get", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "entries": [
+      {
+        "coordinates": {
+          "x": 120.5,
+          "y": 80.0
+        },
+        "description": "Home of *Bag End*",
+        "descriptionHtml": "<p>Home of <em>Bag End</em></p>\n",
+        "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04",
+        "kind": "City",
+        "name": "Hobbiton",
+        "parent": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "description": "",
+        "descriptionHtml": "",
+        "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a01",
+        "kind": "Continent",
+        "name": "Middle-earth",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      }
+    ],
+    "total": 5
+  }
+  "###);
+}
+
+#[test]
+fn test_list_top_level_locations() {
+  let service = ServiceWrapper::default();
+  seed_locations(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations?parent=none")
+    .dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "entries": [
+      {
+        "description": "",
+        "descriptionHtml": "",
+        "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a01",
+        "kind": "Continent",
+        "name": "Middle-earth",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      }
+    ],
+    "total": 1
+  }
+  "###);
+}
+
+#[test]
+fn test_list_child_locations() {
+  let service = ServiceWrapper::default();
+  seed_locations(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations?parent=3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a01")
+    .dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "entries": [
+      {
+        "description": "",
+        "descriptionHtml": "",
+        "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a02",
+        "kind": "Region",
+        "name": "Eriador",
+        "parent": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a01",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "description": "",
+        "descriptionHtml": "",
+        "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a05",
+        "kind": "Region",
+        "name": "Mordor",
+        "parent": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a01",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      }
+    ],
+    "total": 2
+  }
+  "###);
+}
+
+#[test]
+fn test_list_locations_private_world() {
+  let service = ServiceWrapper::default();
+  seed_locations(&service, "private");
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
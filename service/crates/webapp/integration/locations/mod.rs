@@ -0,0 +1,5 @@
+mod delete;
+mod get;
+mod patch;
+mod post;
+mod tree;
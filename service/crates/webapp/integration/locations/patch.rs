@@ -0,0 +1,193 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use rocket::http::ContentType;
+use serde_json::json;
+use std::str::FromStr;
+use test_env_log::test;
+use universe_testdata::{seed, Location, User, World};
+
+fn seed_locations(service: &ServiceWrapper) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    ..Default::default()
+  };
+  let shire = Location {
+    location_id: uuid::Uuid::parse_str("3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03").unwrap(),
+    world_id: world.world_id,
+    name: "The Shire".to_owned(),
+    ..Default::default()
+  };
+  let hobbiton = Location {
+    location_id: uuid::Uuid::parse_str("3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04").unwrap(),
+    world_id: world.world_id,
+    parent_id: Some(shire.location_id),
+    name: "Hobbiton".to_owned(),
+    kind: "Village".to_owned(),
+    description: "Home of *Bag End*".to_owned(),
+    x: Some(120.5),
+    y: Some(80.0),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&user, &world, &shire, &hobbiton]);
+
+  user
+}
+
+#[test]
+fn test_patch_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_locations(&service);
+
+  let response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .body(json!({ "name": "Bywater" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_patch_unknown_location() {
+  let service = ServiceWrapper::default();
+  let user = seed_locations(&service);
+
+  let mut response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a09")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "name": "Bywater" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested location could not be found",
+    "type": "tag:universe,2020:locations/problems/unknown-location"
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_with_differences() {
+  let service = ServiceWrapper::default();
+  let user = seed_locations(&service);
+
+  let mut response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "name": "  Bywater  ",
+        "kind": " Town ",
+        "description": "On *the Water*",
+        "coordinates": { "x": 130, "y": 75.25 }
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(
+    &body,
+    "/worlds/{worldId}/locations/{locationId}",
+    "patch",
+    200,
+  );
+  assert_json_snapshot!(body, @r###"
+  {
+    "coordinates": {
+      "x": 130.0,
+      "y": 75.25
+    },
+    "description": "On *the Water*",
+    "descriptionHtml": "<p>On <em>the Water</em></p>\n",
+    "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04",
+    "kind": "Town",
+    "name": "Bywater",
+    "parent": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_clear_coordinates() {
+  let service = ServiceWrapper::default();
+  let user = seed_locations(&service);
+
+  let mut response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "coordinates": null }).to_string())
+    .dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "description": "Home of *Bag End*",
+    "descriptionHtml": "<p>Home of <em>Bag End</em></p>\n",
+    "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04",
+    "kind": "Village",
+    "name": "Hobbiton",
+    "parent": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_invalid_values() {
+  let service = ServiceWrapper::default();
+  let user = seed_locations(&service);
+
+  let mut response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "name": "" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "name",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
@@ -0,0 +1,260 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, build_rewrite_headers,
+  regex_replace, ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use rocket::http::ContentType;
+use serde_json::json;
+use test_env_log::test;
+use universe_testdata::{seed, Location, User, World};
+
+fn seed_world(service: &ServiceWrapper) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    ..Default::default()
+  };
+  let other_world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d9").unwrap(),
+    owner_id: user.user_id,
+    name: "Other World".to_owned(),
+    slug: "other-world".to_owned(),
+    ..Default::default()
+  };
+  let shire = Location {
+    location_id: uuid::Uuid::parse_str("3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03").unwrap(),
+    world_id: world.world_id,
+    name: "The Shire".to_owned(),
+    ..Default::default()
+  };
+  let narnia = Location {
+    location_id: uuid::Uuid::parse_str("3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a08").unwrap(),
+    world_id: other_world.world_id,
+    name: "Narnia".to_owned(),
+    ..Default::default()
+  };
+  seed(
+    service.database(),
+    vec![&user, &world, &other_world, &shire, &narnia],
+  );
+
+  user
+}
+
+#[test]
+fn test_post_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+
+  let response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations")
+    .header(ContentType::JSON)
+    .body(json!({ "name": "Hobbiton" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_post_not_member() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+  let other_user = User {
+    username: "otheruser".to_owned(),
+    email: "other@example.com".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&other_user]);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .body(json!({ "name": "Hobbiton" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 403 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 403,
+    "title": "You are not permitted to perform this request",
+    "type": "tag:universe,2020:problems/authentication/forbidden"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_success() {
+  let service = ServiceWrapper::default();
+  let user = seed_world(&service);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "name": "  Hobbiton  ",
+        "kind": " City ",
+        "description": "Home of *Bag End*",
+        "parent": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03",
+        "coordinates": { "x": 120.5, "y": 80 }
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_rewrite_headers(&response, |h| {
+    regex_replace(h, r#"/locations/[a-z0-9]{8}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{12}"#, "/locations/[uuid]")
+  }), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/[uuid]>; rel="canonical"
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/locations", "post", 200);
+  assert_json_snapshot!(body, {
+        ".id" => "[uuid]",
+    },
+    @r###"
+  {
+    "coordinates": {
+      "x": 120.5,
+      "y": 80.0
+    },
+    "description": "Home of *Bag End*",
+    "descriptionHtml": "<p>Home of <em>Bag End</em></p>\n",
+    "id": "[uuid]",
+    "kind": "City",
+    "name": "Hobbiton",
+    "parent": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_minimal() {
+  let service = ServiceWrapper::default();
+  let user = seed_world(&service);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "name": "Middle-earth" }).to_string())
+    .dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/locations", "post", 200);
+  assert_json_snapshot!(body, {
+        ".id" => "[uuid]",
+    },
+    @r###"
+  {
+    "description": "",
+    "descriptionHtml": "",
+    "id": "[uuid]",
+    "kind": "",
+    "name": "Middle-earth",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_parent_in_other_world() {
+  let service = ServiceWrapper::default();
+  let user = seed_world(&service);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "name": "Cair Paravel",
+        "parent": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a08"
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "parent",
+        "title": "The parent location is not a location of the world",
+        "type": "tag:universe,2020:locations/validation-errors/parent/unknown"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_invalid_values() {
+  let service = ServiceWrapper::default();
+  let user = seed_world(&service);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "name": " ", "parent": "shire" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "name",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      },
+      {
+        "field": "parent",
+        "title": "The parent location is not a location of the world",
+        "type": "tag:universe,2020:locations/validation-errors/parent/unknown"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
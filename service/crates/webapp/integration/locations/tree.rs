@@ -0,0 +1,587 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use rocket::http::ContentType;
+use serde_json::json;
+use test_env_log::test;
+use universe_testdata::{seed, Location, User, World};
+
+fn seed_locations(service: &ServiceWrapper, visibility: &str) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    visibility: visibility.to_owned(),
+    ..Default::default()
+  };
+  let middle_earth = Location {
+    location_id: uuid::Uuid::parse_str("3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a01").unwrap(),
+    version: uuid::Uuid::parse_str("4f3c8d2b-7a5e-4c9f-b2da-6e8a3f9c5b01").unwrap(),
+    created: "2020-03-11T13:00:31Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    name: "Middle-earth".to_owned(),
+    kind: "Continent".to_owned(),
+    description: "".to_owned(),
+    ..Default::default()
+  };
+  let eriador = Location {
+    location_id: uuid::Uuid::parse_str("3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a02").unwrap(),
+    version: uuid::Uuid::parse_str("4f3c8d2b-7a5e-4c9f-b2da-6e8a3f9c5b02").unwrap(),
+    created: "2020-03-11T13:00:32Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    parent_id: Some(middle_earth.location_id),
+    name: "Eriador".to_owned(),
+    kind: "Region".to_owned(),
+    description: "".to_owned(),
+    ..Default::default()
+  };
+  let shire = Location {
+    location_id: uuid::Uuid::parse_str("3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03").unwrap(),
+    version: uuid::Uuid::parse_str("4f3c8d2b-7a5e-4c9f-b2da-6e8a3f9c5b03").unwrap(),
+    created: "2020-03-11T13:00:33Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    parent_id: Some(eriador.location_id),
+    name: "The Shire".to_owned(),
+    kind: "Region".to_owned(),
+    description: "".to_owned(),
+    ..Default::default()
+  };
+  let hobbiton = Location {
+    location_id: uuid::Uuid::parse_str("3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04").unwrap(),
+    version: uuid::Uuid::parse_str("4f3c8d2b-7a5e-4c9f-b2da-6e8a3f9c5b04").unwrap(),
+    created: "2020-03-11T13:00:34Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    parent_id: Some(shire.location_id),
+    name: "Hobbiton".to_owned(),
+    kind: "City".to_owned(),
+    description: "Home of *Bag End*".to_owned(),
+    x: Some(120.5),
+    y: Some(80.0),
+  };
+  let mordor = Location {
+    location_id: uuid::Uuid::parse_str("3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a05").unwrap(),
+    version: uuid::Uuid::parse_str("4f3c8d2b-7a5e-4c9f-b2da-6e8a3f9c5b05").unwrap(),
+    created: "2020-03-11T13:00:35Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    parent_id: Some(middle_earth.location_id),
+    name: "Mordor".to_owned(),
+    kind: "Region".to_owned(),
+    description: "".to_owned(),
+    ..Default::default()
+  };
+  seed(
+    service.database(),
+    vec![&user, &world, &middle_earth, &eriador, &shire, &hobbiton, &mordor],
+  );
+
+  user
+}
+
+#[test]
+fn test_get_ancestors() {
+  let service = ServiceWrapper::default();
+  seed_locations(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04/ancestors")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Cache-Control: public, max-age=3600
+  Vary: Authorization
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(
+    &body,
+    "/worlds/{worldId}/locations/{locationId}/ancestors",
+    "get",
+    200,
+  );
+  assert_json_snapshot!(body, @r###"
+  {
+    "entries": [
+      {
+        "description": "",
+        "descriptionHtml": "",
+        "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a01",
+        "kind": "Continent",
+        "name": "Middle-earth",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "description": "",
+        "descriptionHtml": "",
+        "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a02",
+        "kind": "Region",
+        "name": "Eriador",
+        "parent": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a01",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "description": "",
+        "descriptionHtml": "",
+        "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03",
+        "kind": "Region",
+        "name": "The Shire",
+        "parent": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a02",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      }
+    ],
+    "total": 3
+  }
+  "###);
+}
+
+#[test]
+fn test_get_ancestors_of_top_level_location() {
+  let service = ServiceWrapper::default();
+  seed_locations(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a01/ancestors")
+    .dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "entries": [],
+    "total": 0
+  }
+  "###);
+}
+
+#[test]
+fn test_get_ancestors_unknown_location() {
+  let service = ServiceWrapper::default();
+  seed_locations(&service, "public");
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a09/ancestors")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_get_descendants() {
+  let service = ServiceWrapper::default();
+  seed_locations(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a01/descendants")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Cache-Control: public, max-age=3600
+  Vary: Authorization
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(
+    &body,
+    "/worlds/{worldId}/locations/{locationId}/descendants",
+    "get",
+    200,
+  );
+  assert_json_snapshot!(body, @r###"
+  {
+    "entries": [
+      {
+        "depth": 1,
+        "description": "",
+        "descriptionHtml": "",
+        "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a02",
+        "kind": "Region",
+        "name": "Eriador",
+        "parent": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a01",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "depth": 1,
+        "description": "",
+        "descriptionHtml": "",
+        "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a05",
+        "kind": "Region",
+        "name": "Mordor",
+        "parent": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a01",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "depth": 2,
+        "description": "",
+        "descriptionHtml": "",
+        "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03",
+        "kind": "Region",
+        "name": "The Shire",
+        "parent": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a02",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "coordinates": {
+          "x": 120.5,
+          "y": 80.0
+        },
+        "depth": 3,
+        "description": "Home of *Bag End*",
+        "descriptionHtml": "<p>Home of <em>Bag End</em></p>\n",
+        "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04",
+        "kind": "City",
+        "name": "Hobbiton",
+        "parent": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      }
+    ],
+    "total": 4
+  }
+  "###);
+}
+
+#[test]
+fn test_get_descendants_limited_depth() {
+  let service = ServiceWrapper::default();
+  seed_locations(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a01/descendants?depth=1")
+    .dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "entries": [
+      {
+        "depth": 1,
+        "description": "",
+        "descriptionHtml": "",
+        "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a02",
+        "kind": "Region",
+        "name": "Eriador",
+        "parent": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a01",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "depth": 1,
+        "description": "",
+        "descriptionHtml": "",
+        "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a05",
+        "kind": "Region",
+        "name": "Mordor",
+        "parent": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a01",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      }
+    ],
+    "total": 2
+  }
+  "###);
+}
+
+#[test]
+fn test_get_descendants_invalid_depth() {
+  let service = ServiceWrapper::default();
+  seed_locations(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a01/descendants?depth=0")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "depth",
+        "title": "The depth must be at least 1",
+        "type": "tag:universe,2020:locations/validation-errors/depth/invalid"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_descendants_private_world() {
+  let service = ServiceWrapper::default();
+  seed_locations(&service, "private");
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a01/descendants")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_move_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_locations(&service, "public");
+
+  let response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03/move")
+    .header(ContentType::JSON)
+    .body(json!({ "parent": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a05" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_move_subtree() {
+  let service = ServiceWrapper::default();
+  let user = seed_locations(&service, "public");
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03/move")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "parent": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a05" }).to_string())
+    .dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(
+    &body,
+    "/worlds/{worldId}/locations/{locationId}/move",
+    "post",
+    200,
+  );
+  assert_json_snapshot!(body, @r###"
+  {
+    "description": "",
+    "descriptionHtml": "",
+    "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03",
+    "kind": "Region",
+    "name": "The Shire",
+    "parent": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a05",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04/ancestors")
+    .dispatch();
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "entries": [
+      {
+        "description": "",
+        "descriptionHtml": "",
+        "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a01",
+        "kind": "Continent",
+        "name": "Middle-earth",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "description": "",
+        "descriptionHtml": "",
+        "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a05",
+        "kind": "Region",
+        "name": "Mordor",
+        "parent": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a01",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "description": "",
+        "descriptionHtml": "",
+        "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03",
+        "kind": "Region",
+        "name": "The Shire",
+        "parent": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a05",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      }
+    ],
+    "total": 3
+  }
+  "###);
+}
+
+#[test]
+fn test_move_to_top_level() {
+  let service = ServiceWrapper::default();
+  let user = seed_locations(&service, "public");
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03/move")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "parent": null }).to_string())
+    .dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "description": "",
+    "descriptionHtml": "",
+    "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03",
+    "kind": "Region",
+    "name": "The Shire",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_move_inside_itself() {
+  let service = ServiceWrapper::default();
+  let user = seed_locations(&service, "public");
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03/move")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "parent": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "parent",
+        "title": "A location may not be moved inside itself or any location within it",
+        "type": "tag:universe,2020:locations/validation-errors/parent/cycle"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_move_inside_descendant() {
+  let service = ServiceWrapper::default();
+  let user = seed_locations(&service, "public");
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a01/move")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "parent": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "parent",
+        "title": "A location may not be moved inside itself or any location within it",
+        "type": "tag:universe,2020:locations/validation-errors/parent/cycle"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_move_unknown_parent() {
+  let service = ServiceWrapper::default();
+  let user = seed_locations(&service, "public");
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03/move")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "parent": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a09" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "parent",
+        "title": "The parent location is not a location of the world",
+        "type": "tag:universe,2020:locations/validation-errors/parent/unknown"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_move_missing_parent() {
+  let service = ServiceWrapper::default();
+  let user = seed_locations(&service, "public");
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03/move")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({}).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "parent",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
@@ -0,0 +1,112 @@
+use crate::{authenticate_user, build_headers, build_json_body, ServiceWrapper};
+use insta::{assert_json_snapshot, assert_snapshot};
+use test_env_log::test;
+use universe_testdata::{seed, Map, MapFeature, User, World};
+
+fn seed_map(service: &ServiceWrapper) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    ..Default::default()
+  };
+  let map = Map {
+    map_id: uuid::Uuid::parse_str("7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01").unwrap(),
+    world_id: world.world_id,
+    name: "Middle-earth".to_owned(),
+    ..Default::default()
+  };
+  let feature = MapFeature {
+    feature_id: uuid::Uuid::parse_str("2b8e4d6f-1a3c-4e5b-9d7f-3c5e7a9b1d01").unwrap(),
+    world_id: world.world_id,
+    map_id: map.map_id,
+    name: "Hobbiton".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&user, &world, &map, &feature]);
+
+  user
+}
+
+#[test]
+fn test_delete_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_map(&service);
+
+  let response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_delete_success() {
+  let service = ServiceWrapper::default();
+  let user = seed_map(&service);
+
+  let response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 204 No Content.
+  Server: Rocket
+  "###);
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01")
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/features/2b8e4d6f-1a3c-4e5b-9d7f-3c5e7a9b1d01")
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_delete_unknown_map() {
+  let service = ServiceWrapper::default();
+  let user = seed_map(&service);
+
+  let mut response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b09")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested map could not be found",
+    "type": "tag:universe,2020:maps/problems/unknown-map"
+  }
+  "###);
+}
@@ -0,0 +1,888 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, build_rewrite_headers,
+  regex_replace, ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use rocket::http::ContentType;
+use serde_json::json;
+use test_env_log::test;
+use universe_testdata::{seed, Location, Map, MapFeature, User, World};
+
+fn seed_features(service: &ServiceWrapper, visibility: &str) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    visibility: visibility.to_owned(),
+    ..Default::default()
+  };
+  let other_world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d9").unwrap(),
+    owner_id: user.user_id,
+    name: "Other World".to_owned(),
+    slug: "other-world".to_owned(),
+    ..Default::default()
+  };
+  let hobbiton = Location {
+    location_id: uuid::Uuid::parse_str("3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04").unwrap(),
+    world_id: world.world_id,
+    name: "Hobbiton".to_owned(),
+    ..Default::default()
+  };
+  let narnia = Location {
+    location_id: uuid::Uuid::parse_str("3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a08").unwrap(),
+    world_id: other_world.world_id,
+    name: "Narnia".to_owned(),
+    ..Default::default()
+  };
+  let middle_earth = Map {
+    map_id: uuid::Uuid::parse_str("7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01").unwrap(),
+    world_id: world.world_id,
+    name: "Middle-earth".to_owned(),
+    image_id: Some(uuid::Uuid::parse_str("9a4c2e6b-8d1f-4b3a-a5e7-0c2d4f6b8e19").unwrap()),
+    image_content_type: Some("image/png".to_owned()),
+    image_width: Some(1000),
+    image_height: Some(800),
+    ..Default::default()
+  };
+  let beleriand = Map {
+    map_id: uuid::Uuid::parse_str("7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b02").unwrap(),
+    world_id: world.world_id,
+    name: "Beleriand".to_owned(),
+    image_id: None,
+    image_content_type: None,
+    image_width: None,
+    image_height: None,
+    ..Default::default()
+  };
+  let hobbiton_pin = MapFeature {
+    feature_id: uuid::Uuid::parse_str("2b8e4d6f-1a3c-4e5b-9d7f-3c5e7a9b1d01").unwrap(),
+    version: uuid::Uuid::parse_str("5c7e9a1b-3d5f-4a7c-9e1b-4d6f8a0c2e01").unwrap(),
+    created: "2020-03-11T13:00:31Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    map_id: middle_earth.map_id,
+    name: "Hobbiton".to_owned(),
+    kind: "pin".to_owned(),
+    geometry: json!({ "type": "Point", "coordinates": [120.5, 80.0] }),
+    location_id: Some(hobbiton.location_id),
+    ..Default::default()
+  };
+  let east_road = MapFeature {
+    feature_id: uuid::Uuid::parse_str("2b8e4d6f-1a3c-4e5b-9d7f-3c5e7a9b1d02").unwrap(),
+    version: uuid::Uuid::parse_str("5c7e9a1b-3d5f-4a7c-9e1b-4d6f8a0c2e02").unwrap(),
+    created: "2020-03-11T13:00:32Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    map_id: middle_earth.map_id,
+    name: "The East Road".to_owned(),
+    kind: "polyline".to_owned(),
+    geometry: json!({
+      "type": "LineString",
+      "coordinates": [[120.5, 80.0], [240.0, 95.5], [410.0, 120.0]]
+    }),
+    ..Default::default()
+  };
+  let shire = MapFeature {
+    feature_id: uuid::Uuid::parse_str("2b8e4d6f-1a3c-4e5b-9d7f-3c5e7a9b1d03").unwrap(),
+    version: uuid::Uuid::parse_str("5c7e9a1b-3d5f-4a7c-9e1b-4d6f8a0c2e03").unwrap(),
+    created: "2020-03-11T13:00:33Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    map_id: middle_earth.map_id,
+    name: "The Shire".to_owned(),
+    kind: "region".to_owned(),
+    geometry: json!({
+      "type": "Polygon",
+      "coordinates": [[[80.0, 40.0], [180.0, 40.0], [180.0, 120.0], [80.0, 120.0], [80.0, 40.0]]]
+    }),
+    ..Default::default()
+  };
+  seed(
+    service.database(),
+    vec![
+      &user,
+      &world,
+      &other_world,
+      &hobbiton,
+      &narnia,
+      &middle_earth,
+      &beleriand,
+      &hobbiton_pin,
+      &east_road,
+      &shire,
+    ],
+  );
+
+  user
+}
+
+#[test]
+fn test_list_features() {
+  let service = ServiceWrapper::default();
+  seed_features(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/features")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/geo+json
+  Cache-Control: public, max-age=3600
+  Vary: Authorization
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(
+    &body,
+    "/worlds/{worldId}/maps/{mapId}/features",
+    "get",
+    200,
+  );
+  assert_json_snapshot!(body, @r###"
+  {
+    "bbox": [
+      0.0,
+      0.0,
+      1000.0,
+      800.0
+    ],
+    "features": [
+      {
+        "geometry": {
+          "coordinates": [
+            120.5,
+            80.0
+          ],
+          "type": "Point"
+        },
+        "id": "2b8e4d6f-1a3c-4e5b-9d7f-3c5e7a9b1d01",
+        "properties": {
+          "kind": "pin",
+          "link": {
+            "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04",
+            "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04",
+            "type": "location"
+          },
+          "name": "Hobbiton"
+        },
+        "type": "Feature"
+      },
+      {
+        "geometry": {
+          "coordinates": [
+            [
+              120.5,
+              80.0
+            ],
+            [
+              240.0,
+              95.5
+            ],
+            [
+              410.0,
+              120.0
+            ]
+          ],
+          "type": "LineString"
+        },
+        "id": "2b8e4d6f-1a3c-4e5b-9d7f-3c5e7a9b1d02",
+        "properties": {
+          "kind": "polyline",
+          "name": "The East Road"
+        },
+        "type": "Feature"
+      },
+      {
+        "geometry": {
+          "coordinates": [
+            [
+              [
+                80.0,
+                40.0
+              ],
+              [
+                180.0,
+                40.0
+              ],
+              [
+                180.0,
+                120.0
+              ],
+              [
+                80.0,
+                120.0
+              ],
+              [
+                80.0,
+                40.0
+              ]
+            ]
+          ],
+          "type": "Polygon"
+        },
+        "id": "2b8e4d6f-1a3c-4e5b-9d7f-3c5e7a9b1d03",
+        "properties": {
+          "kind": "region",
+          "name": "The Shire"
+        },
+        "type": "Feature"
+      }
+    ],
+    "type": "FeatureCollection"
+  }
+  "###);
+}
+
+#[test]
+fn test_list_features_by_kind() {
+  let service = ServiceWrapper::default();
+  seed_features(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/features?kind=region")
+    .dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(
+    &body,
+    "/worlds/{worldId}/maps/{mapId}/features",
+    "get",
+    200,
+  );
+  assert_json_snapshot!(body, @r###"
+  {
+    "bbox": [
+      0.0,
+      0.0,
+      1000.0,
+      800.0
+    ],
+    "features": [
+      {
+        "geometry": {
+          "coordinates": [
+            [
+              [
+                80.0,
+                40.0
+              ],
+              [
+                180.0,
+                40.0
+              ],
+              [
+                180.0,
+                120.0
+              ],
+              [
+                80.0,
+                120.0
+              ],
+              [
+                80.0,
+                40.0
+              ]
+            ]
+          ],
+          "type": "Polygon"
+        },
+        "id": "2b8e4d6f-1a3c-4e5b-9d7f-3c5e7a9b1d03",
+        "properties": {
+          "kind": "region",
+          "name": "The Shire"
+        },
+        "type": "Feature"
+      }
+    ],
+    "type": "FeatureCollection"
+  }
+  "###);
+}
+
+#[test]
+fn test_list_features_invalid_kind() {
+  let service = ServiceWrapper::default();
+  seed_features(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/features?kind=castle")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "kind",
+        "title": "The kind must be one of \"pin\", \"polyline\" or \"region\"",
+        "type": "tag:universe,2020:maps/validation-errors/kind/invalid"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_list_features_private_world() {
+  let service = ServiceWrapper::default();
+  let user = seed_features(&service, "private");
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/features")
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/features")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/geo+json
+  Cache-Control: private, max-age=3600
+  Vary: Authorization
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_get_feature() {
+  let service = ServiceWrapper::default();
+  seed_features(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/features/2b8e4d6f-1a3c-4e5b-9d7f-3c5e7a9b1d01")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/geo+json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/features/2b8e4d6f-1a3c-4e5b-9d7f-3c5e7a9b1d01>; rel="self"
+  ETag: "5c7e9a1b-3d5f-4a7c-9e1b-4d6f8a0c2e01"
+  Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT
+  Cache-Control: public, max-age=3600
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(
+    &body,
+    "/worlds/{worldId}/maps/{mapId}/features/{featureId}",
+    "get",
+    200,
+  );
+  assert_json_snapshot!(body, @r###"
+  {
+    "geometry": {
+      "coordinates": [
+        120.5,
+        80.0
+      ],
+      "type": "Point"
+    },
+    "id": "2b8e4d6f-1a3c-4e5b-9d7f-3c5e7a9b1d01",
+    "properties": {
+      "kind": "pin",
+      "link": {
+        "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04",
+        "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04",
+        "type": "location"
+      },
+      "name": "Hobbiton"
+    },
+    "type": "Feature"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_unknown_feature() {
+  let service = ServiceWrapper::default();
+  seed_features(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b02/features/2b8e4d6f-1a3c-4e5b-9d7f-3c5e7a9b1d01")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested feature could not be found",
+    "type": "tag:universe,2020:maps/problems/unknown-feature"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_feature_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_features(&service, "public");
+
+  let response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/features")
+    .header(ContentType::JSON)
+    .body(json!({ "type": "Feature", "geometry": { "type": "Point", "coordinates": [500, 400] } }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_post_feature_success() {
+  let service = ServiceWrapper::default();
+  let user = seed_features(&service, "public");
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/features")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "type": "Feature",
+        "geometry": { "type": "Point", "coordinates": [130, 85.5] },
+        "properties": {
+          "name": "  Bag End  ",
+          "link": { "type": "location", "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04" }
+        }
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_rewrite_headers(&response, |h| {
+    regex_replace(h, r#"/features/[a-z0-9]{8}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{12}"#, "/features/[uuid]")
+  }), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/geo+json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/features/[uuid]>; rel="canonical"
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(
+    &body,
+    "/worlds/{worldId}/maps/{mapId}/features",
+    "post",
+    200,
+  );
+  assert_json_snapshot!(body, {
+        ".id" => "[uuid]",
+    },
+    @r###"
+  {
+    "geometry": {
+      "coordinates": [
+        130.0,
+        85.5
+      ],
+      "type": "Point"
+    },
+    "id": "[uuid]",
+    "properties": {
+      "kind": "pin",
+      "link": {
+        "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04",
+        "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04",
+        "type": "location"
+      },
+      "name": "Bag End"
+    },
+    "type": "Feature"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_feature_out_of_bounds() {
+  let service = ServiceWrapper::default();
+  let user = seed_features(&service, "public");
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/features")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "type": "Feature",
+        "geometry": { "type": "LineString", "coordinates": [[100, 100], [1200, 100]] },
+        "properties": { "name": "The Long Road" }
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "geometry",
+        "title": "Every position must be within the map image",
+        "type": "tag:universe,2020:maps/validation-errors/geometry/out-of-bounds"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_feature_no_image() {
+  let service = ServiceWrapper::default();
+  let user = seed_features(&service, "public");
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b02/features")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "type": "Feature", "geometry": { "type": "Point", "coordinates": [10, 10] } }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "geometry",
+        "title": "Features can't be placed on a map until it has an image",
+        "type": "tag:universe,2020:maps/validation-errors/geometry/no-image"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_feature_invalid() {
+  let service = ServiceWrapper::default();
+  let user = seed_features(&service, "public");
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/features")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "type": "Feature",
+        "geometry": { "type": "MultiPoint", "coordinates": [[10, 10], [20, 20]] },
+        "properties": { "link": { "type": "dragon", "id": "Smaug" } }
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "geometry",
+        "title": "The geometry must be a GeoJSON Point, LineString or Polygon",
+        "type": "tag:universe,2020:maps/validation-errors/geometry/unsupported"
+      },
+      {
+        "field": "properties.link",
+        "title": "The link is not to a location, character or article of the world",
+        "type": "tag:universe,2020:maps/validation-errors/link/unknown"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_feature_link_in_other_world() {
+  let service = ServiceWrapper::default();
+  let user = seed_features(&service, "public");
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/features")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "type": "Feature",
+        "geometry": { "type": "Point", "coordinates": [500, 400] },
+        "properties": {
+          "name": "Cair Paravel",
+          "link": { "type": "location", "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a08" }
+        }
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "properties.link",
+        "title": "The link is not to a location, character or article of the world",
+        "type": "tag:universe,2020:maps/validation-errors/link/unknown"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_put_feature() {
+  let service = ServiceWrapper::default();
+  let user = seed_features(&service, "public");
+
+  let mut response = service
+    .put("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/features/2b8e4d6f-1a3c-4e5b-9d7f-3c5e7a9b1d01")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "type": "Feature",
+        "geometry": {
+          "type": "Polygon",
+          "coordinates": [[[100, 60], [140, 60], [140, 100], [100, 100], [100, 60]]]
+        },
+        "properties": { "name": "Hobbiton and Bywater" }
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(
+    &body,
+    "/worlds/{worldId}/maps/{mapId}/features/{featureId}",
+    "put",
+    200,
+  );
+  assert_json_snapshot!(body, @r###"
+  {
+    "geometry": {
+      "coordinates": [
+        [
+          [
+            100.0,
+            60.0
+          ],
+          [
+            140.0,
+            60.0
+          ],
+          [
+            140.0,
+            100.0
+          ],
+          [
+            100.0,
+            100.0
+          ],
+          [
+            100.0,
+            60.0
+          ]
+        ]
+      ],
+      "type": "Polygon"
+    },
+    "id": "2b8e4d6f-1a3c-4e5b-9d7f-3c5e7a9b1d01",
+    "properties": {
+      "kind": "region",
+      "name": "Hobbiton and Bywater"
+    },
+    "type": "Feature"
+  }
+  "###);
+}
+
+#[test]
+fn test_put_unknown_feature() {
+  let service = ServiceWrapper::default();
+  let user = seed_features(&service, "public");
+
+  let mut response = service
+    .put("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/features/2b8e4d6f-1a3c-4e5b-9d7f-3c5e7a9b1d09")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "type": "Feature", "geometry": { "type": "Point", "coordinates": [10, 10] } }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested feature could not be found",
+    "type": "tag:universe,2020:maps/problems/unknown-feature"
+  }
+  "###);
+}
+
+#[test]
+fn test_delete_feature() {
+  let service = ServiceWrapper::default();
+  let user = seed_features(&service, "public");
+
+  let response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/features/2b8e4d6f-1a3c-4e5b-9d7f-3c5e7a9b1d02")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 204 No Content.
+  Server: Rocket
+  "###);
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/features")
+    .dispatch();
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "bbox": [
+      0.0,
+      0.0,
+      1000.0,
+      800.0
+    ],
+    "features": [
+      {
+        "geometry": {
+          "coordinates": [
+            120.5,
+            80.0
+          ],
+          "type": "Point"
+        },
+        "id": "2b8e4d6f-1a3c-4e5b-9d7f-3c5e7a9b1d01",
+        "properties": {
+          "kind": "pin",
+          "link": {
+            "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04",
+            "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04",
+            "type": "location"
+          },
+          "name": "Hobbiton"
+        },
+        "type": "Feature"
+      },
+      {
+        "geometry": {
+          "coordinates": [
+            [
+              [
+                80.0,
+                40.0
+              ],
+              [
+                180.0,
+                40.0
+              ],
+              [
+                180.0,
+                120.0
+              ],
+              [
+                80.0,
+                120.0
+              ],
+              [
+                80.0,
+                40.0
+              ]
+            ]
+          ],
+          "type": "Polygon"
+        },
+        "id": "2b8e4d6f-1a3c-4e5b-9d7f-3c5e7a9b1d03",
+        "properties": {
+          "kind": "region",
+          "name": "The Shire"
+        },
+        "type": "Feature"
+      }
+    ],
+    "type": "FeatureCollection"
+  }
+  "###);
+}
+
+#[test]
+fn test_delete_unknown_feature() {
+  let service = ServiceWrapper::default();
+  let user = seed_features(&service, "public");
+
+  let mut response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/features/2b8e4d6f-1a3c-4e5b-9d7f-3c5e7a9b1d09")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested feature could not be found",
+    "type": "tag:universe,2020:maps/problems/unknown-feature"
+  }
+  "###);
+}
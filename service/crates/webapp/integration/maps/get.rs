@@ -0,0 +1,198 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use test_env_log::test;
+use universe_testdata::{seed, Map, User, World};
+
+fn seed_maps(service: &ServiceWrapper, visibility: &str) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    visibility: visibility.to_owned(),
+    ..Default::default()
+  };
+  let middle_earth = Map {
+    map_id: uuid::Uuid::parse_str("7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01").unwrap(),
+    version: uuid::Uuid::parse_str("8e2a4c6d-9b1f-4d3e-a7c5-2f4b6d8e0a01").unwrap(),
+    created: "2020-03-11T13:00:31Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    name: "Middle-earth".to_owned(),
+    image_id: Some(uuid::Uuid::parse_str("9a4c2e6b-8d1f-4b3a-a5e7-0c2d4f6b8e19").unwrap()),
+    image_content_type: Some("image/png".to_owned()),
+    image_width: Some(1000),
+    image_height: Some(800),
+  };
+  let beleriand = Map {
+    map_id: uuid::Uuid::parse_str("7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b02").unwrap(),
+    version: uuid::Uuid::parse_str("8e2a4c6d-9b1f-4d3e-a7c5-2f4b6d8e0a02").unwrap(),
+    created: "2020-03-11T13:00:32Z".parse().unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    world_id: world.world_id,
+    name: "Beleriand".to_owned(),
+    image_id: None,
+    image_content_type: None,
+    image_width: None,
+    image_height: None,
+  };
+  seed(
+    service.database(),
+    vec![&user, &world, &middle_earth, &beleriand],
+  );
+
+  user
+}
+
+#[test]
+fn test_list_maps() {
+  let service = ServiceWrapper::default();
+  seed_maps(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Cache-Control: public, max-age=3600
+  Vary: Authorization
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/maps", "get", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "entries": [
+      {
+        "features": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b02/features",
+        "id": "7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b02",
+        "name": "Beleriand",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "features": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/features",
+        "id": "7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01",
+        "image": {
+          "contentType": "image/png",
+          "height": 800,
+          "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/image/9a4c2e6b-8d1f-4b3a-a5e7-0c2d4f6b8e19",
+          "width": 1000
+        },
+        "name": "Middle-earth",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      }
+    ],
+    "total": 2
+  }
+  "###);
+}
+
+#[test]
+fn test_get_map() {
+  let service = ServiceWrapper::default();
+  seed_maps(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01>; rel="self"
+  Accept-Patch: application/merge-patch+json
+  ETag: "8e2a4c6d-9b1f-4d3e-a7c5-2f4b6d8e0a01"
+  Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT
+  Cache-Control: public, max-age=3600
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/maps/{mapId}", "get", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "features": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/features",
+    "id": "7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01",
+    "image": {
+      "contentType": "image/png",
+      "height": 800,
+      "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/image/9a4c2e6b-8d1f-4b3a-a5e7-0c2d4f6b8e19",
+      "width": 1000
+    },
+    "name": "Middle-earth",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_map_private_world() {
+  let service = ServiceWrapper::default();
+  let user = seed_maps(&service, "private");
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b02")
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b02")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b02>; rel="self"
+  Accept-Patch: application/merge-patch+json
+  ETag: "8e2a4c6d-9b1f-4d3e-a7c5-2f4b6d8e0a02"
+  Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT
+  Cache-Control: private, max-age=3600
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "features": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b02/features",
+    "id": "7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b02",
+    "name": "Beleriand",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_unknown_map() {
+  let service = ServiceWrapper::default();
+  seed_maps(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b09")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested map could not be found",
+    "type": "tag:universe,2020:maps/problems/unknown-map"
+  }
+  "###);
+}
@@ -0,0 +1,216 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, ServiceWrapper,
+};
+use image::{DynamicImage, ImageOutputFormat};
+use insta::{assert_json_snapshot, assert_snapshot};
+use test_env_log::test;
+use universe_testdata::{seed, Map, User, World};
+
+fn seed_map(service: &ServiceWrapper, visibility: &str) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    visibility: visibility.to_owned(),
+    ..Default::default()
+  };
+  let map = Map {
+    map_id: uuid::Uuid::parse_str("7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01").unwrap(),
+    world_id: world.world_id,
+    name: "Middle-earth".to_owned(),
+    image_id: None,
+    image_content_type: None,
+    image_width: None,
+    image_height: None,
+    ..Default::default()
+  };
+  seed(service.database(), vec![&user, &world, &map]);
+
+  user
+}
+
+fn build_png(width: u32, height: u32) -> Vec<u8> {
+  let mut bytes = vec![];
+  DynamicImage::new_rgb8(width, height)
+    .write_to(&mut bytes, ImageOutputFormat::Png)
+    .unwrap();
+  bytes
+}
+
+#[test]
+fn test_put_image_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_map(&service, "public");
+
+  let response = service
+    .put("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/image")
+    .body(build_png(640, 480))
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_put_image_unsupported_format() {
+  let service = ServiceWrapper::default();
+  let user = seed_map(&service, "public");
+
+  let mut response = service
+    .put("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/image")
+    .header(authenticate_user(&service, &user).unwrap())
+    .body("This is not an image")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 415 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 415,
+    "title": "The map image must be a PNG, JPEG or WebP image",
+    "type": "tag:universe,2020:maps/problems/image-unsupported-format"
+  }
+  "###);
+}
+
+#[test]
+fn test_put_image_unknown_map() {
+  let service = ServiceWrapper::default();
+  let user = seed_map(&service, "public");
+
+  let mut response = service
+    .put("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b09/image")
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(build_png(640, 480))
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested map could not be found",
+    "type": "tag:universe,2020:maps/problems/unknown-map"
+  }
+  "###);
+}
+
+#[test]
+fn test_put_image_success() {
+  let service = ServiceWrapper::default();
+  let user = seed_map(&service, "public");
+
+  let mut response = service
+    .put("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/image")
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(build_png(640, 480))
+    .dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(
+    &body,
+    "/worlds/{worldId}/maps/{mapId}/image",
+    "put",
+    200,
+  );
+  assert_json_snapshot!(body, {
+        ".image.href" => "[url]",
+    },
+    @r###"
+  {
+    "features": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/features",
+    "id": "7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01",
+    "image": {
+      "contentType": "image/png",
+      "height": 480,
+      "href": "[url]",
+      "width": 640
+    },
+    "name": "Middle-earth",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+
+  let href = body["image"]["href"].as_str().unwrap();
+  let mut response = service.get(href).dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: image/png
+  Cache-Control: public, max-age=31536000, immutable
+  Server: Rocket
+  "###);
+  assert_eq!(build_png(640, 480), response.body_bytes().unwrap());
+}
+
+#[test]
+fn test_get_image_private_world() {
+  let service = ServiceWrapper::default();
+  let user = seed_map(&service, "private");
+
+  let mut response = service
+    .put("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/image")
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(build_png(640, 480))
+    .dispatch();
+  let body = build_json_body(&mut response);
+  let href = body["image"]["href"].as_str().unwrap();
+
+  let response = service.get(href).dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+
+  let response = service
+    .get(href)
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: image/png
+  Cache-Control: private, max-age=31536000, immutable
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_get_unknown_image() {
+  let service = ServiceWrapper::default();
+  seed_map(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/image/9a4c2e6b-8d1f-4b3a-a5e7-0c2d4f6b8e19")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested map image could not be found",
+    "type": "tag:universe,2020:maps/problems/unknown-image"
+  }
+  "###);
+}
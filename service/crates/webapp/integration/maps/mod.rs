@@ -0,0 +1,6 @@
+mod delete;
+mod features;
+mod get;
+mod image;
+mod patch;
+mod post;
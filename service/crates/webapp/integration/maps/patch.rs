@@ -0,0 +1,144 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use rocket::http::ContentType;
+use serde_json::json;
+use std::str::FromStr;
+use test_env_log::test;
+use universe_testdata::{seed, Map, User, World};
+
+fn seed_map(service: &ServiceWrapper) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    ..Default::default()
+  };
+  let map = Map {
+    map_id: uuid::Uuid::parse_str("7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01").unwrap(),
+    world_id: world.world_id,
+    name: "Middle-earth".to_owned(),
+    image_id: Some(uuid::Uuid::parse_str("9a4c2e6b-8d1f-4b3a-a5e7-0c2d4f6b8e19").unwrap()),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&user, &world, &map]);
+
+  user
+}
+
+#[test]
+fn test_patch_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_map(&service);
+
+  let response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .body(json!({ "name": "Arda" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_patch_unknown_map() {
+  let service = ServiceWrapper::default();
+  let user = seed_map(&service);
+
+  let mut response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b09")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "name": "Arda" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested map could not be found",
+    "type": "tag:universe,2020:maps/problems/unknown-map"
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_name() {
+  let service = ServiceWrapper::default();
+  let user = seed_map(&service);
+
+  let mut response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "name": "  Arda  " }).to_string())
+    .dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/maps/{mapId}", "patch", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "features": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/features",
+    "id": "7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01",
+    "image": {
+      "contentType": "image/png",
+      "height": 800,
+      "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/image/9a4c2e6b-8d1f-4b3a-a5e7-0c2d4f6b8e19",
+      "width": 1000
+    },
+    "name": "Arda",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_blank_name() {
+  let service = ServiceWrapper::default();
+  let user = seed_map(&service);
+
+  let mut response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "name": "  " }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "name",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
@@ -0,0 +1,282 @@
+use crate::{authenticate_user, build_headers, build_json_body, ServiceWrapper};
+use insta::{assert_json_snapshot, assert_snapshot};
+use serde_json::json;
+use test_env_log::test;
+use universe_testdata::{
+  seed, Article, Calendar, Character, Location, Relationship, RelationshipDates, User, World,
+};
+
+fn seed_relationships(service: &ServiceWrapper, visibility: &str) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    visibility: visibility.to_owned(),
+    ..Default::default()
+  };
+  let character = |id: &str, name: &str| Character {
+    character_id: uuid::Uuid::parse_str(id).unwrap(),
+    world_id: world.world_id,
+    name: name.to_owned(),
+    ..Default::default()
+  };
+  let frodo = character("8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c01", "Frodo Baggins");
+  let bilbo = character("8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c02", "Bilbo Baggins");
+  let sam = character("8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c03", "Samwise Gamgee");
+  let gollum = character("8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c04", "Gollum");
+  let sauron = character("8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c05", "Sauron");
+  let saruman = character("8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c06", "Saruman");
+  let bag_end = Location {
+    location_id: uuid::Uuid::parse_str("8c1e3a5b-7d9f-4b2c-a4e6-2f3b5d7a9c01").unwrap(),
+    world_id: world.world_id,
+    name: "Bag End".to_owned(),
+    ..Default::default()
+  };
+  let red_book = Article {
+    article_id: uuid::Uuid::parse_str("8c1e3a5b-7d9f-4b2c-a4e6-3f3b5d7a9c01").unwrap(),
+    world_id: world.world_id,
+    title: "The Red Book of Westmarch".to_owned(),
+    slug: "red-book".to_owned(),
+    ..Default::default()
+  };
+  let calendar = Calendar {
+    calendar_id: uuid::Uuid::parse_str("8c1e3a5b-7d9f-4b2c-a4e6-4f3b5d7a9c01").unwrap(),
+    world_id: world.world_id,
+    name: "Shire Reckoning".to_owned(),
+    definition: json!({
+      "months": [
+        { "name": "Afteryule", "days": 30 },
+        { "name": "Solmath", "days": 30 }
+      ]
+    }),
+    ..Default::default()
+  };
+  let relationship = |n: u32, source: (&str, uuid::Uuid), relation: &str, target: (&str, uuid::Uuid), symmetric: bool| {
+    Relationship {
+      relationship_id: uuid::Uuid::parse_str(&format!("9e1a3c5b-7d2f-4b6e-8a0c-3e5b7d9f1a{:02}", n)).unwrap(),
+      version: uuid::Uuid::parse_str(&format!("a1b3c5d7-e9f1-4a3b-8c5d-7e9f1a3b5c{:02}", n)).unwrap(),
+      created: format!("2020-03-11T13:00:{:02}Z", 30 + n).parse().unwrap(),
+      updated: "2020-03-11T13:00:45Z".parse().unwrap(),
+      world_id: world.world_id,
+      relation: relation.to_owned(),
+      symmetric,
+      source_type: source.0.to_owned(),
+      source_id: source.1,
+      target_type: target.0.to_owned(),
+      target_id: target.1,
+    }
+  };
+  let uncle = relationship(1, ("character", bilbo.character_id), "uncle of", ("character", frodo.character_id), false);
+  let lives_in = relationship(2, ("character", frodo.character_id), "lives in", ("location", bag_end.location_id), false);
+  let lives_in_dates = RelationshipDates {
+    relationship_id: lives_in.relationship_id,
+    world_id: world.world_id,
+    calendar_id: calendar.calendar_id,
+    start: Some((2, 1, 22)),
+    end: None,
+  };
+  let author = relationship(3, ("character", bilbo.character_id), "author of", ("article", red_book.article_id), false);
+  let friend = relationship(4, ("character", sam.character_id), "friend of", ("character", frodo.character_id), true);
+  let rival = relationship(5, ("character", gollum.character_id), "rival of", ("character", sam.character_id), true);
+  let ally = relationship(6, ("character", sauron.character_id), "ally of", ("character", saruman.character_id), true);
+
+  seed(
+    service.database(),
+    vec![
+      &user,
+      &world,
+      &frodo,
+      &bilbo,
+      &sam,
+      &gollum,
+      &sauron,
+      &saruman,
+      &bag_end,
+      &red_book,
+      &calendar,
+      &uncle,
+      &lives_in,
+      &lives_in_dates,
+      &author,
+      &friend,
+      &rival,
+      &ally,
+    ],
+  );
+
+  user
+}
+
+#[test]
+fn test_delete_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_relationships(&service, "public");
+
+  let response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/relationships/9e1a3c5b-7d2f-4b6e-8a0c-3e5b7d9f1a01")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_delete_success() {
+  let service = ServiceWrapper::default();
+  let user = seed_relationships(&service, "public");
+
+  let response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/relationships/9e1a3c5b-7d2f-4b6e-8a0c-3e5b7d9f1a01")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 204 No Content.
+  Server: Rocket
+  "###);
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/relationships/9e1a3c5b-7d2f-4b6e-8a0c-3e5b7d9f1a01")
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested relationship could not be found",
+    "type": "tag:universe,2020:relationships/problems/unknown-relationship"
+  }
+  "###);
+}
+
+#[test]
+fn test_delete_unknown_relationship() {
+  let service = ServiceWrapper::default();
+  let user = seed_relationships(&service, "public");
+
+  let mut response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/relationships/9e1a3c5b-7d2f-4b6e-8a0c-3e5b7d9f1a99")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested relationship could not be found",
+    "type": "tag:universe,2020:relationships/problems/unknown-relationship"
+  }
+  "###);
+}
+
+#[test]
+fn test_deleting_entity_deletes_relationships() {
+  let service = ServiceWrapper::default();
+  let user = seed_relationships(&service, "public");
+
+  let response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c02")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 204 No Content.
+  Server: Rocket
+  "###);
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/relationships")
+    .dispatch();
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "entries": [
+      {
+        "during": {
+          "calendar": "8c1e3a5b-7d9f-4b2c-a4e6-4f3b5d7a9c01",
+          "start": "22 Afteryule 2"
+        },
+        "id": "9e1a3c5b-7d2f-4b6e-8a0c-3e5b7d9f1a02",
+        "relation": "lives in",
+        "source": {
+          "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c01",
+          "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c01",
+          "type": "character"
+        },
+        "symmetric": false,
+        "target": {
+          "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/8c1e3a5b-7d9f-4b2c-a4e6-2f3b5d7a9c01",
+          "id": "8c1e3a5b-7d9f-4b2c-a4e6-2f3b5d7a9c01",
+          "type": "location"
+        },
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "id": "9e1a3c5b-7d2f-4b6e-8a0c-3e5b7d9f1a04",
+        "relation": "friend of",
+        "source": {
+          "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c03",
+          "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c03",
+          "type": "character"
+        },
+        "symmetric": true,
+        "target": {
+          "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c01",
+          "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c01",
+          "type": "character"
+        },
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "id": "9e1a3c5b-7d2f-4b6e-8a0c-3e5b7d9f1a05",
+        "relation": "rival of",
+        "source": {
+          "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c04",
+          "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c04",
+          "type": "character"
+        },
+        "symmetric": true,
+        "target": {
+          "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c03",
+          "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c03",
+          "type": "character"
+        },
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "id": "9e1a3c5b-7d2f-4b6e-8a0c-3e5b7d9f1a06",
+        "relation": "ally of",
+        "source": {
+          "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c05",
+          "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c05",
+          "type": "character"
+        },
+        "symmetric": true,
+        "target": {
+          "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c06",
+          "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c06",
+          "type": "character"
+        },
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      }
+    ],
+    "total": 4
+  }
+  "###);
+}
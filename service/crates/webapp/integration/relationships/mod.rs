@@ -0,0 +1,5 @@
+mod delete;
+mod get;
+mod graph;
+mod patch;
+mod post;
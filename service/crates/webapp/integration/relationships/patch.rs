@@ -0,0 +1,325 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, build_rewrite_headers,
+  regex_replace, ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use rocket::http::ContentType;
+use serde_json::json;
+use std::str::FromStr;
+use test_env_log::test;
+use universe_testdata::{
+  seed, Article, Calendar, Character, Location, Relationship, RelationshipDates, User, World,
+};
+
+fn seed_relationships(service: &ServiceWrapper, visibility: &str) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    visibility: visibility.to_owned(),
+    ..Default::default()
+  };
+  let character = |id: &str, name: &str| Character {
+    character_id: uuid::Uuid::parse_str(id).unwrap(),
+    world_id: world.world_id,
+    name: name.to_owned(),
+    ..Default::default()
+  };
+  let frodo = character("8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c01", "Frodo Baggins");
+  let bilbo = character("8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c02", "Bilbo Baggins");
+  let sam = character("8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c03", "Samwise Gamgee");
+  let gollum = character("8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c04", "Gollum");
+  let sauron = character("8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c05", "Sauron");
+  let saruman = character("8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c06", "Saruman");
+  let bag_end = Location {
+    location_id: uuid::Uuid::parse_str("8c1e3a5b-7d9f-4b2c-a4e6-2f3b5d7a9c01").unwrap(),
+    world_id: world.world_id,
+    name: "Bag End".to_owned(),
+    ..Default::default()
+  };
+  let red_book = Article {
+    article_id: uuid::Uuid::parse_str("8c1e3a5b-7d9f-4b2c-a4e6-3f3b5d7a9c01").unwrap(),
+    world_id: world.world_id,
+    title: "The Red Book of Westmarch".to_owned(),
+    slug: "red-book".to_owned(),
+    ..Default::default()
+  };
+  let calendar = Calendar {
+    calendar_id: uuid::Uuid::parse_str("8c1e3a5b-7d9f-4b2c-a4e6-4f3b5d7a9c01").unwrap(),
+    world_id: world.world_id,
+    name: "Shire Reckoning".to_owned(),
+    definition: json!({
+      "months": [
+        { "name": "Afteryule", "days": 30 },
+        { "name": "Solmath", "days": 30 }
+      ]
+    }),
+    ..Default::default()
+  };
+  let relationship = |n: u32, source: (&str, uuid::Uuid), relation: &str, target: (&str, uuid::Uuid), symmetric: bool| {
+    Relationship {
+      relationship_id: uuid::Uuid::parse_str(&format!("9e1a3c5b-7d2f-4b6e-8a0c-3e5b7d9f1a{:02}", n)).unwrap(),
+      version: uuid::Uuid::parse_str(&format!("a1b3c5d7-e9f1-4a3b-8c5d-7e9f1a3b5c{:02}", n)).unwrap(),
+      created: format!("2020-03-11T13:00:{:02}Z", 30 + n).parse().unwrap(),
+      updated: "2020-03-11T13:00:45Z".parse().unwrap(),
+      world_id: world.world_id,
+      relation: relation.to_owned(),
+      symmetric,
+      source_type: source.0.to_owned(),
+      source_id: source.1,
+      target_type: target.0.to_owned(),
+      target_id: target.1,
+    }
+  };
+  let uncle = relationship(1, ("character", bilbo.character_id), "uncle of", ("character", frodo.character_id), false);
+  let lives_in = relationship(2, ("character", frodo.character_id), "lives in", ("location", bag_end.location_id), false);
+  let lives_in_dates = RelationshipDates {
+    relationship_id: lives_in.relationship_id,
+    world_id: world.world_id,
+    calendar_id: calendar.calendar_id,
+    start: Some((2, 1, 22)),
+    end: None,
+  };
+  let author = relationship(3, ("character", bilbo.character_id), "author of", ("article", red_book.article_id), false);
+  let friend = relationship(4, ("character", sam.character_id), "friend of", ("character", frodo.character_id), true);
+  let rival = relationship(5, ("character", gollum.character_id), "rival of", ("character", sam.character_id), true);
+  let ally = relationship(6, ("character", sauron.character_id), "ally of", ("character", saruman.character_id), true);
+
+  seed(
+    service.database(),
+    vec![
+      &user,
+      &world,
+      &frodo,
+      &bilbo,
+      &sam,
+      &gollum,
+      &sauron,
+      &saruman,
+      &bag_end,
+      &red_book,
+      &calendar,
+      &uncle,
+      &lives_in,
+      &lives_in_dates,
+      &author,
+      &friend,
+      &rival,
+      &ally,
+    ],
+  );
+
+  user
+}
+
+#[test]
+fn test_patch_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_relationships(&service, "public");
+
+  let response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/relationships/9e1a3c5b-7d2f-4b6e-8a0c-3e5b7d9f1a01")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .body(json!({ "relation": "cousin of" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_patch_unknown_relationship() {
+  let service = ServiceWrapper::default();
+  let user = seed_relationships(&service, "public");
+
+  let mut response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/relationships/9e1a3c5b-7d2f-4b6e-8a0c-3e5b7d9f1a99")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "relation": "cousin of" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested relationship could not be found",
+    "type": "tag:universe,2020:relationships/problems/unknown-relationship"
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_success() {
+  let service = ServiceWrapper::default();
+  let user = seed_relationships(&service, "public");
+
+  let mut response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/relationships/9e1a3c5b-7d2f-4b6e-8a0c-3e5b7d9f1a01")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "relation": "cousin of",
+        "symmetric": true,
+        "during": { "calendar": "8c1e3a5b-7d9f-4b2c-a4e6-4f3b5d7a9c01", "end": "30 Solmath 9" }
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_rewrite_headers(&response, |h| {
+    let h = regex_replace(h, r#"ETag: "[a-z0-9]{8}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{12}""#, r#"ETag: "a7fd01dc-dcf7-45dd-a932-0b6b263e17d0""#);
+    regex_replace(h, r#"^Last-Modified: .*$"#, "Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT")
+  }), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/relationships/9e1a3c5b-7d2f-4b6e-8a0c-3e5b7d9f1a01>; rel="self"
+  Accept-Patch: application/merge-patch+json
+  ETag: "a7fd01dc-dcf7-45dd-a932-0b6b263e17d0"
+  Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT
+  Cache-Control: public, max-age=3600
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(
+    &body,
+    "/worlds/{worldId}/relationships/{relationshipId}",
+    "patch",
+    200,
+  );
+  assert_json_snapshot!(body, @r###"
+  {
+    "during": {
+      "calendar": "8c1e3a5b-7d9f-4b2c-a4e6-4f3b5d7a9c01",
+      "end": "30 Solmath 9"
+    },
+    "id": "9e1a3c5b-7d2f-4b6e-8a0c-3e5b7d9f1a01",
+    "relation": "cousin of",
+    "source": {
+      "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c02",
+      "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c02",
+      "type": "character"
+    },
+    "symmetric": true,
+    "target": {
+      "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c01",
+      "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c01",
+      "type": "character"
+    },
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_clear_dates() {
+  let service = ServiceWrapper::default();
+  let user = seed_relationships(&service, "public");
+
+  let mut response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/relationships/9e1a3c5b-7d2f-4b6e-8a0c-3e5b7d9f1a02")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "source": { "type": "character", "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c02" },
+        "during": null
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_rewrite_headers(&response, |h| {
+    let h = regex_replace(h, r#"ETag: "[a-z0-9]{8}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{12}""#, r#"ETag: "a7fd01dc-dcf7-45dd-a932-0b6b263e17d0""#);
+    regex_replace(h, r#"^Last-Modified: .*$"#, "Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT")
+  }), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/relationships/9e1a3c5b-7d2f-4b6e-8a0c-3e5b7d9f1a02>; rel="self"
+  Accept-Patch: application/merge-patch+json
+  ETag: "a7fd01dc-dcf7-45dd-a932-0b6b263e17d0"
+  Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT
+  Cache-Control: public, max-age=3600
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "id": "9e1a3c5b-7d2f-4b6e-8a0c-3e5b7d9f1a02",
+    "relation": "lives in",
+    "source": {
+      "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c02",
+      "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c02",
+      "type": "character"
+    },
+    "symmetric": false,
+    "target": {
+      "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/8c1e3a5b-7d9f-4b2c-a4e6-2f3b5d7a9c01",
+      "id": "8c1e3a5b-7d9f-4b2c-a4e6-2f3b5d7a9c01",
+      "type": "location"
+    },
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_invalid() {
+  let service = ServiceWrapper::default();
+  let user = seed_relationships(&service, "public");
+
+  let mut response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/relationships/9e1a3c5b-7d2f-4b6e-8a0c-3e5b7d9f1a01")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "target": { "type": "character", "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c02" },
+        "relation": "",
+        "during": { "start": "1-1-1" }
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "relation",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      },
+      {
+        "field": "during.calendar",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
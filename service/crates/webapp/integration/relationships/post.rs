@@ -0,0 +1,569 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, build_rewrite_headers,
+  regex_replace, ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use serde_json::json;
+use test_env_log::test;
+use universe_testdata::{
+  seed, Article, Calendar, Character, Location, Relationship, RelationshipDates, User, World,
+};
+
+fn seed_relationships(service: &ServiceWrapper, visibility: &str) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    visibility: visibility.to_owned(),
+    ..Default::default()
+  };
+  let character = |id: &str, name: &str| Character {
+    character_id: uuid::Uuid::parse_str(id).unwrap(),
+    world_id: world.world_id,
+    name: name.to_owned(),
+    ..Default::default()
+  };
+  let frodo = character("8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c01", "Frodo Baggins");
+  let bilbo = character("8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c02", "Bilbo Baggins");
+  let sam = character("8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c03", "Samwise Gamgee");
+  let gollum = character("8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c04", "Gollum");
+  let sauron = character("8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c05", "Sauron");
+  let saruman = character("8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c06", "Saruman");
+  let bag_end = Location {
+    location_id: uuid::Uuid::parse_str("8c1e3a5b-7d9f-4b2c-a4e6-2f3b5d7a9c01").unwrap(),
+    world_id: world.world_id,
+    name: "Bag End".to_owned(),
+    ..Default::default()
+  };
+  let red_book = Article {
+    article_id: uuid::Uuid::parse_str("8c1e3a5b-7d9f-4b2c-a4e6-3f3b5d7a9c01").unwrap(),
+    world_id: world.world_id,
+    title: "The Red Book of Westmarch".to_owned(),
+    slug: "red-book".to_owned(),
+    ..Default::default()
+  };
+  let calendar = Calendar {
+    calendar_id: uuid::Uuid::parse_str("8c1e3a5b-7d9f-4b2c-a4e6-4f3b5d7a9c01").unwrap(),
+    world_id: world.world_id,
+    name: "Shire Reckoning".to_owned(),
+    definition: json!({
+      "months": [
+        { "name": "Afteryule", "days": 30 },
+        { "name": "Solmath", "days": 30 }
+      ]
+    }),
+    ..Default::default()
+  };
+  let relationship = |n: u32, source: (&str, uuid::Uuid), relation: &str, target: (&str, uuid::Uuid), symmetric: bool| {
+    Relationship {
+      relationship_id: uuid::Uuid::parse_str(&format!("9e1a3c5b-7d2f-4b6e-8a0c-3e5b7d9f1a{:02}", n)).unwrap(),
+      version: uuid::Uuid::parse_str(&format!("a1b3c5d7-e9f1-4a3b-8c5d-7e9f1a3b5c{:02}", n)).unwrap(),
+      created: format!("2020-03-11T13:00:{:02}Z", 30 + n).parse().unwrap(),
+      updated: "2020-03-11T13:00:45Z".parse().unwrap(),
+      world_id: world.world_id,
+      relation: relation.to_owned(),
+      symmetric,
+      source_type: source.0.to_owned(),
+      source_id: source.1,
+      target_type: target.0.to_owned(),
+      target_id: target.1,
+    }
+  };
+  let uncle = relationship(1, ("character", bilbo.character_id), "uncle of", ("character", frodo.character_id), false);
+  let lives_in = relationship(2, ("character", frodo.character_id), "lives in", ("location", bag_end.location_id), false);
+  let lives_in_dates = RelationshipDates {
+    relationship_id: lives_in.relationship_id,
+    world_id: world.world_id,
+    calendar_id: calendar.calendar_id,
+    start: Some((2, 1, 22)),
+    end: None,
+  };
+  let author = relationship(3, ("character", bilbo.character_id), "author of", ("article", red_book.article_id), false);
+  let friend = relationship(4, ("character", sam.character_id), "friend of", ("character", frodo.character_id), true);
+  let rival = relationship(5, ("character", gollum.character_id), "rival of", ("character", sam.character_id), true);
+  let ally = relationship(6, ("character", sauron.character_id), "ally of", ("character", saruman.character_id), true);
+
+  seed(
+    service.database(),
+    vec![
+      &user,
+      &world,
+      &frodo,
+      &bilbo,
+      &sam,
+      &gollum,
+      &sauron,
+      &saruman,
+      &bag_end,
+      &red_book,
+      &calendar,
+      &uncle,
+      &lives_in,
+      &lives_in_dates,
+      &author,
+      &friend,
+      &rival,
+      &ally,
+    ],
+  );
+
+  user
+}
+
+#[test]
+fn test_post_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_relationships(&service, "public");
+
+  let response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/relationships")
+    .body(
+      json!({
+        "source": { "type": "character", "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c03" },
+        "target": { "type": "location", "id": "8c1e3a5b-7d9f-4b2c-a4e6-2f3b5d7a9c01" },
+        "relation": "gardener of"
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_post_success() {
+  let service = ServiceWrapper::default();
+  let user = seed_relationships(&service, "public");
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/relationships")
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "source": { "type": "character", "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c03" },
+        "target": { "type": "location", "id": "8c1e3a5b-7d9f-4b2c-a4e6-2f3b5d7a9c01" },
+        "relation": "  gardener of  ",
+        "during": {
+          "calendar": "8c1e3a5b-7d9f-4b2c-a4e6-4f3b5d7a9c01",
+          "start": "1-2-3",
+          "end": "4 Solmath 5"
+        }
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_rewrite_headers(&response, |h| {
+    regex_replace(h, r#"/relationships/[a-z0-9]{8}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{12}"#, "/relationships/9e1a3c5b-7d2f-4b6e-8a0c-3e5b7d9f1a07")
+  }), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/relationships/9e1a3c5b-7d2f-4b6e-8a0c-3e5b7d9f1a07>; rel="canonical"
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/relationships", "post", 200);
+  assert_json_snapshot!(body, {
+    ".id" => "[uuid]"
+  }, @r###"
+  {
+    "during": {
+      "calendar": "8c1e3a5b-7d9f-4b2c-a4e6-4f3b5d7a9c01",
+      "end": "4 Solmath 5",
+      "start": "3 Solmath 1"
+    },
+    "id": "[uuid]",
+    "relation": "gardener of",
+    "source": {
+      "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c03",
+      "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c03",
+      "type": "character"
+    },
+    "symmetric": false,
+    "target": {
+      "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/8c1e3a5b-7d9f-4b2c-a4e6-2f3b5d7a9c01",
+      "id": "8c1e3a5b-7d9f-4b2c-a4e6-2f3b5d7a9c01",
+      "type": "location"
+    },
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/relationships?entity=location:8c1e3a5b-7d9f-4b2c-a4e6-2f3b5d7a9c01")
+    .dispatch();
+  assert_json_snapshot!(build_json_body(&mut response), {
+    ".entries[].id" => "[uuid]"
+  }, @r###"
+  {
+    "entries": [
+      {
+        "during": {
+          "calendar": "8c1e3a5b-7d9f-4b2c-a4e6-4f3b5d7a9c01",
+          "start": "22 Afteryule 2"
+        },
+        "id": "[uuid]",
+        "relation": "lives in",
+        "source": {
+          "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c01",
+          "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c01",
+          "type": "character"
+        },
+        "symmetric": false,
+        "target": {
+          "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/8c1e3a5b-7d9f-4b2c-a4e6-2f3b5d7a9c01",
+          "id": "8c1e3a5b-7d9f-4b2c-a4e6-2f3b5d7a9c01",
+          "type": "location"
+        },
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      },
+      {
+        "during": {
+          "calendar": "8c1e3a5b-7d9f-4b2c-a4e6-4f3b5d7a9c01",
+          "end": "4 Solmath 5",
+          "start": "3 Solmath 1"
+        },
+        "id": "[uuid]",
+        "relation": "gardener of",
+        "source": {
+          "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c03",
+          "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c03",
+          "type": "character"
+        },
+        "symmetric": false,
+        "target": {
+          "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/locations/8c1e3a5b-7d9f-4b2c-a4e6-2f3b5d7a9c01",
+          "id": "8c1e3a5b-7d9f-4b2c-a4e6-2f3b5d7a9c01",
+          "type": "location"
+        },
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      }
+    ],
+    "total": 2
+  }
+  "###);
+}
+
+#[test]
+fn test_post_symmetric() {
+  let service = ServiceWrapper::default();
+  let user = seed_relationships(&service, "public");
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/relationships")
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "source": { "type": "character", "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c04" },
+        "target": { "type": "character", "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c01" },
+        "relation": "rival of",
+        "symmetric": true
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_rewrite_headers(&response, |h| {
+    regex_replace(h, r#"/relationships/[a-z0-9]{8}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{12}"#, "/relationships/9e1a3c5b-7d2f-4b6e-8a0c-3e5b7d9f1a07")
+  }), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/relationships/9e1a3c5b-7d2f-4b6e-8a0c-3e5b7d9f1a07>; rel="canonical"
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), {
+    ".id" => "[uuid]"
+  }, @r###"
+  {
+    "id": "[uuid]",
+    "relation": "rival of",
+    "source": {
+      "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c04",
+      "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c04",
+      "type": "character"
+    },
+    "symmetric": true,
+    "target": {
+      "href": "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/characters/8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c01",
+      "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c01",
+      "type": "character"
+    },
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_missing_fields() {
+  let service = ServiceWrapper::default();
+  let user = seed_relationships(&service, "public");
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/relationships")
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "source": { "type": "character" },
+        "relation": "  "
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "source",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      },
+      {
+        "field": "target",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      },
+      {
+        "field": "relation",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_unknown_entities() {
+  let service = ServiceWrapper::default();
+  let user = seed_relationships(&service, "public");
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/relationships")
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "source": { "type": "character", "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c99" },
+        "target": { "type": "map", "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c01" },
+        "relation": "drawn on"
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "source",
+        "title": "The source is not an entity of the world",
+        "type": "tag:universe,2020:relationships/validation-errors/source/unknown"
+      },
+      {
+        "field": "target",
+        "title": "The target is not an entity of the world",
+        "type": "tag:universe,2020:relationships/validation-errors/target/unknown"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_same_entity() {
+  let service = ServiceWrapper::default();
+  let user = seed_relationships(&service, "public");
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/relationships")
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "source": { "type": "character", "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c04" },
+        "target": { "type": "character", "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c04" },
+        "relation": "rival of",
+        "symmetric": true
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "target",
+        "title": "An entity can't have a relationship with itself",
+        "type": "tag:universe,2020:relationships/validation-errors/target/same-as-source"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_invalid_dates() {
+  let service = ServiceWrapper::default();
+  let user = seed_relationships(&service, "public");
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/relationships")
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "source": { "type": "character", "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c03" },
+        "target": { "type": "location", "id": "8c1e3a5b-7d9f-4b2c-a4e6-2f3b5d7a9c01" },
+        "relation": "gardener of",
+        "during": {
+          "calendar": "8c1e3a5b-7d9f-4b2c-a4e6-4f3b5d7a9c01",
+          "start": "31 Afteryule 3",
+          "end": "1 Rethe 4"
+        }
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "during.start",
+        "title": "The month doesn't have that many days",
+        "type": "tag:universe,2020:calendars/validation-errors/date/day-out-of-range"
+      },
+      {
+        "field": "during.end",
+        "title": "The calendar has no month with that name",
+        "type": "tag:universe,2020:calendars/validation-errors/date/unknown-month"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/relationships")
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "source": { "type": "character", "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c03" },
+        "target": { "type": "location", "id": "8c1e3a5b-7d9f-4b2c-a4e6-2f3b5d7a9c01" },
+        "relation": "gardener of",
+        "during": {
+          "calendar": "8c1e3a5b-7d9f-4b2c-a4e6-4f3b5d7a9c01",
+          "start": "5-1-1",
+          "end": "4-1-1"
+        }
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "during.end",
+        "title": "The end of the date range must not be before the start",
+        "type": "tag:universe,2020:relationships/validation-errors/during/out-of-order"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_unknown_calendar() {
+  let service = ServiceWrapper::default();
+  let user = seed_relationships(&service, "public");
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/relationships")
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "source": { "type": "character", "id": "8c1e3a5b-7d9f-4b2c-a4e6-1f3b5d7a9c03" },
+        "target": { "type": "location", "id": "8c1e3a5b-7d9f-4b2c-a4e6-2f3b5d7a9c01" },
+        "relation": "gardener of",
+        "during": {
+          "calendar": "8c1e3a5b-7d9f-4b2c-a4e6-4f3b5d7a9c99",
+          "start": "1-1-1"
+        }
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "during.calendar",
+        "title": "The calendar is not a calendar of the world",
+        "type": "tag:universe,2020:relationships/validation-errors/during/unknown-calendar"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
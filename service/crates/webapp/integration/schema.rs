@@ -0,0 +1,146 @@
+use serde_json::Value;
+use std::fs::File;
+
+/// The location of the OpenAPI document that describes the API, relative to the crate
+const OPENAPI_PATH: &str = "../../../api/reference/universe.v1.yaml";
+
+/// Load the OpenAPI document that describes the API
+fn load_openapi() -> Value {
+    let file = File::open(OPENAPI_PATH).unwrap();
+    serde_yaml::from_reader(file).unwrap()
+}
+
+/// Assert that a JSON body matches the schema that is documented for the given response.
+///
+/// # Arguments
+/// * `body` The JSON body to check
+/// * `path` The path of the operation, as written in the OpenAPI document
+/// * `method` The HTTP method of the operation, in lower case
+/// * `status` The status code of the response
+pub fn assert_response_schema(body: &Value, path: &str, method: &str, status: u16) {
+    let openapi = load_openapi();
+    let schema = openapi
+        .get("paths")
+        .and_then(|paths| paths.get(path))
+        .and_then(|operations| operations.get(method))
+        .and_then(|operation| operation.get("responses"))
+        .and_then(|responses| responses.get(status.to_string()))
+        .and_then(|response| response.get("content"))
+        .and_then(|content| {
+            content
+                .get("application/json")
+                .or_else(|| content.get("application/geo+json"))
+        })
+        .and_then(|content| content.get("schema"))
+        .unwrap_or_else(|| panic!("No schema documented for {} {} {}", method, path, status));
+
+    let mut errors = vec![];
+    validate(&openapi, schema, body, "$", &mut errors);
+
+    if !errors.is_empty() {
+        panic!(
+            "Response did not match schema for {} {} {}:\n{}\n{}",
+            method,
+            path,
+            status,
+            errors.join("\n"),
+            body
+        );
+    }
+}
+
+/// Validate a value against a schema from the OpenAPI document, recording any mismatches.
+///
+/// This supports the subset of schema keywords that are used by the OpenAPI document.
+fn validate(
+    openapi: &Value,
+    schema: &Value,
+    value: &Value,
+    location: &str,
+    errors: &mut Vec<String>,
+) {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let target = openapi
+            .pointer(reference.trim_start_matches('#'))
+            .unwrap_or_else(|| panic!("Unknown schema reference {}", reference));
+        validate(openapi, target, value, location, errors);
+        return;
+    }
+
+    if let Some(all_of) = schema.get("allOf").and_then(Value::as_array) {
+        for schema in all_of {
+            validate(openapi, schema, value, location, errors);
+        }
+    }
+
+    let valid_type = match schema.get("type").and_then(Value::as_str) {
+        None => true,
+        Some("object") => value.is_object(),
+        Some("array") => value.is_array(),
+        Some("string") => value.is_string(),
+        Some("integer") => value.is_i64() || value.is_u64(),
+        Some("number") => value.is_number(),
+        Some("boolean") => value.is_boolean(),
+        Some(other) => panic!("Unsupported schema type {}", other),
+    };
+    if !valid_type {
+        errors.push(format!(
+            "{}: expected {}, got {}",
+            location, schema["type"], value
+        ));
+        return;
+    }
+
+    if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64) {
+        if value.as_f64().map(|v| v < minimum).unwrap_or(false) {
+            errors.push(format!("{}: {} is less than {}", location, value, minimum));
+        }
+    }
+    if let Some(maximum) = schema.get("maximum").and_then(Value::as_f64) {
+        if value.as_f64().map(|v| v > maximum).unwrap_or(false) {
+            errors.push(format!(
+                "{}: {} is greater than {}",
+                location, value, maximum
+            ));
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for field in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(field) {
+                    errors.push(format!("{}: missing required field {}", location, field));
+                }
+            }
+        }
+
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for (field, field_value) in object {
+            match properties.and_then(|properties| properties.get(field)) {
+                Some(field_schema) => validate(
+                    openapi,
+                    field_schema,
+                    field_value,
+                    &format!("{}.{}", location, field),
+                    errors,
+                ),
+                None if schema.get("additionalProperties") == Some(&Value::Bool(false)) => {
+                    errors.push(format!("{}: unexpected field {}", location, field))
+                }
+                None => {}
+            }
+        }
+    }
+
+    if let (Some(items), Some(array)) = (schema.get("items"), value.as_array()) {
+        for (index, item) in array.iter().enumerate() {
+            validate(
+                openapi,
+                items,
+                item,
+                &format!("{}[{}]", location, index),
+                errors,
+            );
+        }
+    }
+}
@@ -1,10 +1,14 @@
 use rocket::local::Client;
+use std::sync::Arc;
+use universe_storage::{BlobStore, LocalBlobStore, UrlSigner};
 use universe_test_database_wrapper::TestDatabaseWrapper;
 use universe_webapp::Service;
 
 /// Wrapper around the service being tested
 pub struct ServiceWrapper<'d> {
     database: TestDatabaseWrapper<'d>,
+    storage: std::path::PathBuf,
+    blob_store: Arc<dyn BlobStore>,
     webapp: Client,
 }
 
@@ -17,20 +21,37 @@ impl<'d> Default for ServiceWrapper<'d> {
         let migrations_glob = format!("{}/**/*.sql", migrations_path.to_str().unwrap());
 
         let database = TestDatabaseWrapper::new();
+        let storage =
+            std::env::temp_dir().join(format!("universe-integration-{}", uuid::Uuid::new_v4()));
+        let url_signer = UrlSigner::new("/storage", "storageSigningKey");
+        let blob_store: Arc<dyn BlobStore> =
+            Arc::new(LocalBlobStore::new(&storage, url_signer.clone()));
 
         let webapp = Service::new(
             &database.url(),
             None,
             "accessTokenSecretKey",
             &migrations_glob,
+            chrono::Duration::days(30),
+            blob_store.clone(),
+            None,
+            url_signer,
         );
         Self {
             database,
+            storage,
+            blob_store,
             webapp: webapp.client(),
         }
     }
 }
 
+impl<'d> Drop for ServiceWrapper<'d> {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.storage);
+    }
+}
+
 impl<'d> ServiceWrapper<'d> {
     /// Get the HTTP Client used for interacting with the service
     pub fn client(&self) -> &Client {
@@ -67,6 +88,31 @@ impl<'d> ServiceWrapper<'d> {
         ))
     }
 
+    pub fn delete<'c, 'u: 'c, U: Into<std::borrow::Cow<'u, str>>>(
+        &'c self,
+        uri: U,
+    ) -> rocket::local::LocalRequest<'c> {
+        self.client().delete(uri).header(rocket::http::Header::new(
+            "X-Request-Client-Name",
+            "IntegrationTest",
+        ))
+    }
+
+    pub fn put<'c, 'u: 'c, U: Into<std::borrow::Cow<'u, str>>>(
+        &'c self,
+        uri: U,
+    ) -> rocket::local::LocalRequest<'c> {
+        self.client().put(uri).header(rocket::http::Header::new(
+            "X-Request-Client-Name",
+            "IntegrationTest",
+        ))
+    }
+
+    /// Get the blob store the service is using
+    pub fn blob_store(&self) -> &dyn BlobStore {
+        &*self.blob_store
+    }
+
     /// Get the database the service is using
     pub fn database(&self) -> &TestDatabaseWrapper<'d> {
         &self.database
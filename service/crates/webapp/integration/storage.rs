@@ -0,0 +1,112 @@
+use crate::{build_headers, build_json_body, ServiceWrapper};
+use insta::{assert_json_snapshot, assert_snapshot};
+use std::time::Duration;
+use test_env_log::test;
+use universe_storage::Blob;
+
+#[test]
+fn test_get_signed_blob() {
+    let service = ServiceWrapper::default();
+    service
+        .blob_store()
+        .put(
+            "users/1234/notes",
+            &Blob::sniffed(b"Hello, World!".to_vec()),
+        )
+        .unwrap();
+    let url = service
+        .blob_store()
+        .signed_url("users/1234/notes", Duration::from_secs(60))
+        .unwrap();
+
+    let mut response = service.get(url).dispatch();
+
+    assert_snapshot!(build_headers(&response), @r###"
+    HTTP/1.1 200 OK.
+    Content-Type: text/plain; charset=utf-8
+    Cache-Control: private
+    Server: Rocket
+    "###);
+    assert_eq!(Some("Hello, World!".to_owned()), response.body_string());
+}
+
+#[test]
+fn test_get_tampered_blob() {
+    let service = ServiceWrapper::default();
+    service
+        .blob_store()
+        .put(
+            "users/1234/notes",
+            &Blob::sniffed(b"Hello, World!".to_vec()),
+        )
+        .unwrap();
+    service
+        .blob_store()
+        .put("users/5678/notes", &Blob::sniffed(b"Secret".to_vec()))
+        .unwrap();
+    let url = service
+        .blob_store()
+        .signed_url("users/1234/notes", Duration::from_secs(60))
+        .unwrap();
+
+    let mut response = service
+        .get(url.replace("users/1234", "users/5678"))
+        .dispatch();
+
+    assert_snapshot!(build_headers(&response), @r###"
+    HTTP/1.1 403 .
+    Content-Type: application/problem+json
+    Server: Rocket
+    "###);
+    assert_json_snapshot!(build_json_body(&mut response), @r###"
+    {
+      "status": 403,
+      "title": "The link to the file is not valid",
+      "type": "tag:universe,2020:storage/problems/invalid-signature"
+    }
+    "###);
+}
+
+#[test]
+fn test_get_unsigned_blob() {
+    let service = ServiceWrapper::default();
+    service
+        .blob_store()
+        .put(
+            "users/1234/notes",
+            &Blob::sniffed(b"Hello, World!".to_vec()),
+        )
+        .unwrap();
+
+    let response = service.get("/storage/users/1234/notes").dispatch();
+
+    assert_snapshot!(build_headers(&response), @r###"
+    HTTP/1.1 404 Not Found.
+    Content-Type: text/html; charset=utf-8
+    Server: Rocket
+    "###);
+}
+
+#[test]
+fn test_get_unknown_blob() {
+    let service = ServiceWrapper::default();
+    let url = service
+        .blob_store()
+        .signed_url("users/1234/notes", Duration::from_secs(60))
+        .unwrap();
+
+    let mut response = service.get(url).dispatch();
+
+    assert_snapshot!(build_headers(&response), @r###"
+    HTTP/1.1 404 .
+    Content-Type: application/problem+json
+    Server: Rocket
+    "###);
+    assert_json_snapshot!(build_json_body(&mut response), @r###"
+    {
+      "status": 404,
+      "title": "The requested file could not be found",
+      "type": "tag:universe,2020:storage/problems/unknown-blob"
+    }
+    "###);
+}
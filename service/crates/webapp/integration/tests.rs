@@ -1,9 +1,20 @@
+mod articles;
 mod assert;
+mod authenticate;
 mod authentication;
+mod calendars;
+mod characters;
 mod health;
+mod locations;
+mod maps;
+mod relationships;
+mod schema;
 mod service;
+mod storage;
 mod users;
 mod worlds;
 
 pub use assert::*;
+pub use authenticate::*;
+pub use schema::*;
 pub use service::ServiceWrapper;
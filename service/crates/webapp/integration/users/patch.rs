@@ -1,41 +1,15 @@
-use crate::{build_headers, build_json_body, build_rewrite_headers, regex_replace, ServiceWrapper};
+use crate::{
+  authenticate, authenticate_user, build_headers, build_json_body, build_rewrite_headers, regex_replace,
+  ServiceWrapper,
+};
 use insta::{assert_json_snapshot, assert_snapshot};
-use rocket::http::{ContentType, Header, Status};
+use rocket::http::ContentType;
 use serde_json::json;
 use spectral::prelude::*;
 use std::str::FromStr;
 use test_env_log::test;
 use universe_testdata::{seed, User};
 
-fn authenticate_user<'h>(service: &ServiceWrapper, user: &User) -> Option<Header<'h>> {
-  authenticate(&service, &user.username, &user.password)
-}
-
-fn authenticate<'h>(
-  service: &ServiceWrapper,
-  username: &str,
-  password: &str,
-) -> Option<Header<'h>> {
-  let req = service.post("/login").header(ContentType::JSON).body(
-    json!({
-        "username": username,
-        "password": password
-    })
-    .to_string(),
-  );
-  let mut response = req.dispatch();
-  if response.status() != Status::Ok {
-    return None;
-  }
-
-  let body = build_json_body(&mut response);
-  body
-    .get("accessToken")
-    .and_then(|token| token.get("token"))
-    .and_then(|token| token.as_str())
-    .map(|token| Header::new("Authorization", format!("Bearer {}", token)))
-}
-
 #[test]
 fn test_patch_unauthorized() {
   let service = ServiceWrapper::default();
@@ -0,0 +1,319 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, ServiceWrapper,
+};
+use image::{DynamicImage, GenericImageView, ImageOutputFormat};
+use insta::{assert_json_snapshot, assert_snapshot};
+use test_env_log::test;
+use universe_testdata::{seed, User, World};
+
+fn seed_world(service: &ServiceWrapper, visibility: &str) -> (User, World) {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    description: "This is a test world".to_owned(),
+    visibility: visibility.to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&user, &world]);
+
+  (user, world)
+}
+
+fn seed_other_user(service: &ServiceWrapper) -> User {
+  let other_user = User {
+    user_id: uuid::Uuid::parse_str("3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90").unwrap(),
+    username: "otheruser".to_owned(),
+    email: "other@example.com".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&other_user]);
+
+  other_user
+}
+
+fn build_png(width: u32, height: u32) -> Vec<u8> {
+  let mut bytes = vec![];
+  DynamicImage::new_rgb8(width, height)
+    .write_to(&mut bytes, ImageOutputFormat::Png)
+    .unwrap();
+  bytes
+}
+
+#[test]
+fn test_put_cover_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_world(&service, "public");
+
+  let response = service
+    .put("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/cover")
+    .body(build_png(640, 480))
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_put_cover_wrong_user() {
+  let service = ServiceWrapper::default();
+  seed_world(&service, "public");
+  let other_user = seed_other_user(&service);
+
+  let mut response = service
+    .put("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/cover")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .body(build_png(640, 480))
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 403 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 403,
+    "title": "You are not permitted to perform this request",
+    "type": "tag:universe,2020:problems/authentication/forbidden"
+  }
+  "###);
+}
+
+#[test]
+fn test_put_cover_unsupported_format() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service, "public");
+
+  let mut response = service
+    .put("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/cover")
+    .header(authenticate_user(&service, &user).unwrap())
+    .body("This is not an image")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 415 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 415,
+    "title": "The cover image must be a PNG, JPEG or WebP image",
+    "type": "tag:universe,2020:worlds/problems/cover-unsupported-format"
+  }
+  "###);
+}
+
+#[test]
+fn test_put_cover_malformed() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service, "public");
+
+  let mut image = build_png(640, 480);
+  image.truncate(64);
+
+  let mut response = service
+    .put("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/cover")
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(image)
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 422,
+    "title": "The cover image could not be read",
+    "type": "tag:universe,2020:worlds/problems/cover-malformed"
+  }
+  "###);
+}
+
+#[test]
+fn test_put_cover_success() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service, "public");
+
+  let mut response = service
+    .put("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/cover")
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(build_png(640, 480))
+    .dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/cover", "put", 200);
+  assert_json_snapshot!(body, {
+        ".cover.*" => "[url]",
+    },
+    @r###"
+  {
+    "cover": {
+      "large": "[url]",
+      "original": "[url]",
+      "small": "[url]"
+    },
+    "description": "This is a test world",
+    "descriptionHtml": "<p>This is a test world</p>\n",
+    "id": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+    "name": "Test World",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "slug": "test-world",
+    "stars": 0,
+    "visibility": "public"
+  }
+  "###);
+
+  let small = body["cover"]["small"].as_str().unwrap();
+  let mut response = service.get(small).dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: image/jpeg
+  Cache-Control: public, max-age=31536000, immutable
+  Server: Rocket
+  "###);
+
+  let thumbnail = image::load_from_memory(&response.body_bytes().unwrap()).unwrap();
+  assert_eq!((320, 180), thumbnail.dimensions());
+
+  let original = body["cover"]["original"].as_str().unwrap();
+  let mut response = service.get(original).dispatch();
+  assert_eq!(build_png(640, 480), response.body_bytes().unwrap());
+}
+
+#[test]
+fn test_get_cover_private_world() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service, "private");
+  let other_user = seed_other_user(&service);
+
+  let mut response = service
+    .put("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/cover")
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(build_png(640, 480))
+    .dispatch();
+  let body = build_json_body(&mut response);
+  let small = body["cover"]["small"].as_str().unwrap();
+
+  let response = service
+    .get(small)
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: image/jpeg
+  Cache-Control: private, max-age=31536000, immutable
+  Server: Rocket
+  "###);
+
+  let response = service
+    .get(small)
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_get_unknown_cover() {
+  let service = ServiceWrapper::default();
+  seed_world(&service, "public");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/cover/5b0e9c1e-3d8f-4a4e-9b1c-7f2d6a8e4c31/small")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested cover image could not be found",
+    "type": "tag:universe,2020:worlds/problems/unknown-cover"
+  }
+  "###);
+}
+
+#[test]
+fn test_delete_cover() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service, "public");
+
+  let mut response = service
+    .put("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/cover")
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(build_png(640, 480))
+    .dispatch();
+  let body = build_json_body(&mut response);
+  let small = body["cover"]["small"].as_str().unwrap();
+
+  let mut response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/cover")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "description": "This is a test world",
+    "descriptionHtml": "<p>This is a test world</p>\n",
+    "id": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+    "name": "Test World",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "slug": "test-world",
+    "stars": 0,
+    "visibility": "public"
+  }
+  "###);
+
+  let response = service.get(small).dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_delete_unknown_cover() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service, "public");
+
+  let mut response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/cover")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested cover image could not be found",
+    "type": "tag:universe,2020:worlds/problems/unknown-cover"
+  }
+  "###);
+}
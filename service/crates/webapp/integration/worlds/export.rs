@@ -0,0 +1,1109 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, ServiceWrapper,
+};
+use image::{DynamicImage, ImageOutputFormat};
+use insta::{assert_json_snapshot, assert_snapshot};
+use rocket::http::ContentType;
+use serde_json::json;
+use test_env_log::test;
+use universe_testdata::{
+  seed, Article, Calendar, Character, Location, Map, Relationship, TimelineEvent, User, World,
+};
+
+fn seed_world(service: &ServiceWrapper, visibility: &str) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    description: "This is a test world".to_owned(),
+    visibility: visibility.to_owned(),
+    tags: vec!["fantasy".to_owned()],
+    ..Default::default()
+  };
+  let history = Article {
+    article_id: uuid::Uuid::parse_str("0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f01").unwrap(),
+    world_id: world.world_id,
+    title: "History".to_owned(),
+    slug: "history".to_owned(),
+    body: "The history of the world".to_owned(),
+    ..Default::default()
+  };
+  let first_age = Article {
+    article_id: uuid::Uuid::parse_str("0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f02").unwrap(),
+    world_id: world.world_id,
+    parent_id: Some(history.article_id),
+    title: "The First Age".to_owned(),
+    slug: "first-age".to_owned(),
+    body: "Long ago".to_owned(),
+    ..Default::default()
+  };
+  let frodo = Character {
+    character_id: uuid::Uuid::parse_str("5b2c8e1f-3a7d-4c9b-8e6f-1d4a7c2b9e01").unwrap(),
+    world_id: world.world_id,
+    name: "Frodo Baggins".to_owned(),
+    aliases: vec!["Mr Underhill".to_owned()],
+    summary: "A hobbit of the Shire".to_owned(),
+    biography: "Bearer of the *One Ring*".to_owned(),
+    attributes: vec![("Race".to_owned(), "Hobbit".to_owned())]
+      .into_iter()
+      .collect(),
+    ..Default::default()
+  };
+  let shire = Location {
+    location_id: uuid::Uuid::parse_str("3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03").unwrap(),
+    world_id: world.world_id,
+    name: "The Shire".to_owned(),
+    kind: "Region".to_owned(),
+    description: "".to_owned(),
+    ..Default::default()
+  };
+  let hobbiton = Location {
+    location_id: uuid::Uuid::parse_str("3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04").unwrap(),
+    world_id: world.world_id,
+    parent_id: Some(shire.location_id),
+    name: "Hobbiton".to_owned(),
+    kind: "City".to_owned(),
+    description: "Home of *Bag End*".to_owned(),
+    x: Some(120.5),
+    y: Some(80.0),
+    ..Default::default()
+  };
+  let middle_earth = Map {
+    map_id: uuid::Uuid::parse_str("7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01").unwrap(),
+    world_id: world.world_id,
+    name: "Middle-earth".to_owned(),
+    image_id: None,
+    image_content_type: None,
+    image_width: None,
+    image_height: None,
+    ..Default::default()
+  };
+  let shire_reckoning = Calendar {
+    calendar_id: uuid::Uuid::parse_str("3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01").unwrap(),
+    world_id: world.world_id,
+    name: "Shire Reckoning".to_owned(),
+    definition: json!({
+      "months": [
+        { "name": "Afteryule", "days": 30 },
+        { "name": "Solmath", "days": 30 }
+      ]
+    }),
+    ..Default::default()
+  };
+  let founding = TimelineEvent {
+    world_id: world.world_id,
+    calendar_id: shire_reckoning.calendar_id,
+    title: "Founding of the Shire".to_owned(),
+    description: "Marcho and Blanco cross the *Brandywine*".to_owned(),
+    year: 1,
+    month: 2,
+    day: 1,
+    day_number: 30,
+    ..Default::default()
+  };
+  let lives_in = Relationship {
+    world_id: world.world_id,
+    relation: "lives in".to_owned(),
+    source_type: "character".to_owned(),
+    source_id: frodo.character_id,
+    target_type: "location".to_owned(),
+    target_id: hobbiton.location_id,
+    ..Default::default()
+  };
+  seed(
+    service.database(),
+    vec![
+      &user,
+      &world,
+      &history,
+      &first_age,
+      &frodo,
+      &shire,
+      &hobbiton,
+      &middle_earth,
+      &shire_reckoning,
+      &founding,
+      &lives_in,
+    ],
+  );
+
+  user
+}
+
+fn seed_other_user(service: &ServiceWrapper) -> User {
+  let other_user = User {
+    user_id: uuid::Uuid::parse_str("3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90").unwrap(),
+    username: "otheruser".to_owned(),
+    email: "other@example.com".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&other_user]);
+
+  other_user
+}
+
+fn build_png(width: u32, height: u32) -> Vec<u8> {
+  let mut bytes = vec![];
+  DynamicImage::new_rgb8(width, height)
+    .write_to(&mut bytes, ImageOutputFormat::Png)
+    .unwrap();
+  bytes
+}
+
+/// Build an archive of a world, as it would be written by an export
+fn archive(articles: serde_json::Value) -> String {
+  json!({
+    "schemaVersion": 1,
+    "world": {
+      "name": "Imported World",
+      "slug": "imported-world",
+      "description": "Brought in from elsewhere",
+      "visibility": "private",
+      "tags": ["fantasy"]
+    },
+    "articles": articles
+  })
+  .to_string()
+}
+
+#[test]
+fn test_export_unknown_world() {
+  let service = ServiceWrapper::default();
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/export")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested world could not be found",
+    "type": "tag:universe,2020:worlds/problems/unknown-world"
+  }
+  "###);
+}
+
+#[test]
+fn test_export_private_world_anonymous() {
+  let service = ServiceWrapper::default();
+  seed_world(&service, "private");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/export")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested world could not be found",
+    "type": "tag:universe,2020:worlds/problems/unknown-world"
+  }
+  "###);
+}
+
+#[test]
+fn test_export_world() {
+  let service = ServiceWrapper::default();
+  let user = seed_world(&service, "private");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/export")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Content-Disposition: attachment; filename="test-world.json"
+  Cache-Control: private, no-cache
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/export", "get", 200);
+  assert_json_snapshot!(body, {
+        ".exported" => "[timestamp]",
+    },
+    @r###"
+  {
+    "articles": [
+      {
+        "body": "The history of the world",
+        "id": "0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f01",
+        "slug": "history",
+        "title": "History"
+      },
+      {
+        "body": "Long ago",
+        "id": "0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f02",
+        "parent": "0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f01",
+        "slug": "first-age",
+        "title": "The First Age"
+      }
+    ],
+    "calendars": [
+      {
+        "epochWeekday": 0,
+        "eras": [],
+        "events": [
+          {
+            "date": {
+              "day": 1,
+              "month": 2,
+              "year": 1
+            },
+            "description": "Marcho and Blanco cross the *Brandywine*",
+            "title": "Founding of the Shire"
+          }
+        ],
+        "id": "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01",
+        "months": [
+          {
+            "days": 30,
+            "leapDays": 0,
+            "name": "Afteryule"
+          },
+          {
+            "days": 30,
+            "leapDays": 0,
+            "name": "Solmath"
+          }
+        ],
+        "name": "Shire Reckoning",
+        "weekdays": []
+      }
+    ],
+    "characters": [
+      {
+        "aliases": [
+          "Mr Underhill"
+        ],
+        "attributes": {
+          "Race": "Hobbit"
+        },
+        "biography": "Bearer of the *One Ring*",
+        "id": "5b2c8e1f-3a7d-4c9b-8e6f-1d4a7c2b9e01",
+        "name": "Frodo Baggins",
+        "summary": "A hobbit of the Shire"
+      }
+    ],
+    "exported": "[timestamp]",
+    "locations": [
+      {
+        "description": "",
+        "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03",
+        "kind": "Region",
+        "name": "The Shire"
+      },
+      {
+        "coordinates": {
+          "x": 120.5,
+          "y": 80.0
+        },
+        "description": "Home of *Bag End*",
+        "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04",
+        "kind": "City",
+        "name": "Hobbiton",
+        "parent": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03"
+      }
+    ],
+    "maps": [
+      {
+        "features": [],
+        "id": "7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01",
+        "name": "Middle-earth"
+      }
+    ],
+    "relationships": [
+      {
+        "relation": "lives in",
+        "source": {
+          "id": "5b2c8e1f-3a7d-4c9b-8e6f-1d4a7c2b9e01",
+          "type": "character"
+        },
+        "symmetric": false,
+        "target": {
+          "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04",
+          "type": "location"
+        }
+      }
+    ],
+    "schemaVersion": 1,
+    "world": {
+      "description": "This is a test world",
+      "id": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+      "name": "Test World",
+      "slug": "test-world",
+      "tags": [
+        "fantasy"
+      ],
+      "visibility": "private"
+    }
+  }
+  "###);
+}
+
+#[test]
+fn test_import_unauthorized() {
+  let service = ServiceWrapper::default();
+
+  let response = service
+    .post("/worlds/import")
+    .header(ContentType::JSON)
+    .body(archive(json!([])))
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_import_exported_world() {
+  let service = ServiceWrapper::default();
+  let user = seed_world(&service, "public");
+  let other_user = seed_other_user(&service);
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/export")
+    .dispatch();
+  let exported = build_json_body(&mut response).to_string();
+
+  // Importing into another account keeps the slug, since slugs are only unique per owner
+  let mut response = service
+    .post("/worlds/import")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .body(exported.clone())
+    .dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/import", "post", 200);
+  assert_json_snapshot!(body, {
+        ".id" => "[uuid]",
+    },
+    @r###"
+  {
+    "description": "This is a test world",
+    "descriptionHtml": "<p>This is a test world</p>\n",
+    "id": "[uuid]",
+    "name": "Test World",
+    "owner": "3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90",
+    "slug": "test-world",
+    "stars": 0,
+    "tags": [
+      "fantasy"
+    ],
+    "visibility": "public"
+  }
+  "###);
+
+  // Importing back into the original account needs a new slug
+  let mut response = service
+    .post("/worlds/import")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(exported)
+    .dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_json_snapshot!(body, {
+        ".id" => "[uuid]",
+    },
+    @r###"
+  {
+    "description": "This is a test world",
+    "descriptionHtml": "<p>This is a test world</p>\n",
+    "id": "[uuid]",
+    "name": "Test World",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "slug": "test-world-2",
+    "stars": 0,
+    "tags": [
+      "fantasy"
+    ],
+    "visibility": "public"
+  }
+  "###);
+
+  // The articles are all given new IDs, but keep their tree
+  let world_id = body["id"].as_str().unwrap();
+  let mut response = service
+    .get(format!("/worlds/{}/articles", world_id))
+    .dispatch();
+  let articles = build_json_body(&mut response);
+  let entries = articles["entries"].as_array().unwrap();
+  let history = entries.iter().find(|a| a["slug"] == "history").unwrap();
+  let first_age = entries.iter().find(|a| a["slug"] == "first-age").unwrap();
+  assert_ne!(history["id"], "0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f01");
+  assert_eq!(first_age["parent"], history["id"]);
+  assert_eq!(first_age["world"], world_id);
+  assert_eq!(articles["total"], 2);
+
+  // The characters come along too
+  let mut response = service
+    .get(format!("/worlds/{}/characters", world_id))
+    .dispatch();
+  assert_json_snapshot!(build_json_body(&mut response), {
+        ".entries[].id" => "[uuid]",
+        ".entries[].world" => "[uuid]",
+    },
+    @r###"
+  {
+    "entries": [
+      {
+        "aliases": [
+          "Mr Underhill"
+        ],
+        "attributes": {
+          "Race": "Hobbit"
+        },
+        "biography": "Bearer of the *One Ring*",
+        "biographyHtml": "<p>Bearer of the <em>One Ring</em></p>\n",
+        "id": "[uuid]",
+        "name": "Frodo Baggins",
+        "summary": "A hobbit of the Shire",
+        "world": "[uuid]"
+      }
+    ],
+    "total": 1
+  }
+  "###);
+
+  // As do the locations, which keep their tree
+  let mut response = service
+    .get(format!("/worlds/{}/locations", world_id))
+    .dispatch();
+  let locations = build_json_body(&mut response);
+  let entries = locations["entries"].as_array().unwrap();
+  let shire = entries.iter().find(|l| l["name"] == "The Shire").unwrap();
+  let hobbiton = entries.iter().find(|l| l["name"] == "Hobbiton").unwrap();
+  assert_ne!(shire["id"], "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03");
+  assert_eq!(hobbiton["parent"], shire["id"]);
+  assert_eq!(hobbiton["coordinates"], json!({ "x": 120.5, "y": 80.0 }));
+  assert_eq!(locations["total"], 2);
+
+  // And the maps
+  let mut response = service.get(format!("/worlds/{}/maps", world_id)).dispatch();
+  let maps = build_json_body(&mut response);
+  assert_ne!(
+    maps["entries"][0]["id"],
+    "7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01"
+  );
+  assert_eq!(maps["entries"][0]["name"], "Middle-earth");
+  assert_eq!(maps["total"], 1);
+
+  // And the calendars, with their events
+  let mut response = service
+    .get(format!("/worlds/{}/calendars", world_id))
+    .dispatch();
+  let calendars = build_json_body(&mut response);
+  let calendar = &calendars["entries"][0];
+  assert_ne!(calendar["id"], "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01");
+  assert_eq!(calendar["name"], "Shire Reckoning");
+  assert_eq!(calendars["total"], 1);
+
+  let mut response = service
+    .get(format!(
+      "/worlds/{}/calendars/{}/events",
+      world_id,
+      calendar["id"].as_str().unwrap()
+    ))
+    .dispatch();
+  let events = build_json_body(&mut response);
+  assert_eq!(events["entries"][0]["title"], "Founding of the Shire");
+  assert_eq!(events["entries"][0]["date"]["text"], "1 Solmath 1");
+  assert_eq!(events["total"], 1);
+
+  // And the relationships, between the imported entities
+  let mut response = service
+    .get(format!("/worlds/{}/characters", world_id))
+    .dispatch();
+  let characters = build_json_body(&mut response);
+  let mut response = service
+    .get(format!("/worlds/{}/relationships", world_id))
+    .dispatch();
+  let relationships = build_json_body(&mut response);
+  let relationship = &relationships["entries"][0];
+  assert_eq!(relationship["relation"], "lives in");
+  assert_eq!(relationship["source"]["id"], characters["entries"][0]["id"]);
+  assert_eq!(relationship["target"]["id"], hobbiton["id"]);
+  assert_eq!(relationships["total"], 1);
+}
+
+#[test]
+fn test_import_exported_maps() {
+  let service = ServiceWrapper::default();
+  let user = seed_world(&service, "public");
+  let other_user = seed_other_user(&service);
+
+  service
+    .put("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/image")
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(build_png(640, 480))
+    .dispatch();
+  service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/features")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "type": "Feature",
+        "geometry": { "type": "Point", "coordinates": [130, 85.5] },
+        "properties": {
+          "name": "Bag End",
+          "link": { "type": "location", "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04" }
+        }
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/export")
+    .dispatch();
+  let exported = build_json_body(&mut response).to_string();
+
+  let mut response = service
+    .post("/worlds/import")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .body(exported)
+    .dispatch();
+  let world = build_json_body(&mut response);
+  let world_id = world["id"].as_str().unwrap();
+
+  // The map keeps its image
+  let mut response = service.get(format!("/worlds/{}/maps", world_id)).dispatch();
+  let maps = build_json_body(&mut response);
+  let map = &maps["entries"][0];
+  assert_eq!(map["image"]["width"], 640);
+  assert_eq!(map["image"]["height"], 480);
+  let mut response = service
+    .get(map["image"]["href"].as_str().unwrap())
+    .dispatch();
+  assert_eq!(build_png(640, 480), response.body_bytes().unwrap());
+
+  // And its features link to the imported location rather than the original one
+  let mut response = service
+    .get(format!("/worlds/{}/locations", world_id))
+    .dispatch();
+  let locations = build_json_body(&mut response);
+  let entries = locations["entries"].as_array().unwrap();
+  let hobbiton = entries.iter().find(|l| l["name"] == "Hobbiton").unwrap();
+
+  let mut response = service
+    .get(format!(
+      "/worlds/{}/maps/{}/features",
+      world_id,
+      map["id"].as_str().unwrap()
+    ))
+    .dispatch();
+  let features = build_json_body(&mut response);
+  let feature = &features["features"][0];
+  assert_eq!(feature["properties"]["name"], "Bag End");
+  assert_eq!(feature["properties"]["link"]["type"], "location");
+  assert_eq!(feature["properties"]["link"]["id"], hobbiton["id"]);
+  assert_eq!(
+    feature["geometry"],
+    json!({ "type": "Point", "coordinates": [130.0, 85.5] })
+  );
+}
+
+#[test]
+fn test_import_unsupported_version() {
+  let service = ServiceWrapper::default();
+  let user = seed_world(&service, "public");
+
+  let mut response = service
+    .post("/worlds/import")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({ "schemaVersion": 99, "world": { "name": "Future", "slug": "future" } }).to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "detail": "The schema version was 99",
+    "status": 422,
+    "title": "World archives must have a schema version between 1 and 1",
+    "type": "tag:universe,2020:worlds/problems/unsupported-archive-version"
+  }
+  "###);
+}
+
+#[test]
+fn test_import_malformed_archive() {
+  let service = ServiceWrapper::default();
+  let user = seed_world(&service, "public");
+
+  let mut response = service
+    .post("/worlds/import")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body("{\"world\": 1}")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 400 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "detail": "invalid type: integer `1`, expected struct ArchivedWorld at line 1 column 11",
+    "status": 400,
+    "title": "The world archive could not be read",
+    "type": "tag:universe,2020:worlds/problems/invalid-archive"
+  }
+  "###);
+}
+
+#[test]
+fn test_import_invalid_values() {
+  let service = ServiceWrapper::default();
+  let user = seed_world(&service, "public");
+
+  let mut response = service
+    .post("/worlds/import")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "schemaVersion": 1,
+        "world": {
+          "name": " ",
+          "slug": "imported-world",
+          "visibility": "secret",
+          "cover": { "contentType": "image/png", "data": "not base64!" }
+        },
+        "articles": [
+          { "id": "a", "title": "First", "slug": "no spaces" }
+        ]
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "world.name",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      },
+      {
+        "field": "world.visibility",
+        "title": "The visibility must be one of public, unlisted or private",
+        "type": "tag:universe,2020:worlds/validation-errors/visibility/unknown"
+      },
+      {
+        "field": "world.cover.data",
+        "title": "The cover image data must be Base64 encoded",
+        "type": "tag:universe,2020:worlds/validation-errors/cover/invalid-data"
+      },
+      {
+        "field": "articles[0].slug",
+        "title": "The slug may only contain letters, digits, hyphens and underscores",
+        "type": "tag:universe,2020:worlds/validation-errors/slug/invalid-characters"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_import_article_cycle() {
+  let service = ServiceWrapper::default();
+  let user = seed_world(&service, "public");
+
+  let mut response = service
+    .post("/worlds/import")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(archive(json!([
+      { "id": "a", "parent": "b", "title": "First", "slug": "first" },
+      { "id": "b", "parent": "a", "title": "Second", "slug": "second" }
+    ])))
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "articles",
+        "title": "An article may not be its own ancestor",
+        "type": "tag:universe,2020:worlds/validation-errors/articles/parent-cycle"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+
+  // Nothing is left behind by an import that fails
+  let mut response = service
+    .get("/worlds?owner=2fcc3850-bb9b-405e-bbab-22978283fef8")
+    .dispatch();
+  assert_eq!(build_json_body(&mut response)["total"], 1);
+}
+
+#[test]
+fn test_import_failure_discards_world() {
+  let service = ServiceWrapper::default();
+  let user = seed_world(&service, "public");
+
+  // The database refuses text containing NUL characters, which is only found out once the
+  // world has already been created
+  let mut archive: serde_json::Value = serde_json::from_str(&archive(json!([
+    { "id": "a", "title": "First", "slug": "first" }
+  ])))
+  .unwrap();
+  archive["locations"] = json!([
+    { "id": "shire", "name": "The Shire", "description": "Broken\u{0}" }
+  ]);
+
+  let response = service
+    .post("/worlds/import")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(archive.to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 500 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+
+  // The world that was created for the import is thrown away again
+  let mut response = service
+    .get("/worlds?owner=2fcc3850-bb9b-405e-bbab-22978283fef8")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+  assert_eq!(build_json_body(&mut response)["total"], 1);
+}
+
+#[test]
+fn test_import_invalid_characters() {
+  let service = ServiceWrapper::default();
+  let other_user = seed_other_user(&service);
+
+  let mut archive: serde_json::Value = serde_json::from_str(&archive(json!([]))).unwrap();
+  archive["characters"] = json!([
+    { "id": "frodo", "name": "Frodo Baggins", "aliases": ["Mr Underhill"] },
+    { "id": "nobody", "name": " ", "aliases": [" "], "attributes": { "": "Unnamed" } }
+  ]);
+
+  let mut response = service
+    .post("/worlds/import")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .body(archive.to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "characters[1].name",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      },
+      {
+        "field": "characters[1].aliases",
+        "title": "Aliases may not be blank",
+        "type": "tag:universe,2020:characters/validation-errors/aliases/blank"
+      },
+      {
+        "field": "characters[1].attributes",
+        "title": "Attribute names may not be blank",
+        "type": "tag:universe,2020:characters/validation-errors/attributes/blank-name"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_import_location_cycle() {
+  let service = ServiceWrapper::default();
+  let other_user = seed_other_user(&service);
+
+  let mut archive: serde_json::Value = serde_json::from_str(&archive(json!([]))).unwrap();
+  archive["locations"] = json!([
+    { "id": "shire", "parent": "hobbiton", "name": "The Shire" },
+    { "id": "hobbiton", "parent": "shire", "name": "Hobbiton" }
+  ]);
+
+  let mut response = service
+    .post("/worlds/import")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .body(archive.to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "locations",
+        "title": "A location may not be its own ancestor",
+        "type": "tag:universe,2020:worlds/validation-errors/locations/parent-cycle"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_import_invalid_map_features() {
+  let service = ServiceWrapper::default();
+  let other_user = seed_other_user(&service);
+
+  let mut archive: serde_json::Value = serde_json::from_str(&archive(json!([]))).unwrap();
+  archive["maps"] = json!([
+    {
+      "id": "middle-earth",
+      "name": "Middle-earth",
+      "features": [
+        {
+          "name": "Bag End",
+          "geometry": { "type": "Point", "coordinates": [130, 85.5] },
+          "link": { "type": "location", "id": "hobbiton" }
+        }
+      ]
+    }
+  ]);
+
+  let mut response = service
+    .post("/worlds/import")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .body(archive.to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "maps[0].features[0].geometry",
+        "title": "Features can't be placed on a map until it has an image",
+        "type": "tag:universe,2020:maps/validation-errors/geometry/no-image"
+      },
+      {
+        "field": "maps[0].features[0].link",
+        "title": "The link is not to a location, character or article of the world",
+        "type": "tag:universe,2020:maps/validation-errors/link/unknown"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_import_invalid_calendars() {
+  let service = ServiceWrapper::default();
+  let other_user = seed_other_user(&service);
+
+  let mut archive: serde_json::Value = serde_json::from_str(&archive(json!([]))).unwrap();
+  archive["calendars"] = json!([
+    {
+      "id": "shire-reckoning",
+      "name": "Shire Reckoning",
+      "months": [{ "name": "Afteryule", "days": 30 }],
+      "events": [
+        {
+          "title": "Founding of the Shire",
+          "date": { "year": 1, "month": 1, "day": 31 }
+        }
+      ]
+    },
+    {
+      "id": "kings-reckoning",
+      "name": "Kings' Reckoning",
+      "months": [],
+      "events": []
+    }
+  ]);
+
+  let mut response = service
+    .post("/worlds/import")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .body(archive.to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "calendars[0].events[0].date",
+        "title": "The month doesn't have that many days",
+        "type": "tag:universe,2020:calendars/validation-errors/date/day-out-of-range"
+      },
+      {
+        "field": "calendars[1].months",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_import_invalid_relationships() {
+  let service = ServiceWrapper::default();
+  let other_user = seed_other_user(&service);
+
+  let mut archive: serde_json::Value = serde_json::from_str(&archive(json!([]))).unwrap();
+  archive["characters"] = json!([{ "id": "frodo", "name": "Frodo Baggins" }]);
+  archive["calendars"] = json!([
+    {
+      "id": "shire-reckoning",
+      "name": "Shire Reckoning",
+      "months": [{ "name": "Afteryule", "days": 30 }]
+    }
+  ]);
+  archive["relationships"] = json!([
+    {
+      "source": { "type": "character", "id": "frodo" },
+      "target": { "type": "location", "id": "hobbiton" },
+      "relation": "lives in",
+      "during": { "calendar": "kings-reckoning" }
+    },
+    {
+      "source": { "type": "character", "id": "frodo" },
+      "target": { "type": "character", "id": "frodo" },
+      "relation": " ",
+      "during": {
+        "calendar": "shire-reckoning",
+        "start": { "year": 1, "month": 1, "day": 20 },
+        "end": { "year": 1, "month": 1, "day": 10 }
+      }
+    }
+  ]);
+
+  let mut response = service
+    .post("/worlds/import")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .body(archive.to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "relationships[0].target",
+        "title": "The target is not an entity of the world",
+        "type": "tag:universe,2020:relationships/validation-errors/target/unknown"
+      },
+      {
+        "field": "relationships[0].during.calendar",
+        "title": "The calendar is not a calendar of the world",
+        "type": "tag:universe,2020:relationships/validation-errors/during/unknown-calendar"
+      },
+      {
+        "field": "relationships[1].target",
+        "title": "An entity can't have a relationship with itself",
+        "type": "tag:universe,2020:relationships/validation-errors/target/same-as-source"
+      },
+      {
+        "field": "relationships[1].relation",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      },
+      {
+        "field": "relationships[1].during.end",
+        "title": "The end of the date range must not be before the start",
+        "type": "tag:universe,2020:relationships/validation-errors/during/out-of-order"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
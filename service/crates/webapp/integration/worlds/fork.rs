@@ -0,0 +1,667 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, build_rewrite_headers,
+  regex_replace, ServiceWrapper,
+};
+use image::{DynamicImage, ImageOutputFormat};
+use insta::{assert_json_snapshot, assert_snapshot};
+use rocket::http::ContentType;
+use serde_json::json;
+use test_env_log::test;
+use universe_testdata::{
+  seed, Article, Calendar, Character, Location, Map, TimelineEvent, User, World, WorldMember,
+};
+
+fn seed_world(service: &ServiceWrapper, visibility: &str) -> (User, World) {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    description: "This is a test world".to_owned(),
+    visibility: visibility.to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&user, &world]);
+
+  (user, world)
+}
+
+fn seed_other_user(service: &ServiceWrapper) -> User {
+  let other_user = User {
+    user_id: uuid::Uuid::parse_str("3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90").unwrap(),
+    username: "otheruser".to_owned(),
+    email: "other@example.com".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&other_user]);
+
+  other_user
+}
+
+#[test]
+fn test_fork_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_world(&service, "public");
+
+  let response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/fork")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_fork_unknown_world() {
+  let service = ServiceWrapper::default();
+  let other_user = seed_other_user(&service);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/fork")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested world could not be found",
+    "type": "tag:universe,2020:worlds/problems/unknown-world"
+  }
+  "###);
+}
+
+#[test]
+fn test_fork_private_world() {
+  let service = ServiceWrapper::default();
+  seed_world(&service, "private");
+  let other_user = seed_other_user(&service);
+
+  let response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/fork")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_fork_private_world_as_member() {
+  let service = ServiceWrapper::default();
+  seed_world(&service, "private");
+  let other_user = seed_other_user(&service);
+  seed(
+    service.database(),
+    vec![&WorldMember {
+      world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+      user_id: other_user.user_id,
+      ..Default::default()
+    }],
+  );
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/fork")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), {
+        ".id" => "[uuid]",
+    },
+    @r###"
+  {
+    "description": "This is a test world",
+    "descriptionHtml": "<p>This is a test world</p>\n",
+    "forkedFrom": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+    "id": "[uuid]",
+    "name": "Test World",
+    "owner": "3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90",
+    "slug": "test-world",
+    "stars": 0,
+    "visibility": "private"
+  }
+  "###);
+}
+
+#[test]
+fn test_fork_other_users_world() {
+  let service = ServiceWrapper::default();
+  seed_world(&service, "public");
+  let other_user = seed_other_user(&service);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/fork")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_rewrite_headers(&response, |h| {
+    regex_replace(h, r#"/worlds/[a-z0-9]{8}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{12}"#, "/worlds/0e1a4f4c-5a3f-4a57-8d2b-3c9ee5e4c2b1")
+  }), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/0e1a4f4c-5a3f-4a57-8d2b-3c9ee5e4c2b1>; rel="canonical"
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/fork", "post", 200);
+  assert_json_snapshot!(body, {
+        ".id" => "[uuid]",
+    },
+    @r###"
+  {
+    "description": "This is a test world",
+    "descriptionHtml": "<p>This is a test world</p>\n",
+    "forkedFrom": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+    "id": "[uuid]",
+    "name": "Test World",
+    "owner": "3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90",
+    "slug": "test-world",
+    "stars": 0,
+    "visibility": "public"
+  }
+  "###);
+}
+
+#[test]
+fn test_fork_own_world() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service, "public");
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/fork")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/fork", "post", 200);
+  assert_json_snapshot!(body, {
+        ".id" => "[uuid]",
+    },
+    @r###"
+  {
+    "description": "This is a test world",
+    "descriptionHtml": "<p>This is a test world</p>\n",
+    "forkedFrom": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+    "id": "[uuid]",
+    "name": "Test World",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "slug": "test-world-2",
+    "stars": 0,
+    "visibility": "public"
+  }
+  "###);
+}
+
+#[test]
+fn test_fork_copies_articles() {
+  let service = ServiceWrapper::default();
+  seed_world(&service, "public");
+  let other_user = seed_other_user(&service);
+  let history = Article {
+    article_id: uuid::Uuid::parse_str("0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f01").unwrap(),
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    title: "History".to_owned(),
+    slug: "history".to_owned(),
+    ..Default::default()
+  };
+  let first_age = Article {
+    article_id: uuid::Uuid::parse_str("0a4c9f1e-5b3d-4e7a-9c2f-1d8b6e3a7f02").unwrap(),
+    world_id: history.world_id,
+    parent_id: Some(history.article_id),
+    title: "The First Age".to_owned(),
+    slug: "first-age".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&history, &first_age]);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/fork")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+  let fork = build_json_body(&mut response);
+
+  let mut response = service
+    .get(format!(
+      "/worlds/{}/articles?parent=none",
+      fork["id"].as_str().unwrap()
+    ))
+    .dispatch();
+  let top_level = build_json_body(&mut response);
+  assert_json_snapshot!(top_level, {
+        ".entries[].id" => "[uuid]",
+        ".entries[].world" => "[uuid]",
+    },
+    @r###"
+  {
+    "entries": [
+      {
+        "body": "This is a test article",
+        "bodyHtml": "<p>This is a test article</p>\n",
+        "id": "[uuid]",
+        "slug": "history",
+        "title": "History",
+        "world": "[uuid]"
+      }
+    ],
+    "total": 1
+  }
+  "###);
+
+  let mut response = service
+    .get(format!(
+      "/worlds/{}/articles?parent={}",
+      fork["id"].as_str().unwrap(),
+      top_level["entries"][0]["id"].as_str().unwrap()
+    ))
+    .dispatch();
+  assert_json_snapshot!(build_json_body(&mut response), {
+        ".entries[].id" => "[uuid]",
+        ".entries[].parent" => "[uuid]",
+        ".entries[].world" => "[uuid]",
+    },
+    @r###"
+  {
+    "entries": [
+      {
+        "body": "This is a test article",
+        "bodyHtml": "<p>This is a test article</p>\n",
+        "id": "[uuid]",
+        "parent": "[uuid]",
+        "slug": "first-age",
+        "title": "The First Age",
+        "world": "[uuid]"
+      }
+    ],
+    "total": 1
+  }
+  "###);
+}
+
+#[test]
+fn test_fork_copies_characters() {
+  let service = ServiceWrapper::default();
+  seed_world(&service, "public");
+  let other_user = seed_other_user(&service);
+  let frodo = Character {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    name: "Frodo Baggins".to_owned(),
+    aliases: vec!["Mr Underhill".to_owned()],
+    attributes: vec![("Race".to_owned(), "Hobbit".to_owned())]
+      .into_iter()
+      .collect(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&frodo]);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/fork")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+  let fork = build_json_body(&mut response);
+
+  let mut response = service
+    .get(format!(
+      "/worlds/{}/characters",
+      fork["id"].as_str().unwrap()
+    ))
+    .dispatch();
+  let characters = build_json_body(&mut response);
+  assert_eq!(characters["entries"][0]["world"], fork["id"]);
+  assert_json_snapshot!(characters, {
+        ".entries[].id" => "[uuid]",
+        ".entries[].world" => "[uuid]",
+    },
+    @r###"
+  {
+    "entries": [
+      {
+        "aliases": [
+          "Mr Underhill"
+        ],
+        "attributes": {
+          "Race": "Hobbit"
+        },
+        "biography": "This is a test character",
+        "biographyHtml": "<p>This is a test character</p>\n",
+        "id": "[uuid]",
+        "name": "Frodo Baggins",
+        "summary": "A test character",
+        "world": "[uuid]"
+      }
+    ],
+    "total": 1
+  }
+  "###);
+}
+
+#[test]
+fn test_fork_copies_locations() {
+  let service = ServiceWrapper::default();
+  seed_world(&service, "public");
+  let other_user = seed_other_user(&service);
+  let shire = Location {
+    location_id: uuid::Uuid::parse_str("3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03").unwrap(),
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    name: "The Shire".to_owned(),
+    kind: "Region".to_owned(),
+    ..Default::default()
+  };
+  let hobbiton = Location {
+    location_id: uuid::Uuid::parse_str("3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04").unwrap(),
+    world_id: shire.world_id,
+    parent_id: Some(shire.location_id),
+    name: "Hobbiton".to_owned(),
+    kind: "City".to_owned(),
+    x: Some(120.5),
+    y: Some(80.0),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&shire, &hobbiton]);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/fork")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+  let fork = build_json_body(&mut response);
+
+  let mut response = service
+    .get(format!(
+      "/worlds/{}/locations?parent=none",
+      fork["id"].as_str().unwrap()
+    ))
+    .dispatch();
+  let top_level = build_json_body(&mut response);
+  assert_ne!(
+    top_level["entries"][0]["id"],
+    "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a03"
+  );
+
+  let mut response = service
+    .get(format!(
+      "/worlds/{}/locations/{}/descendants",
+      fork["id"].as_str().unwrap(),
+      top_level["entries"][0]["id"].as_str().unwrap()
+    ))
+    .dispatch();
+  assert_json_snapshot!(build_json_body(&mut response), {
+        ".entries[].id" => "[uuid]",
+        ".entries[].parent" => "[uuid]",
+        ".entries[].world" => "[uuid]",
+    },
+    @r###"
+  {
+    "entries": [
+      {
+        "coordinates": {
+          "x": 120.5,
+          "y": 80.0
+        },
+        "depth": 1,
+        "description": "This is a test location",
+        "descriptionHtml": "<p>This is a test location</p>\n",
+        "id": "[uuid]",
+        "kind": "City",
+        "name": "Hobbiton",
+        "parent": "[uuid]",
+        "world": "[uuid]"
+      }
+    ],
+    "total": 1
+  }
+  "###);
+}
+
+#[test]
+fn test_fork_copies_maps() {
+  let service = ServiceWrapper::default();
+  let (user, world) = seed_world(&service, "public");
+  let other_user = seed_other_user(&service);
+  let hobbiton = Location {
+    location_id: uuid::Uuid::parse_str("3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04").unwrap(),
+    world_id: world.world_id,
+    name: "Hobbiton".to_owned(),
+    kind: "City".to_owned(),
+    ..Default::default()
+  };
+  let middle_earth = Map {
+    map_id: uuid::Uuid::parse_str("7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01").unwrap(),
+    world_id: world.world_id,
+    name: "Middle-earth".to_owned(),
+    image_id: None,
+    image_content_type: None,
+    image_width: None,
+    image_height: None,
+    ..Default::default()
+  };
+  seed(service.database(), vec![&hobbiton, &middle_earth]);
+
+  let mut image = vec![];
+  DynamicImage::new_rgb8(640, 480)
+    .write_to(&mut image, ImageOutputFormat::Png)
+    .unwrap();
+  service
+    .put("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/image")
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(image.clone())
+    .dispatch();
+  service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/maps/7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01/features")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "type": "Feature",
+        "geometry": { "type": "Point", "coordinates": [130, 85.5] },
+        "properties": {
+          "name": "Bag End",
+          "link": { "type": "location", "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04" }
+        }
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/fork")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+  let fork = build_json_body(&mut response);
+  let fork_id = fork["id"].as_str().unwrap();
+
+  let mut response = service.get(format!("/worlds/{}/maps", fork_id)).dispatch();
+  let maps = build_json_body(&mut response);
+  let map = &maps["entries"][0];
+  assert_ne!(map["id"], "7d1f3a9e-2c4b-4e8a-b6d0-1a9c5e3f7b01");
+  assert_eq!(map["name"], "Middle-earth");
+  assert_eq!(maps["total"], 1);
+
+  let mut response = service
+    .get(map["image"]["href"].as_str().unwrap())
+    .dispatch();
+  assert_eq!(image, response.body_bytes().unwrap());
+
+  let mut response = service
+    .get(format!("/worlds/{}/locations", fork_id))
+    .dispatch();
+  let locations = build_json_body(&mut response);
+
+  let mut response = service
+    .get(format!(
+      "/worlds/{}/maps/{}/features",
+      fork_id,
+      map["id"].as_str().unwrap()
+    ))
+    .dispatch();
+  let features = build_json_body(&mut response);
+  let link = &features["features"][0]["properties"]["link"];
+  assert_eq!(link["type"], "location");
+  assert_eq!(link["id"], locations["entries"][0]["id"]);
+  assert_ne!(link["id"], "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04");
+}
+
+#[test]
+fn test_fork_copies_calendars() {
+  let service = ServiceWrapper::default();
+  let (_, world) = seed_world(&service, "public");
+  let other_user = seed_other_user(&service);
+  let shire = Calendar {
+    calendar_id: uuid::Uuid::parse_str("3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01").unwrap(),
+    world_id: world.world_id,
+    name: "Shire Reckoning".to_owned(),
+    definition: json!({
+      "months": [
+        { "name": "Afteryule", "days": 30 },
+        { "name": "Solmath", "days": 30 }
+      ]
+    }),
+    ..Default::default()
+  };
+  let founding = TimelineEvent {
+    world_id: world.world_id,
+    calendar_id: shire.calendar_id,
+    title: "Founding of the Shire".to_owned(),
+    year: 1,
+    month: 2,
+    day: 1,
+    day_number: 30,
+    ..Default::default()
+  };
+  seed(service.database(), vec![&shire, &founding]);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/fork")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+  let fork = build_json_body(&mut response);
+  let fork_id = fork["id"].as_str().unwrap();
+
+  let mut response = service
+    .get(format!("/worlds/{}/calendars", fork_id))
+    .dispatch();
+  let calendars = build_json_body(&mut response);
+  let calendar = &calendars["entries"][0];
+  assert_ne!(calendar["id"], "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01");
+  assert_eq!(calendar["name"], "Shire Reckoning");
+  assert_eq!(calendar["months"][1]["name"], "Solmath");
+  assert_eq!(calendars["total"], 1);
+
+  let mut response = service
+    .get(format!(
+      "/worlds/{}/calendars/{}/events",
+      fork_id,
+      calendar["id"].as_str().unwrap()
+    ))
+    .dispatch();
+  let events = build_json_body(&mut response);
+  assert_eq!(events["entries"][0]["title"], "Founding of the Shire");
+  assert_eq!(events["entries"][0]["date"]["text"], "1 Solmath 1");
+  assert_eq!(events["total"], 1);
+}
+
+#[test]
+fn test_fork_copies_relationships() {
+  let service = ServiceWrapper::default();
+  let (user, world) = seed_world(&service, "public");
+  let other_user = seed_other_user(&service);
+  let frodo = Character {
+    character_id: uuid::Uuid::parse_str("5b2c8e1f-3a7d-4c9b-8e6f-1d4a7c2b9e01").unwrap(),
+    world_id: world.world_id,
+    name: "Frodo Baggins".to_owned(),
+    ..Default::default()
+  };
+  let hobbiton = Location {
+    location_id: uuid::Uuid::parse_str("3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04").unwrap(),
+    world_id: world.world_id,
+    name: "Hobbiton".to_owned(),
+    kind: "City".to_owned(),
+    ..Default::default()
+  };
+  let shire = Calendar {
+    calendar_id: uuid::Uuid::parse_str("3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01").unwrap(),
+    world_id: world.world_id,
+    name: "Shire Reckoning".to_owned(),
+    definition: json!({
+      "months": [
+        { "name": "Afteryule", "days": 30 },
+        { "name": "Solmath", "days": 30 }
+      ]
+    }),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&frodo, &hobbiton, &shire]);
+
+  service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/relationships")
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "source": { "type": "character", "id": "5b2c8e1f-3a7d-4c9b-8e6f-1d4a7c2b9e01" },
+        "target": { "type": "location", "id": "3e2b7c1a-6f4d-4b8e-a1c9-5d7f2e8b4a04" },
+        "relation": "lives in",
+        "during": {
+          "calendar": "3b5d7f9a-1c2e-4a6b-8d0f-2e4a6c8b0d01",
+          "start": "1 Solmath 1"
+        }
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/fork")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+  let fork = build_json_body(&mut response);
+  let fork_id = fork["id"].as_str().unwrap();
+
+  let mut response = service
+    .get(format!("/worlds/{}/characters", fork_id))
+    .dispatch();
+  let characters = build_json_body(&mut response);
+  let mut response = service
+    .get(format!("/worlds/{}/locations", fork_id))
+    .dispatch();
+  let locations = build_json_body(&mut response);
+  let mut response = service
+    .get(format!("/worlds/{}/calendars", fork_id))
+    .dispatch();
+  let calendars = build_json_body(&mut response);
+
+  let mut response = service
+    .get(format!("/worlds/{}/relationships", fork_id))
+    .dispatch();
+  let relationships = build_json_body(&mut response);
+  let relationship = &relationships["entries"][0];
+  assert_eq!(relationship["relation"], "lives in");
+  assert_eq!(relationship["source"]["id"], characters["entries"][0]["id"]);
+  assert_eq!(relationship["target"]["id"], locations["entries"][0]["id"]);
+  assert_eq!(
+    relationship["during"]["calendar"],
+    calendars["entries"][0]["id"]
+  );
+  assert_eq!(relationship["during"]["start"], "1 Solmath 1");
+  assert_ne!(
+    relationship["source"]["id"],
+    "5b2c8e1f-3a7d-4c9b-8e6f-1d4a7c2b9e01"
+  );
+  assert_eq!(relationships["total"], 1);
+}
@@ -0,0 +1,350 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use rocket::http::ContentType;
+use serde_json::json;
+use std::str::FromStr;
+use test_env_log::test;
+use universe_testdata::{seed, User, World};
+
+fn seed_world(service: &ServiceWrapper) -> (User, World) {
+  seed_world_with_visibility(service, "public")
+}
+
+fn seed_world_with_visibility(service: &ServiceWrapper, visibility: &str) -> (User, World) {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    version: uuid::Uuid::parse_str("a7fd01dc-dcf7-45dd-a932-0b6b263e17d0").unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    description: "This is a test world".to_owned(),
+    visibility: visibility.to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&user, &world]);
+
+  (user, world)
+}
+
+#[test]
+fn test_get_unknown_world_by_id() {
+  let service = ServiceWrapper::default();
+
+  let req = service.get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8");
+  let mut response = req.dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested world could not be found",
+    "type": "tag:universe,2020:worlds/problems/unknown-world"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_invalid_world_id() {
+  let service = ServiceWrapper::default();
+
+  let req = service.get("/worlds/invalid");
+  let mut response = req.dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested world could not be found",
+    "type": "tag:universe,2020:worlds/problems/unknown-world"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_known_world_by_id() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+
+  let req = service.get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8");
+  let mut response = req.dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8>; rel="self"
+  Accept-Patch: application/merge-patch+json
+  ETag: "a7fd01dc-dcf7-45dd-a932-0b6b263e17d0-0"
+  Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT
+  Cache-Control: public, max-age=3600
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}", "get", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "description": "This is a test world",
+    "descriptionHtml": "<p>This is a test world</p>\n",
+    "id": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+    "name": "Test World",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "slug": "test-world",
+    "stars": 0,
+    "visibility": "public"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_known_world_by_slug() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+
+  let req = service.get("/users/TestUser/worlds/Test-World");
+  let mut response = req.dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8>; rel="self"
+  Accept-Patch: application/merge-patch+json
+  ETag: "a7fd01dc-dcf7-45dd-a932-0b6b263e17d0-0"
+  Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT
+  Cache-Control: public, max-age=3600
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/users/{username}/worlds/{slug}", "get", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "description": "This is a test world",
+    "descriptionHtml": "<p>This is a test world</p>\n",
+    "id": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+    "name": "Test World",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "slug": "test-world",
+    "stars": 0,
+    "visibility": "public"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_world_by_slug_unknown_user() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+
+  let req = service.get("/users/otheruser/worlds/test-world");
+  let mut response = req.dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested world could not be found",
+    "type": "tag:universe,2020:worlds/problems/unknown-world"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_world_by_slug_unknown_slug() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+
+  let req = service.get("/users/testuser/worlds/other-world");
+  let mut response = req.dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested world could not be found",
+    "type": "tag:universe,2020:worlds/problems/unknown-world"
+  }
+  "###);
+}
+
+/// Change the slug of the seeded world via the API
+fn change_slug(service: &ServiceWrapper, user: &User, slug: &str) {
+  let response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(service, user).unwrap())
+    .body(json!({ "slug": slug }).to_string())
+    .dispatch();
+  assert_eq!(response.status().code, 200);
+}
+
+#[test]
+fn test_get_world_by_previous_slug() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+  change_slug(&service, &user, "renamed-world");
+
+  let req = service.get("/users/TestUser/worlds/Test-World");
+  let response = req.dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 301 Moved Permanently.
+  Location: /users/testuser/worlds/renamed-world
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8>; rel="canonical"
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_get_world_by_slug_after_renaming_back() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+  change_slug(&service, &user, "renamed-world");
+  change_slug(&service, &user, "test-world");
+
+  let req = service.get("/users/testuser/worlds/test-world");
+  let mut response = req.dispatch();
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "description": "This is a test world",
+    "descriptionHtml": "<p>This is a test world</p>\n",
+    "id": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+    "name": "Test World",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "slug": "test-world",
+    "stars": 0,
+    "visibility": "public"
+  }
+  "###);
+
+  let req = service.get("/users/testuser/worlds/renamed-world");
+  let response = req.dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 301 Moved Permanently.
+  Location: /users/testuser/worlds/test-world
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8>; rel="canonical"
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_get_private_world_anonymous() {
+  let service = ServiceWrapper::default();
+  seed_world_with_visibility(&service, "private");
+
+  for url in &[
+    "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+    "/users/testuser/worlds/test-world",
+  ] {
+    let response = service.get(*url).dispatch();
+    assert_snapshot!(build_headers(&response), @r###"
+    HTTP/1.1 404 .
+    Content-Type: application/problem+json
+    Server: Rocket
+    "###);
+  }
+}
+
+#[test]
+fn test_get_private_world_other_user() {
+  let service = ServiceWrapper::default();
+  seed_world_with_visibility(&service, "private");
+  let other_user = User {
+    username: "otheruser".to_owned(),
+    email: "other@example.com".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&other_user]);
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_get_private_world_owner() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world_with_visibility(&service, "private");
+
+  let mut response = service
+    .get("/users/testuser/worlds/test-world")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8>; rel="self"
+  Accept-Patch: application/merge-patch+json
+  ETag: "a7fd01dc-dcf7-45dd-a932-0b6b263e17d0-0"
+  Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT
+  Cache-Control: private, max-age=3600
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "description": "This is a test world",
+    "descriptionHtml": "<p>This is a test world</p>\n",
+    "id": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+    "name": "Test World",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "slug": "test-world",
+    "stars": 0,
+    "visibility": "private"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_unlisted_world_anonymous() {
+  let service = ServiceWrapper::default();
+  seed_world_with_visibility(&service, "unlisted");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .dispatch();
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}", "get", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "description": "This is a test world",
+    "descriptionHtml": "<p>This is a test world</p>\n",
+    "id": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+    "name": "Test World",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "slug": "test-world",
+    "stars": 0,
+    "visibility": "unlisted"
+  }
+  "###);
+}
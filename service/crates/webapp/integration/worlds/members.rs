@@ -0,0 +1,529 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use rocket::http::ContentType;
+use serde_json::json;
+use std::str::FromStr;
+use test_env_log::test;
+use universe_testdata::{seed, User, World, WorldMember};
+
+fn seed_world(service: &ServiceWrapper) -> (User, World) {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    version: uuid::Uuid::parse_str("a7fd01dc-dcf7-45dd-a932-0b6b263e17d0").unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    description: "This is a test world".to_owned(),
+    visibility: "private".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&user, &world]);
+
+  (user, world)
+}
+
+fn seed_other_user(service: &ServiceWrapper) -> User {
+  let other_user = User {
+    user_id: uuid::Uuid::parse_str("3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90").unwrap(),
+    username: "otheruser".to_owned(),
+    email: "other@example.com".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&other_user]);
+
+  other_user
+}
+
+fn seed_member(service: &ServiceWrapper, role: &str) -> User {
+  let member = seed_other_user(service);
+  seed(
+    service.database(),
+    vec![&WorldMember {
+      world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+      user_id: member.user_id,
+      role: role.to_owned(),
+      ..Default::default()
+    }],
+  );
+
+  member
+}
+
+#[test]
+fn test_list_members_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/members")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_list_members_not_member() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+  let other_user = seed_other_user(&service);
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/members")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_list_members_as_viewer() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+  let member = seed_member(&service, "viewer");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/members")
+    .header(authenticate_user(&service, &member).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Cache-Control: private, no-cache
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/members", "get", 200);
+  assert_json_snapshot!(body, {
+        ".entries[].created" => "[timestamp]",
+        ".entries[].updated" => "[timestamp]",
+    },
+    @r###"
+  {
+    "entries": [
+      {
+        "created": "[timestamp]",
+        "role": "viewer",
+        "updated": "[timestamp]",
+        "user": "3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90"
+      }
+    ],
+    "total": 1
+  }
+  "###);
+}
+
+#[test]
+fn test_add_member() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+  let other_user = seed_other_user(&service);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/members")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "user": "3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90",
+        "role": "editor"
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Cache-Control: private, no-cache
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/members", "post", 200);
+  assert_json_snapshot!(body, {
+        ".created" => "[timestamp]",
+        ".updated" => "[timestamp]",
+    },
+    @r###"
+  {
+    "created": "[timestamp]",
+    "role": "editor",
+    "updated": "[timestamp]",
+    "user": "3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90"
+  }
+  "###);
+
+  // The new member can now see the private world
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8>; rel="self"
+  Accept-Patch: application/merge-patch+json
+  ETag: "a7fd01dc-dcf7-45dd-a932-0b6b263e17d0-0"
+  Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT
+  Cache-Control: private, max-age=3600
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_add_member_not_owner() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+  let member = seed_member(&service, "editor");
+
+  let response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/members")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &member).unwrap())
+    .body(
+      json!({
+        "user": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+        "role": "viewer"
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 403 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_add_member_invalid() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/members")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "role": "admin" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "user",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      },
+      {
+        "field": "role",
+        "title": "The role must be one of owner, editor or viewer",
+        "type": "tag:universe,2020:worlds/validation-errors/role/unknown"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_add_member_unknown_user() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/members")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "user": "3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90",
+        "role": "viewer"
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "user",
+        "title": "The user could not be found",
+        "type": "tag:universe,2020:worlds/validation-errors/user/unknown"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_add_member_already_member() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+  seed_member(&service, "viewer");
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/members")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "user": "3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90",
+        "role": "editor"
+      })
+      .to_string(),
+    )
+    .dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "user",
+        "title": "The user is already a member of the world",
+        "type": "tag:universe,2020:worlds/validation-errors/user/duplicate-member"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_update_member() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+  seed_member(&service, "viewer");
+
+  let mut response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/members/3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "role": "owner" }).to_string())
+    .dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(
+    &body,
+    "/worlds/{worldId}/members/{userId}",
+    "patch",
+    200,
+  );
+  assert_json_snapshot!(body, {
+        ".created" => "[timestamp]",
+        ".updated" => "[timestamp]",
+    },
+    @r###"
+  {
+    "created": "[timestamp]",
+    "role": "owner",
+    "updated": "[timestamp]",
+    "user": "3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90"
+  }
+  "###);
+}
+
+#[test]
+fn test_update_unknown_member() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+  seed_other_user(&service);
+
+  let mut response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/members/3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "role": "owner" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested member could not be found",
+    "type": "tag:universe,2020:worlds/problems/unknown-member"
+  }
+  "###);
+}
+
+#[test]
+fn test_remove_member() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+  seed_member(&service, "editor");
+
+  let response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/members/3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 204 No Content.
+  Server: Rocket
+  "###);
+
+  let response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/members/3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_member_leaves_world() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+  let member = seed_member(&service, "viewer");
+
+  let response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/members/3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90")
+    .header(authenticate_user(&service, &member).unwrap())
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 204 No Content.
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_viewer_cannot_remove_other_member() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+  let member = seed_member(&service, "viewer");
+
+  let response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/members/2fcc3850-bb9b-405e-bbab-22978283fef8")
+    .header(authenticate_user(&service, &member).unwrap())
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 403 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_editor_can_update_world() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+  let member = seed_member(&service, "editor");
+
+  let mut response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &member).unwrap())
+    .body(json!({ "name": "Shared World" }).to_string())
+    .dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "description": "This is a test world",
+    "descriptionHtml": "<p>This is a test world</p>\n",
+    "id": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+    "name": "Shared World",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "slug": "test-world",
+    "stars": 0,
+    "visibility": "private"
+  }
+  "###);
+}
+
+#[test]
+fn test_editor_cannot_change_visibility() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+  let member = seed_member(&service, "editor");
+
+  let response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &member).unwrap())
+    .body(json!({ "visibility": "public" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 403 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_viewer_cannot_update_world() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+  let member = seed_member(&service, "viewer");
+
+  let response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &member).unwrap())
+    .body(json!({ "name": "Shared World" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 403 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_editor_cannot_delete_world() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+  let member = seed_member(&service, "editor");
+
+  let response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .header(authenticate_user(&service, &member).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 403 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
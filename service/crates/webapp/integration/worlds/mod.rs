@@ -1 +1,12 @@
+mod cover;
+mod export;
+mod fork;
+mod get;
+mod members;
+mod patch;
+mod post;
+mod revisions;
 mod search;
+mod stars;
+mod transfer;
+mod trash;
@@ -0,0 +1,460 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, build_rewrite_headers,
+  regex_replace, ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use rocket::http::ContentType;
+use serde_json::json;
+use std::str::FromStr;
+use test_env_log::test;
+use universe_testdata::{seed, User, World};
+
+fn seed_world(service: &ServiceWrapper) -> (User, World) {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    description: "This is a test world".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&user, &world]);
+
+  (user, world)
+}
+
+#[test]
+fn test_patch_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+
+  let req = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .body(json!({}).to_string());
+  let response = req.dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_patch_wrong_user() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+  let other_user = User {
+    username: "otheruser".to_owned(),
+    email: "other@example.com".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&other_user]);
+
+  let req = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .body(json!({ "name": "Stolen World" }).to_string());
+  let mut response = req.dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 403 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 403,
+    "title": "You are not permitted to perform this request",
+    "type": "tag:universe,2020:problems/authentication/forbidden"
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_unknown_world() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+
+  let req = service
+    .patch("/worlds/83c60ad3-2a4f-455b-b685-c16da785bf6e")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({}).to_string());
+  let mut response = req.dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested world could not be found",
+    "type": "tag:universe,2020:worlds/problems/unknown-world"
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_no_differences() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+
+  let req = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({}).to_string());
+  let mut response = req.dispatch();
+
+  assert_snapshot!(build_rewrite_headers(&response, |h| {
+    let h = regex_replace(h, r#"ETag: "[a-z0-9]{8}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{12}-"#, r#"ETag: "a7fd01dc-dcf7-45dd-a932-0b6b263e17d0-"#);
+    regex_replace(h, r#"^Last-Modified: .*$"#, "Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT")
+  }), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8>; rel="self"
+  Accept-Patch: application/merge-patch+json
+  ETag: "a7fd01dc-dcf7-45dd-a932-0b6b263e17d0-0"
+  Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT
+  Cache-Control: public, max-age=3600
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}", "patch", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "description": "This is a test world",
+    "descriptionHtml": "<p>This is a test world</p>\n",
+    "id": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+    "name": "Test World",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "slug": "test-world",
+    "stars": 0,
+    "visibility": "public"
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_with_differences() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+
+  let req = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "name": "  Middle Earth  ",
+        "slug": "middle-earth",
+        "description": "Home of the Hobbits"
+      })
+      .to_string(),
+    );
+  let mut response = req.dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}", "patch", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "description": "Home of the Hobbits",
+    "descriptionHtml": "<p>Home of the Hobbits</p>\n",
+    "id": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+    "name": "Middle Earth",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "slug": "middle-earth",
+    "stars": 0,
+    "visibility": "public"
+  }
+  "###);
+
+  let req = service.get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8");
+  let mut response = req.dispatch();
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "description": "Home of the Hobbits",
+    "descriptionHtml": "<p>Home of the Hobbits</p>\n",
+    "id": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+    "name": "Middle Earth",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "slug": "middle-earth",
+    "stars": 0,
+    "visibility": "public"
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_blank_values() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+
+  let req = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "name": "  ",
+        "slug": ""
+      })
+      .to_string(),
+    );
+  let mut response = req.dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "name",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      },
+      {
+        "field": "slug",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_duplicate_slug() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+  let other_world = World {
+    owner_id: user.user_id,
+    slug: "other-world".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&other_world]);
+
+  let req = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "slug": "Other-World" }).to_string());
+  let mut response = req.dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "slug",
+        "title": "The slug is already used by another world",
+        "type": "tag:universe,2020:worlds/validation-errors/slug/duplicate"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_invalid_slug() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+
+  let req = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "slug": "my/world" }).to_string());
+  let mut response = req.dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "slug",
+        "title": "The slug may only contain letters, digits, hyphens and underscores",
+        "type": "tag:universe,2020:worlds/validation-errors/slug/invalid-characters"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_visibility() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+
+  let req = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "visibility": "private" }).to_string());
+  let mut response = req.dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}", "patch", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "description": "This is a test world",
+    "descriptionHtml": "<p>This is a test world</p>\n",
+    "id": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+    "name": "Test World",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "slug": "test-world",
+    "stars": 0,
+    "visibility": "private"
+  }
+  "###);
+
+  let req = service.get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8");
+  let response = req.dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_patch_unknown_visibility() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+
+  let req = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "visibility": "secret" }).to_string());
+  let mut response = req.dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "visibility",
+        "title": "The visibility must be one of public, unlisted or private",
+        "type": "tag:universe,2020:worlds/validation-errors/visibility/unknown"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_tags() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+
+  let req = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "tags": ["Fantasy", "  High   Magic ", "fantasy"] }).to_string());
+  let mut response = req.dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}", "patch", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "description": "This is a test world",
+    "descriptionHtml": "<p>This is a test world</p>\n",
+    "id": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+    "name": "Test World",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "slug": "test-world",
+    "stars": 0,
+    "tags": [
+      "fantasy",
+      "high magic"
+    ],
+    "visibility": "public"
+  }
+  "###);
+
+  let req = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "tags": [] }).to_string());
+  let mut response = req.dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "description": "This is a test world",
+    "descriptionHtml": "<p>This is a test world</p>\n",
+    "id": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+    "name": "Test World",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "slug": "test-world",
+    "stars": 0,
+    "visibility": "public"
+  }
+  "###);
+}
+
+#[test]
+fn test_patch_invalid_tags() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+
+  let req = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "tags": ["fantasy, magic"] }).to_string());
+  let mut response = req.dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "tags",
+        "title": "Tags may not contain commas or control characters",
+        "type": "tag:universe,2020:worlds/validation-errors/tags/invalid-characters"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
@@ -0,0 +1,587 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, build_rewrite_headers, regex_replace,
+  ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use rocket::http::ContentType;
+use serde_json::json;
+use test_env_log::test;
+use universe_testdata::{seed, User, World};
+
+fn seed_user(service: &ServiceWrapper) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&user]);
+
+  user
+}
+
+#[test]
+fn test_post_unauthorized() {
+  let service = ServiceWrapper::default();
+
+  let req = service.post("/worlds").header(ContentType::JSON).body(
+    json!({
+      "name": "Test World",
+      "slug": "test-world"
+    })
+    .to_string(),
+  );
+  let response = req.dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_post_empty_object() {
+  let service = ServiceWrapper::default();
+  let user = seed_user(&service);
+
+  let req = service
+    .post("/worlds")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({}).to_string());
+  let mut response = req.dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "name",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_all_whitespace() {
+  let service = ServiceWrapper::default();
+  let user = seed_user(&service);
+
+  let req = service
+    .post("/worlds")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "name": "   ",
+        "slug": "   ",
+        "description": "   "
+      })
+      .to_string(),
+    );
+  let mut response = req.dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "name",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      },
+      {
+        "field": "slug",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_duplicate_slug() {
+  let service = ServiceWrapper::default();
+  let user = seed_user(&service);
+  let world = World {
+    owner_id: user.user_id,
+    slug: "test-world".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&world]);
+
+  let req = service
+    .post("/worlds")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "name": "Test World",
+        "slug": "Test-World"
+      })
+      .to_string(),
+    );
+  let mut response = req.dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "slug",
+        "title": "The slug is already used by another world",
+        "type": "tag:universe,2020:worlds/validation-errors/slug/duplicate"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_success() {
+  let service = ServiceWrapper::default();
+  let user = seed_user(&service);
+
+  let req = service
+    .post("/worlds")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "name": "Test World",
+        "slug": "test-world",
+        "description": "This is a test world"
+      })
+      .to_string(),
+    );
+  let mut response = req.dispatch();
+
+  assert_snapshot!(build_rewrite_headers(&response, |h| {
+    regex_replace(h, r#"/worlds/[a-z0-9]{8}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{12}"#, "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+  }), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8>; rel="canonical"
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds", "post", 200);
+  assert_json_snapshot!(body, {
+        ".id" => "[uuid]",
+    },
+    @r###"
+  {
+    "description": "This is a test world",
+    "descriptionHtml": "<p>This is a test world</p>\n",
+    "id": "[uuid]",
+    "name": "Test World",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "slug": "test-world",
+    "stars": 0,
+    "visibility": "public"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_without_description() {
+  let service = ServiceWrapper::default();
+  let user = seed_user(&service);
+
+  let req = service
+    .post("/worlds")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "name": "Test World",
+        "slug": "test-world"
+      })
+      .to_string(),
+    );
+  let mut response = req.dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), {
+        ".id" => "[uuid]",
+    },
+    @r###"
+  {
+    "description": "",
+    "descriptionHtml": "",
+    "id": "[uuid]",
+    "name": "Test World",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "slug": "test-world",
+    "stars": 0,
+    "visibility": "public"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_invalid_slug() {
+  let service = ServiceWrapper::default();
+  let user = seed_user(&service);
+
+  let req = service
+    .post("/worlds")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "name": "My World",
+        "slug": "My World!!"
+      })
+      .to_string(),
+    );
+  let mut response = req.dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "slug",
+        "title": "The slug may only contain letters, digits, hyphens and underscores",
+        "type": "tag:universe,2020:worlds/validation-errors/slug/invalid-characters"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_too_long_slug() {
+  let service = ServiceWrapper::default();
+  let user = seed_user(&service);
+
+  let req = service
+    .post("/worlds")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "name": "My World",
+        "slug": "a".repeat(65)
+      })
+      .to_string(),
+    );
+  let mut response = req.dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "slug",
+        "title": "The slug may be at most 64 characters long",
+        "type": "tag:universe,2020:worlds/validation-errors/slug/too-long"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_generated_slug() {
+  let service = ServiceWrapper::default();
+  let user = seed_user(&service);
+
+  let req = service
+    .post("/worlds")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "name": "Éowyn's Rohan!"
+      })
+      .to_string(),
+    );
+  let mut response = req.dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds", "post", 200);
+  assert_json_snapshot!(body, {
+        ".id" => "[uuid]",
+    },
+    @r###"
+  {
+    "description": "",
+    "descriptionHtml": "",
+    "id": "[uuid]",
+    "name": "Éowyn's Rohan!",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "slug": "eowyn-s-rohan",
+    "stars": 0,
+    "visibility": "public"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_generated_slug_already_used() {
+  let service = ServiceWrapper::default();
+  let user = seed_user(&service);
+  let worlds = vec![
+    World {
+      owner_id: user.user_id,
+      slug: "test-world".to_owned(),
+      ..Default::default()
+    },
+    World {
+      owner_id: user.user_id,
+      slug: "test-world-2".to_owned(),
+      ..Default::default()
+    },
+  ];
+  seed(service.database(), vec![&worlds[0], &worlds[1]]);
+
+  let req = service
+    .post("/worlds")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "name": "Test World"
+      })
+      .to_string(),
+    );
+  let mut response = req.dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), {
+        ".id" => "[uuid]",
+    },
+    @r###"
+  {
+    "description": "",
+    "descriptionHtml": "",
+    "id": "[uuid]",
+    "name": "Test World",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "slug": "test-world-3",
+    "stars": 0,
+    "visibility": "public"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_with_visibility() {
+  let service = ServiceWrapper::default();
+  let user = seed_user(&service);
+
+  let req = service
+    .post("/worlds")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "name": "Test World",
+        "visibility": "unlisted"
+      })
+      .to_string(),
+    );
+  let mut response = req.dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds", "post", 200);
+  assert_json_snapshot!(body, {
+        ".id" => "[uuid]",
+    },
+    @r###"
+  {
+    "description": "",
+    "descriptionHtml": "",
+    "id": "[uuid]",
+    "name": "Test World",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "slug": "test-world",
+    "stars": 0,
+    "visibility": "unlisted"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_unknown_visibility() {
+  let service = ServiceWrapper::default();
+  let user = seed_user(&service);
+
+  let req = service
+    .post("/worlds")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "name": "Test World",
+        "visibility": "secret"
+      })
+      .to_string(),
+    );
+  let mut response = req.dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "visibility",
+        "title": "The visibility must be one of public, unlisted or private",
+        "type": "tag:universe,2020:worlds/validation-errors/visibility/unknown"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_with_tags() {
+  let service = ServiceWrapper::default();
+  let user = seed_user(&service);
+
+  let req = service
+    .post("/worlds")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "name": "Test World",
+        "tags": ["Fantasy", "Science   Fiction", "fantasy"]
+      })
+      .to_string(),
+    );
+  let mut response = req.dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds", "post", 200);
+  assert_json_snapshot!(body, {
+        ".id" => "[uuid]",
+    },
+    @r###"
+  {
+    "description": "",
+    "descriptionHtml": "",
+    "id": "[uuid]",
+    "name": "Test World",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "slug": "test-world",
+    "stars": 0,
+    "tags": [
+      "fantasy",
+      "science fiction"
+    ],
+    "visibility": "public"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_too_many_tags() {
+  let service = ServiceWrapper::default();
+  let user = seed_user(&service);
+  let tags: Vec<String> = (0..21).map(|i| format!("tag {}", i)).collect();
+
+  let req = service
+    .post("/worlds")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "name": "Test World",
+        "tags": tags
+      })
+      .to_string(),
+    );
+  let mut response = req.dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "tags",
+        "title": "A world may have at most 20 tags",
+        "type": "tag:universe,2020:worlds/validation-errors/tags/too-many"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_post_markdown_description() {
+  let service = ServiceWrapper::default();
+  let user = seed_user(&service);
+
+  let req = service
+    .post("/worlds")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(
+      json!({
+        "name": "Test World",
+        "description": "# Test World\n\nThis is a *test* world.<script>alert('Hello')</script>\n\n[Click me](javascript:alert('Hello'))"
+      })
+      .to_string(),
+    );
+  let mut response = req.dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds", "post", 200);
+  assert_json_snapshot!(body, {
+        ".id" => "[uuid]",
+    },
+    @r###"
+  {
+    "description": "# Test World\n\nThis is a *test* world.<script>alert('Hello')</script>\n\n[Click me](javascript:alert('Hello'))",
+    "descriptionHtml": "<h1>Test World</h1>\n<p>This is a <em>test</em> world.alert('Hello')</p>\n<p><a href=\"\">Click me</a></p>\n",
+    "id": "[uuid]",
+    "name": "Test World",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "slug": "test-world",
+    "stars": 0,
+    "visibility": "public"
+  }
+  "###);
+}
@@ -0,0 +1,457 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, build_rewrite_headers,
+  regex_replace, ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use rocket::http::ContentType;
+use serde_json::json;
+use std::str::FromStr;
+use test_env_log::test;
+use universe_testdata::{seed, User, World, WorldMember};
+
+fn seed_world(service: &ServiceWrapper) -> (User, World) {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    version: uuid::Uuid::parse_str("a7fd01dc-dcf7-45dd-a932-0b6b263e17d0").unwrap(),
+    updated: "2020-03-11T13:00:36Z".parse().unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    description: "This is a test world".to_owned(),
+    visibility: "public".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&user, &world]);
+
+  (user, world)
+}
+
+fn seed_member(service: &ServiceWrapper, role: &str) -> User {
+  let member = User {
+    user_id: uuid::Uuid::parse_str("3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90").unwrap(),
+    username: "otheruser".to_owned(),
+    email: "other@example.com".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  seed(
+    service.database(),
+    vec![
+      &member,
+      &WorldMember {
+        world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+        user_id: member.user_id,
+        role: role.to_owned(),
+        ..Default::default()
+      },
+    ],
+  );
+
+  member
+}
+
+/// Make a change to the seeded world, so that it has some revisions
+fn patch_world(service: &ServiceWrapper, user: &User, patch: serde_json::Value) {
+  let response = service
+    .patch("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .header(ContentType::from_str("application/merge-patch+json").unwrap())
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(patch.to_string())
+    .dispatch();
+  assert_eq!(response.status().code, 200);
+}
+
+/// Seed the world and then rename and retag it, giving it three revisions
+fn seed_revisions(service: &ServiceWrapper) -> User {
+  let (user, _) = seed_world(&service);
+  patch_world(&service, &user, json!({ "name": "Renamed World" }));
+  patch_world(
+    &service,
+    &user,
+    json!({ "slug": "renamed-world", "tags": ["Fantasy"] }),
+  );
+
+  user
+}
+
+#[test]
+fn test_list_revisions_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/revisions")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_list_revisions() {
+  let service = ServiceWrapper::default();
+  let user = seed_revisions(&service);
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/revisions?limit=2")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Cache-Control: private, no-cache
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/revisions", "get", 200);
+  assert_json_snapshot!(body, {
+        ".entries[].created" => "[timestamp]",
+    },
+    @r###"
+  {
+    "entries": [
+      {
+        "created": "[timestamp]",
+        "description": "This is a test world",
+        "name": "Renamed World",
+        "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+        "revision": 3,
+        "slug": "renamed-world",
+        "tags": [
+          "fantasy"
+        ],
+        "visibility": "public"
+      },
+      {
+        "created": "[timestamp]",
+        "description": "This is a test world",
+        "name": "Renamed World",
+        "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+        "revision": 2,
+        "slug": "test-world",
+        "tags": [],
+        "visibility": "public"
+      }
+    ],
+    "total": 3
+  }
+  "###);
+}
+
+#[test]
+fn test_get_revision_as_viewer() {
+  let service = ServiceWrapper::default();
+  seed_revisions(&service);
+  let member = seed_member(&service, "viewer");
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/revisions/1")
+    .header(authenticate_user(&service, &member).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Cache-Control: private, no-cache
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/revisions/{revision}", "get", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "created": "2020-03-11T13:00:36Z",
+    "description": "This is a test world",
+    "name": "Test World",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "revision": 1,
+    "slug": "test-world",
+    "tags": [],
+    "visibility": "public"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_unknown_revision() {
+  let service = ServiceWrapper::default();
+  let user = seed_revisions(&service);
+
+  for revision in &["4", "0", "-1", "latest"] {
+    let mut response = service
+      .get(format!(
+        "/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/revisions/{}",
+        revision
+      ))
+      .header(authenticate_user(&service, &user).unwrap())
+      .dispatch();
+
+    assert_eq!(response.status().code, 404);
+    let body = build_json_body(&mut response);
+    assert_eq!(
+      body["type"],
+      json!("tag:universe,2020:worlds/problems/unknown-revision")
+    );
+  }
+}
+
+#[test]
+fn test_diff_revisions() {
+  let service = ServiceWrapper::default();
+  let user = seed_revisions(&service);
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/revisions/1/diff/3")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Cache-Control: private, no-cache
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(
+    &body,
+    "/worlds/{worldId}/revisions/{revision}/diff/{to}",
+    "get",
+    200,
+  );
+  assert_json_snapshot!(body, @r###"
+  {
+    "changes": [
+      {
+        "field": "name",
+        "from": "Test World",
+        "to": "Renamed World"
+      },
+      {
+        "field": "slug",
+        "from": "test-world",
+        "to": "renamed-world"
+      },
+      {
+        "field": "tags",
+        "from": [],
+        "to": [
+          "fantasy"
+        ]
+      }
+    ],
+    "from": 1,
+    "to": 3
+  }
+  "###);
+}
+
+#[test]
+fn test_diff_same_revision() {
+  let service = ServiceWrapper::default();
+  let user = seed_revisions(&service);
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/revisions/2/diff/2")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Cache-Control: private, no-cache
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_json_snapshot!(body, @r###"
+  {
+    "changes": [],
+    "from": 2,
+    "to": 2
+  }
+  "###);
+}
+
+#[test]
+fn test_restore_revision() {
+  let service = ServiceWrapper::default();
+  let user = seed_revisions(&service);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/revisions/1/restore")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_rewrite_headers(&response, |h| {
+    let h = regex_replace(h, r#"ETag: "[a-z0-9]{8}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{12}-"#, r#"ETag: "a7fd01dc-dcf7-45dd-a932-0b6b263e17d0-"#);
+    regex_replace(h, r#"^Last-Modified: .*$"#, "Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT")
+  }), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8>; rel="self"
+  Accept-Patch: application/merge-patch+json
+  ETag: "a7fd01dc-dcf7-45dd-a932-0b6b263e17d0-0"
+  Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT
+  Cache-Control: public, max-age=3600
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(
+    &body,
+    "/worlds/{worldId}/revisions/{revision}/restore",
+    "post",
+    200,
+  );
+  assert_json_snapshot!(body, @r###"
+  {
+    "description": "This is a test world",
+    "descriptionHtml": "<p>This is a test world</p>\n",
+    "id": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+    "name": "Test World",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "slug": "test-world",
+    "stars": 0,
+    "visibility": "public"
+  }
+  "###);
+
+  // Restoring is itself a change, so the newer revisions are all kept
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/revisions/3/diff/4")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_json_snapshot!(body, @r###"
+  {
+    "changes": [
+      {
+        "field": "name",
+        "from": "Renamed World",
+        "to": "Test World"
+      },
+      {
+        "field": "slug",
+        "from": "renamed-world",
+        "to": "test-world"
+      },
+      {
+        "field": "tags",
+        "from": [
+          "fantasy"
+        ],
+        "to": []
+      }
+    ],
+    "from": 3,
+    "to": 4
+  }
+  "###);
+}
+
+#[test]
+fn test_restore_revision_as_viewer() {
+  let service = ServiceWrapper::default();
+  seed_revisions(&service);
+  let member = seed_member(&service, "viewer");
+
+  let response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/revisions/1/restore")
+    .header(authenticate_user(&service, &member).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 403 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_restore_revision_changing_visibility_as_editor() {
+  let service = ServiceWrapper::default();
+  let user = seed_revisions(&service);
+  patch_world(&service, &user, json!({ "visibility": "private" }));
+  let member = seed_member(&service, "editor");
+
+  // Revision 3 is public, so restoring it would change the visibility
+  let response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/revisions/3/restore")
+    .header(authenticate_user(&service, &member).unwrap())
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 403 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+
+  // Revision 4 is private, so restoring it is allowed
+  let response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/revisions/4/restore")
+    .header(authenticate_user(&service, &member).unwrap())
+    .dispatch();
+  assert_snapshot!(build_rewrite_headers(&response, |h| {
+    let h = regex_replace(h, r#"ETag: "[a-z0-9]{8}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{4}-[a-z0-9]{12}-"#, r#"ETag: "a7fd01dc-dcf7-45dd-a932-0b6b263e17d0-"#);
+    regex_replace(h, r#"^Last-Modified: .*$"#, "Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT")
+  }), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8>; rel="self"
+  Accept-Patch: application/merge-patch+json
+  ETag: "a7fd01dc-dcf7-45dd-a932-0b6b263e17d0-0"
+  Last-Modified: Wed, 11 Mar 2020 13:00:36 GMT
+  Cache-Control: private, max-age=3600
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_restore_revision_duplicate_slug() {
+  let service = ServiceWrapper::default();
+  let user = seed_revisions(&service);
+  seed(
+    service.database(),
+    vec![&World {
+      owner_id: user.user_id,
+      name: "Another World".to_owned(),
+      slug: "test-world".to_owned(),
+      ..Default::default()
+    }],
+  );
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/revisions/1/restore")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_json_snapshot!(body, @r###"
+  {
+    "errors": [
+      {
+        "field": "slug",
+        "title": "The slug is already used by another world",
+        "type": "tag:universe,2020:worlds/validation-errors/slug/duplicate"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
@@ -1,6 +1,39 @@
-use crate::{build_headers, build_json_body, ServiceWrapper};
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, ServiceWrapper,
+};
 use insta::{assert_json_snapshot, assert_snapshot};
 use test_env_log::test;
+use universe_testdata::{seed, User, World};
+
+fn seed_worlds(service: &ServiceWrapper) -> User {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let first_world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Middle Earth".to_owned(),
+    slug: "middle-earth".to_owned(),
+    description: "Home of the Hobbits".to_owned(),
+    ..Default::default()
+  };
+  let second_world = World {
+    world_id: uuid::Uuid::parse_str("1f1d4d4e-8e1b-4a3b-9d1e-34a0e7f5bd3c").unwrap(),
+    owner_id: user.user_id,
+    name: "Discworld".to_owned(),
+    slug: "discworld".to_owned(),
+    description: "A flat world on the back of a turtle".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&user, &first_world, &second_world]);
+
+  user
+}
 
 #[test]
 fn test_search_no_worlds() {
@@ -13,12 +46,440 @@ fn test_search_no_worlds() {
   HTTP/1.1 200 OK.
   Content-Type: application/json
   Cache-Control: public, max-age=3600
+  Vary: Authorization
   Server: Rocket
   "###);
-  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds", "get", 200);
+  assert_json_snapshot!(body, @r###"
   {
     "entries": [],
     "total": 0
   }
   "###);
 }
+
+#[test]
+fn test_search_all_worlds() {
+  let service = ServiceWrapper::default();
+  seed_worlds(&service);
+
+  let req = service.get("/worlds?sort=name");
+  let mut response = req.dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Cache-Control: public, max-age=3600
+  Vary: Authorization
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds", "get", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "entries": [
+      {
+        "description": "A flat world on the back of a turtle",
+        "descriptionHtml": "<p>A flat world on the back of a turtle</p>\n",
+        "id": "1f1d4d4e-8e1b-4a3b-9d1e-34a0e7f5bd3c",
+        "name": "Discworld",
+        "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+        "slug": "discworld",
+        "stars": 0,
+        "visibility": "public"
+      },
+      {
+        "description": "Home of the Hobbits",
+        "descriptionHtml": "<p>Home of the Hobbits</p>\n",
+        "id": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+        "name": "Middle Earth",
+        "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+        "slug": "middle-earth",
+        "stars": 0,
+        "visibility": "public"
+      }
+    ],
+    "total": 2
+  }
+  "###);
+}
+
+#[test]
+fn test_search_worlds_by_keyword() {
+  let service = ServiceWrapper::default();
+  seed_worlds(&service);
+
+  let req = service.get("/worlds?keyword=hobbits");
+  let mut response = req.dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds", "get", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "entries": [
+      {
+        "description": "Home of the Hobbits",
+        "descriptionHtml": "<p>Home of the Hobbits</p>\n",
+        "id": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+        "name": "Middle Earth",
+        "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+        "slug": "middle-earth",
+        "stars": 0,
+        "visibility": "public"
+      }
+    ],
+    "total": 1
+  }
+  "###);
+}
+
+#[test]
+fn test_search_private_worlds() {
+  let service = ServiceWrapper::default();
+  let user = seed_worlds(&service);
+  let private_world = World {
+    world_id: uuid::Uuid::parse_str("0c0dd1e4-8e0f-4b39-9b06-7cb5b5e4b0a1").unwrap(),
+    owner_id: user.user_id,
+    name: "Narnia".to_owned(),
+    slug: "narnia".to_owned(),
+    description: "Through the wardrobe".to_owned(),
+    visibility: "private".to_owned(),
+    ..Default::default()
+  };
+  let unlisted_world = World {
+    world_id: uuid::Uuid::parse_str("5a0e5e9c-2b38-4c35-a1c3-1b0f3a2c1f10").unwrap(),
+    owner_id: user.user_id,
+    name: "Oz".to_owned(),
+    slug: "oz".to_owned(),
+    description: "Over the rainbow".to_owned(),
+    visibility: "unlisted".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&private_world, &unlisted_world]);
+
+  let req = service.get("/worlds?sort=name");
+  let mut response = req.dispatch();
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "entries": [
+      {
+        "description": "A flat world on the back of a turtle",
+        "descriptionHtml": "<p>A flat world on the back of a turtle</p>\n",
+        "id": "1f1d4d4e-8e1b-4a3b-9d1e-34a0e7f5bd3c",
+        "name": "Discworld",
+        "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+        "slug": "discworld",
+        "stars": 0,
+        "visibility": "public"
+      },
+      {
+        "description": "Home of the Hobbits",
+        "descriptionHtml": "<p>Home of the Hobbits</p>\n",
+        "id": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+        "name": "Middle Earth",
+        "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+        "slug": "middle-earth",
+        "stars": 0,
+        "visibility": "public"
+      }
+    ],
+    "total": 2
+  }
+  "###);
+
+  let req = service
+    .get("/worlds?sort=name")
+    .header(authenticate_user(&service, &user).unwrap());
+  let mut response = req.dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Cache-Control: private, max-age=3600
+  Vary: Authorization
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds", "get", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "entries": [
+      {
+        "description": "A flat world on the back of a turtle",
+        "descriptionHtml": "<p>A flat world on the back of a turtle</p>\n",
+        "id": "1f1d4d4e-8e1b-4a3b-9d1e-34a0e7f5bd3c",
+        "name": "Discworld",
+        "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+        "slug": "discworld",
+        "stars": 0,
+        "visibility": "public"
+      },
+      {
+        "description": "Home of the Hobbits",
+        "descriptionHtml": "<p>Home of the Hobbits</p>\n",
+        "id": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+        "name": "Middle Earth",
+        "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+        "slug": "middle-earth",
+        "stars": 0,
+        "visibility": "public"
+      },
+      {
+        "description": "Through the wardrobe",
+        "descriptionHtml": "<p>Through the wardrobe</p>\n",
+        "id": "0c0dd1e4-8e0f-4b39-9b06-7cb5b5e4b0a1",
+        "name": "Narnia",
+        "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+        "slug": "narnia",
+        "stars": 0,
+        "visibility": "private"
+      },
+      {
+        "description": "Over the rainbow",
+        "descriptionHtml": "<p>Over the rainbow</p>\n",
+        "id": "5a0e5e9c-2b38-4c35-a1c3-1b0f3a2c1f10",
+        "name": "Oz",
+        "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+        "slug": "oz",
+        "stars": 0,
+        "visibility": "unlisted"
+      }
+    ],
+    "total": 4
+  }
+  "###);
+}
+
+fn seed_tagged_worlds(service: &ServiceWrapper) {
+  let user = seed_worlds(service);
+  let other_user = User {
+    user_id: uuid::Uuid::parse_str("6d1b2a4e-0c4f-4b8e-a6a3-1f2f0fd8c3b7").unwrap(),
+    username: "otheruser".to_owned(),
+    email: "other@example.com".to_owned(),
+    display_name: "Other User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let worlds = vec![
+    World {
+      world_id: uuid::Uuid::parse_str("6a0b0c5e-1c3f-4f0e-8a9b-2d1e3f4a5b6c").unwrap(),
+      owner_id: user.user_id,
+      name: "Earthsea".to_owned(),
+      slug: "earthsea".to_owned(),
+      description: "An archipelago of wizards".to_owned(),
+      tags: vec!["fantasy".to_owned(), "magic".to_owned()],
+      ..Default::default()
+    },
+    World {
+      world_id: uuid::Uuid::parse_str("7b1c1d6f-2d4a-4a1f-9b0c-3e2f4a5b6c7d").unwrap(),
+      owner_id: other_user.user_id,
+      name: "Arrakis".to_owned(),
+      slug: "arrakis".to_owned(),
+      description: "A desert planet".to_owned(),
+      tags: vec!["science fiction".to_owned()],
+      ..Default::default()
+    },
+    World {
+      world_id: uuid::Uuid::parse_str("8c2d2e7a-3e5b-4b2a-8c1d-4f3a5b6c7d8e").unwrap(),
+      owner_id: other_user.user_id,
+      name: "Roshar".to_owned(),
+      slug: "roshar".to_owned(),
+      description: "A world of storms".to_owned(),
+      tags: vec!["fantasy".to_owned()],
+      ..Default::default()
+    },
+  ];
+  seed(
+    service.database(),
+    vec![&other_user, &worlds[0], &worlds[1], &worlds[2]],
+  );
+}
+
+#[test]
+fn test_search_worlds_by_any_tags() {
+  let service = ServiceWrapper::default();
+  seed_tagged_worlds(&service);
+
+  let req = service.get("/worlds?tags=Magic,science%20fiction&sort=name");
+  let mut response = req.dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds", "get", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "entries": [
+      {
+        "description": "A desert planet",
+        "descriptionHtml": "<p>A desert planet</p>\n",
+        "id": "7b1c1d6f-2d4a-4a1f-9b0c-3e2f4a5b6c7d",
+        "name": "Arrakis",
+        "owner": "6d1b2a4e-0c4f-4b8e-a6a3-1f2f0fd8c3b7",
+        "slug": "arrakis",
+        "stars": 0,
+        "tags": [
+          "science fiction"
+        ],
+        "visibility": "public"
+      },
+      {
+        "description": "An archipelago of wizards",
+        "descriptionHtml": "<p>An archipelago of wizards</p>\n",
+        "id": "6a0b0c5e-1c3f-4f0e-8a9b-2d1e3f4a5b6c",
+        "name": "Earthsea",
+        "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+        "slug": "earthsea",
+        "stars": 0,
+        "tags": [
+          "fantasy",
+          "magic"
+        ],
+        "visibility": "public"
+      }
+    ],
+    "total": 2
+  }
+  "###);
+}
+
+#[test]
+fn test_search_worlds_by_all_tags() {
+  let service = ServiceWrapper::default();
+  seed_tagged_worlds(&service);
+
+  let req = service.get("/worlds?tags=fantasy,magic&tag_match=all");
+  let mut response = req.dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds", "get", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "entries": [
+      {
+        "description": "An archipelago of wizards",
+        "descriptionHtml": "<p>An archipelago of wizards</p>\n",
+        "id": "6a0b0c5e-1c3f-4f0e-8a9b-2d1e3f4a5b6c",
+        "name": "Earthsea",
+        "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+        "slug": "earthsea",
+        "stars": 0,
+        "tags": [
+          "fantasy",
+          "magic"
+        ],
+        "visibility": "public"
+      }
+    ],
+    "total": 1
+  }
+  "###);
+}
+
+#[test]
+fn test_search_worlds_unknown_tag_match() {
+  let service = ServiceWrapper::default();
+
+  let req = service.get("/worlds?tags=fantasy&tag_match=some");
+  let mut response = req.dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "tag_match",
+        "title": "The tag match must be one of any or all",
+        "type": "tag:universe,2020:worlds/validation-errors/tag_match/unknown"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_search_worlds_with_facets() {
+  let service = ServiceWrapper::default();
+  seed_tagged_worlds(&service);
+
+  let req = service.get("/worlds?facets=tags,owners&limit=1&sort=name");
+  let mut response = req.dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds", "get", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "entries": [
+      {
+        "description": "A desert planet",
+        "descriptionHtml": "<p>A desert planet</p>\n",
+        "id": "7b1c1d6f-2d4a-4a1f-9b0c-3e2f4a5b6c7d",
+        "name": "Arrakis",
+        "owner": "6d1b2a4e-0c4f-4b8e-a6a3-1f2f0fd8c3b7",
+        "slug": "arrakis",
+        "stars": 0,
+        "tags": [
+          "science fiction"
+        ],
+        "visibility": "public"
+      }
+    ],
+    "facets": {
+      "owners": [
+        {
+          "count": 3,
+          "value": "2fcc3850-bb9b-405e-bbab-22978283fef8"
+        },
+        {
+          "count": 2,
+          "value": "6d1b2a4e-0c4f-4b8e-a6a3-1f2f0fd8c3b7"
+        }
+      ],
+      "tags": [
+        {
+          "count": 2,
+          "value": "fantasy"
+        },
+        {
+          "count": 1,
+          "value": "magic"
+        },
+        {
+          "count": 1,
+          "value": "science fiction"
+        }
+      ]
+    },
+    "total": 5
+  }
+  "###);
+}
+
+#[test]
+fn test_search_worlds_unknown_facets() {
+  let service = ServiceWrapper::default();
+
+  let req = service.get("/worlds?facets=tags,colours");
+  let mut response = req.dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 422,
+    "title": "Unknown Facets requested",
+    "type": "tag:universe,2020:worlds/problems/unknown-facets",
+    "unknownFacets": [
+      "colours"
+    ]
+  }
+  "###);
+}
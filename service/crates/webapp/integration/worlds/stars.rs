@@ -0,0 +1,346 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use test_env_log::test;
+use universe_testdata::{seed, User, World, WorldStar};
+
+fn seed_world(service: &ServiceWrapper, visibility: &str) -> (User, World) {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    description: "This is a test world".to_owned(),
+    visibility: visibility.to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&user, &world]);
+
+  (user, world)
+}
+
+fn seed_other_user(service: &ServiceWrapper) -> User {
+  let other_user = User {
+    user_id: uuid::Uuid::parse_str("3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90").unwrap(),
+    username: "otheruser".to_owned(),
+    email: "other@example.com".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&other_user]);
+
+  other_user
+}
+
+#[test]
+fn test_star_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_world(&service, "public");
+
+  let response = service
+    .put("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/star")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+
+  "###);
+}
+
+#[test]
+fn test_star_unknown_world() {
+  let service = ServiceWrapper::default();
+  let other_user = seed_other_user(&service);
+
+  let mut response = service
+    .put("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/star")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested world could not be found",
+    "type": "tag:universe,2020:worlds/problems/unknown-world"
+  }
+  "###);
+}
+
+#[test]
+fn test_star_private_world() {
+  let service = ServiceWrapper::default();
+  seed_world(&service, "private");
+  let other_user = seed_other_user(&service);
+
+  let response = service
+    .put("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/star")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+
+  "###);
+}
+
+#[test]
+fn test_star_world() {
+  let service = ServiceWrapper::default();
+  seed_world(&service, "public");
+  let other_user = seed_other_user(&service);
+
+  let original_etag = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .dispatch()
+    .headers()
+    .get_one("ETag")
+    .unwrap()
+    .to_owned();
+
+  // Starring twice only counts once
+  for _ in 0..2 {
+    let response = service
+      .put("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/star")
+      .header(authenticate_user(&service, &other_user).unwrap())
+      .dispatch();
+
+    assert_snapshot!(build_headers(&response), @r###"
+    HTTP/1.1 204 No Content.
+    Server: Rocket
+
+    "###);
+  }
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .dispatch();
+
+  // The star count changed, so anything cached from before is out of date
+  assert_ne!(response.headers().get_one("ETag"), Some(original_etag.as_str()));
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}", "get", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "description": "This is a test world",
+    "descriptionHtml": "<p>This is a test world</p>\n",
+    "id": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+    "name": "Test World",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "slug": "test-world",
+    "stars": 1,
+    "visibility": "public"
+  }
+  "###);
+}
+
+#[test]
+fn test_unstar_world() {
+  let service = ServiceWrapper::default();
+  seed_world(&service, "public");
+  let other_user = seed_other_user(&service);
+  seed(
+    service.database(),
+    vec![&WorldStar {
+      world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+      user_id: other_user.user_id,
+      ..Default::default()
+    }],
+  );
+
+  // Unstarring a world that isn't starred any more is fine
+  for _ in 0..2 {
+    let response = service
+      .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/star")
+      .header(authenticate_user(&service, &other_user).unwrap())
+      .dispatch();
+
+    assert_snapshot!(build_headers(&response), @r###"
+    HTTP/1.1 204 No Content.
+    Server: Rocket
+
+    "###);
+  }
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_json_snapshot!(body["stars"], @r###"
+  0
+  "###);
+}
+
+#[test]
+fn test_list_starred_worlds_wrong_user() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service, "public");
+  seed_other_user(&service);
+
+  let response = service
+    .get("/users/3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90/stars")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 403 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_list_starred_worlds() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service, "public");
+  let other_user = seed_other_user(&service);
+  let private_world = World {
+    world_id: uuid::Uuid::parse_str("83c60ad3-2a4f-455b-b685-c16da785bf6e").unwrap(),
+    owner_id: user.user_id,
+    name: "Private World".to_owned(),
+    slug: "private-world".to_owned(),
+    visibility: "private".to_owned(),
+    ..Default::default()
+  };
+  seed(
+    service.database(),
+    vec![
+      &private_world,
+      &WorldStar {
+        world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+        user_id: other_user.user_id,
+        ..Default::default()
+      },
+      &WorldStar {
+        world_id: private_world.world_id,
+        user_id: other_user.user_id,
+        ..Default::default()
+      },
+    ],
+  );
+
+  let mut response = service
+    .get("/users/3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90/stars")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Cache-Control: private, no-cache
+  Server: Rocket
+
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/users/{userId}/stars", "get", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "entries": [
+      {
+        "description": "This is a test world",
+        "descriptionHtml": "<p>This is a test world</p>\n",
+        "id": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+        "name": "Test World",
+        "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+        "slug": "test-world",
+        "stars": 1,
+        "visibility": "public"
+      }
+    ],
+    "total": 1
+  }
+  "###);
+}
+
+#[test]
+fn test_search_by_popularity() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service, "public");
+  let other_user = seed_other_user(&service);
+  let popular_world = World {
+    world_id: uuid::Uuid::parse_str("83c60ad3-2a4f-455b-b685-c16da785bf6e").unwrap(),
+    owner_id: user.user_id,
+    name: "Popular World".to_owned(),
+    slug: "popular-world".to_owned(),
+    ..Default::default()
+  };
+  seed(
+    service.database(),
+    vec![
+      &popular_world,
+      &WorldStar {
+        world_id: popular_world.world_id,
+        user_id: user.user_id,
+        ..Default::default()
+      },
+      &WorldStar {
+        world_id: popular_world.world_id,
+        user_id: other_user.user_id,
+        ..Default::default()
+      },
+    ],
+  );
+
+  let mut response = service.get("/worlds?sort=-popularity").dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds", "get", 200);
+  assert_json_snapshot!(popularity(&body), @r###"
+  [
+    [
+      "Popular World",
+      2
+    ],
+    [
+      "Test World",
+      0
+    ]
+  ]
+  "###);
+
+  let mut response = service.get("/worlds?sort=%2Bpopularity").dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds", "get", 200);
+  assert_json_snapshot!(popularity(&body), @r###"
+  [
+    [
+      "Test World",
+      0
+    ],
+    [
+      "Popular World",
+      2
+    ]
+  ]
+  "###);
+}
+
+fn popularity(body: &serde_json::Value) -> Vec<(serde_json::Value, serde_json::Value)> {
+  body["entries"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|world| (world["name"].clone(), world["stars"].clone()))
+    .collect()
+}
@@ -0,0 +1,543 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use rocket::http::ContentType;
+use serde_json::json;
+use test_env_log::test;
+use universe_testdata::{seed, User, World, WorldMember, WorldTransfer};
+
+fn seed_world(service: &ServiceWrapper) -> (User, World) {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    description: "This is a test world".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&user, &world]);
+
+  (user, world)
+}
+
+fn seed_other_user(service: &ServiceWrapper) -> User {
+  let other_user = User {
+    user_id: uuid::Uuid::parse_str("3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90").unwrap(),
+    username: "otheruser".to_owned(),
+    email: "other@example.com".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&other_user]);
+
+  other_user
+}
+
+fn seed_transfer(service: &ServiceWrapper, from: &User, to: &User) {
+  seed(
+    service.database(),
+    vec![&WorldTransfer {
+      world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+      from_user_id: from.user_id,
+      to_user_id: to.user_id,
+      created: "2020-03-11T13:00:36Z".parse().unwrap(),
+    }],
+  );
+}
+
+#[test]
+fn test_offer_transfer_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+
+  let response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/transfer")
+    .header(ContentType::JSON)
+    .body(json!({ "to": "3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_offer_transfer_as_owner_member() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+  let other_user = seed_other_user(&service);
+  seed(
+    service.database(),
+    vec![&WorldMember {
+      world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+      user_id: other_user.user_id,
+      role: "owner".to_owned(),
+      ..Default::default()
+    }],
+  );
+
+  let response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/transfer")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .body(json!({ "to": "3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 403 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_offer_transfer() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+  seed_other_user(&service);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/transfer")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "to": "3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Cache-Control: private, no-cache
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/transfer", "post", 200);
+  assert_json_snapshot!(body, {
+        ".created" => "[timestamp]",
+    },
+    @r###"
+  {
+    "created": "[timestamp]",
+    "from": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "to": "3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_offer_transfer_missing_user() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/transfer")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({}).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "to",
+        "title": "Required field was missing a value",
+        "type": "tag:universe,2020:validation-errors/missing"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_offer_transfer_unknown_user() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/transfer")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "to": "3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 422 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "to",
+        "title": "The user could not be found",
+        "type": "tag:universe,2020:worlds/validation-errors/to/unknown"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_offer_transfer_to_current_owner() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/transfer")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "to": "2fcc3850-bb9b-405e-bbab-22978283fef8" }).to_string())
+    .dispatch();
+
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "errors": [
+      {
+        "field": "to",
+        "title": "The user already owns the world",
+        "type": "tag:universe,2020:worlds/validation-errors/to/current-owner"
+      }
+    ],
+    "status": 422,
+    "title": "The input had validation errors",
+    "type": "tag:universe,2020:problems/validation-error"
+  }
+  "###);
+}
+
+#[test]
+fn test_offer_transfer_slug_conflict() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+  let other_user = seed_other_user(&service);
+  seed(
+    service.database(),
+    vec![&World {
+      owner_id: other_user.user_id,
+      slug: "test-world".to_owned(),
+      ..Default::default()
+    }],
+  );
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/transfer")
+    .header(ContentType::JSON)
+    .header(authenticate_user(&service, &user).unwrap())
+    .body(json!({ "to": "3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90" }).to_string())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 409 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 409,
+    "title": "The new owner already has a world with the same slug",
+    "type": "tag:universe,2020:worlds/problems/transfer-slug-conflict"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_transfer_as_recipient() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+  let other_user = seed_other_user(&service);
+  seed_transfer(&service, &user, &other_user);
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/transfer")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Cache-Control: private, no-cache
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/transfer", "get", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "created": "2020-03-11T13:00:36Z",
+    "from": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "to": "3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90",
+    "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+  }
+  "###);
+}
+
+#[test]
+fn test_get_no_transfer() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+
+  let mut response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/transfer")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The world is not being transferred",
+    "type": "tag:universe,2020:worlds/problems/unknown-transfer"
+  }
+  "###);
+}
+
+#[test]
+fn test_list_incoming_transfers() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+  let other_user = seed_other_user(&service);
+  seed_transfer(&service, &user, &other_user);
+
+  let mut response = service
+    .get("/users/3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90/transfers")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Cache-Control: private, no-cache
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/users/{userId}/transfers", "get", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "entries": [
+      {
+        "created": "2020-03-11T13:00:36Z",
+        "from": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+        "to": "3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90",
+        "world": "fcc07c44-3fd3-4834-8f33-edf5e87779d8"
+      }
+    ],
+    "total": 1
+  }
+  "###);
+}
+
+#[test]
+fn test_list_incoming_transfers_other_user() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+  seed_other_user(&service);
+
+  let response = service
+    .get("/users/3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90/transfers")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 403 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_accept_transfer() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+  let other_user = seed_other_user(&service);
+  seed_transfer(&service, &user, &other_user);
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/transfer/accept")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/worlds/{worldId}/transfer/accept", "post", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "description": "This is a test world",
+    "descriptionHtml": "<p>This is a test world</p>\n",
+    "id": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+    "name": "Test World",
+    "owner": "3a8f5e2c-7c1d-4b0e-9f6a-2d4c8b1e6f90",
+    "slug": "test-world",
+    "stars": 0,
+    "visibility": "public"
+  }
+  "###);
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/transfer")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+
+  let response = service.get("/users/otheruser/worlds/test-world").dispatch();
+  assert_eq!(response.status().code, 200);
+}
+
+#[test]
+fn test_get_world_by_previous_owner_after_transfer() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+  let other_user = seed_other_user(&service);
+  seed_transfer(&service, &user, &other_user);
+
+  let response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/transfer/accept")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+  assert_eq!(response.status().code, 200);
+
+  let response = service.get("/users/testuser/worlds/test-world").dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 301 Moved Permanently.
+  Location: /users/otheruser/worlds/test-world
+  Link: </worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8>; rel="canonical"
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_accept_transfer_as_owner() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+  let other_user = seed_other_user(&service);
+  seed_transfer(&service, &user, &other_user);
+
+  let response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/transfer/accept")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 403 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_accept_transfer_slug_conflict() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+  let other_user = seed_other_user(&service);
+  seed_transfer(&service, &user, &other_user);
+  // The recipient created a world with the same slug after the offer was made
+  seed(
+    service.database(),
+    vec![&World {
+      owner_id: other_user.user_id,
+      slug: "test-world".to_owned(),
+      ..Default::default()
+    }],
+  );
+
+  let mut response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/transfer/accept")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 409 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 409,
+    "title": "The new owner already has a world with the same slug",
+    "type": "tag:universe,2020:worlds/problems/transfer-slug-conflict"
+  }
+  "###);
+}
+
+#[test]
+fn test_decline_transfer() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+  let other_user = seed_other_user(&service);
+  seed_transfer(&service, &user, &other_user);
+
+  let response = service
+    .post("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/transfer/decline")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 204 No Content.
+  Server: Rocket
+  "###);
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/transfer")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_cancel_transfer() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+  let other_user = seed_other_user(&service);
+  seed_transfer(&service, &user, &other_user);
+
+  let response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/transfer")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 403 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+
+  let response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8/transfer")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 204 No Content.
+  Server: Rocket
+  "###);
+}
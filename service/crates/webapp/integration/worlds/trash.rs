@@ -0,0 +1,328 @@
+use crate::{
+  assert_response_schema, authenticate_user, build_headers, build_json_body, ServiceWrapper,
+};
+use insta::{assert_json_snapshot, assert_snapshot};
+use test_env_log::test;
+use universe_testdata::{seed, User, World};
+
+fn seed_world(service: &ServiceWrapper) -> (User, World) {
+  let user = User {
+    user_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+    username: "testuser".to_owned(),
+    email: "testing@example.com".to_owned(),
+    display_name: "Test User".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  let world = World {
+    world_id: uuid::Uuid::parse_str("fcc07c44-3fd3-4834-8f33-edf5e87779d8").unwrap(),
+    owner_id: user.user_id,
+    name: "Test World".to_owned(),
+    slug: "test-world".to_owned(),
+    description: "This is a test world".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&user, &world]);
+
+  (user, world)
+}
+
+fn seed_other_user(service: &ServiceWrapper) -> User {
+  let other_user = User {
+    username: "otheruser".to_owned(),
+    email: "other@example.com".to_owned(),
+    password: "Pa55word".to_owned(),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&other_user]);
+
+  other_user
+}
+
+#[test]
+fn test_delete_unauthorized() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+
+  let response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 401 Unauthorized.
+  Content-Type: text/html; charset=utf-8
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_delete_wrong_user() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+  let other_user = seed_other_user(&service);
+
+  let mut response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 403 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 403,
+    "title": "You are not permitted to perform this request",
+    "type": "tag:universe,2020:problems/authentication/forbidden"
+  }
+  "###);
+}
+
+#[test]
+fn test_delete_private_world_wrong_user() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+  let other_user = seed_other_user(&service);
+  seed(
+    service.database(),
+    vec![&World {
+      world_id: uuid::Uuid::parse_str("5d6c1b2a-8e4f-4a3b-9c7d-2e1f0a9b8c7d").unwrap(),
+      owner_id: uuid::Uuid::parse_str("2fcc3850-bb9b-405e-bbab-22978283fef8").unwrap(),
+      name: "Secret World".to_owned(),
+      slug: "secret-world".to_owned(),
+      visibility: "private".to_owned(),
+      ..Default::default()
+    }],
+  );
+
+  let mut response = service
+    .delete("/worlds/5d6c1b2a-8e4f-4a3b-9c7d-2e1f0a9b8c7d")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested world could not be found",
+    "type": "tag:universe,2020:worlds/problems/unknown-world"
+  }
+  "###);
+}
+
+#[test]
+fn test_delete_unknown_world() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+
+  let mut response = service
+    .delete("/worlds/83c60ad3-2a4f-455b-b685-c16da785bf6e")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested world could not be found",
+    "type": "tag:universe,2020:worlds/problems/unknown-world"
+  }
+  "###);
+}
+
+#[test]
+fn test_delete_world() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+
+  let response = service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 204 No Content.
+  Server: Rocket
+  "###);
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .dispatch();
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+
+  let mut response = service.get("/worlds").dispatch();
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "entries": [],
+    "total": 0
+  }
+  "###);
+}
+
+#[test]
+fn test_list_trash_wrong_user() {
+  let service = ServiceWrapper::default();
+  seed_world(&service);
+  let other_user = seed_other_user(&service);
+
+  let response = service
+    .get("/users/2fcc3850-bb9b-405e-bbab-22978283fef8/trash")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 403 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
+
+#[test]
+fn test_list_trash() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+  let trashed = World {
+    world_id: uuid::Uuid::parse_str("4fc6b1e5-4a63-4f4b-9a2c-8a4b6b4fd6a7").unwrap(),
+    owner_id: user.user_id,
+    name: "Atlantis".to_owned(),
+    slug: "atlantis".to_owned(),
+    description: "Lost beneath the waves".to_owned(),
+    deleted: Some("2020-03-11T13:00:36Z".parse().unwrap()),
+    ..Default::default()
+  };
+  seed(service.database(), vec![&trashed]);
+
+  let mut response = service
+    .get("/users/2fcc3850-bb9b-405e-bbab-22978283fef8/trash")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 200 OK.
+  Content-Type: application/json
+  Cache-Control: private, no-cache
+  Server: Rocket
+  "###);
+  let body = build_json_body(&mut response);
+  assert_response_schema(&body, "/users/{userId}/trash", "get", 200);
+  assert_json_snapshot!(body, @r###"
+  {
+    "entries": [
+      {
+        "deleted": "2020-03-11T13:00:36Z",
+        "description": "Lost beneath the waves",
+        "descriptionHtml": "<p>Lost beneath the waves</p>\n",
+        "id": "4fc6b1e5-4a63-4f4b-9a2c-8a4b6b4fd6a7",
+        "name": "Atlantis",
+        "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+        "slug": "atlantis",
+        "stars": 0,
+        "visibility": "public"
+      }
+    ],
+    "total": 1
+  }
+  "###);
+}
+
+#[test]
+fn test_restore_world() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+
+  service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  let mut response = service
+    .post("/users/2fcc3850-bb9b-405e-bbab-22978283fef8/trash/fcc07c44-3fd3-4834-8f33-edf5e87779d8/restore")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  let body = build_json_body(&mut response);
+  assert_response_schema(
+    &body,
+    "/users/{userId}/trash/{worldId}/restore",
+    "post",
+    200,
+  );
+  assert_json_snapshot!(body, @r###"
+  {
+    "description": "This is a test world",
+    "descriptionHtml": "<p>This is a test world</p>\n",
+    "id": "fcc07c44-3fd3-4834-8f33-edf5e87779d8",
+    "name": "Test World",
+    "owner": "2fcc3850-bb9b-405e-bbab-22978283fef8",
+    "slug": "test-world",
+    "stars": 0,
+    "visibility": "public"
+  }
+  "###);
+
+  let response = service
+    .get("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .dispatch();
+  assert_snapshot!(build_headers(&response).lines().next().unwrap(), @"HTTP/1.1 200 OK.");
+}
+
+#[test]
+fn test_restore_world_not_in_trash() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+
+  let mut response = service
+    .post("/users/2fcc3850-bb9b-405e-bbab-22978283fef8/trash/fcc07c44-3fd3-4834-8f33-edf5e87779d8/restore")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 404 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+  assert_json_snapshot!(build_json_body(&mut response), @r###"
+  {
+    "status": 404,
+    "title": "The requested world could not be found",
+    "type": "tag:universe,2020:worlds/problems/unknown-world"
+  }
+  "###);
+}
+
+#[test]
+fn test_restore_world_wrong_user() {
+  let service = ServiceWrapper::default();
+  let (user, _) = seed_world(&service);
+  let other_user = seed_other_user(&service);
+
+  service
+    .delete("/worlds/fcc07c44-3fd3-4834-8f33-edf5e87779d8")
+    .header(authenticate_user(&service, &user).unwrap())
+    .dispatch();
+
+  let response = service
+    .post("/users/2fcc3850-bb9b-405e-bbab-22978283fef8/trash/fcc07c44-3fd3-4834-8f33-edf5e87779d8/restore")
+    .header(authenticate_user(&service, &other_user).unwrap())
+    .dispatch();
+
+  assert_snapshot!(build_headers(&response), @r###"
+  HTTP/1.1 403 .
+  Content-Type: application/problem+json
+  Server: Rocket
+  "###);
+}
@@ -0,0 +1,25 @@
+use super::world::parse_article_id;
+use crate::problem::Problem;
+use crate::worlds::access::find_editable_world;
+use crate::{authentication::Authorizer, request_id::RequestId};
+use rocket::{delete, http::Status, State};
+use universe_articles::ArticleService;
+use universe_worlds::WorldService;
+
+#[delete("/worlds/<world_id>/articles/<article_id>")]
+#[tracing::instrument(skip(world_service, article_service, authorizer))]
+pub fn delete_article(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  article_id: String,
+  world_service: State<Box<dyn WorldService>>,
+  article_service: State<Box<dyn ArticleService>>,
+) -> Result<Status, Problem> {
+  let world = find_editable_world(&**world_service, &authorizer, world_id)?;
+  let article_id = parse_article_id(article_id)?;
+
+  article_service.delete_article(&world.identity.id, &article_id)?;
+
+  Ok(Status::NoContent)
+}
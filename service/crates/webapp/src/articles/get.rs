@@ -0,0 +1,64 @@
+use super::model::{Article, ArticlePage};
+use super::problems::unknown_parent_error;
+use super::world::find_article;
+use crate::authentication::ApiAccessToken;
+use crate::problem::{validation_error, Problem};
+use crate::request_id::RequestId;
+use crate::worlds::access::find_visible_world;
+use rocket::{get, State};
+use tracing::warn;
+use universe_articles::{ArticleParentFilter, ArticleService};
+use universe_entity::Pagination;
+use universe_worlds::WorldService;
+
+#[allow(clippy::too_many_arguments)]
+#[get("/worlds/<world_id>/articles?<parent>&<offset>&<limit>")]
+#[tracing::instrument(skip(world_service, article_service))]
+pub fn list_articles(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  parent: Option<String>,
+  offset: Option<u32>,
+  limit: Option<u32>,
+  world_service: State<Box<dyn WorldService>>,
+  article_service: State<Box<dyn ArticleService>>,
+) -> Result<ArticlePage, Problem> {
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+
+  // "none" lists the articles at the top of the tree. Otherwise we list those under the given one
+  let parent = match parent.as_deref() {
+    None => None,
+    Some("none") => Some(ArticleParentFilter::TopLevel),
+    Some(parent) => Some(ArticleParentFilter::Children(parent.parse().map_err(
+      |e| {
+        warn!("Invalid Article ID: {}", e);
+        validation_error(vec![unknown_parent_error()])
+      },
+    )?)),
+  };
+
+  let pagination = Pagination {
+    offset: offset.unwrap_or(0),
+    limit: limit.unwrap_or(10),
+  };
+
+  let articles = article_service.list_articles(&world.identity.id, parent, pagination);
+  Ok(ArticlePage::new(articles, &world))
+}
+
+#[get("/worlds/<world_id>/articles/<article_id>")]
+#[tracing::instrument(skip(world_service, article_service))]
+pub fn get_article(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  article_id: String,
+  world_service: State<Box<dyn WorldService>>,
+  article_service: State<Box<dyn ArticleService>>,
+) -> Result<Article, Problem> {
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+  let article = find_article(&**article_service, &world, article_id)?;
+
+  Ok(Article::new(&article, &world))
+}
@@ -0,0 +1,10 @@
+mod delete;
+mod get;
+mod model;
+mod patch;
+mod post;
+mod problems;
+mod routes;
+mod world;
+
+pub use routes::routes;
@@ -0,0 +1,161 @@
+use crate::headers::*;
+use crate::page::SearchPage;
+use chrono::{DateTime, Utc};
+use rocket::{
+  http::{
+    hyper::header::{CacheControl, CacheDirective, ETag, EntityTag, HttpDate, LastModified},
+    Status,
+  },
+  response::{Responder, Response},
+  Request,
+};
+use rocket_contrib::json::Json;
+use serde::Serialize;
+use universe_articles::{ArticleEntity, ArticleID};
+use universe_entity::Page;
+use universe_markdown::render_html;
+use universe_worlds::{Slug, Visibility, WorldEntity, WorldID};
+use uuid::Uuid;
+
+/// Representation of an Article to return over the API
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Article {
+  pub id: ArticleID,
+  pub world: WorldID,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub parent: Option<ArticleID>,
+  pub title: String,
+  pub slug: Slug,
+  /// The body of the article, as the CommonMark source that the user provided
+  pub body: String,
+  /// The body of the article, rendered to sanitized HTML
+  pub body_html: String,
+  #[serde(skip_serializing)]
+  version: Uuid,
+  #[serde(skip_serializing)]
+  updated: DateTime<Utc>,
+  #[serde(skip_serializing)]
+  visibility: Visibility,
+}
+
+impl Article {
+  /// Build the representation of an article of a world
+  ///
+  /// # Arguments
+  /// * `article` The article
+  /// * `world` The world that the article belongs to
+  pub fn new(article: &ArticleEntity, world: &WorldEntity) -> Self {
+    Article {
+      id: article.identity.id.clone(),
+      world: article.data.world.clone(),
+      parent: article.data.parent.clone(),
+      title: article.data.title.clone(),
+      slug: article.data.slug.clone(),
+      body: article.data.body.clone(),
+      body_html: render_html(&article.data.body),
+      version: article.identity.version,
+      updated: article.identity.updated,
+      visibility: world.data.visibility,
+    }
+  }
+}
+
+/// Work out who may cache a response about the articles of a world
+fn cache_scope(visibility: Visibility) -> CacheDirective {
+  // Articles of private worlds must never be stored in a shared cache
+  match visibility {
+    Visibility::Private => CacheDirective::Private,
+    _ => CacheDirective::Public,
+  }
+}
+
+impl<'a> Responder<'a> for Article {
+  /// Generate a Rocket response for the Article
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(
+        Link::from_href(format!("/worlds/{}/articles/{}", self.world, self.id)).with_rel("self"),
+      )
+      .header(AcceptPatch("application/merge-patch+json"))
+      .header(ETag(EntityTag::new(false, self.version.to_string())))
+      .header(LastModified(HttpDate(time::at_utc(time::Timespec::new(
+        self.updated.timestamp(),
+        0,
+      )))))
+      .header(CacheControl(vec![
+        cache_scope(self.visibility),
+        CacheDirective::MaxAge(3600),
+      ]))
+      .ok()
+  }
+}
+
+/// Representation of an Article that has just been created
+#[derive(Debug, Serialize)]
+pub struct CreatedArticle {
+  #[serde(flatten)]
+  pub article: Article,
+}
+
+impl<'a> Responder<'a> for CreatedArticle {
+  /// Generate a Rocket response for the newly created Article
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(
+        Link::from_href(format!(
+          "/worlds/{}/articles/{}",
+          self.article.world, self.article.id
+        ))
+        .with_rel("canonical"),
+      )
+      .ok()
+  }
+}
+
+/// Representation of a page of the Articles of a World
+#[derive(Debug, Serialize)]
+pub struct ArticlePage {
+  #[serde(flatten)]
+  pub page: SearchPage<Article>,
+  #[serde(skip_serializing)]
+  visibility: Visibility,
+}
+
+impl ArticlePage {
+  /// Build the representation of a page of the articles of a world
+  ///
+  /// # Arguments
+  /// * `articles` The page of articles
+  /// * `world` The world that the articles belong to
+  pub fn new(articles: Page<ArticleEntity>, world: &WorldEntity) -> Self {
+    ArticlePage {
+      page: SearchPage {
+        entries: articles
+          .entries
+          .iter()
+          .map(|article| Article::new(article, world))
+          .collect(),
+        total: articles.total,
+      },
+      visibility: world.data.visibility,
+    }
+  }
+}
+
+impl<'a> Responder<'a> for ArticlePage {
+  /// Generate a Rocket response for the page of Articles
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(CacheControl(vec![
+        cache_scope(self.visibility),
+        CacheDirective::MaxAge(3600),
+      ]))
+      // Members of private worlds can see articles that nobody else can
+      .raw_header("Vary", "Authorization")
+      .ok()
+  }
+}
@@ -0,0 +1,124 @@
+use super::model::Article;
+use super::problems::unknown_parent_error;
+use super::world::parse_article_id;
+use crate::problem::{missing_error, Problem, ValidationError, ValidationErrors};
+use crate::worlds::access::find_editable_world;
+use crate::{authentication::Authorizer, request_id::RequestId};
+use rocket::{patch, State};
+use rocket_contrib::json::Json;
+use serde::{Deserialize, Deserializer};
+use tracing::{debug, warn};
+use universe_articles::{ArticleID, ArticleService};
+use universe_worlds::{Slug, SlugParseError, WorldService};
+
+#[patch(
+  "/worlds/<world_id>/articles/<article_id>",
+  format = "application/merge-patch+json",
+  data = "<patch_data>"
+)]
+#[tracing::instrument(skip(world_service, article_service, authorizer))]
+pub fn update_article(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  article_id: String,
+  patch_data: Json<PatchData>,
+  world_service: State<Box<dyn WorldService>>,
+  article_service: State<Box<dyn ArticleService>>,
+) -> Result<Article, Problem> {
+  debug!("Patch Data: {:?}", patch_data);
+
+  let world = find_editable_world(&**world_service, &authorizer, world_id)?;
+  let article_id = parse_article_id(article_id)?;
+
+  let article =
+    article_service.update_article(&world.identity.id, &article_id, &mut |mut article| {
+      debug!("Patching article details");
+
+      let title_error = patch_data.title.and_then(|title| {
+        let title = title.trim();
+        if title.is_empty() {
+          Some(missing_error("title"))
+        } else {
+          article.title = title.to_owned();
+          None
+        }
+      });
+
+      let slug_error = patch_data.slug.and_then(|slug| {
+        slug
+          .parse()
+          .map_err(|e: SlugParseError| e.into())
+          .map(|slug: Slug| {
+            article.slug = slug;
+          })
+          .err()
+      });
+
+      // A parent of `null` moves the article to the top of the tree
+      let parent_error = patch_data.parent.and_then(|parent| match parent {
+        NewParent::TopLevel => {
+          article.parent = None;
+          None
+        }
+        NewParent::Article(parent) => parent
+          .parse()
+          .map_err(|_| unknown_parent_error())
+          .map(|parent: ArticleID| {
+            article.parent = Some(parent);
+          })
+          .err(),
+      });
+
+      if let Some(body) = &patch_data.body {
+        article.body = body.trim().to_owned();
+      }
+
+      match (title_error, slug_error, parent_error) {
+        (None, None, None) => Ok(article),
+        (title, slug, parent) => {
+          let errors: Vec<ValidationError> = vec![title, slug, parent]
+            .into_iter()
+            .filter_map(|v| v)
+            .collect();
+          warn!("Error patching article: {:?}", errors);
+          Err(Box::new(ValidationErrors { errors }))
+        }
+      }
+    })?;
+
+  Ok(Article::new(&article, &world))
+}
+
+/// Struct representing the input data for updating an article
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchData<'a> {
+  pub title: Option<&'a str>,
+  pub slug: Option<&'a str>,
+  pub body: Option<String>,
+  #[serde(default, borrow, deserialize_with = "deserialize_parent")]
+  pub parent: Option<NewParent<'a>>,
+}
+
+/// Where in the tree of articles an article is to be moved to
+#[derive(Debug, Clone, Copy)]
+pub enum NewParent<'a> {
+  /// The article was given an explicit `null` parent, so is to be moved to the top of the tree
+  TopLevel,
+  /// The article is to be moved under the article with this ID
+  Article(&'a str),
+}
+
+/// Deserialize the parent of an article, so that an explicit `null` can be told apart from the
+/// field being missing
+fn deserialize_parent<'de: 'a, 'a, D>(deserializer: D) -> Result<Option<NewParent<'a>>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let parent: Option<&'a str> = Deserialize::deserialize(deserializer)?;
+  Ok(Some(match parent {
+    None => NewParent::TopLevel,
+    Some(parent) => NewParent::Article(parent),
+  }))
+}
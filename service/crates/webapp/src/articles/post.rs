@@ -0,0 +1,99 @@
+use super::model::{Article, CreatedArticle};
+use super::problems::unknown_parent_error;
+use crate::authentication::Authorizer;
+use crate::problem::{missing_error, Problem, ValidationError};
+use crate::request_id::RequestId;
+use crate::worlds::access::find_editable_world;
+use rocket::{post, State};
+use rocket_contrib::json::Json;
+use serde::Deserialize;
+use tracing::debug;
+use universe_articles::{ArticleData, ArticleID, ArticleService};
+use universe_worlds::{Slug, SlugParseError, WorldID, WorldService};
+
+#[post("/worlds/<world_id>/articles", data = "<new_article>")]
+#[tracing::instrument(skip(world_service, article_service, authorizer))]
+pub fn create_article(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  new_article: Json<NewArticle>,
+  world_service: State<Box<dyn WorldService>>,
+  article_service: State<Box<dyn ArticleService>>,
+) -> Result<CreatedArticle, Problem> {
+  debug!("New Article: {:?}", new_article);
+
+  let world = find_editable_world(&**world_service, &authorizer, world_id)?;
+
+  let generate_slug = new_article.slug.is_none();
+  let article = new_article
+    .into_inner()
+    .into_article_data(world.identity.id.clone())?;
+  debug!("Article Data: {:?}", article);
+
+  let result = if generate_slug {
+    article_service.create_article_with_unique_slug(article)?
+  } else {
+    article_service.create_article(article)?
+  };
+  debug!("Created article: {:?}", result);
+
+  Ok(CreatedArticle {
+    article: Article::new(&result, &world),
+  })
+}
+
+/// Struct representing the input data for creating a new article
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct NewArticle<'a> {
+  pub title: Option<&'a str>,
+  pub slug: Option<&'a str>,
+  pub body: Option<String>,
+  pub parent: Option<&'a str>,
+}
+
+impl<'a> NewArticle<'a> {
+  /// Convert the incoming details into the data for an article of the given world.
+  ///
+  /// If no slug was provided then one is generated from the title of the article
+  ///
+  /// # Arguments
+  /// * `world` The ID of the world that the article is to belong to
+  ///
+  /// # Returns
+  /// The article data, or the validation errors with the incoming details
+  fn into_article_data(self, world: WorldID) -> Result<ArticleData, Vec<ValidationError>> {
+    let title = self
+      .title
+      .map(|title| title.trim())
+      .filter(|title| !title.is_empty())
+      .ok_or_else(|| missing_error("title"));
+    let slug: Result<Slug, ValidationError> = match self.slug {
+      None => Ok(Slug::from_name(self.title.unwrap_or(""))),
+      Some(slug) => slug.parse().map_err(|e: SlugParseError| e.into()),
+    };
+    let parent: Result<Option<ArticleID>, ValidationError> = match self.parent {
+      None => Ok(None),
+      Some(parent) => parent.parse().map(Some).map_err(|_| unknown_parent_error()),
+    };
+
+    match (title, slug, parent) {
+      (Ok(title), Ok(slug), Ok(parent)) => Ok(ArticleData {
+        world,
+        parent,
+        title: title.to_owned(),
+        slug,
+        body: self.body.as_deref().unwrap_or("").trim().to_owned(),
+      }),
+      (title, slug, parent) => {
+        let errors = vec![title.err(), slug.err(), parent.err()]
+          .into_iter()
+          .filter_map(|v| v)
+          .collect();
+
+        Err(errors)
+      }
+    }
+  }
+}
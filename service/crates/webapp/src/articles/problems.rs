@@ -0,0 +1,104 @@
+use crate::problem::{
+  unexpected_error, validation_error, Problem, ValidationError, ValidationErrors,
+};
+use crate::worlds::problems::unknown_world_problem;
+use universe_articles::*;
+
+/// Helper to build a Problem response for an unknown article
+pub fn unknown_article_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:articles/problems/unknown-article".to_owned(),
+    title: "The requested article could not be found".to_owned(),
+    status: 404,
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Problem response for when an article can't be deleted because other
+/// articles sit under it
+pub fn article_has_children_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:articles/problems/has-children".to_owned(),
+    title: "The article has other articles under it".to_owned(),
+    status: 409,
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Problem response for when an article was modified by someone else
+pub fn optimistic_lock_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:articles/problems/optimistic-lock-failure".to_owned(),
+    title: "The article was modified by another request".to_owned(),
+    status: 409,
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Validation Error for a parent article that isn't in the same world
+pub fn unknown_parent_error() -> ValidationError {
+  (&ArticleValidationError::UnknownParent).into()
+}
+
+impl From<&ArticleValidationError> for ValidationError {
+  fn from(e: &ArticleValidationError) -> Self {
+    match e {
+      ArticleValidationError::DuplicateSlug => ValidationError {
+        r#type: "tag:universe,2020:articles/validation-errors/slug/duplicate".to_owned(),
+        title: "The slug is already used by another article in the world".to_owned(),
+        field: "slug".to_owned(),
+      },
+      ArticleValidationError::UnknownParent => ValidationError {
+        r#type: "tag:universe,2020:articles/validation-errors/parent/unknown".to_owned(),
+        title: "The parent article is not an article of the world".to_owned(),
+        field: "parent".to_owned(),
+      },
+      ArticleValidationError::ParentCycle => ValidationError {
+        r#type: "tag:universe,2020:articles/validation-errors/parent/cycle".to_owned(),
+        title: "An article may not be moved under itself".to_owned(),
+        field: "parent".to_owned(),
+      },
+    }
+  }
+}
+
+impl From<CreateArticleError> for Problem {
+  fn from(e: CreateArticleError) -> Self {
+    match e {
+      CreateArticleError::ValidationError(errors) => {
+        validation_error(errors.iter().map(|e| e.into()).collect())
+      }
+      CreateArticleError::UnknownWorld => unknown_world_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<UpdateArticleError> for Problem {
+  fn from(e: UpdateArticleError) -> Self {
+    match e {
+      UpdateArticleError::ValidationError(errors) => {
+        validation_error(errors.iter().map(|e| e.into()).collect())
+      }
+      UpdateArticleError::UnknownArticle => unknown_article_problem(),
+      UpdateArticleError::OptimisticLockFailure => optimistic_lock_problem(),
+      UpdateArticleError::UpdateError(e) if e.is::<ValidationErrors>() => e
+        .downcast_ref::<ValidationErrors>()
+        .unwrap()
+        .errors
+        .clone()
+        .into(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<DeleteArticleError> for Problem {
+  fn from(e: DeleteArticleError) -> Self {
+    match e {
+      DeleteArticleError::UnknownArticle => unknown_article_problem(),
+      DeleteArticleError::HasChildren => article_has_children_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
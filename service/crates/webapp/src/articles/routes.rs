@@ -0,0 +1,15 @@
+use super::delete::*;
+use super::get::*;
+use super::patch::*;
+use super::post::*;
+use rocket::{routes, Route};
+
+pub fn routes() -> Vec<Route> {
+  routes![
+    list_articles,
+    get_article,
+    create_article,
+    update_article,
+    delete_article
+  ]
+}
@@ -0,0 +1,34 @@
+use super::problems::unknown_article_problem;
+use crate::problem::Problem;
+use tracing::warn;
+use universe_articles::{ArticleEntity, ArticleID, ArticleService};
+use universe_worlds::WorldEntity;
+
+/// Parse the ID of an article, as provided in the URL
+pub(super) fn parse_article_id(article_id: String) -> Result<ArticleID, Problem> {
+  article_id.parse().map_err(|e| {
+    warn!("Invalid Article ID: {}", e);
+    unknown_article_problem()
+  })
+}
+
+/// Look up an article of a world
+///
+/// # Arguments
+/// * `article_service` The article service to look the article up with
+/// * `world` The world that the article belongs to
+/// * `article_id` The ID of the article, as provided in the URL
+///
+/// # Returns
+/// The article, or a Problem if it couldn't be found
+pub(super) fn find_article(
+  article_service: &dyn ArticleService,
+  world: &WorldEntity,
+  article_id: String,
+) -> Result<ArticleEntity, Problem> {
+  let article_id = parse_article_id(article_id)?;
+
+  article_service
+    .get_article_by_id(&world.identity.id, &article_id)
+    .ok_or_else(unknown_article_problem)
+}
@@ -3,6 +3,7 @@ use crate::problem::Problem;
 use rocket::request::{FromRequest, Outcome, Request};
 use universe_authentication::AccessToken;
 use universe_users::UserID;
+use universe_worlds::{WorldEntity, WorldRole, WorldService};
 
 /// Authenticator to determine whether a request is allowed to proceed or not
 pub struct Authorizer {
@@ -36,6 +37,11 @@ impl Authorizer {
     Authorizer { access_token }
   }
 
+  /// The ID of the user that is authenticated
+  pub fn user_id(&self) -> &UserID {
+    &self.access_token.user_id
+  }
+
   pub fn same_user(&self, user_id: &UserID) -> Authorized {
     if user_id == &self.access_token.user_id {
       Authorized::Success
@@ -43,6 +49,30 @@ impl Authorizer {
       Authorized::Failure
     }
   }
+
+  /// Check that the authenticated user has at least the given role on a world
+  ///
+  /// # Arguments
+  /// * `world_service` The world service to look up the role of the user with
+  /// * `world` The world to check
+  /// * `role` The minimum role that the user needs
+  pub fn world_role(
+    &self,
+    world_service: &dyn WorldService,
+    world: &WorldEntity,
+    role: WorldRole,
+  ) -> Authorized {
+    let actual = world_service.get_world_role(world, &self.access_token.user_id);
+    role_at_least(actual, role)
+  }
+}
+
+/// Determine whether a role that a user has on a world is enough for the role that is required
+fn role_at_least(actual: Option<WorldRole>, required: WorldRole) -> Authorized {
+  match actual {
+    Some(actual) if actual >= required => Authorized::Success,
+    _ => Authorized::Failure,
+  }
 }
 
 impl Authorized {
@@ -113,6 +143,17 @@ mod tests {
     assert_that(&authorizer.same_user(&user_id)).is_equal_to(Authorized::Failure)
   }
 
+  #[test]
+  fn test_role_at_least() {
+    assert_that(&role_at_least(Some(WorldRole::Owner), WorldRole::Editor))
+      .is_equal_to(Authorized::Success);
+    assert_that(&role_at_least(Some(WorldRole::Editor), WorldRole::Editor))
+      .is_equal_to(Authorized::Success);
+    assert_that(&role_at_least(Some(WorldRole::Viewer), WorldRole::Editor))
+      .is_equal_to(Authorized::Failure);
+    assert_that(&role_at_least(None, WorldRole::Viewer)).is_equal_to(Authorized::Failure);
+  }
+
   #[test]
   fn test_success_result() {
     let result = Authorized::Success.to_result();
@@ -0,0 +1,114 @@
+use super::model::{CalculatedDate, DateDifference};
+use super::problems::date_error;
+use super::world::find_calendar;
+use crate::authentication::ApiAccessToken;
+use crate::problem::{missing_error, validation_error, Problem, ValidationError};
+use crate::request_id::RequestId;
+use crate::worlds::access::find_visible_world;
+use rocket::{get, State};
+use universe_calendars::{CalendarDate, CalendarEntity, CalendarService};
+use universe_worlds::WorldService;
+
+/// Parse a date of a calendar that was provided in a request
+///
+/// # Arguments
+/// * `calendar` The calendar that the date is in
+/// * `field` The field that the date was provided in
+/// * `input` The date, as provided in the request
+///
+/// # Returns
+/// The date, or the validation error with it
+pub(super) fn parse_date(
+  calendar: &CalendarEntity,
+  field: &str,
+  input: Option<&str>,
+) -> Result<CalendarDate, ValidationError> {
+  match input
+    .map(|input| input.trim())
+    .filter(|input| !input.is_empty())
+  {
+    None => Err(missing_error(field)),
+    Some(input) => calendar
+      .data
+      .definition
+      .parse_date(input)
+      .map_err(|e| date_error(field, &e)),
+  }
+}
+
+#[allow(clippy::too_many_arguments)]
+#[get(
+  "/worlds/<world_id>/calendars/<calendar_id>/dates?<date>&<add_years>&<add_months>&<add_days>"
+)]
+#[tracing::instrument(skip(world_service, calendar_service))]
+pub fn get_date(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  calendar_id: String,
+  date: Option<String>,
+  add_years: Option<i64>,
+  add_months: Option<i64>,
+  add_days: Option<i64>,
+  world_service: State<Box<dyn WorldService>>,
+  calendar_service: State<Box<dyn CalendarService>>,
+) -> Result<CalculatedDate, Problem> {
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+  let calendar = find_calendar(&**calendar_service, &world, calendar_id)?;
+  let definition = &calendar.data.definition;
+
+  let mut result =
+    parse_date(&calendar, "date", date.as_deref()).map_err(|e| validation_error(vec![e]))?;
+
+  // Years and months first, so that e.g. adding a month and a day to the last day of a month
+  // lands on the first day of the month after next
+  if let Some(years) = add_years {
+    result = definition
+      .add_years(&result, years)
+      .map_err(|e| validation_error(vec![date_error("add_years", &e)]))?;
+  }
+  if let Some(months) = add_months {
+    result = definition
+      .add_months(&result, months)
+      .map_err(|e| validation_error(vec![date_error("add_months", &e)]))?;
+  }
+  if let Some(days) = add_days {
+    result = definition
+      .add_days(&result, days)
+      .map_err(|e| validation_error(vec![date_error("add_days", &e)]))?;
+  }
+
+  Ok(CalculatedDate::new(&calendar, &result, &world))
+}
+
+#[allow(clippy::too_many_arguments)]
+#[get("/worlds/<world_id>/calendars/<calendar_id>/dates/difference?<from>&<to>")]
+#[tracing::instrument(skip(world_service, calendar_service))]
+pub fn get_date_difference(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  calendar_id: String,
+  from: Option<String>,
+  to: Option<String>,
+  world_service: State<Box<dyn WorldService>>,
+  calendar_service: State<Box<dyn CalendarService>>,
+) -> Result<DateDifference, Problem> {
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+  let calendar = find_calendar(&**calendar_service, &world, calendar_id)?;
+
+  let from = parse_date(&calendar, "from", from.as_deref());
+  let to = parse_date(&calendar, "to", to.as_deref());
+
+  match (from, to) {
+    (Ok(from), Ok(to)) => Ok(DateDifference::new(&calendar, &from, &to, &world)),
+    (from, to) => {
+      let errors = vec![from.err(), to.err()]
+        .into_iter()
+        .filter_map(|v| v)
+        .collect();
+
+      Err(validation_error(errors))
+    }
+  }
+}
@@ -0,0 +1,25 @@
+use super::world::parse_calendar_id;
+use crate::problem::Problem;
+use crate::worlds::access::find_editable_world;
+use crate::{authentication::Authorizer, request_id::RequestId};
+use rocket::{delete, http::Status, State};
+use universe_calendars::CalendarService;
+use universe_worlds::WorldService;
+
+#[delete("/worlds/<world_id>/calendars/<calendar_id>")]
+#[tracing::instrument(skip(world_service, calendar_service, authorizer))]
+pub fn delete_calendar(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  calendar_id: String,
+  world_service: State<Box<dyn WorldService>>,
+  calendar_service: State<Box<dyn CalendarService>>,
+) -> Result<Status, Problem> {
+  let world = find_editable_world(&**world_service, &authorizer, world_id)?;
+  let calendar_id = parse_calendar_id(calendar_id)?;
+
+  calendar_service.delete_calendar(&world.identity.id, &calendar_id)?;
+
+  Ok(Status::NoContent)
+}
@@ -0,0 +1,237 @@
+use super::dates::parse_date;
+use super::model::{CreatedEvent, Event, EventPage};
+use super::world::{find_calendar, find_event, parse_calendar_id, parse_event_id};
+use crate::authentication::{ApiAccessToken, Authorizer};
+use crate::problem::{missing_error, validation_error, Problem, ValidationErrors};
+use crate::request_id::RequestId;
+use crate::worlds::access::{find_editable_world, find_visible_world};
+use rocket::{delete, get, http::Status, patch, post, State};
+use rocket_contrib::json::Json;
+use serde::Deserialize;
+use tracing::{debug, warn};
+use universe_calendars::{CalendarService, EventData, TimelineService};
+use universe_entity::Pagination;
+use universe_worlds::WorldService;
+
+#[allow(clippy::too_many_arguments)]
+#[get("/worlds/<world_id>/calendars/<calendar_id>/events?<from>&<to>&<offset>&<limit>")]
+#[tracing::instrument(skip(world_service, calendar_service, timeline_service))]
+pub fn list_events(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  calendar_id: String,
+  from: Option<String>,
+  to: Option<String>,
+  offset: Option<u32>,
+  limit: Option<u32>,
+  world_service: State<Box<dyn WorldService>>,
+  calendar_service: State<Box<dyn CalendarService>>,
+  timeline_service: State<Box<dyn TimelineService>>,
+) -> Result<EventPage, Problem> {
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+  let calendar = find_calendar(&**calendar_service, &world, calendar_id)?;
+
+  // Both ends of the range are optional, but must be dates of the calendar if they are given
+  let from = from.map(|from| parse_date(&calendar, "from", Some(&from)));
+  let to = to.map(|to| parse_date(&calendar, "to", Some(&to)));
+  let (from, to) = match (from.transpose(), to.transpose()) {
+    (Ok(from), Ok(to)) => (from, to),
+    (from, to) => {
+      let errors = vec![from.err(), to.err()]
+        .into_iter()
+        .filter_map(|v| v)
+        .collect();
+
+      return Err(validation_error(errors));
+    }
+  };
+
+  let pagination = Pagination {
+    offset: offset.unwrap_or(0),
+    limit: limit.unwrap_or(10),
+  };
+
+  let events = timeline_service.list_events(&calendar, from, to, pagination);
+  Ok(EventPage::new(events, &calendar, &world))
+}
+
+#[allow(clippy::too_many_arguments)]
+#[get("/worlds/<world_id>/calendars/<calendar_id>/events/<event_id>")]
+#[tracing::instrument(skip(world_service, calendar_service, timeline_service))]
+pub fn get_event(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  calendar_id: String,
+  event_id: String,
+  world_service: State<Box<dyn WorldService>>,
+  calendar_service: State<Box<dyn CalendarService>>,
+  timeline_service: State<Box<dyn TimelineService>>,
+) -> Result<Event, Problem> {
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+  let calendar = find_calendar(&**calendar_service, &world, calendar_id)?;
+  let event = find_event(&**timeline_service, &calendar, event_id)?;
+
+  Ok(Event::new(&event, &calendar, &world))
+}
+
+#[allow(clippy::too_many_arguments)]
+#[post(
+  "/worlds/<world_id>/calendars/<calendar_id>/events",
+  data = "<new_event>"
+)]
+#[tracing::instrument(skip(world_service, calendar_service, timeline_service, authorizer))]
+pub fn create_event(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  calendar_id: String,
+  new_event: Json<NewEvent>,
+  world_service: State<Box<dyn WorldService>>,
+  calendar_service: State<Box<dyn CalendarService>>,
+  timeline_service: State<Box<dyn TimelineService>>,
+) -> Result<CreatedEvent, Problem> {
+  debug!("New Event: {:?}", new_event);
+
+  let world = find_editable_world(&**world_service, &authorizer, world_id)?;
+  let calendar = find_calendar(&**calendar_service, &world, calendar_id)?;
+
+  let title = new_event
+    .title
+    .map(|title| title.trim())
+    .filter(|title| !title.is_empty())
+    .ok_or_else(|| missing_error("title"));
+  let date = parse_date(&calendar, "date", new_event.date);
+  let (title, date) = match (title, date) {
+    (Ok(title), Ok(date)) => (title, date),
+    (title, date) => {
+      let errors = vec![title.err(), date.err()]
+        .into_iter()
+        .filter_map(|v| v)
+        .collect();
+
+      return Err(validation_error(errors));
+    }
+  };
+
+  let result = timeline_service.create_event(EventData {
+    world: world.identity.id.clone(),
+    calendar: calendar.identity.id.clone(),
+    title: title.to_owned(),
+    description: new_event
+      .description
+      .as_deref()
+      .unwrap_or("")
+      .trim()
+      .to_owned(),
+    date,
+  })?;
+  debug!("Created event: {:?}", result);
+
+  Ok(CreatedEvent {
+    event: Event::new(&result, &calendar, &world),
+  })
+}
+
+#[allow(clippy::too_many_arguments)]
+#[patch(
+  "/worlds/<world_id>/calendars/<calendar_id>/events/<event_id>",
+  format = "application/merge-patch+json",
+  data = "<patch_data>"
+)]
+#[tracing::instrument(skip(world_service, calendar_service, timeline_service, authorizer))]
+pub fn update_event(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  calendar_id: String,
+  event_id: String,
+  patch_data: Json<PatchEvent>,
+  world_service: State<Box<dyn WorldService>>,
+  calendar_service: State<Box<dyn CalendarService>>,
+  timeline_service: State<Box<dyn TimelineService>>,
+) -> Result<Event, Problem> {
+  debug!("Patch Data: {:?}", patch_data);
+
+  let world = find_editable_world(&**world_service, &authorizer, world_id)?;
+  let calendar = find_calendar(&**calendar_service, &world, calendar_id)?;
+  let event_id = parse_event_id(event_id)?;
+
+  let event = timeline_service.update_event(
+    &world.identity.id,
+    &calendar.identity.id,
+    &event_id,
+    &mut |mut event| {
+      debug!("Patching event details");
+
+      let mut errors = vec![];
+
+      if let Some(title) = patch_data.title {
+        let title = title.trim();
+        if title.is_empty() {
+          errors.push(missing_error("title"));
+        } else {
+          event.title = title.to_owned();
+        }
+      }
+
+      if let Some(description) = &patch_data.description {
+        event.description = description.trim().to_owned();
+      }
+
+      if let Some(date) = patch_data.date {
+        match parse_date(&calendar, "date", Some(date)) {
+          Ok(date) => event.date = date,
+          Err(e) => errors.push(e),
+        }
+      }
+
+      if errors.is_empty() {
+        Ok(event)
+      } else {
+        warn!("Error patching event: {:?}", errors);
+        Err(Box::new(ValidationErrors { errors }))
+      }
+    },
+  )?;
+
+  Ok(Event::new(&event, &calendar, &world))
+}
+
+#[delete("/worlds/<world_id>/calendars/<calendar_id>/events/<event_id>")]
+#[tracing::instrument(skip(world_service, timeline_service, authorizer))]
+pub fn delete_event(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  calendar_id: String,
+  event_id: String,
+  world_service: State<Box<dyn WorldService>>,
+  timeline_service: State<Box<dyn TimelineService>>,
+) -> Result<Status, Problem> {
+  let world = find_editable_world(&**world_service, &authorizer, world_id)?;
+  let calendar_id = parse_calendar_id(calendar_id)?;
+  let event_id = parse_event_id(event_id)?;
+
+  timeline_service.delete_event(&world.identity.id, &calendar_id, &event_id)?;
+
+  Ok(Status::NoContent)
+}
+
+/// Struct representing the input data for creating a new event. The date is written the same way
+/// as any other date of the calendar
+#[derive(Deserialize, Debug)]
+pub struct NewEvent<'a> {
+  pub title: Option<&'a str>,
+  pub description: Option<String>,
+  pub date: Option<&'a str>,
+}
+
+/// Struct representing the input data for updating an event
+#[derive(Deserialize, Debug)]
+pub struct PatchEvent<'a> {
+  pub title: Option<&'a str>,
+  pub description: Option<String>,
+  pub date: Option<&'a str>,
+}
@@ -0,0 +1,48 @@
+use super::model::{Calendar, CalendarPage};
+use super::world::find_calendar;
+use crate::authentication::ApiAccessToken;
+use crate::problem::Problem;
+use crate::request_id::RequestId;
+use crate::worlds::access::find_visible_world;
+use rocket::{get, State};
+use universe_calendars::CalendarService;
+use universe_entity::Pagination;
+use universe_worlds::WorldService;
+
+#[get("/worlds/<world_id>/calendars?<offset>&<limit>")]
+#[tracing::instrument(skip(world_service, calendar_service))]
+pub fn list_calendars(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  offset: Option<u32>,
+  limit: Option<u32>,
+  world_service: State<Box<dyn WorldService>>,
+  calendar_service: State<Box<dyn CalendarService>>,
+) -> Result<CalendarPage, Problem> {
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+
+  let pagination = Pagination {
+    offset: offset.unwrap_or(0),
+    limit: limit.unwrap_or(10),
+  };
+
+  let calendars = calendar_service.list_calendars(&world.identity.id, pagination);
+  Ok(CalendarPage::new(calendars, &world))
+}
+
+#[get("/worlds/<world_id>/calendars/<calendar_id>")]
+#[tracing::instrument(skip(world_service, calendar_service))]
+pub fn get_calendar(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  calendar_id: String,
+  world_service: State<Box<dyn WorldService>>,
+  calendar_service: State<Box<dyn CalendarService>>,
+) -> Result<Calendar, Problem> {
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+  let calendar = find_calendar(&**calendar_service, &world, calendar_id)?;
+
+  Ok(Calendar::new(&calendar, &world))
+}
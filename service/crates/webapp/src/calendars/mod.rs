@@ -0,0 +1,12 @@
+mod dates;
+mod delete;
+mod events;
+mod get;
+mod model;
+mod post;
+pub(crate) mod problems;
+mod put;
+mod routes;
+mod world;
+
+pub use routes::routes;
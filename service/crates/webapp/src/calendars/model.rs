@@ -0,0 +1,430 @@
+use crate::headers::*;
+use crate::page::SearchPage;
+use chrono::{DateTime, Utc};
+use rocket::{
+  http::{
+    hyper::header::{CacheControl, CacheDirective, ETag, EntityTag, HttpDate, LastModified},
+    Status,
+  },
+  response::{Responder, Response},
+  Request,
+};
+use rocket_contrib::json::Json;
+use serde::Serialize;
+use std::cmp::Ordering;
+use universe_calendars::{
+  CalendarDate, CalendarDefinition, CalendarEntity, CalendarID, EventEntity, EventID,
+};
+use universe_entity::Page;
+use universe_markdown::render_html;
+use universe_worlds::{Visibility, WorldEntity, WorldID};
+use uuid::Uuid;
+
+/// Work out who may cache a response about the calendars of a world
+fn cache_scope(visibility: Visibility) -> CacheDirective {
+  // Calendars of private worlds must never be stored in a shared cache
+  match visibility {
+    Visibility::Private => CacheDirective::Private,
+    _ => CacheDirective::Public,
+  }
+}
+
+/// Representation of a date in some Calendar
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Date {
+  /// The date written out in full, in the same form that dates are accepted in
+  pub text: String,
+  /// The absolute year, counting from year 1 of the calendar
+  pub year: i64,
+  pub month: u32,
+  pub month_name: String,
+  pub day: u32,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub weekday: Option<String>,
+  /// The abbreviation of the era that the date is in, if any
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub era: Option<String>,
+  /// The year of the era that the date is in, if any
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub era_year: Option<i64>,
+}
+
+impl Date {
+  /// Build the representation of a date
+  ///
+  /// # Arguments
+  /// * `definition` The definition of the calendar that the date is in
+  /// * `date` The date, which must be valid in the calendar
+  pub fn new(definition: &CalendarDefinition, date: &CalendarDate) -> Self {
+    let era = definition.era(date.year);
+
+    Date {
+      text: definition.format_date(date),
+      year: date.year,
+      month: date.month,
+      month_name: definition.months[date.month as usize - 1]
+        .name
+        .trim()
+        .to_owned(),
+      day: date.day,
+      weekday: definition.weekday(date).map(|weekday| weekday.to_owned()),
+      era: era.map(|(era, _)| era.abbreviation.trim().to_owned()),
+      era_year: era.map(|(_, year)| year),
+    }
+  }
+}
+
+/// Representation of a Calendar to return over the API
+#[derive(Debug, Serialize)]
+pub struct Calendar {
+  pub id: CalendarID,
+  pub world: WorldID,
+  pub name: String,
+  #[serde(flatten)]
+  pub definition: CalendarDefinition,
+  /// Where to load the timeline of events of the calendar from
+  pub events: String,
+  #[serde(skip_serializing)]
+  version: Uuid,
+  #[serde(skip_serializing)]
+  updated: DateTime<Utc>,
+  #[serde(skip_serializing)]
+  visibility: Visibility,
+}
+
+impl Calendar {
+  /// Build the representation of a calendar of a world
+  ///
+  /// # Arguments
+  /// * `calendar` The calendar
+  /// * `world` The world that the calendar belongs to
+  pub fn new(calendar: &CalendarEntity, world: &WorldEntity) -> Self {
+    Calendar {
+      id: calendar.identity.id.clone(),
+      world: calendar.data.world.clone(),
+      name: calendar.data.name.clone(),
+      definition: calendar.data.definition.clone(),
+      events: format!(
+        "/worlds/{}/calendars/{}/events",
+        calendar.data.world, calendar.identity.id
+      ),
+      version: calendar.identity.version,
+      updated: calendar.identity.updated,
+      visibility: world.data.visibility,
+    }
+  }
+
+  /// The URL of the calendar
+  fn href(&self) -> String {
+    format!("/worlds/{}/calendars/{}", self.world, self.id)
+  }
+}
+
+impl<'a> Responder<'a> for Calendar {
+  /// Generate a Rocket response for the Calendar
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(Link::from_href(self.href()).with_rel("self"))
+      .header(ETag(EntityTag::new(false, self.version.to_string())))
+      .header(LastModified(HttpDate(time::at_utc(time::Timespec::new(
+        self.updated.timestamp(),
+        0,
+      )))))
+      .header(CacheControl(vec![
+        cache_scope(self.visibility),
+        CacheDirective::MaxAge(3600),
+      ]))
+      .ok()
+  }
+}
+
+/// Representation of a Calendar that has just been created
+#[derive(Debug, Serialize)]
+pub struct CreatedCalendar {
+  #[serde(flatten)]
+  pub calendar: Calendar,
+}
+
+impl<'a> Responder<'a> for CreatedCalendar {
+  /// Generate a Rocket response for the newly created Calendar
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(Link::from_href(self.calendar.href()).with_rel("canonical"))
+      .ok()
+  }
+}
+
+/// Representation of a list of the Calendars of a World
+#[derive(Debug, Serialize)]
+pub struct CalendarPage {
+  #[serde(flatten)]
+  pub page: SearchPage<Calendar>,
+  #[serde(skip_serializing)]
+  visibility: Visibility,
+}
+
+impl CalendarPage {
+  /// Build the representation of a page of the calendars of a world
+  ///
+  /// # Arguments
+  /// * `calendars` The page of calendars
+  /// * `world` The world that the calendars belong to
+  pub fn new(calendars: Page<CalendarEntity>, world: &WorldEntity) -> Self {
+    CalendarPage {
+      page: SearchPage {
+        entries: calendars
+          .entries
+          .iter()
+          .map(|calendar| Calendar::new(calendar, world))
+          .collect(),
+        total: calendars.total,
+      },
+      visibility: world.data.visibility,
+    }
+  }
+}
+
+impl<'a> Responder<'a> for CalendarPage {
+  /// Generate a Rocket response for the list of Calendars
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(CacheControl(vec![
+        cache_scope(self.visibility),
+        CacheDirective::MaxAge(3600),
+      ]))
+      // Members of private worlds can see calendars that nobody else can
+      .raw_header("Vary", "Authorization")
+      .ok()
+  }
+}
+
+/// Representation of a date that was worked out from a Calendar
+#[derive(Debug, Serialize)]
+pub struct CalculatedDate {
+  #[serde(flatten)]
+  pub date: Date,
+  #[serde(skip_serializing)]
+  visibility: Visibility,
+}
+
+impl CalculatedDate {
+  /// Build the representation of a date that was worked out from a calendar
+  ///
+  /// # Arguments
+  /// * `calendar` The calendar that the date is in
+  /// * `date` The date
+  /// * `world` The world that the calendar belongs to
+  pub fn new(calendar: &CalendarEntity, date: &CalendarDate, world: &WorldEntity) -> Self {
+    CalculatedDate {
+      date: Date::new(&calendar.data.definition, date),
+      visibility: world.data.visibility,
+    }
+  }
+}
+
+impl<'a> Responder<'a> for CalculatedDate {
+  /// Generate a Rocket response for the Calculated Date
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(CacheControl(vec![
+        cache_scope(self.visibility),
+        CacheDirective::MaxAge(3600),
+      ]))
+      .ok()
+  }
+}
+
+/// Representation of how far apart two dates of a Calendar are
+#[derive(Debug, Serialize)]
+pub struct DateDifference {
+  pub from: Date,
+  pub to: Date,
+  /// The number of days from the first date to the second, which is negative if the second date
+  /// is the earlier one
+  pub days: i64,
+  /// Where the first date falls compared to the second. One of "before", "same" or "after"
+  pub order: &'static str,
+  #[serde(skip_serializing)]
+  visibility: Visibility,
+}
+
+impl DateDifference {
+  /// Build the representation of how far apart two dates of a calendar are
+  ///
+  /// # Arguments
+  /// * `calendar` The calendar that the dates are in
+  /// * `from` The first date
+  /// * `to` The second date
+  /// * `world` The world that the calendar belongs to
+  pub fn new(
+    calendar: &CalendarEntity,
+    from: &CalendarDate,
+    to: &CalendarDate,
+    world: &WorldEntity,
+  ) -> Self {
+    let definition = &calendar.data.definition;
+
+    DateDifference {
+      from: Date::new(definition, from),
+      to: Date::new(definition, to),
+      days: definition.days_between(from, to),
+      order: match definition.compare_dates(from, to) {
+        Ordering::Less => "before",
+        Ordering::Equal => "same",
+        Ordering::Greater => "after",
+      },
+      visibility: world.data.visibility,
+    }
+  }
+}
+
+impl<'a> Responder<'a> for DateDifference {
+  /// Generate a Rocket response for the Date Difference
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(CacheControl(vec![
+        cache_scope(self.visibility),
+        CacheDirective::MaxAge(3600),
+      ]))
+      .ok()
+  }
+}
+
+/// Representation of an Event on the timeline of a Calendar
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Event {
+  pub id: EventID,
+  pub world: WorldID,
+  pub calendar: CalendarID,
+  pub title: String,
+  pub description: String,
+  pub description_html: String,
+  pub date: Date,
+  #[serde(skip_serializing)]
+  version: Uuid,
+  #[serde(skip_serializing)]
+  updated: DateTime<Utc>,
+  #[serde(skip_serializing)]
+  visibility: Visibility,
+}
+
+impl Event {
+  /// Build the representation of an event of a calendar
+  ///
+  /// # Arguments
+  /// * `event` The event
+  /// * `calendar` The calendar that the event is dated in
+  /// * `world` The world that the calendar belongs to
+  pub fn new(event: &EventEntity, calendar: &CalendarEntity, world: &WorldEntity) -> Self {
+    Event {
+      id: event.identity.id.clone(),
+      world: event.data.world.clone(),
+      calendar: event.data.calendar.clone(),
+      title: event.data.title.clone(),
+      description: event.data.description.clone(),
+      description_html: render_html(&event.data.description),
+      date: Date::new(&calendar.data.definition, &event.data.date),
+      version: event.identity.version,
+      updated: event.identity.updated,
+      visibility: world.data.visibility,
+    }
+  }
+
+  /// The URL of the event
+  fn href(&self) -> String {
+    format!(
+      "/worlds/{}/calendars/{}/events/{}",
+      self.world, self.calendar, self.id
+    )
+  }
+}
+
+impl<'a> Responder<'a> for Event {
+  /// Generate a Rocket response for the Event
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(Link::from_href(self.href()).with_rel("self"))
+      .header(AcceptPatch("application/merge-patch+json"))
+      .header(ETag(EntityTag::new(false, self.version.to_string())))
+      .header(LastModified(HttpDate(time::at_utc(time::Timespec::new(
+        self.updated.timestamp(),
+        0,
+      )))))
+      .header(CacheControl(vec![
+        cache_scope(self.visibility),
+        CacheDirective::MaxAge(3600),
+      ]))
+      .ok()
+  }
+}
+
+/// Representation of an Event that has just been created
+#[derive(Debug, Serialize)]
+pub struct CreatedEvent {
+  #[serde(flatten)]
+  pub event: Event,
+}
+
+impl<'a> Responder<'a> for CreatedEvent {
+  /// Generate a Rocket response for the newly created Event
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(Link::from_href(self.event.href()).with_rel("canonical"))
+      .ok()
+  }
+}
+
+/// Representation of a page of the timeline of a Calendar, in the order that the events happened
+#[derive(Debug, Serialize)]
+pub struct EventPage {
+  #[serde(flatten)]
+  pub page: SearchPage<Event>,
+  #[serde(skip_serializing)]
+  visibility: Visibility,
+}
+
+impl EventPage {
+  /// Build the representation of a page of the events of a calendar
+  ///
+  /// # Arguments
+  /// * `events` The page of events
+  /// * `calendar` The calendar that the events are dated in
+  /// * `world` The world that the calendar belongs to
+  pub fn new(events: Page<EventEntity>, calendar: &CalendarEntity, world: &WorldEntity) -> Self {
+    EventPage {
+      page: SearchPage {
+        entries: events
+          .entries
+          .iter()
+          .map(|event| Event::new(event, calendar, world))
+          .collect(),
+        total: events.total,
+      },
+      visibility: world.data.visibility,
+    }
+  }
+}
+
+impl<'a> Responder<'a> for EventPage {
+  /// Generate a Rocket response for the list of Events
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(CacheControl(vec![
+        cache_scope(self.visibility),
+        CacheDirective::MaxAge(3600),
+      ]))
+      // Members of private worlds can see events that nobody else can
+      .raw_header("Vary", "Authorization")
+      .ok()
+  }
+}
@@ -0,0 +1,71 @@
+use super::model::{Calendar, CreatedCalendar};
+use crate::authentication::Authorizer;
+use crate::problem::{missing_error, validation_error, Problem};
+use crate::request_id::RequestId;
+use crate::worlds::access::find_editable_world;
+use rocket::{post, State};
+use rocket_contrib::json::Json;
+use serde::Deserialize;
+use tracing::debug;
+use universe_calendars::{CalendarData, CalendarDefinition, CalendarService};
+use universe_worlds::{WorldID, WorldService};
+
+#[post("/worlds/<world_id>/calendars", data = "<new_calendar>")]
+#[tracing::instrument(skip(world_service, calendar_service, authorizer))]
+pub fn create_calendar(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  new_calendar: Json<NewCalendar>,
+  world_service: State<Box<dyn WorldService>>,
+  calendar_service: State<Box<dyn CalendarService>>,
+) -> Result<CreatedCalendar, Problem> {
+  debug!("New Calendar: {:?}", new_calendar);
+
+  let world = find_editable_world(&**world_service, &authorizer, world_id)?;
+
+  let calendar = new_calendar
+    .into_inner()
+    .into_calendar_data(world.identity.id.clone())?;
+
+  let result = calendar_service.create_calendar(calendar)?;
+  debug!("Created calendar: {:?}", result);
+
+  Ok(CreatedCalendar {
+    calendar: Calendar::new(&result, &world),
+  })
+}
+
+/// Struct representing the input data for a calendar, in the same form that calendars are
+/// returned in
+#[derive(Deserialize, Debug)]
+pub struct NewCalendar {
+  pub name: Option<String>,
+  #[serde(flatten)]
+  pub definition: CalendarDefinition,
+}
+
+impl NewCalendar {
+  /// Convert the incoming details into the data for a calendar of the given world. The definition
+  /// of the calendar is checked when it's saved
+  ///
+  /// # Arguments
+  /// * `world` The ID of the world that the calendar is to belong to
+  ///
+  /// # Returns
+  /// The calendar data, or the Problem with the incoming details
+  pub(super) fn into_calendar_data(self, world: WorldID) -> Result<CalendarData, Problem> {
+    let name = self
+      .name
+      .as_deref()
+      .map(|name| name.trim())
+      .filter(|name| !name.is_empty())
+      .ok_or_else(|| validation_error(vec![missing_error("name")]))?;
+
+    Ok(CalendarData {
+      world,
+      name: name.to_owned(),
+      definition: self.definition,
+    })
+  }
+}
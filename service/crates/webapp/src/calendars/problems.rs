@@ -0,0 +1,192 @@
+use crate::problem::{
+  missing_error, unexpected_error, validation_error, Problem, ValidationError, ValidationErrors,
+};
+use crate::worlds::problems::unknown_world_problem;
+use std::collections::HashMap;
+use universe_calendars::*;
+
+/// Helper to build a Problem response for an unknown calendar
+pub fn unknown_calendar_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:calendars/problems/unknown-calendar".to_owned(),
+    title: "The requested calendar could not be found".to_owned(),
+    status: 404,
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Problem response for an unknown event
+pub fn unknown_event_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:calendars/problems/unknown-event".to_owned(),
+    title: "The requested event could not be found".to_owned(),
+    status: 404,
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Problem response for when a calendar or event was modified by someone else
+pub fn optimistic_lock_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:calendars/problems/optimistic-lock-failure".to_owned(),
+    title: "The calendar was modified by another request".to_owned(),
+    status: 409,
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Problem response for a change to a calendar that would leave some of its
+/// events on dates that no longer exist
+pub fn events_invalidated_problem(events: Vec<EventID>) -> Problem {
+  let mut extra = HashMap::new();
+  extra.insert("events".to_owned(), serde_json::to_value(events).unwrap());
+
+  Problem {
+    r#type: "tag:universe,2020:calendars/problems/events-invalidated".to_owned(),
+    title: "Some events of the calendar are on dates that the new calendar doesn't have".to_owned(),
+    status: 409,
+    extra,
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Validation Error for a date that isn't valid in a calendar
+///
+/// # Arguments
+/// * `field` The field that the date was provided in
+/// * `e` What was wrong with the date
+pub fn date_error(field: &str, e: &DateError) -> ValidationError {
+  let kind = match e {
+    DateError::Malformed => "malformed",
+    DateError::UnknownMonth => "unknown-month",
+    DateError::UnknownEra => "unknown-era",
+    DateError::YearOutsideEra => "year-outside-era",
+    DateError::YearOutOfRange => "year-out-of-range",
+    DateError::MonthOutOfRange => "month-out-of-range",
+    DateError::DayOutOfRange => "day-out-of-range",
+  };
+
+  ValidationError {
+    r#type: format!(
+      "tag:universe,2020:calendars/validation-errors/date/{}",
+      kind
+    ),
+    title: e.to_string(),
+    field: field.to_owned(),
+  }
+}
+
+impl From<&DefinitionError> for ValidationError {
+  fn from(e: &DefinitionError) -> Self {
+    let (kind, field) = match e {
+      DefinitionError::NoMonths => return missing_error("months"),
+      DefinitionError::MonthName(i) => return missing_error(&format!("months[{}].name", i)),
+      DefinitionError::WeekdayName(i) => return missing_error(&format!("weekdays[{}]", i)),
+      DefinitionError::EraName(i) => return missing_error(&format!("eras[{}].name", i)),
+      DefinitionError::TooManyMonths => ("months/too-many", "months".to_owned()),
+      DefinitionError::DuplicateMonthName(i) => {
+        ("months/duplicate-name", format!("months[{}].name", i))
+      }
+      DefinitionError::MonthLength(i) => ("months/invalid-days", format!("months[{}].days", i)),
+      DefinitionError::TooManyWeekdays => ("weekdays/too-many", "weekdays".to_owned()),
+      DefinitionError::EpochWeekday => ("epochWeekday/invalid", "epochWeekday".to_owned()),
+      DefinitionError::LeapRule => ("leapRule/invalid", "leapRule".to_owned()),
+      DefinitionError::EraAbbreviation(i) => (
+        "eras/invalid-abbreviation",
+        format!("eras[{}].abbreviation", i),
+      ),
+      DefinitionError::DuplicateEraAbbreviation(i) => (
+        "eras/duplicate-abbreviation",
+        format!("eras[{}].abbreviation", i),
+      ),
+      DefinitionError::EraStartYear(i) => (
+        "eras/start-year-out-of-range",
+        format!("eras[{}].startYear", i),
+      ),
+      DefinitionError::EraOrder(i) => ("eras/out-of-order", format!("eras[{}].startYear", i)),
+    };
+
+    ValidationError {
+      r#type: format!("tag:universe,2020:calendars/validation-errors/{}", kind),
+      title: e.to_string(),
+      field,
+    }
+  }
+}
+
+impl From<CreateCalendarError> for Problem {
+  fn from(e: CreateCalendarError) -> Self {
+    match e {
+      CreateCalendarError::ValidationError(errors) => {
+        validation_error(errors.iter().map(|e| e.into()).collect())
+      }
+      CreateCalendarError::UnknownWorld => unknown_world_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<UpdateCalendarError> for Problem {
+  fn from(e: UpdateCalendarError) -> Self {
+    match e {
+      UpdateCalendarError::ValidationError(errors) => {
+        validation_error(errors.iter().map(|e| e.into()).collect())
+      }
+      UpdateCalendarError::EventDatesInvalid(events) => events_invalidated_problem(events),
+      UpdateCalendarError::UnknownCalendar => unknown_calendar_problem(),
+      UpdateCalendarError::OptimisticLockFailure => optimistic_lock_problem(),
+      UpdateCalendarError::UpdateError(e) if e.is::<ValidationErrors>() => e
+        .downcast_ref::<ValidationErrors>()
+        .unwrap()
+        .errors
+        .clone()
+        .into(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<DeleteCalendarError> for Problem {
+  fn from(e: DeleteCalendarError) -> Self {
+    match e {
+      DeleteCalendarError::UnknownCalendar => unknown_calendar_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<CreateEventError> for Problem {
+  fn from(e: CreateEventError) -> Self {
+    match e {
+      CreateEventError::InvalidDate(e) => validation_error(vec![date_error("date", &e)]),
+      CreateEventError::UnknownCalendar => unknown_calendar_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<UpdateEventError> for Problem {
+  fn from(e: UpdateEventError) -> Self {
+    match e {
+      UpdateEventError::InvalidDate(e) => validation_error(vec![date_error("date", &e)]),
+      UpdateEventError::UnknownEvent => unknown_event_problem(),
+      UpdateEventError::OptimisticLockFailure => optimistic_lock_problem(),
+      UpdateEventError::UpdateError(e) if e.is::<ValidationErrors>() => e
+        .downcast_ref::<ValidationErrors>()
+        .unwrap()
+        .errors
+        .clone()
+        .into(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<DeleteEventError> for Problem {
+  fn from(e: DeleteEventError) -> Self {
+    match e {
+      DeleteEventError::UnknownEvent => unknown_event_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
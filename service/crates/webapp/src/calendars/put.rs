@@ -0,0 +1,41 @@
+use super::model::Calendar;
+use super::post::NewCalendar;
+use super::world::parse_calendar_id;
+use crate::authentication::Authorizer;
+use crate::problem::Problem;
+use crate::request_id::RequestId;
+use crate::worlds::access::find_editable_world;
+use rocket::{put, State};
+use rocket_contrib::json::Json;
+use tracing::debug;
+use universe_calendars::CalendarService;
+use universe_worlds::WorldService;
+
+#[put("/worlds/<world_id>/calendars/<calendar_id>", data = "<new_calendar>")]
+#[tracing::instrument(skip(world_service, calendar_service, authorizer))]
+pub fn replace_calendar(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  calendar_id: String,
+  new_calendar: Json<NewCalendar>,
+  world_service: State<Box<dyn WorldService>>,
+  calendar_service: State<Box<dyn CalendarService>>,
+) -> Result<Calendar, Problem> {
+  debug!("Replacement Calendar: {:?}", new_calendar);
+
+  let world = find_editable_world(&**world_service, &authorizer, world_id)?;
+  let calendar_id = parse_calendar_id(calendar_id)?;
+
+  let replacement = new_calendar
+    .into_inner()
+    .into_calendar_data(world.identity.id.clone())?;
+
+  // Events keep their dates, so this fails if any of them would no longer exist
+  let calendar = calendar_service.update_calendar(&world.identity.id, &calendar_id, &mut |_| {
+    Ok(replacement.clone())
+  })?;
+  debug!("Replaced calendar: {:?}", calendar);
+
+  Ok(Calendar::new(&calendar, &world))
+}
@@ -0,0 +1,24 @@
+use super::dates::*;
+use super::delete::*;
+use super::events::*;
+use super::get::*;
+use super::post::*;
+use super::put::*;
+use rocket::{routes, Route};
+
+pub fn routes() -> Vec<Route> {
+  routes![
+    list_calendars,
+    get_calendar,
+    create_calendar,
+    replace_calendar,
+    delete_calendar,
+    get_date,
+    get_date_difference,
+    list_events,
+    get_event,
+    create_event,
+    update_event,
+    delete_event
+  ]
+}
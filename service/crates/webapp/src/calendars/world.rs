@@ -0,0 +1,65 @@
+use super::problems::{unknown_calendar_problem, unknown_event_problem};
+use crate::problem::Problem;
+use tracing::warn;
+use universe_calendars::{
+  CalendarEntity, CalendarID, CalendarService, EventEntity, EventID, TimelineService,
+};
+use universe_worlds::WorldEntity;
+
+/// Parse the ID of a calendar, as provided in the URL
+pub(super) fn parse_calendar_id(calendar_id: String) -> Result<CalendarID, Problem> {
+  calendar_id.parse().map_err(|e| {
+    warn!("Invalid Calendar ID: {}", e);
+    unknown_calendar_problem()
+  })
+}
+
+/// Parse the ID of an event of a calendar, as provided in the URL
+pub(super) fn parse_event_id(event_id: String) -> Result<EventID, Problem> {
+  event_id.parse().map_err(|e| {
+    warn!("Invalid Event ID: {}", e);
+    unknown_event_problem()
+  })
+}
+
+/// Look up a calendar of a world
+///
+/// # Arguments
+/// * `calendar_service` The calendar service to look the calendar up with
+/// * `world` The world that the calendar belongs to
+/// * `calendar_id` The ID of the calendar, as provided in the URL
+///
+/// # Returns
+/// The calendar, or a Problem if it couldn't be found
+pub(super) fn find_calendar(
+  calendar_service: &dyn CalendarService,
+  world: &WorldEntity,
+  calendar_id: String,
+) -> Result<CalendarEntity, Problem> {
+  let calendar_id = parse_calendar_id(calendar_id)?;
+
+  calendar_service
+    .get_calendar_by_id(&world.identity.id, &calendar_id)
+    .ok_or_else(unknown_calendar_problem)
+}
+
+/// Look up an event of a calendar
+///
+/// # Arguments
+/// * `timeline_service` The timeline service to look the event up with
+/// * `calendar` The calendar that the event is dated in
+/// * `event_id` The ID of the event, as provided in the URL
+///
+/// # Returns
+/// The event, or a Problem if it couldn't be found
+pub(super) fn find_event(
+  timeline_service: &dyn TimelineService,
+  calendar: &CalendarEntity,
+  event_id: String,
+) -> Result<EventEntity, Problem> {
+  let event_id = parse_event_id(event_id)?;
+
+  timeline_service
+    .get_event_by_id(&calendar.data.world, &calendar.identity.id, &event_id)
+    .ok_or_else(unknown_event_problem)
+}
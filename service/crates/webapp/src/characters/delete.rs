@@ -0,0 +1,25 @@
+use super::world::parse_character_id;
+use crate::problem::Problem;
+use crate::worlds::access::find_editable_world;
+use crate::{authentication::Authorizer, request_id::RequestId};
+use rocket::{delete, http::Status, State};
+use universe_characters::CharacterService;
+use universe_worlds::WorldService;
+
+#[delete("/worlds/<world_id>/characters/<character_id>")]
+#[tracing::instrument(skip(world_service, character_service, authorizer))]
+pub fn delete_character(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  character_id: String,
+  world_service: State<Box<dyn WorldService>>,
+  character_service: State<Box<dyn CharacterService>>,
+) -> Result<Status, Problem> {
+  let world = find_editable_world(&**world_service, &authorizer, world_id)?;
+  let character_id = parse_character_id(character_id)?;
+
+  character_service.delete_character(&world.identity.id, &character_id)?;
+
+  Ok(Status::NoContent)
+}
@@ -0,0 +1,65 @@
+use super::model::{Character, CharacterPage};
+use super::world::find_character;
+use crate::authentication::ApiAccessToken;
+use crate::problem::{invalid_sort_fields, Problem};
+use crate::request_id::RequestId;
+use crate::worlds::access::find_visible_world;
+use rocket::{get, State};
+use tracing::debug;
+use universe_characters::{CharacterFilters, CharacterService, CharacterSorts};
+use universe_entity::{parse_sorts, Pagination, SortField};
+use universe_worlds::WorldService;
+
+#[allow(clippy::too_many_arguments)]
+#[get("/worlds/<world_id>/characters?<name>&<alias>&<offset>&<limit>&<sort>")]
+#[tracing::instrument(skip(world_service, character_service))]
+pub fn search_characters(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  name: Option<String>,
+  alias: Option<String>,
+  offset: Option<u32>,
+  limit: Option<u32>,
+  sort: Option<String>,
+  world_service: State<Box<dyn WorldService>>,
+  character_service: State<Box<dyn CharacterService>>,
+) -> Result<CharacterPage, Problem> {
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+
+  let pagination = Pagination {
+    offset: offset.unwrap_or(0),
+    limit: limit.unwrap_or(10),
+  };
+
+  let sort_param = sort
+    .map(|sort| sort.trim().to_owned())
+    .filter(|sort| !sort.is_empty())
+    .unwrap_or_else(|| "name".to_owned());
+  let sorts: Vec<SortField<CharacterSorts>> =
+    parse_sorts(sort_param).map_err(invalid_sort_fields)?;
+  debug!("Parsed sorts: {:?}", sorts);
+
+  let filters = CharacterFilters { name, alias };
+  debug!("Parsed filters: {:?}", filters);
+
+  let characters =
+    character_service.search_characters(&world.identity.id, filters, sorts, pagination);
+  Ok(CharacterPage::new(characters, &world))
+}
+
+#[get("/worlds/<world_id>/characters/<character_id>")]
+#[tracing::instrument(skip(world_service, character_service))]
+pub fn get_character(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  character_id: String,
+  world_service: State<Box<dyn WorldService>>,
+  character_service: State<Box<dyn CharacterService>>,
+) -> Result<Character, Problem> {
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+  let character = find_character(&**character_service, &world, character_id)?;
+
+  Ok(Character::new(&character, &world))
+}
@@ -0,0 +1,163 @@
+use crate::headers::*;
+use crate::page::SearchPage;
+use chrono::{DateTime, Utc};
+use rocket::{
+  http::{
+    hyper::header::{CacheControl, CacheDirective, ETag, EntityTag, HttpDate, LastModified},
+    Status,
+  },
+  response::{Responder, Response},
+  Request,
+};
+use rocket_contrib::json::Json;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use universe_characters::{CharacterEntity, CharacterID};
+use universe_entity::Page;
+use universe_markdown::render_html;
+use universe_worlds::{Visibility, WorldEntity, WorldID};
+use uuid::Uuid;
+
+/// Representation of a Character to return over the API
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Character {
+  pub id: CharacterID,
+  pub world: WorldID,
+  pub name: String,
+  pub aliases: Vec<String>,
+  pub summary: String,
+  /// The biography of the character, as the CommonMark source that the user provided
+  pub biography: String,
+  /// The biography of the character, rendered to sanitized HTML
+  pub biography_html: String,
+  pub attributes: BTreeMap<String, String>,
+  #[serde(skip_serializing)]
+  version: Uuid,
+  #[serde(skip_serializing)]
+  updated: DateTime<Utc>,
+  #[serde(skip_serializing)]
+  visibility: Visibility,
+}
+
+impl Character {
+  /// Build the representation of a character of a world
+  ///
+  /// # Arguments
+  /// * `character` The character
+  /// * `world` The world that the character belongs to
+  pub fn new(character: &CharacterEntity, world: &WorldEntity) -> Self {
+    Character {
+      id: character.identity.id.clone(),
+      world: character.data.world.clone(),
+      name: character.data.name.clone(),
+      aliases: character.data.aliases.clone(),
+      summary: character.data.summary.clone(),
+      biography: character.data.biography.clone(),
+      biography_html: render_html(&character.data.biography),
+      attributes: character.data.attributes.clone(),
+      version: character.identity.version,
+      updated: character.identity.updated,
+      visibility: world.data.visibility,
+    }
+  }
+}
+
+/// Work out who may cache a response about the characters of a world
+fn cache_scope(visibility: Visibility) -> CacheDirective {
+  // Characters of private worlds must never be stored in a shared cache
+  match visibility {
+    Visibility::Private => CacheDirective::Private,
+    _ => CacheDirective::Public,
+  }
+}
+
+impl<'a> Responder<'a> for Character {
+  /// Generate a Rocket response for the Character
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(
+        Link::from_href(format!("/worlds/{}/characters/{}", self.world, self.id)).with_rel("self"),
+      )
+      .header(AcceptPatch("application/merge-patch+json"))
+      .header(ETag(EntityTag::new(false, self.version.to_string())))
+      .header(LastModified(HttpDate(time::at_utc(time::Timespec::new(
+        self.updated.timestamp(),
+        0,
+      )))))
+      .header(CacheControl(vec![
+        cache_scope(self.visibility),
+        CacheDirective::MaxAge(3600),
+      ]))
+      .ok()
+  }
+}
+
+/// Representation of a Character that has just been created
+#[derive(Debug, Serialize)]
+pub struct CreatedCharacter {
+  #[serde(flatten)]
+  pub character: Character,
+}
+
+impl<'a> Responder<'a> for CreatedCharacter {
+  /// Generate a Rocket response for the newly created Character
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(
+        Link::from_href(format!(
+          "/worlds/{}/characters/{}",
+          self.character.world, self.character.id
+        ))
+        .with_rel("canonical"),
+      )
+      .ok()
+  }
+}
+
+/// Representation of a page of the Characters of a World
+#[derive(Debug, Serialize)]
+pub struct CharacterPage {
+  #[serde(flatten)]
+  pub page: SearchPage<Character>,
+  #[serde(skip_serializing)]
+  visibility: Visibility,
+}
+
+impl CharacterPage {
+  /// Build the representation of a page of the characters of a world
+  ///
+  /// # Arguments
+  /// * `characters` The page of characters
+  /// * `world` The world that the characters belong to
+  pub fn new(characters: Page<CharacterEntity>, world: &WorldEntity) -> Self {
+    CharacterPage {
+      page: SearchPage {
+        entries: characters
+          .entries
+          .iter()
+          .map(|character| Character::new(character, world))
+          .collect(),
+        total: characters.total,
+      },
+      visibility: world.data.visibility,
+    }
+  }
+}
+
+impl<'a> Responder<'a> for CharacterPage {
+  /// Generate a Rocket response for the page of Characters
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(CacheControl(vec![
+        cache_scope(self.visibility),
+        CacheDirective::MaxAge(3600),
+      ]))
+      // Members of private worlds can see characters that nobody else can
+      .raw_header("Vary", "Authorization")
+      .ok()
+  }
+}
@@ -0,0 +1,149 @@
+use super::model::Character;
+use super::world::parse_character_id;
+use crate::problem::{missing_error, Problem, ValidationError, ValidationErrors};
+use crate::worlds::access::find_editable_world;
+use crate::{authentication::Authorizer, request_id::RequestId};
+use rocket::{patch, State};
+use rocket_contrib::json::Json;
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+use tracing::{debug, warn};
+use universe_characters::{parse_aliases, parse_attributes, CharacterService};
+use universe_worlds::WorldService;
+
+#[patch(
+  "/worlds/<world_id>/characters/<character_id>",
+  format = "application/merge-patch+json",
+  data = "<patch_data>"
+)]
+#[tracing::instrument(skip(world_service, character_service, authorizer))]
+pub fn update_character(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  character_id: String,
+  patch_data: Json<PatchData>,
+  world_service: State<Box<dyn WorldService>>,
+  character_service: State<Box<dyn CharacterService>>,
+) -> Result<Character, Problem> {
+  debug!("Patch Data: {:?}", patch_data);
+
+  let world = find_editable_world(&**world_service, &authorizer, world_id)?;
+  let character_id = parse_character_id(character_id)?;
+
+  let character = character_service.update_character(
+    &world.identity.id,
+    &character_id,
+    &mut |mut character| {
+      debug!("Patching character details");
+
+      let name_error = patch_data.name.and_then(|name| {
+        let name = name.trim();
+        if name.is_empty() {
+          Some(missing_error("name"))
+        } else {
+          character.name = name.to_owned();
+          None
+        }
+      });
+
+      let aliases_error = patch_data.aliases.clone().and_then(|aliases| {
+        parse_aliases(aliases)
+          .map(|aliases| {
+            character.aliases = aliases;
+          })
+          .err()
+          .map(ValidationError::from)
+      });
+
+      if let Some(summary) = &patch_data.summary {
+        character.summary = summary.trim().to_owned();
+      }
+
+      if let Some(biography) = &patch_data.biography {
+        character.biography = biography.trim().to_owned();
+      }
+
+      // Attributes are merged into the existing ones, with a value of `null` removing an
+      // attribute and an `attributes` of `null` removing all of them
+      let attributes_error =
+        patch_data
+          .attributes
+          .clone()
+          .and_then(|attributes| match attributes {
+            AttributesPatch::Clear => {
+              character.attributes.clear();
+              None
+            }
+            AttributesPatch::Merge(attributes) => {
+              let (set, removed): (BTreeMap<_, _>, BTreeMap<_, _>) = attributes
+                .into_iter()
+                .partition(|(_, value)| value.is_some());
+              for name in removed.keys() {
+                character.attributes.remove(name.trim());
+              }
+
+              parse_attributes(
+                set
+                  .into_iter()
+                  .map(|(name, value)| (name, value.unwrap_or_default()))
+                  .collect(),
+              )
+              .map(|set| character.attributes.extend(set))
+              .err()
+              .map(ValidationError::from)
+            }
+          });
+
+      match (name_error, aliases_error, attributes_error) {
+        (None, None, None) => Ok(character),
+        (name, aliases, attributes) => {
+          let errors: Vec<ValidationError> = vec![name, aliases, attributes]
+            .into_iter()
+            .filter_map(|v| v)
+            .collect();
+          warn!("Error patching character: {:?}", errors);
+          Err(Box::new(ValidationErrors { errors }))
+        }
+      }
+    },
+  )?;
+
+  Ok(Character::new(&character, &world))
+}
+
+/// Struct representing the input data for updating a character
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchData<'a> {
+  pub name: Option<&'a str>,
+  pub aliases: Option<Vec<String>>,
+  pub summary: Option<String>,
+  pub biography: Option<String>,
+  #[serde(default, deserialize_with = "deserialize_attributes")]
+  pub attributes: Option<AttributesPatch>,
+}
+
+/// How the custom attributes of a character are to be changed
+#[derive(Debug, Clone)]
+pub enum AttributesPatch {
+  /// The attributes were given as an explicit `null`, so are all to be removed
+  Clear,
+  /// The attributes are to be merged into the existing ones. Any with a value of `None` are to
+  /// be removed
+  Merge(BTreeMap<String, Option<String>>),
+}
+
+/// Deserialize the attributes of a character, so that an explicit `null` can be told apart from
+/// the field being missing
+fn deserialize_attributes<'de, D>(deserializer: D) -> Result<Option<AttributesPatch>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let attributes: Option<BTreeMap<String, Option<String>>> =
+    Deserialize::deserialize(deserializer)?;
+  Ok(Some(match attributes {
+    None => AttributesPatch::Clear,
+    Some(attributes) => AttributesPatch::Merge(attributes),
+  }))
+}
@@ -0,0 +1,90 @@
+use super::model::{Character, CreatedCharacter};
+use crate::authentication::Authorizer;
+use crate::problem::{missing_error, Problem, ValidationError};
+use crate::request_id::RequestId;
+use crate::worlds::access::find_editable_world;
+use rocket::{post, State};
+use rocket_contrib::json::Json;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use tracing::debug;
+use universe_characters::{parse_aliases, parse_attributes, CharacterData, CharacterService};
+use universe_worlds::{WorldID, WorldService};
+
+#[post("/worlds/<world_id>/characters", data = "<new_character>")]
+#[tracing::instrument(skip(world_service, character_service, authorizer))]
+pub fn create_character(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  new_character: Json<NewCharacter>,
+  world_service: State<Box<dyn WorldService>>,
+  character_service: State<Box<dyn CharacterService>>,
+) -> Result<CreatedCharacter, Problem> {
+  debug!("New Character: {:?}", new_character);
+
+  let world = find_editable_world(&**world_service, &authorizer, world_id)?;
+
+  let character = new_character
+    .into_inner()
+    .into_character_data(world.identity.id.clone())?;
+  debug!("Character Data: {:?}", character);
+
+  let result = character_service.create_character(character)?;
+  debug!("Created character: {:?}", result);
+
+  Ok(CreatedCharacter {
+    character: Character::new(&result, &world),
+  })
+}
+
+/// Struct representing the input data for creating a new character
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct NewCharacter<'a> {
+  pub name: Option<&'a str>,
+  #[serde(default)]
+  pub aliases: Vec<String>,
+  pub summary: Option<String>,
+  pub biography: Option<String>,
+  #[serde(default)]
+  pub attributes: BTreeMap<String, String>,
+}
+
+impl<'a> NewCharacter<'a> {
+  /// Convert the incoming details into the data for a character of the given world.
+  ///
+  /// # Arguments
+  /// * `world` The ID of the world that the character is to belong to
+  ///
+  /// # Returns
+  /// The character data, or the validation errors with the incoming details
+  fn into_character_data(self, world: WorldID) -> Result<CharacterData, Vec<ValidationError>> {
+    let name = self
+      .name
+      .map(|name| name.trim())
+      .filter(|name| !name.is_empty())
+      .ok_or_else(|| missing_error("name"));
+    let aliases = parse_aliases(self.aliases).map_err(ValidationError::from);
+    let attributes = parse_attributes(self.attributes).map_err(ValidationError::from);
+
+    match (name, aliases, attributes) {
+      (Ok(name), Ok(aliases), Ok(attributes)) => Ok(CharacterData {
+        world,
+        name: name.to_owned(),
+        aliases,
+        summary: self.summary.as_deref().unwrap_or("").trim().to_owned(),
+        biography: self.biography.as_deref().unwrap_or("").trim().to_owned(),
+        attributes,
+      }),
+      (name, aliases, attributes) => {
+        let errors = vec![name.err(), aliases.err(), attributes.err()]
+          .into_iter()
+          .filter_map(|v| v)
+          .collect();
+
+        Err(errors)
+      }
+    }
+  }
+}
@@ -0,0 +1,74 @@
+use crate::problem::{unexpected_error, Problem, ValidationError, ValidationErrors};
+use crate::worlds::problems::unknown_world_problem;
+use universe_characters::*;
+
+/// Helper to build a Problem response for an unknown character
+pub fn unknown_character_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:characters/problems/unknown-character".to_owned(),
+    title: "The requested character could not be found".to_owned(),
+    status: 404,
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Problem response for when a character was modified by someone else
+pub fn optimistic_lock_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:characters/problems/optimistic-lock-failure".to_owned(),
+    title: "The character was modified by another request".to_owned(),
+    status: 409,
+    ..Default::default()
+  }
+}
+
+impl From<CharacterDetailsError> for ValidationError {
+  fn from(e: CharacterDetailsError) -> Self {
+    match e {
+      CharacterDetailsError::BlankAlias => ValidationError {
+        r#type: "tag:universe,2020:characters/validation-errors/aliases/blank".to_owned(),
+        title: "Aliases may not be blank".to_owned(),
+        field: "aliases".to_owned(),
+      },
+      CharacterDetailsError::BlankAttributeName => ValidationError {
+        r#type: "tag:universe,2020:characters/validation-errors/attributes/blank-name".to_owned(),
+        title: "Attribute names may not be blank".to_owned(),
+        field: "attributes".to_owned(),
+      },
+    }
+  }
+}
+
+impl From<CreateCharacterError> for Problem {
+  fn from(e: CreateCharacterError) -> Self {
+    match e {
+      CreateCharacterError::UnknownWorld => unknown_world_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<UpdateCharacterError> for Problem {
+  fn from(e: UpdateCharacterError) -> Self {
+    match e {
+      UpdateCharacterError::UnknownCharacter => unknown_character_problem(),
+      UpdateCharacterError::OptimisticLockFailure => optimistic_lock_problem(),
+      UpdateCharacterError::UpdateError(e) if e.is::<ValidationErrors>() => e
+        .downcast_ref::<ValidationErrors>()
+        .unwrap()
+        .errors
+        .clone()
+        .into(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<DeleteCharacterError> for Problem {
+  fn from(e: DeleteCharacterError) -> Self {
+    match e {
+      DeleteCharacterError::UnknownCharacter => unknown_character_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
@@ -0,0 +1,15 @@
+use super::delete::*;
+use super::get::*;
+use super::patch::*;
+use super::post::*;
+use rocket::{routes, Route};
+
+pub fn routes() -> Vec<Route> {
+  routes![
+    search_characters,
+    get_character,
+    create_character,
+    update_character,
+    delete_character
+  ]
+}
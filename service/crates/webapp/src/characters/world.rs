@@ -0,0 +1,34 @@
+use super::problems::unknown_character_problem;
+use crate::problem::Problem;
+use tracing::warn;
+use universe_characters::{CharacterEntity, CharacterID, CharacterService};
+use universe_worlds::WorldEntity;
+
+/// Parse the ID of a character, as provided in the URL
+pub(super) fn parse_character_id(character_id: String) -> Result<CharacterID, Problem> {
+  character_id.parse().map_err(|e| {
+    warn!("Invalid Character ID: {}", e);
+    unknown_character_problem()
+  })
+}
+
+/// Look up a character of a world
+///
+/// # Arguments
+/// * `character_service` The character service to look the character up with
+/// * `world` The world that the character belongs to
+/// * `character_id` The ID of the character, as provided in the URL
+///
+/// # Returns
+/// The character, or a Problem if it couldn't be found
+pub(super) fn find_character(
+  character_service: &dyn CharacterService,
+  world: &WorldEntity,
+  character_id: String,
+) -> Result<CharacterEntity, Problem> {
+  let character_id = parse_character_id(character_id)?;
+
+  character_service
+    .get_character_by_id(&world.identity.id, &character_id)
+    .ok_or_else(unknown_character_problem)
+}
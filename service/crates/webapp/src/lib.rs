@@ -1,12 +1,19 @@
 #![feature(proc_macro_hygiene, decl_macro)]
 
+mod articles;
 mod authentication;
+mod calendars;
+mod characters;
 mod headers;
 mod health;
+mod locations;
+mod maps;
 mod page;
 mod problem;
+mod relationships;
 mod request_id;
 mod service;
+mod storage;
 mod users;
 mod worlds;
 
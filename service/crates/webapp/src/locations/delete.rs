@@ -0,0 +1,25 @@
+use super::world::parse_location_id;
+use crate::problem::Problem;
+use crate::worlds::access::find_editable_world;
+use crate::{authentication::Authorizer, request_id::RequestId};
+use rocket::{delete, http::Status, State};
+use universe_locations::LocationService;
+use universe_worlds::WorldService;
+
+#[delete("/worlds/<world_id>/locations/<location_id>")]
+#[tracing::instrument(skip(world_service, location_service, authorizer))]
+pub fn delete_location(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  location_id: String,
+  world_service: State<Box<dyn WorldService>>,
+  location_service: State<Box<dyn LocationService>>,
+) -> Result<Status, Problem> {
+  let world = find_editable_world(&**world_service, &authorizer, world_id)?;
+  let location_id = parse_location_id(location_id)?;
+
+  location_service.delete_location(&world.identity.id, &location_id)?;
+
+  Ok(Status::NoContent)
+}
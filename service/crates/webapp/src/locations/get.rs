@@ -0,0 +1,64 @@
+use super::model::{Location, LocationPage};
+use super::problems::unknown_parent_error;
+use super::world::find_location;
+use crate::authentication::ApiAccessToken;
+use crate::problem::{validation_error, Problem};
+use crate::request_id::RequestId;
+use crate::worlds::access::find_visible_world;
+use rocket::{get, State};
+use tracing::warn;
+use universe_entity::Pagination;
+use universe_locations::{LocationParentFilter, LocationService};
+use universe_worlds::WorldService;
+
+#[allow(clippy::too_many_arguments)]
+#[get("/worlds/<world_id>/locations?<parent>&<offset>&<limit>")]
+#[tracing::instrument(skip(world_service, location_service))]
+pub fn list_locations(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  parent: Option<String>,
+  offset: Option<u32>,
+  limit: Option<u32>,
+  world_service: State<Box<dyn WorldService>>,
+  location_service: State<Box<dyn LocationService>>,
+) -> Result<LocationPage<Location>, Problem> {
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+
+  // "none" lists the locations at the top of the tree. Otherwise we list those inside the given one
+  let parent = match parent.as_deref() {
+    None => None,
+    Some("none") => Some(LocationParentFilter::TopLevel),
+    Some(parent) => Some(LocationParentFilter::Children(parent.parse().map_err(
+      |e| {
+        warn!("Invalid Location ID: {}", e);
+        validation_error(vec![unknown_parent_error()])
+      },
+    )?)),
+  };
+
+  let pagination = Pagination {
+    offset: offset.unwrap_or(0),
+    limit: limit.unwrap_or(10),
+  };
+
+  let locations = location_service.list_locations(&world.identity.id, parent, pagination);
+  Ok(LocationPage::new(locations, &world))
+}
+
+#[get("/worlds/<world_id>/locations/<location_id>")]
+#[tracing::instrument(skip(world_service, location_service))]
+pub fn get_location(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  location_id: String,
+  world_service: State<Box<dyn WorldService>>,
+  location_service: State<Box<dyn LocationService>>,
+) -> Result<Location, Problem> {
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+  let location = find_location(&**location_service, &world, location_id)?;
+
+  Ok(Location::new(&location, &world))
+}
@@ -0,0 +1,11 @@
+mod delete;
+mod get;
+mod model;
+mod patch;
+mod post;
+mod problems;
+mod routes;
+mod tree;
+mod world;
+
+pub use routes::routes;
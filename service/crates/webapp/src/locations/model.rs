@@ -0,0 +1,220 @@
+use crate::headers::*;
+use crate::page::SearchPage;
+use chrono::{DateTime, Utc};
+use rocket::{
+  http::{
+    hyper::header::{CacheControl, CacheDirective, ETag, EntityTag, HttpDate, LastModified},
+    Status,
+  },
+  response::{Responder, Response},
+  Request,
+};
+use rocket_contrib::json::Json;
+use serde::Serialize;
+use universe_entity::Page;
+use universe_locations::{Coordinates, LocationDescendant, LocationEntity, LocationID};
+use universe_markdown::render_html;
+use universe_worlds::{Visibility, WorldEntity, WorldID};
+use uuid::Uuid;
+
+/// Representation of a Location to return over the API
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+  pub id: LocationID,
+  pub world: WorldID,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub parent: Option<LocationID>,
+  pub name: String,
+  pub kind: String,
+  /// The description of the location, as the CommonMark source that the user provided
+  pub description: String,
+  /// The description of the location, rendered to sanitized HTML
+  pub description_html: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub coordinates: Option<Coordinates>,
+  #[serde(skip_serializing)]
+  version: Uuid,
+  #[serde(skip_serializing)]
+  updated: DateTime<Utc>,
+  #[serde(skip_serializing)]
+  visibility: Visibility,
+}
+
+impl Location {
+  /// Build the representation of a location of a world
+  ///
+  /// # Arguments
+  /// * `location` The location
+  /// * `world` The world that the location belongs to
+  pub fn new(location: &LocationEntity, world: &WorldEntity) -> Self {
+    Location {
+      id: location.identity.id.clone(),
+      world: location.data.world.clone(),
+      parent: location.data.parent.clone(),
+      name: location.data.name.clone(),
+      kind: location.data.kind.clone(),
+      description: location.data.description.clone(),
+      description_html: render_html(&location.data.description),
+      coordinates: location.data.coordinates,
+      version: location.identity.version,
+      updated: location.identity.updated,
+      visibility: world.data.visibility,
+    }
+  }
+}
+
+/// Representation of a Location that sits somewhere inside another one
+#[derive(Debug, Serialize)]
+pub struct Descendant {
+  /// How far inside the other location this one is, where direct children have a depth of 1
+  pub depth: u32,
+  #[serde(flatten)]
+  pub location: Location,
+}
+
+/// Work out who may cache a response about the locations of a world
+fn cache_scope(visibility: Visibility) -> CacheDirective {
+  // Locations of private worlds must never be stored in a shared cache
+  match visibility {
+    Visibility::Private => CacheDirective::Private,
+    _ => CacheDirective::Public,
+  }
+}
+
+impl<'a> Responder<'a> for Location {
+  /// Generate a Rocket response for the Location
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(
+        Link::from_href(format!("/worlds/{}/locations/{}", self.world, self.id)).with_rel("self"),
+      )
+      .header(AcceptPatch("application/merge-patch+json"))
+      .header(ETag(EntityTag::new(false, self.version.to_string())))
+      .header(LastModified(HttpDate(time::at_utc(time::Timespec::new(
+        self.updated.timestamp(),
+        0,
+      )))))
+      .header(CacheControl(vec![
+        cache_scope(self.visibility),
+        CacheDirective::MaxAge(3600),
+      ]))
+      .ok()
+  }
+}
+
+/// Representation of a Location that has just been created
+#[derive(Debug, Serialize)]
+pub struct CreatedLocation {
+  #[serde(flatten)]
+  pub location: Location,
+}
+
+impl<'a> Responder<'a> for CreatedLocation {
+  /// Generate a Rocket response for the newly created Location
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(
+        Link::from_href(format!(
+          "/worlds/{}/locations/{}",
+          self.location.world, self.location.id
+        ))
+        .with_rel("canonical"),
+      )
+      .ok()
+  }
+}
+
+/// Representation of a list of the Locations of a World
+#[derive(Debug, Serialize)]
+pub struct LocationPage<T>
+where
+  T: Serialize,
+{
+  #[serde(flatten)]
+  pub page: SearchPage<T>,
+  #[serde(skip_serializing)]
+  visibility: Visibility,
+}
+
+impl LocationPage<Location> {
+  /// Build the representation of a page of the locations of a world
+  ///
+  /// # Arguments
+  /// * `locations` The page of locations
+  /// * `world` The world that the locations belong to
+  pub fn new(locations: Page<LocationEntity>, world: &WorldEntity) -> Self {
+    LocationPage {
+      page: SearchPage {
+        entries: locations
+          .entries
+          .iter()
+          .map(|location| Location::new(location, world))
+          .collect(),
+        total: locations.total,
+      },
+      visibility: world.data.visibility,
+    }
+  }
+
+  /// Build the representation of every one of a list of locations of a world
+  ///
+  /// # Arguments
+  /// * `locations` The locations
+  /// * `world` The world that the locations belong to
+  pub fn from_list(locations: Vec<LocationEntity>, world: &WorldEntity) -> Self {
+    LocationPage {
+      page: SearchPage {
+        total: locations.len() as u32,
+        entries: locations
+          .iter()
+          .map(|location| Location::new(location, world))
+          .collect(),
+      },
+      visibility: world.data.visibility,
+    }
+  }
+}
+
+impl LocationPage<Descendant> {
+  /// Build the representation of the locations that sit inside another location
+  ///
+  /// # Arguments
+  /// * `descendants` The locations
+  /// * `world` The world that the locations belong to
+  pub fn from_descendants(descendants: Vec<LocationDescendant>, world: &WorldEntity) -> Self {
+    LocationPage {
+      page: SearchPage {
+        total: descendants.len() as u32,
+        entries: descendants
+          .iter()
+          .map(|descendant| Descendant {
+            depth: descendant.depth,
+            location: Location::new(&descendant.location, world),
+          })
+          .collect(),
+      },
+      visibility: world.data.visibility,
+    }
+  }
+}
+
+impl<'a, T> Responder<'a> for LocationPage<T>
+where
+  T: Serialize,
+{
+  /// Generate a Rocket response for the list of Locations
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(CacheControl(vec![
+        cache_scope(self.visibility),
+        CacheDirective::MaxAge(3600),
+      ]))
+      // Members of private worlds can see locations that nobody else can
+      .raw_header("Vary", "Authorization")
+      .ok()
+  }
+}
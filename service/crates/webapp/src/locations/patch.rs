@@ -0,0 +1,102 @@
+use super::model::Location;
+use super::world::parse_location_id;
+use crate::problem::{missing_error, Problem, ValidationErrors};
+use crate::worlds::access::find_editable_world;
+use crate::{authentication::Authorizer, request_id::RequestId};
+use rocket::{patch, State};
+use rocket_contrib::json::Json;
+use serde::{Deserialize, Deserializer};
+use tracing::{debug, warn};
+use universe_locations::{Coordinates, LocationService};
+use universe_worlds::WorldService;
+
+#[patch(
+  "/worlds/<world_id>/locations/<location_id>",
+  format = "application/merge-patch+json",
+  data = "<patch_data>"
+)]
+#[tracing::instrument(skip(world_service, location_service, authorizer))]
+pub fn update_location(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  location_id: String,
+  patch_data: Json<PatchData>,
+  world_service: State<Box<dyn WorldService>>,
+  location_service: State<Box<dyn LocationService>>,
+) -> Result<Location, Problem> {
+  debug!("Patch Data: {:?}", patch_data);
+
+  let world = find_editable_world(&**world_service, &authorizer, world_id)?;
+  let location_id = parse_location_id(location_id)?;
+
+  let location =
+    location_service.update_location(&world.identity.id, &location_id, &mut |mut location| {
+      debug!("Patching location details");
+
+      if let Some(name) = patch_data.name {
+        let name = name.trim();
+        if name.is_empty() {
+          let errors = vec![missing_error("name")];
+          warn!("Error patching location: {:?}", errors);
+          return Err(Box::new(ValidationErrors { errors }));
+        }
+        location.name = name.to_owned();
+      }
+
+      if let Some(kind) = patch_data.kind {
+        location.kind = kind.trim().to_owned();
+      }
+
+      if let Some(description) = &patch_data.description {
+        location.description = description.trim().to_owned();
+      }
+
+      // Coordinates of `null` take the location off of the map
+      match patch_data.coordinates {
+        None => {}
+        Some(NewCoordinates::Clear) => location.coordinates = None,
+        Some(NewCoordinates::Set(coordinates)) => location.coordinates = Some(coordinates),
+      }
+
+      Ok(location)
+    })?;
+
+  Ok(Location::new(&location, &world))
+}
+
+/// Struct representing the input data for updating a location.
+///
+/// Locations are moved around the tree with their own endpoint, so the parent can't be changed
+/// here.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchData<'a> {
+  pub name: Option<&'a str>,
+  pub kind: Option<&'a str>,
+  pub description: Option<String>,
+  #[serde(default, deserialize_with = "deserialize_coordinates")]
+  pub coordinates: Option<NewCoordinates>,
+}
+
+/// Where on the map of the world a location is to be
+#[derive(Debug, Clone, Copy)]
+pub enum NewCoordinates {
+  /// The location was given explicit `null` coordinates, so is no longer on the map
+  Clear,
+  /// The location is to be at these coordinates
+  Set(Coordinates),
+}
+
+/// Deserialize the coordinates of a location, so that an explicit `null` can be told apart from
+/// the field being missing
+fn deserialize_coordinates<'de, D>(deserializer: D) -> Result<Option<NewCoordinates>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let coordinates: Option<Coordinates> = Deserialize::deserialize(deserializer)?;
+  Ok(Some(match coordinates {
+    None => NewCoordinates::Clear,
+    Some(coordinates) => NewCoordinates::Set(coordinates),
+  }))
+}
@@ -0,0 +1,90 @@
+use super::model::{CreatedLocation, Location};
+use super::problems::unknown_parent_error;
+use crate::authentication::Authorizer;
+use crate::problem::{missing_error, Problem, ValidationError};
+use crate::request_id::RequestId;
+use crate::worlds::access::find_editable_world;
+use rocket::{post, State};
+use rocket_contrib::json::Json;
+use serde::Deserialize;
+use tracing::debug;
+use universe_locations::{Coordinates, LocationData, LocationID, LocationService};
+use universe_worlds::{WorldID, WorldService};
+
+#[post("/worlds/<world_id>/locations", data = "<new_location>")]
+#[tracing::instrument(skip(world_service, location_service, authorizer))]
+pub fn create_location(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  new_location: Json<NewLocation>,
+  world_service: State<Box<dyn WorldService>>,
+  location_service: State<Box<dyn LocationService>>,
+) -> Result<CreatedLocation, Problem> {
+  debug!("New Location: {:?}", new_location);
+
+  let world = find_editable_world(&**world_service, &authorizer, world_id)?;
+
+  let location = new_location
+    .into_inner()
+    .into_location_data(world.identity.id.clone())?;
+  debug!("Location Data: {:?}", location);
+
+  let result = location_service.create_location(location)?;
+  debug!("Created location: {:?}", result);
+
+  Ok(CreatedLocation {
+    location: Location::new(&result, &world),
+  })
+}
+
+/// Struct representing the input data for creating a new location
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct NewLocation<'a> {
+  pub name: Option<&'a str>,
+  pub kind: Option<&'a str>,
+  pub description: Option<String>,
+  pub parent: Option<&'a str>,
+  pub coordinates: Option<Coordinates>,
+}
+
+impl<'a> NewLocation<'a> {
+  /// Convert the incoming details into the data for a location of the given world.
+  ///
+  /// # Arguments
+  /// * `world` The ID of the world that the location is to belong to
+  ///
+  /// # Returns
+  /// The location data, or the validation errors with the incoming details
+  fn into_location_data(self, world: WorldID) -> Result<LocationData, Vec<ValidationError>> {
+    let name = self
+      .name
+      .map(|name| name.trim())
+      .filter(|name| !name.is_empty())
+      .ok_or_else(|| missing_error("name"));
+    let parent: Result<Option<LocationID>, ValidationError> = match self.parent {
+      None => Ok(None),
+      Some(parent) => parent.parse().map(Some).map_err(|_| unknown_parent_error()),
+    };
+
+    match (name, parent) {
+      (Ok(name), Ok(parent)) => Ok(LocationData {
+        world,
+        parent,
+        name: name.to_owned(),
+        kind: self.kind.unwrap_or("").trim().to_owned(),
+        description: self.description.as_deref().unwrap_or("").trim().to_owned(),
+        coordinates: self.coordinates,
+      }),
+      (name, parent) => {
+        let errors = vec![name.err(), parent.err()]
+          .into_iter()
+          .filter_map(|v| v)
+          .collect();
+
+        Err(errors)
+      }
+    }
+  }
+}
@@ -0,0 +1,108 @@
+use crate::problem::{
+  unexpected_error, validation_error, Problem, ValidationError, ValidationErrors,
+};
+use crate::worlds::problems::unknown_world_problem;
+use universe_locations::*;
+
+/// Helper to build a Problem response for an unknown location
+pub fn unknown_location_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:locations/problems/unknown-location".to_owned(),
+    title: "The requested location could not be found".to_owned(),
+    status: 404,
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Problem response for when a location can't be deleted because other
+/// locations sit inside it
+pub fn location_has_children_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:locations/problems/has-children".to_owned(),
+    title: "The location has other locations inside it".to_owned(),
+    status: 409,
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Problem response for when a location was modified by someone else
+pub fn optimistic_lock_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:locations/problems/optimistic-lock-failure".to_owned(),
+    title: "The location was modified by another request".to_owned(),
+    status: 409,
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Validation Error for a parent location that isn't in the same world
+pub fn unknown_parent_error() -> ValidationError {
+  (&LocationValidationError::UnknownParent).into()
+}
+
+/// Helper to build a Validation Error for a depth to list descendants to that isn't positive
+pub fn invalid_depth_error() -> ValidationError {
+  ValidationError {
+    r#type: "tag:universe,2020:locations/validation-errors/depth/invalid".to_owned(),
+    title: "The depth must be at least 1".to_owned(),
+    field: "depth".to_owned(),
+  }
+}
+
+impl From<&LocationValidationError> for ValidationError {
+  fn from(e: &LocationValidationError) -> Self {
+    match e {
+      LocationValidationError::UnknownParent => ValidationError {
+        r#type: "tag:universe,2020:locations/validation-errors/parent/unknown".to_owned(),
+        title: "The parent location is not a location of the world".to_owned(),
+        field: "parent".to_owned(),
+      },
+      LocationValidationError::ParentCycle => ValidationError {
+        r#type: "tag:universe,2020:locations/validation-errors/parent/cycle".to_owned(),
+        title: "A location may not be moved inside itself or any location within it".to_owned(),
+        field: "parent".to_owned(),
+      },
+    }
+  }
+}
+
+impl From<CreateLocationError> for Problem {
+  fn from(e: CreateLocationError) -> Self {
+    match e {
+      CreateLocationError::ValidationError(errors) => {
+        validation_error(errors.iter().map(|e| e.into()).collect())
+      }
+      CreateLocationError::UnknownWorld => unknown_world_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<UpdateLocationError> for Problem {
+  fn from(e: UpdateLocationError) -> Self {
+    match e {
+      UpdateLocationError::ValidationError(errors) => {
+        validation_error(errors.iter().map(|e| e.into()).collect())
+      }
+      UpdateLocationError::UnknownLocation => unknown_location_problem(),
+      UpdateLocationError::OptimisticLockFailure => optimistic_lock_problem(),
+      UpdateLocationError::UpdateError(e) if e.is::<ValidationErrors>() => e
+        .downcast_ref::<ValidationErrors>()
+        .unwrap()
+        .errors
+        .clone()
+        .into(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<DeleteLocationError> for Problem {
+  fn from(e: DeleteLocationError) -> Self {
+    match e {
+      DeleteLocationError::UnknownLocation => unknown_location_problem(),
+      DeleteLocationError::HasChildren => location_has_children_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
@@ -0,0 +1,19 @@
+use super::delete::*;
+use super::get::*;
+use super::patch::*;
+use super::post::*;
+use super::tree::*;
+use rocket::{routes, Route};
+
+pub fn routes() -> Vec<Route> {
+  routes![
+    list_locations,
+    get_location,
+    get_location_ancestors,
+    get_location_descendants,
+    create_location,
+    update_location,
+    move_location,
+    delete_location
+  ]
+}
@@ -0,0 +1,125 @@
+use super::model::{Descendant, Location, LocationPage};
+use super::problems::{invalid_depth_error, unknown_parent_error};
+use super::world::{find_location, parse_location_id};
+use crate::authentication::{ApiAccessToken, Authorizer};
+use crate::problem::{missing_error, validation_error, Problem};
+use crate::request_id::RequestId;
+use crate::worlds::access::{find_editable_world, find_visible_world};
+use rocket::{get, post, State};
+use rocket_contrib::json::Json;
+use serde::{Deserialize, Deserializer};
+use tracing::{debug, warn};
+use universe_locations::{LocationData, LocationID, LocationService};
+use universe_worlds::WorldService;
+
+#[get("/worlds/<world_id>/locations/<location_id>/ancestors")]
+#[tracing::instrument(skip(world_service, location_service))]
+pub fn get_location_ancestors(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  location_id: String,
+  world_service: State<Box<dyn WorldService>>,
+  location_service: State<Box<dyn LocationService>>,
+) -> Result<LocationPage<Location>, Problem> {
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+  let location = find_location(&**location_service, &world, location_id)?;
+
+  let ancestors =
+    location_service.get_location_ancestors(&world.identity.id, &location.identity.id);
+  Ok(LocationPage::from_list(ancestors, &world))
+}
+
+#[allow(clippy::too_many_arguments)]
+#[get("/worlds/<world_id>/locations/<location_id>/descendants?<depth>")]
+#[tracing::instrument(skip(world_service, location_service))]
+pub fn get_location_descendants(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  location_id: String,
+  depth: Option<u32>,
+  world_service: State<Box<dyn WorldService>>,
+  location_service: State<Box<dyn LocationService>>,
+) -> Result<LocationPage<Descendant>, Problem> {
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+  let location = find_location(&**location_service, &world, location_id)?;
+
+  if depth == Some(0) {
+    return Err(validation_error(vec![invalid_depth_error()]));
+  }
+
+  let descendants =
+    location_service.get_location_descendants(&world.identity.id, &location.identity.id, depth);
+  Ok(LocationPage::from_descendants(descendants, &world))
+}
+
+#[post(
+  "/worlds/<world_id>/locations/<location_id>/move",
+  data = "<move_data>"
+)]
+#[tracing::instrument(skip(world_service, location_service, authorizer))]
+pub fn move_location(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  location_id: String,
+  move_data: Json<MoveData>,
+  world_service: State<Box<dyn WorldService>>,
+  location_service: State<Box<dyn LocationService>>,
+) -> Result<Location, Problem> {
+  debug!("Move Data: {:?}", move_data);
+
+  let world = find_editable_world(&**world_service, &authorizer, world_id)?;
+  let location_id = parse_location_id(location_id)?;
+
+  let parent: Option<LocationID> = match move_data.parent {
+    None => return Err(validation_error(vec![missing_error("parent")])),
+    Some(NewParent::TopLevel) => None,
+    Some(NewParent::Location(parent)) => Some(parent.parse().map_err(|e| {
+      warn!("Invalid Location ID: {}", e);
+      validation_error(vec![unknown_parent_error()])
+    })?),
+  };
+
+  // Everything inside the location lives by reference to it, so moves along with it
+  let location =
+    location_service.update_location(&world.identity.id, &location_id, &mut |location| {
+      Ok(LocationData {
+        parent: parent.clone(),
+        ..location
+      })
+    })?;
+  debug!("Moved location: {:?}", location);
+
+  Ok(Location::new(&location, &world))
+}
+
+/// Struct representing the input data for moving a location
+#[derive(Deserialize, Debug)]
+pub struct MoveData<'a> {
+  #[serde(default, borrow, deserialize_with = "deserialize_parent")]
+  pub parent: Option<NewParent<'a>>,
+}
+
+/// Where in the tree of locations a location is to be moved to
+#[derive(Debug, Clone, Copy)]
+pub enum NewParent<'a> {
+  /// The location was given an explicit `null` parent, so is to be moved to the top of the tree
+  TopLevel,
+  /// The location is to be moved inside the location with this ID
+  Location(&'a str),
+}
+
+/// Deserialize the new parent of a location, so that an explicit `null` can be told apart from the
+/// field being missing
+fn deserialize_parent<'de: 'a, 'a, D>(deserializer: D) -> Result<Option<NewParent<'a>>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let parent: Option<&'a str> = Deserialize::deserialize(deserializer)?;
+  Ok(Some(match parent {
+    None => NewParent::TopLevel,
+    Some(parent) => NewParent::Location(parent),
+  }))
+}
@@ -0,0 +1,34 @@
+use super::problems::unknown_location_problem;
+use crate::problem::Problem;
+use tracing::warn;
+use universe_locations::{LocationEntity, LocationID, LocationService};
+use universe_worlds::WorldEntity;
+
+/// Parse the ID of a location, as provided in the URL
+pub(super) fn parse_location_id(location_id: String) -> Result<LocationID, Problem> {
+  location_id.parse().map_err(|e| {
+    warn!("Invalid Location ID: {}", e);
+    unknown_location_problem()
+  })
+}
+
+/// Look up a location of a world
+///
+/// # Arguments
+/// * `location_service` The location service to look the location up with
+/// * `world` The world that the location belongs to
+/// * `location_id` The ID of the location, as provided in the URL
+///
+/// # Returns
+/// The location, or a Problem if it couldn't be found
+pub(super) fn find_location(
+  location_service: &dyn LocationService,
+  world: &WorldEntity,
+  location_id: String,
+) -> Result<LocationEntity, Problem> {
+  let location_id = parse_location_id(location_id)?;
+
+  location_service
+    .get_location_by_id(&world.identity.id, &location_id)
+    .ok_or_else(unknown_location_problem)
+}
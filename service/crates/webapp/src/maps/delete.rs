@@ -0,0 +1,25 @@
+use super::world::parse_map_id;
+use crate::problem::Problem;
+use crate::worlds::access::find_editable_world;
+use crate::{authentication::Authorizer, request_id::RequestId};
+use rocket::{delete, http::Status, State};
+use universe_maps::MapService;
+use universe_worlds::WorldService;
+
+#[delete("/worlds/<world_id>/maps/<map_id>")]
+#[tracing::instrument(skip(world_service, map_service, authorizer))]
+pub fn delete_map(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  map_id: String,
+  world_service: State<Box<dyn WorldService>>,
+  map_service: State<Box<dyn MapService>>,
+) -> Result<Status, Problem> {
+  let world = find_editable_world(&**world_service, &authorizer, world_id)?;
+  let map_id = parse_map_id(map_id)?;
+
+  map_service.delete_map(&world.identity.id, &map_id)?;
+
+  Ok(Status::NoContent)
+}
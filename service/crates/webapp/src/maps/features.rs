@@ -0,0 +1,231 @@
+use super::model::{CreatedFeature, Feature, FeatureCollection};
+use super::problems::{invalid_kind_error, unknown_link_error, unsupported_geometry_error};
+use super::world::{find_feature, find_map, parse_feature_id, parse_map_id};
+use crate::authentication::{ApiAccessToken, Authorizer};
+use crate::problem::{missing_error, validation_error, Problem, ValidationError};
+use crate::request_id::RequestId;
+use crate::worlds::access::{find_editable_world, find_visible_world};
+use rocket::{delete, get, http::Status, post, put, State};
+use rocket_contrib::json::Json;
+use serde::Deserialize;
+use tracing::{debug, warn};
+use universe_maps::{
+  FeatureData, FeatureKind, FeatureLink, Geometry, MapFeatureService, MapID, MapService,
+};
+use universe_worlds::{WorldID, WorldService};
+
+#[allow(clippy::too_many_arguments)]
+#[get("/worlds/<world_id>/maps/<map_id>/features?<kind>")]
+#[tracing::instrument(skip(world_service, map_service, map_feature_service))]
+pub fn list_features(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  map_id: String,
+  kind: Option<String>,
+  world_service: State<Box<dyn WorldService>>,
+  map_service: State<Box<dyn MapService>>,
+  map_feature_service: State<Box<dyn MapFeatureService>>,
+) -> Result<FeatureCollection, Problem> {
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+  let map = find_map(&**map_service, &world, map_id)?;
+
+  let kind: Option<FeatureKind> = match kind {
+    None => None,
+    Some(kind) => Some(kind.parse().map_err(|e| {
+      warn!("Invalid Feature Kind: {}", e);
+      validation_error(vec![invalid_kind_error()])
+    })?),
+  };
+
+  let features = map_feature_service.list_features(&world.identity.id, &map.identity.id, kind);
+  Ok(FeatureCollection::new(&map, features, &world))
+}
+
+#[allow(clippy::too_many_arguments)]
+#[get("/worlds/<world_id>/maps/<map_id>/features/<feature_id>")]
+#[tracing::instrument(skip(world_service, map_service, map_feature_service))]
+pub fn get_feature(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  map_id: String,
+  feature_id: String,
+  world_service: State<Box<dyn WorldService>>,
+  map_service: State<Box<dyn MapService>>,
+  map_feature_service: State<Box<dyn MapFeatureService>>,
+) -> Result<Feature, Problem> {
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+  let map = find_map(&**map_service, &world, map_id)?;
+  let feature = find_feature(&**map_feature_service, &map, feature_id)?;
+
+  Ok(Feature::new(&feature, &world))
+}
+
+#[post("/worlds/<world_id>/maps/<map_id>/features", data = "<new_feature>")]
+#[tracing::instrument(skip(world_service, map_feature_service, authorizer))]
+pub fn create_feature(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  map_id: String,
+  new_feature: Json<NewFeature>,
+  world_service: State<Box<dyn WorldService>>,
+  map_feature_service: State<Box<dyn MapFeatureService>>,
+) -> Result<CreatedFeature, Problem> {
+  debug!("New Feature: {:?}", new_feature);
+
+  let world = find_editable_world(&**world_service, &authorizer, world_id)?;
+  let map_id = parse_map_id(map_id)?;
+
+  let feature = new_feature
+    .into_inner()
+    .into_feature_data(world.identity.id.clone(), map_id)?;
+  debug!("Feature Data: {:?}", feature);
+
+  let result = map_feature_service.create_feature(feature)?;
+  debug!("Created feature: {:?}", result);
+
+  Ok(CreatedFeature {
+    feature: Feature::new(&result, &world),
+  })
+}
+
+#[allow(clippy::too_many_arguments)]
+#[put(
+  "/worlds/<world_id>/maps/<map_id>/features/<feature_id>",
+  data = "<new_feature>"
+)]
+#[tracing::instrument(skip(world_service, map_feature_service, authorizer))]
+pub fn replace_feature(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  map_id: String,
+  feature_id: String,
+  new_feature: Json<NewFeature>,
+  world_service: State<Box<dyn WorldService>>,
+  map_feature_service: State<Box<dyn MapFeatureService>>,
+) -> Result<Feature, Problem> {
+  debug!("Replacement Feature: {:?}", new_feature);
+
+  let world = find_editable_world(&**world_service, &authorizer, world_id)?;
+  let map_id = parse_map_id(map_id)?;
+  let feature_id = parse_feature_id(feature_id)?;
+
+  let replacement = new_feature
+    .into_inner()
+    .into_feature_data(world.identity.id.clone(), map_id.clone())?;
+
+  let feature =
+    map_feature_service.update_feature(&world.identity.id, &map_id, &feature_id, &mut |_| {
+      Ok(replacement.clone())
+    })?;
+  debug!("Replaced feature: {:?}", feature);
+
+  Ok(Feature::new(&feature, &world))
+}
+
+#[delete("/worlds/<world_id>/maps/<map_id>/features/<feature_id>")]
+#[tracing::instrument(skip(world_service, map_feature_service, authorizer))]
+pub fn delete_feature(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  map_id: String,
+  feature_id: String,
+  world_service: State<Box<dyn WorldService>>,
+  map_feature_service: State<Box<dyn MapFeatureService>>,
+) -> Result<Status, Problem> {
+  let world = find_editable_world(&**world_service, &authorizer, world_id)?;
+  let map_id = parse_map_id(map_id)?;
+  let feature_id = parse_feature_id(feature_id)?;
+
+  map_feature_service.delete_feature(&world.identity.id, &map_id, &feature_id)?;
+
+  Ok(Status::NoContent)
+}
+
+/// Struct representing the input data for a feature on a map, as a GeoJSON Feature in image pixel
+/// coordinates
+#[derive(Deserialize, Debug)]
+pub struct NewFeature {
+  pub geometry: Option<serde_json::Value>,
+  pub properties: Option<NewProperties>,
+}
+
+/// The properties of an incoming GeoJSON Feature
+#[derive(Deserialize, Debug)]
+pub struct NewProperties {
+  pub name: Option<String>,
+  pub link: Option<NewLink>,
+}
+
+/// The entity that an incoming feature is to link to
+#[derive(Deserialize, Debug)]
+pub struct NewLink {
+  /// What sort of entity to link to. One of "location", "character" or "article"
+  pub r#type: String,
+  pub id: String,
+}
+
+impl NewLink {
+  /// Convert the incoming link into the entity to link to
+  fn into_feature_link(self) -> Option<FeatureLink> {
+    FeatureLink::new(&self.r#type, &self.id)
+  }
+}
+
+impl NewFeature {
+  /// Convert the incoming details into the data for a feature on the given map.
+  ///
+  /// # Arguments
+  /// * `world` The ID of the world that the map belongs to
+  /// * `map` The ID of the map that the feature is to be placed on
+  ///
+  /// # Returns
+  /// The feature data, or the validation errors with the incoming details
+  fn into_feature_data(
+    self,
+    world: WorldID,
+    map: MapID,
+  ) -> Result<FeatureData, Vec<ValidationError>> {
+    let geometry: Result<Geometry, ValidationError> = match self.geometry {
+      None | Some(serde_json::Value::Null) => Err(missing_error("geometry")),
+      Some(geometry) => serde_json::from_value(geometry).map_err(|e| {
+        warn!("Invalid geometry: {}", e);
+        unsupported_geometry_error()
+      }),
+    };
+
+    let (name, link) = match self.properties {
+      None => (None, None),
+      Some(properties) => (properties.name, properties.link),
+    };
+    let link: Result<Option<FeatureLink>, ValidationError> = match link {
+      None => Ok(None),
+      Some(link) => link
+        .into_feature_link()
+        .map(Some)
+        .ok_or_else(unknown_link_error),
+    };
+
+    match (geometry, link) {
+      (Ok(geometry), Ok(link)) => Ok(FeatureData {
+        world,
+        map,
+        name: name.as_deref().unwrap_or("").trim().to_owned(),
+        geometry,
+        link,
+      }),
+      (geometry, link) => {
+        let errors = vec![geometry.err(), link.err()]
+          .into_iter()
+          .filter_map(|v| v)
+          .collect();
+
+        Err(errors)
+      }
+    }
+  }
+}
@@ -0,0 +1,48 @@
+use super::model::{Map, MapPage};
+use super::world::find_map;
+use crate::authentication::ApiAccessToken;
+use crate::problem::Problem;
+use crate::request_id::RequestId;
+use crate::worlds::access::find_visible_world;
+use rocket::{get, State};
+use universe_entity::Pagination;
+use universe_maps::MapService;
+use universe_worlds::WorldService;
+
+#[get("/worlds/<world_id>/maps?<offset>&<limit>")]
+#[tracing::instrument(skip(world_service, map_service))]
+pub fn list_maps(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  offset: Option<u32>,
+  limit: Option<u32>,
+  world_service: State<Box<dyn WorldService>>,
+  map_service: State<Box<dyn MapService>>,
+) -> Result<MapPage, Problem> {
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+
+  let pagination = Pagination {
+    offset: offset.unwrap_or(0),
+    limit: limit.unwrap_or(10),
+  };
+
+  let maps = map_service.list_maps(&world.identity.id, pagination);
+  Ok(MapPage::new(maps, &world))
+}
+
+#[get("/worlds/<world_id>/maps/<map_id>")]
+#[tracing::instrument(skip(world_service, map_service))]
+pub fn get_map(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  map_id: String,
+  world_service: State<Box<dyn WorldService>>,
+  map_service: State<Box<dyn MapService>>,
+) -> Result<Map, Problem> {
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+  let map = find_map(&**map_service, &world, map_id)?;
+
+  Ok(Map::new(&map, &world))
+}
@@ -0,0 +1,75 @@
+use super::model::{Map, MapImage};
+use super::problems::{image_too_large_problem, unknown_image_problem};
+use super::world::{find_map, parse_map_id};
+use crate::authentication::{ApiAccessToken, Authorizer};
+use crate::problem::{unexpected_error, Problem};
+use crate::request_id::RequestId;
+use crate::worlds::access::{find_editable_world, find_visible_world};
+use rocket::{get, put, Data, State};
+use std::io::Read;
+use tracing::{debug, warn};
+use universe_maps::{MapImageID, MapService, MAX_MAP_IMAGE_BYTES};
+use universe_worlds::WorldService;
+
+#[put("/worlds/<world_id>/maps/<map_id>/image", data = "<image>")]
+#[tracing::instrument(skip(world_service, map_service, authorizer, image))]
+pub fn put_map_image(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  map_id: String,
+  image: Data,
+  world_service: State<Box<dyn WorldService>>,
+  map_service: State<Box<dyn MapService>>,
+) -> Result<Map, Problem> {
+  let world = find_editable_world(&**world_service, &authorizer, world_id)?;
+  let map_id = parse_map_id(map_id)?;
+
+  // Read one byte more than we allow, so that we can tell if the upload was too large without
+  // reading the whole thing into memory
+  let mut bytes = vec![];
+  image
+    .open()
+    .take(MAX_MAP_IMAGE_BYTES as u64 + 1)
+    .read_to_end(&mut bytes)
+    .map_err(|e| {
+      warn!("Failed to read map image: {}", e);
+      unexpected_error()
+    })?;
+  if bytes.len() > MAX_MAP_IMAGE_BYTES {
+    return Err(image_too_large_problem());
+  }
+
+  let map = map_service.set_map_image(&world.identity.id, &map_id, &bytes)?;
+  debug!("Updated image of map: {:?}", map);
+
+  Ok(Map::new(&map, &world))
+}
+
+#[get("/worlds/<world_id>/maps/<map_id>/image/<image_id>")]
+#[tracing::instrument(skip(world_service, map_service))]
+pub fn get_map_image(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  map_id: String,
+  image_id: String,
+  world_service: State<Box<dyn WorldService>>,
+  map_service: State<Box<dyn MapService>>,
+) -> Result<MapImage, Problem> {
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+  let map = find_map(&**map_service, &world, map_id)?;
+  let image_id: MapImageID = image_id.parse().map_err(|e| {
+    warn!("Invalid Map Image ID: {}", e);
+    unknown_image_problem()
+  })?;
+
+  let blob = map_service
+    .get_map_image(&world.identity.id, &map.identity.id, &image_id)
+    .ok_or_else(unknown_image_problem)?;
+
+  Ok(MapImage {
+    blob,
+    visibility: world.data.visibility,
+  })
+}
@@ -0,0 +1,12 @@
+mod delete;
+mod features;
+mod get;
+mod image;
+mod model;
+mod patch;
+mod post;
+mod problems;
+mod routes;
+mod world;
+
+pub use routes::routes;
@@ -0,0 +1,378 @@
+use crate::headers::*;
+use crate::page::SearchPage;
+use chrono::{DateTime, Utc};
+use rocket::{
+  http::{
+    hyper::header::{CacheControl, CacheDirective, ETag, EntityTag, HttpDate, LastModified},
+    ContentType, Status,
+  },
+  response::{Responder, Response},
+  Request,
+};
+use rocket_contrib::json::Json;
+use serde::Serialize;
+use std::io::Cursor;
+use universe_entity::Page;
+use universe_maps::{
+  FeatureEntity, FeatureID, FeatureKind, FeatureLink, Geometry, MapEntity, MapID,
+};
+use universe_storage::Blob;
+use universe_worlds::{Visibility, WorldEntity, WorldID};
+use uuid::Uuid;
+
+/// Work out who may cache a response about the maps of a world
+fn cache_scope(visibility: Visibility) -> CacheDirective {
+  // Maps of private worlds must never be stored in a shared cache
+  match visibility {
+    Visibility::Private => CacheDirective::Private,
+    _ => CacheDirective::Public,
+  }
+}
+
+/// The media type of GeoJSON documents
+fn geo_json() -> ContentType {
+  ContentType::new("application", "geo+json")
+}
+
+/// Representation of the image that a Map is drawn on
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Image {
+  /// Where to download the image from
+  pub href: String,
+  pub content_type: String,
+  pub width: u32,
+  pub height: u32,
+}
+
+/// Representation of a Map to return over the API
+#[derive(Debug, Serialize)]
+pub struct Map {
+  pub id: MapID,
+  pub world: WorldID,
+  pub name: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub image: Option<Image>,
+  /// Where to load the features on the map from, as GeoJSON
+  pub features: String,
+  #[serde(skip_serializing)]
+  version: Uuid,
+  #[serde(skip_serializing)]
+  updated: DateTime<Utc>,
+  #[serde(skip_serializing)]
+  visibility: Visibility,
+}
+
+impl Map {
+  /// Build the representation of a map of a world
+  ///
+  /// # Arguments
+  /// * `map` The map
+  /// * `world` The world that the map belongs to
+  pub fn new(map: &MapEntity, world: &WorldEntity) -> Self {
+    let href = format!("/worlds/{}/maps/{}", map.data.world, map.identity.id);
+
+    Map {
+      id: map.identity.id.clone(),
+      world: map.data.world.clone(),
+      name: map.data.name.clone(),
+      image: map.data.image.as_ref().map(|image| Image {
+        href: format!("{}/image/{}", href, image.id),
+        content_type: image.content_type.clone(),
+        width: image.width,
+        height: image.height,
+      }),
+      features: format!("{}/features", href),
+      version: map.identity.version,
+      updated: map.identity.updated,
+      visibility: world.data.visibility,
+    }
+  }
+}
+
+impl<'a> Responder<'a> for Map {
+  /// Generate a Rocket response for the Map
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(Link::from_href(format!("/worlds/{}/maps/{}", self.world, self.id)).with_rel("self"))
+      .header(AcceptPatch("application/merge-patch+json"))
+      .header(ETag(EntityTag::new(false, self.version.to_string())))
+      .header(LastModified(HttpDate(time::at_utc(time::Timespec::new(
+        self.updated.timestamp(),
+        0,
+      )))))
+      .header(CacheControl(vec![
+        cache_scope(self.visibility),
+        CacheDirective::MaxAge(3600),
+      ]))
+      .ok()
+  }
+}
+
+/// Representation of a Map that has just been created
+#[derive(Debug, Serialize)]
+pub struct CreatedMap {
+  #[serde(flatten)]
+  pub map: Map,
+}
+
+impl<'a> Responder<'a> for CreatedMap {
+  /// Generate a Rocket response for the newly created Map
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(
+        Link::from_href(format!("/worlds/{}/maps/{}", self.map.world, self.map.id))
+          .with_rel("canonical"),
+      )
+      .ok()
+  }
+}
+
+/// Representation of a list of the Maps of a World
+#[derive(Debug, Serialize)]
+pub struct MapPage {
+  #[serde(flatten)]
+  pub page: SearchPage<Map>,
+  #[serde(skip_serializing)]
+  visibility: Visibility,
+}
+
+impl MapPage {
+  /// Build the representation of a page of the maps of a world
+  ///
+  /// # Arguments
+  /// * `maps` The page of maps
+  /// * `world` The world that the maps belong to
+  pub fn new(maps: Page<MapEntity>, world: &WorldEntity) -> Self {
+    MapPage {
+      page: SearchPage {
+        entries: maps
+          .entries
+          .iter()
+          .map(|map| Map::new(map, world))
+          .collect(),
+        total: maps.total,
+      },
+      visibility: world.data.visibility,
+    }
+  }
+}
+
+impl<'a> Responder<'a> for MapPage {
+  /// Generate a Rocket response for the list of Maps
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(CacheControl(vec![
+        cache_scope(self.visibility),
+        CacheDirective::MaxAge(3600),
+      ]))
+      // Members of private worlds can see maps that nobody else can
+      .raw_header("Vary", "Authorization")
+      .ok()
+  }
+}
+
+/// The image of a map, to return as the raw image data
+pub struct MapImage {
+  pub blob: Blob,
+  pub visibility: Visibility,
+}
+
+impl<'a> Responder<'a> for MapImage {
+  /// Generate a Rocket response for the map image
+  fn respond_to(self, _: &Request) -> Result<Response<'a>, Status> {
+    // Map images never change once uploaded, since a new upload gets a new URL
+    Response::build()
+      .raw_header("Content-Type", self.blob.content_type)
+      .header(CacheControl(vec![
+        cache_scope(self.visibility),
+        CacheDirective::MaxAge(31_536_000),
+        CacheDirective::Extension("immutable".to_owned(), None),
+      ]))
+      .sized_body(Cursor::new(self.blob.data))
+      .ok()
+  }
+}
+
+/// Representation of the entity that a feature on a map links to
+#[derive(Debug, Serialize)]
+pub struct LinkedEntity {
+  /// What sort of entity is linked to. One of "location", "character" or "article"
+  pub r#type: &'static str,
+  pub id: String,
+  pub href: String,
+}
+
+/// The properties of a GeoJSON feature that represents a feature on a map
+#[derive(Debug, Serialize)]
+pub struct Properties {
+  pub kind: FeatureKind,
+  pub name: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub link: Option<LinkedEntity>,
+}
+
+/// Representation of a feature on a Map, as a GeoJSON Feature in image pixel coordinates
+#[derive(Debug, Serialize)]
+pub struct Feature {
+  /// Always "Feature", as required by GeoJSON
+  pub r#type: &'static str,
+  pub id: FeatureID,
+  pub geometry: Geometry,
+  pub properties: Properties,
+  #[serde(skip_serializing)]
+  world: WorldID,
+  #[serde(skip_serializing)]
+  map: MapID,
+  #[serde(skip_serializing)]
+  version: Uuid,
+  #[serde(skip_serializing)]
+  updated: DateTime<Utc>,
+  #[serde(skip_serializing)]
+  visibility: Visibility,
+}
+
+impl Feature {
+  /// Build the representation of a feature on a map
+  ///
+  /// # Arguments
+  /// * `feature` The feature
+  /// * `world` The world that the map belongs to
+  pub fn new(feature: &FeatureEntity, world: &WorldEntity) -> Self {
+    let world_id = &feature.data.world;
+
+    Feature {
+      r#type: "Feature",
+      id: feature.identity.id.clone(),
+      geometry: feature.data.geometry.clone(),
+      properties: Properties {
+        kind: feature.data.geometry.kind(),
+        name: feature.data.name.clone(),
+        link: feature.data.link.as_ref().map(|link| match link {
+          FeatureLink::Location(id) => LinkedEntity {
+            r#type: "location",
+            id: id.to_string(),
+            href: format!("/worlds/{}/locations/{}", world_id, id),
+          },
+          FeatureLink::Character(id) => LinkedEntity {
+            r#type: "character",
+            id: id.to_string(),
+            href: format!("/worlds/{}/characters/{}", world_id, id),
+          },
+          FeatureLink::Article(id) => LinkedEntity {
+            r#type: "article",
+            id: id.to_string(),
+            href: format!("/worlds/{}/articles/{}", world_id, id),
+          },
+        }),
+      },
+      world: world_id.clone(),
+      map: feature.data.map.clone(),
+      version: feature.identity.version,
+      updated: feature.identity.updated,
+      visibility: world.data.visibility,
+    }
+  }
+
+  /// The URL of the feature
+  fn href(&self) -> String {
+    format!(
+      "/worlds/{}/maps/{}/features/{}",
+      self.world, self.map, self.id
+    )
+  }
+}
+
+impl<'a> Responder<'a> for Feature {
+  /// Generate a Rocket response for the Feature
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(geo_json())
+      .header(Link::from_href(self.href()).with_rel("self"))
+      .header(ETag(EntityTag::new(false, self.version.to_string())))
+      .header(LastModified(HttpDate(time::at_utc(time::Timespec::new(
+        self.updated.timestamp(),
+        0,
+      )))))
+      .header(CacheControl(vec![
+        cache_scope(self.visibility),
+        CacheDirective::MaxAge(3600),
+      ]))
+      .ok()
+  }
+}
+
+/// Representation of a feature that has just been placed on a Map
+#[derive(Debug, Serialize)]
+pub struct CreatedFeature {
+  #[serde(flatten)]
+  pub feature: Feature,
+}
+
+impl<'a> Responder<'a> for CreatedFeature {
+  /// Generate a Rocket response for the newly created Feature
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(geo_json())
+      .header(Link::from_href(self.feature.href()).with_rel("canonical"))
+      .ok()
+  }
+}
+
+/// Representation of the features on a Map, as a GeoJSON Feature Collection
+#[derive(Debug, Serialize)]
+pub struct FeatureCollection {
+  /// Always "FeatureCollection", as required by GeoJSON
+  pub r#type: &'static str,
+  /// The extent of the map image, as `[minX, minY, maxX, maxY]`, if the map has one
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub bbox: Option<[f64; 4]>,
+  pub features: Vec<Feature>,
+  #[serde(skip_serializing)]
+  visibility: Visibility,
+}
+
+impl FeatureCollection {
+  /// Build the representation of the features on a map
+  ///
+  /// # Arguments
+  /// * `map` The map that the features are on
+  /// * `features` The features
+  /// * `world` The world that the map belongs to
+  pub fn new(map: &MapEntity, features: Vec<FeatureEntity>, world: &WorldEntity) -> Self {
+    FeatureCollection {
+      r#type: "FeatureCollection",
+      bbox: map
+        .data
+        .image
+        .as_ref()
+        .map(|image| [0.0, 0.0, f64::from(image.width), f64::from(image.height)]),
+      features: features
+        .iter()
+        .map(|feature| Feature::new(feature, world))
+        .collect(),
+      visibility: world.data.visibility,
+    }
+  }
+}
+
+impl<'a> Responder<'a> for FeatureCollection {
+  /// Generate a Rocket response for the Feature Collection
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(geo_json())
+      .header(CacheControl(vec![
+        cache_scope(self.visibility),
+        CacheDirective::MaxAge(3600),
+      ]))
+      // Members of private worlds can see maps that nobody else can
+      .raw_header("Vary", "Authorization")
+      .ok()
+  }
+}
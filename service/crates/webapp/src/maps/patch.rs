@@ -0,0 +1,58 @@
+use super::model::Map;
+use super::world::parse_map_id;
+use crate::problem::{missing_error, Problem, ValidationErrors};
+use crate::worlds::access::find_editable_world;
+use crate::{authentication::Authorizer, request_id::RequestId};
+use rocket::{patch, State};
+use rocket_contrib::json::Json;
+use serde::Deserialize;
+use tracing::{debug, warn};
+use universe_maps::MapService;
+use universe_worlds::WorldService;
+
+#[patch(
+  "/worlds/<world_id>/maps/<map_id>",
+  format = "application/merge-patch+json",
+  data = "<patch_data>"
+)]
+#[tracing::instrument(skip(world_service, map_service, authorizer))]
+pub fn update_map(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  map_id: String,
+  patch_data: Json<PatchData>,
+  world_service: State<Box<dyn WorldService>>,
+  map_service: State<Box<dyn MapService>>,
+) -> Result<Map, Problem> {
+  debug!("Patch Data: {:?}", patch_data);
+
+  let world = find_editable_world(&**world_service, &authorizer, world_id)?;
+  let map_id = parse_map_id(map_id)?;
+
+  let map = map_service.update_map(&world.identity.id, &map_id, &mut |mut map| {
+    debug!("Patching map details");
+
+    if let Some(name) = patch_data.name {
+      let name = name.trim();
+      if name.is_empty() {
+        let errors = vec![missing_error("name")];
+        warn!("Error patching map: {:?}", errors);
+        return Err(Box::new(ValidationErrors { errors }));
+      }
+      map.name = name.to_owned();
+    }
+
+    Ok(map)
+  })?;
+
+  Ok(Map::new(&map, &world))
+}
+
+/// Struct representing the input data for updating a map.
+///
+/// The image of a map is replaced by uploading a new one, so can't be changed here.
+#[derive(Deserialize, Debug)]
+pub struct PatchData<'a> {
+  pub name: Option<&'a str>,
+}
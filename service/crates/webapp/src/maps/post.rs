@@ -0,0 +1,50 @@
+use super::model::{CreatedMap, Map};
+use crate::authentication::Authorizer;
+use crate::problem::{missing_error, validation_error, Problem};
+use crate::request_id::RequestId;
+use crate::worlds::access::find_editable_world;
+use rocket::{post, State};
+use rocket_contrib::json::Json;
+use serde::Deserialize;
+use tracing::debug;
+use universe_maps::{MapData, MapService};
+use universe_worlds::WorldService;
+
+#[post("/worlds/<world_id>/maps", data = "<new_map>")]
+#[tracing::instrument(skip(world_service, map_service, authorizer))]
+pub fn create_map(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  new_map: Json<NewMap>,
+  world_service: State<Box<dyn WorldService>>,
+  map_service: State<Box<dyn MapService>>,
+) -> Result<CreatedMap, Problem> {
+  debug!("New Map: {:?}", new_map);
+
+  let world = find_editable_world(&**world_service, &authorizer, world_id)?;
+
+  let name = new_map
+    .name
+    .map(|name| name.trim())
+    .filter(|name| !name.is_empty())
+    .ok_or_else(|| validation_error(vec![missing_error("name")]))?;
+
+  // The image is uploaded separately, once the map exists
+  let result = map_service.create_map(MapData {
+    world: world.identity.id.clone(),
+    name: name.to_owned(),
+    image: None,
+  })?;
+  debug!("Created map: {:?}", result);
+
+  Ok(CreatedMap {
+    map: Map::new(&result, &world),
+  })
+}
+
+/// Struct representing the input data for creating a new map
+#[derive(Deserialize, Debug)]
+pub struct NewMap<'a> {
+  pub name: Option<&'a str>,
+}
@@ -0,0 +1,213 @@
+use crate::problem::{
+  unexpected_error, validation_error, Problem, ValidationError, ValidationErrors,
+};
+use crate::worlds::problems::unknown_world_problem;
+use universe_maps::*;
+
+/// Helper to build a Problem response for an unknown map
+pub fn unknown_map_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:maps/problems/unknown-map".to_owned(),
+    title: "The requested map could not be found".to_owned(),
+    status: 404,
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Problem response for a map that doesn't have the requested image
+pub fn unknown_image_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:maps/problems/unknown-image".to_owned(),
+    title: "The requested map image could not be found".to_owned(),
+    status: 404,
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Problem response for an unknown feature
+pub fn unknown_feature_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:maps/problems/unknown-feature".to_owned(),
+    title: "The requested feature could not be found".to_owned(),
+    status: 404,
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Problem response for a map image that was larger than we accept
+pub fn image_too_large_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:maps/problems/image-too-large".to_owned(),
+    title: format!("The map image may be at most {} bytes", MAX_MAP_IMAGE_BYTES),
+    status: 413,
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Problem response for when a map or feature was modified by someone else
+pub fn optimistic_lock_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:maps/problems/optimistic-lock-failure".to_owned(),
+    title: "The map was modified by another request".to_owned(),
+    status: 409,
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Validation Error for a kind of feature that doesn't exist
+pub fn invalid_kind_error() -> ValidationError {
+  ValidationError {
+    r#type: "tag:universe,2020:maps/validation-errors/kind/invalid".to_owned(),
+    title: "The kind must be one of \"pin\", \"polyline\" or \"region\"".to_owned(),
+    field: "kind".to_owned(),
+  }
+}
+
+/// Helper to build a Validation Error for a geometry that isn't a supported GeoJSON geometry
+pub fn unsupported_geometry_error() -> ValidationError {
+  ValidationError {
+    r#type: "tag:universe,2020:maps/validation-errors/geometry/unsupported".to_owned(),
+    title: "The geometry must be a GeoJSON Point, LineString or Polygon".to_owned(),
+    field: "geometry".to_owned(),
+  }
+}
+
+/// Helper to build a Validation Error for a link to something that isn't an entity of the world
+pub fn unknown_link_error() -> ValidationError {
+  (&FeatureValidationError::UnknownLink).into()
+}
+
+impl From<&FeatureValidationError> for ValidationError {
+  fn from(e: &FeatureValidationError) -> Self {
+    match e {
+      FeatureValidationError::NoImage => ValidationError {
+        r#type: "tag:universe,2020:maps/validation-errors/geometry/no-image".to_owned(),
+        title: "Features can't be placed on a map until it has an image".to_owned(),
+        field: "geometry".to_owned(),
+      },
+      FeatureValidationError::Geometry(GeometryError::OutOfBounds) => ValidationError {
+        r#type: "tag:universe,2020:maps/validation-errors/geometry/out-of-bounds".to_owned(),
+        title: "Every position must be within the map image".to_owned(),
+        field: "geometry".to_owned(),
+      },
+      FeatureValidationError::Geometry(e) => ValidationError {
+        r#type: "tag:universe,2020:maps/validation-errors/geometry/invalid".to_owned(),
+        title: e.to_string(),
+        field: "geometry".to_owned(),
+      },
+      FeatureValidationError::UnknownLink => ValidationError {
+        r#type: "tag:universe,2020:maps/validation-errors/link/unknown".to_owned(),
+        title: "The link is not to a location, character or article of the world".to_owned(),
+        field: "properties.link".to_owned(),
+      },
+    }
+  }
+}
+
+impl From<MapImageError> for Problem {
+  fn from(e: MapImageError) -> Self {
+    match e {
+      MapImageError::TooLarge => image_too_large_problem(),
+      MapImageError::UnsupportedFormat => Problem {
+        r#type: "tag:universe,2020:maps/problems/image-unsupported-format".to_owned(),
+        title: "The map image must be a PNG, JPEG or WebP image".to_owned(),
+        status: 415,
+        ..Default::default()
+      },
+      MapImageError::DimensionsTooLarge => Problem {
+        r#type: "tag:universe,2020:maps/problems/image-dimensions-too-large".to_owned(),
+        title: format!(
+          "The map image may be at most {} pixels wide and high",
+          MAX_MAP_IMAGE_DIMENSION
+        ),
+        status: 422,
+        ..Default::default()
+      },
+      MapImageError::Malformed => Problem {
+        r#type: "tag:universe,2020:maps/problems/image-malformed".to_owned(),
+        title: "The map image could not be read".to_owned(),
+        status: 422,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+impl From<CreateMapError> for Problem {
+  fn from(e: CreateMapError) -> Self {
+    match e {
+      CreateMapError::UnknownWorld => unknown_world_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<UpdateMapError> for Problem {
+  fn from(e: UpdateMapError) -> Self {
+    match e {
+      UpdateMapError::UnknownMap => unknown_map_problem(),
+      UpdateMapError::OptimisticLockFailure => optimistic_lock_problem(),
+      UpdateMapError::UpdateError(e) if e.is::<ValidationErrors>() => e
+        .downcast_ref::<ValidationErrors>()
+        .unwrap()
+        .errors
+        .clone()
+        .into(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<DeleteMapError> for Problem {
+  fn from(e: DeleteMapError) -> Self {
+    match e {
+      DeleteMapError::UnknownMap => unknown_map_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<SetMapImageError> for Problem {
+  fn from(e: SetMapImageError) -> Self {
+    match e {
+      SetMapImageError::UnknownMap => unknown_map_problem(),
+      SetMapImageError::InvalidImage(e) => e.into(),
+      SetMapImageError::OptimisticLockFailure => optimistic_lock_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<CreateFeatureError> for Problem {
+  fn from(e: CreateFeatureError) -> Self {
+    match e {
+      CreateFeatureError::ValidationError(errors) => {
+        validation_error(errors.iter().map(|e| e.into()).collect())
+      }
+      CreateFeatureError::UnknownMap => unknown_map_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<UpdateFeatureError> for Problem {
+  fn from(e: UpdateFeatureError) -> Self {
+    match e {
+      UpdateFeatureError::ValidationError(errors) => {
+        validation_error(errors.iter().map(|e| e.into()).collect())
+      }
+      UpdateFeatureError::UnknownFeature => unknown_feature_problem(),
+      UpdateFeatureError::OptimisticLockFailure => optimistic_lock_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<DeleteFeatureError> for Problem {
+  fn from(e: DeleteFeatureError) -> Self {
+    match e {
+      DeleteFeatureError::UnknownFeature => unknown_feature_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
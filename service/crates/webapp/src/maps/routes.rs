@@ -0,0 +1,24 @@
+use super::delete::*;
+use super::features::*;
+use super::get::*;
+use super::image::*;
+use super::patch::*;
+use super::post::*;
+use rocket::{routes, Route};
+
+pub fn routes() -> Vec<Route> {
+  routes![
+    list_maps,
+    get_map,
+    create_map,
+    update_map,
+    delete_map,
+    put_map_image,
+    get_map_image,
+    list_features,
+    get_feature,
+    create_feature,
+    replace_feature,
+    delete_feature
+  ]
+}
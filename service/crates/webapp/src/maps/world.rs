@@ -0,0 +1,63 @@
+use super::problems::{unknown_feature_problem, unknown_map_problem};
+use crate::problem::Problem;
+use tracing::warn;
+use universe_maps::{FeatureEntity, FeatureID, MapEntity, MapFeatureService, MapID, MapService};
+use universe_worlds::WorldEntity;
+
+/// Parse the ID of a map, as provided in the URL
+pub(super) fn parse_map_id(map_id: String) -> Result<MapID, Problem> {
+  map_id.parse().map_err(|e| {
+    warn!("Invalid Map ID: {}", e);
+    unknown_map_problem()
+  })
+}
+
+/// Parse the ID of a feature on a map, as provided in the URL
+pub(super) fn parse_feature_id(feature_id: String) -> Result<FeatureID, Problem> {
+  feature_id.parse().map_err(|e| {
+    warn!("Invalid Feature ID: {}", e);
+    unknown_feature_problem()
+  })
+}
+
+/// Look up a map of a world
+///
+/// # Arguments
+/// * `map_service` The map service to look the map up with
+/// * `world` The world that the map belongs to
+/// * `map_id` The ID of the map, as provided in the URL
+///
+/// # Returns
+/// The map, or a Problem if it couldn't be found
+pub(super) fn find_map(
+  map_service: &dyn MapService,
+  world: &WorldEntity,
+  map_id: String,
+) -> Result<MapEntity, Problem> {
+  let map_id = parse_map_id(map_id)?;
+
+  map_service
+    .get_map_by_id(&world.identity.id, &map_id)
+    .ok_or_else(unknown_map_problem)
+}
+
+/// Look up a feature on a map
+///
+/// # Arguments
+/// * `map_feature_service` The map feature service to look the feature up with
+/// * `map` The map that the feature is on
+/// * `feature_id` The ID of the feature, as provided in the URL
+///
+/// # Returns
+/// The feature, or a Problem if it couldn't be found
+pub(super) fn find_feature(
+  map_feature_service: &dyn MapFeatureService,
+  map: &MapEntity,
+  feature_id: String,
+) -> Result<FeatureEntity, Problem> {
+  let feature_id = parse_feature_id(feature_id)?;
+
+  map_feature_service
+    .get_feature_by_id(&map.data.world, &map.identity.id, &feature_id)
+    .ok_or_else(unknown_feature_problem)
+}
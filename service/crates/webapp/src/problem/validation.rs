@@ -10,6 +10,21 @@ pub struct ValidationError {
     pub field: String,
 }
 
+/// Error wrapping a set of validation errors, so that they can be returned from callbacks that
+/// expect a standard error type
+#[derive(Debug)]
+pub struct ValidationErrors {
+    pub errors: Vec<ValidationError>,
+}
+
+impl std::fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self.errors)
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
 /// Construct a Problem that represents validation errors with an incoming request
 ///
 /// # Arguments
@@ -0,0 +1,25 @@
+use super::world::parse_relationship_id;
+use crate::problem::Problem;
+use crate::worlds::access::find_editable_world;
+use crate::{authentication::Authorizer, request_id::RequestId};
+use rocket::{delete, http::Status, State};
+use universe_relationships::RelationshipService;
+use universe_worlds::WorldService;
+
+#[delete("/worlds/<world_id>/relationships/<relationship_id>")]
+#[tracing::instrument(skip(world_service, relationship_service, authorizer))]
+pub fn delete_relationship(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  relationship_id: String,
+  world_service: State<Box<dyn WorldService>>,
+  relationship_service: State<Box<dyn RelationshipService>>,
+) -> Result<Status, Problem> {
+  let world = find_editable_world(&**world_service, &authorizer, world_id)?;
+  let relationship_id = parse_relationship_id(relationship_id)?;
+
+  relationship_service.delete_relationship(&world.identity.id, &relationship_id)?;
+
+  Ok(Status::NoContent)
+}
@@ -0,0 +1,71 @@
+use super::model::{Relationship, RelationshipPage};
+use super::world::{find_calendars, find_relationship, parse_entity};
+use crate::authentication::ApiAccessToken;
+use crate::problem::{validation_error, Problem};
+use crate::request_id::RequestId;
+use crate::worlds::access::find_visible_world;
+use rocket::{get, State};
+use universe_calendars::CalendarService;
+use universe_entity::Pagination;
+use universe_relationships::{RelationshipFilters, RelationshipService};
+use universe_worlds::WorldService;
+
+#[allow(clippy::too_many_arguments)]
+#[get("/worlds/<world_id>/relationships?<entity>&<relation>&<offset>&<limit>")]
+#[tracing::instrument(skip(world_service, relationship_service, calendar_service))]
+pub fn list_relationships(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  entity: Option<String>,
+  relation: Option<String>,
+  offset: Option<u32>,
+  limit: Option<u32>,
+  world_service: State<Box<dyn WorldService>>,
+  relationship_service: State<Box<dyn RelationshipService>>,
+  calendar_service: State<Box<dyn CalendarService>>,
+) -> Result<RelationshipPage, Problem> {
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+
+  let entity = match entity {
+    None => None,
+    Some(entity) => {
+      Some(parse_entity("entity", Some(&entity)).map_err(|e| validation_error(vec![e]))?)
+    }
+  };
+  let filters = RelationshipFilters {
+    entity,
+    relation: relation
+      .map(|relation| relation.trim().to_owned())
+      .filter(|relation| !relation.is_empty()),
+  };
+
+  let pagination = Pagination {
+    offset: offset.unwrap_or(0),
+    limit: limit.unwrap_or(10),
+  };
+
+  let relationships =
+    relationship_service.list_relationships(&world.identity.id, filters, pagination);
+  let calendars = find_calendars(&**calendar_service, &world, &relationships.entries);
+
+  Ok(RelationshipPage::new(relationships, &calendars, &world))
+}
+
+#[get("/worlds/<world_id>/relationships/<relationship_id>")]
+#[tracing::instrument(skip(world_service, relationship_service, calendar_service))]
+pub fn get_relationship(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  relationship_id: String,
+  world_service: State<Box<dyn WorldService>>,
+  relationship_service: State<Box<dyn RelationshipService>>,
+  calendar_service: State<Box<dyn CalendarService>>,
+) -> Result<Relationship, Problem> {
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+  let relationship = find_relationship(&**relationship_service, &world, relationship_id)?;
+  let calendars = find_calendars(&**calendar_service, &world, vec![&relationship]);
+
+  Ok(Relationship::new(&relationship, &calendars, &world))
+}
@@ -0,0 +1,159 @@
+use super::model::{render_dot, GraphExport, JsonGraphDocument, Neighbours, Path};
+use super::problems::{invalid_format_error, no_path_problem, unknown_entity_problem};
+use super::world::{find_calendars, parse_direction, parse_entity};
+use crate::authentication::ApiAccessToken;
+use crate::problem::{validation_error, Problem};
+use crate::request_id::RequestId;
+use crate::worlds::access::find_visible_world;
+use rocket::{get, State};
+use universe_calendars::CalendarService;
+use universe_relationships::RelationshipService;
+use universe_worlds::WorldService;
+
+#[allow(clippy::too_many_arguments)]
+#[get("/worlds/<world_id>/graph/neighbours?<entity>&<direction>")]
+#[tracing::instrument(skip(world_service, relationship_service, calendar_service))]
+pub fn get_neighbours(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  entity: Option<String>,
+  direction: Option<String>,
+  world_service: State<Box<dyn WorldService>>,
+  relationship_service: State<Box<dyn RelationshipService>>,
+  calendar_service: State<Box<dyn CalendarService>>,
+) -> Result<Neighbours, Problem> {
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+
+  let entity = parse_entity("entity", entity.as_deref());
+  let direction = parse_direction(direction.as_deref());
+  let (entity, direction) = match (entity, direction) {
+    (Ok(entity), Ok(direction)) => (entity, direction),
+    (entity, direction) => {
+      let errors = vec![entity.err(), direction.err()]
+        .into_iter()
+        .filter_map(|v| v)
+        .collect();
+
+      return Err(validation_error(errors));
+    }
+  };
+
+  let graph = relationship_service.load_graph(&world.identity.id);
+  graph.node(&entity).ok_or_else(unknown_entity_problem)?;
+
+  let steps = graph.neighbours(&entity, direction);
+  let calendars = find_calendars(
+    &**calendar_service,
+    &world,
+    steps.iter().map(|step| step.relationship),
+  );
+
+  Ok(Neighbours::new(&graph, &entity, &steps, &calendars, &world))
+}
+
+#[allow(clippy::too_many_arguments)]
+#[get("/worlds/<world_id>/graph/path?<from>&<to>&<direction>")]
+#[tracing::instrument(skip(world_service, relationship_service, calendar_service))]
+pub fn get_path(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  from: Option<String>,
+  to: Option<String>,
+  direction: Option<String>,
+  world_service: State<Box<dyn WorldService>>,
+  relationship_service: State<Box<dyn RelationshipService>>,
+  calendar_service: State<Box<dyn CalendarService>>,
+) -> Result<Path, Problem> {
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+
+  let from = parse_entity("from", from.as_deref());
+  let to = parse_entity("to", to.as_deref());
+  let direction = parse_direction(direction.as_deref());
+  let (from, to, direction) = match (from, to, direction) {
+    (Ok(from), Ok(to), Ok(direction)) => (from, to, direction),
+    (from, to, direction) => {
+      let errors = vec![from.err(), to.err(), direction.err()]
+        .into_iter()
+        .filter_map(|v| v)
+        .collect();
+
+      return Err(validation_error(errors));
+    }
+  };
+
+  let graph = relationship_service.load_graph(&world.identity.id);
+  graph.node(&from).ok_or_else(unknown_entity_problem)?;
+  graph.node(&to).ok_or_else(unknown_entity_problem)?;
+
+  let steps = graph
+    .shortest_path(&from, &to, direction)
+    .ok_or_else(no_path_problem)?;
+  let calendars = find_calendars(
+    &**calendar_service,
+    &world,
+    steps.iter().map(|step| step.relationship),
+  );
+
+  Ok(Path::new(&graph, &from, &steps, &calendars, &world))
+}
+
+#[allow(clippy::too_many_arguments)]
+#[get("/worlds/<world_id>/graph?<entity>&<hops>&<direction>&<format>")]
+#[tracing::instrument(skip(world_service, relationship_service, calendar_service))]
+pub fn get_graph(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  entity: Option<String>,
+  hops: Option<u32>,
+  direction: Option<String>,
+  format: Option<String>,
+  world_service: State<Box<dyn WorldService>>,
+  relationship_service: State<Box<dyn RelationshipService>>,
+  calendar_service: State<Box<dyn CalendarService>>,
+) -> Result<GraphExport, Problem> {
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+
+  let entity = entity
+    .map(|entity| parse_entity("entity", Some(&entity)))
+    .transpose();
+  let direction = parse_direction(direction.as_deref());
+  let dot = match format.as_deref().map(|format| format.trim()) {
+    None | Some("json") => Ok(false),
+    Some("dot") => Ok(true),
+    Some(_) => Err(invalid_format_error()),
+  };
+  let (entity, direction, dot) = match (entity, direction, dot) {
+    (Ok(entity), Ok(direction), Ok(dot)) => (entity, direction, dot),
+    (entity, direction, dot) => {
+      let errors = vec![entity.err(), direction.err(), dot.err()]
+        .into_iter()
+        .filter_map(|v| v)
+        .collect();
+
+      return Err(validation_error(errors));
+    }
+  };
+
+  // Without an entity to start from, the entire graph of the world is exported
+  let mut graph = relationship_service.load_graph(&world.identity.id);
+  if let Some(entity) = entity {
+    graph.node(&entity).ok_or_else(unknown_entity_problem)?;
+    graph = graph.within(&entity, hops.unwrap_or(1), direction);
+  }
+
+  if dot {
+    Ok(GraphExport::Dot(
+      render_dot(&world.data.name, &graph),
+      world.data.visibility,
+    ))
+  } else {
+    let calendars = find_calendars(&**calendar_service, &world, &graph.edges);
+    Ok(GraphExport::Json(
+      JsonGraphDocument::new(&graph, &calendars, &world),
+      world.data.visibility,
+    ))
+  }
+}
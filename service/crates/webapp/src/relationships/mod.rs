@@ -0,0 +1,11 @@
+mod delete;
+mod get;
+mod graph;
+mod model;
+mod patch;
+mod post;
+pub(crate) mod problems;
+mod routes;
+mod world;
+
+pub use routes::routes;
@@ -0,0 +1,604 @@
+use crate::headers::*;
+use crate::page::SearchPage;
+use chrono::{DateTime, Utc};
+use rocket::{
+  http::{
+    hyper::header::{CacheControl, CacheDirective, ETag, EntityTag, HttpDate, LastModified},
+    ContentType, Status,
+  },
+  response::{Responder, Response},
+  Request,
+};
+use rocket_contrib::json::Json;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::Cursor;
+use universe_calendars::{CalendarDate, CalendarEntity, CalendarID};
+use universe_entity::Page;
+use universe_relationships::{
+  DateRange, EntityRef, Graph, GraphNode, RelationshipEntity, RelationshipID, Step,
+};
+use universe_worlds::{Visibility, WorldEntity, WorldID};
+use uuid::Uuid;
+
+/// Work out who may cache a response about the relationships of a world
+fn cache_scope(visibility: Visibility) -> CacheDirective {
+  // Relationships of private worlds must never be stored in a shared cache
+  match visibility {
+    Visibility::Private => CacheDirective::Private,
+    _ => CacheDirective::Public,
+  }
+}
+
+/// The URL of an entity of a world
+fn entity_href(world: &WorldID, entity: &EntityRef) -> String {
+  let collection = match entity {
+    EntityRef::Article(_) => "articles",
+    EntityRef::Character(_) => "characters",
+    EntityRef::Location(_) => "locations",
+    EntityRef::Map(_) => "maps",
+  };
+
+  format!("/worlds/{}/{}/{}", world, collection, entity.id())
+}
+
+/// Representation of the entity at one end of a relationship
+#[derive(Debug, Serialize)]
+pub struct RelatedEntity {
+  /// What sort of entity it is. One of "article", "character", "location" or "map"
+  pub r#type: &'static str,
+  pub id: String,
+  pub href: String,
+}
+
+impl RelatedEntity {
+  fn new(world: &WorldID, entity: &EntityRef) -> Self {
+    RelatedEntity {
+      r#type: entity.kind(),
+      id: entity.id(),
+      href: entity_href(world, entity),
+    }
+  }
+}
+
+/// Representation of the in-world dates that a relationship held between
+#[derive(Debug, Serialize)]
+pub struct During {
+  pub calendar: CalendarID,
+  /// The first day of the relationship, written out in full, if it hasn't always held
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub start: Option<String>,
+  /// The last day of the relationship, written out in full, if it no longer holds
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub end: Option<String>,
+}
+
+impl During {
+  /// Build the representation of the dates of a relationship
+  ///
+  /// # Arguments
+  /// * `during` The dates of the relationship
+  /// * `calendars` The calendars that the dates might be in
+  pub fn new(during: &DateRange, calendars: &[CalendarEntity]) -> Self {
+    let calendar = calendars
+      .iter()
+      .find(|calendar| calendar.identity.id == during.calendar);
+    // A date that the calendar no longer has can't be written out, so is left off
+    let format = |date: &Option<CalendarDate>| {
+      calendar.and_then(|calendar| {
+        let definition = &calendar.data.definition;
+        date
+          .as_ref()
+          .filter(|date| definition.check_date(date).is_ok())
+          .map(|date| definition.format_date(date))
+      })
+    };
+
+    During {
+      calendar: during.calendar.clone(),
+      start: format(&during.start),
+      end: format(&during.end),
+    }
+  }
+}
+
+/// Representation of a Relationship to return over the API
+#[derive(Debug, Serialize)]
+pub struct Relationship {
+  pub id: RelationshipID,
+  pub world: WorldID,
+  pub source: RelatedEntity,
+  pub target: RelatedEntity,
+  pub relation: String,
+  pub symmetric: bool,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub during: Option<During>,
+  #[serde(skip_serializing)]
+  version: Uuid,
+  #[serde(skip_serializing)]
+  updated: DateTime<Utc>,
+  #[serde(skip_serializing)]
+  visibility: Visibility,
+}
+
+impl Relationship {
+  /// Build the representation of a relationship between two entities of a world
+  ///
+  /// # Arguments
+  /// * `relationship` The relationship
+  /// * `calendars` The calendars that the relationship might be dated in
+  /// * `world` The world that the relationship belongs to
+  pub fn new(
+    relationship: &RelationshipEntity,
+    calendars: &[CalendarEntity],
+    world: &WorldEntity,
+  ) -> Self {
+    let world_id = &relationship.data.world;
+
+    Relationship {
+      id: relationship.identity.id.clone(),
+      world: world_id.clone(),
+      source: RelatedEntity::new(world_id, &relationship.data.source),
+      target: RelatedEntity::new(world_id, &relationship.data.target),
+      relation: relationship.data.relation.clone(),
+      symmetric: relationship.data.symmetric,
+      during: relationship
+        .data
+        .during
+        .as_ref()
+        .map(|during| During::new(during, calendars)),
+      version: relationship.identity.version,
+      updated: relationship.identity.updated,
+      visibility: world.data.visibility,
+    }
+  }
+
+  /// The URL of the relationship
+  fn href(&self) -> String {
+    format!("/worlds/{}/relationships/{}", self.world, self.id)
+  }
+}
+
+impl<'a> Responder<'a> for Relationship {
+  /// Generate a Rocket response for the Relationship
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(Link::from_href(self.href()).with_rel("self"))
+      .header(AcceptPatch("application/merge-patch+json"))
+      .header(ETag(EntityTag::new(false, self.version.to_string())))
+      .header(LastModified(HttpDate(time::at_utc(time::Timespec::new(
+        self.updated.timestamp(),
+        0,
+      )))))
+      .header(CacheControl(vec![
+        cache_scope(self.visibility),
+        CacheDirective::MaxAge(3600),
+      ]))
+      .ok()
+  }
+}
+
+/// Representation of a Relationship that has just been created
+#[derive(Debug, Serialize)]
+pub struct CreatedRelationship {
+  #[serde(flatten)]
+  pub relationship: Relationship,
+}
+
+impl<'a> Responder<'a> for CreatedRelationship {
+  /// Generate a Rocket response for the newly created Relationship
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(Link::from_href(self.relationship.href()).with_rel("canonical"))
+      .ok()
+  }
+}
+
+/// Representation of a list of the Relationships of a World
+#[derive(Debug, Serialize)]
+pub struct RelationshipPage {
+  #[serde(flatten)]
+  pub page: SearchPage<Relationship>,
+  #[serde(skip_serializing)]
+  visibility: Visibility,
+}
+
+impl RelationshipPage {
+  /// Build the representation of a page of the relationships of a world
+  ///
+  /// # Arguments
+  /// * `relationships` The page of relationships
+  /// * `calendars` The calendars that the relationships might be dated in
+  /// * `world` The world that the relationships belong to
+  pub fn new(
+    relationships: Page<RelationshipEntity>,
+    calendars: &[CalendarEntity],
+    world: &WorldEntity,
+  ) -> Self {
+    RelationshipPage {
+      page: SearchPage {
+        entries: relationships
+          .entries
+          .iter()
+          .map(|relationship| Relationship::new(relationship, calendars, world))
+          .collect(),
+        total: relationships.total,
+      },
+      visibility: world.data.visibility,
+    }
+  }
+}
+
+impl<'a> Responder<'a> for RelationshipPage {
+  /// Generate a Rocket response for the list of Relationships
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(CacheControl(vec![
+        cache_scope(self.visibility),
+        CacheDirective::MaxAge(3600),
+      ]))
+      // Members of private worlds can see relationships that nobody else can
+      .raw_header("Vary", "Authorization")
+      .ok()
+  }
+}
+
+/// Representation of an entity as a node of the graph of relationships
+#[derive(Debug, Serialize)]
+pub struct Node {
+  pub r#type: &'static str,
+  pub id: String,
+  pub name: String,
+  pub href: String,
+}
+
+impl Node {
+  /// Build the representation of an entity of the graph
+  ///
+  /// # Arguments
+  /// * `world` The ID of the world that the entity belongs to
+  /// * `node` The entity
+  pub fn new(world: &WorldID, node: &GraphNode) -> Self {
+    Node {
+      r#type: node.entity.kind(),
+      id: node.entity.id(),
+      name: node.name.clone(),
+      href: entity_href(world, &node.entity),
+    }
+  }
+}
+
+/// Look up an entity in the graph, falling back to its ID as its name should it not be there
+fn graph_node(world: &WorldID, graph: &Graph, entity: &EntityRef) -> Node {
+  match graph.node(entity) {
+    Some(node) => Node::new(world, node),
+    None => Node::new(
+      world,
+      &GraphNode {
+        entity: entity.clone(),
+        name: entity.id(),
+      },
+    ),
+  }
+}
+
+/// Representation of an entity that is directly related to another
+#[derive(Debug, Serialize)]
+pub struct Neighbour {
+  /// Whether the relationship is "outgoing" from the entity asked about, or "incoming" to it
+  pub direction: &'static str,
+  pub relationship: Relationship,
+  pub entity: Node,
+}
+
+/// Representation of every entity that is directly related to an entity
+#[derive(Debug, Serialize)]
+pub struct Neighbours {
+  pub entity: Node,
+  pub neighbours: Vec<Neighbour>,
+  #[serde(skip_serializing)]
+  visibility: Visibility,
+}
+
+impl Neighbours {
+  /// Build the representation of the neighbours of an entity
+  ///
+  /// # Arguments
+  /// * `graph` The graph of the relationships of the world
+  /// * `entity` The entity that the neighbours are of
+  /// * `steps` The steps from the entity to each of its neighbours
+  /// * `calendars` The calendars that the relationships might be dated in
+  /// * `world` The world that the graph belongs to
+  pub fn new(
+    graph: &Graph,
+    entity: &EntityRef,
+    steps: &[Step],
+    calendars: &[CalendarEntity],
+    world: &WorldEntity,
+  ) -> Self {
+    let world_id = &world.identity.id;
+
+    Neighbours {
+      entity: graph_node(world_id, graph, entity),
+      neighbours: steps
+        .iter()
+        .map(|step| Neighbour {
+          direction: if step.forwards {
+            "outgoing"
+          } else {
+            "incoming"
+          },
+          relationship: Relationship::new(step.relationship, calendars, world),
+          entity: graph_node(world_id, graph, step.to()),
+        })
+        .collect(),
+      visibility: world.data.visibility,
+    }
+  }
+}
+
+impl<'a> Responder<'a> for Neighbours {
+  /// Generate a Rocket response for the neighbours of an entity
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(CacheControl(vec![
+        cache_scope(self.visibility),
+        CacheDirective::MaxAge(3600),
+      ]))
+      .raw_header("Vary", "Authorization")
+      .ok()
+  }
+}
+
+/// Representation of the shortest path between two entities. Each relationship connects the
+/// entity at the same position to the one after it
+#[derive(Debug, Serialize)]
+pub struct Path {
+  /// How many relationships the path crosses
+  pub length: usize,
+  pub entities: Vec<Node>,
+  pub relationships: Vec<Relationship>,
+  #[serde(skip_serializing)]
+  visibility: Visibility,
+}
+
+impl Path {
+  /// Build the representation of a path between two entities
+  ///
+  /// # Arguments
+  /// * `graph` The graph of the relationships of the world
+  /// * `from` The entity that the path starts at
+  /// * `steps` The steps along the path
+  /// * `calendars` The calendars that the relationships might be dated in
+  /// * `world` The world that the graph belongs to
+  pub fn new(
+    graph: &Graph,
+    from: &EntityRef,
+    steps: &[Step],
+    calendars: &[CalendarEntity],
+    world: &WorldEntity,
+  ) -> Self {
+    let world_id = &world.identity.id;
+
+    Path {
+      length: steps.len(),
+      entities: std::iter::once(from)
+        .chain(steps.iter().map(|step| step.to()))
+        .map(|entity| graph_node(world_id, graph, entity))
+        .collect(),
+      relationships: steps
+        .iter()
+        .map(|step| Relationship::new(step.relationship, calendars, world))
+        .collect(),
+      visibility: world.data.visibility,
+    }
+  }
+}
+
+impl<'a> Responder<'a> for Path {
+  /// Generate a Rocket response for the path between two entities
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(CacheControl(vec![
+        cache_scope(self.visibility),
+        CacheDirective::MaxAge(3600),
+      ]))
+      .raw_header("Vary", "Authorization")
+      .ok()
+  }
+}
+
+/// The metadata of a node in a JSON Graph Format document
+#[derive(Debug, Serialize)]
+pub struct JsonGraphNodeMetadata {
+  pub r#type: &'static str,
+  pub href: String,
+}
+
+/// A node in a JSON Graph Format document
+#[derive(Debug, Serialize)]
+pub struct JsonGraphNode {
+  pub label: String,
+  pub metadata: JsonGraphNodeMetadata,
+}
+
+/// The metadata of an edge in a JSON Graph Format document
+#[derive(Debug, Serialize)]
+pub struct JsonGraphEdgeMetadata {
+  pub href: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub during: Option<During>,
+}
+
+/// An edge in a JSON Graph Format document
+#[derive(Debug, Serialize)]
+pub struct JsonGraphEdge {
+  pub id: RelationshipID,
+  pub source: String,
+  pub target: String,
+  pub relation: String,
+  /// Symmetric relationships are undirected edges
+  pub directed: bool,
+  pub metadata: JsonGraphEdgeMetadata,
+}
+
+/// The single graph of a JSON Graph Format document
+#[derive(Debug, Serialize)]
+pub struct JsonGraph {
+  pub directed: bool,
+  /// The nodes, keyed by the entity written as "type:id"
+  pub nodes: BTreeMap<String, JsonGraphNode>,
+  pub edges: Vec<JsonGraphEdge>,
+}
+
+/// Representation of a graph of relationships as a JSON Graph Format document
+#[derive(Debug, Serialize)]
+pub struct JsonGraphDocument {
+  pub graph: JsonGraph,
+}
+
+impl JsonGraphDocument {
+  /// Build the JSON Graph Format document for a graph of relationships
+  ///
+  /// # Arguments
+  /// * `graph` The graph
+  /// * `calendars` The calendars that the relationships might be dated in
+  /// * `world` The world that the graph belongs to
+  pub fn new(graph: &Graph, calendars: &[CalendarEntity], world: &WorldEntity) -> Self {
+    let world_id = &world.identity.id;
+
+    JsonGraphDocument {
+      graph: JsonGraph {
+        directed: true,
+        nodes: graph
+          .nodes
+          .iter()
+          .map(|node| {
+            (
+              node.entity.to_string(),
+              JsonGraphNode {
+                label: node.name.clone(),
+                metadata: JsonGraphNodeMetadata {
+                  r#type: node.entity.kind(),
+                  href: entity_href(world_id, &node.entity),
+                },
+              },
+            )
+          })
+          .collect(),
+        edges: graph
+          .edges
+          .iter()
+          .map(|edge| JsonGraphEdge {
+            id: edge.identity.id.clone(),
+            source: edge.data.source.to_string(),
+            target: edge.data.target.to_string(),
+            relation: edge.data.relation.clone(),
+            directed: !edge.data.symmetric,
+            metadata: JsonGraphEdgeMetadata {
+              href: format!("/worlds/{}/relationships/{}", world_id, edge.identity.id),
+              during: edge
+                .data
+                .during
+                .as_ref()
+                .map(|during| During::new(during, calendars)),
+            },
+          })
+          .collect(),
+      },
+    }
+  }
+}
+
+/// Quote a string for use as an ID or label in a GraphViz DOT document
+fn dot_quote(value: &str) -> String {
+  let escaped: String = value
+    .chars()
+    .flat_map(|c| match c {
+      '"' => vec!['\\', '"'],
+      '\\' => vec!['\\', '\\'],
+      '\n' => vec!['\\', 'n'],
+      c => vec![c],
+    })
+    .collect();
+
+  format!("\"{}\"", escaped)
+}
+
+/// Write a graph of relationships out as a GraphViz DOT document
+///
+/// # Arguments
+/// * `name` The name to give the graph
+/// * `graph` The graph
+///
+/// # Returns
+/// The DOT document
+pub fn render_dot(name: &str, graph: &Graph) -> String {
+  let mut dot = format!("digraph {} {{\n", dot_quote(name));
+
+  for node in &graph.nodes {
+    dot.push_str(&format!(
+      "  {} [label={}];\n",
+      dot_quote(&node.entity.to_string()),
+      dot_quote(&node.name)
+    ));
+  }
+  for edge in &graph.edges {
+    dot.push_str(&format!(
+      "  {} -> {} [label={}{}];\n",
+      dot_quote(&edge.data.source.to_string()),
+      dot_quote(&edge.data.target.to_string()),
+      dot_quote(&edge.data.relation),
+      // Symmetric relationships are drawn without arrowheads
+      if edge.data.symmetric {
+        ", dir=none"
+      } else {
+        ""
+      }
+    ));
+  }
+
+  dot.push_str("}\n");
+  dot
+}
+
+/// Representation of a graph of relationships, in one of the supported export formats
+#[derive(Debug)]
+pub enum GraphExport {
+  /// The graph as a JSON Graph Format document
+  Json(JsonGraphDocument, Visibility),
+  /// The graph as a GraphViz DOT document
+  Dot(String, Visibility),
+}
+
+impl<'a> Responder<'a> for GraphExport {
+  /// Generate a Rocket response for the graph
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    let (mut response, visibility) = match self {
+      GraphExport::Json(document, visibility) => (
+        Response::build_from(Json(&document).respond_to(req)?),
+        visibility,
+      ),
+      GraphExport::Dot(dot, visibility) => {
+        let mut response = Response::build();
+        response
+          .header(ContentType::new("text", "vnd.graphviz"))
+          .sized_body(Cursor::new(dot));
+        (response, visibility)
+      }
+    };
+
+    response
+      .header(CacheControl(vec![
+        cache_scope(visibility),
+        CacheDirective::MaxAge(3600),
+      ]))
+      .raw_header("Vary", "Authorization")
+      .ok()
+  }
+}
@@ -0,0 +1,128 @@
+use super::model::Relationship;
+use super::world::{find_calendars, parse_relationship_id, NewDuring, NewEntity};
+use crate::problem::{missing_error, Problem, ValidationErrors};
+use crate::worlds::access::find_editable_world;
+use crate::{authentication::Authorizer, request_id::RequestId};
+use rocket::{patch, State};
+use rocket_contrib::json::Json;
+use serde::{Deserialize, Deserializer};
+use tracing::{debug, warn};
+use universe_calendars::CalendarService;
+use universe_relationships::RelationshipService;
+use universe_worlds::WorldService;
+
+#[allow(clippy::too_many_arguments)]
+#[patch(
+  "/worlds/<world_id>/relationships/<relationship_id>",
+  format = "application/merge-patch+json",
+  data = "<patch_data>"
+)]
+#[tracing::instrument(skip(world_service, relationship_service, calendar_service, authorizer))]
+pub fn update_relationship(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  relationship_id: String,
+  patch_data: Json<PatchData>,
+  world_service: State<Box<dyn WorldService>>,
+  relationship_service: State<Box<dyn RelationshipService>>,
+  calendar_service: State<Box<dyn CalendarService>>,
+) -> Result<Relationship, Problem> {
+  debug!("Patch Data: {:?}", patch_data);
+
+  let world = find_editable_world(&**world_service, &authorizer, world_id)?;
+  let relationship_id = parse_relationship_id(relationship_id)?;
+
+  let relationship = relationship_service.update_relationship(
+    &world.identity.id,
+    &relationship_id,
+    &mut |mut relationship| {
+      debug!("Patching relationship details");
+
+      let mut errors = vec![];
+
+      if let Some(source) = &patch_data.source {
+        match source.clone().into_entity_ref("source") {
+          Ok(source) => relationship.source = source,
+          Err(e) => errors.push(e),
+        }
+      }
+
+      if let Some(target) = &patch_data.target {
+        match target.clone().into_entity_ref("target") {
+          Ok(target) => relationship.target = target,
+          Err(e) => errors.push(e),
+        }
+      }
+
+      if let Some(relation) = &patch_data.relation {
+        let relation = relation.trim();
+        if relation.is_empty() {
+          errors.push(missing_error("relation"));
+        } else {
+          relationship.relation = relation.to_owned();
+        }
+      }
+
+      if let Some(symmetric) = patch_data.symmetric {
+        relationship.symmetric = symmetric;
+      }
+
+      // Dates of `null` mean that the relationship has always held
+      match &patch_data.during {
+        None => {}
+        Some(NewDates::Clear) => relationship.during = None,
+        Some(NewDates::Set(during)) => {
+          match during.clone().into_date_range(&**calendar_service, &world) {
+            Ok(during) => relationship.during = Some(during),
+            Err(e) => errors.extend(e),
+          }
+        }
+      }
+
+      if errors.is_empty() {
+        Ok(relationship)
+      } else {
+        warn!("Error patching relationship: {:?}", errors);
+        Err(Box::new(ValidationErrors { errors }))
+      }
+    },
+  )?;
+
+  let calendars = find_calendars(&**calendar_service, &world, vec![&relationship]);
+
+  Ok(Relationship::new(&relationship, &calendars, &world))
+}
+
+/// Struct representing the input data for updating a relationship
+#[derive(Deserialize, Debug)]
+pub struct PatchData {
+  pub source: Option<NewEntity>,
+  pub target: Option<NewEntity>,
+  pub relation: Option<String>,
+  pub symmetric: Option<bool>,
+  #[serde(default, deserialize_with = "deserialize_during")]
+  pub during: Option<NewDates>,
+}
+
+/// When a relationship is to have held
+#[derive(Debug)]
+pub enum NewDates {
+  /// The relationship was given explicit `null` dates, so has always held
+  Clear,
+  /// The relationship held between these dates. They replace any existing dates entirely
+  Set(NewDuring),
+}
+
+/// Deserialize the dates of a relationship, so that an explicit `null` can be told apart from the
+/// field being missing
+fn deserialize_during<'de, D>(deserializer: D) -> Result<Option<NewDates>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let during: Option<NewDuring> = Deserialize::deserialize(deserializer)?;
+  Ok(Some(match during {
+    None => NewDates::Clear,
+    Some(during) => NewDates::Set(during),
+  }))
+}
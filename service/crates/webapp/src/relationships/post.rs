@@ -0,0 +1,89 @@
+use super::model::{CreatedRelationship, Relationship};
+use super::world::{find_calendars, NewDuring, NewEntity};
+use crate::authentication::Authorizer;
+use crate::problem::{missing_error, validation_error, Problem};
+use crate::request_id::RequestId;
+use crate::worlds::access::find_editable_world;
+use rocket::{post, State};
+use rocket_contrib::json::Json;
+use serde::Deserialize;
+use tracing::debug;
+use universe_calendars::CalendarService;
+use universe_relationships::{RelationshipData, RelationshipService};
+use universe_worlds::WorldService;
+
+#[post("/worlds/<world_id>/relationships", data = "<new_relationship>")]
+#[tracing::instrument(skip(world_service, relationship_service, calendar_service, authorizer))]
+pub fn create_relationship(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  new_relationship: Json<NewRelationship>,
+  world_service: State<Box<dyn WorldService>>,
+  relationship_service: State<Box<dyn RelationshipService>>,
+  calendar_service: State<Box<dyn CalendarService>>,
+) -> Result<CreatedRelationship, Problem> {
+  debug!("New Relationship: {:?}", new_relationship);
+
+  let world = find_editable_world(&**world_service, &authorizer, world_id)?;
+  let new_relationship = new_relationship.into_inner();
+
+  let source = new_relationship
+    .source
+    .ok_or_else(|| missing_error("source"))
+    .and_then(|source| source.into_entity_ref("source"));
+  let target = new_relationship
+    .target
+    .ok_or_else(|| missing_error("target"))
+    .and_then(|target| target.into_entity_ref("target"));
+  let relation = new_relationship
+    .relation
+    .as_deref()
+    .map(|relation| relation.trim())
+    .filter(|relation| !relation.is_empty())
+    .ok_or_else(|| missing_error("relation"));
+  let during = new_relationship
+    .during
+    .map(|during| during.into_date_range(&**calendar_service, &world))
+    .transpose();
+
+  let (source, target, relation, during) = match (source, target, relation, during) {
+    (Ok(source), Ok(target), Ok(relation), Ok(during)) => (source, target, relation, during),
+    (source, target, relation, during) => {
+      let errors = vec![source.err(), target.err(), relation.err()]
+        .into_iter()
+        .filter_map(|v| v)
+        .chain(during.err().into_iter().flatten())
+        .collect();
+
+      return Err(validation_error(errors));
+    }
+  };
+
+  let result = relationship_service.create_relationship(RelationshipData {
+    world: world.identity.id.clone(),
+    source,
+    target,
+    relation: relation.to_owned(),
+    symmetric: new_relationship.symmetric.unwrap_or(false),
+    during,
+  })?;
+  debug!("Created relationship: {:?}", result);
+
+  let calendars = find_calendars(&**calendar_service, &world, vec![&result]);
+
+  Ok(CreatedRelationship {
+    relationship: Relationship::new(&result, &calendars, &world),
+  })
+}
+
+/// Struct representing the input data for creating a new relationship
+#[derive(Deserialize, Debug)]
+pub struct NewRelationship {
+  pub source: Option<NewEntity>,
+  pub target: Option<NewEntity>,
+  pub relation: Option<String>,
+  /// Whether the relationship reads the same both ways. Relationships are directed by default
+  pub symmetric: Option<bool>,
+  pub during: Option<NewDuring>,
+}
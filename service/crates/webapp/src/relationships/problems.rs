@@ -0,0 +1,169 @@
+use crate::calendars::problems::date_error;
+use crate::problem::{
+  unexpected_error, validation_error, Problem, ValidationError, ValidationErrors,
+};
+use crate::worlds::problems::unknown_world_problem;
+use universe_calendars::DateError;
+use universe_relationships::*;
+
+/// Helper to build a Problem response for an unknown relationship
+pub fn unknown_relationship_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:relationships/problems/unknown-relationship".to_owned(),
+    title: "The requested relationship could not be found".to_owned(),
+    status: 404,
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Problem response for an entity that isn't part of the graph of a world
+pub fn unknown_entity_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:relationships/problems/unknown-entity".to_owned(),
+    title: "The requested entity has no relationships in the world".to_owned(),
+    status: 404,
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Problem response for two entities that aren't connected by relationships
+pub fn no_path_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:relationships/problems/no-path".to_owned(),
+    title: "The entities are not connected by any relationships".to_owned(),
+    status: 404,
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Problem response for when a relationship was modified by someone else
+pub fn optimistic_lock_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:relationships/problems/optimistic-lock-failure".to_owned(),
+    title: "The relationship was modified by another request".to_owned(),
+    status: 409,
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Validation Error for a reference to an entity that couldn't be understood
+///
+/// # Arguments
+/// * `field` The field that the entity was provided in
+pub fn invalid_entity_error(field: &str) -> ValidationError {
+  ValidationError {
+    r#type: "tag:universe,2020:relationships/validation-errors/entity/invalid".to_owned(),
+    title: "The entity must have a type of \"article\", \"character\", \"location\" or \"map\", and a valid ID".to_owned(),
+    field: field.to_owned(),
+  }
+}
+
+/// Helper to build a Validation Error for a direction to walk the graph in that doesn't exist
+pub fn invalid_direction_error() -> ValidationError {
+  ValidationError {
+    r#type: "tag:universe,2020:relationships/validation-errors/direction/invalid".to_owned(),
+    title: "The direction must be one of \"both\", \"outgoing\" or \"incoming\"".to_owned(),
+    field: "direction".to_owned(),
+  }
+}
+
+/// Helper to build a Validation Error for a graph format that isn't supported
+pub fn invalid_format_error() -> ValidationError {
+  ValidationError {
+    r#type: "tag:universe,2020:relationships/validation-errors/format/invalid".to_owned(),
+    title: "The format must be one of \"json\" or \"dot\"".to_owned(),
+    field: "format".to_owned(),
+  }
+}
+
+/// Helper to build a Validation Error for a calendar that isn't part of the world
+pub fn unknown_calendar_error() -> ValidationError {
+  ValidationError {
+    r#type: "tag:universe,2020:relationships/validation-errors/during/unknown-calendar".to_owned(),
+    title: "The calendar is not a calendar of the world".to_owned(),
+    field: "during.calendar".to_owned(),
+  }
+}
+
+/// Helper to build a Validation Error for a date range that ends before it starts
+pub fn dates_out_of_order_error() -> ValidationError {
+  ValidationError {
+    r#type: "tag:universe,2020:relationships/validation-errors/during/out-of-order".to_owned(),
+    title: "The end of the date range must not be before the start".to_owned(),
+    field: "during.end".to_owned(),
+  }
+}
+
+/// Helper to build a Validation Error for one end of a date range
+///
+/// # Arguments
+/// * `field` Which end of the date range the date was provided for
+/// * `e` What was wrong with the date
+pub fn during_date_error(field: &str, e: &DateError) -> ValidationError {
+  date_error(&format!("during.{}", field), e)
+}
+
+impl From<&RelationshipValidationError> for ValidationError {
+  fn from(e: &RelationshipValidationError) -> Self {
+    match e {
+      RelationshipValidationError::UnknownSource => ValidationError {
+        r#type: "tag:universe,2020:relationships/validation-errors/source/unknown".to_owned(),
+        title: "The source is not an entity of the world".to_owned(),
+        field: "source".to_owned(),
+      },
+      RelationshipValidationError::UnknownTarget => ValidationError {
+        r#type: "tag:universe,2020:relationships/validation-errors/target/unknown".to_owned(),
+        title: "The target is not an entity of the world".to_owned(),
+        field: "target".to_owned(),
+      },
+      RelationshipValidationError::SameEntity => ValidationError {
+        r#type: "tag:universe,2020:relationships/validation-errors/target/same-as-source"
+          .to_owned(),
+        title: "An entity can't have a relationship with itself".to_owned(),
+        field: "target".to_owned(),
+      },
+    }
+  }
+}
+
+impl From<CreateRelationshipError> for Problem {
+  fn from(e: CreateRelationshipError) -> Self {
+    match e {
+      CreateRelationshipError::ValidationError(errors) => {
+        validation_error(errors.iter().map(|e| e.into()).collect())
+      }
+      CreateRelationshipError::UnknownWorld => unknown_world_problem(),
+      CreateRelationshipError::UnknownCalendar => validation_error(vec![unknown_calendar_error()]),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<UpdateRelationshipError> for Problem {
+  fn from(e: UpdateRelationshipError) -> Self {
+    match e {
+      UpdateRelationshipError::ValidationError(errors) => {
+        validation_error(errors.iter().map(|e| e.into()).collect())
+      }
+      UpdateRelationshipError::UnknownRelationship => unknown_relationship_problem(),
+      UpdateRelationshipError::UnknownCalendar => validation_error(vec![unknown_calendar_error()]),
+      UpdateRelationshipError::OptimisticLockFailure => optimistic_lock_problem(),
+      UpdateRelationshipError::UpdateError(e) if e.is::<ValidationErrors>() => e
+        .downcast_ref::<ValidationErrors>()
+        .unwrap()
+        .errors
+        .clone()
+        .into(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<DeleteRelationshipError> for Problem {
+  fn from(e: DeleteRelationshipError) -> Self {
+    match e {
+      DeleteRelationshipError::UnknownRelationship => unknown_relationship_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
@@ -0,0 +1,19 @@
+use super::delete::*;
+use super::get::*;
+use super::graph::*;
+use super::patch::*;
+use super::post::*;
+use rocket::{routes, Route};
+
+pub fn routes() -> Vec<Route> {
+  routes![
+    list_relationships,
+    get_relationship,
+    create_relationship,
+    update_relationship,
+    delete_relationship,
+    get_neighbours,
+    get_path,
+    get_graph
+  ]
+}
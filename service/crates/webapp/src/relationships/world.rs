@@ -0,0 +1,211 @@
+use super::problems::{
+  dates_out_of_order_error, during_date_error, invalid_direction_error, invalid_entity_error,
+  unknown_calendar_error, unknown_relationship_problem,
+};
+use crate::problem::{missing_error, Problem, ValidationError};
+use serde::Deserialize;
+use std::cmp::Ordering;
+use tracing::warn;
+use universe_calendars::{CalendarEntity, CalendarService};
+use universe_relationships::{
+  DateRange, Direction, EntityRef, RelationshipEntity, RelationshipID, RelationshipService,
+};
+use universe_worlds::WorldEntity;
+
+/// Parse the ID of a relationship, as provided in the URL
+pub(super) fn parse_relationship_id(relationship_id: String) -> Result<RelationshipID, Problem> {
+  relationship_id.parse().map_err(|e| {
+    warn!("Invalid Relationship ID: {}", e);
+    unknown_relationship_problem()
+  })
+}
+
+/// Parse a reference to an entity, as provided in the query string in the form "type:id"
+///
+/// # Arguments
+/// * `field` The query parameter that the entity was provided in
+/// * `input` The entity, as provided in the request
+///
+/// # Returns
+/// The entity, or the validation error with it
+pub(super) fn parse_entity(field: &str, input: Option<&str>) -> Result<EntityRef, ValidationError> {
+  match input
+    .map(|input| input.trim())
+    .filter(|input| !input.is_empty())
+  {
+    None => Err(missing_error(field)),
+    Some(input) => input.parse().map_err(|e| {
+      warn!("Invalid Entity: {}", e);
+      invalid_entity_error(field)
+    }),
+  }
+}
+
+/// Parse the direction to walk the graph in, as provided in the query string. Relationships are
+/// followed both ways if no direction was given
+pub(super) fn parse_direction(input: Option<&str>) -> Result<Direction, ValidationError> {
+  match input.map(|input| input.trim()) {
+    None | Some("both") => Ok(Direction::Both),
+    Some("outgoing") => Ok(Direction::Outgoing),
+    Some("incoming") => Ok(Direction::Incoming),
+    Some(direction) => {
+      warn!("Invalid Direction: {}", direction);
+      Err(invalid_direction_error())
+    }
+  }
+}
+
+/// Look up a relationship of a world
+///
+/// # Arguments
+/// * `relationship_service` The relationship service to look the relationship up with
+/// * `world` The world that the relationship belongs to
+/// * `relationship_id` The ID of the relationship, as provided in the URL
+///
+/// # Returns
+/// The relationship, or a Problem if it couldn't be found
+pub(super) fn find_relationship(
+  relationship_service: &dyn RelationshipService,
+  world: &WorldEntity,
+  relationship_id: String,
+) -> Result<RelationshipEntity, Problem> {
+  let relationship_id = parse_relationship_id(relationship_id)?;
+
+  relationship_service
+    .get_relationship_by_id(&world.identity.id, &relationship_id)
+    .ok_or_else(unknown_relationship_problem)
+}
+
+/// Look up every calendar that the given relationships are dated in, so that their dates can be
+/// written out
+///
+/// # Arguments
+/// * `calendar_service` The calendar service to look the calendars up with
+/// * `world` The world that the relationships belong to
+/// * `relationships` The relationships
+///
+/// # Returns
+/// The calendars
+pub(super) fn find_calendars<'a>(
+  calendar_service: &dyn CalendarService,
+  world: &WorldEntity,
+  relationships: impl IntoIterator<Item = &'a RelationshipEntity>,
+) -> Vec<CalendarEntity> {
+  let mut calendars: Vec<CalendarEntity> = vec![];
+
+  for during in relationships
+    .into_iter()
+    .filter_map(|relationship| relationship.data.during.as_ref())
+  {
+    if calendars
+      .iter()
+      .any(|calendar| calendar.identity.id == during.calendar)
+    {
+      continue;
+    }
+    if let Some(calendar) =
+      calendar_service.get_calendar_by_id(&world.identity.id, &during.calendar)
+    {
+      calendars.push(calendar);
+    }
+  }
+
+  calendars
+}
+
+/// The entity at one end of an incoming relationship
+#[derive(Deserialize, Debug, Clone)]
+pub struct NewEntity {
+  /// What sort of entity it is. One of "article", "character", "location" or "map"
+  pub r#type: Option<String>,
+  pub id: Option<String>,
+}
+
+impl NewEntity {
+  /// Convert the incoming entity into a reference to the entity
+  ///
+  /// # Arguments
+  /// * `field` The field that the entity was provided in
+  pub(super) fn into_entity_ref(self, field: &str) -> Result<EntityRef, ValidationError> {
+    match (self.r#type, self.id) {
+      (Some(kind), Some(id)) => EntityRef::new(kind.trim(), id.trim()).map_err(|e| {
+        warn!("Invalid Entity: {}", e);
+        invalid_entity_error(field)
+      }),
+      _ => Err(missing_error(field)),
+    }
+  }
+}
+
+/// When an incoming relationship held, in some calendar of the world. The dates are written the
+/// same way as any other date of the calendar
+#[derive(Deserialize, Debug, Clone)]
+pub struct NewDuring {
+  pub calendar: Option<String>,
+  pub start: Option<String>,
+  pub end: Option<String>,
+}
+
+impl NewDuring {
+  /// Convert the incoming dates into the date range of a relationship
+  ///
+  /// # Arguments
+  /// * `calendar_service` The calendar service to look the calendar up with
+  /// * `world` The world that the relationship belongs to
+  ///
+  /// # Returns
+  /// The date range, or the validation errors with the incoming dates
+  pub(super) fn into_date_range(
+    self,
+    calendar_service: &dyn CalendarService,
+    world: &WorldEntity,
+  ) -> Result<DateRange, Vec<ValidationError>> {
+    let calendar = match self.calendar.as_deref().map(|calendar| calendar.trim()) {
+      None | Some("") => return Err(vec![missing_error("during.calendar")]),
+      Some(calendar) => calendar
+        .parse()
+        .ok()
+        .and_then(|calendar_id| {
+          calendar_service.get_calendar_by_id(&world.identity.id, &calendar_id)
+        })
+        .ok_or_else(|| vec![unknown_calendar_error()])?,
+    };
+    let definition = &calendar.data.definition;
+
+    // Either end of the range may be left open
+    let parse = |field: &str, input: Option<String>| match input
+      .as_deref()
+      .map(|input| input.trim())
+      .filter(|input| !input.is_empty())
+    {
+      None => Ok(None),
+      Some(input) => definition
+        .parse_date(input)
+        .map(Some)
+        .map_err(|e| during_date_error(field, &e)),
+    };
+    let (start, end) = match (parse("start", self.start), parse("end", self.end)) {
+      (Ok(start), Ok(end)) => (start, end),
+      (start, end) => {
+        return Err(
+          vec![start.err(), end.err()]
+            .into_iter()
+            .filter_map(|v| v)
+            .collect(),
+        )
+      }
+    };
+
+    if let (Some(start), Some(end)) = (&start, &end) {
+      if definition.compare_dates(start, end) == Ordering::Greater {
+        return Err(vec![dates_out_of_order_error()]);
+      }
+    }
+
+    Ok(DateRange {
+      calendar: calendar.identity.id,
+      start,
+      end,
+    })
+  }
+}
@@ -1,25 +1,52 @@
 use rocket::{local::Client, Rocket};
 use rocket_cors::CorsOptions;
 use std::boxed::Box;
+use std::sync::Arc;
 use tracing::debug;
 
+/// How often to check the trash for worlds that have passed their retention period
+const TRASH_PURGE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3600);
+
 pub struct Service {
     rocket: Rocket,
+    world_service: Box<dyn universe_worlds::WorldService>,
+    world_retention: chrono::Duration,
 }
 
 impl Service {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         database_url: &str,
         port: Option<u16>,
         access_token_key: &str,
         migration_files: &str,
+        world_retention: chrono::Duration,
+        blob_store: Arc<dyn universe_storage::BlobStore>,
+        storage_user_quota: Option<u64>,
+        url_signer: universe_storage::UrlSigner,
     ) -> Self {
         debug!("Building Universe...");
 
         let database = universe_database::builder::new(database_url, migration_files).unwrap();
 
+        // Blobs stored for a world count against the quota of the user that owns the world
+        let blob_store: Arc<dyn universe_storage::BlobStore> = match storage_user_quota {
+            Some(quota) => Arc::new(
+                universe_storage::QuotaBlobStore::new(blob_store, quota).with_owners(Arc::new(
+                    universe_worlds::new_world_blob_owners(database.clone()),
+                )),
+            ),
+            None => blob_store,
+        };
+
         let healthchecker = crate::health::HealthcheckerBuilder::default()
             .add("database", Box::new(database.clone()))
+            .add(
+                "storage",
+                Box::new(universe_storage::StorageHealthcheck::new(
+                    blob_store.clone(),
+                )),
+            )
             .build();
 
         let mut config = rocket::Config::active().unwrap();
@@ -46,19 +73,85 @@ impl Service {
             // TODO: Stop cloning the database wrapper
             .manage(Box::new(universe_users::new_user_service(database.clone()))
                 as Box<dyn universe_users::UserService>)
+            .manage(Box::new(universe_worlds::new_world_service(
+                database.clone(),
+                blob_store.clone(),
+            )) as Box<dyn universe_worlds::WorldService>)
+            .manage(Box::new(universe_worlds::new_world_cover_service(
+                database.clone(),
+                blob_store.clone(),
+            ))
+                as Box<dyn universe_worlds::WorldCoverService>)
             .manage(
-                Box::new(universe_worlds::new_world_service(database.clone()))
-                    as Box<dyn universe_worlds::WorldService>,
+                Box::new(universe_articles::new_article_service(database.clone()))
+                    as Box<dyn universe_articles::ArticleService>,
             )
+            .manage(
+                Box::new(universe_characters::new_character_service(database.clone()))
+                    as Box<dyn universe_characters::CharacterService>,
+            )
+            .manage(
+                Box::new(universe_locations::new_location_service(database.clone()))
+                    as Box<dyn universe_locations::LocationService>,
+            )
+            .manage(Box::new(universe_maps::new_map_service(
+                database.clone(),
+                blob_store.clone(),
+            )) as Box<dyn universe_maps::MapService>)
+            .manage(
+                Box::new(universe_maps::new_map_feature_service(database.clone()))
+                    as Box<dyn universe_maps::MapFeatureService>,
+            )
+            .manage(
+                Box::new(universe_calendars::new_calendar_service(database.clone()))
+                    as Box<dyn universe_calendars::CalendarService>,
+            )
+            .manage(
+                Box::new(universe_calendars::new_timeline_service(database.clone()))
+                    as Box<dyn universe_calendars::TimelineService>,
+            )
+            .manage(Box::new(universe_relationships::new_relationship_service(
+                database.clone(),
+            )) as Box<dyn universe_relationships::RelationshipService>)
+            .manage(Box::new(universe_world_content::new_world_content_service(
+                database.clone(),
+                blob_store.clone(),
+            ))
+                as Box<dyn universe_world_content::WorldContentService>)
+            .manage(blob_store.clone())
+            .manage(url_signer)
             .mount("/", crate::health::routes())
             .mount("/", crate::users::routes())
             .mount("/", crate::worlds::routes())
+            .mount("/", crate::articles::routes())
+            .mount("/", crate::characters::routes())
+            .mount("/", crate::locations::routes())
+            .mount("/", crate::maps::routes())
+            .mount("/", crate::calendars::routes())
+            .mount("/", crate::relationships::routes())
+            .mount("/", crate::storage::routes())
             .mount("/", crate::authentication::routes());
 
-        Service { rocket }
+        let world_service = Box::new(universe_worlds::new_world_service(
+            database.clone(),
+            blob_store,
+        ));
+
+        Service {
+            rocket,
+            world_service,
+            world_retention,
+        }
     }
 
     pub fn launch(self) {
+        let world_service = self.world_service;
+        let world_retention = self.world_retention;
+        std::thread::spawn(move || loop {
+            world_service.purge_trashed_worlds(world_retention);
+            std::thread::sleep(TRASH_PURGE_INTERVAL);
+        });
+
         self.rocket.launch();
     }
 
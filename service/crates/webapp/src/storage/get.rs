@@ -0,0 +1,68 @@
+use super::problems::unknown_blob_problem;
+use crate::problem::{unexpected_error, Problem};
+use crate::request_id::RequestId;
+use rocket::{
+  get,
+  http::{
+    hyper::header::{CacheControl, CacheDirective},
+    Status,
+  },
+  response::{Body, Responder, Response},
+  Request, State,
+};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::warn;
+use universe_storage::{BlobStore, BlobStream, UrlSigner};
+
+/// Representation of a stored file that is being downloaded
+#[derive(Debug)]
+pub struct StoredBlob(BlobStream);
+
+impl<'a> Responder<'a> for StoredBlob {
+  /// Generate a Rocket response that streams the file to the client
+  fn respond_to(self, _: &Request) -> Result<Response<'a>, Status> {
+    let BlobStream {
+      content_type,
+      length,
+      reader,
+    } = self.0;
+
+    // The link is specific to whoever it was generated for, so must never be in a shared cache
+    Response::build()
+      .raw_header("Content-Type", content_type)
+      .header(CacheControl(vec![CacheDirective::Private]))
+      .raw_body(Body::Sized(reader, length))
+      .ok()
+  }
+}
+
+#[get("/storage/<key..>?<expires>&<signature>")]
+#[tracing::instrument(skip(blob_store, url_signer))]
+pub fn get_blob(
+  _request_id: RequestId,
+  key: PathBuf,
+  expires: u64,
+  signature: String,
+  blob_store: State<Arc<dyn BlobStore>>,
+  url_signer: State<UrlSigner>,
+) -> Result<StoredBlob, Problem> {
+  let key = key
+    .iter()
+    .map(|segment| segment.to_str())
+    .collect::<Option<Vec<&str>>>()
+    .ok_or_else(unknown_blob_problem)?
+    .join("/");
+
+  url_signer.verify(&key, expires, &signature)?;
+
+  let stream = blob_store
+    .stream(&key)
+    .map_err(|e| {
+      warn!("Failed to read blob {}: {}", key, e);
+      unexpected_error()
+    })?
+    .ok_or_else(unknown_blob_problem)?;
+
+  Ok(StoredBlob(stream))
+}
@@ -0,0 +1,5 @@
+mod get;
+mod problems;
+mod routes;
+
+pub use routes::routes;
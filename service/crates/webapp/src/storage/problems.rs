@@ -0,0 +1,31 @@
+use crate::problem::Problem;
+use universe_storage::SignatureError;
+
+/// Helper to build a Problem response for a blob that doesn't exist
+pub fn unknown_blob_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:storage/problems/unknown-blob".to_owned(),
+    title: "The requested file could not be found".to_owned(),
+    status: 404,
+    ..Default::default()
+  }
+}
+
+impl From<SignatureError> for Problem {
+  fn from(e: SignatureError) -> Self {
+    match e {
+      SignatureError::Expired => Problem {
+        r#type: "tag:universe,2020:storage/problems/expired-signature".to_owned(),
+        title: "The link to the file has expired".to_owned(),
+        status: 403,
+        ..Default::default()
+      },
+      SignatureError::Invalid => Problem {
+        r#type: "tag:universe,2020:storage/problems/invalid-signature".to_owned(),
+        title: "The link to the file is not valid".to_owned(),
+        status: 403,
+        ..Default::default()
+      },
+    }
+  }
+}
@@ -0,0 +1,6 @@
+use super::get::*;
+use rocket::{routes, Route};
+
+pub fn routes() -> Vec<Route> {
+  routes![get_blob]
+}
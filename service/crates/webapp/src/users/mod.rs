@@ -2,7 +2,7 @@ mod get;
 pub(crate) mod model;
 mod patch;
 mod post;
-mod problems;
+pub(crate) mod problems;
 mod routes;
 
 pub use routes::routes;
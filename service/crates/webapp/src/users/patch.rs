@@ -1,6 +1,6 @@
 use super::model::User;
-use super::problems::{unknown_user_problem, ValidationErrors};
-use crate::problem::{Problem, ValidationError};
+use super::problems::unknown_user_problem;
+use crate::problem::{Problem, ValidationError, ValidationErrors};
 use crate::{authentication::Authorizer, request_id::RequestId};
 use rocket::{patch, State};
 use rocket_contrib::json::Json;
@@ -1,4 +1,6 @@
-use crate::problem::{missing_error, unexpected_error, validation_error, Problem, ValidationError};
+use crate::problem::{
+  missing_error, unexpected_error, validation_error, Problem, ValidationError, ValidationErrors,
+};
 use universe_users::*;
 
 /// Helper to build a Problem response for an unknown user
@@ -87,19 +89,6 @@ impl From<RegisterUserError> for Problem {
   }
 }
 
-#[derive(Debug)]
-pub struct ValidationErrors {
-  pub errors: Vec<ValidationError>,
-}
-
-impl std::fmt::Display for ValidationErrors {
-  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-    write!(f, "{:?}", self.errors)
-  }
-}
-
-impl std::error::Error for ValidationErrors {}
-
 impl From<UpdateUserError> for Problem {
   fn from(e: UpdateUserError) -> Self {
     match e {
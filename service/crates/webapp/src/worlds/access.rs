@@ -0,0 +1,93 @@
+use super::problems::unknown_world_problem;
+use crate::authentication::{ApiAccessToken, Authorizer};
+use crate::problem::Problem;
+use tracing::warn;
+use universe_users::UserID;
+use universe_worlds::{WorldEntity, WorldID, WorldRole, WorldService};
+
+/// Look up a world, as long as it is visible to the given user
+///
+/// # Arguments
+/// * `world_service` The world service to look the world up with
+/// * `viewer` The ID of the user looking at the world, if they are authenticated
+/// * `world_id` The ID of the world, as provided in the URL
+///
+/// # Returns
+/// The world, or a Problem if it couldn't be found
+fn find_world(
+  world_service: &dyn WorldService,
+  viewer: Option<&UserID>,
+  world_id: String,
+) -> Result<WorldEntity, Problem> {
+  let world_id: WorldID = world_id.parse().map_err(|e| {
+    warn!("Invalid World ID: {}", e);
+    unknown_world_problem()
+  })?;
+
+  world_service
+    .get_world_by_id(&world_id)
+    .filter(|world| world_service.is_world_visible_to(world, viewer))
+    .ok_or_else(unknown_world_problem)
+}
+
+/// Look up the world that some content belongs to, as long as it is visible to the caller.
+/// Anyone that can see a world can read all of its content.
+///
+/// # Arguments
+/// * `world_service` The world service to look the world up with
+/// * `access_token` The access token of the caller, if they are authenticated
+/// * `world_id` The ID of the world, as provided in the URL
+///
+/// # Returns
+/// The world, or a Problem if it couldn't be found
+pub(crate) fn find_visible_world(
+  world_service: &dyn WorldService,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+) -> Result<WorldEntity, Problem> {
+  let viewer = access_token.map(|access_token| access_token.access_token.user_id);
+
+  find_world(world_service, viewer.as_ref(), world_id)
+}
+
+/// Look up a world that the authenticated caller wants to act on, as long as it is visible to
+/// them. Worlds that the caller can't see are reported as not found rather than forbidden, so
+/// that the role checks made afterwards don't reveal that private worlds exist.
+///
+/// # Arguments
+/// * `world_service` The world service to look the world up with
+/// * `authorizer` The authorizer for the caller
+/// * `world_id` The ID of the world, as provided in the URL
+///
+/// # Returns
+/// The world, or a Problem if it couldn't be found
+pub(crate) fn find_authorized_world(
+  world_service: &dyn WorldService,
+  authorizer: &Authorizer,
+  world_id: String,
+) -> Result<WorldEntity, Problem> {
+  find_world(world_service, Some(authorizer.user_id()), world_id)
+}
+
+/// Look up the world that some content belongs to, as long as the caller is allowed to edit it.
+/// Anyone that can edit a world can change all of its content.
+///
+/// # Arguments
+/// * `world_service` The world service to look the world up with
+/// * `authorizer` The authorizer for the caller
+/// * `world_id` The ID of the world, as provided in the URL
+///
+/// # Returns
+/// The world, or a Problem if it couldn't be found or the caller may not edit it
+pub(crate) fn find_editable_world(
+  world_service: &dyn WorldService,
+  authorizer: &Authorizer,
+  world_id: String,
+) -> Result<WorldEntity, Problem> {
+  let world = find_authorized_world(world_service, authorizer, world_id)?;
+  authorizer
+    .world_role(world_service, &world, WorldRole::Editor)
+    .to_result()?;
+
+  Ok(world)
+}
@@ -0,0 +1,100 @@
+use super::{
+  access::{find_authorized_world, find_visible_world},
+  model::{CoverImage, World},
+  problems::{cover_too_large_problem, unknown_cover_problem},
+};
+use crate::authentication::{ApiAccessToken, Authorizer};
+use crate::problem::{unexpected_error, Problem};
+use crate::request_id::RequestId;
+use rocket::{delete, get, put, Data, State};
+use std::io::Read;
+use tracing::{debug, warn};
+use universe_worlds::{
+  CoverID, CoverSize, WorldCoverService, WorldRole, WorldService, MAX_COVER_BYTES,
+};
+
+#[put("/worlds/<world_id>/cover", data = "<image>")]
+#[tracing::instrument(skip(world_service, world_cover_service, authorizer, image))]
+pub fn put_world_cover(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  image: Data,
+  world_service: State<Box<dyn WorldService>>,
+  world_cover_service: State<Box<dyn WorldCoverService>>,
+) -> Result<World, Problem> {
+  let world = find_authorized_world(&**world_service, &authorizer, world_id)?;
+  authorizer
+    .world_role(&**world_service, &world, WorldRole::Editor)
+    .to_result()?;
+
+  // Read one byte more than we allow, so that we can tell if the upload was too large without
+  // reading the whole thing into memory
+  let mut bytes = vec![];
+  image
+    .open()
+    .take(MAX_COVER_BYTES as u64 + 1)
+    .read_to_end(&mut bytes)
+    .map_err(|e| {
+      warn!("Failed to read cover image: {}", e);
+      unexpected_error()
+    })?;
+  if bytes.len() > MAX_COVER_BYTES {
+    return Err(cover_too_large_problem());
+  }
+
+  let world = world_cover_service.set_world_cover(&world.identity.id, &bytes)?;
+  debug!("Updated cover of world: {:?}", world);
+
+  Ok(world.into())
+}
+
+#[delete("/worlds/<world_id>/cover")]
+#[tracing::instrument(skip(world_service, world_cover_service, authorizer))]
+pub fn delete_world_cover(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  world_service: State<Box<dyn WorldService>>,
+  world_cover_service: State<Box<dyn WorldCoverService>>,
+) -> Result<World, Problem> {
+  let world = find_authorized_world(&**world_service, &authorizer, world_id)?;
+  authorizer
+    .world_role(&**world_service, &world, WorldRole::Editor)
+    .to_result()?;
+
+  let world = world_cover_service.remove_world_cover(&world.identity.id)?;
+
+  Ok(world.into())
+}
+
+#[get("/worlds/<world_id>/cover/<cover_id>/<size>")]
+#[tracing::instrument(skip(world_service, world_cover_service))]
+pub fn get_world_cover(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  cover_id: String,
+  size: String,
+  world_service: State<Box<dyn WorldService>>,
+  world_cover_service: State<Box<dyn WorldCoverService>>,
+) -> Result<CoverImage, Problem> {
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+  let cover_id: CoverID = cover_id.parse().map_err(|e| {
+    warn!("Invalid Cover ID: {}", e);
+    unknown_cover_problem()
+  })?;
+  let size: CoverSize = size.parse().map_err(|e| {
+    warn!("Invalid Cover Size: {}", e);
+    unknown_cover_problem()
+  })?;
+
+  let blob = world_cover_service
+    .get_world_cover(&world.identity.id, &cover_id, size)
+    .ok_or_else(unknown_cover_problem)?;
+
+  Ok(CoverImage {
+    blob,
+    visibility: world.data.visibility,
+  })
+}
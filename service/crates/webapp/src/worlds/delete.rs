@@ -0,0 +1,23 @@
+use super::access::find_authorized_world;
+use crate::problem::Problem;
+use crate::{authentication::Authorizer, request_id::RequestId};
+use rocket::{delete, http::Status, State};
+use universe_worlds::{WorldRole, WorldService};
+
+#[delete("/worlds/<world_id>")]
+#[tracing::instrument(skip(world_service, authorizer))]
+pub fn delete_world(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  world_service: State<Box<dyn WorldService>>,
+) -> Result<Status, Problem> {
+  let world = find_authorized_world(&**world_service, &authorizer, world_id)?;
+  authorizer
+    .world_role(&**world_service, &world, WorldRole::Owner)
+    .to_result()?;
+
+  world_service.delete_world(&world.identity.id)?;
+
+  Ok(Status::NoContent)
+}
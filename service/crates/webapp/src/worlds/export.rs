@@ -0,0 +1,28 @@
+use super::access::find_visible_world;
+use super::model::ExportedWorld;
+use crate::authentication::ApiAccessToken;
+use crate::problem::Problem;
+use crate::request_id::RequestId;
+use rocket::{get, State};
+use universe_world_content::WorldContentService;
+use universe_worlds::WorldService;
+
+#[get("/worlds/<world_id>/export")]
+#[tracing::instrument(skip(world_service, world_content_service))]
+pub fn export_world(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  world_service: State<Box<dyn WorldService>>,
+  world_content_service: State<Box<dyn WorldContentService>>,
+) -> Result<ExportedWorld, Problem> {
+  // Anyone that can see a world is allowed to export it
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+
+  let archive = world_content_service.export_world(&world);
+
+  Ok(ExportedWorld {
+    filename: format!("{}.json", world.data.slug),
+    archive,
+  })
+}
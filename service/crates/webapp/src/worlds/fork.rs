@@ -0,0 +1,30 @@
+use super::access::find_visible_world;
+use super::model::CreatedWorld;
+use crate::authentication::ApiAccessToken;
+use crate::problem::Problem;
+use crate::request_id::RequestId;
+use rocket::{post, State};
+use tracing::debug;
+use universe_world_content::WorldContentService;
+use universe_worlds::WorldService;
+
+#[post("/worlds/<world_id>/fork")]
+#[tracing::instrument(skip(world_service, world_content_service))]
+pub fn fork_world(
+  _request_id: RequestId,
+  access_token: ApiAccessToken,
+  world_id: String,
+  world_service: State<Box<dyn WorldService>>,
+  world_content_service: State<Box<dyn WorldContentService>>,
+) -> Result<CreatedWorld, Problem> {
+  let user_id = access_token.access_token.user_id.clone();
+
+  // Anyone that can see a world is allowed to fork it
+  let world = find_visible_world(&**world_service, Some(access_token), world_id)?;
+
+  // The content of the world comes along with it, so that the fork is a complete copy
+  let fork = world_content_service.fork_world(&world.identity.id, &user_id)?;
+  debug!("Forked world: {:?}", fork);
+
+  Ok(CreatedWorld { world: fork.into() })
+}
@@ -0,0 +1,82 @@
+use super::{
+  access::find_visible_world,
+  model::{MovedWorld, World, WorldBySlug},
+  problems::unknown_world_problem,
+};
+use crate::authentication::ApiAccessToken;
+use crate::problem::Problem;
+use crate::request_id::RequestId;
+use rocket::{get, State};
+use tracing::{debug, warn};
+use universe_users::{UserService, Username};
+use universe_worlds::{Slug, WorldService};
+
+#[get("/worlds/<world_id>")]
+#[tracing::instrument(skip(world_service))]
+pub fn get_world_by_id(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  world_id: String,
+  world_service: State<Box<dyn WorldService>>,
+) -> Result<World, Problem> {
+  let world = find_visible_world(&**world_service, access_token, world_id)?;
+
+  Ok(world.into())
+}
+
+#[get("/users/<username>/worlds/<slug>")]
+#[tracing::instrument(skip(user_service, world_service))]
+pub fn get_world_by_slug(
+  _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
+  username: String,
+  slug: String,
+  user_service: State<Box<dyn UserService>>,
+  world_service: State<Box<dyn WorldService>>,
+) -> Result<WorldBySlug, Problem> {
+  let username: Username = username.parse().map_err(|e| {
+    warn!("Invalid Username: {}", e);
+    unknown_world_problem()
+  })?;
+  let slug: Slug = slug.parse().map_err(|e| {
+    warn!("Invalid Slug: {}", e);
+    unknown_world_problem()
+  })?;
+
+  let owner = user_service
+    .get_user_by_username(&username)
+    .ok_or_else(unknown_world_problem)?;
+
+  let viewer = access_token.map(|access_token| access_token.access_token.user_id);
+
+  if let Some(world) = world_service.get_world_by_slug(&owner.identity.id, &slug) {
+    return if world_service.is_world_visible_to(&world, viewer.as_ref()) {
+      Ok(WorldBySlug::Current(Box::new(world.into())))
+    } else {
+      Err(unknown_world_problem())
+    };
+  }
+
+  let world = world_service
+    .get_world_by_previous_slug(&owner.identity.id, &slug)
+    .filter(|world| world_service.is_world_visible_to(world, viewer.as_ref()))
+    .ok_or_else(unknown_world_problem)?;
+  debug!(
+    "World {} was requested by previous slug {}",
+    world.identity.id, slug
+  );
+
+  // The world may since have been given to somebody else, so the redirect needs to go to whoever
+  // owns it now rather than the user it was requested by
+  let current_owner = user_service
+    .get_user_by_id(&world.data.owner)
+    .ok_or_else(unknown_world_problem)?;
+
+  Ok(WorldBySlug::Moved(MovedWorld {
+    location: format!(
+      "/users/{}/worlds/{}",
+      current_owner.data.username, world.data.slug
+    ),
+    id: world.identity.id,
+  }))
+}
@@ -0,0 +1,44 @@
+use super::model::CreatedWorld;
+use super::problems::{archive_too_large_problem, invalid_archive_problem};
+use crate::authentication::ApiAccessToken;
+use crate::problem::{unexpected_error, Problem};
+use crate::request_id::RequestId;
+use rocket::{post, Data, State};
+use std::io::Read;
+use tracing::warn;
+use universe_world_content::{WorldArchive, WorldContentService, MAX_ARCHIVE_BYTES};
+
+#[post("/worlds/import", data = "<archive>")]
+#[tracing::instrument(skip(world_content_service, archive))]
+pub fn import_world(
+  _request_id: RequestId,
+  access_token: ApiAccessToken,
+  archive: Data,
+  world_content_service: State<Box<dyn WorldContentService>>,
+) -> Result<CreatedWorld, Problem> {
+  // Read one byte more than we allow, so that we can tell if the upload was too large without
+  // reading the whole thing into memory
+  let mut bytes = vec![];
+  archive
+    .open()
+    .take(MAX_ARCHIVE_BYTES as u64 + 1)
+    .read_to_end(&mut bytes)
+    .map_err(|e| {
+      warn!("Failed to read world archive: {}", e);
+      unexpected_error()
+    })?;
+  if bytes.len() > MAX_ARCHIVE_BYTES {
+    return Err(archive_too_large_problem());
+  }
+
+  let archive: WorldArchive = serde_json::from_slice(&bytes).map_err(|e| {
+    warn!("Invalid world archive: {}", e);
+    invalid_archive_problem(e.to_string())
+  })?;
+
+  let world = world_content_service.import_world(&access_token.access_token.user_id, archive)?;
+
+  Ok(CreatedWorld {
+    world: world.into(),
+  })
+}
@@ -0,0 +1,151 @@
+use super::access::find_authorized_world;
+use super::model::Member;
+use super::problems::{unknown_member_problem, unknown_member_user_error, unknown_role_error};
+use crate::problem::{missing_error, Problem, ValidationError};
+use crate::{
+  authentication::Authorizer, page::SearchPage, problem::validation_error, request_id::RequestId,
+};
+use rocket::{delete, get, http::Status, patch, post, State};
+use rocket_contrib::json::Json;
+use serde::Deserialize;
+use tracing::{debug, warn};
+use universe_users::UserID;
+use universe_worlds::{WorldRole, WorldService};
+
+#[get("/worlds/<world_id>/members")]
+#[tracing::instrument(skip(world_service, authorizer))]
+pub fn list_world_members(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  world_service: State<Box<dyn WorldService>>,
+) -> Result<SearchPage<Member>, Problem> {
+  let world = find_authorized_world(&**world_service, &authorizer, world_id)?;
+  authorizer
+    .world_role(&**world_service, &world, WorldRole::Viewer)
+    .to_result()?;
+
+  let members = world_service.list_world_members(&world.identity.id);
+  Ok(members.into())
+}
+
+#[post("/worlds/<world_id>/members", data = "<new_member>")]
+#[tracing::instrument(skip(world_service, authorizer))]
+pub fn add_world_member(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  new_member: Json<NewMember>,
+  world_service: State<Box<dyn WorldService>>,
+) -> Result<Member, Problem> {
+  debug!("New Member: {:?}", new_member);
+
+  let world = find_authorized_world(&**world_service, &authorizer, world_id)?;
+  authorizer
+    .world_role(&**world_service, &world, WorldRole::Owner)
+    .to_result()?;
+
+  let user: Result<UserID, ValidationError> = match new_member.user {
+    None => Err(missing_error("user")),
+    Some(user) => user.trim().parse().map_err(|e| {
+      warn!("Invalid User ID: {}", e);
+      unknown_member_user_error()
+    }),
+  };
+  let role = parse_role(new_member.role);
+
+  let (user, role) = match (user, role) {
+    (Ok(user), Ok(role)) => (user, role),
+    (user, role) => {
+      let errors = vec![user.err(), role.err()]
+        .into_iter()
+        .filter_map(|v| v)
+        .collect();
+      return Err(validation_error(errors));
+    }
+  };
+
+  let member = world_service.add_world_member(&world.identity.id, &user, role)?;
+  Ok(member.into())
+}
+
+#[patch(
+  "/worlds/<world_id>/members/<user_id>",
+  format = "application/merge-patch+json",
+  data = "<patch_data>"
+)]
+#[tracing::instrument(skip(world_service, authorizer))]
+pub fn update_world_member(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  user_id: String,
+  patch_data: Json<PatchMember>,
+  world_service: State<Box<dyn WorldService>>,
+) -> Result<Member, Problem> {
+  debug!("Patch Data: {:?}", patch_data);
+
+  let world = find_authorized_world(&**world_service, &authorizer, world_id)?;
+  authorizer
+    .world_role(&**world_service, &world, WorldRole::Owner)
+    .to_result()?;
+  let user_id = parse_member(user_id)?;
+
+  let role = parse_role(patch_data.role).map_err(|e| validation_error(vec![e]))?;
+
+  let member = world_service.update_world_member(&world.identity.id, &user_id, role)?;
+  Ok(member.into())
+}
+
+#[delete("/worlds/<world_id>/members/<user_id>")]
+#[tracing::instrument(skip(world_service, authorizer))]
+pub fn remove_world_member(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  user_id: String,
+  world_service: State<Box<dyn WorldService>>,
+) -> Result<Status, Problem> {
+  let world = find_authorized_world(&**world_service, &authorizer, world_id)?;
+  let user_id = parse_member(user_id)?;
+  // Members are always allowed to leave a world, even if they can't manage its other members
+  authorizer
+    .world_role(&**world_service, &world, WorldRole::Owner)
+    .or(authorizer.same_user(&user_id))
+    .to_result()?;
+
+  world_service.remove_world_member(&world.identity.id, &user_id)?;
+
+  Ok(Status::NoContent)
+}
+
+/// Struct representing the input data for adding a new member to a world
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct NewMember<'a> {
+  pub user: Option<&'a str>,
+  pub role: Option<&'a str>,
+}
+
+/// Struct representing the input data for changing the role of a world member
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchMember<'a> {
+  pub role: Option<&'a str>,
+}
+
+/// Parse the ID of a world member, as provided in the URL
+fn parse_member(user_id: String) -> Result<UserID, Problem> {
+  user_id.parse().map_err(|e| {
+    warn!("Invalid User ID: {}", e);
+    unknown_member_problem()
+  })
+}
+
+/// Parse the role of a world member, as provided in the request body
+fn parse_role(role: Option<&str>) -> Result<WorldRole, ValidationError> {
+  match role {
+    None => Err(missing_error("role")),
+    Some(role) => role.trim().parse().map_err(|_| unknown_role_error()),
+  }
+}
@@ -1,5 +1,20 @@
+pub(crate) mod access;
+mod cover;
+mod delete;
+mod export;
+mod fork;
+mod get;
+mod import;
+mod members;
 mod model;
+mod patch;
+mod post;
+pub(crate) mod problems;
+mod revisions;
 mod routes;
 mod search;
+mod stars;
+mod transfer;
+mod trash;
 
 pub use routes::routes;
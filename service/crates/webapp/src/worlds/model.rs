@@ -11,7 +11,13 @@ use rocket::{
 };
 use rocket_contrib::json::Json;
 use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::Cursor;
 use universe_entity::Page;
+use universe_markdown::render_html;
+use universe_storage::Blob;
+use universe_users::UserID;
+use universe_world_content::WorldArchive;
 use universe_worlds::*;
 use uuid::Uuid;
 
@@ -20,6 +26,22 @@ use uuid::Uuid;
 #[serde(rename_all = "camelCase")]
 pub struct World {
   pub id: WorldID,
+  pub slug: Slug,
+  pub name: String,
+  /// The description of the world, as the CommonMark source that the user provided
+  pub description: String,
+  /// The description of the world, rendered to sanitized HTML
+  pub description_html: String,
+  pub owner: UserID,
+  pub visibility: Visibility,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub tags: Vec<Tag>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub forked_from: Option<WorldID>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub cover: Option<Cover>,
+  /// The number of users that have starred the world
+  pub stars: u32,
   #[serde(skip_serializing)]
   version: Uuid,
   #[serde(skip_serializing)]
@@ -27,29 +49,161 @@ pub struct World {
 }
 
 impl<'a> Responder<'a> for World {
-  /// Generate a Rocket response for the User
+  /// Generate a Rocket response for the World
   fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    // Private worlds must never be stored in a shared cache
+    let cache_scope = match self.visibility {
+      Visibility::Private => CacheDirective::Private,
+      _ => CacheDirective::Public,
+    };
+
     Response::build()
       .merge(Json(&self).respond_to(req)?)
-      .header(Link::from_href(format!("/users/{}", self.id)).with_rel("self"))
+      .header(Link::from_href(format!("/worlds/{}", self.id)).with_rel("self"))
       .header(AcceptPatch("application/merge-patch+json"))
-      .header(ETag(EntityTag::new(false, self.version.to_string())))
+      // The star count changes without the world being edited, so it's part of the tag as well
+      .header(ETag(EntityTag::new(
+        false,
+        format!("{}-{}", self.version, self.stars),
+      )))
       .header(LastModified(HttpDate(time::at_utc(time::Timespec::new(
         self.updated.timestamp(),
         0,
       )))))
       .header(CacheControl(vec![
-        CacheDirective::Public,
+        cache_scope,
         CacheDirective::MaxAge(3600),
       ]))
       .ok()
   }
 }
 
+/// Representation of the URLs of every size of the cover image of a World
+#[derive(Debug, Serialize)]
+pub struct Cover {
+  pub original: String,
+  pub small: String,
+  pub large: String,
+}
+
+impl Cover {
+  /// Build the URLs for every size of a cover image
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world the cover image belongs to
+  /// * `cover_id` The ID of the cover image
+  pub fn new(world_id: &WorldID, cover_id: &CoverID) -> Self {
+    let url = |size: CoverSize| format!("/worlds/{}/cover/{}/{}", world_id, cover_id, size);
+
+    Cover {
+      original: url(CoverSize::Original),
+      small: url(CoverSize::Small),
+      large: url(CoverSize::Large),
+    }
+  }
+}
+
+/// Representation of one size of the cover image of a World
+#[derive(Debug)]
+pub struct CoverImage {
+  pub blob: Blob,
+  pub visibility: Visibility,
+}
+
+impl<'a> Responder<'a> for CoverImage {
+  /// Generate a Rocket response for the cover image
+  fn respond_to(self, _: &Request) -> Result<Response<'a>, Status> {
+    // Private worlds must never be stored in a shared cache
+    let cache_scope = match self.visibility {
+      Visibility::Private => CacheDirective::Private,
+      _ => CacheDirective::Public,
+    };
+
+    // Cover images never change once uploaded, since a new upload gets a new URL
+    Response::build()
+      .raw_header("Content-Type", self.blob.content_type)
+      .header(CacheControl(vec![
+        cache_scope,
+        CacheDirective::MaxAge(31_536_000),
+        CacheDirective::Extension("immutable".to_owned(), None),
+      ]))
+      .sized_body(Cursor::new(self.blob.data))
+      .ok()
+  }
+}
+
+/// Representation of a World that has just been created
+#[derive(Debug, Serialize)]
+pub struct CreatedWorld {
+  #[serde(flatten)]
+  pub world: World,
+}
+
+impl<'a> Responder<'a> for CreatedWorld {
+  /// Generate a Rocket response for the newly created World
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(Link::from_href(format!("/worlds/{}", self.world.id)).with_rel("canonical"))
+      .ok()
+  }
+}
+
+/// Representation of a World that was requested by an old URL, and which has since moved
+#[derive(Debug)]
+pub struct MovedWorld {
+  pub id: WorldID,
+  pub location: String,
+}
+
+impl<'a> Responder<'a> for MovedWorld {
+  /// Generate a Rocket response redirecting the client to the new location of the World
+  fn respond_to(self, _: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .status(Status::MovedPermanently)
+      .raw_header("Location", self.location)
+      .header(Link::from_href(format!("/worlds/{}", self.id)).with_rel("canonical"))
+      .ok()
+  }
+}
+
+/// The possible outcomes of looking up a World by its slug
+#[derive(Debug)]
+pub enum WorldBySlug {
+  /// The World currently has the requested slug
+  Current(Box<World>),
+  /// The World used to have the requested slug, but has since been changed
+  Moved(MovedWorld),
+}
+
+impl<'a> Responder<'a> for WorldBySlug {
+  /// Generate a Rocket response for whichever outcome the lookup had
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    match self {
+      WorldBySlug::Current(world) => world.respond_to(req),
+      WorldBySlug::Moved(moved) => moved.respond_to(req),
+    }
+  }
+}
+
 impl From<&WorldEntity> for World {
   fn from(world: &WorldEntity) -> Self {
     World {
       id: world.identity.id.clone(),
+      slug: world.data.slug.clone(),
+      name: world.data.name.clone(),
+      description: world.data.description.clone(),
+      description_html: render_html(&world.data.description),
+      owner: world.data.owner.clone(),
+      visibility: world.data.visibility,
+      tags: world.data.tags.clone(),
+      forked_from: world.data.forked_from.clone(),
+      cover: world
+        .data
+        .cover
+        .as_ref()
+        .map(|cover_id| Cover::new(&world.identity.id, cover_id)),
+      stars: world.stars,
       version: world.identity.version,
       updated: world.identity.updated,
     }
@@ -61,15 +215,50 @@ impl From<WorldEntity> for World {
   }
 }
 
-impl<'a> Responder<'a> for SearchPage<World> {
-  /// Generate a Rocket response for the User
+/// Representation of a page of Worlds that matched a search, along with any facet counts that
+/// were requested
+#[derive(Debug, Serialize)]
+pub struct WorldSearchPage {
+  #[serde(flatten)]
+  pub page: SearchPage<World>,
+  #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+  pub facets: BTreeMap<String, Vec<FacetCount>>,
+  /// Whether the search was made on behalf of a user, and so may include worlds that only they can see
+  #[serde(skip_serializing)]
+  pub authenticated: bool,
+}
+
+impl<'a> Responder<'a> for WorldSearchPage {
+  /// Generate a Rocket response for the page of Worlds
   fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    // Authenticated searches can include private worlds, so must never be stored in a shared cache
+    let cache_scope = if self.authenticated {
+      CacheDirective::Private
+    } else {
+      CacheDirective::Public
+    };
+
     Response::build()
       .merge(Json(&self).respond_to(req)?)
       .header(CacheControl(vec![
-        CacheDirective::Public,
+        cache_scope,
         CacheDirective::MaxAge(3600),
       ]))
+      // The worlds that are returned depend on who is searching
+      .raw_header("Vary", "Authorization")
+      .ok()
+  }
+}
+
+impl<'a> Responder<'a> for SearchPage<World> {
+  /// Generate a Rocket response for the page of starred Worlds
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(CacheControl(vec![
+        CacheDirective::Private,
+        CacheDirective::NoCache,
+      ]))
       .ok()
   }
 }
@@ -82,3 +271,278 @@ impl From<Page<WorldEntity>> for SearchPage<World> {
     }
   }
 }
+
+/// Representation of a World that is currently in the trash
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashedWorld {
+  #[serde(flatten)]
+  pub world: World,
+  pub deleted: DateTime<Utc>,
+}
+
+impl From<&universe_worlds::TrashedWorld> for TrashedWorld {
+  fn from(trashed: &universe_worlds::TrashedWorld) -> Self {
+    TrashedWorld {
+      world: (&trashed.world).into(),
+      deleted: trashed.deleted,
+    }
+  }
+}
+
+impl<'a> Responder<'a> for SearchPage<TrashedWorld> {
+  /// Generate a Rocket response for the page of trashed Worlds
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(CacheControl(vec![
+        CacheDirective::Private,
+        CacheDirective::NoCache,
+      ]))
+      .ok()
+  }
+}
+
+impl From<Page<universe_worlds::TrashedWorld>> for SearchPage<TrashedWorld> {
+  fn from(trash: Page<universe_worlds::TrashedWorld>) -> Self {
+    SearchPage {
+      entries: trash.entries.iter().map(|trashed| trashed.into()).collect(),
+      total: trash.total,
+    }
+  }
+}
+
+/// Representation of a member of a World to return over the API
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Member {
+  pub user: UserID,
+  pub role: WorldRole,
+  pub created: DateTime<Utc>,
+  pub updated: DateTime<Utc>,
+}
+
+impl From<WorldMember> for Member {
+  fn from(member: WorldMember) -> Self {
+    Member {
+      user: member.user,
+      role: member.role,
+      created: member.created,
+      updated: member.updated,
+    }
+  }
+}
+
+impl<'a> Responder<'a> for Member {
+  /// Generate a Rocket response for the World Member
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(CacheControl(vec![
+        CacheDirective::Private,
+        CacheDirective::NoCache,
+      ]))
+      .ok()
+  }
+}
+
+impl<'a> Responder<'a> for SearchPage<Member> {
+  /// Generate a Rocket response for the list of World Members
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(CacheControl(vec![
+        CacheDirective::Private,
+        CacheDirective::NoCache,
+      ]))
+      .ok()
+  }
+}
+
+impl From<Vec<WorldMember>> for SearchPage<Member> {
+  fn from(members: Vec<WorldMember>) -> Self {
+    SearchPage {
+      total: members.len() as u32,
+      entries: members.into_iter().map(|member| member.into()).collect(),
+    }
+  }
+}
+
+/// Representation of a single revision of a World to return over the API
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Revision {
+  pub revision: u32,
+  pub created: DateTime<Utc>,
+  pub slug: Slug,
+  pub name: String,
+  pub description: String,
+  pub owner: UserID,
+  pub visibility: Visibility,
+  pub tags: Vec<Tag>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub forked_from: Option<WorldID>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub cover: Option<CoverID>,
+}
+
+impl From<WorldRevision> for Revision {
+  fn from(revision: WorldRevision) -> Self {
+    Revision {
+      revision: revision.revision,
+      created: revision.created,
+      slug: revision.data.slug,
+      name: revision.data.name,
+      description: revision.data.description,
+      owner: revision.data.owner,
+      visibility: revision.data.visibility,
+      tags: revision.data.tags,
+      forked_from: revision.data.forked_from,
+      cover: revision.data.cover,
+    }
+  }
+}
+
+impl<'a> Responder<'a> for Revision {
+  /// Generate a Rocket response for the World Revision
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(CacheControl(vec![
+        CacheDirective::Private,
+        CacheDirective::NoCache,
+      ]))
+      .ok()
+  }
+}
+
+impl<'a> Responder<'a> for SearchPage<Revision> {
+  /// Generate a Rocket response for the page of World Revisions
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(CacheControl(vec![
+        CacheDirective::Private,
+        CacheDirective::NoCache,
+      ]))
+      .ok()
+  }
+}
+
+impl From<Page<WorldRevision>> for SearchPage<Revision> {
+  fn from(revisions: Page<WorldRevision>) -> Self {
+    SearchPage {
+      total: revisions.total,
+      entries: revisions
+        .entries
+        .into_iter()
+        .map(|revision| revision.into())
+        .collect(),
+    }
+  }
+}
+
+/// Representation of the differences between two revisions of a World
+#[derive(Debug, Serialize)]
+pub struct RevisionDiff {
+  pub from: u32,
+  pub to: u32,
+  pub changes: Vec<WorldChange>,
+}
+
+impl<'a> Responder<'a> for RevisionDiff {
+  /// Generate a Rocket response for the differences between the World Revisions
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(CacheControl(vec![
+        CacheDirective::Private,
+        CacheDirective::NoCache,
+      ]))
+      .ok()
+  }
+}
+
+/// Representation of a pending transfer of the ownership of a World
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transfer {
+  pub world: WorldID,
+  pub from: UserID,
+  pub to: UserID,
+  pub created: DateTime<Utc>,
+}
+
+impl From<WorldTransfer> for Transfer {
+  fn from(transfer: WorldTransfer) -> Self {
+    Transfer {
+      world: transfer.world,
+      from: transfer.from,
+      to: transfer.to,
+      created: transfer.created,
+    }
+  }
+}
+
+impl<'a> Responder<'a> for Transfer {
+  /// Generate a Rocket response for the World Transfer
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(CacheControl(vec![
+        CacheDirective::Private,
+        CacheDirective::NoCache,
+      ]))
+      .ok()
+  }
+}
+
+impl<'a> Responder<'a> for SearchPage<Transfer> {
+  /// Generate a Rocket response for the list of World Transfers
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self).respond_to(req)?)
+      .header(CacheControl(vec![
+        CacheDirective::Private,
+        CacheDirective::NoCache,
+      ]))
+      .ok()
+  }
+}
+
+impl From<Vec<WorldTransfer>> for SearchPage<Transfer> {
+  fn from(transfers: Vec<WorldTransfer>) -> Self {
+    SearchPage {
+      total: transfers.len() as u32,
+      entries: transfers
+        .into_iter()
+        .map(|transfer| transfer.into())
+        .collect(),
+    }
+  }
+}
+
+/// Representation of a World that has been exported to an archive
+#[derive(Debug)]
+pub struct ExportedWorld {
+  pub archive: WorldArchive,
+  /// The name that clients should save the archive as
+  pub filename: String,
+}
+
+impl<'a> Responder<'a> for ExportedWorld {
+  /// Generate a Rocket response for the archive of the World
+  fn respond_to(self, req: &Request) -> Result<Response<'a>, Status> {
+    Response::build()
+      .merge(Json(&self.archive).respond_to(req)?)
+      .raw_header(
+        "Content-Disposition",
+        format!("attachment; filename=\"{}\"", self.filename),
+      )
+      .header(CacheControl(vec![
+        CacheDirective::Private,
+        CacheDirective::NoCache,
+      ]))
+      .ok()
+  }
+}
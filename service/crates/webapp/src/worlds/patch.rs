@@ -0,0 +1,110 @@
+use super::access::find_authorized_world;
+use super::model::World;
+use super::problems::unknown_visibility_error;
+use crate::problem::{missing_error, Problem, ValidationError, ValidationErrors};
+use crate::{authentication::Authorizer, request_id::RequestId};
+use rocket::{patch, State};
+use rocket_contrib::json::Json;
+use serde::Deserialize;
+use tracing::{debug, warn};
+use universe_worlds::*;
+
+#[patch(
+  "/worlds/<world_id>",
+  format = "application/merge-patch+json",
+  data = "<patch_data>"
+)]
+#[tracing::instrument(skip(world_service, authorizer))]
+pub fn update_world(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  patch_data: Json<PatchData>,
+  world_service: State<Box<dyn WorldService>>,
+) -> Result<World, Problem> {
+  debug!("Patch Data: {:?}", patch_data);
+
+  let world = find_authorized_world(&**world_service, &authorizer, world_id)?;
+  authorizer
+    .world_role(&**world_service, &world, WorldRole::Editor)
+    .to_result()?;
+  // Only owners are allowed to change who is able to see the world
+  if patch_data.visibility.is_some() {
+    authorizer
+      .world_role(&**world_service, &world, WorldRole::Owner)
+      .to_result()?;
+  }
+
+  let world = world_service.update_world(&world.identity.id, &mut |mut world| {
+    debug!("Patching world details");
+
+    let name_error = patch_data.name.and_then(|name| {
+      let name = name.trim();
+      if name.is_empty() {
+        Some(missing_error("name"))
+      } else {
+        world.name = name.to_owned();
+        None
+      }
+    });
+
+    let slug_error = patch_data.slug.and_then(|slug| {
+      slug
+        .parse()
+        .map_err(|e: SlugParseError| e.into())
+        .map(|slug: Slug| {
+          world.slug = slug;
+        })
+        .err()
+    });
+
+    let visibility_error = patch_data.visibility.and_then(|visibility| {
+      visibility
+        .trim()
+        .parse()
+        .map_err(|_| unknown_visibility_error())
+        .map(|visibility: Visibility| {
+          world.visibility = visibility;
+        })
+        .err()
+    });
+
+    let tags_error = patch_data.tags.as_ref().and_then(|tags| {
+      parse_tags(tags)
+        .map_err(|e| e.into())
+        .map(|tags| {
+          world.tags = tags;
+        })
+        .err()
+    });
+
+    if let Some(description) = &patch_data.description {
+      world.description = description.trim().to_owned();
+    }
+
+    match (name_error, slug_error, visibility_error, tags_error) {
+      (None, None, None, None) => Ok(world),
+      (name, slug, visibility, tags) => {
+        let errors: Vec<ValidationError> = vec![name, slug, visibility, tags]
+          .into_iter()
+          .filter_map(|v| v)
+          .collect();
+        warn!("Error patching world: {:?}", errors);
+        Err(Box::new(ValidationErrors { errors }))
+      }
+    }
+  })?;
+
+  Ok(world.into())
+}
+
+/// Struct representing the input data for updating a world
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchData<'a> {
+  pub name: Option<&'a str>,
+  pub slug: Option<&'a str>,
+  pub description: Option<String>,
+  pub visibility: Option<&'a str>,
+  pub tags: Option<Vec<String>>,
+}
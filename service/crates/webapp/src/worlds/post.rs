@@ -0,0 +1,105 @@
+use super::model::CreatedWorld;
+use super::problems::unknown_visibility_error;
+use crate::authentication::ApiAccessToken;
+use crate::problem::{missing_error, Problem, ValidationError};
+use crate::request_id::RequestId;
+use rocket::{post, State};
+use rocket_contrib::json::Json;
+use serde::Deserialize;
+use tracing::debug;
+use universe_users::UserID;
+use universe_worlds::*;
+
+#[post("/worlds", data = "<new_world>")]
+#[tracing::instrument(skip(world_service))]
+pub fn create_world(
+  _request_id: RequestId,
+  access_token: ApiAccessToken,
+  new_world: Json<NewWorld>,
+  world_service: State<Box<dyn WorldService>>,
+) -> Result<CreatedWorld, Problem> {
+  debug!("New World: {:?}", new_world);
+
+  let generate_slug = new_world.slug.is_none();
+  let world = new_world
+    .into_inner()
+    .into_world_data(access_token.access_token.user_id)?;
+  debug!("World Data: {:?}", world);
+
+  let result = if generate_slug {
+    world_service.create_world_with_unique_slug(world)?
+  } else {
+    world_service.create_world(world)?
+  };
+  debug!("Created world: {:?}", result);
+
+  Ok(CreatedWorld {
+    world: result.into(),
+  })
+}
+
+/// Struct representing the input data for creating a new world
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct NewWorld<'a> {
+  pub name: Option<&'a str>,
+  pub slug: Option<&'a str>,
+  pub description: Option<String>,
+  pub visibility: Option<&'a str>,
+  pub tags: Option<Vec<String>>,
+}
+
+impl<'a> NewWorld<'a> {
+  /// Convert the incoming details into the data for a world owned by the given user.
+  ///
+  /// If no slug was provided then one is generated from the name of the world
+  ///
+  /// # Arguments
+  /// * `owner` The ID of the user that is to own the world
+  ///
+  /// # Returns
+  /// The world data, or the validation errors with the incoming details
+  fn into_world_data(self, owner: UserID) -> Result<WorldData, Vec<ValidationError>> {
+    let name = self
+      .name
+      .map(|name| name.trim())
+      .filter(|name| !name.is_empty())
+      .ok_or_else(|| missing_error("name"));
+    let slug: Result<Slug, ValidationError> = match self.slug {
+      None => Ok(Slug::from_name(self.name.unwrap_or(""))),
+      Some(slug) => slug.parse().map_err(|e: SlugParseError| e.into()),
+    };
+    let visibility: Result<Visibility, ValidationError> = match self.visibility {
+      None => Ok(Visibility::default()),
+      Some(visibility) => visibility
+        .trim()
+        .parse()
+        .map_err(|_| unknown_visibility_error()),
+    };
+    let tags: Result<Vec<Tag>, ValidationError> = match &self.tags {
+      None => Ok(vec![]),
+      Some(tags) => parse_tags(tags).map_err(|e| e.into()),
+    };
+
+    match (name, slug, visibility, tags) {
+      (Ok(name), Ok(slug), Ok(visibility), Ok(tags)) => Ok(WorldData {
+        owner,
+        name: name.to_owned(),
+        slug,
+        description: self.description.as_deref().unwrap_or("").trim().to_owned(),
+        visibility,
+        forked_from: None,
+        tags,
+        cover: None,
+      }),
+      (name, slug, visibility, tags) => {
+        let errors = vec![name.err(), slug.err(), visibility.err(), tags.err()]
+          .into_iter()
+          .filter_map(|v| v)
+          .collect();
+
+        Err(errors)
+      }
+    }
+  }
+}
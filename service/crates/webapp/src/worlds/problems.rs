@@ -0,0 +1,595 @@
+use crate::calendars::problems::date_error;
+use crate::problem::{
+  missing_error, unexpected_error, validation_error, Problem, ValidationError, ValidationErrors,
+};
+use crate::relationships::problems::{
+  dates_out_of_order_error, during_date_error, unknown_calendar_error,
+};
+use std::collections::HashMap;
+use universe_articles::ArticleImportError;
+use universe_locations::LocationImportError;
+use universe_maps::{FeatureValidationError, MapImageError};
+use universe_world_content::{
+  ArchiveProblem, ArchiveValidationError, ImportWorldError, ARCHIVE_SCHEMA_VERSION,
+  MAX_ARCHIVE_BYTES,
+};
+use universe_worlds::*;
+
+/// Helper to build a Problem response for an unknown world
+pub fn unknown_world_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:worlds/problems/unknown-world".to_owned(),
+    title: "The requested world could not be found".to_owned(),
+    status: 404,
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Validation Error for when the visibility of a world isn't one we know about
+pub fn unknown_visibility_error() -> ValidationError {
+  ValidationError {
+    r#type: "tag:universe,2020:worlds/validation-errors/visibility/unknown".to_owned(),
+    title: "The visibility must be one of public, unlisted or private".to_owned(),
+    field: "visibility".to_owned(),
+  }
+}
+
+/// Helper to build a Problem response for a user that isn't a member of a world
+pub fn unknown_member_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:worlds/problems/unknown-member".to_owned(),
+    title: "The requested member could not be found".to_owned(),
+    status: 404,
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Validation Error for when the role of a member isn't one we know about
+pub fn unknown_role_error() -> ValidationError {
+  ValidationError {
+    r#type: "tag:universe,2020:worlds/validation-errors/role/unknown".to_owned(),
+    title: "The role must be one of owner, editor or viewer".to_owned(),
+    field: "role".to_owned(),
+  }
+}
+
+/// Helper to build a Validation Error for when the user to add as a member doesn't exist
+pub fn unknown_member_user_error() -> ValidationError {
+  ValidationError {
+    r#type: "tag:universe,2020:worlds/validation-errors/user/unknown".to_owned(),
+    title: "The user could not be found".to_owned(),
+    field: "user".to_owned(),
+  }
+}
+
+/// Helper to build a Problem response for a world that isn't being transferred
+pub fn unknown_transfer_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:worlds/problems/unknown-transfer".to_owned(),
+    title: "The world is not being transferred".to_owned(),
+    status: 404,
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Problem response for when the new owner of a world already has a world with
+/// the same slug
+pub fn transfer_slug_conflict_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:worlds/problems/transfer-slug-conflict".to_owned(),
+    title: "The new owner already has a world with the same slug".to_owned(),
+    status: 409,
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Validation Error for when the user to transfer a world to doesn't exist
+pub fn unknown_transfer_user_error() -> ValidationError {
+  ValidationError {
+    r#type: "tag:universe,2020:worlds/validation-errors/to/unknown".to_owned(),
+    title: "The user could not be found".to_owned(),
+    field: "to".to_owned(),
+  }
+}
+
+impl From<&WorldValidationError> for ValidationError {
+  fn from(e: &WorldValidationError) -> Self {
+    match e {
+      WorldValidationError::DuplicateSlug => ValidationError {
+        r#type: "tag:universe,2020:worlds/validation-errors/slug/duplicate".to_owned(),
+        title: "The slug is already used by another world".to_owned(),
+        field: "slug".to_owned(),
+      },
+    }
+  }
+}
+
+impl From<SlugParseError> for ValidationError {
+  fn from(e: SlugParseError) -> Self {
+    match e {
+      SlugParseError::Blank => missing_error("slug"),
+      SlugParseError::InvalidCharacters => ValidationError {
+        r#type: "tag:universe,2020:worlds/validation-errors/slug/invalid-characters".to_owned(),
+        title: "The slug may only contain letters, digits, hyphens and underscores".to_owned(),
+        field: "slug".to_owned(),
+      },
+      SlugParseError::TooLong => ValidationError {
+        r#type: "tag:universe,2020:worlds/validation-errors/slug/too-long".to_owned(),
+        title: format!(
+          "The slug may be at most {} characters long",
+          MAX_SLUG_LENGTH
+        ),
+        field: "slug".to_owned(),
+      },
+    }
+  }
+}
+
+impl From<TagParseError> for ValidationError {
+  fn from(e: TagParseError) -> Self {
+    match e {
+      TagParseError::Blank => ValidationError {
+        r#type: "tag:universe,2020:worlds/validation-errors/tags/blank".to_owned(),
+        title: "Tags may not be blank".to_owned(),
+        field: "tags".to_owned(),
+      },
+      TagParseError::InvalidCharacters => ValidationError {
+        r#type: "tag:universe,2020:worlds/validation-errors/tags/invalid-characters".to_owned(),
+        title: "Tags may not contain commas or control characters".to_owned(),
+        field: "tags".to_owned(),
+      },
+      TagParseError::TooLong => ValidationError {
+        r#type: "tag:universe,2020:worlds/validation-errors/tags/too-long".to_owned(),
+        title: format!("Tags may be at most {} characters long", MAX_TAG_LENGTH),
+        field: "tags".to_owned(),
+      },
+      TagParseError::TooMany => ValidationError {
+        r#type: "tag:universe,2020:worlds/validation-errors/tags/too-many".to_owned(),
+        title: format!("A world may have at most {} tags", MAX_TAGS),
+        field: "tags".to_owned(),
+      },
+    }
+  }
+}
+
+/// Helper to build a Problem response for when the way to match tags in a search isn't one we
+/// know about
+pub fn unknown_tag_match_problem() -> Problem {
+  validation_error(vec![ValidationError {
+    r#type: "tag:universe,2020:worlds/validation-errors/tag_match/unknown".to_owned(),
+    title: "The tag match must be one of any or all".to_owned(),
+    field: "tag_match".to_owned(),
+  }])
+}
+
+/// Helper to build a Problem response for when some facets were requested that we can't count
+pub fn unknown_facets_problem(facets: Vec<String>) -> Problem {
+  let mut extra = HashMap::new();
+  extra.insert(
+    "unknownFacets".to_owned(),
+    serde_json::to_value(facets).unwrap(),
+  );
+
+  Problem {
+    r#type: "tag:universe,2020:worlds/problems/unknown-facets".to_owned(),
+    title: "Unknown Facets requested".to_owned(),
+    status: 422,
+    extra,
+    ..Default::default()
+  }
+}
+
+impl From<CreateWorldError> for Problem {
+  fn from(e: CreateWorldError) -> Self {
+    match e {
+      CreateWorldError::ValidationError(errors) => {
+        validation_error(errors.iter().map(|e| e.into()).collect())
+      }
+      _ => unexpected_error(),
+    }
+  }
+}
+
+/// Helper to build a Problem response for when a world was modified by someone else
+pub fn optimistic_lock_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:worlds/problems/optimistic-lock-failure".to_owned(),
+    title: "The world was modified by another request".to_owned(),
+    status: 409,
+    ..Default::default()
+  }
+}
+
+impl From<UpdateWorldError> for Problem {
+  fn from(e: UpdateWorldError) -> Self {
+    match e {
+      UpdateWorldError::ValidationError(errors) => {
+        validation_error(errors.iter().map(|e| e.into()).collect())
+      }
+      UpdateWorldError::UnknownWorld => unknown_world_problem(),
+      UpdateWorldError::OptimisticLockFailure => optimistic_lock_problem(),
+      UpdateWorldError::UpdateError(e) if e.is::<ValidationErrors>() => e
+        .downcast_ref::<ValidationErrors>()
+        .unwrap()
+        .errors
+        .clone()
+        .into(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+/// Helper to build a Problem response for an unknown revision of a world
+pub fn unknown_revision_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:worlds/problems/unknown-revision".to_owned(),
+    title: "The requested revision could not be found".to_owned(),
+    status: 404,
+    ..Default::default()
+  }
+}
+
+impl From<RestoreWorldRevisionError> for Problem {
+  fn from(e: RestoreWorldRevisionError) -> Self {
+    match e {
+      RestoreWorldRevisionError::ValidationError(errors) => {
+        validation_error(errors.iter().map(|e| e.into()).collect())
+      }
+      RestoreWorldRevisionError::UnknownWorld => unknown_world_problem(),
+      RestoreWorldRevisionError::UnknownRevision => unknown_revision_problem(),
+      RestoreWorldRevisionError::OptimisticLockFailure => optimistic_lock_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<DeleteWorldError> for Problem {
+  fn from(e: DeleteWorldError) -> Self {
+    match e {
+      DeleteWorldError::UnknownWorld => unknown_world_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<RestoreWorldError> for Problem {
+  fn from(e: RestoreWorldError) -> Self {
+    match e {
+      RestoreWorldError::ValidationError(errors) => {
+        validation_error(errors.iter().map(|e| e.into()).collect())
+      }
+      RestoreWorldError::UnknownWorld => unknown_world_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<AddWorldMemberError> for Problem {
+  fn from(e: AddWorldMemberError) -> Self {
+    match e {
+      AddWorldMemberError::UnknownWorld => unknown_world_problem(),
+      AddWorldMemberError::UnknownUser => validation_error(vec![unknown_member_user_error()]),
+      AddWorldMemberError::DuplicateMember => validation_error(vec![ValidationError {
+        r#type: "tag:universe,2020:worlds/validation-errors/user/duplicate-member".to_owned(),
+        title: "The user is already a member of the world".to_owned(),
+        field: "user".to_owned(),
+      }]),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<UpdateWorldMemberError> for Problem {
+  fn from(e: UpdateWorldMemberError) -> Self {
+    match e {
+      UpdateWorldMemberError::UnknownMember => unknown_member_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<RemoveWorldMemberError> for Problem {
+  fn from(e: RemoveWorldMemberError) -> Self {
+    match e {
+      RemoveWorldMemberError::UnknownMember => unknown_member_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<OfferWorldTransferError> for Problem {
+  fn from(e: OfferWorldTransferError) -> Self {
+    match e {
+      OfferWorldTransferError::UnknownWorld => unknown_world_problem(),
+      OfferWorldTransferError::UnknownUser => validation_error(vec![unknown_transfer_user_error()]),
+      OfferWorldTransferError::SameOwner => validation_error(vec![ValidationError {
+        r#type: "tag:universe,2020:worlds/validation-errors/to/current-owner".to_owned(),
+        title: "The user already owns the world".to_owned(),
+        field: "to".to_owned(),
+      }]),
+      OfferWorldTransferError::SlugConflict => transfer_slug_conflict_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<AcceptWorldTransferError> for Problem {
+  fn from(e: AcceptWorldTransferError) -> Self {
+    match e {
+      AcceptWorldTransferError::UnknownTransfer => unknown_transfer_problem(),
+      AcceptWorldTransferError::SlugConflict => transfer_slug_conflict_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<CancelWorldTransferError> for Problem {
+  fn from(e: CancelWorldTransferError) -> Self {
+    match e {
+      CancelWorldTransferError::UnknownTransfer => unknown_transfer_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<ForkWorldError> for Problem {
+  fn from(e: ForkWorldError) -> Self {
+    match e {
+      ForkWorldError::UnknownWorld => unknown_world_problem(),
+      ForkWorldError::ValidationError(errors) => {
+        validation_error(errors.iter().map(|e| e.into()).collect())
+      }
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<StarWorldError> for Problem {
+  fn from(e: StarWorldError) -> Self {
+    match e {
+      StarWorldError::UnknownWorld => unknown_world_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<UnstarWorldError> for Problem {
+  fn from(_: UnstarWorldError) -> Self {
+    unexpected_error()
+  }
+}
+
+/// Helper to build a Problem response for a world that doesn't have the requested cover image
+pub fn unknown_cover_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:worlds/problems/unknown-cover".to_owned(),
+    title: "The requested cover image could not be found".to_owned(),
+    status: 404,
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Problem response for a cover image that was larger than we accept
+pub fn cover_too_large_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:worlds/problems/cover-too-large".to_owned(),
+    title: format!("The cover image may be at most {} bytes", MAX_COVER_BYTES),
+    status: 413,
+    ..Default::default()
+  }
+}
+
+impl From<CoverImageError> for Problem {
+  fn from(e: CoverImageError) -> Self {
+    match e {
+      CoverImageError::TooLarge => cover_too_large_problem(),
+      CoverImageError::UnsupportedFormat => Problem {
+        r#type: "tag:universe,2020:worlds/problems/cover-unsupported-format".to_owned(),
+        title: "The cover image must be a PNG, JPEG or WebP image".to_owned(),
+        status: 415,
+        ..Default::default()
+      },
+      CoverImageError::DimensionsTooLarge => Problem {
+        r#type: "tag:universe,2020:worlds/problems/cover-dimensions-too-large".to_owned(),
+        title: format!(
+          "The cover image may be at most {} pixels wide and high",
+          MAX_COVER_DIMENSION
+        ),
+        status: 422,
+        ..Default::default()
+      },
+      CoverImageError::Malformed => Problem {
+        r#type: "tag:universe,2020:worlds/problems/cover-malformed".to_owned(),
+        title: "The cover image could not be read".to_owned(),
+        status: 422,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+impl From<SetWorldCoverError> for Problem {
+  fn from(e: SetWorldCoverError) -> Self {
+    match e {
+      SetWorldCoverError::UnknownWorld => unknown_world_problem(),
+      SetWorldCoverError::InvalidImage(e) => e.into(),
+      SetWorldCoverError::OptimisticLockFailure => optimistic_lock_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+impl From<RemoveWorldCoverError> for Problem {
+  fn from(e: RemoveWorldCoverError) -> Self {
+    match e {
+      RemoveWorldCoverError::UnknownWorld => unknown_world_problem(),
+      RemoveWorldCoverError::UnknownCover => unknown_cover_problem(),
+      RemoveWorldCoverError::OptimisticLockFailure => optimistic_lock_problem(),
+      _ => unexpected_error(),
+    }
+  }
+}
+
+/// Helper to build a Problem response for a world archive that was larger than we accept
+pub fn archive_too_large_problem() -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:worlds/problems/archive-too-large".to_owned(),
+    title: format!(
+      "The world archive may be at most {} bytes",
+      MAX_ARCHIVE_BYTES
+    ),
+    status: 413,
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Problem response for a world archive that couldn't be read
+pub fn invalid_archive_problem(detail: String) -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:worlds/problems/invalid-archive".to_owned(),
+    title: "The world archive could not be read".to_owned(),
+    status: 400,
+    detail: Some(detail),
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Problem response for a world archive written in a version of the format
+/// that we don't know
+pub fn unsupported_archive_version_problem(schema_version: u32) -> Problem {
+  Problem {
+    r#type: "tag:universe,2020:worlds/problems/unsupported-archive-version".to_owned(),
+    title: format!(
+      "World archives must have a schema version between 1 and {}",
+      ARCHIVE_SCHEMA_VERSION
+    ),
+    status: 422,
+    detail: Some(format!("The schema version was {}", schema_version)),
+    ..Default::default()
+  }
+}
+
+/// Helper to build a Validation Error for a cover image in an archive that isn't valid Base64
+pub fn invalid_cover_data_error() -> ValidationError {
+  ValidationError {
+    r#type: "tag:universe,2020:worlds/validation-errors/cover/invalid-data".to_owned(),
+    title: "The cover image data must be Base64 encoded".to_owned(),
+    field: "cover.data".to_owned(),
+  }
+}
+
+/// Helper to build a Validation Error for a map image in an archive that isn't valid Base64
+pub fn invalid_map_image_data_error() -> ValidationError {
+  ValidationError {
+    r#type: "tag:universe,2020:worlds/validation-errors/maps/invalid-image-data".to_owned(),
+    title: "The map image data must be Base64 encoded".to_owned(),
+    field: "image.data".to_owned(),
+  }
+}
+
+/// Helper to build a Validation Error for a map image in an archive that isn't acceptable
+pub fn invalid_map_image_error(e: &MapImageError) -> ValidationError {
+  ValidationError {
+    r#type: "tag:universe,2020:worlds/validation-errors/maps/invalid-image".to_owned(),
+    title: e.to_string(),
+    field: "image".to_owned(),
+  }
+}
+
+impl From<&ArticleImportError> for ValidationError {
+  fn from(e: &ArticleImportError) -> Self {
+    let (kind, title) = match e {
+      ArticleImportError::DuplicateKey(_) => ("duplicate-id", "Article IDs must be unique"),
+      ArticleImportError::UnknownParent(_) => (
+        "unknown-parent",
+        "The parent of an article must be another article in the archive",
+      ),
+      ArticleImportError::ParentCycle(_) => {
+        ("parent-cycle", "An article may not be its own ancestor")
+      }
+    };
+
+    ValidationError {
+      r#type: format!(
+        "tag:universe,2020:worlds/validation-errors/articles/{}",
+        kind
+      ),
+      title: title.to_owned(),
+      field: "articles".to_owned(),
+    }
+  }
+}
+
+impl From<&LocationImportError> for ValidationError {
+  fn from(e: &LocationImportError) -> Self {
+    let (kind, title) = match e {
+      LocationImportError::DuplicateKey(_) => ("duplicate-id", "Location IDs must be unique"),
+      LocationImportError::UnknownParent(_) => (
+        "unknown-parent",
+        "The parent of a location must be another location in the archive",
+      ),
+      LocationImportError::ParentCycle(_) => {
+        ("parent-cycle", "A location may not be its own ancestor")
+      }
+    };
+
+    ValidationError {
+      r#type: format!(
+        "tag:universe,2020:worlds/validation-errors/locations/{}",
+        kind
+      ),
+      title: title.to_owned(),
+      field: "locations".to_owned(),
+    }
+  }
+}
+
+impl From<&ArchiveValidationError> for ValidationError {
+  fn from(e: &ArchiveValidationError) -> Self {
+    let error = match &e.problem {
+      ArchiveProblem::Missing(field) => missing_error(field),
+      ArchiveProblem::Slug(e) => e.clone().into(),
+      ArchiveProblem::UnknownVisibility => unknown_visibility_error(),
+      ArchiveProblem::Tags(e) => e.clone().into(),
+      ArchiveProblem::InvalidCoverData => invalid_cover_data_error(),
+      ArchiveProblem::Character(e) => e.clone().into(),
+      ArchiveProblem::InvalidMapImageData => invalid_map_image_data_error(),
+      ArchiveProblem::InvalidMapImage(e) => invalid_map_image_error(e),
+      // Features in an archive hold their link directly, rather than inside of their properties
+      ArchiveProblem::Feature(FeatureValidationError::UnknownLink) => ValidationError {
+        field: "link".to_owned(),
+        ..ValidationError::from(&FeatureValidationError::UnknownLink)
+      },
+      ArchiveProblem::Feature(e) => e.into(),
+      ArchiveProblem::Definition(e) => e.into(),
+      ArchiveProblem::Date(e) => date_error("date", e),
+      ArchiveProblem::UnknownCalendar => unknown_calendar_error(),
+      ArchiveProblem::DuringDate(field, e) => during_date_error(field, e),
+      ArchiveProblem::DatesOutOfOrder => dates_out_of_order_error(),
+      ArchiveProblem::Relationship(e) => e.into(),
+      ArchiveProblem::Articles(e) => e.into(),
+      ArchiveProblem::Locations(e) => e.into(),
+    };
+
+    ValidationError {
+      field: format!("{}{}", e.location, error.field),
+      ..error
+    }
+  }
+}
+
+impl From<ImportWorldError> for Problem {
+  fn from(e: ImportWorldError) -> Self {
+    match e {
+      ImportWorldError::UnsupportedVersion(schema_version) => {
+        unsupported_archive_version_problem(schema_version)
+      }
+      ImportWorldError::ValidationError(errors) => {
+        validation_error(errors.iter().map(|e| e.into()).collect())
+      }
+      ImportWorldError::InvalidCover(e) => e.into(),
+      ImportWorldError::CreateWorldError(e) => e.into(),
+      _ => unexpected_error(),
+    }
+  }
+}
@@ -0,0 +1,127 @@
+use super::access::find_authorized_world;
+use super::model::{Revision, RevisionDiff, World};
+use super::problems::unknown_revision_problem;
+use crate::{
+  authentication::Authorizer, page::SearchPage, problem::Problem, request_id::RequestId,
+};
+use rocket::{get, post, State};
+use tracing::warn;
+use universe_entity::Pagination;
+use universe_worlds::{WorldEntity, WorldRevision, WorldRole, WorldService};
+
+#[get("/worlds/<world_id>/revisions?<offset>&<limit>")]
+#[tracing::instrument(skip(world_service, authorizer))]
+pub fn list_world_revisions(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  offset: Option<u32>,
+  limit: Option<u32>,
+  world_service: State<Box<dyn WorldService>>,
+) -> Result<SearchPage<Revision>, Problem> {
+  let world = find_authorized_world(&**world_service, &authorizer, world_id)?;
+  authorizer
+    .world_role(&**world_service, &world, WorldRole::Viewer)
+    .to_result()?;
+
+  let pagination = Pagination {
+    offset: offset.unwrap_or(0),
+    limit: limit.unwrap_or(10),
+  };
+
+  let revisions = world_service.list_world_revisions(&world.identity.id, pagination);
+  Ok(revisions.into())
+}
+
+#[get("/worlds/<world_id>/revisions/<revision>")]
+#[tracing::instrument(skip(world_service, authorizer))]
+pub fn get_world_revision(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  revision: String,
+  world_service: State<Box<dyn WorldService>>,
+) -> Result<Revision, Problem> {
+  let world = find_authorized_world(&**world_service, &authorizer, world_id)?;
+  authorizer
+    .world_role(&**world_service, &world, WorldRole::Viewer)
+    .to_result()?;
+
+  let revision = find_revision(&**world_service, &world, revision)?;
+  Ok(revision.into())
+}
+
+#[get("/worlds/<world_id>/revisions/<from>/diff/<to>")]
+#[tracing::instrument(skip(world_service, authorizer))]
+pub fn diff_world_revisions(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  from: String,
+  to: String,
+  world_service: State<Box<dyn WorldService>>,
+) -> Result<RevisionDiff, Problem> {
+  let world = find_authorized_world(&**world_service, &authorizer, world_id)?;
+  authorizer
+    .world_role(&**world_service, &world, WorldRole::Viewer)
+    .to_result()?;
+
+  let from = find_revision(&**world_service, &world, from)?;
+  let to = find_revision(&**world_service, &world, to)?;
+
+  Ok(RevisionDiff {
+    from: from.revision,
+    to: to.revision,
+    changes: from.data.diff(&to.data),
+  })
+}
+
+#[post("/worlds/<world_id>/revisions/<revision>/restore")]
+#[tracing::instrument(skip(world_service, authorizer))]
+pub fn restore_world_revision(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  revision: String,
+  world_service: State<Box<dyn WorldService>>,
+) -> Result<World, Problem> {
+  let world = find_authorized_world(&**world_service, &authorizer, world_id)?;
+  authorizer
+    .world_role(&**world_service, &world, WorldRole::Editor)
+    .to_result()?;
+
+  let revision = find_revision(&**world_service, &world, revision)?;
+  // Only owners are allowed to change who is able to see the world
+  if revision.data.visibility != world.data.visibility {
+    authorizer
+      .world_role(&**world_service, &world, WorldRole::Owner)
+      .to_result()?;
+  }
+
+  let world = world_service.restore_world_revision(&world.identity.id, revision.revision)?;
+  Ok(world.into())
+}
+
+/// Load a single revision of a world
+///
+/// # Arguments
+/// * `world_service` The world service to load the revision with
+/// * `world` The world that the revision belongs to
+/// * `revision` The number of the revision, as provided in the URL
+///
+/// # Returns
+/// The revision, or a Problem if it couldn't be found
+fn find_revision(
+  world_service: &dyn WorldService,
+  world: &WorldEntity,
+  revision: String,
+) -> Result<WorldRevision, Problem> {
+  let revision: u32 = revision.parse().map_err(|e| {
+    warn!("Invalid Revision: {}", e);
+    unknown_revision_problem()
+  })?;
+
+  world_service
+    .get_world_revision(&world.identity.id, revision)
+    .ok_or_else(unknown_revision_problem)
+}
@@ -1,6 +1,51 @@
+use super::cover::*;
+use super::delete::*;
+use super::export::*;
+use super::fork::*;
+use super::get::*;
+use super::import::*;
+use super::members::*;
+use super::patch::*;
+use super::post::*;
+use super::revisions::*;
 use super::search::*;
+use super::stars::*;
+use super::transfer::*;
+use super::trash::*;
 use rocket::{routes, Route};
 
 pub fn routes() -> Vec<Route> {
-  routes![search_worlds]
+  routes![
+    search_worlds,
+    get_world_by_id,
+    get_world_by_slug,
+    create_world,
+    update_world,
+    delete_world,
+    fork_world,
+    export_world,
+    import_world,
+    list_world_revisions,
+    get_world_revision,
+    diff_world_revisions,
+    restore_world_revision,
+    star_world,
+    unstar_world,
+    list_starred_worlds,
+    put_world_cover,
+    delete_world_cover,
+    get_world_cover,
+    list_trashed_worlds,
+    restore_world,
+    list_world_members,
+    add_world_member,
+    update_world_member,
+    remove_world_member,
+    get_world_transfer,
+    offer_world_transfer,
+    accept_world_transfer,
+    decline_world_transfer,
+    cancel_world_transfer,
+    list_incoming_world_transfers
+  ]
 }
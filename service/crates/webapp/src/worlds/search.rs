@@ -1,23 +1,37 @@
-use super::model::World;
-use crate::{page::SearchPage, problem::Problem, request_id::RequestId};
+use super::model::WorldSearchPage;
+use super::problems::{unknown_facets_problem, unknown_tag_match_problem};
+use crate::{
+  authentication::ApiAccessToken,
+  page::SearchPage,
+  problem::{validation_error, Problem},
+  request_id::RequestId,
+};
 use rocket::{get, State};
+use std::collections::BTreeMap;
 use std::str::FromStr;
 use tracing::debug;
 use universe_entity::{parse_sorts, Pagination, SortField};
 use universe_users::UserID;
-use universe_worlds::{WorldFilters, WorldService, WorldSorts};
+use universe_worlds::{
+  parse_tags, Tag, TagFilter, WorldFacet, WorldFilters, WorldService, WorldSorts,
+};
 
-#[get("/worlds?<owner>&<keyword>&<offset>&<limit>&<sort>")]
+#[allow(clippy::too_many_arguments)]
+#[get("/worlds?<owner>&<keyword>&<tags>&<tag_match>&<facets>&<offset>&<limit>&<sort>")]
 #[tracing::instrument(skip(world_service))]
 pub fn search_worlds(
   _request_id: RequestId,
+  access_token: Option<ApiAccessToken>,
   world_service: State<Box<dyn WorldService>>,
   owner: Option<String>,
   keyword: Option<String>,
+  tags: Option<String>,
+  tag_match: Option<String>,
+  facets: Option<String>,
   offset: Option<u32>,
   limit: Option<u32>,
   sort: Option<String>,
-) -> Result<SearchPage<World>, Problem> {
+) -> Result<WorldSearchPage, Problem> {
   debug!("Searching worlds");
 
   let pagination = Pagination {
@@ -33,6 +47,9 @@ pub fn search_worlds(
     parse_sorts(sort_param).map_err(|e| crate::problem::invalid_sort_fields(e))?;
   debug!("Parsed sorts: {:?}", sorts);
 
+  let tags = parse_tag_filter(tags, tag_match)?;
+  let facets = parse_facets(facets)?;
+
   let filters = WorldFilters {
     keyword,
     owner: match owner {
@@ -42,10 +59,96 @@ pub fn search_worlds(
       // TODO: Fix this so that an invalid User ID actually means we don't even bother doing the search
       Some(owner) => Some(UserID::from_str(&owner).unwrap_or_default()),
     },
+    visible_to: access_token
+      .as_ref()
+      .map(|access_token| access_token.access_token.user_id),
+    tags,
+    starred_by: None,
   };
   debug!("Parsed filters: {:?}", filters);
 
+  let facets = facets
+    .into_iter()
+    .map(|facet| {
+      let counts = world_service.count_world_facet(filters.clone(), facet);
+      (facet.to_string(), counts)
+    })
+    .collect::<BTreeMap<_, _>>();
+  debug!("Facet counts: {:?}", facets);
+
   let results = world_service.search_worlds(filters, sorts, pagination);
   debug!("Matching worlds: {:?}", results);
-  Ok(results.into())
+  Ok(WorldSearchPage {
+    page: SearchPage::from(results),
+    facets,
+    authenticated: access_token.is_some(),
+  })
+}
+
+/// Parse the comma-separated list of tags to filter by, and how they should be matched
+///
+/// # Arguments
+/// * `tags` The comma-separated list of tags, if any
+/// * `tag_match` Whether worlds must match "any" or "all" of the tags. Defaults to "any"
+///
+/// # Returns
+/// The tag filter to apply, if any tags were provided
+fn parse_tag_filter(
+  tags: Option<String>,
+  tag_match: Option<String>,
+) -> Result<Option<TagFilter>, Problem> {
+  let tags: Vec<&str> = tags
+    .as_deref()
+    .unwrap_or("")
+    .split(',')
+    .filter(|tag| !tag.trim().is_empty())
+    .collect();
+  let tags = parse_tags(&tags).map_err(|e| validation_error(vec![e.into()]))?;
+
+  let tag_match = tag_match
+    .map(|tag_match| tag_match.trim().to_lowercase())
+    .filter(|tag_match| !tag_match.is_empty());
+  let filter: fn(Vec<Tag>) -> TagFilter = match tag_match.as_deref() {
+    None | Some("any") => TagFilter::Any,
+    Some("all") => TagFilter::All,
+    Some(_) => return Err(unknown_tag_match_problem()),
+  };
+
+  Ok(if tags.is_empty() {
+    None
+  } else {
+    Some(filter(tags))
+  })
+}
+
+/// Parse the comma-separated list of facets to count
+///
+/// # Arguments
+/// * `facets` The comma-separated list of facets, if any
+///
+/// # Returns
+/// The facets to count
+fn parse_facets(facets: Option<String>) -> Result<Vec<WorldFacet>, Problem> {
+  let mut parsed = vec![];
+  let mut unknown = vec![];
+
+  for facet in facets
+    .as_deref()
+    .unwrap_or("")
+    .split(',')
+    .map(|facet| facet.trim())
+    .filter(|facet| !facet.is_empty())
+  {
+    match facet.parse() {
+      Ok(facet) if !parsed.contains(&facet) => parsed.push(facet),
+      Ok(_) => {}
+      Err(_) => unknown.push(facet.to_owned()),
+    }
+  }
+
+  if unknown.is_empty() {
+    Ok(parsed)
+  } else {
+    Err(unknown_facets_problem(unknown))
+  }
 }
@@ -0,0 +1,93 @@
+use super::access::find_visible_world;
+use super::model::World;
+use crate::users::problems::unknown_user_problem;
+use crate::{
+  authentication::{ApiAccessToken, Authorizer},
+  page::SearchPage,
+  problem::Problem,
+  request_id::RequestId,
+};
+use rocket::{delete, get, http::Status, put, State};
+use tracing::{debug, warn};
+use universe_entity::{parse_sorts, Pagination, SortField};
+use universe_users::UserID;
+use universe_worlds::{WorldFilters, WorldService, WorldSorts};
+
+#[put("/worlds/<world_id>/star")]
+#[tracing::instrument(skip(world_service))]
+pub fn star_world(
+  _request_id: RequestId,
+  access_token: ApiAccessToken,
+  world_id: String,
+  world_service: State<Box<dyn WorldService>>,
+) -> Result<Status, Problem> {
+  let user_id = access_token.access_token.user_id.clone();
+
+  // Anyone that can see a world is allowed to star it
+  let world = find_visible_world(&**world_service, Some(access_token), world_id)?;
+
+  world_service.star_world(&world.identity.id, &user_id)?;
+
+  Ok(Status::NoContent)
+}
+
+#[delete("/worlds/<world_id>/star")]
+#[tracing::instrument(skip(world_service))]
+pub fn unstar_world(
+  _request_id: RequestId,
+  access_token: ApiAccessToken,
+  world_id: String,
+  world_service: State<Box<dyn WorldService>>,
+) -> Result<Status, Problem> {
+  let user_id = access_token.access_token.user_id.clone();
+
+  let world = find_visible_world(&**world_service, Some(access_token), world_id)?;
+
+  world_service.unstar_world(&world.identity.id, &user_id)?;
+
+  Ok(Status::NoContent)
+}
+
+#[get("/users/<user_id>/stars?<offset>&<limit>&<sort>")]
+#[tracing::instrument(skip(world_service, authorizer))]
+pub fn list_starred_worlds(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  user_id: String,
+  offset: Option<u32>,
+  limit: Option<u32>,
+  sort: Option<String>,
+  world_service: State<Box<dyn WorldService>>,
+) -> Result<SearchPage<World>, Problem> {
+  let user_id: UserID = user_id.parse().map_err(|e| {
+    warn!("Invalid User ID: {}", e);
+    unknown_user_problem()
+  })?;
+  authorizer.same_user(&user_id).to_result()?;
+
+  let pagination = Pagination {
+    offset: offset.unwrap_or(0),
+    limit: limit.unwrap_or(10),
+  };
+
+  let sort_param = sort
+    .map(|a| a.trim().to_owned())
+    .filter(|b| !b.is_empty())
+    .unwrap_or_else(|| "name".to_owned());
+  let sorts: Vec<SortField<WorldSorts>> =
+    parse_sorts(sort_param).map_err(crate::problem::invalid_sort_fields)?;
+  debug!("Parsed sorts: {:?}", sorts);
+
+  // Worlds that have been starred but are no longer visible to the user are left out
+  let filters = WorldFilters {
+    keyword: None,
+    owner: None,
+    visible_to: Some(user_id.clone()),
+    tags: None,
+    starred_by: Some(user_id),
+  };
+
+  let results = world_service.search_worlds(filters, sorts, pagination);
+  debug!("Starred worlds: {:?}", results);
+  Ok(results.into())
+}
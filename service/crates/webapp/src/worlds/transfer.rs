@@ -0,0 +1,179 @@
+use super::model::{Transfer, World};
+use super::problems::{
+  unknown_transfer_problem, unknown_transfer_user_error, unknown_world_problem,
+};
+use crate::users::problems::unknown_user_problem;
+use crate::{
+  authentication::Authorizer,
+  page::SearchPage,
+  problem::{missing_error, validation_error, Problem},
+  request_id::RequestId,
+};
+use rocket::{delete, get, http::Status, post, State};
+use rocket_contrib::json::Json;
+use serde::Deserialize;
+use tracing::{debug, warn};
+use universe_users::UserID;
+use universe_worlds::{WorldEntity, WorldID, WorldService, WorldTransfer};
+
+#[get("/worlds/<world_id>/transfer")]
+#[tracing::instrument(skip(world_service, authorizer))]
+pub fn get_world_transfer(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  world_service: State<Box<dyn WorldService>>,
+) -> Result<Transfer, Problem> {
+  let world = find_world(&**world_service, &authorizer, world_id)?;
+  let transfer = find_transfer(&**world_service, &world)?;
+  authorizer
+    .same_user(&transfer.from)
+    .or(authorizer.same_user(&transfer.to))
+    .to_result()?;
+
+  Ok(transfer.into())
+}
+
+#[post("/worlds/<world_id>/transfer", data = "<new_transfer>")]
+#[tracing::instrument(skip(world_service, authorizer))]
+pub fn offer_world_transfer(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  new_transfer: Json<NewTransfer>,
+  world_service: State<Box<dyn WorldService>>,
+) -> Result<Transfer, Problem> {
+  debug!("New Transfer: {:?}", new_transfer);
+
+  let world = find_world(&**world_service, &authorizer, world_id)?;
+  // Only the actual owner can give the world away, not members with the Owner role
+  authorizer.same_user(&world.data.owner).to_result()?;
+
+  let to: UserID = match new_transfer.to {
+    None => Err(missing_error("to")),
+    Some(to) => to.trim().parse().map_err(|e| {
+      warn!("Invalid User ID: {}", e);
+      unknown_transfer_user_error()
+    }),
+  }
+  .map_err(|e| validation_error(vec![e]))?;
+
+  let transfer = world_service.offer_world_transfer(&world.identity.id, &to)?;
+  Ok(transfer.into())
+}
+
+#[post("/worlds/<world_id>/transfer/accept")]
+#[tracing::instrument(skip(world_service, authorizer))]
+pub fn accept_world_transfer(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  world_service: State<Box<dyn WorldService>>,
+) -> Result<World, Problem> {
+  let world = find_world(&**world_service, &authorizer, world_id)?;
+  let transfer = find_transfer(&**world_service, &world)?;
+  authorizer.same_user(&transfer.to).to_result()?;
+
+  let world = world_service.accept_world_transfer(&world.identity.id, &transfer.to)?;
+  Ok(world.into())
+}
+
+#[post("/worlds/<world_id>/transfer/decline")]
+#[tracing::instrument(skip(world_service, authorizer))]
+pub fn decline_world_transfer(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  world_service: State<Box<dyn WorldService>>,
+) -> Result<Status, Problem> {
+  let world = find_world(&**world_service, &authorizer, world_id)?;
+  let transfer = find_transfer(&**world_service, &world)?;
+  authorizer.same_user(&transfer.to).to_result()?;
+
+  world_service.cancel_world_transfer(&world.identity.id)?;
+  Ok(Status::NoContent)
+}
+
+#[delete("/worlds/<world_id>/transfer")]
+#[tracing::instrument(skip(world_service, authorizer))]
+pub fn cancel_world_transfer(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  world_id: String,
+  world_service: State<Box<dyn WorldService>>,
+) -> Result<Status, Problem> {
+  let world = find_world(&**world_service, &authorizer, world_id)?;
+  authorizer.same_user(&world.data.owner).to_result()?;
+
+  world_service.cancel_world_transfer(&world.identity.id)?;
+  Ok(Status::NoContent)
+}
+
+#[get("/users/<user_id>/transfers")]
+#[tracing::instrument(skip(world_service, authorizer))]
+pub fn list_incoming_world_transfers(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  user_id: String,
+  world_service: State<Box<dyn WorldService>>,
+) -> Result<SearchPage<Transfer>, Problem> {
+  let user_id: UserID = user_id.parse().map_err(|e| {
+    warn!("Invalid User ID: {}", e);
+    unknown_user_problem()
+  })?;
+  authorizer.same_user(&user_id).to_result()?;
+
+  let transfers = world_service.list_world_transfers_to(&user_id);
+  Ok(transfers.into())
+}
+
+/// Struct representing the input data for offering a world to another user
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct NewTransfer<'a> {
+  pub to: Option<&'a str>,
+}
+
+/// Load the world that a transfer is being managed for.
+///
+/// The recipient of a transfer is always able to see the world that is being offered to them,
+/// even if it is private, but anybody else that can't see the world is told that it doesn't exist
+///
+/// # Arguments
+/// * `world_service` The world service to load the world with
+/// * `authorizer` The authorizer for the caller
+/// * `world_id` The ID of the world, as provided in the URL
+///
+/// # Returns
+/// The world, or a Problem if it couldn't be found
+fn find_world(
+  world_service: &dyn WorldService,
+  authorizer: &Authorizer,
+  world_id: String,
+) -> Result<WorldEntity, Problem> {
+  let world_id: WorldID = world_id.parse().map_err(|e| {
+    warn!("Invalid World ID: {}", e);
+    unknown_world_problem()
+  })?;
+
+  world_service
+    .get_world_by_id(&world_id)
+    .filter(|world| {
+      world_service.is_world_visible_to(world, Some(authorizer.user_id()))
+        || world_service
+          .get_world_transfer(&world.identity.id)
+          .map(|transfer| &transfer.to == authorizer.user_id())
+          .unwrap_or(false)
+    })
+    .ok_or_else(unknown_world_problem)
+}
+
+/// Load the pending transfer of the given world
+fn find_transfer(
+  world_service: &dyn WorldService,
+  world: &WorldEntity,
+) -> Result<WorldTransfer, Problem> {
+  world_service
+    .get_world_transfer(&world.identity.id)
+    .ok_or_else(unknown_transfer_problem)
+}
@@ -0,0 +1,60 @@
+use super::model::{TrashedWorld, World};
+use super::problems::unknown_world_problem;
+use crate::users::problems::unknown_user_problem;
+use crate::{
+  authentication::Authorizer, page::SearchPage, problem::Problem, request_id::RequestId,
+};
+use rocket::{get, post, State};
+use tracing::warn;
+use universe_entity::Pagination;
+use universe_users::UserID;
+use universe_worlds::{WorldID, WorldService};
+
+#[get("/users/<user_id>/trash?<offset>&<limit>")]
+#[tracing::instrument(skip(world_service, authorizer))]
+pub fn list_trashed_worlds(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  user_id: String,
+  offset: Option<u32>,
+  limit: Option<u32>,
+  world_service: State<Box<dyn WorldService>>,
+) -> Result<SearchPage<TrashedWorld>, Problem> {
+  let user_id: UserID = user_id.parse().map_err(|e| {
+    warn!("Invalid User ID: {}", e);
+    unknown_user_problem()
+  })?;
+  authorizer.same_user(&user_id).to_result()?;
+
+  let pagination = Pagination {
+    offset: offset.unwrap_or(0),
+    limit: limit.unwrap_or(10),
+  };
+
+  let trash = world_service.list_trashed_worlds(&user_id, pagination);
+  Ok(trash.into())
+}
+
+#[post("/users/<user_id>/trash/<world_id>/restore")]
+#[tracing::instrument(skip(world_service, authorizer))]
+pub fn restore_world(
+  _request_id: RequestId,
+  authorizer: Authorizer,
+  user_id: String,
+  world_id: String,
+  world_service: State<Box<dyn WorldService>>,
+) -> Result<World, Problem> {
+  let user_id: UserID = user_id.parse().map_err(|e| {
+    warn!("Invalid User ID: {}", e);
+    unknown_user_problem()
+  })?;
+  authorizer.same_user(&user_id).to_result()?;
+
+  let world_id: WorldID = world_id.parse().map_err(|e| {
+    warn!("Invalid World ID: {}", e);
+    unknown_world_problem()
+  })?;
+
+  let world = world_service.restore_world(&user_id, &world_id)?;
+  Ok(world.into())
+}
@@ -0,0 +1,432 @@
+//! The format of the archives that worlds are exported to and imported from.
+//!
+//! Archives are JSON documents that hold a world along with all of its content. Every archive
+//! records the version of the format that it was written in as `schemaVersion`. Whenever the
+//! format changes in a way that older readers can't cope with, `ARCHIVE_SCHEMA_VERSION` is bumped
+//! and `WorldArchive::upgrade` learns how to bring archives of the older version up to date, so
+//! that anything that was ever exported can still be imported.
+//!
+//! ## Version 1
+//! * `world` - The name, slug, description, visibility and tags of the world, and optionally the
+//!   original cover image as Base64 encoded data.
+//! * `articles` - Every article of the world. Articles refer to their parent by the `id` that
+//!   they had in the world that they were exported from.
+//! * `characters` - Every character of the world, with their aliases and custom attributes, and
+//!   the `id` that it had in the world that it was exported from so that other content can refer
+//!   to it.
+//! * `locations` - Every location of the world, with their map coordinates if they have them.
+//!   Locations refer to their parent by the `id` that they had in the world that they were
+//!   exported from, in the same way as articles.
+//! * `maps` - Every map of the world, with its image as Base64 encoded data if it has one, and
+//!   the features placed on it. Features link to locations, characters and articles by the `id`
+//!   that those had in the world that they were exported from.
+//! * `calendars` - Every calendar of the world, with its definition in the same form that the API
+//!   uses, and the events dated in it. Dates are written as the year, month and day numbers, so
+//!   that they don't depend on how the calendar formats them.
+//! * `relationships` - Every relationship between the articles, characters, locations and maps of
+//!   the world. Both ends of a relationship refer to the entity by its type and the `id` that it
+//!   had in the world that it was exported from, in the same way as the links of map features. The
+//!   dates that a relationship held between refer to their calendar by the `id` that it had.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use universe_articles::ArticleEntity;
+use universe_calendars::{CalendarDate, CalendarDefinition, CalendarEntity, EventEntity};
+use universe_characters::CharacterEntity;
+use universe_locations::{Coordinates, LocationEntity};
+use universe_maps::{FeatureEntity, Geometry, MapEntity};
+use universe_relationships::{EntityRef, RelationshipEntity};
+use universe_storage::Blob;
+use universe_worlds::WorldEntity;
+
+/// The version of the archive format that is currently written
+pub const ARCHIVE_SCHEMA_VERSION: u32 = 1;
+
+/// The largest archive that we will accept for import, in bytes. This is enough for the largest
+/// cover image that we allow once it has been Base64 encoded, along with plenty of articles.
+pub const MAX_ARCHIVE_BYTES: usize = 32 * 1024 * 1024;
+
+/// An archive of a world and all of its content
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorldArchive {
+  pub schema_version: u32,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub exported: Option<DateTime<Utc>>,
+  pub world: ArchivedWorld,
+  #[serde(default)]
+  pub articles: Vec<ArchivedArticle>,
+  #[serde(default)]
+  pub characters: Vec<ArchivedCharacter>,
+  #[serde(default)]
+  pub locations: Vec<ArchivedLocation>,
+  #[serde(default)]
+  pub maps: Vec<ArchivedMap>,
+  #[serde(default)]
+  pub calendars: Vec<ArchivedCalendar>,
+  #[serde(default)]
+  pub relationships: Vec<ArchivedRelationship>,
+}
+
+/// The details of the world itself within an archive
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedWorld {
+  /// The ID that the world had when it was exported. This is only informative, since imported
+  /// worlds are always given a new ID
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub id: Option<String>,
+  pub name: String,
+  pub slug: String,
+  #[serde(default)]
+  pub description: String,
+  #[serde(default)]
+  pub visibility: Option<String>,
+  #[serde(default)]
+  pub tags: Vec<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub cover: Option<ArchivedImage>,
+}
+
+/// An image within an archive, such as the cover of a world or the image of a map
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedImage {
+  pub content_type: String,
+  /// The image data, Base64 encoded
+  pub data: String,
+}
+
+/// An article of a world within an archive
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedArticle {
+  /// The ID that the article had when it was exported, used by other articles to refer to it
+  pub id: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub parent: Option<String>,
+  pub title: String,
+  pub slug: String,
+  #[serde(default)]
+  pub body: String,
+}
+
+/// A character of a world within an archive
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedCharacter {
+  /// The ID that the character had when it was exported, used by other content to refer to it
+  pub id: String,
+  pub name: String,
+  #[serde(default)]
+  pub aliases: Vec<String>,
+  #[serde(default)]
+  pub summary: String,
+  #[serde(default)]
+  pub biography: String,
+  #[serde(default)]
+  pub attributes: BTreeMap<String, String>,
+}
+
+/// A location of a world within an archive
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedLocation {
+  /// The ID that the location had when it was exported, used by other locations to refer to it
+  pub id: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub parent: Option<String>,
+  pub name: String,
+  #[serde(default)]
+  pub kind: String,
+  #[serde(default)]
+  pub description: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub coordinates: Option<Coordinates>,
+}
+
+/// A map of a world within an archive
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedMap {
+  /// The ID that the map had when it was exported
+  pub id: String,
+  pub name: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub image: Option<ArchivedImage>,
+  #[serde(default)]
+  pub features: Vec<ArchivedFeature>,
+}
+
+/// A feature placed on a map within an archive
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedFeature {
+  #[serde(default)]
+  pub name: String,
+  /// The shape of the feature, as a GeoJSON geometry in pixels of the map image
+  pub geometry: Geometry,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub link: Option<ArchivedLink>,
+}
+
+/// A reference to an entity of the world within an archive, such as the entity that a feature
+/// links to
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedLink {
+  /// What sort of entity is referred to. One of "article", "character", "location" or "map"
+  pub r#type: String,
+  /// The ID that the entity had when it was exported
+  pub id: String,
+}
+
+/// A calendar of a world within an archive
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedCalendar {
+  /// The ID that the calendar had when it was exported
+  pub id: String,
+  pub name: String,
+  #[serde(flatten)]
+  pub definition: CalendarDefinition,
+  #[serde(default)]
+  pub events: Vec<ArchivedEvent>,
+}
+
+/// An event dated in a calendar within an archive
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedEvent {
+  pub title: String,
+  #[serde(default)]
+  pub description: String,
+  pub date: ArchivedDate,
+}
+
+/// A date in some calendar within an archive
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedDate {
+  /// The absolute year, counting from year 1 of the calendar
+  pub year: i64,
+  /// The month, counting from 1
+  pub month: u32,
+  /// The day of the month, counting from 1
+  pub day: u32,
+}
+
+/// A relationship between two entities of a world within an archive
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedRelationship {
+  pub source: ArchivedLink,
+  pub target: ArchivedLink,
+  pub relation: String,
+  #[serde(default)]
+  pub symmetric: bool,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub during: Option<ArchivedDuring>,
+}
+
+/// The dates that a relationship held between within an archive
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedDuring {
+  /// The ID that the calendar of the dates had when it was exported
+  pub calendar: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub start: Option<ArchivedDate>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub end: Option<ArchivedDate>,
+}
+
+impl From<Blob> for ArchivedImage {
+  fn from(blob: Blob) -> Self {
+    ArchivedImage {
+      content_type: blob.content_type,
+      data: base64::encode(&blob.data),
+    }
+  }
+}
+
+impl From<ArticleEntity> for ArchivedArticle {
+  fn from(article: ArticleEntity) -> Self {
+    ArchivedArticle {
+      id: article.identity.id.to_string(),
+      parent: article.data.parent.map(|parent| parent.to_string()),
+      title: article.data.title,
+      slug: article.data.slug.to_string(),
+      body: article.data.body,
+    }
+  }
+}
+
+impl From<CharacterEntity> for ArchivedCharacter {
+  fn from(character: CharacterEntity) -> Self {
+    ArchivedCharacter {
+      id: character.identity.id.to_string(),
+      name: character.data.name,
+      aliases: character.data.aliases,
+      summary: character.data.summary,
+      biography: character.data.biography,
+      attributes: character.data.attributes,
+    }
+  }
+}
+
+impl From<LocationEntity> for ArchivedLocation {
+  fn from(location: LocationEntity) -> Self {
+    ArchivedLocation {
+      id: location.identity.id.to_string(),
+      parent: location.data.parent.map(|parent| parent.to_string()),
+      name: location.data.name,
+      kind: location.data.kind,
+      description: location.data.description,
+      coordinates: location.data.coordinates,
+    }
+  }
+}
+
+impl From<FeatureEntity> for ArchivedFeature {
+  fn from(feature: FeatureEntity) -> Self {
+    ArchivedFeature {
+      name: feature.data.name,
+      geometry: feature.data.geometry,
+      link: feature.data.link.map(|link| ArchivedLink {
+        r#type: link.kind().to_owned(),
+        id: link.id(),
+      }),
+    }
+  }
+}
+
+impl ArchivedMap {
+  /// Build the archive of a map
+  ///
+  /// # Arguments
+  /// * `map` The map to archive
+  /// * `image` The image of the map, if it has one
+  /// * `features` The features on the map
+  pub fn new(map: MapEntity, image: Option<Blob>, features: Vec<FeatureEntity>) -> Self {
+    ArchivedMap {
+      id: map.identity.id.to_string(),
+      name: map.data.name,
+      image: image.map(Into::into),
+      features: features.into_iter().map(Into::into).collect(),
+    }
+  }
+}
+
+impl From<CalendarDate> for ArchivedDate {
+  fn from(date: CalendarDate) -> Self {
+    ArchivedDate {
+      year: date.year,
+      month: date.month,
+      day: date.day,
+    }
+  }
+}
+
+impl From<ArchivedDate> for CalendarDate {
+  fn from(date: ArchivedDate) -> Self {
+    CalendarDate {
+      year: date.year,
+      month: date.month,
+      day: date.day,
+    }
+  }
+}
+
+impl From<EventEntity> for ArchivedEvent {
+  fn from(event: EventEntity) -> Self {
+    ArchivedEvent {
+      title: event.data.title,
+      description: event.data.description,
+      date: event.data.date.into(),
+    }
+  }
+}
+
+impl From<&EntityRef> for ArchivedLink {
+  fn from(entity: &EntityRef) -> Self {
+    ArchivedLink {
+      r#type: entity.kind().to_owned(),
+      id: entity.id(),
+    }
+  }
+}
+
+impl From<RelationshipEntity> for ArchivedRelationship {
+  fn from(relationship: RelationshipEntity) -> Self {
+    ArchivedRelationship {
+      source: (&relationship.data.source).into(),
+      target: (&relationship.data.target).into(),
+      relation: relationship.data.relation,
+      symmetric: relationship.data.symmetric,
+      during: relationship.data.during.map(|during| ArchivedDuring {
+        calendar: during.calendar.to_string(),
+        start: during.start.map(Into::into),
+        end: during.end.map(Into::into),
+      }),
+    }
+  }
+}
+
+impl ArchivedCalendar {
+  /// Build the archive of a calendar
+  ///
+  /// # Arguments
+  /// * `calendar` The calendar to archive
+  /// * `events` The events dated in the calendar
+  pub fn new(calendar: CalendarEntity, events: Vec<EventEntity>) -> Self {
+    ArchivedCalendar {
+      id: calendar.identity.id.to_string(),
+      name: calendar.data.name,
+      definition: calendar.data.definition,
+      events: events.into_iter().map(Into::into).collect(),
+    }
+  }
+}
+
+impl WorldArchive {
+  /// Build an archive of a world, without any of its content. The content of the world is added
+  /// to the archive afterwards.
+  ///
+  /// # Arguments
+  /// * `world` The world to archive
+  /// * `cover` The original cover image of the world, if it has one
+  pub fn new(world: &WorldEntity, cover: Option<Blob>) -> Self {
+    WorldArchive {
+      schema_version: ARCHIVE_SCHEMA_VERSION,
+      exported: Some(Utc::now()),
+      world: ArchivedWorld {
+        id: Some(world.identity.id.to_string()),
+        name: world.data.name.clone(),
+        slug: world.data.slug.to_string(),
+        description: world.data.description.clone(),
+        visibility: Some(world.data.visibility.to_string()),
+        tags: world.data.tags.iter().map(|tag| tag.to_string()).collect(),
+        cover: cover.map(Into::into),
+      },
+      articles: vec![],
+      characters: vec![],
+      locations: vec![],
+      maps: vec![],
+      calendars: vec![],
+      relationships: vec![],
+    }
+  }
+
+  /// Bring an archive that was written in an older version of the format up to the current one
+  ///
+  /// # Returns
+  /// The upgraded archive, or `None` if the archive was written in a version that we don't know
+  pub fn upgrade(self) -> Option<Self> {
+    match self.schema_version {
+      ARCHIVE_SCHEMA_VERSION => Some(self),
+      _ => None,
+    }
+  }
+}
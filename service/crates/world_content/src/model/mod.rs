@@ -0,0 +1,3 @@
+mod archive;
+
+pub use archive::*;
@@ -0,0 +1,352 @@
+use super::prepare::{prepare_import, PreparedContent};
+use super::service::*;
+use crate::{ArchivedCalendar, ArchivedMap, WorldArchive};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+use universe_articles::{new_article_service, ArticleService};
+use universe_calendars::{
+  new_calendar_service, new_timeline_service, CalendarService, TimelineService,
+};
+use universe_characters::{new_character_service, CharacterData, CharacterService};
+use universe_database::Database;
+use universe_entity::IdMap;
+use universe_locations::{new_location_service, LocationService};
+use universe_maps::{new_map_feature_service, new_map_service, MapFeatureService, MapService};
+use universe_relationships::{new_relationship_service, RelationshipService};
+use universe_storage::BlobStore;
+use universe_users::UserID;
+use universe_worlds::{
+  new_world_cover_service, new_world_service, process_cover_image, CoverSize, ForkWorldError,
+  WorldCoverService, WorldEntity, WorldID, WorldService,
+};
+
+/// The World Content Service to work with a world along with everything inside of it
+pub struct WorldContentServiceImpl {
+  world_service: Box<dyn WorldService>,
+  world_cover_service: Box<dyn WorldCoverService>,
+  article_service: Box<dyn ArticleService>,
+  character_service: Box<dyn CharacterService>,
+  location_service: Box<dyn LocationService>,
+  map_service: Box<dyn MapService>,
+  map_feature_service: Box<dyn MapFeatureService>,
+  calendar_service: Box<dyn CalendarService>,
+  timeline_service: Box<dyn TimelineService>,
+  relationship_service: Box<dyn RelationshipService>,
+}
+
+/// Create a new World Content Service
+///
+/// # Arguments
+/// * `database` The database that every type of content is stored in
+/// * `blob_store` The Blob Store that cover images and map images are stored in
+///
+/// # Returns
+/// The World Content Service
+pub fn new_world_content_service(
+  database: Database,
+  blob_store: Arc<dyn BlobStore>,
+) -> impl WorldContentService {
+  WorldContentServiceImpl {
+    world_service: Box::new(new_world_service(database.clone(), blob_store.clone())),
+    world_cover_service: Box::new(new_world_cover_service(
+      database.clone(),
+      blob_store.clone(),
+    )),
+    article_service: Box::new(new_article_service(database.clone())),
+    character_service: Box::new(new_character_service(database.clone())),
+    location_service: Box::new(new_location_service(database.clone())),
+    map_service: Box::new(new_map_service(database.clone(), blob_store)),
+    map_feature_service: Box::new(new_map_feature_service(database.clone())),
+    calendar_service: Box::new(new_calendar_service(database.clone())),
+    timeline_service: Box::new(new_timeline_service(database.clone())),
+    relationship_service: Box::new(new_relationship_service(database)),
+  }
+}
+
+impl WorldContentService for WorldContentServiceImpl {
+  /// Fork a world, copying all of its content into the new world.
+  ///
+  /// If any of the content fails to copy then the fork is thrown away again, rather than leaving
+  /// a partial copy behind.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to fork
+  /// * `owner` The ID of the user that is to own the fork
+  ///
+  /// # Returns
+  /// The newly created fork
+  fn fork_world(&self, world_id: &WorldID, owner: &UserID) -> Result<WorldEntity, ForkWorldError> {
+    let fork = self.world_service.fork_world(world_id, owner)?;
+    info!("Forked world {} into {}", world_id, fork.identity.id);
+
+    if let Err(e) = self.copy_content(world_id, &fork.identity.id) {
+      warn!("Failed to copy content into fork: {}", e);
+      self.discard_world(&fork.identity.id);
+      return Err(ForkWorldError::UnknownError);
+    }
+
+    Ok(fork)
+  }
+
+  /// Build an archive of a world and all of its content
+  ///
+  /// # Arguments
+  /// * `world` The world to export
+  ///
+  /// # Returns
+  /// The archive of the world
+  fn export_world(&self, world: &WorldEntity) -> WorldArchive {
+    let world_id = &world.identity.id;
+
+    let cover = world.data.cover.as_ref().and_then(|cover_id| {
+      self
+        .world_cover_service
+        .get_world_cover(world_id, cover_id, CoverSize::Original)
+    });
+
+    let mut archive = WorldArchive::new(world, cover);
+    archive.articles = self
+      .article_service
+      .list_all_articles(world_id)
+      .into_iter()
+      .map(Into::into)
+      .collect();
+    archive.characters = self
+      .character_service
+      .list_all_characters(world_id)
+      .into_iter()
+      .map(Into::into)
+      .collect();
+    archive.locations = self
+      .location_service
+      .list_all_locations(world_id)
+      .into_iter()
+      .map(Into::into)
+      .collect();
+    archive.maps = self
+      .map_service
+      .list_all_maps(world_id)
+      .into_iter()
+      .map(|map| {
+        let image = map.data.image.as_ref().and_then(|image| {
+          self
+            .map_service
+            .get_map_image(world_id, &map.identity.id, &image.id)
+        });
+        let features = self
+          .map_feature_service
+          .list_features(world_id, &map.identity.id, None);
+        ArchivedMap::new(map, image, features)
+      })
+      .collect();
+    archive.calendars = self
+      .calendar_service
+      .list_all_calendars(world_id)
+      .into_iter()
+      .map(|calendar| {
+        let events = self
+          .timeline_service
+          .list_all_events(world_id, &calendar.identity.id);
+        ArchivedCalendar::new(calendar, events)
+      })
+      .collect();
+    archive.relationships = self
+      .relationship_service
+      .list_all_relationships(world_id)
+      .into_iter()
+      .map(Into::into)
+      .collect();
+    debug!(
+      "Exporting world {} with {} articles, {} characters, {} locations, {} maps, {} calendars and {} relationships",
+      world_id,
+      archive.articles.len(),
+      archive.characters.len(),
+      archive.locations.len(),
+      archive.maps.len(),
+      archive.calendars.len(),
+      archive.relationships.len()
+    );
+
+    archive
+  }
+
+  /// Create a new world from an archive, along with all of the content held in the archive.
+  ///
+  /// The whole archive is checked before anything is created, and if any of the content then
+  /// fails to be created the new world is thrown away again, rather than leaving a partial world
+  /// behind.
+  ///
+  /// # Arguments
+  /// * `owner` The ID of the user that is to own the imported world
+  /// * `archive` The archive to import
+  ///
+  /// # Returns
+  /// The newly created world
+  fn import_world(
+    &self,
+    owner: &UserID,
+    archive: WorldArchive,
+  ) -> Result<WorldEntity, ImportWorldError> {
+    let schema_version = archive.schema_version;
+    let archive = archive.upgrade().ok_or_else(|| {
+      warn!("Unsupported world archive version: {}", schema_version);
+      ImportWorldError::UnsupportedVersion(schema_version)
+    })?;
+
+    // Check everything that we can before creating anything, so that a bad archive doesn't leave
+    // half of a world behind
+    let prepared =
+      prepare_import(archive, owner.clone()).map_err(ImportWorldError::ValidationError)?;
+    if let Some(cover) = &prepared.content.cover {
+      process_cover_image(cover)?;
+    }
+
+    let world = self
+      .world_service
+      .create_world_with_unique_slug(prepared.world)?;
+    info!("Imported world {}", world.identity.id);
+
+    // Anything going wrong from here on would leave a partial world behind, so throw it away again
+    let world_id = world.identity.id.clone();
+    self.import_content(world, prepared.content).map_err(|e| {
+      warn!("Failed to import content into world: {}", e);
+      self.discard_world(&world_id);
+      ImportWorldError::UnknownError
+    })
+  }
+}
+
+impl WorldContentServiceImpl {
+  /// Copy all of the content of one world into another.
+  ///
+  /// The new IDs of everything that is copied are recorded as it goes, so that anything copied
+  /// later that refers to it can be pointed at the copy instead of the original.
+  ///
+  /// # Arguments
+  /// * `from` The ID of the world to copy the content from
+  /// * `to` The ID of the world to copy the content into
+  fn copy_content(&self, from: &WorldID, to: &WorldID) -> Result<(), Box<dyn std::error::Error>> {
+    let mut ids = IdMap::default();
+
+    let articles = self.article_service.copy_articles(from, to, &mut ids)?;
+    debug!("Copied {} articles into world {}", articles.len(), to);
+
+    let characters = self.character_service.copy_characters(from, to, &mut ids)?;
+    debug!("Copied {} characters into world {}", characters.len(), to);
+
+    let locations = self.location_service.copy_locations(from, to, &mut ids)?;
+    debug!("Copied {} locations into world {}", locations.len(), to);
+
+    let maps = self.map_service.copy_maps(from, to, &mut ids)?;
+    debug!("Copied {} maps into world {}", maps.len(), to);
+
+    let calendars = self.calendar_service.copy_calendars(from, to, &mut ids)?;
+    debug!("Copied {} calendars into world {}", calendars.len(), to);
+
+    let relationships = self
+      .relationship_service
+      .copy_relationships(from, to, &ids)?;
+    debug!(
+      "Copied {} relationships into world {}",
+      relationships.len(),
+      to
+    );
+
+    Ok(())
+  }
+
+  /// Create all of the content from an archive inside of the newly created world.
+  ///
+  /// The new IDs of everything that is created are recorded as it goes, so that anything created
+  /// later that refers to it by the ID from the archive can be pointed at the new one instead.
+  ///
+  /// # Arguments
+  /// * `world` The world that was created from the archive
+  /// * `content` The content to create inside the world
+  ///
+  /// # Returns
+  /// The world, updated with any changes made by importing the content
+  fn import_content(
+    &self,
+    mut world: WorldEntity,
+    content: PreparedContent,
+  ) -> Result<WorldEntity, Box<dyn std::error::Error>> {
+    if let Some(cover) = content.cover {
+      world = self
+        .world_cover_service
+        .set_world_cover(&world.identity.id, &cover)?;
+    }
+
+    let world_id = &world.identity.id;
+    let mut ids = IdMap::default();
+
+    let articles = self
+      .article_service
+      .import_articles(world_id, content.articles, &mut ids)?;
+    debug!(
+      "Imported {} articles into world {}",
+      articles.len(),
+      world_id
+    );
+
+    for character in content.characters {
+      let created = self.character_service.create_character(CharacterData {
+        world: world_id.clone(),
+        name: character.name,
+        aliases: character.aliases,
+        summary: character.summary,
+        biography: character.biography,
+        attributes: character.attributes,
+      })?;
+
+      ids.insert("character", &character.id, &created.identity.id);
+    }
+
+    let locations =
+      self
+        .location_service
+        .import_locations(world_id, content.locations, &mut ids)?;
+    debug!(
+      "Imported {} locations into world {}",
+      locations.len(),
+      world_id
+    );
+
+    let maps = self
+      .map_service
+      .import_maps(world_id, content.maps, &mut ids)?;
+    debug!("Imported {} maps into world {}", maps.len(), world_id);
+
+    let calendars =
+      self
+        .calendar_service
+        .import_calendars(world_id, content.calendars, &mut ids)?;
+    debug!(
+      "Imported {} calendars into world {}",
+      calendars.len(),
+      world_id
+    );
+
+    let relationships =
+      self
+        .relationship_service
+        .import_relationships(world_id, content.relationships, &ids)?;
+    debug!(
+      "Imported {} relationships into world {}",
+      relationships.len(),
+      world_id
+    );
+
+    Ok(world)
+  }
+
+  /// Throw away a world that was only partly filled with content
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to throw away
+  fn discard_world(&self, world_id: &WorldID) {
+    if let Err(e) = self.world_service.purge_world(world_id) {
+      warn!("Failed to discard partly filled world {}: {}", world_id, e);
+    }
+  }
+}
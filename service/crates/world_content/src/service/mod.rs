@@ -0,0 +1,6 @@
+mod implementation;
+mod prepare;
+mod service;
+
+pub use implementation::*;
+pub use service::*;
@@ -0,0 +1,433 @@
+use super::service::{ArchiveProblem, ArchiveValidationError};
+use crate::{ArchivedCharacter, ArchivedWorld, WorldArchive, ARCHIVE_SCHEMA_VERSION};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use universe_articles::{order_article_imports, ArticleImport};
+use universe_calendars::{CalendarDate, CalendarImport, EventImport};
+use universe_characters::{parse_aliases, parse_attributes};
+use universe_entity::EntityKey;
+use universe_locations::{order_location_imports, LocationImport};
+use universe_maps::{check_map_image, FeatureImport, FeatureValidationError, MapImport};
+use universe_relationships::{DateRangeImport, RelationshipImport, RelationshipValidationError};
+use universe_users::UserID;
+use universe_worlds::{parse_tags, Slug, Visibility, WorldData};
+
+/// The details needed to create the world held in an archive
+pub struct PreparedImport {
+  pub world: WorldData,
+  /// The content to create inside of the world once it exists
+  pub content: PreparedContent,
+}
+
+/// The content held in an archive, ready to create inside of the imported world
+pub struct PreparedContent {
+  /// The original cover image of the world, if it has one
+  pub cover: Option<Vec<u8>>,
+  /// The articles of the world, in the order to create them
+  pub articles: Vec<ArticleImport>,
+  /// The characters of the world, tidied up ready to create
+  pub characters: Vec<ArchivedCharacter>,
+  /// The locations of the world, in the order to create them
+  pub locations: Vec<LocationImport>,
+  /// The maps of the world, with their images decoded and checked
+  pub maps: Vec<MapImport>,
+  /// The calendars of the world, with the dates of their events checked
+  pub calendars: Vec<CalendarImport>,
+  /// The relationships of the world, with the entities and dates that they refer to checked
+  pub relationships: Vec<RelationshipImport>,
+}
+
+/// Convert the contents of an archive into the details needed to create the world that it holds.
+///
+/// # Arguments
+/// * `archive` The archive to import, already upgraded to the current version of the format
+/// * `owner` The ID of the user that is to own the imported world
+///
+/// # Returns
+/// The details to create the world from, or the problems with the archive
+pub fn prepare_import(
+  archive: WorldArchive,
+  owner: UserID,
+) -> Result<PreparedImport, Vec<ArchiveValidationError>> {
+  debug_assert_eq!(archive.schema_version, ARCHIVE_SCHEMA_VERSION);
+
+  let world = prepare_world(archive.world, owner);
+
+  let mut errors = vec![];
+  // Everything in the archive that other content can refer to, by its kind and ID
+  let mut keys = HashSet::new();
+
+  let mut articles = Vec::with_capacity(archive.articles.len());
+  for (index, article) in archive.articles.into_iter().enumerate() {
+    let location = format!("articles[{}].", index);
+
+    let title = article.title.trim().to_owned();
+    if title.is_empty() {
+      errors.push(error(&location, ArchiveProblem::Missing("title")));
+    }
+
+    keys.insert(EntityKey::new("article", &article.id));
+    match article.slug.parse::<Slug>() {
+      Ok(slug) => articles.push(ArticleImport {
+        key: article.id,
+        parent: article.parent,
+        title,
+        slug,
+        body: article.body.trim().to_owned(),
+      }),
+      Err(e) => errors.push(error(&location, ArchiveProblem::Slug(e))),
+    }
+  }
+
+  let mut characters = Vec::with_capacity(archive.characters.len());
+  for (index, character) in archive.characters.into_iter().enumerate() {
+    let location = format!("characters[{}].", index);
+
+    let name = character.name.trim().to_owned();
+    if name.is_empty() {
+      errors.push(error(&location, ArchiveProblem::Missing("name")));
+    }
+
+    keys.insert(EntityKey::new("character", &character.id));
+    match (
+      parse_aliases(character.aliases),
+      parse_attributes(character.attributes),
+    ) {
+      (Ok(aliases), Ok(attributes)) => characters.push(ArchivedCharacter {
+        id: character.id,
+        name,
+        aliases,
+        summary: character.summary.trim().to_owned(),
+        biography: character.biography.trim().to_owned(),
+        attributes,
+      }),
+      (aliases, attributes) => errors.extend(
+        vec![aliases.err(), attributes.err()]
+          .into_iter()
+          .filter_map(|v| v)
+          .map(|e| error(&location, ArchiveProblem::Character(e))),
+      ),
+    }
+  }
+
+  let mut locations = Vec::with_capacity(archive.locations.len());
+  for (index, archived) in archive.locations.into_iter().enumerate() {
+    let location = format!("locations[{}].", index);
+
+    let name = archived.name.trim().to_owned();
+    if name.is_empty() {
+      errors.push(error(&location, ArchiveProblem::Missing("name")));
+    }
+
+    keys.insert(EntityKey::new("location", &archived.id));
+    locations.push(LocationImport {
+      key: archived.id,
+      parent: archived.parent,
+      name,
+      kind: archived.kind.trim().to_owned(),
+      description: archived.description.trim().to_owned(),
+      coordinates: archived.coordinates,
+    });
+  }
+
+  let mut maps = Vec::with_capacity(archive.maps.len());
+  for (index, map) in archive.maps.into_iter().enumerate() {
+    let location = format!("maps[{}].", index);
+
+    let name = map.name.trim().to_owned();
+    if name.is_empty() {
+      errors.push(error(&location, ArchiveProblem::Missing("name")));
+    }
+
+    let image = match map.image {
+      None => None,
+      Some(image) => match base64::decode(&image.data) {
+        Err(_) => {
+          errors.push(error(&location, ArchiveProblem::InvalidMapImageData));
+          continue;
+        }
+        Ok(data) => match check_map_image(&data) {
+          Err(e) => {
+            errors.push(error(&location, ArchiveProblem::InvalidMapImage(e)));
+            continue;
+          }
+          Ok(checked) => Some((data, checked)),
+        },
+      },
+    };
+
+    let mut features = Vec::with_capacity(map.features.len());
+    for (index, feature) in map.features.into_iter().enumerate() {
+      let location = format!("{}features[{}].", location, index);
+
+      let geometry = match &image {
+        None => Err(FeatureValidationError::NoImage),
+        Some((_, checked)) => feature
+          .geometry
+          .validate(checked.width, checked.height)
+          .map_err(FeatureValidationError::Geometry),
+      };
+      if let Err(e) = geometry {
+        errors.push(error(&location, ArchiveProblem::Feature(e)));
+      }
+
+      let link = feature
+        .link
+        .map(|link| EntityKey::new(&link.r#type, &link.id));
+      if let Some(link) = &link {
+        if !keys.contains(link) {
+          errors.push(error(
+            &location,
+            ArchiveProblem::Feature(FeatureValidationError::UnknownLink),
+          ));
+        }
+      }
+
+      features.push(FeatureImport {
+        name: feature.name.trim().to_owned(),
+        geometry: feature.geometry,
+        link,
+      });
+    }
+
+    maps.push(MapImport {
+      key: map.id,
+      name,
+      image: image.map(|(data, _)| data),
+      features,
+    });
+  }
+  // Features can't link to maps, but relationships can refer to them
+  keys.extend(maps.iter().map(|map| EntityKey::new("map", &map.key)));
+
+  let mut calendars = Vec::with_capacity(archive.calendars.len());
+  for (index, calendar) in archive.calendars.into_iter().enumerate() {
+    let location = format!("calendars[{}].", index);
+
+    let name = calendar.name.trim().to_owned();
+    if name.is_empty() {
+      errors.push(error(&location, ArchiveProblem::Missing("name")));
+    }
+
+    // The dates of the events can only be checked against a calendar that makes sense
+    let definition_errors = calendar.definition.validate();
+    let definition_valid = definition_errors.is_empty();
+    errors.extend(
+      definition_errors
+        .into_iter()
+        .map(|e| error(&location, ArchiveProblem::Definition(e))),
+    );
+
+    let mut events = Vec::with_capacity(calendar.events.len());
+    for (index, event) in calendar.events.into_iter().enumerate() {
+      let location = format!("{}events[{}].", location, index);
+
+      let title = event.title.trim().to_owned();
+      if title.is_empty() {
+        errors.push(error(&location, ArchiveProblem::Missing("title")));
+      }
+
+      let date = event.date.into();
+      if definition_valid {
+        if let Err(e) = calendar.definition.check_date(&date) {
+          errors.push(error(&location, ArchiveProblem::Date(e)));
+        }
+      }
+
+      events.push(EventImport {
+        title,
+        description: event.description.trim().to_owned(),
+        date,
+      });
+    }
+
+    calendars.push(CalendarImport {
+      key: calendar.id,
+      name,
+      definition: calendar.definition,
+      events,
+    });
+  }
+
+  let mut relationships = Vec::with_capacity(archive.relationships.len());
+  for (index, relationship) in archive.relationships.into_iter().enumerate() {
+    let location = format!("relationships[{}].", index);
+
+    let source = EntityKey::new(&relationship.source.r#type, &relationship.source.id);
+    let target = EntityKey::new(&relationship.target.r#type, &relationship.target.id);
+    if !keys.contains(&source) {
+      errors.push(error(
+        &location,
+        ArchiveProblem::Relationship(RelationshipValidationError::UnknownSource),
+      ));
+    }
+    if !keys.contains(&target) {
+      errors.push(error(
+        &location,
+        ArchiveProblem::Relationship(RelationshipValidationError::UnknownTarget),
+      ));
+    }
+    if source == target {
+      errors.push(error(
+        &location,
+        ArchiveProblem::Relationship(RelationshipValidationError::SameEntity),
+      ));
+    }
+
+    let relation = relationship.relation.trim().to_owned();
+    if relation.is_empty() {
+      errors.push(error(&location, ArchiveProblem::Missing("relation")));
+    }
+
+    let during = match relationship.during {
+      None => None,
+      Some(during) => {
+        let start = during.start.map(CalendarDate::from);
+        let end = during.end.map(CalendarDate::from);
+        let calendar_key = during.calendar;
+
+        match calendars
+          .iter()
+          .find(|calendar| calendar.key == calendar_key)
+        {
+          None => errors.push(error(&location, ArchiveProblem::UnknownCalendar)),
+          Some(calendar) => {
+            let definition = &calendar.definition;
+            let check = |field: &'static str, date: &Option<CalendarDate>| match date {
+              None => Ok(()),
+              Some(date) => definition
+                .check_date(date)
+                .map_err(|e| ArchiveProblem::DuringDate(field, e)),
+            };
+
+            match (check("start", &start), check("end", &end)) {
+              (Ok(()), Ok(())) => {
+                if let (Some(start), Some(end)) = (&start, &end) {
+                  if definition.compare_dates(start, end) == Ordering::Greater {
+                    errors.push(error(&location, ArchiveProblem::DatesOutOfOrder));
+                  }
+                }
+              }
+              (start, end) => errors.extend(
+                vec![start.err(), end.err()]
+                  .into_iter()
+                  .filter_map(|v| v)
+                  .map(|problem| error(&location, problem)),
+              ),
+            }
+          }
+        }
+
+        Some(DateRangeImport {
+          calendar: calendar_key,
+          start,
+          end,
+        })
+      }
+    };
+
+    relationships.push(RelationshipImport {
+      source,
+      target,
+      relation,
+      symmetric: relationship.symmetric,
+      during,
+    });
+  }
+
+  let (world, cover) = match world {
+    Ok(world) if errors.is_empty() => world,
+    Ok(_) => return Err(errors),
+    Err(mut world_errors) => {
+      world_errors.extend(errors);
+      return Err(world_errors);
+    }
+  };
+
+  let articles =
+    order_article_imports(articles).map_err(|e| vec![error("", ArchiveProblem::Articles(e))])?;
+  let locations =
+    order_location_imports(locations).map_err(|e| vec![error("", ArchiveProblem::Locations(e))])?;
+
+  Ok(PreparedImport {
+    world,
+    content: PreparedContent {
+      cover,
+      articles,
+      characters,
+      locations,
+      maps,
+      calendars,
+      relationships,
+    },
+  })
+}
+
+/// Convert the details of a world from an archive into the data to create it from
+///
+/// # Arguments
+/// * `world` The world from the archive
+/// * `owner` The ID of the user that is to own the imported world
+///
+/// # Returns
+/// The world data and cover image, or the problems with the world
+fn prepare_world(
+  world: ArchivedWorld,
+  owner: UserID,
+) -> Result<(WorldData, Option<Vec<u8>>), Vec<ArchiveValidationError>> {
+  let name = Some(world.name.trim())
+    .filter(|name| !name.is_empty())
+    .ok_or(ArchiveProblem::Missing("name"));
+  let slug: Result<Slug, ArchiveProblem> = world.slug.parse().map_err(ArchiveProblem::Slug);
+  let visibility: Result<Visibility, ArchiveProblem> = match &world.visibility {
+    None => Ok(Visibility::default()),
+    Some(visibility) => visibility
+      .trim()
+      .parse()
+      .map_err(|_| ArchiveProblem::UnknownVisibility),
+  };
+  let tags = parse_tags(&world.tags).map_err(ArchiveProblem::Tags);
+  let cover = match &world.cover {
+    None => Ok(None),
+    Some(cover) => base64::decode(&cover.data)
+      .map(Some)
+      .map_err(|_| ArchiveProblem::InvalidCoverData),
+  };
+
+  match (name, slug, visibility, tags, cover) {
+    (Ok(name), Ok(slug), Ok(visibility), Ok(tags), Ok(cover)) => Ok((
+      WorldData {
+        owner,
+        name: name.to_owned(),
+        slug,
+        description: world.description.trim().to_owned(),
+        visibility,
+        forked_from: None,
+        tags,
+        cover: None,
+      },
+      cover,
+    )),
+    (name, slug, visibility, tags, cover) => Err(
+      vec![
+        name.err(),
+        slug.err(),
+        visibility.err(),
+        tags.err(),
+        cover.err(),
+      ]
+      .into_iter()
+      .filter_map(|v| v)
+      .map(|problem| error("world.", problem))
+      .collect(),
+    ),
+  }
+}
+
+/// Record a problem found at some location within an archive
+fn error(location: &str, problem: ArchiveProblem) -> ArchiveValidationError {
+  ArchiveValidationError {
+    location: location.to_owned(),
+    problem,
+  }
+}
@@ -0,0 +1,120 @@
+use crate::WorldArchive;
+use universe_articles::ArticleImportError;
+use universe_calendars::{DateError, DefinitionError};
+use universe_characters::CharacterDetailsError;
+use universe_locations::LocationImportError;
+use universe_maps::{FeatureValidationError, MapImageError};
+use universe_relationships::RelationshipValidationError;
+use universe_users::UserID;
+use universe_worlds::{
+  CoverImageError, CreateWorldError, ForkWorldError, SlugParseError, TagParseError, WorldEntity,
+  WorldID,
+};
+
+/// The World Content Service to work with a world along with everything inside of it, rather than
+/// one type of entity at a time
+pub trait WorldContentService: Send + Sync {
+  /// Fork a world, copying all of its content into the new world.
+  ///
+  /// If any of the content fails to copy then the fork is thrown away again, rather than leaving
+  /// a partial copy behind.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to fork
+  /// * `owner` The ID of the user that is to own the fork
+  ///
+  /// # Returns
+  /// The newly created fork
+  fn fork_world(&self, world_id: &WorldID, owner: &UserID) -> Result<WorldEntity, ForkWorldError>;
+
+  /// Build an archive of a world and all of its content
+  ///
+  /// # Arguments
+  /// * `world` The world to export
+  ///
+  /// # Returns
+  /// The archive of the world
+  fn export_world(&self, world: &WorldEntity) -> WorldArchive;
+
+  /// Create a new world from an archive, along with all of the content held in the archive.
+  ///
+  /// The whole archive is checked before anything is created, and if any of the content then
+  /// fails to be created the new world is thrown away again, rather than leaving a partial world
+  /// behind.
+  ///
+  /// # Arguments
+  /// * `owner` The ID of the user that is to own the imported world
+  /// * `archive` The archive to import
+  ///
+  /// # Returns
+  /// The newly created world
+  fn import_world(
+    &self,
+    owner: &UserID,
+    archive: WorldArchive,
+  ) -> Result<WorldEntity, ImportWorldError>;
+}
+
+/// A problem with some part of a world archive
+#[derive(Debug, PartialEq)]
+pub struct ArchiveValidationError {
+  /// Where in the archive the problem is, such as `characters[2].`. This is blank for problems
+  /// with the archive as a whole
+  pub location: String,
+  /// What the problem is
+  pub problem: ArchiveProblem,
+}
+
+/// Enumeration of the problems that can be found with the content of a world archive
+#[derive(Debug, PartialEq)]
+pub enum ArchiveProblem {
+  /// A field that must have a value was blank
+  Missing(&'static str),
+  /// The slug of the world or of an article was not valid
+  Slug(SlugParseError),
+  /// The visibility of the world isn't one that we know about
+  UnknownVisibility,
+  /// The tags of the world were not valid
+  Tags(TagParseError),
+  /// The cover image of the world wasn't valid Base64
+  InvalidCoverData,
+  /// The aliases or attributes of a character were not valid
+  Character(CharacterDetailsError),
+  /// The image of a map wasn't valid Base64
+  InvalidMapImageData,
+  /// The image of a map wasn't acceptable
+  InvalidMapImage(MapImageError),
+  /// A feature on a map was not valid
+  Feature(FeatureValidationError),
+  /// The definition of a calendar was not valid
+  Definition(DefinitionError),
+  /// The date of an event doesn't exist in its calendar
+  Date(DateError),
+  /// The calendar that a relationship is dated in isn't part of the archive
+  UnknownCalendar,
+  /// The start or end date of a relationship, as named, doesn't exist in its calendar
+  DuringDate(&'static str, DateError),
+  /// A relationship ends before it starts
+  DatesOutOfOrder,
+  /// The entities of a relationship were not valid
+  Relationship(RelationshipValidationError),
+  /// The articles don't form a valid tree
+  Articles(ArticleImportError),
+  /// The locations don't form a valid tree
+  Locations(LocationImportError),
+}
+
+/// Enumeration of reasons why we failed to import a world
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum ImportWorldError {
+  #[error("The archive schema version {0} is not supported")]
+  UnsupportedVersion(u32),
+  #[error("Archive contents were invalid: {0:?}")]
+  ValidationError(Vec<ArchiveValidationError>),
+  #[error("The cover image was not acceptable: {0}")]
+  InvalidCover(#[from] CoverImageError),
+  #[error("Failed to create the world: {0}")]
+  CreateWorldError(#[from] CreateWorldError),
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
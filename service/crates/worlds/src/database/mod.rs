@@ -1,9 +1,194 @@
-use super::{WorldFilters, WorldSorts};
+mod search;
+
+use super::{FacetCount, WorldFacet, WorldFilters, WorldSorts};
 use crate::{model::*, service::repository::*};
+use chrono::{DateTime, Utc};
+use postgres::types::ToSql;
+use search::WorldSearchQuery;
+use std::{error::Error, ops::Deref};
+use tracing::{debug, warn};
 use universe_database::Database;
-use universe_entity::{Page, Pagination, SortField};
+use universe_entity::{Identity, Page, Pagination, SortField};
+use universe_users::UserID;
+use uuid::Uuid;
+
+impl From<&postgres::Row> for WorldEntity {
+  fn from(row: &postgres::Row) -> Self {
+    let stars: i32 = row.get("stars");
+
+    WorldEntity {
+      identity: Identity {
+        id: row.get("world_id"),
+        version: row.get("version"),
+        created: row.get("created"),
+        updated: row.get("updated"),
+      },
+      data: row.into(),
+      stars: stars as u32,
+    }
+  }
+}
+
+impl From<&postgres::Row> for WorldData {
+  fn from(row: &postgres::Row) -> Self {
+    WorldData {
+      owner: row.get("owner_id"),
+      name: row.get("name"),
+      slug: row.get("slug"),
+      description: row.get("description"),
+      visibility: row.get("visibility"),
+      forked_from: row.get("forked_from"),
+      tags: row.get("tags"),
+      cover: row.get("cover_id"),
+    }
+  }
+}
+
+impl From<&postgres::Row> for WorldRevision {
+  fn from(row: &postgres::Row) -> Self {
+    let revision: i32 = row.get("revision");
+
+    WorldRevision {
+      world: row.get("world_id"),
+      revision: revision as u32,
+      version: row.get("version"),
+      created: row.get("created"),
+      data: row.into(),
+    }
+  }
+}
+
+/// SQL to snapshot the current state of a world as its next revision
+const RECORD_REVISION_SQL: &str =
+  "INSERT INTO world_revisions(world_id, revision, version, created, owner_id, name, slug,
+      description, visibility, forked_from, tags, cover_id)
+    SELECT world_id,
+      COALESCE((SELECT MAX(revision) FROM world_revisions WHERE world_id = $1), 0) + 1,
+      version, updated, owner_id, name, slug, description, visibility, forked_from, tags, cover_id
+    FROM worlds
+    WHERE world_id = $1";
+
+impl From<&postgres::Row> for TrashedWorld {
+  fn from(row: &postgres::Row) -> Self {
+    TrashedWorld {
+      world: row.into(),
+      deleted: row.get("deleted"),
+    }
+  }
+}
+
+impl From<&postgres::Row> for WorldMember {
+  fn from(row: &postgres::Row) -> Self {
+    WorldMember {
+      world: row.get("world_id"),
+      user: row.get("user_id"),
+      role: row.get("role"),
+      created: row.get("created"),
+      updated: row.get("updated"),
+    }
+  }
+}
+
+impl From<&postgres::Row> for WorldTransfer {
+  fn from(row: &postgres::Row) -> Self {
+    WorldTransfer {
+      world: row.get("world_id"),
+      from: row.get("from_user_id"),
+      to: row.get("to_user_id"),
+      created: row.get("created"),
+    }
+  }
+}
 
 impl WorldRepository for Database {
+  /// Retrieve the world that has the given unique ID
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to retrieve
+  ///
+  /// # Returns
+  /// The world, or `None` if it wasn't found
+  fn get_world_by_id(&self, world_id: &WorldID) -> Option<WorldEntity> {
+    let mut client = self.client().unwrap();
+
+    let world = client
+      .query(
+        "SELECT * FROM worlds WHERE world_id = $1 AND deleted IS NULL",
+        &[&world_id],
+      )
+      .map_err(|e| {
+        warn!("Error loading world from database: {}", e);
+        e
+      })
+      .ok()
+      .and_then(|rows| rows.get(0).map(|row| row.into()));
+
+    debug!("World for ID {}: {:?}", world_id, world);
+    world
+  }
+
+  /// Retrieve the world that is owned by the given user and has the given slug
+  ///
+  /// # Arguments
+  /// * `owner` The ID of the user that owns the world
+  /// * `slug` The slug of the world
+  ///
+  /// # Returns
+  /// The world, or `None` if it wasn't found
+  fn get_world_by_slug(&self, owner: &UserID, slug: &Slug) -> Option<WorldEntity> {
+    let mut client = self.client().unwrap();
+
+    let world = client
+      .query(
+        "SELECT * FROM worlds WHERE owner_id = $1 AND UPPER(slug) = UPPER($2) AND deleted IS NULL",
+        &[&owner, &slug],
+      )
+      .map_err(|e| {
+        warn!("Error loading world from database: {}", e);
+        e
+      })
+      .ok()
+      .and_then(|rows| rows.get(0).map(|row| row.into()));
+
+    debug!("World for owner {} and slug {}: {:?}", owner, slug, world);
+    world
+  }
+
+  /// Retrieve the world that is owned by the given user and used to have the given slug before
+  /// it was changed
+  ///
+  /// # Arguments
+  /// * `owner` The ID of the user that owns the world
+  /// * `slug` The previous slug of the world
+  ///
+  /// # Returns
+  /// The world, or `None` if it wasn't found
+  fn get_world_by_previous_slug(&self, owner: &UserID, slug: &Slug) -> Option<WorldEntity> {
+    let mut client = self.client().unwrap();
+
+    let world = client
+      .query(
+        "SELECT worlds.* FROM worlds
+          JOIN world_slug_history ON world_slug_history.world_id = worlds.world_id
+          WHERE world_slug_history.owner_id = $1
+          AND UPPER(world_slug_history.slug) = UPPER($2)
+          AND worlds.deleted IS NULL",
+        &[&owner, &slug],
+      )
+      .map_err(|e| {
+        warn!("Error loading world from database: {}", e);
+        e
+      })
+      .ok()
+      .and_then(|rows| rows.get(0).map(|row| row.into()));
+
+    debug!(
+      "World for owner {} and previous slug {}: {:?}",
+      owner, slug, world
+    );
+    world
+  }
+
   /// Perform a search for all the worlds that match the given filters, sorted in the requested order.
   ///
   /// # Arguments
@@ -15,14 +200,2884 @@ impl WorldRepository for Database {
   /// A page of worlds
   fn search_worlds(
     &self,
-    _filters: WorldFilters,
-    _sorts: Vec<SortField<WorldSorts>>,
+    filters: WorldFilters,
+    sorts: Vec<SortField<WorldSorts>>,
     pagination: Pagination,
   ) -> Page<WorldEntity> {
-    Page {
-      entries: vec![],
-      total: 0,
+    let mut client = self.client().unwrap();
+
+    let (select_sql, select_binds) =
+      WorldSearchQuery::new(filters.clone()).select(&sorts, pagination);
+    let (count_sql, count_binds) = WorldSearchQuery::new(filters).count();
+    debug!("Searching worlds: {}", select_sql);
+
+    let select_binds: Vec<&(dyn ToSql + Sync)> = select_binds.iter().map(|b| b.deref()).collect();
+    let count_binds: Vec<&(dyn ToSql + Sync)> = count_binds.iter().map(|b| b.deref()).collect();
+
+    let entries: Vec<WorldEntity> = client
+      .query(select_sql.as_str(), &select_binds)
+      .map_err(|e| {
+        warn!("Error searching worlds in database: {}", e);
+        e
+      })
+      .map(|rows| rows.iter().map(|row| row.into()).collect())
+      .unwrap_or_default();
+
+    let total: i64 = client
+      .query_one(count_sql.as_str(), &count_binds)
+      .map_err(|e| {
+        warn!("Error counting worlds in database: {}", e);
+        e
+      })
+      .map(|row| row.get("total"))
+      .unwrap_or(0);
+
+    let page = Page {
+      entries,
+      total: total as u32,
+      offset: pagination.offset,
+    };
+    debug!("Found worlds: {:?}", page);
+
+    page
+  }
+
+  /// Count how many of the worlds that match the given filters have each value of a facet, most
+  /// common values first
+  ///
+  /// # Arguments
+  /// * `filters` The filters to apply when searching for worlds
+  /// * `facet` The facet to count the values of
+  /// * `limit` The maximum number of values to return
+  ///
+  /// # Returns
+  /// The counts of each value of the facet
+  fn count_world_facet(
+    &self,
+    filters: WorldFilters,
+    facet: WorldFacet,
+    limit: u32,
+  ) -> Vec<FacetCount> {
+    let mut client = self.client().unwrap();
+
+    let (sql, binds) = WorldSearchQuery::new(filters).facet(facet, limit);
+    debug!("Counting world facet: {}", sql);
+    let binds: Vec<&(dyn ToSql + Sync)> = binds.iter().map(|b| b.deref()).collect();
+
+    let counts = client
+      .query(sql.as_str(), &binds)
+      .map_err(|e| {
+        warn!("Error counting world facet in database: {}", e);
+        e
+      })
+      .map(|rows| {
+        rows
+          .iter()
+          .map(|row| FacetCount {
+            value: row.get("value"),
+            count: row.get::<_, i64>("count") as u32,
+          })
+          .collect()
+      })
+      .unwrap_or_default();
+
+    debug!("Counts of {} facet: {:?}", facet, counts);
+    counts
+  }
+
+  /// Create a new world record in the data store
+  ///
+  /// # Arguments
+  /// * `world` The world details to persist to the data store
+  ///
+  /// # Returns
+  /// The world that was persisted
+  fn create_world(&self, world: WorldData) -> Result<WorldEntity, PersistWorldError> {
+    debug!("Creating record for world: {:?}", world);
+
+    let mut client = self.client().unwrap();
+    let mut transaction = client.transaction().unwrap();
+
+    let new_id = WorldID::default();
+    let new_version = Uuid::new_v4();
+    let new_updated = Utc::now();
+
+    let result: WorldEntity = transaction
+      .query(
+        "INSERT INTO worlds(world_id, version, created, updated, owner_id, name, slug, description, visibility, forked_from, tags, cover_id)
+          VALUES ($1, $2, $3, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+          RETURNING *",
+        &[
+          &new_id,
+          &new_version,
+          &new_updated,
+          &world.owner,
+          &world.name,
+          &world.slug,
+          &world.description,
+          &world.visibility,
+          &world.forked_from,
+          &world.tags,
+          &world.cover,
+        ],
+      )
+      .map(|rows| rows.get(0).unwrap().into())?;
+    transaction.execute(RECORD_REVISION_SQL, &[&result.identity.id])?;
+
+    transaction.commit().unwrap();
+
+    debug!("Created record for world: {:?}", result);
+
+    Ok(result)
+  }
+
+  /// Update an existing world in the data store
+  ///
+  /// # Arguments
+  /// * `world` The world entity to persist to the data store
+  ///
+  /// # Returns
+  /// The world that was persisted
+  fn update_world(&self, world: WorldEntity) -> Result<WorldEntity, PersistWorldError> {
+    debug!("Updating record for world: {:?}", world);
+
+    let mut client = self.client().unwrap();
+    let mut transaction = client.transaction().unwrap();
+
+    let new_version = Uuid::new_v4();
+    let new_updated = Utc::now();
+
+    // Worlds that were created before revisions were recorded need their current state
+    // remembering before it's changed, so that it can still be restored
+    let record_initial_revision = format!(
+      "{} AND NOT EXISTS (SELECT 1 FROM world_revisions WHERE world_id = $1)",
+      RECORD_REVISION_SQL
+    );
+    transaction.execute(record_initial_revision.as_str(), &[&world.identity.id])?;
+
+    // Remember the current slug if it's about to change, so that old links can still find the world
+    transaction.execute(
+      "INSERT INTO world_slug_history(world_id, owner_id, slug, replaced)
+          SELECT world_id, owner_id, slug, $1 FROM worlds
+          WHERE world_id = $2
+          AND version = $3
+          AND deleted IS NULL
+          AND (owner_id <> $4 OR UPPER(slug) <> UPPER($5))
+          ON CONFLICT (owner_id, UPPER(slug))
+          DO UPDATE SET world_id = EXCLUDED.world_id, replaced = EXCLUDED.replaced",
+      &[
+        &new_updated,
+        &world.identity.id,
+        &world.identity.version,
+        &world.data.owner,
+        &world.data.slug,
+      ],
+    )?;
+    transaction.execute(
+      "DELETE FROM world_slug_history WHERE owner_id = $1 AND UPPER(slug) = UPPER($2)",
+      &[&world.data.owner, &world.data.slug],
+    )?;
+
+    let rows = transaction.query(
+      "UPDATE worlds SET owner_id = $1, name = $2, slug = $3, description = $4, visibility = $5,
+          tags = $6, cover_id = $7, version = $8, updated = $9
+          WHERE world_id = $10
+          AND version = $11
+          AND deleted IS NULL
+          RETURNING *",
+      &[
+        &world.data.owner,
+        &world.data.name,
+        &world.data.slug,
+        &world.data.description,
+        &world.data.visibility,
+        &world.data.tags,
+        &world.data.cover,
+        &new_version,
+        &new_updated,
+        &world.identity.id,
+        &world.identity.version,
+      ],
+    )?;
+
+    if rows.is_empty() {
+      let world_found = transaction.query(
+        "SELECT version FROM worlds WHERE world_id = $1 AND deleted IS NULL",
+        &[&world.identity.id],
+      )?;
+
+      if world_found.is_empty() {
+        warn!(
+          "Attempted to update world {} that wasn't found",
+          world.identity.id
+        );
+        Err(PersistWorldError::WorldNotFound)
+      } else {
+        let world_row = world_found.get(0).unwrap();
+        let old_version: Uuid = world_row.get("version");
+
+        warn!(
+          "Attempted to update world {}. Expected version {} but database had {}",
+          world.identity.id, world.identity.version, old_version
+        );
+        Err(PersistWorldError::OptimisticLockFailure)
+      }
+    } else {
+      let result = rows.get(0).unwrap().into();
+      transaction.execute(RECORD_REVISION_SQL, &[&world.identity.id])?;
+
+      transaction.commit().unwrap();
+
+      debug!("Updated record for world: {:?}", result);
+      Ok(result)
+    }
+  }
+
+  /// Retrieve the revisions of the given world, most recent first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the revisions of
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of revisions
+  fn list_world_revisions(
+    &self,
+    world_id: &WorldID,
+    pagination: Pagination,
+  ) -> Page<WorldRevision> {
+    let mut client = self.client().unwrap();
+
+    let entries: Vec<WorldRevision> = client
+      .query(
+        "SELECT * FROM world_revisions WHERE world_id = $1
+          ORDER BY revision DESC
+          LIMIT $2 OFFSET $3",
+        &[
+          &world_id,
+          &i64::from(pagination.limit),
+          &i64::from(pagination.offset),
+        ],
+      )
+      .map_err(|e| {
+        warn!("Error loading world revisions from database: {}", e);
+        e
+      })
+      .map(|rows| rows.iter().map(|row| row.into()).collect())
+      .unwrap_or_default();
+
+    let total: i64 = client
+      .query_one(
+        "SELECT COUNT(*) AS total FROM world_revisions WHERE world_id = $1",
+        &[&world_id],
+      )
+      .map_err(|e| {
+        warn!("Error counting world revisions in database: {}", e);
+        e
+      })
+      .map(|row| row.get("total"))
+      .unwrap_or(0);
+
+    let page = Page {
+      entries,
+      total: total as u32,
+      offset: pagination.offset,
+    };
+    debug!("Found revisions for world {}: {:?}", world_id, page);
+
+    page
+  }
+
+  /// Retrieve a single revision of the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  /// * `revision` The number of the revision to retrieve
+  ///
+  /// # Returns
+  /// The revision, or `None` if it wasn't found
+  fn get_world_revision(&self, world_id: &WorldID, revision: u32) -> Option<WorldRevision> {
+    let mut client = self.client().unwrap();
+
+    let result = client
+      .query(
+        "SELECT * FROM world_revisions WHERE world_id = $1 AND revision = $2",
+        &[&world_id, &(revision as i32)],
+      )
+      .map_err(|e| {
+        warn!("Error loading world revision from database: {}", e);
+        e
+      })
+      .ok()
+      .and_then(|rows| rows.get(0).map(|row| row.into()));
+
+    debug!("Revision {} of world {}: {:?}", revision, world_id, result);
+    result
+  }
+
+  /// Move a world into the trash, so that it is no longer visible but can still be restored
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to move to the trash
+  fn delete_world(&self, world_id: &WorldID) -> Result<(), PersistWorldError> {
+    debug!("Moving world to the trash: {}", world_id);
+
+    let mut client = self.client().unwrap();
+
+    let updated = client.execute(
+      "UPDATE worlds SET deleted = $1 WHERE world_id = $2 AND deleted IS NULL",
+      &[&Utc::now(), &world_id],
+    )?;
+
+    if updated == 0 {
+      warn!("Attempted to delete world {} that wasn't found", world_id);
+      Err(PersistWorldError::WorldNotFound)
+    } else {
+      Ok(())
+    }
+  }
+
+  /// Retrieve the worlds owned by the given user that are currently in the trash, most recently
+  /// deleted first
+  ///
+  /// # Arguments
+  /// * `owner` The ID of the user that owns the worlds
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of trashed worlds
+  fn list_trashed_worlds(&self, owner: &UserID, pagination: Pagination) -> Page<TrashedWorld> {
+    let mut client = self.client().unwrap();
+
+    let entries: Vec<TrashedWorld> = client
+      .query(
+        "SELECT * FROM worlds WHERE owner_id = $1 AND deleted IS NOT NULL
+          ORDER BY deleted DESC, world_id ASC
+          LIMIT $2 OFFSET $3",
+        &[
+          &owner,
+          &i64::from(pagination.limit),
+          &i64::from(pagination.offset),
+        ],
+      )
+      .map_err(|e| {
+        warn!("Error loading trashed worlds from database: {}", e);
+        e
+      })
+      .map(|rows| rows.iter().map(|row| row.into()).collect())
+      .unwrap_or_default();
+
+    let total: i64 = client
+      .query_one(
+        "SELECT COUNT(*) AS total FROM worlds WHERE owner_id = $1 AND deleted IS NOT NULL",
+        &[&owner],
+      )
+      .map_err(|e| {
+        warn!("Error counting trashed worlds in database: {}", e);
+        e
+      })
+      .map(|row| row.get("total"))
+      .unwrap_or(0);
+
+    let page = Page {
+      entries,
+      total: total as u32,
       offset: pagination.offset,
+    };
+    debug!("Found trashed worlds for owner {}: {:?}", owner, page);
+
+    page
+  }
+
+  /// Restore a world owned by the given user out of the trash
+  ///
+  /// # Arguments
+  /// * `owner` The ID of the user that owns the world
+  /// * `world_id` The ID of the world to restore
+  ///
+  /// # Returns
+  /// The world that was restored
+  fn restore_world(
+    &self,
+    owner: &UserID,
+    world_id: &WorldID,
+  ) -> Result<WorldEntity, PersistWorldError> {
+    debug!("Restoring world from the trash: {}", world_id);
+
+    let mut client = self.client().unwrap();
+
+    let rows = client.query(
+      "UPDATE worlds SET deleted = NULL, version = $1, updated = $2
+        WHERE world_id = $3
+        AND owner_id = $4
+        AND deleted IS NOT NULL
+        RETURNING *",
+      &[&Uuid::new_v4(), &Utc::now(), &world_id, &owner],
+    )?;
+
+    match rows.get(0) {
+      None => {
+        warn!(
+          "Attempted to restore world {} that wasn't in the trash of user {}",
+          world_id, owner
+        );
+        Err(PersistWorldError::WorldNotFound)
+      }
+      Some(row) => {
+        let result = row.into();
+        debug!("Restored record for world: {:?}", result);
+        Ok(result)
+      }
+    }
+  }
+
+  /// Permanently remove all of the worlds that were moved to the trash before the given time
+  ///
+  /// # Arguments
+  /// * `deleted_before` The cutoff time for worlds to be purged
+  ///
+  /// # Returns
+  /// The IDs of the worlds that were purged
+  fn purge_trashed_worlds(
+    &self,
+    deleted_before: DateTime<Utc>,
+  ) -> Result<Vec<WorldID>, PersistWorldError> {
+    let mut client = self.client().unwrap();
+
+    let purged: Vec<WorldID> = client
+      .query(
+        "DELETE FROM worlds WHERE deleted IS NOT NULL AND deleted < $1 RETURNING world_id",
+        &[&deleted_before],
+      )?
+      .iter()
+      .map(|row| row.get("world_id"))
+      .collect();
+
+    debug!(
+      "Purged {} worlds deleted before {}",
+      purged.len(),
+      deleted_before
+    );
+    Ok(purged)
+  }
+
+  /// Permanently remove a single world, whether it is in the trash or not
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to remove
+  fn purge_world(&self, world_id: &WorldID) -> Result<(), PersistWorldError> {
+    debug!("Purging world: {}", world_id);
+
+    let mut client = self.client().unwrap();
+
+    let deleted = client.execute("DELETE FROM worlds WHERE world_id = $1", &[&world_id])?;
+
+    if deleted == 0 {
+      warn!("Attempted to purge world {} that wasn't found", world_id);
+      Err(PersistWorldError::WorldNotFound)
+    } else {
+      Ok(())
+    }
+  }
+  /// Find out who owns a world, whether it is in the trash or not
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  ///
+  /// # Returns
+  /// The ID of the user that owns the world, or `None` if it wasn't found
+  fn get_world_owner(&self, world_id: &WorldID) -> Option<UserID> {
+    let mut client = self.client().unwrap();
+
+    client
+      .query(
+        "SELECT owner_id FROM worlds WHERE world_id = $1",
+        &[&world_id],
+      )
+      .map_err(|e| {
+        warn!("Error loading world owner from database: {}", e);
+        e
+      })
+      .ok()
+      .and_then(|rows| rows.get(0).map(|row| row.get("owner_id")))
+  }
+
+  /// Retrieve the IDs of every world that a user owns, including those in the trash
+  ///
+  /// # Arguments
+  /// * `owner` The ID of the user
+  ///
+  /// # Returns
+  /// The IDs of the worlds
+  fn list_owned_world_ids(&self, owner: &UserID) -> Vec<WorldID> {
+    let mut client = self.client().unwrap();
+
+    client
+      .query(
+        "SELECT world_id FROM worlds WHERE owner_id = $1 ORDER BY world_id",
+        &[&owner],
+      )
+      .map_err(|e| {
+        warn!("Error loading owned worlds from database: {}", e);
+        e
+      })
+      .map(|rows| rows.iter().map(|row| row.get("world_id")).collect())
+      .unwrap_or_default()
+  }
+
+  /// Retrieve all of the members of the given world, in the order they were added
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the members of
+  ///
+  /// # Returns
+  /// The members of the world
+  fn list_world_members(&self, world_id: &WorldID) -> Vec<WorldMember> {
+    let mut client = self.client().unwrap();
+
+    let members = client
+      .query(
+        "SELECT * FROM world_members WHERE world_id = $1 ORDER BY created ASC, user_id ASC",
+        &[&world_id],
+      )
+      .map_err(|e| {
+        warn!("Error loading world members from database: {}", e);
+        e
+      })
+      .map(|rows| rows.iter().map(|row| row.into()).collect())
+      .unwrap_or_default();
+
+    debug!("Members of world {}: {:?}", world_id, members);
+    members
+  }
+
+  /// Retrieve the membership of the given user on the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  /// * `user_id` The ID of the user
+  ///
+  /// # Returns
+  /// The membership, or `None` if the user isn't a member of the world
+  fn get_world_member(&self, world_id: &WorldID, user_id: &UserID) -> Option<WorldMember> {
+    let mut client = self.client().unwrap();
+
+    let member = client
+      .query(
+        "SELECT * FROM world_members WHERE world_id = $1 AND user_id = $2",
+        &[&world_id, &user_id],
+      )
+      .map_err(|e| {
+        warn!("Error loading world member from database: {}", e);
+        e
+      })
+      .ok()
+      .and_then(|rows| rows.get(0).map(|row| row.into()));
+
+    debug!("Member {} of world {}: {:?}", user_id, world_id, member);
+    member
+  }
+
+  /// Add a new member to the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to add the member to
+  /// * `user_id` The ID of the user to add as a member
+  /// * `role` The role that the user has on the world
+  ///
+  /// # Returns
+  /// The membership that was persisted
+  fn create_world_member(
+    &self,
+    world_id: &WorldID,
+    user_id: &UserID,
+    role: WorldRole,
+  ) -> Result<WorldMember, PersistWorldError> {
+    debug!("Adding user {} to world {} as {}", user_id, world_id, role);
+
+    let mut client = self.client().unwrap();
+
+    let member = client
+      .query(
+        "INSERT INTO world_members(world_id, user_id, role, created, updated)
+          VALUES ($1, $2, $3, $4, $4)
+          RETURNING *",
+        &[&world_id, &user_id, &role, &Utc::now()],
+      )
+      .map(|rows| rows.get(0).unwrap().into())?;
+
+    debug!("Created world member: {:?}", member);
+    Ok(member)
+  }
+
+  /// Change the role of an existing member of the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  /// * `user_id` The ID of the member
+  /// * `role` The new role that the user has on the world
+  ///
+  /// # Returns
+  /// The membership that was persisted
+  fn update_world_member(
+    &self,
+    world_id: &WorldID,
+    user_id: &UserID,
+    role: WorldRole,
+  ) -> Result<WorldMember, PersistWorldError> {
+    debug!(
+      "Changing user {} on world {} to {}",
+      user_id, world_id, role
+    );
+
+    let mut client = self.client().unwrap();
+
+    let rows = client.query(
+      "UPDATE world_members SET role = $1, updated = $2
+        WHERE world_id = $3
+        AND user_id = $4
+        RETURNING *",
+      &[&role, &Utc::now(), &world_id, &user_id],
+    )?;
+
+    match rows.get(0) {
+      None => {
+        warn!(
+          "Attempted to update user {} who isn't a member of world {}",
+          user_id, world_id
+        );
+        Err(PersistWorldError::MemberNotFound)
+      }
+      Some(row) => {
+        let result = row.into();
+        debug!("Updated world member: {:?}", result);
+        Ok(result)
+      }
+    }
+  }
+
+  /// Remove a member from the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  /// * `user_id` The ID of the member to remove
+  fn delete_world_member(
+    &self,
+    world_id: &WorldID,
+    user_id: &UserID,
+  ) -> Result<(), PersistWorldError> {
+    debug!("Removing user {} from world {}", user_id, world_id);
+
+    let mut client = self.client().unwrap();
+
+    let deleted = client.execute(
+      "DELETE FROM world_members WHERE world_id = $1 AND user_id = $2",
+      &[&world_id, &user_id],
+    )?;
+
+    if deleted == 0 {
+      warn!(
+        "Attempted to remove user {} who isn't a member of world {}",
+        user_id, world_id
+      );
+      Err(PersistWorldError::MemberNotFound)
+    } else {
+      Ok(())
     }
   }
+
+  /// Record that the given user has starred the given world. Starring a world that the user has
+  /// already starred does nothing
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to star
+  /// * `user_id` The ID of the user starring the world
+  fn star_world(&self, world_id: &WorldID, user_id: &UserID) -> Result<(), PersistWorldError> {
+    debug!("Starring world {} for user {}", world_id, user_id);
+
+    let mut client = self.client().unwrap();
+
+    client.execute(
+      "INSERT INTO world_stars(world_id, user_id, created) VALUES ($1, $2, $3)
+          ON CONFLICT (world_id, user_id) DO NOTHING",
+      &[&world_id, &user_id, &Utc::now()],
+    )?;
+
+    Ok(())
+  }
+
+  /// Remove the star that the given user gave to the given world, if there is one
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to unstar
+  /// * `user_id` The ID of the user unstarring the world
+  fn unstar_world(&self, world_id: &WorldID, user_id: &UserID) -> Result<(), PersistWorldError> {
+    debug!("Unstarring world {} for user {}", world_id, user_id);
+
+    let mut client = self.client().unwrap();
+
+    client.execute(
+      "DELETE FROM world_stars WHERE world_id = $1 AND user_id = $2",
+      &[&world_id, &user_id],
+    )?;
+
+    Ok(())
+  }
+
+  /// Retrieve the pending ownership transfer of the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  ///
+  /// # Returns
+  /// The transfer, or `None` if the world isn't being transferred
+  fn get_world_transfer(&self, world_id: &WorldID) -> Option<WorldTransfer> {
+    let mut client = self.client().unwrap();
+
+    let transfer = client
+      .query(
+        "SELECT * FROM world_transfers WHERE world_id = $1",
+        &[&world_id],
+      )
+      .map_err(|e| {
+        warn!("Error loading world transfer from database: {}", e);
+        e
+      })
+      .ok()
+      .and_then(|rows| rows.get(0).map(|row| row.into()));
+
+    debug!("Transfer of world {}: {:?}", world_id, transfer);
+    transfer
+  }
+
+  /// Retrieve all of the pending ownership transfers that have been offered to the given user,
+  /// oldest first
+  ///
+  /// # Arguments
+  /// * `user_id` The ID of the user that the worlds are being offered to
+  ///
+  /// # Returns
+  /// The transfers
+  fn list_world_transfers_to(&self, user_id: &UserID) -> Vec<WorldTransfer> {
+    let mut client = self.client().unwrap();
+
+    let transfers = client
+      .query(
+        "SELECT * FROM world_transfers WHERE to_user_id = $1 ORDER BY created ASC, world_id ASC",
+        &[&user_id],
+      )
+      .map_err(|e| {
+        warn!("Error loading world transfers from database: {}", e);
+        e
+      })
+      .map(|rows| rows.iter().map(|row| row.into()).collect())
+      .unwrap_or_default();
+
+    debug!("Transfers to user {}: {:?}", user_id, transfers);
+    transfers
+  }
+
+  /// Save an offer to transfer the ownership of a world, replacing any previous offer for the
+  /// same world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to transfer
+  /// * `from` The ID of the user that currently owns the world
+  /// * `to` The ID of the user that the world is offered to
+  ///
+  /// # Returns
+  /// The transfer that was persisted
+  fn save_world_transfer(
+    &self,
+    world_id: &WorldID,
+    from: &UserID,
+    to: &UserID,
+  ) -> Result<WorldTransfer, PersistWorldError> {
+    debug!("Offering world {} from {} to {}", world_id, from, to);
+
+    let mut client = self.client().unwrap();
+
+    let transfer = client
+      .query(
+        "INSERT INTO world_transfers(world_id, from_user_id, to_user_id, created)
+          VALUES ($1, $2, $3, $4)
+          ON CONFLICT (world_id) DO UPDATE
+          SET from_user_id = EXCLUDED.from_user_id,
+            to_user_id = EXCLUDED.to_user_id,
+            created = EXCLUDED.created
+          RETURNING *",
+        &[&world_id, &from, &to, &Utc::now()],
+      )
+      .map(|rows| rows.get(0).unwrap().into())?;
+
+    debug!("Saved world transfer: {:?}", transfer);
+    Ok(transfer)
+  }
+
+  /// Remove the pending ownership transfer of the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  fn delete_world_transfer(&self, world_id: &WorldID) -> Result<(), PersistWorldError> {
+    debug!("Removing transfer of world {}", world_id);
+
+    let mut client = self.client().unwrap();
+
+    let deleted = client.execute(
+      "DELETE FROM world_transfers WHERE world_id = $1",
+      &[&world_id],
+    )?;
+
+    if deleted == 0 {
+      warn!(
+        "Attempted to remove transfer of world {} that isn't being transferred",
+        world_id
+      );
+      Err(PersistWorldError::TransferNotFound)
+    } else {
+      Ok(())
+    }
+  }
+}
+
+impl From<postgres::Error> for PersistWorldError {
+  fn from(error: postgres::Error) -> Self {
+    warn!("Error persisting world in database: {:?}", error);
+
+    error
+      .source()
+      .and_then(|e| e.downcast_ref::<postgres::error::DbError>())
+      .map(|e| match e.constraint() {
+        Some("worlds_owner_slug_key") => PersistWorldError::DuplicateSlug,
+        Some("world_members_pkey") => PersistWorldError::DuplicateMember,
+        Some("world_members_world_id_fkey") => PersistWorldError::WorldNotFound,
+        Some("world_members_user_id_fkey") => PersistWorldError::UserNotFound,
+        Some("world_transfers_world_id_fkey") => PersistWorldError::WorldNotFound,
+        Some("world_transfers_to_user_id_fkey") => PersistWorldError::UserNotFound,
+        Some("world_stars_world_id_fkey") => PersistWorldError::WorldNotFound,
+        Some("world_stars_user_id_fkey") => PersistWorldError::UserNotFound,
+        _ => PersistWorldError::UnknownError,
+      })
+      .unwrap_or(PersistWorldError::UnknownError)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::TagFilter;
+  use spectral::prelude::*;
+  use test_env_log::test;
+  use universe_entity::SortDirection;
+  use universe_test_database_wrapper::TestDatabaseWrapper;
+  use universe_testdata::{seed, User, World, WorldMember as TestWorldMember, WorldStar};
+  use uuid::Uuid;
+
+  /// Seed a standard set of worlds, all owned by the same user, into the database
+  fn seed_worlds(database: &TestDatabaseWrapper) -> (User, Vec<World>) {
+    let user: User = Default::default();
+    let worlds: Vec<World> = vec![
+      World {
+        owner_id: user.user_id,
+        name: "Middle Earth".to_owned(),
+        slug: "middle-earth".to_owned(),
+        description: "Home of the Hobbits".to_owned(),
+        created: "2020-03-01T12:00:00Z".parse().unwrap(),
+        ..Default::default()
+      },
+      World {
+        owner_id: user.user_id,
+        name: "Discworld".to_owned(),
+        slug: "discworld".to_owned(),
+        description: "A flat world on the back of a turtle, unlike the Earth".to_owned(),
+        created: "2020-03-03T12:00:00Z".parse().unwrap(),
+        ..Default::default()
+      },
+      World {
+        owner_id: user.user_id,
+        name: "Narnia".to_owned(),
+        slug: "narnia".to_owned(),
+        description: "Reached through a wardrobe, not a flat world".to_owned(),
+        created: "2020-03-02T12:00:00Z".parse().unwrap(),
+        ..Default::default()
+      },
+    ];
+
+    seed(&database, vec![&user, &worlds[0], &worlds[1], &worlds[2]]);
+
+    (user, worlds)
+  }
+
+  /// Extract the names of the worlds in a page of results
+  fn names(page: &Page<WorldEntity>) -> Vec<String> {
+    page.entries.iter().map(|w| w.data.name.clone()).collect()
+  }
+
+  fn no_filters() -> WorldFilters {
+    WorldFilters {
+      owner: None,
+      keyword: None,
+      visible_to: None,
+      tags: None,
+      starred_by: None,
+    }
+  }
+
+  fn sort(field: WorldSorts, direction: SortDirection) -> Vec<SortField<WorldSorts>> {
+    vec![SortField { field, direction }]
+  }
+
+  const ALL: Pagination = Pagination {
+    offset: 0,
+    limit: 10,
+  };
+
+  #[test]
+  fn test_get_unknown_world_by_id() {
+    let database = TestDatabaseWrapper::new();
+    seed_worlds(&database);
+
+    let world = database.wrapper.get_world_by_id(&Default::default());
+    assert_that(&world).is_none();
+  }
+
+  #[test]
+  fn test_get_known_world_by_id() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+
+    let world = database
+      .wrapper
+      .get_world_by_id(&WorldID::from_uuid(worlds[1].world_id));
+    assert_that(&world)
+      .is_some()
+      .is_equal_to(WorldEntity::from(worlds[1].clone()));
+  }
+
+  #[test]
+  fn test_get_known_world_by_slug() {
+    let database = TestDatabaseWrapper::new();
+    let (user, worlds) = seed_worlds(&database);
+
+    let world = database.wrapper.get_world_by_slug(
+      &UserID::from_uuid(user.user_id),
+      &"DiscWorld".parse().unwrap(),
+    );
+    assert_that(&world)
+      .is_some()
+      .is_equal_to(WorldEntity::from(worlds[1].clone()));
+  }
+
+  #[test]
+  fn test_get_world_by_slug_wrong_owner() {
+    let database = TestDatabaseWrapper::new();
+    seed_worlds(&database);
+
+    let world = database
+      .wrapper
+      .get_world_by_slug(&Default::default(), &"discworld".parse().unwrap());
+    assert_that(&world).is_none();
+  }
+
+  #[test]
+  fn test_create_world() {
+    let database = TestDatabaseWrapper::new();
+    let user: User = Default::default();
+    seed(&database, vec![&user]);
+
+    let world_data = WorldData {
+      owner: UserID::from_uuid(user.user_id),
+      name: "Middle Earth".to_owned(),
+      slug: "middle-earth".parse().unwrap(),
+      description: "Home of the Hobbits".to_owned(),
+      visibility: Visibility::Public,
+      forked_from: None,
+      tags: vec![],
+      cover: None,
+    };
+    let created = database.wrapper.create_world(world_data.clone());
+    assert_that(&created)
+      .is_ok()
+      .map(|world| &world.data)
+      .is_equal_to(&world_data);
+
+    let page = database.wrapper.search_worlds(no_filters(), vec![], ALL);
+    assert_that(&page.entries).is_equal_to(vec![created.unwrap()]);
+  }
+
+  #[test]
+  fn test_create_forked_world() {
+    let database = TestDatabaseWrapper::new();
+    let (user, worlds) = seed_worlds(&database);
+    let original = WorldEntity::from(worlds[0].clone());
+
+    let created = database.wrapper.create_world(WorldData {
+      slug: "forked-world".parse().unwrap(),
+      forked_from: Some(original.identity.id.clone()),
+      owner: UserID::from_uuid(user.user_id),
+      ..original.data
+    });
+    assert_that(&created)
+      .is_ok()
+      .map(|world| &world.data.forked_from)
+      .is_equal_to(Some(original.identity.id));
+
+    let created = created.unwrap();
+    let reloaded = database.wrapper.get_world_by_id(&created.identity.id);
+    assert_that(&reloaded).is_some().is_equal_to(created);
+  }
+
+  #[test]
+  fn test_create_world_duplicate_slug() {
+    let database = TestDatabaseWrapper::new();
+    let (user, _) = seed_worlds(&database);
+
+    let created = database.wrapper.create_world(WorldData {
+      owner: UserID::from_uuid(user.user_id),
+      name: "Another Middle Earth".to_owned(),
+      slug: "MIDDLE-EARTH".parse().unwrap(),
+      description: "".to_owned(),
+      visibility: Visibility::Public,
+      forked_from: None,
+      tags: vec![],
+      cover: None,
+    });
+    assert_that(&created)
+      .is_err()
+      .is_equal_to(PersistWorldError::DuplicateSlug);
+  }
+
+  #[test]
+  fn test_create_world_same_slug_different_owner() {
+    let database = TestDatabaseWrapper::new();
+    seed_worlds(&database);
+    let other_user = User {
+      username: "other".to_owned(),
+      email: "other@example.com".to_owned(),
+      ..Default::default()
+    };
+    seed(&database, vec![&other_user]);
+
+    let created = database.wrapper.create_world(WorldData {
+      owner: UserID::from_uuid(other_user.user_id),
+      name: "Middle Earth".to_owned(),
+      slug: "middle-earth".parse().unwrap(),
+      description: "".to_owned(),
+      visibility: Visibility::Public,
+      forked_from: None,
+      tags: vec![],
+      cover: None,
+    });
+    assert_that(&created).is_ok();
+  }
+
+  #[test]
+  fn test_update_world() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+
+    let world = WorldEntity::from(worlds[0].clone());
+    let updated = database.wrapper.update_world(WorldEntity {
+      data: WorldData {
+        name: "Arda".to_owned(),
+        slug: "arda".parse().unwrap(),
+        ..world.data.clone()
+      },
+      ..world.clone()
+    });
+    assert_that(&updated).is_ok();
+
+    let updated = updated.unwrap();
+    assert_that(&updated.identity.id).is_equal_to(&world.identity.id);
+    assert_that(&updated.identity.created).is_equal_to(&world.identity.created);
+    assert_that(&updated.identity.version).is_not_equal_to(&world.identity.version);
+    assert_that(&updated.data.name).is_equal_to("Arda".to_owned());
+    assert_that(&updated.data.slug).is_equal_to("arda".parse::<Slug>().unwrap());
+    assert_that(&updated.data.description).is_equal_to(&world.data.description);
+
+    let reloaded = database.wrapper.get_world_by_id(&world.identity.id);
+    assert_that(&reloaded).is_some().is_equal_to(updated);
+  }
+
+  #[test]
+  fn test_update_world_duplicate_slug() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+
+    let world = WorldEntity::from(worlds[0].clone());
+    let updated = database.wrapper.update_world(WorldEntity {
+      data: WorldData {
+        slug: "discworld".parse().unwrap(),
+        ..world.data.clone()
+      },
+      ..world
+    });
+    assert_that(&updated)
+      .is_err()
+      .is_equal_to(PersistWorldError::DuplicateSlug);
+  }
+
+  #[test]
+  fn test_update_world_unknown_world() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+
+    let world = WorldEntity::from(worlds[0].clone());
+    let updated = database.wrapper.update_world(WorldEntity {
+      identity: Identity {
+        id: Default::default(),
+        ..world.identity
+      },
+      ..world
+    });
+    assert_that(&updated)
+      .is_err()
+      .is_equal_to(PersistWorldError::WorldNotFound);
+  }
+
+  #[test]
+  fn test_update_world_wrong_version() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+
+    let world = WorldEntity::from(worlds[0].clone());
+    let updated = database.wrapper.update_world(WorldEntity {
+      identity: Identity {
+        version: Uuid::new_v4(),
+        ..world.identity.clone()
+      },
+      ..world.clone()
+    });
+    assert_that(&updated)
+      .is_err()
+      .is_equal_to(PersistWorldError::OptimisticLockFailure);
+
+    let reloaded = database.wrapper.get_world_by_id(&world.identity.id);
+    assert_that(&reloaded).is_some().is_equal_to(world);
+  }
+
+  /// Change the slug of the given world, returning the updated world
+  fn change_slug(database: &TestDatabaseWrapper, world: WorldEntity, slug: &str) -> WorldEntity {
+    database
+      .wrapper
+      .update_world(WorldEntity {
+        data: WorldData {
+          slug: slug.parse().unwrap(),
+          ..world.data
+        },
+        ..world
+      })
+      .unwrap()
+  }
+
+  #[test]
+  fn test_get_world_by_previous_slug() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+    let world = WorldEntity::from(worlds[0].clone());
+    let owner = world.data.owner.clone();
+
+    let renamed = change_slug(&database, world, "arda");
+    let renamed = change_slug(&database, renamed, "endor");
+
+    for slug in &["middle-earth", "Middle-Earth", "arda"] {
+      assert_that(
+        &database
+          .wrapper
+          .get_world_by_previous_slug(&owner, &slug.parse().unwrap()),
+      )
+      .is_some()
+      .is_equal_to(&renamed);
+    }
+    assert_that(
+      &database
+        .wrapper
+        .get_world_by_previous_slug(&owner, &"endor".parse().unwrap()),
+    )
+    .is_none();
+  }
+
+  #[test]
+  fn test_get_world_by_previous_slug_wrong_owner() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+    let world = WorldEntity::from(worlds[0].clone());
+
+    change_slug(&database, world, "arda");
+
+    assert_that(
+      &database
+        .wrapper
+        .get_world_by_previous_slug(&Default::default(), &"middle-earth".parse().unwrap()),
+    )
+    .is_none();
+  }
+
+  #[test]
+  fn test_get_world_by_previous_slug_reused() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+    let world = WorldEntity::from(worlds[0].clone());
+    let owner = world.data.owner.clone();
+
+    let renamed = change_slug(&database, world, "arda");
+    change_slug(&database, renamed, "middle-earth");
+
+    assert_that(
+      &database
+        .wrapper
+        .get_world_by_previous_slug(&owner, &"middle-earth".parse().unwrap()),
+    )
+    .is_none();
+  }
+
+  #[test]
+  fn test_get_world_by_previous_slug_used_by_new_world() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+    let world = WorldEntity::from(worlds[0].clone());
+    let owner = world.data.owner.clone();
+
+    change_slug(&database, world.clone(), "arda");
+    let new_world = database.wrapper.create_world(world.data).unwrap();
+    let new_world = change_slug(&database, new_world, "shire");
+
+    assert_that(
+      &database
+        .wrapper
+        .get_world_by_previous_slug(&owner, &"middle-earth".parse().unwrap()),
+    )
+    .is_some()
+    .is_equal_to(&new_world);
+  }
+
+  #[test]
+  fn test_get_trashed_world_by_previous_slug() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+    let world = WorldEntity::from(worlds[0].clone());
+    let owner = world.data.owner.clone();
+
+    let renamed = change_slug(&database, world, "arda");
+    database.wrapper.delete_world(&renamed.identity.id).unwrap();
+
+    assert_that(
+      &database
+        .wrapper
+        .get_world_by_previous_slug(&owner, &"middle-earth".parse().unwrap()),
+    )
+    .is_none();
+  }
+
+  #[test]
+  fn test_search_no_worlds() {
+    let database = TestDatabaseWrapper::new();
+
+    let page = database.wrapper.search_worlds(no_filters(), vec![], ALL);
+
+    assert_that(&page.total).is_equal_to(0);
+    assert_that(&page.offset).is_equal_to(0);
+    assert_that(&page.entries).is_empty();
+  }
+
+  #[test]
+  fn test_search_all_worlds() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+
+    let page = database.wrapper.search_worlds(
+      no_filters(),
+      sort(WorldSorts::Name, SortDirection::Natural),
+      ALL,
+    );
+
+    assert_that(&page.total).is_equal_to(3);
+    assert_that(&names(&page)).is_equal_to(vec![
+      "Discworld".to_owned(),
+      "Middle Earth".to_owned(),
+      "Narnia".to_owned(),
+    ]);
+    assert_that(&page.entries[0]).is_equal_to(WorldEntity::from(worlds[1].clone()));
+  }
+
+  #[test]
+  fn test_search_sort_name_descending() {
+    let database = TestDatabaseWrapper::new();
+    seed_worlds(&database);
+
+    let page = database.wrapper.search_worlds(
+      no_filters(),
+      sort(WorldSorts::Name, SortDirection::Descending),
+      ALL,
+    );
+
+    assert_that(&names(&page)).is_equal_to(vec![
+      "Narnia".to_owned(),
+      "Middle Earth".to_owned(),
+      "Discworld".to_owned(),
+    ]);
+  }
+
+  #[test]
+  fn test_search_sort_created_natural() {
+    let database = TestDatabaseWrapper::new();
+    seed_worlds(&database);
+
+    let page = database.wrapper.search_worlds(
+      no_filters(),
+      sort(WorldSorts::Created, SortDirection::Natural),
+      ALL,
+    );
+
+    assert_that(&names(&page)).is_equal_to(vec![
+      "Discworld".to_owned(),
+      "Narnia".to_owned(),
+      "Middle Earth".to_owned(),
+    ]);
+  }
+
+  #[test]
+  fn test_search_sort_created_ascending() {
+    let database = TestDatabaseWrapper::new();
+    seed_worlds(&database);
+
+    let page = database.wrapper.search_worlds(
+      no_filters(),
+      sort(WorldSorts::Created, SortDirection::Ascending),
+      ALL,
+    );
+
+    assert_that(&names(&page)).is_equal_to(vec![
+      "Middle Earth".to_owned(),
+      "Narnia".to_owned(),
+      "Discworld".to_owned(),
+    ]);
+  }
+
+  #[test]
+  fn test_search_sort_owner() {
+    let database = TestDatabaseWrapper::new();
+    let first_user = User {
+      username: "first".to_owned(),
+      email: "first@example.com".to_owned(),
+      display_name: "Alice".to_owned(),
+      ..Default::default()
+    };
+    let second_user = User {
+      username: "second".to_owned(),
+      email: "second@example.com".to_owned(),
+      display_name: "Bob".to_owned(),
+      ..Default::default()
+    };
+    let first_world = World {
+      owner_id: second_user.user_id,
+      name: "Bob's World".to_owned(),
+      slug: "bob".to_owned(),
+      ..Default::default()
+    };
+    let second_world = World {
+      owner_id: first_user.user_id,
+      name: "Alice's World".to_owned(),
+      slug: "alice".to_owned(),
+      ..Default::default()
+    };
+    seed(
+      &database,
+      vec![&first_user, &second_user, &first_world, &second_world],
+    );
+
+    let page = database.wrapper.search_worlds(
+      no_filters(),
+      sort(WorldSorts::Owner, SortDirection::Natural),
+      ALL,
+    );
+
+    assert_that(&names(&page))
+      .is_equal_to(vec!["Alice's World".to_owned(), "Bob's World".to_owned()]);
+  }
+
+  #[test]
+  fn test_search_sort_id() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+
+    let page = database.wrapper.search_worlds(
+      no_filters(),
+      sort(WorldSorts::Id, SortDirection::Descending),
+      ALL,
+    );
+
+    let mut expected: Vec<Uuid> = worlds.iter().map(|w| w.world_id).collect();
+    expected.sort();
+    expected.reverse();
+    let ids: Vec<WorldID> = page.entries.iter().map(|w| w.identity.id.clone()).collect();
+    assert_that(&ids).is_equal_to(
+      expected
+        .into_iter()
+        .map(WorldID::from_uuid)
+        .collect::<Vec<WorldID>>(),
+    );
+  }
+
+  #[test]
+  fn test_search_pagination() {
+    let database = TestDatabaseWrapper::new();
+    seed_worlds(&database);
+
+    let page = database.wrapper.search_worlds(
+      no_filters(),
+      sort(WorldSorts::Name, SortDirection::Natural),
+      Pagination {
+        offset: 1,
+        limit: 1,
+      },
+    );
+
+    assert_that(&page.total).is_equal_to(3);
+    assert_that(&page.offset).is_equal_to(1);
+    assert_that(&names(&page)).is_equal_to(vec!["Middle Earth".to_owned()]);
+  }
+
+  #[test]
+  fn test_search_pagination_past_end() {
+    let database = TestDatabaseWrapper::new();
+    seed_worlds(&database);
+
+    let page = database.wrapper.search_worlds(
+      no_filters(),
+      vec![],
+      Pagination {
+        offset: 10,
+        limit: 10,
+      },
+    );
+
+    assert_that(&page.total).is_equal_to(3);
+    assert_that(&page.entries).is_empty();
+  }
+
+  #[test]
+  fn test_search_by_owner() {
+    let database = TestDatabaseWrapper::new();
+    let (user, _) = seed_worlds(&database);
+    let other_user = User {
+      username: "other".to_owned(),
+      email: "other@example.com".to_owned(),
+      ..Default::default()
+    };
+    let other_world = World {
+      owner_id: other_user.user_id,
+      ..Default::default()
+    };
+    seed(&database, vec![&other_user, &other_world]);
+
+    let page = database.wrapper.search_worlds(
+      WorldFilters {
+        owner: Some(UserID::from_uuid(user.user_id)),
+        keyword: None,
+        visible_to: None,
+        tags: None,
+        starred_by: None,
+      },
+      vec![],
+      ALL,
+    );
+    assert_that(&page.total).is_equal_to(3);
+
+    let page = database.wrapper.search_worlds(
+      WorldFilters {
+        owner: Some(UserID::from_uuid(other_user.user_id)),
+        keyword: None,
+        visible_to: None,
+        tags: None,
+        starred_by: None,
+      },
+      vec![],
+      ALL,
+    );
+    assert_that(&page.total).is_equal_to(1);
+    assert_that(&page.entries[0]).is_equal_to(WorldEntity::from(other_world));
+  }
+
+  #[test]
+  fn test_search_by_keyword() {
+    let database = TestDatabaseWrapper::new();
+    seed_worlds(&database);
+
+    let page = database.wrapper.search_worlds(
+      WorldFilters {
+        owner: None,
+        keyword: Some("FLAT".to_owned()),
+        visible_to: None,
+        tags: None,
+        starred_by: None,
+      },
+      sort(WorldSorts::Name, SortDirection::Natural),
+      ALL,
+    );
+
+    assert_that(&page.total).is_equal_to(2);
+    assert_that(&names(&page)).is_equal_to(vec!["Discworld".to_owned(), "Narnia".to_owned()]);
+  }
+
+  /// Seed worlds with a variety of tags, owned by a new user
+  fn seed_tagged_worlds(database: &TestDatabaseWrapper) -> User {
+    let user = User {
+      username: "tagged".to_owned(),
+      email: "tagged@example.com".to_owned(),
+      ..Default::default()
+    };
+    let worlds: Vec<World> = vec![
+      ("Middle Earth", vec!["fantasy", "tolkien"]),
+      ("Discworld", vec!["fantasy", "comedy"]),
+      ("Arrakis", vec!["sci-fi"]),
+    ]
+    .into_iter()
+    .map(|(name, tags)| World {
+      owner_id: user.user_id,
+      name: name.to_owned(),
+      slug: name.to_lowercase().replace(' ', "-"),
+      tags: tags.into_iter().map(|tag| tag.to_owned()).collect(),
+      ..Default::default()
+    })
+    .collect();
+    seed(&database, vec![&user, &worlds[0], &worlds[1], &worlds[2]]);
+
+    user
+  }
+
+  #[test]
+  fn test_search_by_any_tags() {
+    let database = TestDatabaseWrapper::new();
+    seed_tagged_worlds(&database);
+
+    let page = database.wrapper.search_worlds(
+      WorldFilters {
+        tags: Some(TagFilter::Any(vec![
+          "comedy".parse().unwrap(),
+          "sci-fi".parse().unwrap(),
+        ])),
+        ..no_filters()
+      },
+      sort(WorldSorts::Name, SortDirection::Natural),
+      ALL,
+    );
+
+    assert_that(&page.total).is_equal_to(2);
+    assert_that(&names(&page)).is_equal_to(vec!["Arrakis".to_owned(), "Discworld".to_owned()]);
+  }
+
+  #[test]
+  fn test_search_by_all_tags() {
+    let database = TestDatabaseWrapper::new();
+    seed_tagged_worlds(&database);
+
+    let page = database.wrapper.search_worlds(
+      WorldFilters {
+        tags: Some(TagFilter::All(vec![
+          "fantasy".parse().unwrap(),
+          "tolkien".parse().unwrap(),
+        ])),
+        ..no_filters()
+      },
+      sort(WorldSorts::Name, SortDirection::Natural),
+      ALL,
+    );
+
+    assert_that(&page.total).is_equal_to(1);
+    assert_that(&names(&page)).is_equal_to(vec!["Middle Earth".to_owned()]);
+  }
+
+  #[test]
+  fn test_count_tags_facet() {
+    let database = TestDatabaseWrapper::new();
+    seed_tagged_worlds(&database);
+
+    let counts = database
+      .wrapper
+      .count_world_facet(no_filters(), WorldFacet::Tags, 3);
+
+    assert_that(&counts).is_equal_to(vec![
+      FacetCount {
+        value: "fantasy".to_owned(),
+        count: 2,
+      },
+      FacetCount {
+        value: "comedy".to_owned(),
+        count: 1,
+      },
+      FacetCount {
+        value: "sci-fi".to_owned(),
+        count: 1,
+      },
+    ]);
+  }
+
+  #[test]
+  fn test_count_owners_facet_with_filters() {
+    let database = TestDatabaseWrapper::new();
+    let (user, _) = seed_worlds(&database);
+    let tagged_user = seed_tagged_worlds(&database);
+
+    let counts = database.wrapper.count_world_facet(
+      WorldFilters {
+        keyword: Some("world".to_owned()),
+        ..no_filters()
+      },
+      WorldFacet::Owners,
+      10,
+    );
+
+    // Narnia and Discworld mention a flat world, and all of the tagged worlds are test worlds
+    assert_that(&counts).is_equal_to(vec![
+      FacetCount {
+        value: tagged_user.user_id.to_string(),
+        count: 3,
+      },
+      FacetCount {
+        value: user.user_id.to_string(),
+        count: 2,
+      },
+    ]);
+  }
+
+  #[test]
+  fn test_search_by_keyword_relevance() {
+    let database = TestDatabaseWrapper::new();
+    seed_worlds(&database);
+
+    let page = database.wrapper.search_worlds(
+      WorldFilters {
+        owner: None,
+        keyword: Some("earth".to_owned()),
+        visible_to: None,
+        tags: None,
+        starred_by: None,
+      },
+      sort(WorldSorts::Relevance, SortDirection::Natural),
+      ALL,
+    );
+
+    assert_that(&names(&page)).is_equal_to(vec!["Middle Earth".to_owned(), "Discworld".to_owned()]);
+  }
+
+  #[test]
+  fn test_search_by_keyword_prefix() {
+    let database = TestDatabaseWrapper::new();
+    seed_worlds(&database);
+
+    let page = database.wrapper.search_worlds(
+      WorldFilters {
+        owner: None,
+        keyword: Some("hobb".to_owned()),
+        visible_to: None,
+        tags: None,
+        starred_by: None,
+      },
+      vec![],
+      ALL,
+    );
+
+    assert_that(&names(&page)).is_equal_to(vec!["Middle Earth".to_owned()]);
+  }
+
+  #[test]
+  fn test_search_by_keyword_stemmed() {
+    let database = TestDatabaseWrapper::new();
+    seed_worlds(&database);
+
+    let page = database.wrapper.search_worlds(
+      WorldFilters {
+        owner: None,
+        keyword: Some("turtles".to_owned()),
+        visible_to: None,
+        tags: None,
+        starred_by: None,
+      },
+      vec![],
+      ALL,
+    );
+
+    assert_that(&names(&page)).is_equal_to(vec!["Discworld".to_owned()]);
+  }
+
+  #[test]
+  fn test_search_by_keyword_phrase() {
+    let database = TestDatabaseWrapper::new();
+    seed_worlds(&database);
+
+    let page = database.wrapper.search_worlds(
+      WorldFilters {
+        owner: None,
+        keyword: Some("\"flat world\"".to_owned()),
+        visible_to: None,
+        tags: None,
+        starred_by: None,
+      },
+      sort(WorldSorts::Name, SortDirection::Natural),
+      ALL,
+    );
+    assert_that(&names(&page)).is_equal_to(vec!["Discworld".to_owned(), "Narnia".to_owned()]);
+
+    let page = database.wrapper.search_worlds(
+      WorldFilters {
+        owner: None,
+        keyword: Some("\"world flat\"".to_owned()),
+        visible_to: None,
+        tags: None,
+        starred_by: None,
+      },
+      vec![],
+      ALL,
+    );
+    assert_that(&page.total).is_equal_to(0);
+  }
+
+  #[test]
+  fn test_search_by_keyword_excluded() {
+    let database = TestDatabaseWrapper::new();
+    seed_worlds(&database);
+
+    let page = database.wrapper.search_worlds(
+      WorldFilters {
+        owner: None,
+        keyword: Some("flat -turtle".to_owned()),
+        visible_to: None,
+        tags: None,
+        starred_by: None,
+      },
+      vec![],
+      ALL,
+    );
+
+    assert_that(&names(&page)).is_equal_to(vec!["Narnia".to_owned()]);
+  }
+
+  #[test]
+  fn test_search_by_keyword_either() {
+    let database = TestDatabaseWrapper::new();
+    seed_worlds(&database);
+
+    // Either of the words joined by OR has to match, along with the word after them
+    let page = database.wrapper.search_worlds(
+      WorldFilters {
+        owner: None,
+        keyword: Some("earth OR wardrobe flat".to_owned()),
+        visible_to: None,
+        tags: None,
+        starred_by: None,
+      },
+      sort(WorldSorts::Name, SortDirection::Natural),
+      ALL,
+    );
+
+    assert_that(&names(&page)).is_equal_to(vec!["Discworld".to_owned(), "Narnia".to_owned()]);
+  }
+
+  #[test]
+  fn test_search_by_keyword_stopwords() {
+    let database = TestDatabaseWrapper::new();
+    seed_worlds(&database);
+
+    // Stopwords are never indexed, so a keyword made up only of them doesn't filter anything
+    let page = database.wrapper.search_worlds(
+      WorldFilters {
+        owner: None,
+        keyword: Some("the".to_owned()),
+        visible_to: None,
+        tags: None,
+        starred_by: None,
+      },
+      sort(WorldSorts::Relevance, SortDirection::Natural),
+      ALL,
+    );
+
+    assert_that(&page.total).is_equal_to(3);
+  }
+
+  #[test]
+  fn test_search_by_unknown_owner() {
+    let database = TestDatabaseWrapper::new();
+    seed_worlds(&database);
+
+    let page = database.wrapper.search_worlds(
+      WorldFilters {
+        owner: Some(Default::default()),
+        keyword: None,
+        visible_to: None,
+        tags: None,
+        starred_by: None,
+      },
+      vec![],
+      ALL,
+    );
+
+    assert_that(&page.total).is_equal_to(0);
+    assert_that(&page.entries).is_empty();
+  }
+
+  #[test]
+  fn test_search_excludes_trashed_worlds() {
+    let database = TestDatabaseWrapper::new();
+    let (user, _) = seed_worlds(&database);
+    let trashed = World {
+      owner_id: user.user_id,
+      name: "Atlantis".to_owned(),
+      slug: "atlantis".to_owned(),
+      deleted: Some(Utc::now()),
+      ..Default::default()
+    };
+    seed(&database, vec![&trashed]);
+
+    let page = database.wrapper.search_worlds(
+      no_filters(),
+      sort(WorldSorts::Name, SortDirection::Ascending),
+      ALL,
+    );
+
+    assert_that(&page.total).is_equal_to(3);
+    assert_that(&names(&page)).is_equal_to(vec![
+      "Discworld".to_owned(),
+      "Middle Earth".to_owned(),
+      "Narnia".to_owned(),
+    ]);
+  }
+
+  #[test]
+  fn test_delete_world() {
+    let database = TestDatabaseWrapper::new();
+    let (user, worlds) = seed_worlds(&database);
+    let world = WorldEntity::from(worlds[0].clone());
+
+    let deleted = database.wrapper.delete_world(&world.identity.id);
+    assert_that(&deleted).is_ok();
+
+    assert_that(&database.wrapper.get_world_by_id(&world.identity.id)).is_none();
+    assert_that(
+      &database
+        .wrapper
+        .get_world_by_slug(&world.data.owner, &world.data.slug),
+    )
+    .is_none();
+
+    let trash = database
+      .wrapper
+      .list_trashed_worlds(&UserID::from_uuid(user.user_id), ALL);
+    assert_that(&trash.total).is_equal_to(1);
+    assert_that(&trash.entries[0].world).is_equal_to(&world);
+  }
+
+  #[test]
+  fn test_delete_unknown_world() {
+    let database = TestDatabaseWrapper::new();
+    seed_worlds(&database);
+
+    let deleted = database.wrapper.delete_world(&Default::default());
+    assert_that(&deleted)
+      .is_err()
+      .is_equal_to(PersistWorldError::WorldNotFound);
+  }
+
+  #[test]
+  fn test_delete_trashed_world() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+    let world = WorldEntity::from(worlds[0].clone());
+
+    database.wrapper.delete_world(&world.identity.id).unwrap();
+    let deleted = database.wrapper.delete_world(&world.identity.id);
+    assert_that(&deleted)
+      .is_err()
+      .is_equal_to(PersistWorldError::WorldNotFound);
+  }
+
+  #[test]
+  fn test_update_trashed_world() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+    let world = WorldEntity::from(worlds[0].clone());
+
+    database.wrapper.delete_world(&world.identity.id).unwrap();
+    let updated = database.wrapper.update_world(world);
+    assert_that(&updated)
+      .is_err()
+      .is_equal_to(PersistWorldError::WorldNotFound);
+  }
+
+  #[test]
+  fn test_list_trashed_worlds() {
+    let database = TestDatabaseWrapper::new();
+    let (user, _) = seed_worlds(&database);
+    let other_user = User {
+      username: "otheruser".to_owned(),
+      email: "other@example.com".to_owned(),
+      ..Default::default()
+    };
+    let trashed = vec![
+      World {
+        owner_id: user.user_id,
+        name: "Atlantis".to_owned(),
+        slug: "atlantis".to_owned(),
+        deleted: Some("2020-03-01T12:00:00Z".parse().unwrap()),
+        ..Default::default()
+      },
+      World {
+        owner_id: user.user_id,
+        name: "Lemuria".to_owned(),
+        slug: "lemuria".to_owned(),
+        deleted: Some("2020-03-02T12:00:00Z".parse().unwrap()),
+        ..Default::default()
+      },
+      World {
+        owner_id: other_user.user_id,
+        name: "Mu".to_owned(),
+        slug: "mu".to_owned(),
+        deleted: Some("2020-03-02T12:00:00Z".parse().unwrap()),
+        ..Default::default()
+      },
+    ];
+    seed(
+      &database,
+      vec![&other_user, &trashed[0], &trashed[1], &trashed[2]],
+    );
+
+    let trash = database
+      .wrapper
+      .list_trashed_worlds(&UserID::from_uuid(user.user_id), ALL);
+
+    assert_that(&trash.total).is_equal_to(2);
+    assert_that(&trash.entries).is_equal_to(vec![
+      TrashedWorld {
+        world: WorldEntity::from(trashed[1].clone()),
+        deleted: "2020-03-02T12:00:00Z".parse().unwrap(),
+      },
+      TrashedWorld {
+        world: WorldEntity::from(trashed[0].clone()),
+        deleted: "2020-03-01T12:00:00Z".parse().unwrap(),
+      },
+    ]);
+  }
+
+  #[test]
+  fn test_restore_world() {
+    let database = TestDatabaseWrapper::new();
+    let (user, worlds) = seed_worlds(&database);
+    let world = WorldEntity::from(worlds[0].clone());
+    let owner = UserID::from_uuid(user.user_id);
+
+    database.wrapper.delete_world(&world.identity.id).unwrap();
+    let restored = database.wrapper.restore_world(&owner, &world.identity.id);
+    assert_that(&restored).is_ok();
+
+    let restored = restored.unwrap();
+    assert_that(&restored.identity.id).is_equal_to(&world.identity.id);
+    assert_that(&restored.identity.version).is_not_equal_to(&world.identity.version);
+    assert_that(&restored.data).is_equal_to(&world.data);
+
+    assert_that(&database.wrapper.get_world_by_id(&world.identity.id))
+      .is_some()
+      .is_equal_to(restored);
+    assert_that(&database.wrapper.list_trashed_worlds(&owner, ALL).total).is_equal_to(0);
+  }
+
+  #[test]
+  fn test_restore_world_wrong_owner() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+    let world = WorldEntity::from(worlds[0].clone());
+
+    database.wrapper.delete_world(&world.identity.id).unwrap();
+    let restored = database
+      .wrapper
+      .restore_world(&Default::default(), &world.identity.id);
+    assert_that(&restored)
+      .is_err()
+      .is_equal_to(PersistWorldError::WorldNotFound);
+  }
+
+  #[test]
+  fn test_restore_world_not_trashed() {
+    let database = TestDatabaseWrapper::new();
+    let (user, worlds) = seed_worlds(&database);
+    let world = WorldEntity::from(worlds[0].clone());
+
+    let restored = database
+      .wrapper
+      .restore_world(&UserID::from_uuid(user.user_id), &world.identity.id);
+    assert_that(&restored)
+      .is_err()
+      .is_equal_to(PersistWorldError::WorldNotFound);
+  }
+
+  #[test]
+  fn test_restore_world_duplicate_slug() {
+    let database = TestDatabaseWrapper::new();
+    let (user, worlds) = seed_worlds(&database);
+    let world = WorldEntity::from(worlds[0].clone());
+    let owner = UserID::from_uuid(user.user_id);
+
+    database.wrapper.delete_world(&world.identity.id).unwrap();
+    database.wrapper.create_world(world.data.clone()).unwrap();
+
+    let restored = database.wrapper.restore_world(&owner, &world.identity.id);
+    assert_that(&restored)
+      .is_err()
+      .is_equal_to(PersistWorldError::DuplicateSlug);
+  }
+
+  #[test]
+  fn test_purge_trashed_worlds() {
+    let database = TestDatabaseWrapper::new();
+    let (user, _) = seed_worlds(&database);
+    let trashed = vec![
+      World {
+        owner_id: user.user_id,
+        slug: "old".to_owned(),
+        deleted: Some("2020-03-01T12:00:00Z".parse().unwrap()),
+        ..Default::default()
+      },
+      World {
+        owner_id: user.user_id,
+        slug: "recent".to_owned(),
+        deleted: Some("2020-03-10T12:00:00Z".parse().unwrap()),
+        ..Default::default()
+      },
+    ];
+    seed(&database, vec![&trashed[0], &trashed[1]]);
+
+    let purged = database
+      .wrapper
+      .purge_trashed_worlds("2020-03-05T12:00:00Z".parse().unwrap());
+    assert_that(&purged)
+      .is_ok()
+      .is_equal_to(vec![WorldID::from_uuid(trashed[0].world_id)]);
+
+    let trash = database
+      .wrapper
+      .list_trashed_worlds(&UserID::from_uuid(user.user_id), ALL);
+    assert_that(&trash.entries).is_equal_to(vec![TrashedWorld {
+      world: WorldEntity::from(trashed[1].clone()),
+      deleted: "2020-03-10T12:00:00Z".parse().unwrap(),
+    }]);
+
+    let page = database.wrapper.search_worlds(no_filters(), vec![], ALL);
+    assert_that(&page.total).is_equal_to(3);
+  }
+
+  #[test]
+  fn test_purge_world() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+    let world_id = WorldID::from_uuid(worlds[1].world_id);
+
+    let purged = database.wrapper.purge_world(&world_id);
+    assert_that(&purged).is_ok();
+
+    let loaded = database.wrapper.get_world_by_id(&world_id);
+    assert_that(&loaded).is_none();
+
+    let loaded = database
+      .wrapper
+      .get_world_by_id(&WorldID::from_uuid(worlds[0].world_id));
+    assert_that(&loaded).is_some();
+
+    let purged = database.wrapper.purge_world(&world_id);
+    assert_that(&purged)
+      .is_err()
+      .is_equal_to(PersistWorldError::WorldNotFound);
+  }
+
+  #[test]
+  fn test_get_world_owner() {
+    let database = TestDatabaseWrapper::new();
+    let (user, _) = seed_worlds(&database);
+    let trashed = World {
+      owner_id: user.user_id,
+      name: "Atlantis".to_owned(),
+      slug: "atlantis".to_owned(),
+      deleted: Some(Utc::now()),
+      ..Default::default()
+    };
+    seed(&database, vec![&trashed]);
+
+    let owner = database
+      .wrapper
+      .get_world_owner(&WorldID::from_uuid(trashed.world_id));
+    assert_that(&owner).is_equal_to(Some(UserID::from_uuid(user.user_id)));
+
+    let unknown = database.wrapper.get_world_owner(&Default::default());
+    assert_that(&unknown).is_none();
+  }
+
+  #[test]
+  fn test_list_owned_world_ids() {
+    let database = TestDatabaseWrapper::new();
+    let (user, worlds) = seed_worlds(&database);
+    let trashed = World {
+      owner_id: user.user_id,
+      name: "Atlantis".to_owned(),
+      slug: "atlantis".to_owned(),
+      deleted: Some(Utc::now()),
+      ..Default::default()
+    };
+    let other_user = seed_member(&database);
+    let other_world = World {
+      owner_id: other_user.user_id,
+      name: "Oz".to_owned(),
+      slug: "oz".to_owned(),
+      ..Default::default()
+    };
+    seed(&database, vec![&trashed, &other_world]);
+
+    let mut expected: Vec<WorldID> = worlds
+      .iter()
+      .chain(vec![&trashed])
+      .map(|world| WorldID::from_uuid(world.world_id))
+      .collect();
+    expected.sort_by_key(|world_id| world_id.to_string());
+
+    let owned = database
+      .wrapper
+      .list_owned_world_ids(&UserID::from_uuid(user.user_id));
+    assert_that(&owned).is_equal_to(expected);
+  }
+
+  /// Seed one world of each visibility level, owned by a new user
+  fn seed_visibility_worlds(database: &TestDatabaseWrapper) -> User {
+    let user = User {
+      username: "visibility".to_owned(),
+      email: "visibility@example.com".to_owned(),
+      ..Default::default()
+    };
+    let worlds: Vec<World> = ["public", "unlisted", "private"]
+      .iter()
+      .map(|&visibility| World {
+        owner_id: user.user_id,
+        name: visibility.to_owned(),
+        slug: visibility.to_owned(),
+        visibility: visibility.to_owned(),
+        ..Default::default()
+      })
+      .collect();
+    seed(&database, vec![&user, &worlds[0], &worlds[1], &worlds[2]]);
+
+    user
+  }
+
+  #[test]
+  fn test_search_anonymous_only_public() {
+    let database = TestDatabaseWrapper::new();
+    seed_visibility_worlds(&database);
+
+    let page = database.wrapper.search_worlds(no_filters(), vec![], ALL);
+
+    assert_that(&names(&page)).is_equal_to(vec!["public".to_owned()]);
+  }
+
+  #[test]
+  fn test_search_visible_to_owner() {
+    let database = TestDatabaseWrapper::new();
+    let user = seed_visibility_worlds(&database);
+
+    let page = database.wrapper.search_worlds(
+      WorldFilters {
+        owner: None,
+        keyword: None,
+        visible_to: Some(UserID::from_uuid(user.user_id)),
+        tags: None,
+        starred_by: None,
+      },
+      sort(WorldSorts::Name, SortDirection::Ascending),
+      ALL,
+    );
+
+    assert_that(&names(&page)).is_equal_to(vec![
+      "private".to_owned(),
+      "public".to_owned(),
+      "unlisted".to_owned(),
+    ]);
+  }
+
+  #[test]
+  fn test_search_visible_to_other_user() {
+    let database = TestDatabaseWrapper::new();
+    seed_visibility_worlds(&database);
+
+    let page = database.wrapper.search_worlds(
+      WorldFilters {
+        owner: None,
+        keyword: None,
+        visible_to: Some(Default::default()),
+        tags: None,
+        starred_by: None,
+      },
+      vec![],
+      ALL,
+    );
+
+    assert_that(&names(&page)).is_equal_to(vec!["public".to_owned()]);
+  }
+
+  #[test]
+  fn test_update_world_visibility() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+    let world = WorldEntity::from(worlds[0].clone());
+
+    let updated = database.wrapper.update_world(WorldEntity {
+      data: WorldData {
+        visibility: Visibility::Private,
+        ..world.data.clone()
+      },
+      ..world
+    });
+    assert_that(&updated)
+      .is_ok()
+      .map(|world| &world.data.visibility)
+      .is_equal_to(Visibility::Private);
+
+    let reloaded = database
+      .wrapper
+      .get_world_by_id(&WorldID::from_uuid(worlds[0].world_id));
+    assert_that(&reloaded)
+      .is_some()
+      .is_equal_to(updated.unwrap());
+  }
+
+  /// Seed a second user that can be made a member of the standard worlds
+  fn seed_member(database: &TestDatabaseWrapper) -> User {
+    let user = User {
+      username: "member".to_owned(),
+      email: "member@example.com".to_owned(),
+      ..Default::default()
+    };
+    seed(&database, vec![&user]);
+
+    user
+  }
+
+  #[test]
+  fn test_create_world_member() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+    let member = seed_member(&database);
+    let world_id = WorldID::from_uuid(worlds[0].world_id);
+    let user_id = UserID::from_uuid(member.user_id);
+
+    let created = database
+      .wrapper
+      .create_world_member(&world_id, &user_id, WorldRole::Editor);
+    assert_that(&created)
+      .is_ok()
+      .map(|member| &member.role)
+      .is_equal_to(WorldRole::Editor);
+
+    let loaded = database.wrapper.get_world_member(&world_id, &user_id);
+    assert_that(&loaded).is_some().is_equal_to(created.unwrap());
+  }
+
+  #[test]
+  fn test_create_duplicate_world_member() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+    let member = seed_member(&database);
+    seed(
+      &database,
+      vec![&TestWorldMember {
+        world_id: worlds[0].world_id,
+        user_id: member.user_id,
+        ..Default::default()
+      }],
+    );
+
+    let created = database.wrapper.create_world_member(
+      &WorldID::from_uuid(worlds[0].world_id),
+      &UserID::from_uuid(member.user_id),
+      WorldRole::Editor,
+    );
+    assert_that(&created)
+      .is_err()
+      .is_equal_to(PersistWorldError::DuplicateMember);
+  }
+
+  #[test]
+  fn test_create_world_member_unknown_user() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+
+    let created = database.wrapper.create_world_member(
+      &WorldID::from_uuid(worlds[0].world_id),
+      &Default::default(),
+      WorldRole::Editor,
+    );
+    assert_that(&created)
+      .is_err()
+      .is_equal_to(PersistWorldError::UserNotFound);
+  }
+
+  #[test]
+  fn test_get_unknown_world_member() {
+    let database = TestDatabaseWrapper::new();
+    let (user, worlds) = seed_worlds(&database);
+
+    let loaded = database.wrapper.get_world_member(
+      &WorldID::from_uuid(worlds[0].world_id),
+      &UserID::from_uuid(user.user_id),
+    );
+    assert_that(&loaded).is_none();
+  }
+
+  #[test]
+  fn test_list_world_members() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+    let member = seed_member(&database);
+    let other_member = User {
+      username: "other".to_owned(),
+      email: "other@example.com".to_owned(),
+      ..Default::default()
+    };
+    seed(&database, vec![&other_member]);
+    seed(
+      &database,
+      vec![
+        &TestWorldMember {
+          world_id: worlds[0].world_id,
+          user_id: member.user_id,
+          role: "editor".to_owned(),
+          created: "2020-03-01T12:00:00Z".parse().unwrap(),
+          ..Default::default()
+        },
+        &TestWorldMember {
+          world_id: worlds[0].world_id,
+          user_id: other_member.user_id,
+          role: "viewer".to_owned(),
+          created: "2020-03-02T12:00:00Z".parse().unwrap(),
+          ..Default::default()
+        },
+        &TestWorldMember {
+          world_id: worlds[1].world_id,
+          user_id: other_member.user_id,
+          ..Default::default()
+        },
+      ],
+    );
+
+    let members = database
+      .wrapper
+      .list_world_members(&WorldID::from_uuid(worlds[0].world_id));
+    let members: Vec<(UserID, WorldRole)> = members
+      .into_iter()
+      .map(|member| (member.user, member.role))
+      .collect();
+    assert_that(&members).is_equal_to(vec![
+      (UserID::from_uuid(member.user_id), WorldRole::Editor),
+      (UserID::from_uuid(other_member.user_id), WorldRole::Viewer),
+    ]);
+  }
+
+  #[test]
+  fn test_update_world_member() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+    let member = seed_member(&database);
+    seed(
+      &database,
+      vec![&TestWorldMember {
+        world_id: worlds[0].world_id,
+        user_id: member.user_id,
+        ..Default::default()
+      }],
+    );
+
+    let updated = database.wrapper.update_world_member(
+      &WorldID::from_uuid(worlds[0].world_id),
+      &UserID::from_uuid(member.user_id),
+      WorldRole::Owner,
+    );
+    assert_that(&updated)
+      .is_ok()
+      .map(|member| &member.role)
+      .is_equal_to(WorldRole::Owner);
+  }
+
+  #[test]
+  fn test_update_unknown_world_member() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+    let member = seed_member(&database);
+
+    let updated = database.wrapper.update_world_member(
+      &WorldID::from_uuid(worlds[0].world_id),
+      &UserID::from_uuid(member.user_id),
+      WorldRole::Owner,
+    );
+    assert_that(&updated)
+      .is_err()
+      .is_equal_to(PersistWorldError::MemberNotFound);
+  }
+
+  #[test]
+  fn test_delete_world_member() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+    let member = seed_member(&database);
+    seed(
+      &database,
+      vec![&TestWorldMember {
+        world_id: worlds[0].world_id,
+        user_id: member.user_id,
+        ..Default::default()
+      }],
+    );
+    let world_id = WorldID::from_uuid(worlds[0].world_id);
+    let user_id = UserID::from_uuid(member.user_id);
+
+    let deleted = database.wrapper.delete_world_member(&world_id, &user_id);
+    assert_that(&deleted).is_ok();
+
+    let loaded = database.wrapper.get_world_member(&world_id, &user_id);
+    assert_that(&loaded).is_none();
+
+    let deleted_again = database.wrapper.delete_world_member(&world_id, &user_id);
+    assert_that(&deleted_again)
+      .is_err()
+      .is_equal_to(PersistWorldError::MemberNotFound);
+  }
+
+  #[test]
+  fn test_search_visible_to_member() {
+    let database = TestDatabaseWrapper::new();
+    let owner = seed_visibility_worlds(&database);
+    let member = seed_member(&database);
+    let private_world = database
+      .wrapper
+      .get_world_by_slug(
+        &UserID::from_uuid(owner.user_id),
+        &"private".parse().unwrap(),
+      )
+      .unwrap();
+    database
+      .wrapper
+      .create_world_member(
+        &private_world.identity.id,
+        &UserID::from_uuid(member.user_id),
+        WorldRole::Viewer,
+      )
+      .unwrap();
+
+    let page = database.wrapper.search_worlds(
+      WorldFilters {
+        owner: None,
+        keyword: None,
+        visible_to: Some(UserID::from_uuid(member.user_id)),
+        tags: None,
+        starred_by: None,
+      },
+      sort(WorldSorts::Name, SortDirection::Ascending),
+      ALL,
+    );
+
+    assert_that(&names(&page)).is_equal_to(vec!["private".to_owned(), "public".to_owned()]);
+  }
+
+  #[test]
+  fn test_save_world_transfer() {
+    let database = TestDatabaseWrapper::new();
+    let (owner, worlds) = seed_worlds(&database);
+    let recipient = seed_member(&database);
+    let world_id = WorldID::from_uuid(worlds[0].world_id);
+
+    let saved = database.wrapper.save_world_transfer(
+      &world_id,
+      &UserID::from_uuid(owner.user_id),
+      &UserID::from_uuid(recipient.user_id),
+    );
+    assert_that(&saved)
+      .is_ok()
+      .map(|transfer| &transfer.to)
+      .is_equal_to(UserID::from_uuid(recipient.user_id));
+
+    let loaded = database.wrapper.get_world_transfer(&world_id);
+    assert_that(&loaded).is_some().is_equal_to(saved.unwrap());
+  }
+
+  #[test]
+  fn test_save_world_transfer_replaces_previous() {
+    let database = TestDatabaseWrapper::new();
+    let (owner, worlds) = seed_worlds(&database);
+    let recipient = seed_member(&database);
+    let other_recipient = User {
+      username: "other".to_owned(),
+      email: "other@example.com".to_owned(),
+      ..Default::default()
+    };
+    seed(&database, vec![&other_recipient]);
+    let world_id = WorldID::from_uuid(worlds[0].world_id);
+    let owner_id = UserID::from_uuid(owner.user_id);
+
+    database
+      .wrapper
+      .save_world_transfer(&world_id, &owner_id, &UserID::from_uuid(recipient.user_id))
+      .unwrap();
+    database
+      .wrapper
+      .save_world_transfer(
+        &world_id,
+        &owner_id,
+        &UserID::from_uuid(other_recipient.user_id),
+      )
+      .unwrap();
+
+    assert_that(
+      &database
+        .wrapper
+        .list_world_transfers_to(&UserID::from_uuid(recipient.user_id)),
+    )
+    .is_empty();
+    assert_that(
+      &database
+        .wrapper
+        .list_world_transfers_to(&UserID::from_uuid(other_recipient.user_id)),
+    )
+    .has_length(1);
+  }
+
+  #[test]
+  fn test_save_world_transfer_unknown_user() {
+    let database = TestDatabaseWrapper::new();
+    let (owner, worlds) = seed_worlds(&database);
+
+    let saved = database.wrapper.save_world_transfer(
+      &WorldID::from_uuid(worlds[0].world_id),
+      &UserID::from_uuid(owner.user_id),
+      &Default::default(),
+    );
+    assert_that(&saved)
+      .is_err()
+      .is_equal_to(PersistWorldError::UserNotFound);
+  }
+
+  #[test]
+  fn test_list_world_transfers_to() {
+    let database = TestDatabaseWrapper::new();
+    let (owner, worlds) = seed_worlds(&database);
+    let recipient = seed_member(&database);
+    let owner_id = UserID::from_uuid(owner.user_id);
+    let recipient_id = UserID::from_uuid(recipient.user_id);
+
+    for world in &worlds[0..2] {
+      database
+        .wrapper
+        .save_world_transfer(
+          &WorldID::from_uuid(world.world_id),
+          &owner_id,
+          &recipient_id,
+        )
+        .unwrap();
+    }
+
+    let transfers: Vec<WorldID> = database
+      .wrapper
+      .list_world_transfers_to(&recipient_id)
+      .into_iter()
+      .map(|transfer| transfer.world)
+      .collect();
+    assert_that(&transfers).is_equal_to(vec![
+      WorldID::from_uuid(worlds[0].world_id),
+      WorldID::from_uuid(worlds[1].world_id),
+    ]);
+    assert_that(&database.wrapper.list_world_transfers_to(&owner_id)).is_empty();
+  }
+
+  #[test]
+  fn test_delete_world_transfer() {
+    let database = TestDatabaseWrapper::new();
+    let (owner, worlds) = seed_worlds(&database);
+    let recipient = seed_member(&database);
+    let world_id = WorldID::from_uuid(worlds[0].world_id);
+    database
+      .wrapper
+      .save_world_transfer(
+        &world_id,
+        &UserID::from_uuid(owner.user_id),
+        &UserID::from_uuid(recipient.user_id),
+      )
+      .unwrap();
+
+    let deleted = database.wrapper.delete_world_transfer(&world_id);
+    assert_that(&deleted).is_ok();
+
+    let loaded = database.wrapper.get_world_transfer(&world_id);
+    assert_that(&loaded).is_none();
+
+    let deleted_again = database.wrapper.delete_world_transfer(&world_id);
+    assert_that(&deleted_again)
+      .is_err()
+      .is_equal_to(PersistWorldError::TransferNotFound);
+  }
+
+  #[test]
+  fn test_create_world_records_revision() {
+    let database = TestDatabaseWrapper::new();
+    let user: User = Default::default();
+    seed(&database, vec![&user]);
+
+    let created = database
+      .wrapper
+      .create_world(WorldData {
+        owner: UserID::from_uuid(user.user_id),
+        name: "Middle Earth".to_owned(),
+        slug: "middle-earth".parse().unwrap(),
+        description: "Home of the Hobbits".to_owned(),
+        visibility: Visibility::Public,
+        forked_from: None,
+        tags: vec![],
+        cover: None,
+      })
+      .unwrap();
+
+    let revisions = database
+      .wrapper
+      .list_world_revisions(&created.identity.id, ALL);
+    assert_that(&revisions.total).is_equal_to(1);
+    assert_that(&revisions.entries).is_equal_to(vec![WorldRevision {
+      world: created.identity.id.clone(),
+      revision: 1,
+      version: created.identity.version,
+      created: created.identity.updated,
+      data: created.data,
+    }]);
+  }
+
+  #[test]
+  fn test_update_world_records_revisions() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+    let world = WorldEntity::from(worlds[0].clone());
+
+    let first = database
+      .wrapper
+      .update_world(WorldEntity {
+        data: WorldData {
+          name: "Arda".to_owned(),
+          ..world.data.clone()
+        },
+        ..world.clone()
+      })
+      .unwrap();
+    let second = database
+      .wrapper
+      .update_world(WorldEntity {
+        data: WorldData {
+          description: "Home of the Elves".to_owned(),
+          ..first.data.clone()
+        },
+        ..first.clone()
+      })
+      .unwrap();
+
+    // The world was seeded before any revisions existed, so its original state is recorded first
+    let revisions = database
+      .wrapper
+      .list_world_revisions(&world.identity.id, ALL);
+    assert_that(&revisions.total).is_equal_to(3);
+    let summary: Vec<(u32, Uuid, String, String)> = revisions
+      .entries
+      .into_iter()
+      .map(|revision| {
+        (
+          revision.revision,
+          revision.version,
+          revision.data.name,
+          revision.data.description,
+        )
+      })
+      .collect();
+    assert_that(&summary).is_equal_to(vec![
+      (
+        3,
+        second.identity.version,
+        "Arda".to_owned(),
+        "Home of the Elves".to_owned(),
+      ),
+      (
+        2,
+        first.identity.version,
+        "Arda".to_owned(),
+        "Home of the Hobbits".to_owned(),
+      ),
+      (
+        1,
+        world.identity.version,
+        "Middle Earth".to_owned(),
+        "Home of the Hobbits".to_owned(),
+      ),
+    ]);
+
+    let page = database.wrapper.list_world_revisions(
+      &world.identity.id,
+      Pagination {
+        offset: 1,
+        limit: 1,
+      },
+    );
+    assert_that(&page.total).is_equal_to(3);
+    assert_that(&page.entries.iter().map(|r| r.revision).collect::<Vec<_>>()).is_equal_to(vec![2]);
+  }
+
+  #[test]
+  fn test_failed_update_records_no_revision() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+    let world = WorldEntity::from(worlds[0].clone());
+
+    let updated = database.wrapper.update_world(WorldEntity {
+      identity: Identity {
+        version: Uuid::new_v4(),
+        ..world.identity.clone()
+      },
+      ..world.clone()
+    });
+    assert_that(&updated)
+      .is_err()
+      .is_equal_to(PersistWorldError::OptimisticLockFailure);
+
+    let revisions = database
+      .wrapper
+      .list_world_revisions(&world.identity.id, ALL);
+    assert_that(&revisions.total).is_equal_to(0);
+  }
+
+  #[test]
+  fn test_get_world_revision() {
+    let database = TestDatabaseWrapper::new();
+    let (_, worlds) = seed_worlds(&database);
+    let world = WorldEntity::from(worlds[0].clone());
+    let updated = database
+      .wrapper
+      .update_world(WorldEntity {
+        data: WorldData {
+          name: "Arda".to_owned(),
+          ..world.data.clone()
+        },
+        ..world.clone()
+      })
+      .unwrap();
+
+    let revision = database.wrapper.get_world_revision(&world.identity.id, 2);
+    assert_that(&revision).is_some().is_equal_to(WorldRevision {
+      world: world.identity.id.clone(),
+      revision: 2,
+      version: updated.identity.version,
+      created: updated.identity.updated,
+      data: updated.data,
+    });
+
+    let unknown = database.wrapper.get_world_revision(&world.identity.id, 3);
+    assert_that(&unknown).is_none();
+
+    let other_world = WorldID::from_uuid(worlds[1].world_id);
+    let other = database.wrapper.get_world_revision(&other_world, 1);
+    assert_that(&other).is_none();
+  }
+
+  #[test]
+  fn test_star_world() {
+    let database = TestDatabaseWrapper::new();
+    let (user, worlds) = seed_worlds(&database);
+    let other_user = seed_member(&database);
+    let world_id = WorldID::from_uuid(worlds[0].world_id);
+    let original = database.wrapper.get_world_by_id(&world_id).unwrap();
+
+    for user_id in &[user.user_id, other_user.user_id, user.user_id] {
+      let starred = database
+        .wrapper
+        .star_world(&world_id, &UserID::from_uuid(*user_id));
+      assert_that(&starred).is_ok();
+    }
+
+    // Stars aren't edits to the world, so they leave its version alone
+    let world = database.wrapper.get_world_by_id(&world_id).unwrap();
+    assert_that(&world.stars).is_equal_to(2);
+    assert_that(&world.identity.version).is_equal_to(original.identity.version);
+
+    for _ in 0..2 {
+      let unstarred = database
+        .wrapper
+        .unstar_world(&world_id, &UserID::from_uuid(user.user_id));
+      assert_that(&unstarred).is_ok();
+    }
+
+    let world = database.wrapper.get_world_by_id(&world_id).unwrap();
+    assert_that(&world.stars).is_equal_to(1);
+    assert_that(&world.identity.version).is_equal_to(original.identity.version);
+
+    // Nor do they leave any revisions behind
+    let revisions = database.wrapper.list_world_revisions(&world_id, ALL);
+    assert_that(&revisions.total).is_equal_to(0);
+  }
+
+  #[test]
+  fn test_star_unknown_world() {
+    let database = TestDatabaseWrapper::new();
+    let (user, _) = seed_worlds(&database);
+
+    let starred = database
+      .wrapper
+      .star_world(&Default::default(), &UserID::from_uuid(user.user_id));
+    assert_that(&starred)
+      .is_err()
+      .is_equal_to(PersistWorldError::WorldNotFound);
+  }
+
+  #[test]
+  fn test_search_starred_by_popularity() {
+    let database = TestDatabaseWrapper::new();
+    let (user, worlds) = seed_worlds(&database);
+    let other_user = seed_member(&database);
+    seed(
+      &database,
+      vec![
+        &WorldStar {
+          world_id: worlds[1].world_id,
+          user_id: other_user.user_id,
+          ..Default::default()
+        },
+        &WorldStar {
+          world_id: worlds[2].world_id,
+          user_id: other_user.user_id,
+          ..Default::default()
+        },
+        &WorldStar {
+          world_id: worlds[2].world_id,
+          user_id: user.user_id,
+          ..Default::default()
+        },
+      ],
+    );
+
+    let popular = database.wrapper.search_worlds(
+      no_filters(),
+      sort(WorldSorts::Popularity, SortDirection::Natural),
+      ALL,
+    );
+    assert_that(&names(&popular)).is_equal_to(vec![
+      "Narnia".to_owned(),
+      "Discworld".to_owned(),
+      "Middle Earth".to_owned(),
+    ]);
+    assert_that(&popular.entries.iter().map(|w| w.stars).collect::<Vec<_>>())
+      .is_equal_to(vec![2, 1, 0]);
+
+    let starred = database.wrapper.search_worlds(
+      WorldFilters {
+        starred_by: Some(UserID::from_uuid(user.user_id)),
+        ..no_filters()
+      },
+      vec![],
+      ALL,
+    );
+    assert_that(&names(&starred)).is_equal_to(vec!["Narnia".to_owned()]);
+  }
 }
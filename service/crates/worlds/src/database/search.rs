@@ -0,0 +1,632 @@
+use crate::{TagFilter, WorldFacet, WorldFilters, WorldSorts};
+use postgres::types::ToSql;
+use std::boxed::Box;
+use universe_entity::{Pagination, SortDirection, SortField};
+
+/// Builder for the SQL statements needed to search for worlds
+pub struct WorldSearchQuery {
+  /// The clauses to combine into the WHERE of the queries
+  clauses: Vec<String>,
+  /// The values to bind into the queries
+  binds: Vec<Box<dyn ToSql + Sync>>,
+  /// The bind index of the keyword query, if we are filtering by keyword
+  keyword_bind: Option<usize>,
+}
+
+impl WorldSearchQuery {
+  /// Construct the search query for the given filters
+  ///
+  /// # Arguments
+  /// * `filters` The filters to apply when searching for worlds
+  ///
+  /// # Returns
+  /// The search query
+  pub fn new(filters: WorldFilters) -> Self {
+    // Worlds that are in the trash are never returned by a search
+    let mut query = WorldSearchQuery {
+      clauses: vec!["deleted IS NULL".to_owned()],
+      binds: vec![],
+      keyword_bind: None,
+    };
+
+    // Only public worlds are returned by a search, unless the user searching owns or is a member of the world
+    match filters.visible_to {
+      None => query.clauses.push("visibility = 'public'".to_owned()),
+      Some(user) => {
+        let bind = query.bind(user);
+        query.clauses.push(format!(
+          "(visibility = 'public' OR owner_id = ${0} OR world_id IN (SELECT world_id FROM world_members WHERE user_id = ${0}))",
+          bind
+        ));
+      }
+    }
+
+    if let Some(owner) = filters.owner {
+      let bind = query.bind(owner);
+      query.clauses.push(format!("owner_id = ${}", bind));
+    }
+
+    if let Some(user) = filters.starred_by {
+      let bind = query.bind(user);
+      query.clauses.push(format!(
+        "world_id IN (SELECT world_id FROM world_stars WHERE user_id = ${})",
+        bind
+      ));
+    }
+
+    match filters.tags {
+      None => {}
+      Some(TagFilter::Any(tags)) => {
+        let bind = query.bind(tags);
+        query.clauses.push(format!("tags && ${}", bind));
+      }
+      Some(TagFilter::All(tags)) => {
+        let bind = query.bind(tags);
+        query.clauses.push(format!("tags @> ${}", bind));
+      }
+    }
+
+    // A keyword made up only of stopwords becomes an empty query, which would otherwise match nothing
+    if let Some(tsquery) = filters.keyword.as_deref().and_then(keyword_tsquery) {
+      let bind = query.bind(tsquery);
+      query.clauses.push(format!(
+        "(numnode(to_tsquery('english', ${0})) = 0 OR search_vector @@ to_tsquery('english', ${0}))",
+        bind
+      ));
+      query.keyword_bind = Some(bind);
+    }
+
+    query
+  }
+
+  /// Add a new value to bind into the queries
+  ///
+  /// # Arguments
+  /// * `value` The value to bind
+  ///
+  /// # Returns
+  /// The index of the bind placeholder to use in the SQL
+  fn bind<T>(&mut self, value: T) -> usize
+  where
+    T: ToSql + Sync + 'static,
+  {
+    self.binds.push(Box::new(value));
+    self.binds.len()
+  }
+
+  /// Build the WHERE clause for the queries
+  fn where_clause(&self) -> String {
+    if self.clauses.is_empty() {
+      "".to_owned()
+    } else {
+      format!(" WHERE {}", self.clauses.join(" AND "))
+    }
+  }
+
+  /// Build the ORDER BY clause for the requested sorts.
+  ///
+  /// The World ID is always used as a final sort so that the ordering is deterministic even if
+  /// the requested sorts have duplicate values
+  ///
+  /// # Arguments
+  /// * `sorts` The sorts to apply
+  fn order_by_clause(&self, sorts: &[SortField<WorldSorts>]) -> String {
+    let mut order_by: Vec<String> = sorts
+      .iter()
+      .filter_map(|sort| {
+        let (expression, natural) = match sort.field {
+          WorldSorts::Name => ("UPPER(name)".to_owned(), "ASC"),
+          WorldSorts::Owner => (
+            "(SELECT UPPER(display_name) FROM users WHERE users.user_id = worlds.owner_id)"
+              .to_owned(),
+            "ASC",
+          ),
+          WorldSorts::Created => ("created".to_owned(), "DESC"),
+          WorldSorts::Relevance => (
+            // Without a keyword every world is equally relevant
+            self.keyword_bind.map(|bind| {
+              format!(
+                "ts_rank_cd(search_vector, to_tsquery('english', ${}))",
+                bind
+              )
+            })?,
+            "DESC",
+          ),
+          WorldSorts::Id => ("world_id".to_owned(), "ASC"),
+          WorldSorts::Popularity => ("stars".to_owned(), "DESC"),
+        };
+
+        let direction = match sort.direction {
+          SortDirection::Ascending => "ASC",
+          SortDirection::Descending => "DESC",
+          SortDirection::Natural => natural,
+        };
+
+        Some(format!("{} {}", expression, direction))
+      })
+      .collect();
+    order_by.push("world_id ASC".to_owned());
+
+    format!(" ORDER BY {}", order_by.join(", "))
+  }
+
+  /// Build the SQL to select the requested page of worlds
+  ///
+  /// # Arguments
+  /// * `sorts` The sorts to apply
+  /// * `pagination` The page of worlds to select
+  ///
+  /// # Returns
+  /// The SQL and the binds to use with it
+  pub fn select(
+    mut self,
+    sorts: &[SortField<WorldSorts>],
+    pagination: Pagination,
+  ) -> (String, Vec<Box<dyn ToSql + Sync>>) {
+    let mut sql = format!(
+      "SELECT * FROM worlds{}{}",
+      self.where_clause(),
+      self.order_by_clause(sorts)
+    );
+
+    let limit = self.bind(i64::from(pagination.limit));
+    let offset = self.bind(i64::from(pagination.offset));
+    sql.push_str(&format!(" LIMIT ${} OFFSET ${}", limit, offset));
+
+    (sql, self.binds)
+  }
+
+  /// Build the SQL to count all of the worlds that match the filters
+  ///
+  /// # Returns
+  /// The SQL and the binds to use with it
+  pub fn count(self) -> (String, Vec<Box<dyn ToSql + Sync>>) {
+    let sql = format!(
+      "SELECT COUNT(*) AS total FROM worlds{}",
+      self.where_clause()
+    );
+
+    (sql, self.binds)
+  }
+
+  /// Build the SQL to count how many of the worlds that match the filters have each value of a
+  /// facet, most common values first
+  ///
+  /// # Arguments
+  /// * `facet` The facet to count the values of
+  /// * `limit` The maximum number of values to count
+  ///
+  /// # Returns
+  /// The SQL and the binds to use with it
+  pub fn facet(mut self, facet: WorldFacet, limit: u32) -> (String, Vec<Box<dyn ToSql + Sync>>) {
+    let (from, value) = match facet {
+      WorldFacet::Tags => ("worlds, UNNEST(tags) AS tag", "tag"),
+      WorldFacet::Owners => ("worlds", "owner_id::TEXT"),
+    };
+
+    let mut sql = format!(
+      "SELECT {0} AS value, COUNT(*) AS count FROM {1}{2} GROUP BY {0} ORDER BY count DESC, value ASC",
+      value,
+      from,
+      self.where_clause()
+    );
+
+    let limit = self.bind(i64::from(limit));
+    sql.push_str(&format!(" LIMIT ${}", limit));
+
+    (sql, self.binds)
+  }
+}
+
+/// The individual tokens that a keyword search is made up of
+#[derive(Debug, PartialEq)]
+enum KeywordToken {
+  /// A single word, which is matched as a prefix
+  Word(String),
+  /// A quoted phrase, which is matched exactly
+  Phrase(String),
+  /// The word "OR", which allows either of the surrounding terms to match
+  Or,
+}
+
+/// Split a keyword search into the individual tokens that make it up.
+///
+/// # Arguments
+/// * `keyword` The keyword search to split
+///
+/// # Returns
+/// The tokens, each with a flag indicating if it is negated
+fn tokenize_keyword(keyword: &str) -> Vec<(bool, KeywordToken)> {
+  let mut tokens = vec![];
+  let mut chars = keyword.chars().peekable();
+
+  while let Some(&next) = chars.peek() {
+    if next.is_whitespace() {
+      chars.next();
+      continue;
+    }
+
+    let negated = next == '-';
+    if negated {
+      chars.next();
+    }
+
+    if chars.peek() == Some(&'"') {
+      chars.next();
+      let phrase: String = chars.by_ref().take_while(|&c| c != '"').collect();
+      tokens.push((negated, KeywordToken::Phrase(phrase)));
+    } else {
+      let mut word = String::new();
+      while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+          break;
+        }
+        word.push(c);
+        chars.next();
+      }
+
+      if !negated && word.eq_ignore_ascii_case("or") {
+        tokens.push((false, KeywordToken::Or));
+      } else {
+        tokens.push((negated, KeywordToken::Word(word)));
+      }
+    }
+  }
+
+  tokens
+}
+
+/// Convert a keyword search into a Postgres `tsquery` expression.
+///
+/// * Bare words are matched as prefixes, so that partial words still find results
+/// * Words wrapped in double quotes are matched as an exact phrase
+/// * Words or phrases prefixed with `-` must not be present
+/// * The word `OR` between two terms allows either of them to match
+///
+/// `OR` binds more tightly than the implicit AND between terms, so `hobbit OR elf ring` finds
+/// worlds that mention a ring along with either hobbits or elves. Each run of terms joined by `OR`
+/// is wrapped in parentheses to make this explicit in the expression.
+///
+/// Only alphanumeric characters make it into the expression, so the result is always safe to pass
+/// to `to_tsquery`, which will then apply stemming to every term.
+///
+/// # Arguments
+/// * `keyword` The keyword search to convert
+///
+/// # Returns
+/// The `tsquery` expression, or `None` if the keyword search had no usable terms
+fn keyword_tsquery(keyword: &str) -> Option<String> {
+  let mut groups: Vec<Vec<String>> = vec![];
+  let mut or = false;
+
+  for (negated, token) in tokenize_keyword(keyword) {
+    let (text, prefix) = match token {
+      KeywordToken::Or => {
+        or = true;
+        continue;
+      }
+      KeywordToken::Word(word) => (word, true),
+      KeywordToken::Phrase(phrase) => (phrase, false),
+    };
+
+    let lexemes: Vec<String> = text
+      .split(|c: char| !c.is_alphanumeric())
+      .filter(|lexeme| !lexeme.is_empty())
+      .map(|lexeme| lexeme.to_lowercase())
+      .collect();
+    if lexemes.is_empty() {
+      continue;
+    }
+
+    let mut term = lexemes.join(" <-> ");
+    if prefix {
+      term.push_str(":*");
+    }
+    if lexemes.len() > 1 {
+      term = format!("({})", term);
+    }
+    if negated {
+      term = format!("!{}", term);
+    }
+
+    match groups.last_mut() {
+      Some(group) if or => group.push(term),
+      _ => groups.push(vec![term]),
+    }
+    or = false;
+  }
+
+  if groups.is_empty() {
+    None
+  } else {
+    Some(
+      groups
+        .into_iter()
+        .map(|group| {
+          if group.len() > 1 {
+            format!("({})", group.join(" | "))
+          } else {
+            group.join(" | ")
+          }
+        })
+        .collect::<Vec<String>>()
+        .join(" & "),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use rstest::rstest;
+  use spectral::prelude::*;
+
+  #[test]
+  fn test_select_no_filters_no_sorts() {
+    let query = WorldSearchQuery::new(WorldFilters {
+      owner: None,
+      keyword: None,
+      visible_to: None,
+      tags: None,
+      starred_by: None,
+    });
+    let (sql, binds) = query.select(
+      &[],
+      Pagination {
+        offset: 0,
+        limit: 10,
+      },
+    );
+
+    assert_that(&sql.as_str()).is_equal_to(
+      "SELECT * FROM worlds WHERE deleted IS NULL AND visibility = 'public' ORDER BY world_id ASC LIMIT $1 OFFSET $2",
+    );
+    assert_that(&binds).has_length(2);
+  }
+
+  #[test]
+  fn test_select_natural_sorts() {
+    let query = WorldSearchQuery::new(WorldFilters {
+      owner: None,
+      keyword: None,
+      visible_to: None,
+      tags: None,
+      starred_by: None,
+    });
+    let (sql, _) = query.select(
+      &[
+        SortField {
+          field: WorldSorts::Name,
+          direction: SortDirection::Natural,
+        },
+        SortField {
+          field: WorldSorts::Created,
+          direction: SortDirection::Natural,
+        },
+      ],
+      Pagination {
+        offset: 0,
+        limit: 10,
+      },
+    );
+
+    assert_that(&sql.as_str()).is_equal_to(
+      "SELECT * FROM worlds WHERE deleted IS NULL AND visibility = 'public' ORDER BY UPPER(name) ASC, created DESC, world_id ASC LIMIT $1 OFFSET $2",
+    );
+  }
+
+  #[test]
+  fn test_select_relevance_without_keyword() {
+    let query = WorldSearchQuery::new(WorldFilters {
+      owner: None,
+      keyword: None,
+      visible_to: None,
+      tags: None,
+      starred_by: None,
+    });
+    let (sql, _) = query.select(
+      &[SortField {
+        field: WorldSorts::Relevance,
+        direction: SortDirection::Natural,
+      }],
+      Pagination {
+        offset: 0,
+        limit: 10,
+      },
+    );
+
+    assert_that(&sql.as_str()).is_equal_to(
+      "SELECT * FROM worlds WHERE deleted IS NULL AND visibility = 'public' ORDER BY world_id ASC LIMIT $1 OFFSET $2",
+    );
+  }
+
+  #[test]
+  fn test_select_starred_by_popularity() {
+    let query = WorldSearchQuery::new(WorldFilters {
+      owner: None,
+      keyword: None,
+      visible_to: None,
+      tags: None,
+      starred_by: Some(Default::default()),
+    });
+    let (sql, binds) = query.select(
+      &[SortField {
+        field: WorldSorts::Popularity,
+        direction: SortDirection::Natural,
+      }],
+      Pagination {
+        offset: 0,
+        limit: 10,
+      },
+    );
+
+    assert_that(&sql.as_str()).is_equal_to(
+      "SELECT * FROM worlds WHERE deleted IS NULL AND visibility = 'public' AND world_id IN (SELECT world_id FROM world_stars WHERE user_id = $1) ORDER BY stars DESC, world_id ASC LIMIT $2 OFFSET $3",
+    );
+    assert_that(&binds).has_length(3);
+  }
+
+  #[test]
+  fn test_count_with_filters() {
+    let query = WorldSearchQuery::new(WorldFilters {
+      owner: Some(Default::default()),
+      keyword: Some("test".to_owned()),
+      visible_to: None,
+      tags: None,
+      starred_by: None,
+    });
+    let (sql, binds) = query.count();
+
+    assert_that(&sql.as_str()).is_equal_to(
+      "SELECT COUNT(*) AS total FROM worlds WHERE deleted IS NULL AND visibility = 'public' AND owner_id = $1 AND (numnode(to_tsquery('english', $2)) = 0 OR search_vector @@ to_tsquery('english', $2))",
+    );
+    assert_that(&binds).has_length(2);
+  }
+
+  #[test]
+  fn test_count_visible_to_user() {
+    let query = WorldSearchQuery::new(WorldFilters {
+      owner: None,
+      keyword: None,
+      visible_to: Some(Default::default()),
+      tags: None,
+      starred_by: None,
+    });
+    let (sql, binds) = query.count();
+
+    assert_that(&sql.as_str()).is_equal_to(
+      "SELECT COUNT(*) AS total FROM worlds WHERE deleted IS NULL AND (visibility = 'public' OR owner_id = $1 OR world_id IN (SELECT world_id FROM world_members WHERE user_id = $1))",
+    );
+    assert_that(&binds).has_length(1);
+  }
+
+  #[test]
+  fn test_count_with_any_tags() {
+    let query = WorldSearchQuery::new(WorldFilters {
+      owner: None,
+      keyword: None,
+      visible_to: None,
+      tags: Some(TagFilter::Any(vec!["fantasy".parse().unwrap()])),
+      starred_by: None,
+    });
+    let (sql, binds) = query.count();
+
+    assert_that(&sql.as_str()).is_equal_to(
+      "SELECT COUNT(*) AS total FROM worlds WHERE deleted IS NULL AND visibility = 'public' AND tags && $1",
+    );
+    assert_that(&binds).has_length(1);
+  }
+
+  #[test]
+  fn test_count_with_all_tags() {
+    let query = WorldSearchQuery::new(WorldFilters {
+      owner: None,
+      keyword: None,
+      visible_to: None,
+      tags: Some(TagFilter::All(vec![
+        "fantasy".parse().unwrap(),
+        "dnd".parse().unwrap(),
+      ])),
+      starred_by: None,
+    });
+    let (sql, binds) = query.count();
+
+    assert_that(&sql.as_str()).is_equal_to(
+      "SELECT COUNT(*) AS total FROM worlds WHERE deleted IS NULL AND visibility = 'public' AND tags @> $1",
+    );
+    assert_that(&binds).has_length(1);
+  }
+
+  #[rstest(
+    facet,
+    expected,
+    case(WorldFacet::Tags, "SELECT tag AS value, COUNT(*) AS count FROM worlds, UNNEST(tags) AS tag WHERE deleted IS NULL AND visibility = 'public' AND owner_id = $1 GROUP BY tag ORDER BY count DESC, value ASC LIMIT $2"),
+    case(WorldFacet::Owners, "SELECT owner_id::TEXT AS value, COUNT(*) AS count FROM worlds WHERE deleted IS NULL AND visibility = 'public' AND owner_id = $1 GROUP BY owner_id::TEXT ORDER BY count DESC, value ASC LIMIT $2")
+  )]
+  fn test_facet(facet: WorldFacet, expected: &str) {
+    let query = WorldSearchQuery::new(WorldFilters {
+      owner: Some(Default::default()),
+      keyword: None,
+      visible_to: None,
+      tags: None,
+      starred_by: None,
+    });
+    let (sql, binds) = query.facet(facet, 20);
+
+    assert_that(&sql.as_str()).is_equal_to(expected);
+    assert_that(&binds).has_length(2);
+  }
+
+  #[test]
+  fn test_select_relevance_with_keyword() {
+    let query = WorldSearchQuery::new(WorldFilters {
+      owner: None,
+      keyword: Some("test".to_owned()),
+      visible_to: None,
+      tags: None,
+      starred_by: None,
+    });
+    let (sql, binds) = query.select(
+      &[SortField {
+        field: WorldSorts::Relevance,
+        direction: SortDirection::Natural,
+      }],
+      Pagination {
+        offset: 0,
+        limit: 10,
+      },
+    );
+
+    assert_that(&sql.as_str()).is_equal_to(
+      "SELECT * FROM worlds WHERE deleted IS NULL AND visibility = 'public' AND (numnode(to_tsquery('english', $1)) = 0 OR search_vector @@ to_tsquery('english', $1)) ORDER BY ts_rank_cd(search_vector, to_tsquery('english', $1)) DESC, world_id ASC LIMIT $2 OFFSET $3",
+    );
+    assert_that(&binds).has_length(3);
+  }
+
+  #[test]
+  fn test_select_unusable_keyword() {
+    let query = WorldSearchQuery::new(WorldFilters {
+      owner: None,
+      keyword: Some(" !? ".to_owned()),
+      visible_to: None,
+      tags: None,
+      starred_by: None,
+    });
+    let (sql, _) = query.count();
+
+    assert_that(&sql.as_str()).is_equal_to(
+      "SELECT COUNT(*) AS total FROM worlds WHERE deleted IS NULL AND visibility = 'public'",
+    );
+  }
+
+  #[rstest(
+    input,
+    expected,
+    case("", None),
+    case("   ", None),
+    case("\"\"", None),
+    case("?!", None),
+    case("hobbit", Some("hobbit:*")),
+    case("  Hobbit  ", Some("hobbit:*")),
+    case("hobbit ring", Some("hobbit:* & ring:*")),
+    case("hobbit OR elf", Some("(hobbit:* | elf:*)")),
+    case("hobbit or elf ring", Some("(hobbit:* | elf:*) & ring:*")),
+    case("ring hobbit OR elf", Some("ring:* & (hobbit:* | elf:*)")),
+    case("hobbit OR elf OR -orc", Some("(hobbit:* | elf:* | !orc:*)")),
+    case("OR hobbit", Some("hobbit:*")),
+    case("hobbit OR", Some("hobbit:*")),
+    case("hobbit OR ?! ring", Some("(hobbit:* | ring:*)")),
+    case("hobbit -orc", Some("hobbit:* & !orc:*")),
+    case("\"lord of the rings\"", Some("(lord <-> of <-> the <-> rings)")),
+    case("\"lord of the rings", Some("(lord <-> of <-> the <-> rings)")),
+    case("-\"dark lord\" hobbit", Some("!(dark <-> lord) & hobbit:*")),
+    case("middle-earth", Some("(middle <-> earth:*)")),
+    case("it's", Some("(it <-> s:*)")),
+    case("hob:* & !(orc)", Some("hob:* & orc:*")),
+    case("Éowyn", Some("éowyn:*"))
+  )]
+  fn test_keyword_tsquery(input: &str, expected: Option<&str>) {
+    let result = keyword_tsquery(input);
+    assert_that(&result).is_equal_to(expected.map(|e| e.to_owned()));
+  }
+}
@@ -0,0 +1,115 @@
+use bytes::BytesMut;
+use postgres::types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
+use serde::Serialize;
+use std::str::FromStr;
+use strum_macros::{Display, EnumIter, EnumString};
+use uuid::Uuid;
+
+/// Representation of the ID of a cover image that has been uploaded for a world.
+///
+/// Every upload gets a new ID, so the URLs of a cover image never change and can be cached
+/// forever.
+#[derive(Debug, PartialEq, Clone, Serialize, FromSql)]
+pub struct CoverID(Uuid);
+
+impl CoverID {
+  /// Construct a Cover ID from a UUID value
+  ///
+  /// # Arguments
+  /// * `uuid` The UUID to use
+  ///
+  /// # Returns
+  /// The Cover ID
+  pub fn from_uuid(uuid: Uuid) -> Self {
+    CoverID(uuid)
+  }
+}
+
+impl std::fmt::Display for CoverID {
+  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl Default for CoverID {
+  fn default() -> Self {
+    CoverID(Uuid::new_v4())
+  }
+}
+
+impl FromStr for CoverID {
+  type Err = uuid::Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let uuid: Uuid = s.trim().parse()?;
+    Ok(CoverID(uuid))
+  }
+}
+
+impl ToSql for CoverID {
+  fn to_sql(
+    &self,
+    t: &Type,
+    w: &mut BytesMut,
+  ) -> Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+    self.0.to_sql(t, w)
+  }
+
+  accepts!(UUID);
+  to_sql_checked!();
+}
+
+/// The different sizes that a cover image is available in
+#[derive(Debug, PartialEq, Clone, Copy, Display, EnumString, EnumIter)]
+#[strum(serialize_all = "lowercase")]
+pub enum CoverSize {
+  /// The image exactly as it was uploaded
+  Original,
+  /// A small thumbnail, suitable for lists of worlds
+  Small,
+  /// A large thumbnail, suitable for the banner of a world
+  Large,
+}
+
+impl CoverSize {
+  /// Get the fixed width and height of the thumbnail for this size
+  ///
+  /// # Returns
+  /// The width and height of the thumbnail, or `None` for the original image
+  pub fn dimensions(self) -> Option<(u32, u32)> {
+    match self {
+      CoverSize::Original => None,
+      CoverSize::Small => Some((320, 180)),
+      CoverSize::Large => Some((1280, 720)),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use rstest::rstest;
+  use spectral::prelude::*;
+
+  #[rstest(
+    input,
+    expected,
+    case("original", Some(CoverSize::Original)),
+    case("small", Some(CoverSize::Small)),
+    case("large", Some(CoverSize::Large)),
+    case("medium", None),
+    case("", None)
+  )]
+  fn test_parse_cover_size(input: &str, expected: Option<CoverSize>) {
+    let result: Option<CoverSize> = input.parse().ok();
+    assert_that(&result).is_equal_to(expected);
+  }
+
+  #[test]
+  fn test_parse_cover_id() {
+    let result: Result<CoverID, _> = " 6c1a3f4e-5b8d-4f2a-9e1c-7d3b2a1f0e9d ".parse();
+    assert_that(&result.unwrap()).is_equal_to(CoverID::from_uuid(
+      "6c1a3f4e-5b8d-4f2a-9e1c-7d3b2a1f0e9d".parse().unwrap(),
+    ));
+  }
+}
@@ -1,7 +1,23 @@
+mod cover;
 mod slug;
+mod tag;
+mod trashed_world;
+mod visibility;
 mod world;
 mod world_id;
+mod world_member;
+mod world_revision;
+mod world_role;
+mod world_transfer;
 
+pub use cover::*;
 pub use slug::*;
+pub use tag::*;
+pub use trashed_world::*;
+pub use visibility::*;
 pub use world::*;
 pub use world_id::*;
+pub use world_member::*;
+pub use world_revision::*;
+pub use world_role::*;
+pub use world_transfer::*;
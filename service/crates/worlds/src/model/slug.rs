@@ -3,12 +3,74 @@ use postgres::types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
 use serde::Serialize;
 use std::str::FromStr;
 
+/// The maximum length of a slug, in characters
+pub const MAX_SLUG_LENGTH: usize = 64;
+
+/// The slug to use when one can't be generated from a name
+const DEFAULT_SLUG: &str = "world";
+
 /// Representation of a slug of some world in the system.
 ///
-/// A slug is any valid UTF-8 string, but must not have any whitespace padding to either end.
+/// A slug is made up only of ASCII letters, digits, hyphens and underscores so that it is safe
+/// to use in URLs, and is at most `MAX_SLUG_LENGTH` characters long.
 #[derive(Debug, PartialEq, Clone, Serialize, FromSql)]
 pub struct Slug(String);
 
+impl Slug {
+    /// Generate a slug from the name of a world.
+    ///
+    /// Unicode characters are transliterated into ASCII, and any runs of characters that aren't
+    /// allowed in a slug are replaced with a single hyphen.
+    ///
+    /// # Arguments
+    /// * `name` The name to generate the slug from
+    ///
+    /// # Returns
+    /// The generated slug
+    pub fn from_name(name: &str) -> Self {
+        let transliterated = deunicode::deunicode(name).to_lowercase();
+
+        let slug = transliterated
+            .split(|c: char| c == '-' || !is_slug_char(c))
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<&str>>()
+            .join("-");
+        let slug = truncate(&slug, MAX_SLUG_LENGTH);
+
+        if slug.is_empty() {
+            Slug(DEFAULT_SLUG.to_owned())
+        } else {
+            Slug(slug.to_owned())
+        }
+    }
+
+    /// Produce a new slug by appending a numeric suffix to this one, truncating this slug if
+    /// needed so that the result still fits within `MAX_SLUG_LENGTH`.
+    ///
+    /// # Arguments
+    /// * `suffix` The number to append to the slug
+    ///
+    /// # Returns
+    /// The new slug
+    pub fn with_suffix(&self, suffix: u32) -> Self {
+        let suffix = format!("-{}", suffix);
+        let base = truncate(&self.0, MAX_SLUG_LENGTH - suffix.len());
+
+        Slug(format!("{}{}", base, suffix))
+    }
+}
+
+/// Check whether a character is allowed to appear in a slug
+fn is_slug_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_'
+}
+
+/// Truncate a slug to at most the given number of characters, without leaving a trailing hyphen
+fn truncate(slug: &str, length: usize) -> &str {
+    let end = slug.len().min(length);
+    slug[..end].trim_end_matches('-')
+}
+
 impl std::fmt::Display for Slug {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "{}", self.0)
@@ -21,7 +83,8 @@ impl FromStr for Slug {
 
     /// Attempt to parse a string into a Slug object.
     ///
-    /// A slug is any valid UTF-8 string, but must not have any whitespace padding to either end.
+    /// Any whitespace padding is removed, and what remains must be made up only of ASCII letters,
+    /// digits, hyphens and underscores and be no longer than `MAX_SLUG_LENGTH` characters.
     ///
     /// # Arguments
     /// * `s` The string to parse
@@ -33,6 +96,10 @@ impl FromStr for Slug {
         let trimmed = s.trim();
         if trimmed.is_empty() {
             Err(SlugParseError::Blank)
+        } else if !trimmed.chars().all(is_slug_char) {
+            Err(SlugParseError::InvalidCharacters)
+        } else if trimmed.len() > MAX_SLUG_LENGTH {
+            Err(SlugParseError::TooLong)
         } else {
             Ok(Slug(trimmed.to_owned()))
         }
@@ -61,6 +128,10 @@ impl ToSql for Slug {
 pub enum SlugParseError {
     #[error("Slug was blank")]
     Blank,
+    #[error("Slug contained characters that are not allowed")]
+    InvalidCharacters,
+    #[error("Slug was too long")]
+    TooLong,
 }
 
 #[cfg(test)]
@@ -101,6 +172,77 @@ mod tests {
             .is_equal_to(SlugParseError::Blank);
     }
 
+    #[test]
+    fn test_parse_slug_with_invalid_characters() {
+        for input in &["My World!!", "my world", "my/world", "über", "my.world"] {
+            let slug: Result<Slug, SlugParseError> = input.parse();
+
+            assert_that(&slug)
+                .is_err()
+                .is_equal_to(SlugParseError::InvalidCharacters);
+        }
+    }
+    #[test]
+    fn test_parse_slug_with_allowed_punctuation() {
+        let slug: Result<Slug, SlugParseError> = "My_World-2".parse();
+        assert_that(&slug)
+            .is_ok()
+            .is_equal_to(Slug("My_World-2".to_owned()));
+    }
+    #[test]
+    fn test_parse_longest_slug() {
+        let input = "a".repeat(MAX_SLUG_LENGTH);
+        let slug: Result<Slug, SlugParseError> = input.parse();
+        assert_that(&slug).is_ok().is_equal_to(Slug(input));
+    }
+    #[test]
+    fn test_parse_too_long_slug() {
+        let slug: Result<Slug, SlugParseError> = "a".repeat(MAX_SLUG_LENGTH + 1).parse();
+
+        assert_that(&slug)
+            .is_err()
+            .is_equal_to(SlugParseError::TooLong);
+    }
+
+    #[test]
+    fn test_slug_from_name() {
+        let cases = vec![
+            ("Middle Earth", "middle-earth"),
+            ("  My World!!  ", "my-world"),
+            ("Éowyn's Rohan", "eowyn-s-rohan"),
+            ("Москва", "moskva"),
+            ("snake_case-name", "snake_case-name"),
+            ("--Leading and trailing--", "leading-and-trailing"),
+            ("!!!", "world"),
+            ("", "world"),
+        ];
+
+        for (name, expected) in cases {
+            assert_that(&Slug::from_name(name)).is_equal_to(Slug(expected.to_owned()));
+        }
+    }
+    #[test]
+    fn test_slug_from_long_name() {
+        let name = format!("{} {}", "a".repeat(MAX_SLUG_LENGTH - 1), "b".repeat(10));
+
+        let slug = Slug::from_name(&name);
+        assert_that(&slug).is_equal_to(Slug("a".repeat(MAX_SLUG_LENGTH - 1)));
+    }
+
+    #[test]
+    fn test_slug_with_suffix() {
+        let slug = Slug("test-world".to_owned());
+        assert_that(&slug.with_suffix(2)).is_equal_to(Slug("test-world-2".to_owned()));
+    }
+    #[test]
+    fn test_long_slug_with_suffix() {
+        let slug = Slug("a".repeat(MAX_SLUG_LENGTH));
+
+        let suffixed = slug.with_suffix(12);
+        assert_that(&suffixed.0.len()).is_equal_to(MAX_SLUG_LENGTH);
+        assert_that(&suffixed).is_equal_to(Slug(format!("{}-12", "a".repeat(MAX_SLUG_LENGTH - 3))));
+    }
+
     #[test]
     fn test_serialize_valid_slug() {
         let slug = Slug("testworld".parse().unwrap());
@@ -0,0 +1,176 @@
+use bytes::BytesMut;
+use postgres::types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
+use serde::Serialize;
+use std::str::FromStr;
+
+/// The maximum length of a tag, in characters
+pub const MAX_TAG_LENGTH: usize = 32;
+
+/// The maximum number of tags that a single world can have
+pub const MAX_TAGS: usize = 20;
+
+/// Representation of a free-form tag attached to a world, such as a genre or a game system.
+///
+/// Tags are case-insensitive, so they are always stored in lowercase with any runs of whitespace
+/// collapsed into a single space. Commas are not allowed, so that lists of tags can be written as
+/// comma-separated strings.
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Serialize, FromSql)]
+pub struct Tag(String);
+
+impl std::fmt::Display for Tag {
+  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+/// Implementation of the standard `FromStr` trait to allow us to parse any String into a `Tag` object
+impl FromStr for Tag {
+  type Err = TagParseError;
+
+  /// Attempt to parse a string into a Tag object.
+  ///
+  /// # Arguments
+  /// * `s` The string to parse
+  ///
+  /// # Returns
+  /// The result of parsing the tag. Either a `Tag` object or an error if the incoming string was
+  /// not valid.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let normalized = s.split_whitespace().collect::<Vec<&str>>().join(" ");
+
+    if normalized.is_empty() {
+      Err(TagParseError::Blank)
+    } else if normalized.chars().any(|c| c == ',' || c.is_control()) {
+      Err(TagParseError::InvalidCharacters)
+    } else if normalized.chars().count() > MAX_TAG_LENGTH {
+      Err(TagParseError::TooLong)
+    } else {
+      Ok(Tag(normalized.to_lowercase()))
+    }
+  }
+}
+
+/// Allow us to pass `Tag` objects to Postgres as part of executing a database query.
+impl ToSql for Tag {
+  fn to_sql(
+    &self,
+    t: &Type,
+    w: &mut BytesMut,
+  ) -> Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+    self.0.to_sql(t, w)
+  }
+
+  accepts!(VARCHAR, TEXT);
+  to_sql_checked!();
+}
+
+/// Errors that can happen when parsing a string into a tag.
+#[derive(Debug, PartialEq, Clone, thiserror::Error)]
+pub enum TagParseError {
+  #[error("Tag was blank")]
+  Blank,
+  #[error("Tag contained characters that are not allowed")]
+  InvalidCharacters,
+  #[error("Tag was too long")]
+  TooLong,
+  #[error("There were too many tags")]
+  TooMany,
+}
+
+/// Parse a list of strings into the tags for a world.
+///
+/// Tags that are the same once normalized are only included once, keeping the position of the
+/// first one.
+///
+/// # Arguments
+/// * `tags` The strings to parse
+///
+/// # Returns
+/// The tags, or the first error that was encountered. Having more than `MAX_TAGS` different tags is
+/// also an error
+pub fn parse_tags<S: AsRef<str>>(tags: &[S]) -> Result<Vec<Tag>, TagParseError> {
+  let mut result: Vec<Tag> = vec![];
+
+  for tag in tags {
+    let tag: Tag = tag.as_ref().parse()?;
+    if !result.contains(&tag) {
+      result.push(tag);
+    }
+  }
+
+  if result.len() > MAX_TAGS {
+    Err(TagParseError::TooMany)
+  } else {
+    Ok(result)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use spectral::prelude::*;
+  use test_env_log::test;
+
+  #[test]
+  fn test_parse_tag_normalizes() {
+    let tag: Result<Tag, TagParseError> = "  Call  of\tCthulhu ".parse();
+    assert_that(&tag)
+      .is_ok()
+      .is_equal_to(Tag("call of cthulhu".to_owned()));
+  }
+
+  #[test]
+  fn test_parse_blank_tag() {
+    let tag: Result<Tag, TagParseError> = "   ".parse();
+    assert_that(&tag).is_err().is_equal_to(TagParseError::Blank);
+  }
+
+  #[test]
+  fn test_parse_tag_with_invalid_characters() {
+    for input in &["fantasy,horror", "new\u{0}line"] {
+      let tag: Result<Tag, TagParseError> = input.parse();
+      assert_that(&tag)
+        .is_err()
+        .is_equal_to(TagParseError::InvalidCharacters);
+    }
+  }
+
+  #[test]
+  fn test_parse_longest_tag() {
+    let tag: Result<Tag, TagParseError> = "é".repeat(MAX_TAG_LENGTH).parse();
+    assert_that(&tag).is_ok();
+  }
+
+  #[test]
+  fn test_parse_too_long_tag() {
+    let tag: Result<Tag, TagParseError> = "a".repeat(MAX_TAG_LENGTH + 1).parse();
+    assert_that(&tag)
+      .is_err()
+      .is_equal_to(TagParseError::TooLong);
+  }
+
+  #[test]
+  fn test_parse_tags_removes_duplicates() {
+    let tags = parse_tags(&["Fantasy", "D&D 5e", "fantasy"]);
+    assert_that(&tags)
+      .is_ok()
+      .is_equal_to(vec![Tag("fantasy".to_owned()), Tag("d&d 5e".to_owned())]);
+  }
+
+  #[test]
+  fn test_parse_too_many_tags() {
+    let input: Vec<String> = (0..=MAX_TAGS).map(|i| format!("tag {}", i)).collect();
+    let tags = parse_tags(&input);
+    assert_that(&tags)
+      .is_err()
+      .is_equal_to(TagParseError::TooMany);
+  }
+
+  #[test]
+  fn test_parse_tags_with_invalid_tag() {
+    let tags = parse_tags(&["fantasy", ""]);
+    assert_that(&tags)
+      .is_err()
+      .is_equal_to(TagParseError::Blank);
+  }
+}
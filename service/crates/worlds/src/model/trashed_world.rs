@@ -0,0 +1,9 @@
+use crate::WorldEntity;
+use chrono::{DateTime, Utc};
+
+/// Struct to represent a world that has been moved to the trash
+#[derive(Debug, PartialEq, Clone)]
+pub struct TrashedWorld {
+  pub world: WorldEntity,
+  pub deleted: DateTime<Utc>,
+}
@@ -0,0 +1,66 @@
+use bytes::BytesMut;
+use postgres::types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
+use serde::Serialize;
+use strum_macros::{Display, EnumString};
+
+/// Enumeration of who is able to see a world
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Display, EnumString)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum Visibility {
+  /// The world can be seen by anyone, and is included in search results
+  Public,
+  /// The world can be seen by anyone that knows how to find it, but isn't included in search results
+  Unlisted,
+  /// The world can only be seen by its owner
+  Private,
+}
+
+impl Default for Visibility {
+  fn default() -> Self {
+    Visibility::Public
+  }
+}
+
+/// Allow us to pass `Visibility` values to Postgres as part of executing a database query.
+impl ToSql for Visibility {
+  fn to_sql(
+    &self,
+    t: &Type,
+    w: &mut BytesMut,
+  ) -> Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+    self.to_string().to_sql(t, w)
+  }
+
+  accepts!(VARCHAR, TEXT);
+  to_sql_checked!();
+}
+
+/// Allow us to read `Visibility` values from Postgres query results.
+impl<'a> FromSql<'a> for Visibility {
+  fn from_sql(t: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+    let value = <&str as FromSql>::from_sql(t, raw)?;
+    Ok(value.parse()?)
+  }
+
+  accepts!(VARCHAR, TEXT);
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use spectral::prelude::*;
+
+  #[test]
+  fn test_parse_visibility() {
+    assert_that(&"public".parse()).is_ok_containing(Visibility::Public);
+    assert_that(&"unlisted".parse()).is_ok_containing(Visibility::Unlisted);
+    assert_that(&"private".parse()).is_ok_containing(Visibility::Private);
+    assert_that(&"secret".parse::<Visibility>()).is_err();
+  }
+
+  #[test]
+  fn test_display_visibility() {
+    assert_that(&Visibility::Unlisted.to_string()).is_equal_to("unlisted".to_owned());
+  }
+}
@@ -1,4 +1,4 @@
-use crate::{Slug, WorldID};
+use crate::{CoverID, Slug, Tag, Visibility, WorldID};
 use universe_entity::Identity;
 use universe_users::UserID;
 
@@ -9,6 +9,29 @@ pub struct WorldData {
   pub name: String,
   pub slug: Slug,
   pub description: String,
+  pub visibility: Visibility,
+  /// The world that this one was forked from, if any
+  pub forked_from: Option<WorldID>,
+  /// The free-form tags attached to the world
+  pub tags: Vec<Tag>,
+  /// The current cover image of the world, if it has one
+  pub cover: Option<CoverID>,
+}
+
+impl WorldData {
+  /// Determine whether the world can be seen by the given user.
+  ///
+  /// # Arguments
+  /// * `user` The ID of the user trying to see the world, or `None` if they are anonymous
+  ///
+  /// # Returns
+  /// True if the user is allowed to see the world
+  pub fn is_visible_to(&self, user: Option<&UserID>) -> bool {
+    match self.visibility {
+      Visibility::Public | Visibility::Unlisted => true,
+      Visibility::Private => user == Some(&self.owner),
+    }
+  }
 }
 
 /// Type to represent the entity that is a persisted world record
@@ -16,6 +39,9 @@ pub struct WorldData {
 pub struct WorldEntity {
   pub identity: Identity<WorldID>,
   pub data: WorldData,
+  /// The number of users that have starred the world. This is maintained by the data store, and
+  /// is ignored when the world is saved
+  pub stars: u32,
 }
 
 #[cfg(test)]
@@ -33,7 +59,58 @@ impl From<universe_testdata::World> for WorldEntity {
         name: world.name.clone(),
         slug: world.slug.parse().unwrap(),
         description: world.description.clone(),
+        visibility: world.visibility.parse().unwrap(),
+        forked_from: world.forked_from.map(WorldID::from_uuid),
+        tags: world.tags.iter().map(|tag| tag.parse().unwrap()).collect(),
+        cover: world.cover_id.map(CoverID::from_uuid),
       },
+      stars: 0,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use spectral::prelude::*;
+
+  fn world_data(visibility: Visibility) -> WorldData {
+    WorldData {
+      owner: Default::default(),
+      name: "Test World".to_owned(),
+      slug: "test-world".parse().unwrap(),
+      description: "".to_owned(),
+      visibility,
+      forked_from: None,
+      tags: vec![],
+      cover: None,
     }
   }
+
+  #[test]
+  fn test_public_world_visible_to_everyone() {
+    let world = world_data(Visibility::Public);
+
+    assert_that(&world.is_visible_to(None)).is_true();
+    assert_that(&world.is_visible_to(Some(&Default::default()))).is_true();
+    assert_that(&world.is_visible_to(Some(&world.owner))).is_true();
+  }
+
+  #[test]
+  fn test_unlisted_world_visible_to_everyone() {
+    let world = world_data(Visibility::Unlisted);
+
+    assert_that(&world.is_visible_to(None)).is_true();
+    assert_that(&world.is_visible_to(Some(&Default::default()))).is_true();
+    assert_that(&world.is_visible_to(Some(&world.owner))).is_true();
+  }
+
+  #[test]
+  fn test_private_world_visible_to_owner() {
+    let world = world_data(Visibility::Private);
+
+    assert_that(&world.is_visible_to(None)).is_false();
+    assert_that(&world.is_visible_to(Some(&Default::default()))).is_false();
+    assert_that(&world.is_visible_to(Some(&world.owner))).is_true();
+  }
 }
@@ -0,0 +1,13 @@
+use crate::{WorldID, WorldRole};
+use chrono::{DateTime, Utc};
+use universe_users::UserID;
+
+/// Struct to represent a user that has been given a role on a world
+#[derive(Debug, PartialEq, Clone)]
+pub struct WorldMember {
+  pub world: WorldID,
+  pub user: UserID,
+  pub role: WorldRole,
+  pub created: DateTime<Utc>,
+  pub updated: DateTime<Utc>,
+}
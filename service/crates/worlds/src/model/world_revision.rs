@@ -0,0 +1,187 @@
+use crate::{CoverID, Slug, Tag, Visibility, WorldData, WorldID};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use universe_users::UserID;
+use uuid::Uuid;
+
+/// Struct to represent an immutable snapshot of the data of a world, as it was immediately after
+/// one of the changes that was made to it
+#[derive(Debug, PartialEq, Clone)]
+pub struct WorldRevision {
+  pub world: WorldID,
+  /// The number of the revision. The first revision of a world is 1, and each change increments it
+  pub revision: u32,
+  /// The version of the world record that this revision is a snapshot of
+  pub version: Uuid,
+  pub created: DateTime<Utc>,
+  pub data: WorldData,
+}
+
+/// Enumeration of the changes that can be made to a single field of a world
+#[derive(Debug, PartialEq, Clone, Serialize)]
+#[serde(tag = "field", rename_all = "camelCase")]
+pub enum WorldChange {
+  Owner {
+    from: UserID,
+    to: UserID,
+  },
+  Name {
+    from: String,
+    to: String,
+  },
+  Slug {
+    from: Slug,
+    to: Slug,
+  },
+  Description {
+    from: String,
+    to: String,
+  },
+  Visibility {
+    from: Visibility,
+    to: Visibility,
+  },
+  ForkedFrom {
+    from: Option<WorldID>,
+    to: Option<WorldID>,
+  },
+  Tags {
+    from: Vec<Tag>,
+    to: Vec<Tag>,
+  },
+  Cover {
+    from: Option<CoverID>,
+    to: Option<CoverID>,
+  },
+}
+
+impl WorldData {
+  /// Determine which fields are different between this world data and some other world data.
+  ///
+  /// # Arguments
+  /// * `other` The world data to compare against
+  ///
+  /// # Returns
+  /// The changes that turn this world data into the other, in the order that the fields are declared
+  pub fn diff(&self, other: &WorldData) -> Vec<WorldChange> {
+    let mut changes = vec![];
+
+    if self.owner != other.owner {
+      changes.push(WorldChange::Owner {
+        from: self.owner.clone(),
+        to: other.owner.clone(),
+      });
+    }
+    if self.name != other.name {
+      changes.push(WorldChange::Name {
+        from: self.name.clone(),
+        to: other.name.clone(),
+      });
+    }
+    if self.slug != other.slug {
+      changes.push(WorldChange::Slug {
+        from: self.slug.clone(),
+        to: other.slug.clone(),
+      });
+    }
+    if self.description != other.description {
+      changes.push(WorldChange::Description {
+        from: self.description.clone(),
+        to: other.description.clone(),
+      });
+    }
+    if self.visibility != other.visibility {
+      changes.push(WorldChange::Visibility {
+        from: self.visibility,
+        to: other.visibility,
+      });
+    }
+    if self.forked_from != other.forked_from {
+      changes.push(WorldChange::ForkedFrom {
+        from: self.forked_from.clone(),
+        to: other.forked_from.clone(),
+      });
+    }
+    if self.tags != other.tags {
+      changes.push(WorldChange::Tags {
+        from: self.tags.clone(),
+        to: other.tags.clone(),
+      });
+    }
+    if self.cover != other.cover {
+      changes.push(WorldChange::Cover {
+        from: self.cover.clone(),
+        to: other.cover.clone(),
+      });
+    }
+
+    changes
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+  use spectral::prelude::*;
+
+  fn world_data() -> WorldData {
+    WorldData {
+      owner: Default::default(),
+      name: "Test World".to_owned(),
+      slug: "test-world".parse().unwrap(),
+      description: "".to_owned(),
+      visibility: Visibility::Public,
+      forked_from: None,
+      tags: vec![],
+      cover: None,
+    }
+  }
+
+  #[test]
+  fn test_diff_identical() {
+    let world = world_data();
+
+    assert_that(&world.diff(&world.clone())).is_empty();
+  }
+
+  #[test]
+  fn test_diff_changes() {
+    let world = world_data();
+    let changed = WorldData {
+      name: "New World".to_owned(),
+      visibility: Visibility::Private,
+      tags: vec!["fantasy".parse().unwrap()],
+      ..world.clone()
+    };
+
+    assert_that(&world.diff(&changed)).is_equal_to(vec![
+      WorldChange::Name {
+        from: "Test World".to_owned(),
+        to: "New World".to_owned(),
+      },
+      WorldChange::Visibility {
+        from: Visibility::Public,
+        to: Visibility::Private,
+      },
+      WorldChange::Tags {
+        from: vec![],
+        to: vec!["fantasy".parse().unwrap()],
+      },
+    ]);
+  }
+
+  #[test]
+  fn test_serialize_change() {
+    let change = WorldChange::ForkedFrom {
+      from: None,
+      to: Some(WorldID::from_uuid(Uuid::nil())),
+    };
+
+    assert_that(&serde_json::to_value(&change).unwrap()).is_equal_to(json!({
+      "field": "forkedFrom",
+      "from": null,
+      "to": "00000000-0000-0000-0000-000000000000"
+    }));
+  }
+}
@@ -0,0 +1,65 @@
+use bytes::BytesMut;
+use postgres::types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
+use serde::Serialize;
+use strum_macros::{Display, EnumString};
+
+/// Enumeration of the roles that a user can have on a world.
+///
+/// Roles are ordered so that a more powerful role compares greater than a less powerful one, and
+/// so every role also grants everything that the roles before it do.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Serialize, Display, EnumString)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum WorldRole {
+  /// The user can see the world, even if it is private
+  Viewer,
+  /// The user can also make changes to the world
+  Editor,
+  /// The user can also manage the world itself, including who its members are
+  Owner,
+}
+
+/// Allow us to pass `WorldRole` values to Postgres as part of executing a database query.
+impl ToSql for WorldRole {
+  fn to_sql(
+    &self,
+    t: &Type,
+    w: &mut BytesMut,
+  ) -> Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+    self.to_string().to_sql(t, w)
+  }
+
+  accepts!(VARCHAR, TEXT);
+  to_sql_checked!();
+}
+
+/// Allow us to read `WorldRole` values from Postgres query results.
+impl<'a> FromSql<'a> for WorldRole {
+  fn from_sql(t: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+    let value = <&str as FromSql>::from_sql(t, raw)?;
+    Ok(value.parse()?)
+  }
+
+  accepts!(VARCHAR, TEXT);
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use spectral::prelude::*;
+
+  #[test]
+  fn test_parse_role() {
+    assert_that(&"owner".parse()).is_ok_containing(WorldRole::Owner);
+    assert_that(&"editor".parse()).is_ok_containing(WorldRole::Editor);
+    assert_that(&"viewer".parse()).is_ok_containing(WorldRole::Viewer);
+    assert_that(&"admin".parse::<WorldRole>()).is_err();
+  }
+
+  #[test]
+  fn test_role_ordering() {
+    assert_that(&(WorldRole::Owner > WorldRole::Editor)).is_true();
+    assert_that(&(WorldRole::Editor > WorldRole::Viewer)).is_true();
+    assert_that(&(WorldRole::Viewer >= WorldRole::Editor)).is_false();
+  }
+}
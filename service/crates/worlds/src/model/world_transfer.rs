@@ -0,0 +1,12 @@
+use crate::WorldID;
+use chrono::{DateTime, Utc};
+use universe_users::UserID;
+
+/// Struct to represent an offer to transfer the ownership of a world to another user
+#[derive(Debug, PartialEq, Clone)]
+pub struct WorldTransfer {
+  pub world: WorldID,
+  pub from: UserID,
+  pub to: UserID,
+  pub created: DateTime<Utc>,
+}
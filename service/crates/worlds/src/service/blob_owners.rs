@@ -0,0 +1,105 @@
+use super::repository::WorldRepository;
+use crate::model::*;
+use universe_storage::BlobOwners;
+use universe_users::UserID;
+
+/// Blob Owners that charge everything stored for a world to the user that owns the world
+pub struct WorldBlobOwners<Repo> {
+  repository: Repo,
+}
+
+/// Create a new source of the owners of world blobs
+///
+/// # Arguments
+/// * `repository` The repository to look up the owners of worlds with
+///
+/// # Returns
+/// The Blob Owners
+pub fn new_world_blob_owners<Repo: WorldRepository + Send + Sync>(
+  repository: Repo,
+) -> impl BlobOwners {
+  WorldBlobOwners { repository }
+}
+
+impl<Repo: WorldRepository + Send + Sync> BlobOwners for WorldBlobOwners<Repo> {
+  fn owner(&self, key: &str) -> Option<String> {
+    let mut segments = key.split('/');
+    if segments.next() != Some("worlds") {
+      return None;
+    }
+
+    let world_id: WorldID = segments.next()?.parse().ok()?;
+    self
+      .repository
+      .get_world_owner(&world_id)
+      .map(|owner| owner.to_string())
+  }
+
+  fn prefixes(&self, owner: &str) -> Vec<String> {
+    match owner.parse::<UserID>() {
+      Ok(owner) => self
+        .repository
+        .list_owned_world_ids(&owner)
+        .iter()
+        .map(|world_id| format!("worlds/{}", world_id))
+        .collect(),
+      Err(_) => vec![],
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::service::repository::MockWorldRepository;
+  use mockall::predicate;
+  use rstest::rstest;
+  use spectral::prelude::*;
+
+  const WORLD: &str = "1ae9e2d6-3a9e-4b5f-9a2c-6e1b0f6f2b1a";
+  const OWNER: &str = "9d8c7b6a-5f4e-4d3c-8b2a-1f0e9d8c7b6a";
+
+  #[rstest(
+    input,
+    expected,
+    case(
+      "worlds/1ae9e2d6-3a9e-4b5f-9a2c-6e1b0f6f2b1a/covers/cover/original",
+      Some(OWNER)
+    ),
+    case(
+      "worlds/1ae9e2d6-3a9e-4b5f-9a2c-6e1b0f6f2b1a/maps/map/image",
+      Some(OWNER)
+    ),
+    case(
+      "worlds/0f0e0d0c-0b0a-4908-8706-050403020100/covers/cover/original",
+      None
+    ),
+    case("worlds/not-a-world/covers/cover/original", None),
+    case("users/1ae9e2d6-3a9e-4b5f-9a2c-6e1b0f6f2b1a/avatar", None)
+  )]
+  fn test_owner(input: &str, expected: Option<&str>) {
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_get_world_owner()
+      .returning(|world_id| Some(OWNER.parse().unwrap()).filter(|_| world_id.to_string() == WORLD));
+
+    let owners = new_world_blob_owners(repository);
+
+    let owner = owners.owner(input);
+    assert_that(&owner).is_equal_to(expected.map(|owner| owner.to_owned()));
+  }
+
+  #[test]
+  fn test_prefixes() {
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_list_owned_world_ids()
+      .with(predicate::eq(OWNER.parse::<UserID>().unwrap()))
+      .returning(|_| vec![WORLD.parse().unwrap()]);
+
+    let owners = new_world_blob_owners(repository);
+
+    assert_that(&owners.prefixes(OWNER)).is_equal_to(vec![format!("worlds/{}", WORLD)]);
+    assert_that(&owners.prefixes("not-a-user")).is_equal_to(Vec::<String>::new());
+  }
+}
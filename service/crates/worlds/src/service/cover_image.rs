@@ -0,0 +1,186 @@
+use crate::CoverSize;
+use image::{imageops::FilterType, io::Reader, DynamicImage, ImageFormat, ImageOutputFormat};
+use std::io::Cursor;
+use strum::IntoEnumIterator;
+use universe_storage::Blob;
+
+/// The largest cover image, in bytes, that we will accept
+pub const MAX_COVER_BYTES: usize = 10 * 1024 * 1024;
+
+/// The largest width or height, in pixels, of a cover image that we will accept
+pub const MAX_COVER_DIMENSION: u32 = 8192;
+
+/// The JPEG quality to encode thumbnails with
+const THUMBNAIL_QUALITY: u8 = 85;
+
+/// Errors that can occur when processing an uploaded cover image
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum CoverImageError {
+  #[error("The image was larger than {} bytes", MAX_COVER_BYTES)]
+  TooLarge,
+  #[error("The image was not a PNG, JPEG or WebP image")]
+  UnsupportedFormat,
+  #[error("The image was wider or taller than {} pixels", MAX_COVER_DIMENSION)]
+  DimensionsTooLarge,
+  #[error("The image could not be decoded")]
+  Malformed,
+}
+
+/// Process an uploaded cover image into every size that we make available.
+///
+/// The format of the image is determined from its contents rather than anything the client told
+/// us, and its dimensions are checked before it is decoded so that we never decode an
+/// unreasonably large image.
+///
+/// # Arguments
+/// * `data` The uploaded image
+///
+/// # Returns
+/// The blob to store for each size of the cover image
+pub fn process_cover_image(data: &[u8]) -> Result<Vec<(CoverSize, Blob)>, CoverImageError> {
+  if data.len() > MAX_COVER_BYTES {
+    return Err(CoverImageError::TooLarge);
+  }
+
+  let format = image::guess_format(data).map_err(|_| CoverImageError::UnsupportedFormat)?;
+  let content_type = match format {
+    ImageFormat::Png => "image/png",
+    ImageFormat::Jpeg => "image/jpeg",
+    ImageFormat::WebP => "image/webp",
+    _ => return Err(CoverImageError::UnsupportedFormat),
+  };
+
+  let (width, height) = Reader::with_format(Cursor::new(data), format)
+    .into_dimensions()
+    .map_err(|_| CoverImageError::Malformed)?;
+  if width > MAX_COVER_DIMENSION || height > MAX_COVER_DIMENSION {
+    return Err(CoverImageError::DimensionsTooLarge);
+  }
+
+  let image =
+    image::load_from_memory_with_format(data, format).map_err(|_| CoverImageError::Malformed)?;
+
+  CoverSize::iter()
+    .map(|size| match size.dimensions() {
+      None => Ok((
+        size,
+        Blob {
+          content_type: content_type.to_owned(),
+          data: data.to_vec(),
+        },
+      )),
+      Some((width, height)) => {
+        let thumbnail = image.resize_to_fill(width, height, FilterType::Lanczos3);
+        let mut output = vec![];
+        DynamicImage::ImageRgb8(thumbnail.to_rgb8())
+          .write_to(&mut output, ImageOutputFormat::Jpeg(THUMBNAIL_QUALITY))
+          .map_err(|_| CoverImageError::Malformed)?;
+
+        Ok((
+          size,
+          Blob {
+            content_type: "image/jpeg".to_owned(),
+            data: output,
+          },
+        ))
+      }
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use assert_matches::assert_matches;
+  use image::{GenericImageView, ImageBuffer, Rgba};
+  use spectral::prelude::*;
+
+  fn encode_image(width: u32, height: u32, format: ImageOutputFormat) -> Vec<u8> {
+    let image = ImageBuffer::from_pixel(width, height, Rgba([200u8, 100, 50, 255]));
+    let mut output = vec![];
+    DynamicImage::ImageRgba8(image)
+      .write_to(&mut output, format)
+      .unwrap();
+    output
+  }
+
+  fn dimensions(blob: &Blob) -> (u32, u32) {
+    let image = image::load_from_memory(&blob.data).unwrap();
+    (image.width(), image.height())
+  }
+
+  #[test]
+  fn test_process_png() {
+    let data = encode_image(640, 480, ImageOutputFormat::Png);
+
+    let result = process_cover_image(&data).unwrap();
+
+    let sizes: Vec<CoverSize> = result.iter().map(|(size, _)| *size).collect();
+    assert_that(&sizes).is_equal_to(vec![
+      CoverSize::Original,
+      CoverSize::Small,
+      CoverSize::Large,
+    ]);
+
+    assert_that(&result[0].1.content_type).is_equal_to("image/png".to_owned());
+    assert_that(&result[0].1.data).is_equal_to(data);
+
+    assert_that(&result[1].1.content_type).is_equal_to("image/jpeg".to_owned());
+    assert_that(&dimensions(&result[1].1)).is_equal_to((320, 180));
+
+    assert_that(&result[2].1.content_type).is_equal_to("image/jpeg".to_owned());
+    assert_that(&dimensions(&result[2].1)).is_equal_to((1280, 720));
+  }
+
+  #[test]
+  fn test_process_jpeg() {
+    let data = encode_image(100, 400, ImageOutputFormat::Jpeg(90));
+
+    let result = process_cover_image(&data).unwrap();
+
+    assert_that(&result[0].1.content_type).is_equal_to("image/jpeg".to_owned());
+    assert_that(&dimensions(&result[1].1)).is_equal_to((320, 180));
+  }
+
+  #[test]
+  fn test_process_unsupported_format() {
+    let result = process_cover_image(b"GIF89a\x01\x00\x01\x00\x00\x00\x00;");
+
+    assert_matches!(result, Err(CoverImageError::UnsupportedFormat));
+  }
+
+  #[test]
+  fn test_process_not_an_image() {
+    let result = process_cover_image(b"Hello, World");
+
+    assert_matches!(result, Err(CoverImageError::UnsupportedFormat));
+  }
+
+  #[test]
+  fn test_process_truncated_image() {
+    let data = encode_image(100, 100, ImageOutputFormat::Png);
+
+    let result = process_cover_image(&data[..data.len() / 2]);
+
+    assert_matches!(result, Err(CoverImageError::Malformed));
+  }
+
+  #[test]
+  fn test_process_too_large() {
+    let mut data = encode_image(10, 10, ImageOutputFormat::Png);
+    data.resize(MAX_COVER_BYTES + 1, 0);
+
+    let result = process_cover_image(&data);
+
+    assert_matches!(result, Err(CoverImageError::TooLarge));
+  }
+
+  #[test]
+  fn test_process_dimensions_too_large() {
+    let data = encode_image(MAX_COVER_DIMENSION + 1, 1, ImageOutputFormat::Png);
+
+    let result = process_cover_image(&data);
+
+    assert_matches!(result, Err(CoverImageError::DimensionsTooLarge));
+  }
+}
@@ -0,0 +1,438 @@
+use super::cover_image::{process_cover_image, CoverImageError};
+use super::repository::{PersistWorldError, WorldRepository};
+use crate::model::*;
+use std::sync::Arc;
+use strum::IntoEnumIterator;
+use tracing::{debug, warn};
+use universe_storage::{Blob, BlobStore};
+
+/// Service for managing the cover images of worlds
+pub trait WorldCoverService: Send + Sync {
+  /// Upload a new cover image for a world, replacing any existing one.
+  ///
+  /// The image is stored as it was uploaded, along with a fixed-size thumbnail for every other
+  /// cover size.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  /// * `image` The raw bytes of the uploaded image
+  ///
+  /// # Returns
+  /// The world with its new cover image
+  fn set_world_cover(
+    &self,
+    world_id: &WorldID,
+    image: &[u8],
+  ) -> Result<WorldEntity, SetWorldCoverError>;
+
+  /// Remove the cover image from a world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  ///
+  /// # Returns
+  /// The world without its cover image
+  fn remove_world_cover(&self, world_id: &WorldID) -> Result<WorldEntity, RemoveWorldCoverError>;
+
+  /// Retrieve one size of a cover image of a world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  /// * `cover_id` The ID of the cover image
+  /// * `size` The size of the cover image to retrieve
+  ///
+  /// # Returns
+  /// The image, or `None` if it doesn't exist
+  fn get_world_cover(
+    &self,
+    world_id: &WorldID,
+    cover_id: &CoverID,
+    size: CoverSize,
+  ) -> Option<Blob>;
+}
+
+/// Enumeration of reasons why we failed to set the cover image of a world
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum SetWorldCoverError {
+  #[error("The world was not found")]
+  UnknownWorld,
+  #[error("The image was not acceptable: {0}")]
+  InvalidImage(#[from] CoverImageError),
+  #[error("The version of the world record did not match")]
+  OptimisticLockFailure,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to remove the cover image of a world
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum RemoveWorldCoverError {
+  #[error("The world was not found")]
+  UnknownWorld,
+  #[error("The world has no cover image")]
+  UnknownCover,
+  #[error("The version of the world record did not match")]
+  OptimisticLockFailure,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// The World Cover Service to allow working with the cover images of worlds
+pub struct WorldCoverServiceImpl<Repo> {
+  repository: Repo,
+  blob_store: Arc<dyn BlobStore>,
+}
+
+/// Create a new World Cover Service
+///
+/// # Arguments
+/// * `repository` The repository to load and save worlds with
+/// * `blob_store` The store to keep the cover images in
+///
+/// # Returns
+/// The World Cover Service
+pub fn new_world_cover_service<Repo: WorldRepository + Send + Sync>(
+  repository: Repo,
+  blob_store: Arc<dyn BlobStore>,
+) -> impl WorldCoverService {
+  WorldCoverServiceImpl {
+    repository,
+    blob_store,
+  }
+}
+
+/// Build the key that one size of a cover image of a world is stored under
+pub(crate) fn cover_key(world_id: &WorldID, cover_id: &CoverID, size: CoverSize) -> String {
+  format!("worlds/{}/covers/{}/{}", world_id, cover_id, size)
+}
+
+impl<Repo: WorldRepository + Send + Sync> WorldCoverServiceImpl<Repo> {
+  /// Delete every size of a cover image of a world. Failures are logged but otherwise ignored,
+  /// since all they leave behind is some unreferenced images
+  fn delete_cover(&self, world_id: &WorldID, cover_id: &CoverID) {
+    for size in CoverSize::iter() {
+      let key = cover_key(world_id, cover_id, size);
+      if let Err(e) = self.blob_store.delete(&key) {
+        warn!("Failed to delete cover image {}: {}", key, e);
+      }
+    }
+  }
+}
+
+impl<Repo: WorldRepository + Send + Sync> WorldCoverService for WorldCoverServiceImpl<Repo> {
+  fn set_world_cover(
+    &self,
+    world_id: &WorldID,
+    image: &[u8],
+  ) -> Result<WorldEntity, SetWorldCoverError> {
+    let world = self
+      .repository
+      .get_world_by_id(world_id)
+      .ok_or(SetWorldCoverError::UnknownWorld)?;
+
+    let images = process_cover_image(image)?;
+
+    let cover_id = CoverID::default();
+    debug!("Storing cover image {} for world {}", cover_id, world_id);
+    for (size, blob) in images {
+      if let Err(e) = self
+        .blob_store
+        .put(&cover_key(world_id, &cover_id, size), &blob)
+      {
+        warn!("Failed to store cover image: {}", e);
+        self.delete_cover(world_id, &cover_id);
+        return Err(SetWorldCoverError::UnknownError);
+      }
+    }
+
+    let previous = world.data.cover.clone();
+    let saved = self
+      .repository
+      .update_world(WorldEntity {
+        identity: world.identity,
+        data: WorldData {
+          cover: Some(cover_id.clone()),
+          ..world.data
+        },
+        stars: world.stars,
+      })
+      .map_err(|e| {
+        self.delete_cover(world_id, &cover_id);
+        e
+      })?;
+
+    if let Some(previous) = previous {
+      self.delete_cover(world_id, &previous);
+    }
+
+    Ok(saved)
+  }
+
+  fn remove_world_cover(&self, world_id: &WorldID) -> Result<WorldEntity, RemoveWorldCoverError> {
+    let world = self
+      .repository
+      .get_world_by_id(world_id)
+      .ok_or(RemoveWorldCoverError::UnknownWorld)?;
+    let cover_id = world
+      .data
+      .cover
+      .clone()
+      .ok_or(RemoveWorldCoverError::UnknownCover)?;
+
+    let saved = self.repository.update_world(WorldEntity {
+      identity: world.identity,
+      data: WorldData {
+        cover: None,
+        ..world.data
+      },
+      stars: world.stars,
+    })?;
+
+    self.delete_cover(world_id, &cover_id);
+
+    Ok(saved)
+  }
+
+  fn get_world_cover(
+    &self,
+    world_id: &WorldID,
+    cover_id: &CoverID,
+    size: CoverSize,
+  ) -> Option<Blob> {
+    self
+      .blob_store
+      .get(&cover_key(world_id, cover_id, size))
+      .unwrap_or_else(|e| {
+        warn!("Failed to load cover image: {}", e);
+        None
+      })
+  }
+}
+
+impl From<PersistWorldError> for SetWorldCoverError {
+  fn from(e: PersistWorldError) -> Self {
+    warn!("Error setting world cover: {}", e);
+    match e {
+      PersistWorldError::WorldNotFound => SetWorldCoverError::UnknownWorld,
+      PersistWorldError::OptimisticLockFailure => SetWorldCoverError::OptimisticLockFailure,
+      _ => SetWorldCoverError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistWorldError> for RemoveWorldCoverError {
+  fn from(e: PersistWorldError) -> Self {
+    warn!("Error removing world cover: {}", e);
+    match e {
+      PersistWorldError::WorldNotFound => RemoveWorldCoverError::UnknownWorld,
+      PersistWorldError::OptimisticLockFailure => RemoveWorldCoverError::OptimisticLockFailure,
+      _ => RemoveWorldCoverError::UnknownError,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::super::repository::MockWorldRepository;
+  use super::*;
+  use assert_matches::assert_matches;
+  use chrono::Utc;
+  use image::{DynamicImage, ImageBuffer, ImageOutputFormat, Rgb};
+  use mockall::predicate::*;
+  use spectral::prelude::*;
+  use universe_entity::Identity;
+  use universe_storage::MemoryBlobStore;
+  use universe_users::UserID;
+
+  fn world(cover: Option<CoverID>) -> WorldEntity {
+    WorldEntity {
+      identity: Identity {
+        id: WorldID::from_uuid("6c1a3f4e-5b8d-4f2a-9e1c-7d3b2a1f0e9d".parse().unwrap()),
+        version: uuid::Uuid::new_v4(),
+        created: Utc::now(),
+        updated: Utc::now(),
+      },
+      data: WorldData {
+        owner: UserID::default(),
+        name: "Test World".to_owned(),
+        slug: "test-world".parse().unwrap(),
+        description: "".to_owned(),
+        visibility: Visibility::Public,
+        forked_from: None,
+        tags: vec![],
+        cover,
+      },
+      stars: 0,
+    }
+  }
+
+  fn png() -> Vec<u8> {
+    let image = ImageBuffer::from_pixel(64, 64, Rgb([10u8, 20, 30]));
+    let mut output = vec![];
+    DynamicImage::ImageRgb8(image)
+      .write_to(&mut output, ImageOutputFormat::Png)
+      .unwrap();
+    output
+  }
+
+  fn cover_keys(world: &WorldEntity, cover_id: &CoverID) -> Vec<String> {
+    vec![
+      cover_key(&world.identity.id, cover_id, CoverSize::Large),
+      cover_key(&world.identity.id, cover_id, CoverSize::Original),
+      cover_key(&world.identity.id, cover_id, CoverSize::Small),
+    ]
+  }
+
+  #[test]
+  fn test_set_cover_unknown_world() {
+    let world = world(None);
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_get_world_by_id()
+      .with(eq(world.identity.id.clone()))
+      .times(1)
+      .return_const(None);
+    let blob_store = Arc::new(MemoryBlobStore::default());
+
+    let service = new_world_cover_service(repository, blob_store.clone());
+    let result = service.set_world_cover(&world.identity.id, &png());
+
+    assert_matches!(result, Err(SetWorldCoverError::UnknownWorld));
+    assert_that(&blob_store.keys()).is_empty();
+  }
+
+  #[test]
+  fn test_set_cover_invalid_image() {
+    let world = world(None);
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_get_world_by_id()
+      .times(1)
+      .return_const(Some(world.clone()));
+    let blob_store = Arc::new(MemoryBlobStore::default());
+
+    let service = new_world_cover_service(repository, blob_store.clone());
+    let result = service.set_world_cover(&world.identity.id, b"Hello, World");
+
+    assert_matches!(
+      result,
+      Err(SetWorldCoverError::InvalidImage(
+        CoverImageError::UnsupportedFormat
+      ))
+    );
+    assert_that(&blob_store.keys()).is_empty();
+  }
+
+  #[test]
+  fn test_set_cover_replaces_existing() {
+    let previous = CoverID::default();
+    let world = world(Some(previous.clone()));
+    let blob_store = Arc::new(MemoryBlobStore::default());
+    for key in cover_keys(&world, &previous) {
+      blob_store
+        .put(
+          &key,
+          &Blob {
+            content_type: "image/png".to_owned(),
+            data: vec![],
+          },
+        )
+        .unwrap();
+    }
+
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_get_world_by_id()
+      .times(1)
+      .return_const(Some(world.clone()));
+    repository
+      .expect_update_world()
+      .withf(|world| world.data.cover.is_some())
+      .times(1)
+      .returning(Ok);
+
+    let service = new_world_cover_service(repository, blob_store.clone());
+    let result = service.set_world_cover(&world.identity.id, &png()).unwrap();
+
+    let cover_id = result.data.cover.unwrap();
+    assert_that(&cover_id).is_not_equal_to(&previous);
+
+    let mut expected = cover_keys(&world, &cover_id);
+    expected.sort();
+    assert_that(&blob_store.keys()).is_equal_to(expected);
+
+    let original = service.get_world_cover(&world.identity.id, &cover_id, CoverSize::Original);
+    assert_that(&original.unwrap().data).is_equal_to(png());
+  }
+
+  #[test]
+  fn test_set_cover_update_fails() {
+    let world = world(None);
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_get_world_by_id()
+      .times(1)
+      .return_const(Some(world.clone()));
+    repository
+      .expect_update_world()
+      .times(1)
+      .returning(|_| Err(PersistWorldError::OptimisticLockFailure));
+    let blob_store = Arc::new(MemoryBlobStore::default());
+
+    let service = new_world_cover_service(repository, blob_store.clone());
+    let result = service.set_world_cover(&world.identity.id, &png());
+
+    assert_matches!(result, Err(SetWorldCoverError::OptimisticLockFailure));
+    assert_that(&blob_store.keys()).is_empty();
+  }
+
+  #[test]
+  fn test_remove_cover() {
+    let cover_id = CoverID::default();
+    let world = world(Some(cover_id.clone()));
+    let blob_store = Arc::new(MemoryBlobStore::default());
+    for key in cover_keys(&world, &cover_id) {
+      blob_store
+        .put(
+          &key,
+          &Blob {
+            content_type: "image/png".to_owned(),
+            data: vec![],
+          },
+        )
+        .unwrap();
+    }
+
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_get_world_by_id()
+      .times(1)
+      .return_const(Some(world.clone()));
+    repository
+      .expect_update_world()
+      .withf(|world| world.data.cover.is_none())
+      .times(1)
+      .returning(Ok);
+
+    let service = new_world_cover_service(repository, blob_store.clone());
+    let result = service.remove_world_cover(&world.identity.id).unwrap();
+
+    assert_that(&result.data.cover).is_none();
+    assert_that(&blob_store.keys()).is_empty();
+  }
+
+  #[test]
+  fn test_remove_cover_without_cover() {
+    let world = world(None);
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_get_world_by_id()
+      .times(1)
+      .return_const(Some(world.clone()));
+
+    let service = new_world_cover_service(repository, Arc::new(MemoryBlobStore::default()));
+    let result = service.remove_world_cover(&world.identity.id);
+
+    assert_matches!(result, Err(RemoveWorldCoverError::UnknownCover));
+  }
+}
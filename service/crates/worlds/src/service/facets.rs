@@ -0,0 +1,17 @@
+use serde::Serialize;
+use strum_macros::{Display, EnumString};
+
+/// Facets of the worlds that match a search that we can count the values of
+#[derive(Debug, PartialEq, Clone, Copy, Display, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum WorldFacet {
+  Tags,
+  Owners,
+}
+
+/// The number of worlds matching a search that have a single value of a facet
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct FacetCount {
+  pub value: String,
+  pub count: u32,
+}
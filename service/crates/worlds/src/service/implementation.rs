@@ -1,23 +1,127 @@
-use super::{repository::*, service::*, WorldFilters, WorldSorts};
+use super::covers::cover_key;
+use super::{repository::*, service::*, FacetCount, WorldFacet, WorldFilters, WorldSorts};
 use crate::model::*;
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use strum::IntoEnumIterator;
+use tracing::{debug, info, warn};
 use universe_entity::{Page, Pagination, SortField};
+use universe_storage::{BlobStore, StorageError};
+use universe_users::UserID;
+
+/// The maximum number of suffixes to try when generating a unique slug for a world
+const MAX_SLUG_SUFFIX: u32 = 1000;
+
+/// The maximum number of values of a facet to count when searching for worlds
+const MAX_FACET_VALUES: u32 = 20;
 
 /// The World Service to allow interactoins with world entities
 pub struct WorldServiceImpl<Repo> {
   repository: Repo,
+  blob_store: Arc<dyn BlobStore>,
 }
 
 /// Create a new World Service
 ///
+/// # Arguments
+/// * `repository` The repository to load and save worlds with
+/// * `blob_store` The store that the files belonging to worlds are kept in
+///
 /// # Returns
 /// The World Service
 pub fn new_world_service<Repo: WorldRepository + Send + Sync>(
   repository: Repo,
+  blob_store: Arc<dyn BlobStore>,
 ) -> impl WorldService {
-  WorldServiceImpl { repository }
+  WorldServiceImpl {
+    repository,
+    blob_store,
+  }
+}
+
+impl<Repo: WorldRepository + Send + Sync> WorldServiceImpl<Repo> {
+  /// Delete every file that is stored for a world. Everything stored for a world lives under the
+  /// same prefix, so it can all go at once. Failures are logged but otherwise ignored, since all
+  /// they leave behind is some unreferenced files
+  fn delete_world_files(&self, world_id: &WorldID) {
+    let prefix = format!("worlds/{}", world_id);
+    if let Err(e) = self.blob_store.delete_all(&prefix) {
+      warn!("Failed to delete files of world {}: {}", world_id, e);
+    }
+  }
+
+  /// Copy every size of a cover image from one world to another
+  ///
+  /// # Arguments
+  /// * `from_world` The ID of the world to copy the cover image from
+  /// * `from_cover` The ID of the cover image to copy
+  /// * `to_world` The ID of the world to copy the cover image to
+  /// * `to_cover` The ID to give the copy of the cover image
+  fn copy_cover(
+    &self,
+    from_world: &WorldID,
+    from_cover: &CoverID,
+    to_world: &WorldID,
+    to_cover: &CoverID,
+  ) -> Result<(), StorageError> {
+    for size in CoverSize::iter() {
+      if let Some(blob) = self
+        .blob_store
+        .get(&cover_key(from_world, from_cover, size))?
+      {
+        self
+          .blob_store
+          .put(&cover_key(to_world, to_cover, size), &blob)?;
+      }
+    }
+
+    Ok(())
+  }
 }
 
 impl<Repo: WorldRepository + Send + Sync> WorldService for WorldServiceImpl<Repo> {
+  /// Retrieve the world that has the given unique ID
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to retrieve
+  ///
+  /// # Returns
+  /// The world, or `None` if it wasn't found
+  fn get_world_by_id(&self, world_id: &WorldID) -> Option<WorldEntity> {
+    let world = self.repository.get_world_by_id(world_id);
+
+    if world.is_none() {
+      warn!("No world found with ID {}", world_id);
+    }
+
+    world
+  }
+
+  /// Retrieve the world that is owned by the given user and has the given slug
+  ///
+  /// # Arguments
+  /// * `owner` The ID of the user that owns the world
+  /// * `slug` The slug of the world
+  ///
+  /// # Returns
+  /// The world, or `None` if it wasn't found
+  fn get_world_by_slug(&self, owner: &UserID, slug: &Slug) -> Option<WorldEntity> {
+    self.repository.get_world_by_slug(owner, slug)
+  }
+
+  /// Retrieve the world that is owned by the given user and used to have the given slug before
+  /// it was changed
+  ///
+  /// # Arguments
+  /// * `owner` The ID of the user that owns the world
+  /// * `slug` The previous slug of the world
+  ///
+  /// # Returns
+  /// The world, or `None` if it wasn't found
+  fn get_world_by_previous_slug(&self, owner: &UserID, slug: &Slug) -> Option<WorldEntity> {
+    self.repository.get_world_by_previous_slug(owner, slug)
+  }
+
   /// Perform a search for all the worlds that match the given filters, sorted in the requested order.
   ///
   /// # Arguments
@@ -35,4 +139,1951 @@ impl<Repo: WorldRepository + Send + Sync> WorldService for WorldServiceImpl<Repo
   ) -> Page<WorldEntity> {
     self.repository.search_worlds(filters, sorts, pagination)
   }
+
+  /// Count how many of the worlds that match the given filters have each value of a facet, most
+  /// common values first. Only the most common values are returned
+  ///
+  /// # Arguments
+  /// * `filters` The filters to apply when searching for worlds
+  /// * `facet` The facet to count the values of
+  ///
+  /// # Returns
+  /// The counts of each value of the facet
+  fn count_world_facet(&self, filters: WorldFilters, facet: WorldFacet) -> Vec<FacetCount> {
+    self
+      .repository
+      .count_world_facet(filters, facet, MAX_FACET_VALUES)
+  }
+
+  /// Create a new world
+  ///
+  /// # Arguments
+  /// * `world` The world data to create the world from
+  ///
+  /// # Returns
+  /// The world that was persisted
+  fn create_world(&self, world: WorldData) -> Result<WorldEntity, CreateWorldError> {
+    let created = self.repository.create_world(world)?;
+    Ok(created)
+  }
+
+  /// Create a new world, treating the slug as a starting point.
+  ///
+  /// If the slug is already used by another world with the same owner then a numeric suffix is
+  /// appended to it until it is unique.
+  ///
+  /// # Arguments
+  /// * `world` The world data to create the world from
+  ///
+  /// # Returns
+  /// The world that was persisted
+  fn create_world_with_unique_slug(
+    &self,
+    world: WorldData,
+  ) -> Result<WorldEntity, CreateWorldError> {
+    let base_slug = world.slug.clone();
+    let mut candidate = world;
+
+    for suffix in 2..=MAX_SLUG_SUFFIX {
+      match self.repository.create_world(candidate.clone()) {
+        Err(PersistWorldError::DuplicateSlug) => {
+          debug!("Slug {} is already in use", candidate.slug);
+          candidate.slug = base_slug.with_suffix(suffix);
+        }
+        result => return Ok(result?),
+      }
+    }
+
+    warn!("Unable to find a unique slug based on {}", base_slug);
+    Err(CreateWorldError::ValidationError(vec![
+      WorldValidationError::DuplicateSlug,
+    ]))
+  }
+
+  /// Create a copy of an existing world, owned by the given user.
+  ///
+  /// The new world records which world it was forked from, and has a slug based on the slug of the
+  /// original world that is unique for the new owner. The cover image of the original world is
+  /// copied to the new world as well.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to fork
+  /// * `owner` The ID of the user that is to own the new world
+  ///
+  /// # Returns
+  /// The newly created world
+  fn fork_world(&self, world_id: &WorldID, owner: &UserID) -> Result<WorldEntity, ForkWorldError> {
+    let original = self
+      .get_world_by_id(world_id)
+      .ok_or(ForkWorldError::UnknownWorld)?;
+
+    // Cover images are stored against the world they were uploaded to, so the fork gets its own
+    // copy of the cover under a new ID
+    let original_cover = original.data.cover.clone();
+    let fork_cover = original_cover.as_ref().map(|_| CoverID::default());
+
+    let fork = self.create_world_with_unique_slug(WorldData {
+      owner: owner.clone(),
+      forked_from: Some(original.identity.id),
+      cover: fork_cover.clone(),
+      ..original.data
+    })?;
+    info!("Forked world {} into {}", world_id, fork.identity.id);
+
+    if let (Some(from), Some(to)) = (original_cover, fork_cover) {
+      if let Err(e) = self.copy_cover(world_id, &from, &fork.identity.id, &to) {
+        warn!("Failed to copy cover image into fork: {}", e);
+        if let Err(e) = self.purge_world(&fork.identity.id) {
+          warn!("Failed to discard fork {}: {}", fork.identity.id, e);
+        }
+        return Err(ForkWorldError::UnknownError);
+      }
+    }
+
+    Ok(fork)
+  }
+
+  /// Update an existing world.
+  ///
+  /// This will load the world by ID, and then call a provided callback to mutate the world before persisting
+  /// the changes back to the database
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to update
+  /// * `updater` The callback to mutate the world with
+  ///
+  /// # Returns
+  /// The newly updated world
+  fn update_world(
+    &self,
+    world_id: &WorldID,
+    updater: &mut dyn FnMut(WorldData) -> Result<WorldData, Box<dyn std::error::Error>>,
+  ) -> Result<WorldEntity, UpdateWorldError> {
+    let world = self
+      .get_world_by_id(world_id)
+      .ok_or(UpdateWorldError::UnknownWorld)?;
+
+    let updated = updater(world.data).map_err(UpdateWorldError::UpdateError)?;
+
+    let saved = self.repository.update_world(WorldEntity {
+      identity: world.identity,
+      data: updated,
+      stars: world.stars,
+    })?;
+    Ok(saved)
+  }
+
+  /// Retrieve the revisions of a world, most recent first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the revisions of
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of revisions
+  fn list_world_revisions(
+    &self,
+    world_id: &WorldID,
+    pagination: Pagination,
+  ) -> Page<WorldRevision> {
+    self.repository.list_world_revisions(world_id, pagination)
+  }
+
+  /// Retrieve a single revision of a world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  /// * `revision` The number of the revision to retrieve
+  ///
+  /// # Returns
+  /// The revision, or `None` if it wasn't found
+  fn get_world_revision(&self, world_id: &WorldID, revision: u32) -> Option<WorldRevision> {
+    self.repository.get_world_revision(world_id, revision)
+  }
+
+  /// Restore a world to the state it was in at an earlier revision
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to restore
+  /// * `revision` The number of the revision to restore
+  ///
+  /// # Returns
+  /// The newly updated world
+  fn restore_world_revision(
+    &self,
+    world_id: &WorldID,
+    revision: u32,
+  ) -> Result<WorldEntity, RestoreWorldRevisionError> {
+    let world = self
+      .get_world_by_id(world_id)
+      .ok_or(RestoreWorldRevisionError::UnknownWorld)?;
+    let revision = self
+      .repository
+      .get_world_revision(world_id, revision)
+      .ok_or(RestoreWorldRevisionError::UnknownRevision)?;
+    debug!("Restoring world {} to revision {:?}", world_id, revision);
+
+    let restored = self.repository.update_world(WorldEntity {
+      identity: world.identity,
+      data: WorldData {
+        name: revision.data.name,
+        slug: revision.data.slug,
+        description: revision.data.description,
+        visibility: revision.data.visibility,
+        tags: revision.data.tags,
+        ..world.data
+      },
+      stars: world.stars,
+    })?;
+    Ok(restored)
+  }
+
+  /// Move a world into the owners trash
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to delete
+  fn delete_world(&self, world_id: &WorldID) -> Result<(), DeleteWorldError> {
+    self.repository.delete_world(world_id)?;
+    Ok(())
+  }
+
+  /// Retrieve the worlds that are in the trash of the given user
+  ///
+  /// # Arguments
+  /// * `owner` The ID of the user whose trash should be listed
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of trashed worlds
+  fn list_trashed_worlds(&self, owner: &UserID, pagination: Pagination) -> Page<TrashedWorld> {
+    self.repository.list_trashed_worlds(owner, pagination)
+  }
+
+  /// Restore a world out of the trash of the given user
+  ///
+  /// # Arguments
+  /// * `owner` The ID of the user whose trash the world is in
+  /// * `world_id` The ID of the world to restore
+  ///
+  /// # Returns
+  /// The world that was restored
+  fn restore_world(
+    &self,
+    owner: &UserID,
+    world_id: &WorldID,
+  ) -> Result<WorldEntity, RestoreWorldError> {
+    let restored = self.repository.restore_world(owner, world_id)?;
+    Ok(restored)
+  }
+
+  /// Permanently remove all of the worlds that have been in the trash for longer than the retention period
+  ///
+  /// # Arguments
+  /// * `retention` How long worlds are kept in the trash before being purged
+  ///
+  /// # Returns
+  /// The number of worlds that were purged
+  fn purge_trashed_worlds(&self, retention: Duration) -> u64 {
+    let deleted_before = Utc::now() - retention;
+
+    match self.repository.purge_trashed_worlds(deleted_before) {
+      Ok(purged) => {
+        info!("Purged {} worlds from the trash", purged.len());
+        for world_id in &purged {
+          self.delete_world_files(world_id);
+        }
+        purged.len() as u64
+      }
+      Err(e) => {
+        warn!("Error purging worlds from the trash: {}", e);
+        0
+      }
+    }
+  }
+
+  /// Permanently remove a world, along with all of its content, without it going through the
+  /// trash
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to remove
+  fn purge_world(&self, world_id: &WorldID) -> Result<(), DeleteWorldError> {
+    self.repository.purge_world(world_id)?;
+    info!("Purged world {}", world_id);
+    self.delete_world_files(world_id);
+
+    Ok(())
+  }
+
+  /// Determine the role that the given user has on a world.
+  ///
+  /// The owner of the world always has the `Owner` role, even if they aren't listed as a member
+  ///
+  /// # Arguments
+  /// * `world` The world to check
+  /// * `user_id` The ID of the user to check
+  ///
+  /// # Returns
+  /// The role of the user, or `None` if they have no role on the world
+  fn get_world_role(&self, world: &WorldEntity, user_id: &UserID) -> Option<WorldRole> {
+    if &world.data.owner == user_id {
+      Some(WorldRole::Owner)
+    } else {
+      self
+        .repository
+        .get_world_member(&world.identity.id, user_id)
+        .map(|member| member.role)
+    }
+  }
+
+  /// Determine whether the given user is able to see a world
+  ///
+  /// # Arguments
+  /// * `world` The world to check
+  /// * `user_id` The ID of the user trying to see the world, or `None` if they are anonymous
+  ///
+  /// # Returns
+  /// True if the user is allowed to see the world
+  fn is_world_visible_to(&self, world: &WorldEntity, user_id: Option<&UserID>) -> bool {
+    world.data.is_visible_to(user_id)
+      || user_id
+        .and_then(|user_id| self.get_world_role(world, user_id))
+        .is_some()
+  }
+
+  /// Retrieve all of the members of the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the members of
+  ///
+  /// # Returns
+  /// The members of the world
+  fn list_world_members(&self, world_id: &WorldID) -> Vec<WorldMember> {
+    self.repository.list_world_members(world_id)
+  }
+
+  /// Add a new member to a world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to add the member to
+  /// * `user_id` The ID of the user to add as a member
+  /// * `role` The role that the user has on the world
+  ///
+  /// # Returns
+  /// The new membership
+  fn add_world_member(
+    &self,
+    world_id: &WorldID,
+    user_id: &UserID,
+    role: WorldRole,
+  ) -> Result<WorldMember, AddWorldMemberError> {
+    let world = self
+      .get_world_by_id(world_id)
+      .ok_or(AddWorldMemberError::UnknownWorld)?;
+
+    if &world.data.owner == user_id {
+      warn!("User {} already owns world {}", user_id, world_id);
+      return Err(AddWorldMemberError::DuplicateMember);
+    }
+
+    let member = self
+      .repository
+      .create_world_member(world_id, user_id, role)?;
+    Ok(member)
+  }
+
+  /// Change the role of an existing member of a world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  /// * `user_id` The ID of the member
+  /// * `role` The new role that the user has on the world
+  ///
+  /// # Returns
+  /// The updated membership
+  fn update_world_member(
+    &self,
+    world_id: &WorldID,
+    user_id: &UserID,
+    role: WorldRole,
+  ) -> Result<WorldMember, UpdateWorldMemberError> {
+    let member = self
+      .repository
+      .update_world_member(world_id, user_id, role)?;
+    Ok(member)
+  }
+
+  /// Remove a member from a world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  /// * `user_id` The ID of the member to remove
+  fn remove_world_member(
+    &self,
+    world_id: &WorldID,
+    user_id: &UserID,
+  ) -> Result<(), RemoveWorldMemberError> {
+    self.repository.delete_world_member(world_id, user_id)?;
+    Ok(())
+  }
+
+  /// Star a world on behalf of a user
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to star
+  /// * `user_id` The ID of the user starring the world
+  fn star_world(&self, world_id: &WorldID, user_id: &UserID) -> Result<(), StarWorldError> {
+    self.repository.star_world(world_id, user_id)?;
+    Ok(())
+  }
+
+  /// Remove the star that a user gave to a world, if there is one
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to unstar
+  /// * `user_id` The ID of the user unstarring the world
+  fn unstar_world(&self, world_id: &WorldID, user_id: &UserID) -> Result<(), UnstarWorldError> {
+    self.repository.unstar_world(world_id, user_id)?;
+    Ok(())
+  }
+
+  /// Retrieve the pending ownership transfer of a world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  ///
+  /// # Returns
+  /// The transfer, or `None` if the world isn't being transferred
+  fn get_world_transfer(&self, world_id: &WorldID) -> Option<WorldTransfer> {
+    self.repository.get_world_transfer(world_id)
+  }
+
+  /// Retrieve all of the pending ownership transfers that have been offered to a user
+  ///
+  /// # Arguments
+  /// * `user_id` The ID of the user that the worlds are being offered to
+  ///
+  /// # Returns
+  /// The transfers
+  fn list_world_transfers_to(&self, user_id: &UserID) -> Vec<WorldTransfer> {
+    self.repository.list_world_transfers_to(user_id)
+  }
+
+  /// Offer the ownership of a world to another user. This replaces any previous offer for the
+  /// same world.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to transfer
+  /// * `to` The ID of the user to offer the world to
+  ///
+  /// # Returns
+  /// The pending transfer
+  fn offer_world_transfer(
+    &self,
+    world_id: &WorldID,
+    to: &UserID,
+  ) -> Result<WorldTransfer, OfferWorldTransferError> {
+    let world = self
+      .get_world_by_id(world_id)
+      .ok_or(OfferWorldTransferError::UnknownWorld)?;
+
+    if &world.data.owner == to {
+      warn!("User {} already owns world {}", to, world_id);
+      return Err(OfferWorldTransferError::SameOwner);
+    }
+
+    // Fail early if the transfer could never be accepted
+    if self
+      .repository
+      .get_world_by_slug(to, &world.data.slug)
+      .is_some()
+    {
+      warn!(
+        "User {} already owns a world with slug {}",
+        to, world.data.slug
+      );
+      return Err(OfferWorldTransferError::SlugConflict);
+    }
+
+    let transfer = self
+      .repository
+      .save_world_transfer(world_id, &world.data.owner, to)?;
+    Ok(transfer)
+  }
+
+  /// Accept an offer to transfer the ownership of a world, making the accepting user the new owner
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world being transferred
+  /// * `user_id` The ID of the user accepting the offer
+  ///
+  /// # Returns
+  /// The world, now owned by the accepting user
+  fn accept_world_transfer(
+    &self,
+    world_id: &WorldID,
+    user_id: &UserID,
+  ) -> Result<WorldEntity, AcceptWorldTransferError> {
+    let transfer = self
+      .repository
+      .get_world_transfer(world_id)
+      .filter(|transfer| &transfer.to == user_id)
+      .ok_or(AcceptWorldTransferError::UnknownTransfer)?;
+    let world = self
+      .get_world_by_id(world_id)
+      .filter(|world| world.data.owner == transfer.from)
+      .ok_or(AcceptWorldTransferError::UnknownTransfer)?;
+
+    let transferred = self.repository.update_world(WorldEntity {
+      identity: world.identity,
+      data: WorldData {
+        owner: transfer.to.clone(),
+        ..world.data
+      },
+      stars: world.stars,
+    })?;
+    info!(
+      "Transferred world {} from {} to {}",
+      world_id, transfer.from, transfer.to
+    );
+
+    if let Err(e) = self.repository.delete_world_transfer(world_id) {
+      warn!(
+        "Error removing accepted transfer of world {}: {}",
+        world_id, e
+      );
+    }
+    // The new owner no longer needs to be a member of the world as well
+    if self
+      .repository
+      .get_world_member(world_id, user_id)
+      .is_some()
+    {
+      if let Err(e) = self.repository.delete_world_member(world_id, user_id) {
+        warn!(
+          "Error removing new owner from members of world {}: {}",
+          world_id, e
+        );
+      }
+    }
+
+    Ok(transferred)
+  }
+
+  /// Withdraw or decline an offer to transfer the ownership of a world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world being transferred
+  fn cancel_world_transfer(&self, world_id: &WorldID) -> Result<(), CancelWorldTransferError> {
+    self.repository.delete_world_transfer(world_id)?;
+    Ok(())
+  }
+}
+
+impl From<PersistWorldError> for CreateWorldError {
+  fn from(e: PersistWorldError) -> Self {
+    warn!("Error creating world: {}", e);
+    match e {
+      PersistWorldError::DuplicateSlug => {
+        CreateWorldError::ValidationError(vec![WorldValidationError::DuplicateSlug])
+      }
+      _ => CreateWorldError::UnknownError,
+    }
+  }
+}
+
+impl From<CreateWorldError> for ForkWorldError {
+  fn from(e: CreateWorldError) -> Self {
+    match e {
+      CreateWorldError::ValidationError(errors) => ForkWorldError::ValidationError(errors),
+      CreateWorldError::UnknownError => ForkWorldError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistWorldError> for UpdateWorldError {
+  fn from(e: PersistWorldError) -> Self {
+    warn!("Error updating world: {}", e);
+    match e {
+      PersistWorldError::DuplicateSlug => {
+        UpdateWorldError::ValidationError(vec![WorldValidationError::DuplicateSlug])
+      }
+      PersistWorldError::WorldNotFound => UpdateWorldError::UnknownWorld,
+      PersistWorldError::OptimisticLockFailure => UpdateWorldError::OptimisticLockFailure,
+      _ => UpdateWorldError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistWorldError> for RestoreWorldRevisionError {
+  fn from(e: PersistWorldError) -> Self {
+    warn!("Error restoring world revision: {}", e);
+    match e {
+      PersistWorldError::DuplicateSlug => {
+        RestoreWorldRevisionError::ValidationError(vec![WorldValidationError::DuplicateSlug])
+      }
+      PersistWorldError::WorldNotFound => RestoreWorldRevisionError::UnknownWorld,
+      PersistWorldError::OptimisticLockFailure => RestoreWorldRevisionError::OptimisticLockFailure,
+      _ => RestoreWorldRevisionError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistWorldError> for DeleteWorldError {
+  fn from(e: PersistWorldError) -> Self {
+    warn!("Error deleting world: {}", e);
+    match e {
+      PersistWorldError::WorldNotFound => DeleteWorldError::UnknownWorld,
+      _ => DeleteWorldError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistWorldError> for RestoreWorldError {
+  fn from(e: PersistWorldError) -> Self {
+    warn!("Error restoring world: {}", e);
+    match e {
+      PersistWorldError::DuplicateSlug => {
+        RestoreWorldError::ValidationError(vec![WorldValidationError::DuplicateSlug])
+      }
+      PersistWorldError::WorldNotFound => RestoreWorldError::UnknownWorld,
+      _ => RestoreWorldError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistWorldError> for AddWorldMemberError {
+  fn from(e: PersistWorldError) -> Self {
+    warn!("Error adding world member: {}", e);
+    match e {
+      PersistWorldError::WorldNotFound => AddWorldMemberError::UnknownWorld,
+      PersistWorldError::UserNotFound => AddWorldMemberError::UnknownUser,
+      PersistWorldError::DuplicateMember => AddWorldMemberError::DuplicateMember,
+      _ => AddWorldMemberError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistWorldError> for UpdateWorldMemberError {
+  fn from(e: PersistWorldError) -> Self {
+    warn!("Error updating world member: {}", e);
+    match e {
+      PersistWorldError::MemberNotFound => UpdateWorldMemberError::UnknownMember,
+      _ => UpdateWorldMemberError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistWorldError> for RemoveWorldMemberError {
+  fn from(e: PersistWorldError) -> Self {
+    warn!("Error removing world member: {}", e);
+    match e {
+      PersistWorldError::MemberNotFound => RemoveWorldMemberError::UnknownMember,
+      _ => RemoveWorldMemberError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistWorldError> for StarWorldError {
+  fn from(e: PersistWorldError) -> Self {
+    warn!("Error starring world: {}", e);
+    match e {
+      PersistWorldError::WorldNotFound => StarWorldError::UnknownWorld,
+      _ => StarWorldError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistWorldError> for UnstarWorldError {
+  fn from(e: PersistWorldError) -> Self {
+    warn!("Error unstarring world: {}", e);
+    UnstarWorldError::UnknownError
+  }
+}
+
+impl From<PersistWorldError> for OfferWorldTransferError {
+  fn from(e: PersistWorldError) -> Self {
+    warn!("Error offering world transfer: {}", e);
+    match e {
+      PersistWorldError::WorldNotFound => OfferWorldTransferError::UnknownWorld,
+      PersistWorldError::UserNotFound => OfferWorldTransferError::UnknownUser,
+      _ => OfferWorldTransferError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistWorldError> for AcceptWorldTransferError {
+  fn from(e: PersistWorldError) -> Self {
+    warn!("Error accepting world transfer: {}", e);
+    match e {
+      PersistWorldError::DuplicateSlug => AcceptWorldTransferError::SlugConflict,
+      PersistWorldError::WorldNotFound => AcceptWorldTransferError::UnknownTransfer,
+      _ => AcceptWorldTransferError::UnknownError,
+    }
+  }
+}
+
+impl From<PersistWorldError> for CancelWorldTransferError {
+  fn from(e: PersistWorldError) -> Self {
+    warn!("Error cancelling world transfer: {}", e);
+    match e {
+      PersistWorldError::TransferNotFound => CancelWorldTransferError::UnknownTransfer,
+      _ => CancelWorldTransferError::UnknownError,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::service::repository::MockWorldRepository;
+  use assert_matches::*;
+  use mockall::*;
+  use spectral::prelude::*;
+  use universe_storage::{Blob, MemoryBlobStore};
+
+  fn world_data() -> WorldData {
+    WorldData {
+      owner: Default::default(),
+      name: "Test World".to_owned(),
+      slug: "test-world".parse().unwrap(),
+      description: "This is a test world".to_owned(),
+      visibility: Visibility::Public,
+      forked_from: None,
+      tags: vec![],
+      cover: None,
+    }
+  }
+
+  #[test]
+  fn test_get_unknown_world_by_id() {
+    let world_id: WorldID = Default::default();
+
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_get_world_by_id()
+      .with(predicate::eq(world_id.clone()))
+      .times(1)
+      .returning(|_| None);
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.get_world_by_id(&world_id);
+    assert_that(&result).is_none();
+  }
+
+  #[test]
+  fn test_get_known_world_by_id() {
+    let world = WorldEntity {
+      identity: Default::default(),
+      data: world_data(),
+      stars: 0,
+    };
+
+    let mut repository = MockWorldRepository::new();
+    let returned_world = world.clone();
+    repository
+      .expect_get_world_by_id()
+      .with(predicate::eq(world.identity.id.clone()))
+      .times(1)
+      .returning(move |_| Some(returned_world.clone()));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.get_world_by_id(&world.identity.id);
+    assert_that(&result).is_some().is_equal_to(world);
+  }
+
+  #[test]
+  fn test_get_known_world_by_slug() {
+    let world = WorldEntity {
+      identity: Default::default(),
+      data: world_data(),
+      stars: 0,
+    };
+
+    let mut repository = MockWorldRepository::new();
+    let returned_world = world.clone();
+    repository
+      .expect_get_world_by_slug()
+      .with(
+        predicate::eq(world.data.owner.clone()),
+        predicate::eq(world.data.slug.clone()),
+      )
+      .times(1)
+      .returning(move |_, _| Some(returned_world.clone()));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.get_world_by_slug(&world.data.owner, &world.data.slug);
+    assert_that(&result).is_some().is_equal_to(world);
+  }
+
+  #[test]
+  fn test_create_world_success() {
+    let world_data = world_data();
+    let new_world = WorldEntity {
+      identity: Default::default(),
+      data: world_data.clone(),
+      stars: 0,
+    };
+
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_create_world()
+      .with(predicate::eq(world_data.clone()))
+      .times(1)
+      .returning(move |_| Ok(new_world.clone()));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.create_world(world_data.clone());
+    assert_that(&result)
+      .is_ok()
+      .map(|world| &world.data)
+      .is_equal_to(&world_data);
+  }
+
+  #[test]
+  fn test_create_world_duplicate_slug() {
+    let world_data = world_data();
+
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_create_world()
+      .with(predicate::eq(world_data.clone()))
+      .times(1)
+      .returning(|_| Err(PersistWorldError::DuplicateSlug));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.create_world(world_data);
+    assert_that(&result)
+      .is_err()
+      .is_equal_to(CreateWorldError::ValidationError(vec![
+        WorldValidationError::DuplicateSlug,
+      ]));
+  }
+
+  #[test]
+  fn test_create_world_unexpected_error() {
+    let world_data = world_data();
+
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_create_world()
+      .with(predicate::eq(world_data.clone()))
+      .times(1)
+      .returning(|_| Err(PersistWorldError::UnknownError));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.create_world(world_data);
+    assert_that(&result)
+      .is_err()
+      .is_equal_to(CreateWorldError::UnknownError);
+  }
+
+  #[test]
+  fn test_update_unknown_world() {
+    let world_id: WorldID = Default::default();
+
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_get_world_by_id()
+      .with(predicate::eq(world_id.clone()))
+      .times(1)
+      .returning(|_| None);
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+    let result = service.update_world(&world_id, &mut |world| Ok(world));
+
+    assert_that(&result).is_err();
+    assert_matches!(result.unwrap_err(), UpdateWorldError::UnknownWorld);
+  }
+
+  #[test]
+  fn test_update_with_changes() {
+    // The world as it currently exists
+    let world = WorldEntity {
+      identity: Default::default(),
+      data: world_data(),
+      stars: 0,
+    };
+
+    // The world that we expect to see when calling to save in the repository. This has the data mutated.
+    let expected_world = WorldEntity {
+      identity: world.identity.clone(),
+      data: WorldData {
+        name: "New World".to_owned(),
+        slug: "new-world".parse().unwrap(),
+        ..world.data.clone()
+      },
+      stars: 0,
+    };
+
+    // The world as returned after saving in the repostory. This has a different Identity.
+    let updated_world = WorldEntity {
+      identity: Default::default(),
+      data: expected_world.data.clone(),
+      stars: 0,
+    };
+
+    let mut repository = MockWorldRepository::new();
+
+    let returned_world = world.clone();
+    repository
+      .expect_get_world_by_id()
+      .with(predicate::eq(world.identity.id.clone()))
+      .times(1)
+      .returning(move |_| Some(returned_world.clone()));
+
+    let returned_updated_world = updated_world.clone();
+    repository
+      .expect_update_world()
+      .with(predicate::eq(expected_world))
+      .times(1)
+      .returning(move |_| Ok(returned_updated_world.clone()));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+    let result = service.update_world(&world.identity.id, &mut |mut world| {
+      world.name = "New World".to_owned();
+      world.slug = "new-world".parse().unwrap();
+      Ok(world)
+    });
+
+    assert_that(&result).is_ok().is_equal_to(updated_world);
+  }
+
+  #[test]
+  fn test_update_updater_error() {
+    let world = WorldEntity {
+      identity: Default::default(),
+      data: world_data(),
+      stars: 0,
+    };
+
+    let mut repository = MockWorldRepository::new();
+
+    let returned_world = world.clone();
+    repository
+      .expect_get_world_by_id()
+      .with(predicate::eq(world.identity.id.clone()))
+      .times(1)
+      .returning(move |_| Some(returned_world.clone()));
+    repository.expect_update_world().times(0);
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+    let result = service.update_world(&world.identity.id, &mut |_| {
+      Err(Box::new(std::fmt::Error {}))
+    });
+
+    assert_that(&result).is_err();
+    assert_matches!(result.unwrap_err(), UpdateWorldError::UpdateError(_));
+  }
+
+  #[test]
+  fn test_update_optimistic_lock_failure() {
+    let world = WorldEntity {
+      identity: Default::default(),
+      data: world_data(),
+      stars: 0,
+    };
+
+    let mut repository = MockWorldRepository::new();
+
+    let returned_world = world.clone();
+    repository
+      .expect_get_world_by_id()
+      .with(predicate::eq(world.identity.id.clone()))
+      .times(1)
+      .returning(move |_| Some(returned_world.clone()));
+    repository
+      .expect_update_world()
+      .times(1)
+      .returning(|_| Err(PersistWorldError::OptimisticLockFailure));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+    let result = service.update_world(&world.identity.id, &mut |world| Ok(world));
+
+    assert_that(&result).is_err();
+    assert_matches!(result.unwrap_err(), UpdateWorldError::OptimisticLockFailure);
+  }
+
+  #[test]
+  fn test_update_duplicate_slug() {
+    let world = WorldEntity {
+      identity: Default::default(),
+      data: world_data(),
+      stars: 0,
+    };
+
+    let mut repository = MockWorldRepository::new();
+
+    let returned_world = world.clone();
+    repository
+      .expect_get_world_by_id()
+      .with(predicate::eq(world.identity.id.clone()))
+      .times(1)
+      .returning(move |_| Some(returned_world.clone()));
+    repository
+      .expect_update_world()
+      .times(1)
+      .returning(|_| Err(PersistWorldError::DuplicateSlug));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+    let result = service.update_world(&world.identity.id, &mut |world| Ok(world));
+
+    assert_that(&result).is_err();
+    assert_matches!(
+      result.unwrap_err(),
+      UpdateWorldError::ValidationError(errors) if errors == vec![WorldValidationError::DuplicateSlug]
+    );
+  }
+
+  fn world_revision(world: &WorldEntity, revision: u32, data: WorldData) -> WorldRevision {
+    WorldRevision {
+      world: world.identity.id.clone(),
+      revision,
+      version: Default::default(),
+      created: Utc::now(),
+      data,
+    }
+  }
+
+  #[test]
+  fn test_restore_unknown_world_revision() {
+    let world = WorldEntity {
+      identity: Default::default(),
+      data: world_data(),
+      stars: 0,
+    };
+
+    let mut repository = MockWorldRepository::new();
+    let returned_world = world.clone();
+    repository
+      .expect_get_world_by_id()
+      .returning(move |_| Some(returned_world.clone()));
+    repository
+      .expect_get_world_revision()
+      .with(predicate::eq(world.identity.id.clone()), predicate::eq(3))
+      .times(1)
+      .returning(|_, _| None);
+    repository.expect_update_world().times(0);
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+    let result = service.restore_world_revision(&world.identity.id, 3);
+
+    assert_that(&result)
+      .is_err()
+      .is_equal_to(RestoreWorldRevisionError::UnknownRevision);
+  }
+
+  #[test]
+  fn test_restore_world_revision() {
+    let world = WorldEntity {
+      identity: Default::default(),
+      data: WorldData {
+        name: "New World".to_owned(),
+        visibility: Visibility::Private,
+        cover: Some(CoverID::default()),
+        ..world_data()
+      },
+      stars: 0,
+    };
+    let revision = world_revision(
+      &world,
+      1,
+      WorldData {
+        owner: Default::default(),
+        tags: vec!["fantasy".parse().unwrap()],
+        ..world_data()
+      },
+    );
+
+    // Everything is restored except for the owner and the cover image
+    let expected_world = WorldEntity {
+      identity: world.identity.clone(),
+      data: WorldData {
+        owner: world.data.owner.clone(),
+        cover: world.data.cover.clone(),
+        ..revision.data.clone()
+      },
+      stars: 0,
+    };
+
+    let mut repository = MockWorldRepository::new();
+    let returned_world = world.clone();
+    repository
+      .expect_get_world_by_id()
+      .returning(move |_| Some(returned_world.clone()));
+    repository
+      .expect_get_world_revision()
+      .times(1)
+      .returning(move |_, _| Some(revision.clone()));
+    repository
+      .expect_update_world()
+      .with(predicate::eq(expected_world.clone()))
+      .times(1)
+      .returning(Ok);
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+    let result = service.restore_world_revision(&world.identity.id, 1);
+
+    assert_that(&result).is_ok().is_equal_to(expected_world);
+  }
+
+  #[test]
+  fn test_restore_world_revision_duplicate_slug() {
+    let world = WorldEntity {
+      identity: Default::default(),
+      data: world_data(),
+      stars: 0,
+    };
+    let revision = world_revision(&world, 1, world_data());
+
+    let mut repository = MockWorldRepository::new();
+    let returned_world = world.clone();
+    repository
+      .expect_get_world_by_id()
+      .returning(move |_| Some(returned_world.clone()));
+    repository
+      .expect_get_world_revision()
+      .returning(move |_, _| Some(revision.clone()));
+    repository
+      .expect_update_world()
+      .returning(|_| Err(PersistWorldError::DuplicateSlug));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+    let result = service.restore_world_revision(&world.identity.id, 1);
+
+    assert_that(&result)
+      .is_err()
+      .is_equal_to(RestoreWorldRevisionError::ValidationError(vec![
+        WorldValidationError::DuplicateSlug,
+      ]));
+  }
+
+  #[test]
+  fn test_delete_unknown_world() {
+    let world_id: WorldID = Default::default();
+
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_delete_world()
+      .with(predicate::eq(world_id.clone()))
+      .times(1)
+      .returning(|_| Err(PersistWorldError::WorldNotFound));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.delete_world(&world_id);
+    assert_that(&result)
+      .is_err()
+      .is_equal_to(DeleteWorldError::UnknownWorld);
+  }
+
+  #[test]
+  fn test_restore_world_duplicate_slug() {
+    let owner: UserID = Default::default();
+    let world_id: WorldID = Default::default();
+
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_restore_world()
+      .with(
+        predicate::eq(owner.clone()),
+        predicate::eq(world_id.clone()),
+      )
+      .times(1)
+      .returning(|_, _| Err(PersistWorldError::DuplicateSlug));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.restore_world(&owner, &world_id);
+    assert_that(&result)
+      .is_err()
+      .is_equal_to(RestoreWorldError::ValidationError(vec![
+        WorldValidationError::DuplicateSlug,
+      ]));
+  }
+
+  #[test]
+  fn test_purge_trashed_worlds() {
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_purge_trashed_worlds()
+      .withf(|deleted_before| {
+        let expected = Utc::now() - Duration::days(30);
+        (*deleted_before - expected).num_seconds().abs() < 5
+      })
+      .times(1)
+      .returning(|_| {
+        Ok(vec![
+          "1ae9e2d6-3a9e-4b5f-9a2c-6e1b0f6f2b1a".parse().unwrap(),
+          "2f1c8a6e-5d3b-4c7a-8e9f-0a1b2c3d4e5f".parse().unwrap(),
+          "3b2d9c7f-6e4a-4d8b-9f0a-1b2c3d4e5f60".parse().unwrap(),
+        ])
+      });
+
+    let blob_store = Arc::new(MemoryBlobStore::default());
+    for key in &[
+      "worlds/1ae9e2d6-3a9e-4b5f-9a2c-6e1b0f6f2b1a/covers/cover/original",
+      "worlds/2f1c8a6e-5d3b-4c7a-8e9f-0a1b2c3d4e5f/maps/map/image",
+      "worlds/9d8c7b6a-5f4e-4d3c-8b2a-1f0e9d8c7b6a/covers/cover/original",
+    ] {
+      blob_store
+        .put(
+          key,
+          &Blob {
+            content_type: "image/png".to_owned(),
+            data: vec![1, 2, 3],
+          },
+        )
+        .unwrap();
+    }
+
+    let service = new_world_service(repository, blob_store.clone());
+
+    let result = service.purge_trashed_worlds(Duration::days(30));
+    assert_that(&result).is_equal_to(3);
+    assert_that(&blob_store.keys()).is_equal_to(vec![
+      "worlds/9d8c7b6a-5f4e-4d3c-8b2a-1f0e9d8c7b6a/covers/cover/original".to_owned(),
+    ]);
+  }
+
+  #[test]
+  fn test_purge_trashed_worlds_error() {
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_purge_trashed_worlds()
+      .times(1)
+      .returning(|_| Err(PersistWorldError::UnknownError));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.purge_trashed_worlds(Duration::days(30));
+    assert_that(&result).is_equal_to(0);
+  }
+
+  #[test]
+  fn test_purge_world() {
+    let world_id: WorldID = "1ae9e2d6-3a9e-4b5f-9a2c-6e1b0f6f2b1a".parse().unwrap();
+
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_purge_world()
+      .with(predicate::eq(world_id.clone()))
+      .times(1)
+      .returning(|_| Ok(()));
+
+    let blob_store = Arc::new(MemoryBlobStore::default());
+    for key in &[
+      "worlds/1ae9e2d6-3a9e-4b5f-9a2c-6e1b0f6f2b1a/covers/cover/original",
+      "worlds/9d8c7b6a-5f4e-4d3c-8b2a-1f0e9d8c7b6a/covers/cover/original",
+    ] {
+      blob_store
+        .put(
+          key,
+          &Blob {
+            content_type: "image/png".to_owned(),
+            data: vec![1, 2, 3],
+          },
+        )
+        .unwrap();
+    }
+
+    let service = new_world_service(repository, blob_store.clone());
+
+    let result = service.purge_world(&world_id);
+    assert_that(&result).is_ok();
+    assert_that(&blob_store.keys()).is_equal_to(vec![
+      "worlds/9d8c7b6a-5f4e-4d3c-8b2a-1f0e9d8c7b6a/covers/cover/original".to_owned(),
+    ]);
+  }
+
+  #[test]
+  fn test_purge_unknown_world() {
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_purge_world()
+      .times(1)
+      .returning(|_| Err(PersistWorldError::WorldNotFound));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.purge_world(&Default::default());
+    assert_that(&result)
+      .is_err()
+      .is_equal_to(DeleteWorldError::UnknownWorld);
+  }
+
+  #[test]
+  fn test_create_world_with_unique_slug_first_attempt() {
+    let data = world_data();
+
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_create_world()
+      .with(predicate::eq(data.clone()))
+      .times(1)
+      .returning(|data| {
+        Ok(WorldEntity {
+          identity: Default::default(),
+          data,
+          stars: 0,
+        })
+      });
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.create_world_with_unique_slug(data.clone());
+    assert_that(&result)
+      .is_ok()
+      .map(|world| &world.data)
+      .is_equal_to(data);
+  }
+
+  #[test]
+  fn test_create_world_with_unique_slug_suffixed() {
+    let data = world_data();
+
+    let mut repository = MockWorldRepository::new();
+    let mut sequence = Sequence::new();
+    for slug in &["test-world", "test-world-2"] {
+      repository
+        .expect_create_world()
+        .with(predicate::eq(WorldData {
+          slug: slug.parse().unwrap(),
+          ..data.clone()
+        }))
+        .times(1)
+        .in_sequence(&mut sequence)
+        .returning(|_| Err(PersistWorldError::DuplicateSlug));
+    }
+    repository
+      .expect_create_world()
+      .with(predicate::eq(WorldData {
+        slug: "test-world-3".parse().unwrap(),
+        ..data.clone()
+      }))
+      .times(1)
+      .in_sequence(&mut sequence)
+      .returning(|data| {
+        Ok(WorldEntity {
+          identity: Default::default(),
+          data,
+          stars: 0,
+        })
+      });
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.create_world_with_unique_slug(data);
+    assert_that(&result)
+      .is_ok()
+      .map(|world| &world.data.slug)
+      .is_equal_to("test-world-3".parse::<Slug>().unwrap());
+  }
+
+  #[test]
+  fn test_create_world_with_unique_slug_unexpected_error() {
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_create_world()
+      .times(1)
+      .returning(|_| Err(PersistWorldError::UnknownError));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.create_world_with_unique_slug(world_data());
+    assert_that(&result)
+      .is_err()
+      .is_equal_to(CreateWorldError::UnknownError);
+  }
+
+  #[test]
+  fn test_fork_unknown_world() {
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_get_world_by_id()
+      .times(1)
+      .returning(|_| None);
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.fork_world(&Default::default(), &Default::default());
+    assert_that(&result)
+      .is_err()
+      .is_equal_to(ForkWorldError::UnknownWorld);
+  }
+
+  #[test]
+  fn test_fork_world_success() {
+    let world = WorldEntity {
+      identity: Default::default(),
+      data: world_data(),
+      stars: 0,
+    };
+    let new_owner: UserID = Default::default();
+
+    let mut repository = MockWorldRepository::new();
+    let returned_world = world.clone();
+    repository
+      .expect_get_world_by_id()
+      .times(1)
+      .returning(move |_| Some(returned_world.clone()));
+    let expected = WorldData {
+      owner: new_owner.clone(),
+      forked_from: Some(world.identity.id.clone()),
+      ..world.data.clone()
+    };
+    repository
+      .expect_create_world()
+      .with(predicate::eq(expected))
+      .times(1)
+      .returning(|data| {
+        Ok(WorldEntity {
+          identity: Default::default(),
+          data,
+          stars: 0,
+        })
+      });
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.fork_world(&world.identity.id, &new_owner);
+    assert_that(&result)
+      .is_ok()
+      .map(|fork| &fork.data.forked_from)
+      .is_equal_to(Some(world.identity.id));
+  }
+
+  #[test]
+  fn test_fork_world_with_cover() {
+    let cover_id = CoverID::default();
+    let world = WorldEntity {
+      identity: Default::default(),
+      data: WorldData {
+        cover: Some(cover_id.clone()),
+        ..world_data()
+      },
+      stars: 0,
+    };
+    let blob_store = Arc::new(MemoryBlobStore::default());
+    for size in CoverSize::iter() {
+      blob_store
+        .put(
+          &cover_key(&world.identity.id, &cover_id, size),
+          &Blob {
+            content_type: "image/png".to_owned(),
+            data: size.to_string().into_bytes(),
+          },
+        )
+        .unwrap();
+    }
+
+    let mut repository = MockWorldRepository::new();
+    let returned_world = world.clone();
+    repository
+      .expect_get_world_by_id()
+      .times(1)
+      .returning(move |_| Some(returned_world.clone()));
+    let original_cover = cover_id.clone();
+    repository
+      .expect_create_world()
+      .withf(move |data| data.cover.is_some() && data.cover != Some(original_cover.clone()))
+      .times(1)
+      .returning(|data| {
+        Ok(WorldEntity {
+          identity: Default::default(),
+          data,
+          stars: 0,
+        })
+      });
+
+    let service = new_world_service(repository, blob_store.clone());
+
+    let fork = service
+      .fork_world(&world.identity.id, &Default::default())
+      .unwrap();
+    let fork_cover = fork.data.cover.clone().unwrap();
+    for size in CoverSize::iter() {
+      let copied = blob_store
+        .get(&cover_key(&fork.identity.id, &fork_cover, size))
+        .unwrap();
+      assert_that(&copied.map(|blob| blob.data)).is_equal_to(Some(size.to_string().into_bytes()));
+    }
+  }
+
+  fn world_member(world: &WorldEntity, role: WorldRole) -> WorldMember {
+    WorldMember {
+      world: world.identity.id.clone(),
+      user: Default::default(),
+      role,
+      created: Utc::now(),
+      updated: Utc::now(),
+    }
+  }
+
+  #[test]
+  fn test_get_world_role_owner() {
+    let world = WorldEntity {
+      identity: Default::default(),
+      data: world_data(),
+      stars: 0,
+    };
+
+    let repository = MockWorldRepository::new();
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.get_world_role(&world, &world.data.owner);
+    assert_that(&result).is_some().is_equal_to(WorldRole::Owner);
+  }
+
+  #[test]
+  fn test_get_world_role_member() {
+    let world = WorldEntity {
+      identity: Default::default(),
+      data: world_data(),
+      stars: 0,
+    };
+    let member = world_member(&world, WorldRole::Editor);
+
+    let mut repository = MockWorldRepository::new();
+    let returned_member = member.clone();
+    repository
+      .expect_get_world_member()
+      .with(
+        predicate::eq(world.identity.id.clone()),
+        predicate::eq(member.user.clone()),
+      )
+      .times(1)
+      .returning(move |_, _| Some(returned_member.clone()));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.get_world_role(&world, &member.user);
+    assert_that(&result)
+      .is_some()
+      .is_equal_to(WorldRole::Editor);
+  }
+
+  #[test]
+  fn test_get_world_role_not_member() {
+    let world = WorldEntity {
+      identity: Default::default(),
+      data: world_data(),
+      stars: 0,
+    };
+
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_get_world_member()
+      .times(1)
+      .returning(|_, _| None);
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.get_world_role(&world, &Default::default());
+    assert_that(&result).is_none();
+  }
+
+  #[test]
+  fn test_private_world_visible_to_member() {
+    let world = WorldEntity {
+      identity: Default::default(),
+      data: WorldData {
+        visibility: Visibility::Private,
+        ..world_data()
+      },
+      stars: 0,
+    };
+    let member = world_member(&world, WorldRole::Viewer);
+
+    let mut repository = MockWorldRepository::new();
+    let returned_member = member.clone();
+    repository
+      .expect_get_world_member()
+      .times(1)
+      .returning(move |_, _| Some(returned_member.clone()));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    assert_that(&service.is_world_visible_to(&world, None)).is_false();
+    assert_that(&service.is_world_visible_to(&world, Some(&member.user))).is_true();
+  }
+
+  #[test]
+  fn test_add_world_member_unknown_world() {
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_get_world_by_id()
+      .times(1)
+      .returning(|_| None);
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result =
+      service.add_world_member(&Default::default(), &Default::default(), WorldRole::Editor);
+    assert_that(&result)
+      .is_err()
+      .is_equal_to(AddWorldMemberError::UnknownWorld);
+  }
+
+  #[test]
+  fn test_add_world_member_owner() {
+    let world = WorldEntity {
+      identity: Default::default(),
+      data: world_data(),
+      stars: 0,
+    };
+
+    let mut repository = MockWorldRepository::new();
+    let returned_world = world.clone();
+    repository
+      .expect_get_world_by_id()
+      .times(1)
+      .returning(move |_| Some(returned_world.clone()));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.add_world_member(&world.identity.id, &world.data.owner, WorldRole::Editor);
+    assert_that(&result)
+      .is_err()
+      .is_equal_to(AddWorldMemberError::DuplicateMember);
+  }
+
+  #[test]
+  fn test_add_world_member_success() {
+    let world = WorldEntity {
+      identity: Default::default(),
+      data: world_data(),
+      stars: 0,
+    };
+    let member = world_member(&world, WorldRole::Editor);
+
+    let mut repository = MockWorldRepository::new();
+    let returned_world = world.clone();
+    repository
+      .expect_get_world_by_id()
+      .times(1)
+      .returning(move |_| Some(returned_world.clone()));
+    let returned_member = member.clone();
+    repository
+      .expect_create_world_member()
+      .with(
+        predicate::eq(world.identity.id.clone()),
+        predicate::eq(member.user.clone()),
+        predicate::eq(WorldRole::Editor),
+      )
+      .times(1)
+      .returning(move |_, _, _| Ok(returned_member.clone()));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.add_world_member(&world.identity.id, &member.user, WorldRole::Editor);
+    assert_that(&result).is_ok().is_equal_to(member);
+  }
+
+  #[test]
+  fn test_add_world_member_unknown_user() {
+    let world = WorldEntity {
+      identity: Default::default(),
+      data: world_data(),
+      stars: 0,
+    };
+
+    let mut repository = MockWorldRepository::new();
+    let returned_world = world.clone();
+    repository
+      .expect_get_world_by_id()
+      .times(1)
+      .returning(move |_| Some(returned_world.clone()));
+    repository
+      .expect_create_world_member()
+      .times(1)
+      .returning(|_, _, _| Err(PersistWorldError::UserNotFound));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result =
+      service.add_world_member(&world.identity.id, &Default::default(), WorldRole::Viewer);
+    assert_that(&result)
+      .is_err()
+      .is_equal_to(AddWorldMemberError::UnknownUser);
+  }
+
+  #[test]
+  fn test_update_unknown_world_member() {
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_update_world_member()
+      .times(1)
+      .returning(|_, _, _| Err(PersistWorldError::MemberNotFound));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result =
+      service.update_world_member(&Default::default(), &Default::default(), WorldRole::Owner);
+    assert_that(&result)
+      .is_err()
+      .is_equal_to(UpdateWorldMemberError::UnknownMember);
+  }
+
+  #[test]
+  fn test_remove_unknown_world_member() {
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_delete_world_member()
+      .times(1)
+      .returning(|_, _| Err(PersistWorldError::MemberNotFound));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.remove_world_member(&Default::default(), &Default::default());
+    assert_that(&result)
+      .is_err()
+      .is_equal_to(RemoveWorldMemberError::UnknownMember);
+  }
+
+  #[test]
+  fn test_star_unknown_world() {
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_star_world()
+      .times(1)
+      .returning(|_, _| Err(PersistWorldError::WorldNotFound));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.star_world(&Default::default(), &Default::default());
+    assert_that(&result)
+      .is_err()
+      .is_equal_to(StarWorldError::UnknownWorld);
+  }
+
+  fn world_transfer(world: &WorldEntity) -> WorldTransfer {
+    WorldTransfer {
+      world: world.identity.id.clone(),
+      from: world.data.owner.clone(),
+      to: Default::default(),
+      created: Utc::now(),
+    }
+  }
+
+  #[test]
+  fn test_offer_world_transfer_same_owner() {
+    let world = WorldEntity {
+      identity: Default::default(),
+      data: world_data(),
+      stars: 0,
+    };
+
+    let mut repository = MockWorldRepository::new();
+    let returned_world = world.clone();
+    repository
+      .expect_get_world_by_id()
+      .times(1)
+      .returning(move |_| Some(returned_world.clone()));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.offer_world_transfer(&world.identity.id, &world.data.owner);
+    assert_that(&result)
+      .is_err()
+      .is_equal_to(OfferWorldTransferError::SameOwner);
+  }
+
+  #[test]
+  fn test_offer_world_transfer_slug_conflict() {
+    let world = WorldEntity {
+      identity: Default::default(),
+      data: world_data(),
+      stars: 0,
+    };
+    let to: UserID = Default::default();
+
+    let mut repository = MockWorldRepository::new();
+    let returned_world = world.clone();
+    repository
+      .expect_get_world_by_id()
+      .times(1)
+      .returning(move |_| Some(returned_world.clone()));
+    let conflicting_world = WorldEntity {
+      identity: Default::default(),
+      data: WorldData {
+        owner: to.clone(),
+        ..world_data()
+      },
+      stars: 0,
+    };
+    repository
+      .expect_get_world_by_slug()
+      .with(
+        predicate::eq(to.clone()),
+        predicate::eq(world.data.slug.clone()),
+      )
+      .times(1)
+      .returning(move |_, _| Some(conflicting_world.clone()));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.offer_world_transfer(&world.identity.id, &to);
+    assert_that(&result)
+      .is_err()
+      .is_equal_to(OfferWorldTransferError::SlugConflict);
+  }
+
+  #[test]
+  fn test_offer_world_transfer_success() {
+    let world = WorldEntity {
+      identity: Default::default(),
+      data: world_data(),
+      stars: 0,
+    };
+    let transfer = world_transfer(&world);
+
+    let mut repository = MockWorldRepository::new();
+    let returned_world = world.clone();
+    repository
+      .expect_get_world_by_id()
+      .times(1)
+      .returning(move |_| Some(returned_world.clone()));
+    repository
+      .expect_get_world_by_slug()
+      .times(1)
+      .returning(|_, _| None);
+    let returned_transfer = transfer.clone();
+    repository
+      .expect_save_world_transfer()
+      .with(
+        predicate::eq(world.identity.id.clone()),
+        predicate::eq(world.data.owner.clone()),
+        predicate::eq(transfer.to.clone()),
+      )
+      .times(1)
+      .returning(move |_, _, _| Ok(returned_transfer.clone()));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.offer_world_transfer(&world.identity.id, &transfer.to);
+    assert_that(&result).is_ok().is_equal_to(transfer);
+  }
+
+  #[test]
+  fn test_accept_world_transfer_wrong_user() {
+    let world = WorldEntity {
+      identity: Default::default(),
+      data: world_data(),
+      stars: 0,
+    };
+    let transfer = world_transfer(&world);
+
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_get_world_transfer()
+      .times(1)
+      .returning(move |_| Some(transfer.clone()));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.accept_world_transfer(&world.identity.id, &Default::default());
+    assert_that(&result)
+      .is_err()
+      .is_equal_to(AcceptWorldTransferError::UnknownTransfer);
+  }
+
+  #[test]
+  fn test_accept_world_transfer_slug_conflict() {
+    let world = WorldEntity {
+      identity: Default::default(),
+      data: world_data(),
+      stars: 0,
+    };
+    let transfer = world_transfer(&world);
+    let to = transfer.to.clone();
+
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_get_world_transfer()
+      .times(1)
+      .returning(move |_| Some(transfer.clone()));
+    let returned_world = world.clone();
+    repository
+      .expect_get_world_by_id()
+      .times(1)
+      .returning(move |_| Some(returned_world.clone()));
+    repository
+      .expect_update_world()
+      .times(1)
+      .returning(|_| Err(PersistWorldError::DuplicateSlug));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.accept_world_transfer(&world.identity.id, &to);
+    assert_that(&result)
+      .is_err()
+      .is_equal_to(AcceptWorldTransferError::SlugConflict);
+  }
+
+  #[test]
+  fn test_accept_world_transfer_success() {
+    let world = WorldEntity {
+      identity: Default::default(),
+      data: world_data(),
+      stars: 0,
+    };
+    let transfer = world_transfer(&world);
+    let to = transfer.to.clone();
+
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_get_world_transfer()
+      .times(1)
+      .returning(move |_| Some(transfer.clone()));
+    let returned_world = world.clone();
+    repository
+      .expect_get_world_by_id()
+      .times(1)
+      .returning(move |_| Some(returned_world.clone()));
+    let new_owner = to.clone();
+    repository
+      .expect_update_world()
+      .withf(move |world| world.data.owner == new_owner)
+      .times(1)
+      .returning(Ok);
+    repository
+      .expect_delete_world_transfer()
+      .with(predicate::eq(world.identity.id.clone()))
+      .times(1)
+      .returning(|_| Ok(()));
+    repository
+      .expect_get_world_member()
+      .times(1)
+      .returning(|_, _| None);
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.accept_world_transfer(&world.identity.id, &to);
+    assert_that(&result)
+      .is_ok()
+      .map(|world| &world.data.owner)
+      .is_equal_to(to);
+  }
+
+  #[test]
+  fn test_cancel_unknown_world_transfer() {
+    let mut repository = MockWorldRepository::new();
+    repository
+      .expect_delete_world_transfer()
+      .times(1)
+      .returning(|_| Err(PersistWorldError::TransferNotFound));
+
+    let service = new_world_service(repository, Arc::new(MemoryBlobStore::default()));
+
+    let result = service.cancel_world_transfer(&Default::default());
+    assert_that(&result)
+      .is_err()
+      .is_equal_to(CancelWorldTransferError::UnknownTransfer);
+  }
 }
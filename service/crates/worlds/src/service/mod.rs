@@ -1,9 +1,17 @@
+mod blob_owners;
+mod cover_image;
+mod covers;
+mod facets;
 mod implementation;
 pub mod repository;
 mod search_filters;
 mod service;
 mod sorts;
 
+pub use blob_owners::*;
+pub use cover_image::*;
+pub use covers::*;
+pub use facets::*;
 pub use implementation::*;
 pub use search_filters::*;
 pub use service::*;
@@ -1,12 +1,44 @@
-use super::{WorldFilters, WorldSorts};
+use super::{FacetCount, WorldFacet, WorldFilters, WorldSorts};
 use crate::model::*;
+use chrono::{DateTime, Utc};
 #[cfg(test)]
 use mockall::automock;
 use universe_entity::{Page, Pagination, SortField};
+use universe_users::UserID;
 
 /// Repository that describes how to access world data
 #[cfg_attr(test, automock)]
 pub trait WorldRepository {
+  /// Retrieve the world that has the given unique ID
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to retrieve
+  ///
+  /// # Returns
+  /// The world, or `None` if it wasn't found
+  fn get_world_by_id(&self, world_id: &WorldID) -> Option<WorldEntity>;
+
+  /// Retrieve the world that is owned by the given user and has the given slug
+  ///
+  /// # Arguments
+  /// * `owner` The ID of the user that owns the world
+  /// * `slug` The slug of the world
+  ///
+  /// # Returns
+  /// The world, or `None` if it wasn't found
+  fn get_world_by_slug(&self, owner: &UserID, slug: &Slug) -> Option<WorldEntity>;
+
+  /// Retrieve the world that is owned by the given user and used to have the given slug before
+  /// it was changed
+  ///
+  /// # Arguments
+  /// * `owner` The ID of the user that owns the world
+  /// * `slug` The previous slug of the world
+  ///
+  /// # Returns
+  /// The world, or `None` if it wasn't found
+  fn get_world_by_previous_slug(&self, owner: &UserID, slug: &Slug) -> Option<WorldEntity>;
+
   /// Perform a search for all the worlds that match the given filters, sorted in the requested order.
   ///
   /// # Arguments
@@ -22,4 +54,267 @@ pub trait WorldRepository {
     sorts: Vec<SortField<WorldSorts>>,
     pagination: Pagination,
   ) -> Page<WorldEntity>;
+
+  /// Count how many of the worlds that match the given filters have each value of a facet, most
+  /// common values first
+  ///
+  /// # Arguments
+  /// * `filters` The filters to apply when searching for worlds
+  /// * `facet` The facet to count the values of
+  /// * `limit` The maximum number of values to return
+  ///
+  /// # Returns
+  /// The counts of each value of the facet
+  fn count_world_facet(
+    &self,
+    filters: WorldFilters,
+    facet: WorldFacet,
+    limit: u32,
+  ) -> Vec<FacetCount>;
+
+  /// Create a new world record in the data store
+  ///
+  /// # Arguments
+  /// * `world` The world details to persist to the data store
+  ///
+  /// # Returns
+  /// The world that was persisted
+  fn create_world(&self, world: WorldData) -> Result<WorldEntity, PersistWorldError>;
+
+  /// Update an existing world in the data store
+  ///
+  /// # Arguments
+  /// * `world` The world entity to persist to the data store
+  ///
+  /// # Returns
+  /// The world that was persisted
+  fn update_world(&self, world: WorldEntity) -> Result<WorldEntity, PersistWorldError>;
+
+  /// Retrieve the revisions of the given world, most recent first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the revisions of
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of revisions
+  fn list_world_revisions(&self, world_id: &WorldID, pagination: Pagination)
+    -> Page<WorldRevision>;
+
+  /// Retrieve a single revision of the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  /// * `revision` The number of the revision to retrieve
+  ///
+  /// # Returns
+  /// The revision, or `None` if it wasn't found
+  fn get_world_revision(&self, world_id: &WorldID, revision: u32) -> Option<WorldRevision>;
+
+  /// Move a world into the trash, so that it is no longer visible but can still be restored
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to move to the trash
+  fn delete_world(&self, world_id: &WorldID) -> Result<(), PersistWorldError>;
+
+  /// Retrieve the worlds owned by the given user that are currently in the trash, most recently
+  /// deleted first
+  ///
+  /// # Arguments
+  /// * `owner` The ID of the user that owns the worlds
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of trashed worlds
+  fn list_trashed_worlds(&self, owner: &UserID, pagination: Pagination) -> Page<TrashedWorld>;
+
+  /// Restore a world owned by the given user out of the trash
+  ///
+  /// # Arguments
+  /// * `owner` The ID of the user that owns the world
+  /// * `world_id` The ID of the world to restore
+  ///
+  /// # Returns
+  /// The world that was restored
+  fn restore_world(
+    &self,
+    owner: &UserID,
+    world_id: &WorldID,
+  ) -> Result<WorldEntity, PersistWorldError>;
+
+  /// Permanently remove all of the worlds that were moved to the trash before the given time
+  ///
+  /// # Arguments
+  /// * `deleted_before` The cutoff time for worlds to be purged
+  ///
+  /// # Returns
+  /// The IDs of the worlds that were purged
+  fn purge_trashed_worlds(
+    &self,
+    deleted_before: DateTime<Utc>,
+  ) -> Result<Vec<WorldID>, PersistWorldError>;
+
+  /// Permanently remove a single world, whether it is in the trash or not
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to remove
+  fn purge_world(&self, world_id: &WorldID) -> Result<(), PersistWorldError>;
+
+  /// Find out who owns a world, whether it is in the trash or not
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  ///
+  /// # Returns
+  /// The ID of the user that owns the world, or `None` if it wasn't found
+  fn get_world_owner(&self, world_id: &WorldID) -> Option<UserID>;
+
+  /// Retrieve the IDs of every world that a user owns, including those in the trash
+  ///
+  /// # Arguments
+  /// * `owner` The ID of the user
+  ///
+  /// # Returns
+  /// The IDs of the worlds
+  fn list_owned_world_ids(&self, owner: &UserID) -> Vec<WorldID>;
+
+  /// Retrieve all of the members of the given world, in the order they were added
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the members of
+  ///
+  /// # Returns
+  /// The members of the world
+  fn list_world_members(&self, world_id: &WorldID) -> Vec<WorldMember>;
+
+  /// Retrieve the membership of the given user on the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  /// * `user_id` The ID of the user
+  ///
+  /// # Returns
+  /// The membership, or `None` if the user isn't a member of the world
+  fn get_world_member(&self, world_id: &WorldID, user_id: &UserID) -> Option<WorldMember>;
+
+  /// Add a new member to the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to add the member to
+  /// * `user_id` The ID of the user to add as a member
+  /// * `role` The role that the user has on the world
+  ///
+  /// # Returns
+  /// The membership that was persisted
+  fn create_world_member(
+    &self,
+    world_id: &WorldID,
+    user_id: &UserID,
+    role: WorldRole,
+  ) -> Result<WorldMember, PersistWorldError>;
+
+  /// Change the role of an existing member of the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  /// * `user_id` The ID of the member
+  /// * `role` The new role that the user has on the world
+  ///
+  /// # Returns
+  /// The membership that was persisted
+  fn update_world_member(
+    &self,
+    world_id: &WorldID,
+    user_id: &UserID,
+    role: WorldRole,
+  ) -> Result<WorldMember, PersistWorldError>;
+
+  /// Remove a member from the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  /// * `user_id` The ID of the member to remove
+  fn delete_world_member(
+    &self,
+    world_id: &WorldID,
+    user_id: &UserID,
+  ) -> Result<(), PersistWorldError>;
+
+  /// Record that the given user has starred the given world. Starring a world that the user has
+  /// already starred does nothing
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to star
+  /// * `user_id` The ID of the user starring the world
+  fn star_world(&self, world_id: &WorldID, user_id: &UserID) -> Result<(), PersistWorldError>;
+
+  /// Remove the star that the given user gave to the given world, if there is one
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to unstar
+  /// * `user_id` The ID of the user unstarring the world
+  fn unstar_world(&self, world_id: &WorldID, user_id: &UserID) -> Result<(), PersistWorldError>;
+
+  /// Retrieve the pending ownership transfer of the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  ///
+  /// # Returns
+  /// The transfer, or `None` if the world isn't being transferred
+  fn get_world_transfer(&self, world_id: &WorldID) -> Option<WorldTransfer>;
+
+  /// Retrieve all of the pending ownership transfers that have been offered to the given user,
+  /// oldest first
+  ///
+  /// # Arguments
+  /// * `user_id` The ID of the user that the worlds are being offered to
+  ///
+  /// # Returns
+  /// The transfers
+  fn list_world_transfers_to(&self, user_id: &UserID) -> Vec<WorldTransfer>;
+
+  /// Save an offer to transfer the ownership of a world, replacing any previous offer for the
+  /// same world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to transfer
+  /// * `from` The ID of the user that currently owns the world
+  /// * `to` The ID of the user that the world is offered to
+  ///
+  /// # Returns
+  /// The transfer that was persisted
+  fn save_world_transfer(
+    &self,
+    world_id: &WorldID,
+    from: &UserID,
+    to: &UserID,
+  ) -> Result<WorldTransfer, PersistWorldError>;
+
+  /// Remove the pending ownership transfer of the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  fn delete_world_transfer(&self, world_id: &WorldID) -> Result<(), PersistWorldError>;
+}
+
+/// Enumeration of reasons why we failed to persist a world
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum PersistWorldError {
+  #[error("Duplicate Slug")]
+  DuplicateSlug,
+  #[error("The world was not found")]
+  WorldNotFound,
+  #[error("The version of the world record did not match")]
+  OptimisticLockFailure,
+  #[error("The user is already a member of the world")]
+  DuplicateMember,
+  #[error("The user is not a member of the world")]
+  MemberNotFound,
+  #[error("The user was not found")]
+  UserNotFound,
+  #[error("The world is not being transferred")]
+  TransferNotFound,
+  #[error("An unknown error occurred")]
+  UnknownError,
 }
@@ -1,8 +1,24 @@
+use crate::Tag;
 use universe_users::UserID;
 
 /// Filters that can be applied when searching for worlds
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct WorldFilters {
   pub owner: Option<UserID>,
   pub keyword: Option<String>,
+  /// The user that is performing the search, if any. Only public worlds and worlds owned by this
+  /// user will be returned
+  pub visible_to: Option<UserID>,
+  pub tags: Option<TagFilter>,
+  /// Only return worlds that have been starred by this user
+  pub starred_by: Option<UserID>,
+}
+
+/// Filter on the tags that a world has
+#[derive(Debug, PartialEq, Clone)]
+pub enum TagFilter {
+  /// The world must have at least one of the tags
+  Any(Vec<Tag>),
+  /// The world must have every one of the tags
+  All(Vec<Tag>),
 }
@@ -1,9 +1,41 @@
-use super::{WorldFilters, WorldSorts};
+use super::{FacetCount, WorldFacet, WorldFilters, WorldSorts};
 use crate::model::*;
+use chrono::Duration;
 use universe_entity::{Page, Pagination, SortField};
+use universe_users::UserID;
 
 /// The World Service to allow interactoins with worldentities
 pub trait WorldService: Send + Sync {
+  /// Retrieve the world that has the given unique ID
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to retrieve
+  ///
+  /// # Returns
+  /// The world, or `None` if it wasn't found
+  fn get_world_by_id(&self, world_id: &WorldID) -> Option<WorldEntity>;
+
+  /// Retrieve the world that is owned by the given user and has the given slug
+  ///
+  /// # Arguments
+  /// * `owner` The ID of the user that owns the world
+  /// * `slug` The slug of the world
+  ///
+  /// # Returns
+  /// The world, or `None` if it wasn't found
+  fn get_world_by_slug(&self, owner: &UserID, slug: &Slug) -> Option<WorldEntity>;
+
+  /// Retrieve the world that is owned by the given user and used to have the given slug before
+  /// it was changed
+  ///
+  /// # Arguments
+  /// * `owner` The ID of the user that owns the world
+  /// * `slug` The previous slug of the world
+  ///
+  /// # Returns
+  /// The world, or `None` if it wasn't found
+  fn get_world_by_previous_slug(&self, owner: &UserID, slug: &Slug) -> Option<WorldEntity>;
+
   /// Perform a search for all the worlds that match the given filters, sorted in the requested order.
   ///
   /// # Arguments
@@ -19,4 +51,456 @@ pub trait WorldService: Send + Sync {
     sorts: Vec<SortField<WorldSorts>>,
     pagination: Pagination,
   ) -> Page<WorldEntity>;
+
+  /// Count how many of the worlds that match the given filters have each value of a facet, most
+  /// common values first. Only the most common values are returned
+  ///
+  /// # Arguments
+  /// * `filters` The filters to apply when searching for worlds
+  /// * `facet` The facet to count the values of
+  ///
+  /// # Returns
+  /// The counts of each value of the facet
+  fn count_world_facet(&self, filters: WorldFilters, facet: WorldFacet) -> Vec<FacetCount>;
+
+  /// Create a new world
+  ///
+  /// # Arguments
+  /// * `world` The world data to create the world from
+  ///
+  /// # Returns
+  /// The world that was persisted
+  fn create_world(&self, world: WorldData) -> Result<WorldEntity, CreateWorldError>;
+
+  /// Create a new world, treating the slug as a starting point.
+  ///
+  /// If the slug is already used by another world with the same owner then a numeric suffix is
+  /// appended to it until it is unique.
+  ///
+  /// # Arguments
+  /// * `world` The world data to create the world from
+  ///
+  /// # Returns
+  /// The world that was persisted
+  fn create_world_with_unique_slug(
+    &self,
+    world: WorldData,
+  ) -> Result<WorldEntity, CreateWorldError>;
+
+  /// Create a copy of an existing world, owned by the given user.
+  ///
+  /// The new world records which world it was forked from, and has a slug based on the slug of the
+  /// original world that is unique for the new owner. The cover image of the original world is
+  /// copied to the new world as well.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to fork
+  /// * `owner` The ID of the user that is to own the new world
+  ///
+  /// # Returns
+  /// The newly created world
+  fn fork_world(&self, world_id: &WorldID, owner: &UserID) -> Result<WorldEntity, ForkWorldError>;
+
+  /// Update an existing world.
+  ///
+  /// This will load the world by ID, and then call a provided callback to mutate the world before persisting
+  /// the changes back to the database
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to update
+  /// * `updater` The callback to mutate the world with
+  ///
+  /// # Returns
+  /// The newly updated world
+  fn update_world(
+    &self,
+    world_id: &WorldID,
+    updater: &mut dyn FnMut(WorldData) -> Result<WorldData, Box<dyn std::error::Error>>,
+  ) -> Result<WorldEntity, UpdateWorldError>;
+
+  /// Retrieve the revisions of a world, most recent first
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the revisions of
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of revisions
+  fn list_world_revisions(&self, world_id: &WorldID, pagination: Pagination)
+    -> Page<WorldRevision>;
+
+  /// Retrieve a single revision of a world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  /// * `revision` The number of the revision to retrieve
+  ///
+  /// # Returns
+  /// The revision, or `None` if it wasn't found
+  fn get_world_revision(&self, world_id: &WorldID, revision: u32) -> Option<WorldRevision>;
+
+  /// Restore a world to the state it was in at an earlier revision. This is saved as a new
+  /// revision, so the history of the world is never lost.
+  ///
+  /// Only the details that were edited by hand are restored. The owner and cover image of the
+  /// world are left as they currently are, since they are changed by other means and an old cover
+  /// image might no longer exist.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to restore
+  /// * `revision` The number of the revision to restore
+  ///
+  /// # Returns
+  /// The newly updated world
+  fn restore_world_revision(
+    &self,
+    world_id: &WorldID,
+    revision: u32,
+  ) -> Result<WorldEntity, RestoreWorldRevisionError>;
+
+  /// Move a world into the owners trash
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to delete
+  fn delete_world(&self, world_id: &WorldID) -> Result<(), DeleteWorldError>;
+
+  /// Retrieve the worlds that are in the trash of the given user
+  ///
+  /// # Arguments
+  /// * `owner` The ID of the user whose trash should be listed
+  /// * `pagination` The pagination details for which set of data to return
+  ///
+  /// # Returns
+  /// A page of trashed worlds
+  fn list_trashed_worlds(&self, owner: &UserID, pagination: Pagination) -> Page<TrashedWorld>;
+
+  /// Restore a world out of the trash of the given user
+  ///
+  /// # Arguments
+  /// * `owner` The ID of the user whose trash the world is in
+  /// * `world_id` The ID of the world to restore
+  ///
+  /// # Returns
+  /// The world that was restored
+  fn restore_world(
+    &self,
+    owner: &UserID,
+    world_id: &WorldID,
+  ) -> Result<WorldEntity, RestoreWorldError>;
+
+  /// Permanently remove all of the worlds that have been in the trash for longer than the retention period
+  ///
+  /// # Arguments
+  /// * `retention` How long worlds are kept in the trash before being purged
+  ///
+  /// # Returns
+  /// The number of worlds that were purged
+  fn purge_trashed_worlds(&self, retention: Duration) -> u64;
+
+  /// Permanently remove a world, along with all of its content, without it going through the
+  /// trash. This is for throwing away worlds that were only partly created
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to remove
+  fn purge_world(&self, world_id: &WorldID) -> Result<(), DeleteWorldError>;
+
+  /// Determine the role that the given user has on a world.
+  ///
+  /// The owner of the world always has the `Owner` role, even if they aren't listed as a member
+  ///
+  /// # Arguments
+  /// * `world` The world to check
+  /// * `user_id` The ID of the user to check
+  ///
+  /// # Returns
+  /// The role of the user, or `None` if they have no role on the world
+  fn get_world_role(&self, world: &WorldEntity, user_id: &UserID) -> Option<WorldRole>;
+
+  /// Determine whether the given user is able to see a world
+  ///
+  /// # Arguments
+  /// * `world` The world to check
+  /// * `user_id` The ID of the user trying to see the world, or `None` if they are anonymous
+  ///
+  /// # Returns
+  /// True if the user is allowed to see the world
+  fn is_world_visible_to(&self, world: &WorldEntity, user_id: Option<&UserID>) -> bool;
+
+  /// Retrieve all of the members of the given world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to list the members of
+  ///
+  /// # Returns
+  /// The members of the world
+  fn list_world_members(&self, world_id: &WorldID) -> Vec<WorldMember>;
+
+  /// Add a new member to a world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to add the member to
+  /// * `user_id` The ID of the user to add as a member
+  /// * `role` The role that the user has on the world
+  ///
+  /// # Returns
+  /// The new membership
+  fn add_world_member(
+    &self,
+    world_id: &WorldID,
+    user_id: &UserID,
+    role: WorldRole,
+  ) -> Result<WorldMember, AddWorldMemberError>;
+
+  /// Change the role of an existing member of a world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  /// * `user_id` The ID of the member
+  /// * `role` The new role that the user has on the world
+  ///
+  /// # Returns
+  /// The updated membership
+  fn update_world_member(
+    &self,
+    world_id: &WorldID,
+    user_id: &UserID,
+    role: WorldRole,
+  ) -> Result<WorldMember, UpdateWorldMemberError>;
+
+  /// Remove a member from a world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  /// * `user_id` The ID of the member to remove
+  fn remove_world_member(
+    &self,
+    world_id: &WorldID,
+    user_id: &UserID,
+  ) -> Result<(), RemoveWorldMemberError>;
+
+  /// Star a world on behalf of a user. Starring a world that the user has already starred does
+  /// nothing
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to star
+  /// * `user_id` The ID of the user starring the world
+  fn star_world(&self, world_id: &WorldID, user_id: &UserID) -> Result<(), StarWorldError>;
+
+  /// Remove the star that a user gave to a world, if there is one
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to unstar
+  /// * `user_id` The ID of the user unstarring the world
+  fn unstar_world(&self, world_id: &WorldID, user_id: &UserID) -> Result<(), UnstarWorldError>;
+
+  /// Retrieve the pending ownership transfer of a world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world
+  ///
+  /// # Returns
+  /// The transfer, or `None` if the world isn't being transferred
+  fn get_world_transfer(&self, world_id: &WorldID) -> Option<WorldTransfer>;
+
+  /// Retrieve all of the pending ownership transfers that have been offered to a user
+  ///
+  /// # Arguments
+  /// * `user_id` The ID of the user that the worlds are being offered to
+  ///
+  /// # Returns
+  /// The transfers
+  fn list_world_transfers_to(&self, user_id: &UserID) -> Vec<WorldTransfer>;
+
+  /// Offer the ownership of a world to another user. This replaces any previous offer for the
+  /// same world.
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world to transfer
+  /// * `to` The ID of the user to offer the world to
+  ///
+  /// # Returns
+  /// The pending transfer
+  fn offer_world_transfer(
+    &self,
+    world_id: &WorldID,
+    to: &UserID,
+  ) -> Result<WorldTransfer, OfferWorldTransferError>;
+
+  /// Accept an offer to transfer the ownership of a world, making the accepting user the new owner
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world being transferred
+  /// * `user_id` The ID of the user accepting the offer
+  ///
+  /// # Returns
+  /// The world, now owned by the accepting user
+  fn accept_world_transfer(
+    &self,
+    world_id: &WorldID,
+    user_id: &UserID,
+  ) -> Result<WorldEntity, AcceptWorldTransferError>;
+
+  /// Withdraw or decline an offer to transfer the ownership of a world
+  ///
+  /// # Arguments
+  /// * `world_id` The ID of the world being transferred
+  fn cancel_world_transfer(&self, world_id: &WorldID) -> Result<(), CancelWorldTransferError>;
+}
+
+/// Enumeration of potential validation errors when saving a world
+#[derive(Debug, PartialEq)]
+pub enum WorldValidationError {
+  DuplicateSlug,
+}
+
+/// Enumeration of reasons why we failed to create a new world
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum CreateWorldError {
+  #[error("World details were invalid: {0:?}")]
+  ValidationError(Vec<WorldValidationError>),
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to fork a world
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum ForkWorldError {
+  #[error("The world was not found")]
+  UnknownWorld,
+  #[error("World details were invalid: {0:?}")]
+  ValidationError(Vec<WorldValidationError>),
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to update an existing world
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateWorldError {
+  #[error("World details were invalid: {0:?}")]
+  ValidationError(Vec<WorldValidationError>),
+  #[error("The world was not found")]
+  UnknownWorld,
+  #[error("The version of the world record did not match")]
+  OptimisticLockFailure,
+  #[error("An error occurred updating the world details: {0}")]
+  UpdateError(Box<dyn std::error::Error>),
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to restore a world to an earlier revision
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum RestoreWorldRevisionError {
+  #[error("World details were invalid: {0:?}")]
+  ValidationError(Vec<WorldValidationError>),
+  #[error("The world was not found")]
+  UnknownWorld,
+  #[error("The revision was not found")]
+  UnknownRevision,
+  #[error("The version of the world record did not match")]
+  OptimisticLockFailure,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to delete a world
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum DeleteWorldError {
+  #[error("The world was not found")]
+  UnknownWorld,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to restore a world from the trash
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum RestoreWorldError {
+  #[error("World details were invalid: {0:?}")]
+  ValidationError(Vec<WorldValidationError>),
+  #[error("The world was not found in the trash")]
+  UnknownWorld,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to add a member to a world
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum AddWorldMemberError {
+  #[error("The world was not found")]
+  UnknownWorld,
+  #[error("The user was not found")]
+  UnknownUser,
+  #[error("The user is already a member of the world")]
+  DuplicateMember,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to change the role of a world member
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum UpdateWorldMemberError {
+  #[error("The user is not a member of the world")]
+  UnknownMember,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to remove a member from a world
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum RemoveWorldMemberError {
+  #[error("The user is not a member of the world")]
+  UnknownMember,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to star a world
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum StarWorldError {
+  #[error("The world was not found")]
+  UnknownWorld,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to unstar a world
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum UnstarWorldError {
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to offer a world to another user
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum OfferWorldTransferError {
+  #[error("The world was not found")]
+  UnknownWorld,
+  #[error("The user was not found")]
+  UnknownUser,
+  #[error("The user already owns the world")]
+  SameOwner,
+  #[error("The user already owns a world with the same slug")]
+  SlugConflict,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to accept the transfer of a world
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum AcceptWorldTransferError {
+  #[error("The world is not being transferred to this user")]
+  UnknownTransfer,
+  #[error("The user already owns a world with the same slug")]
+  SlugConflict,
+  #[error("An unknown error occurred")]
+  UnknownError,
+}
+
+/// Enumeration of reasons why we failed to cancel the transfer of a world
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum CancelWorldTransferError {
+  #[error("The world is not being transferred")]
+  UnknownTransfer,
+  #[error("An unknown error occurred")]
+  UnknownError,
 }
@@ -8,4 +8,5 @@ pub enum WorldSorts {
   Created,
   Relevance,
   Id,
+  Popularity,
 }
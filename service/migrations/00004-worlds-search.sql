@@ -0,0 +1,6 @@
+ALTER TABLE worlds ADD COLUMN search_vector TSVECTOR;
+CREATE FUNCTION worlds_search_vector_update() RETURNS trigger AS $$ BEGIN NEW.search_vector := setweight(to_tsvector('english', COALESCE(NEW.name, '')), 'A') || setweight(to_tsvector('english', COALESCE(NEW.description, '')), 'B'); RETURN NEW; END $$ LANGUAGE plpgsql;
+CREATE TRIGGER worlds_search_vector_update BEFORE INSERT OR UPDATE ON worlds FOR EACH ROW EXECUTE PROCEDURE worlds_search_vector_update();
+UPDATE worlds SET name = name;
+ALTER TABLE worlds ALTER COLUMN search_vector SET NOT NULL;
+CREATE INDEX worlds_search_vector_idx ON worlds USING GIN (search_vector);
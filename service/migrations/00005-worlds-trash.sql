@@ -0,0 +1,4 @@
+ALTER TABLE worlds ADD COLUMN deleted TIMESTAMP WITH TIME ZONE NULL;
+DROP INDEX worlds_owner_slug_key;
+CREATE UNIQUE INDEX worlds_owner_slug_key ON worlds (owner_id, UPPER(slug)) WHERE deleted IS NULL;
+CREATE INDEX worlds_deleted_idx ON worlds (deleted) WHERE deleted IS NOT NULL;
@@ -0,0 +1,8 @@
+CREATE TABLE world_slug_history(
+  world_id UUID NOT NULL REFERENCES worlds (world_id) ON DELETE CASCADE ON UPDATE CASCADE,
+  owner_id UUID NOT NULL REFERENCES users (user_id) ON DELETE CASCADE ON UPDATE CASCADE,
+  slug TEXT NOT NULL,
+  replaced TIMESTAMP WITH TIME ZONE NOT NULL
+);
+CREATE UNIQUE INDEX world_slug_history_owner_slug_key ON world_slug_history (owner_id, UPPER(slug));
+CREATE INDEX world_slug_history_world_idx ON world_slug_history (world_id);
@@ -0,0 +1,2 @@
+ALTER TABLE worlds ADD COLUMN visibility TEXT NOT NULL DEFAULT 'public' CHECK (visibility IN ('public', 'unlisted', 'private'));
+CREATE INDEX worlds_visibility_idx ON worlds (visibility);
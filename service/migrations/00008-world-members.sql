@@ -0,0 +1,9 @@
+CREATE TABLE world_members(
+  world_id UUID NOT NULL REFERENCES worlds (world_id) ON DELETE CASCADE ON UPDATE CASCADE,
+  user_id UUID NOT NULL REFERENCES users (user_id) ON DELETE CASCADE ON UPDATE CASCADE,
+  role TEXT NOT NULL CHECK (role IN ('owner', 'editor', 'viewer')),
+  created TIMESTAMP WITH TIME ZONE NOT NULL,
+  updated TIMESTAMP WITH TIME ZONE NOT NULL,
+  PRIMARY KEY (world_id, user_id)
+);
+CREATE INDEX world_members_user_idx ON world_members (user_id);
@@ -0,0 +1,7 @@
+CREATE TABLE world_transfers(
+  world_id UUID PRIMARY KEY REFERENCES worlds (world_id) ON DELETE CASCADE ON UPDATE CASCADE,
+  from_user_id UUID NOT NULL REFERENCES users (user_id) ON DELETE CASCADE ON UPDATE CASCADE,
+  to_user_id UUID NOT NULL REFERENCES users (user_id) ON DELETE CASCADE ON UPDATE CASCADE,
+  created TIMESTAMP WITH TIME ZONE NOT NULL
+);
+CREATE INDEX world_transfers_to_user_idx ON world_transfers (to_user_id);
@@ -0,0 +1,2 @@
+ALTER TABLE worlds ADD COLUMN forked_from UUID NULL REFERENCES worlds (world_id) ON DELETE SET NULL ON UPDATE CASCADE;
+CREATE INDEX worlds_forked_from_idx ON worlds (forked_from);
@@ -0,0 +1,2 @@
+ALTER TABLE worlds ADD COLUMN tags TEXT[] NOT NULL DEFAULT '{}';
+CREATE INDEX worlds_tags_idx ON worlds USING GIN (tags);
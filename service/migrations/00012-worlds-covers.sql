@@ -0,0 +1 @@
+ALTER TABLE worlds ADD COLUMN cover_id UUID NULL;
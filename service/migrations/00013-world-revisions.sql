@@ -0,0 +1,15 @@
+CREATE TABLE world_revisions(
+  world_id UUID NOT NULL REFERENCES worlds (world_id) ON DELETE CASCADE ON UPDATE CASCADE,
+  revision INTEGER NOT NULL,
+  version UUID NOT NULL,
+  created TIMESTAMP WITH TIME ZONE NOT NULL,
+  owner_id UUID NOT NULL,
+  name TEXT NOT NULL,
+  slug TEXT NOT NULL,
+  description TEXT NOT NULL,
+  visibility TEXT NOT NULL,
+  forked_from UUID NULL,
+  tags TEXT[] NOT NULL,
+  cover_id UUID NULL,
+  PRIMARY KEY (world_id, revision)
+);
@@ -0,0 +1,13 @@
+CREATE TABLE world_stars(
+  world_id UUID NOT NULL REFERENCES worlds (world_id) ON DELETE CASCADE ON UPDATE CASCADE,
+  user_id UUID NOT NULL REFERENCES users (user_id) ON DELETE CASCADE ON UPDATE CASCADE,
+  created TIMESTAMP WITH TIME ZONE NOT NULL,
+  PRIMARY KEY (world_id, user_id)
+);
+CREATE INDEX world_stars_user_idx ON world_stars (user_id, created);
+ALTER TABLE worlds ADD COLUMN stars INTEGER NOT NULL DEFAULT 0;
+CREATE INDEX worlds_stars_idx ON worlds (stars);
+CREATE FUNCTION world_stars_count_update() RETURNS trigger AS $$ BEGIN IF TG_OP = 'INSERT' THEN UPDATE worlds SET stars = stars + 1 WHERE world_id = NEW.world_id; ELSE UPDATE worlds SET stars = stars - 1 WHERE world_id = OLD.world_id; END IF; RETURN NULL; END $$ LANGUAGE plpgsql;
+CREATE TRIGGER world_stars_count_update AFTER INSERT OR DELETE ON world_stars FOR EACH ROW EXECUTE PROCEDURE world_stars_count_update();
+DROP TRIGGER worlds_search_vector_update ON worlds;
+CREATE TRIGGER worlds_search_vector_update BEFORE INSERT OR UPDATE OF name, description ON worlds FOR EACH ROW EXECUTE PROCEDURE worlds_search_vector_update();
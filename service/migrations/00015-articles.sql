@@ -0,0 +1,15 @@
+CREATE TABLE articles(
+  article_id UUID PRIMARY KEY,
+  version UUID NOT NULL,
+  created TIMESTAMP WITH TIME ZONE NOT NULL,
+  updated TIMESTAMP WITH TIME ZONE NOT NULL,
+  world_id UUID NOT NULL REFERENCES worlds (world_id) ON DELETE CASCADE ON UPDATE CASCADE,
+  parent_id UUID NULL,
+  title TEXT NOT NULL,
+  slug TEXT NOT NULL,
+  body TEXT NOT NULL,
+  UNIQUE (world_id, article_id),
+  FOREIGN KEY (world_id, parent_id) REFERENCES articles (world_id, article_id)
+);
+CREATE UNIQUE INDEX articles_world_slug_key ON articles (world_id, UPPER(slug));
+CREATE INDEX articles_parent_idx ON articles (parent_id);
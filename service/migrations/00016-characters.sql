@@ -0,0 +1,13 @@
+CREATE TABLE characters(
+  character_id UUID PRIMARY KEY,
+  version UUID NOT NULL,
+  created TIMESTAMP WITH TIME ZONE NOT NULL,
+  updated TIMESTAMP WITH TIME ZONE NOT NULL,
+  world_id UUID NOT NULL REFERENCES worlds (world_id) ON DELETE CASCADE ON UPDATE CASCADE,
+  name TEXT NOT NULL,
+  aliases TEXT[] NOT NULL,
+  summary TEXT NOT NULL,
+  biography TEXT NOT NULL,
+  attributes JSONB NOT NULL
+);
+CREATE INDEX characters_world_name_idx ON characters (world_id, UPPER(name));
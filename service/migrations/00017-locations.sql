@@ -0,0 +1,17 @@
+CREATE TABLE locations(
+  location_id UUID PRIMARY KEY,
+  version UUID NOT NULL,
+  created TIMESTAMP WITH TIME ZONE NOT NULL,
+  updated TIMESTAMP WITH TIME ZONE NOT NULL,
+  world_id UUID NOT NULL REFERENCES worlds (world_id) ON DELETE CASCADE ON UPDATE CASCADE,
+  parent_id UUID NULL,
+  name TEXT NOT NULL,
+  kind TEXT NOT NULL,
+  description TEXT NOT NULL,
+  x DOUBLE PRECISION NULL,
+  y DOUBLE PRECISION NULL,
+  UNIQUE (world_id, location_id),
+  FOREIGN KEY (world_id, parent_id) REFERENCES locations (world_id, location_id),
+  CHECK ((x IS NULL) = (y IS NULL))
+);
+CREATE INDEX locations_parent_idx ON locations (parent_id);
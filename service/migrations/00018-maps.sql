@@ -0,0 +1,32 @@
+CREATE TABLE maps(
+  map_id UUID PRIMARY KEY,
+  version UUID NOT NULL,
+  created TIMESTAMP WITH TIME ZONE NOT NULL,
+  updated TIMESTAMP WITH TIME ZONE NOT NULL,
+  world_id UUID NOT NULL REFERENCES worlds (world_id) ON DELETE CASCADE ON UPDATE CASCADE,
+  name TEXT NOT NULL,
+  image_id UUID NULL,
+  image_content_type TEXT NULL,
+  image_width INTEGER NULL,
+  image_height INTEGER NULL,
+  UNIQUE (world_id, map_id),
+  CHECK (num_nulls(image_id, image_content_type, image_width, image_height) IN (0, 4))
+);
+CREATE INDEX maps_world_name_idx ON maps (world_id, UPPER(name));
+CREATE TABLE map_features(
+  feature_id UUID PRIMARY KEY,
+  version UUID NOT NULL,
+  created TIMESTAMP WITH TIME ZONE NOT NULL,
+  updated TIMESTAMP WITH TIME ZONE NOT NULL,
+  world_id UUID NOT NULL,
+  map_id UUID NOT NULL,
+  name TEXT NOT NULL,
+  kind TEXT NOT NULL CHECK (kind IN ('pin', 'polyline', 'region')),
+  geometry JSONB NOT NULL,
+  location_id UUID NULL REFERENCES locations (location_id) ON DELETE SET NULL,
+  character_id UUID NULL REFERENCES characters (character_id) ON DELETE SET NULL,
+  article_id UUID NULL REFERENCES articles (article_id) ON DELETE SET NULL,
+  FOREIGN KEY (world_id, map_id) REFERENCES maps (world_id, map_id) ON DELETE CASCADE ON UPDATE CASCADE,
+  CHECK (num_nonnulls(location_id, character_id, article_id) <= 1)
+);
+CREATE INDEX map_features_map_kind_idx ON map_features (map_id, kind);
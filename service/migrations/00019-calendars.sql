@@ -0,0 +1,27 @@
+CREATE TABLE calendars(
+  calendar_id UUID PRIMARY KEY,
+  version UUID NOT NULL,
+  created TIMESTAMP WITH TIME ZONE NOT NULL,
+  updated TIMESTAMP WITH TIME ZONE NOT NULL,
+  world_id UUID NOT NULL REFERENCES worlds (world_id) ON DELETE CASCADE ON UPDATE CASCADE,
+  name TEXT NOT NULL,
+  definition JSONB NOT NULL,
+  UNIQUE (world_id, calendar_id)
+);
+CREATE INDEX calendars_world_name_idx ON calendars (world_id, UPPER(name));
+CREATE TABLE timeline_events(
+  event_id UUID PRIMARY KEY,
+  version UUID NOT NULL,
+  created TIMESTAMP WITH TIME ZONE NOT NULL,
+  updated TIMESTAMP WITH TIME ZONE NOT NULL,
+  world_id UUID NOT NULL,
+  calendar_id UUID NOT NULL,
+  title TEXT NOT NULL,
+  description TEXT NOT NULL,
+  year BIGINT NOT NULL,
+  month INTEGER NOT NULL,
+  day INTEGER NOT NULL,
+  day_number BIGINT NOT NULL,
+  FOREIGN KEY (world_id, calendar_id) REFERENCES calendars (world_id, calendar_id) ON DELETE CASCADE ON UPDATE CASCADE
+);
+CREATE INDEX timeline_events_calendar_day_idx ON timeline_events (calendar_id, day_number);
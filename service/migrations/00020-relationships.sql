@@ -0,0 +1,34 @@
+CREATE TABLE relationships(
+  relationship_id UUID PRIMARY KEY,
+  version UUID NOT NULL,
+  created TIMESTAMP WITH TIME ZONE NOT NULL,
+  updated TIMESTAMP WITH TIME ZONE NOT NULL,
+  world_id UUID NOT NULL REFERENCES worlds (world_id) ON DELETE CASCADE ON UPDATE CASCADE,
+  relation TEXT NOT NULL,
+  is_symmetric BOOLEAN NOT NULL,
+  source_article_id UUID NULL REFERENCES articles (article_id) ON DELETE CASCADE,
+  source_character_id UUID NULL REFERENCES characters (character_id) ON DELETE CASCADE,
+  source_location_id UUID NULL REFERENCES locations (location_id) ON DELETE CASCADE,
+  source_map_id UUID NULL REFERENCES maps (map_id) ON DELETE CASCADE,
+  target_article_id UUID NULL REFERENCES articles (article_id) ON DELETE CASCADE,
+  target_character_id UUID NULL REFERENCES characters (character_id) ON DELETE CASCADE,
+  target_location_id UUID NULL REFERENCES locations (location_id) ON DELETE CASCADE,
+  target_map_id UUID NULL REFERENCES maps (map_id) ON DELETE CASCADE,
+  CHECK (num_nonnulls(source_article_id, source_character_id, source_location_id, source_map_id) = 1),
+  CHECK (num_nonnulls(target_article_id, target_character_id, target_location_id, target_map_id) = 1)
+);
+CREATE INDEX relationships_world_created_idx ON relationships (world_id, created);
+CREATE TABLE relationship_dates(
+  relationship_id UUID PRIMARY KEY REFERENCES relationships (relationship_id) ON DELETE CASCADE ON UPDATE CASCADE,
+  world_id UUID NOT NULL,
+  calendar_id UUID NOT NULL,
+  start_year BIGINT NULL,
+  start_month INTEGER NULL,
+  start_day INTEGER NULL,
+  end_year BIGINT NULL,
+  end_month INTEGER NULL,
+  end_day INTEGER NULL,
+  FOREIGN KEY (world_id, calendar_id) REFERENCES calendars (world_id, calendar_id) ON DELETE CASCADE ON UPDATE CASCADE,
+  CHECK (num_nulls(start_year, start_month, start_day) IN (0, 3)),
+  CHECK (num_nulls(end_year, end_month, end_day) IN (0, 3))
+);
@@ -0,0 +1,53 @@
+CREATE FUNCTION world_slug_from_name(name TEXT) RETURNS TEXT AS $$
+DECLARE
+  slug TEXT;
+BEGIN
+  slug := translate(name,
+    'ÀÁÂÃÄÅàáâãäåÇçÈÉÊËèéêëÌÍÎÏìíîïÑñÒÓÔÕÖØòóôõöøÙÚÛÜùúûüÝýÿ',
+    'AAAAAAaaaaaaCcEEEEeeeeIIIIiiiiNnOOOOOOooooooUUUUuuuuYyy');
+  slug := replace(replace(replace(replace(slug, 'ß', 'ss'), 'Æ', 'AE'), 'æ', 'ae'), 'Œ', 'OE');
+  slug := replace(replace(replace(slug, 'œ', 'oe'), 'Þ', 'Th'), 'þ', 'th');
+  slug := trim(BOTH '-' FROM regexp_replace(lower(slug), '[^a-z0-9_]+', '-', 'g'));
+  slug := rtrim(left(slug, 64), '-');
+  IF slug = '' THEN
+    RETURN 'world';
+  END IF;
+  RETURN slug;
+END
+$$ LANGUAGE plpgsql;
+DO $$
+DECLARE
+  world RECORD;
+  base TEXT;
+  candidate TEXT;
+  suffix INTEGER;
+  renamed TIMESTAMP WITH TIME ZONE := NOW();
+BEGIN
+  FOR world IN
+    SELECT world_id, owner_id, slug FROM worlds
+      WHERE slug !~ '^[A-Za-z0-9_-]{1,64}$'
+      ORDER BY created ASC, world_id ASC
+  LOOP
+    base := world_slug_from_name(world.slug);
+    candidate := base;
+    suffix := 1;
+    WHILE EXISTS (
+      SELECT 1 FROM worlds WHERE owner_id = world.owner_id AND UPPER(slug) = UPPER(candidate)
+    ) LOOP
+      suffix := suffix + 1;
+      candidate := rtrim(left(base, 64 - length('-' || suffix)), '-') || '-' || suffix;
+    END LOOP;
+
+    INSERT INTO world_slug_history(world_id, owner_id, slug, replaced)
+      VALUES (world.world_id, world.owner_id, world.slug, renamed)
+      ON CONFLICT (owner_id, UPPER(slug))
+      DO UPDATE SET world_id = EXCLUDED.world_id, replaced = EXCLUDED.replaced;
+    DELETE FROM world_slug_history WHERE owner_id = world.owner_id AND UPPER(slug) = UPPER(candidate);
+
+    UPDATE worlds
+      SET slug = candidate, version = md5(random()::text || clock_timestamp()::text)::uuid, updated = renamed
+      WHERE world_id = world.world_id;
+  END LOOP;
+END
+$$;
+DROP FUNCTION world_slug_from_name(TEXT);